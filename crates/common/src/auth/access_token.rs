@@ -17,7 +17,8 @@ use ahash::AHasher;
 use registry::{
     schema::{
         enums::Permission,
-        structs::{self, Account, Roles, UserRoles},
+        prelude::{OBJ_FILTER_TENANT, Object},
+        structs::{self, Account, GroupAccount, ResourceAccount, Roles, UserRoles},
     },
     types::EnumImpl,
 };
@@ -29,7 +30,7 @@ use std::{
 use store::{query::acl::AclQuery, rand, write::now};
 use tinyvec::TinyVec;
 use trc::{AddContext, StoreEvent};
-use types::{acl::Acl, collection::Collection};
+use types::{acl::Acl, collection::Collection, id::Id};
 use utils::map::bitmap::{Bitmap, BitmapItem};
 use xxhash_rust::xxh3;
 
@@ -183,6 +184,9 @@ impl Server {
                                 })
                             }
                         }
+                        structs::Credential::Passkey(_) => {
+                            // Passkeys authenticate with the account's full permissions
+                        }
                     }
                 }
 
@@ -217,12 +221,29 @@ impl Server {
                 }
                 .update_size())
             }
-            Account::Group(account) => {
-                let tenant_id = account.member_tenant_id.map(|t| t.id() as u32);
+            Account::Group(GroupAccount {
+                member_tenant_id,
+                roles,
+                permissions,
+                ..
+            })
+            | Account::Resource(ResourceAccount {
+                member_tenant_id,
+                roles,
+                permissions,
+                ..
+            })
+            | Account::Location(ResourceAccount {
+                member_tenant_id,
+                roles,
+                permissions,
+                ..
+            }) => {
+                let tenant_id = member_tenant_id.map(|t| t.id() as u32);
                 let permissions = self
                     .effective_permissions(
-                        &account.permissions,
-                        account.roles.role_ids().unwrap_or(
+                        &permissions,
+                        roles.role_ids().unwrap_or(
                             self.core.network.security.default_role_ids_group.as_slice(),
                         ),
                         tenant_id,
@@ -486,6 +507,14 @@ impl AccessToken {
         self.inner.account_id == account_id
     }
 
+    /// Tenant scoped tokens can only access registry objects of their own tenant.
+    pub fn can_access_object(&self, object: &Object) -> bool {
+        (object.object_type().flags() & OBJ_FILTER_TENANT) == 0
+            || self.tenant_id().is_none_or(|tenant_id| {
+                object.inner.member_tenant_id() == Some(Id::from(tenant_id))
+            })
+    }
+
     #[inline(always)]
     pub fn has_permission(&self, permission: Permission) -> bool {
         self.inner
@@ -877,15 +906,32 @@ fn hash_account(account: &Account) -> u64 {
                 group_id.hash(&mut s);
             }
         }
-        Account::Group(account) => {
-            account.member_tenant_id.hash(&mut s);
-            match &account.roles {
+        Account::Group(GroupAccount {
+            member_tenant_id,
+            roles,
+            permissions,
+            ..
+        })
+        | Account::Resource(ResourceAccount {
+            member_tenant_id,
+            roles,
+            permissions,
+            ..
+        })
+        | Account::Location(ResourceAccount {
+            member_tenant_id,
+            roles,
+            permissions,
+            ..
+        }) => {
+            member_tenant_id.hash(&mut s);
+            match roles {
                 Roles::Default => {}
                 Roles::Custom(custom_roles) => {
                     custom_roles.role_ids.as_slice().hash(&mut s);
                 }
             }
-            hash_permissions(&mut s, &account.permissions);
+            hash_permissions(&mut s, permissions);
         }
     }
 
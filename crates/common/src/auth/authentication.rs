@@ -44,32 +44,40 @@ impl Server {
         {
             Ok(token) => Ok(token),
             Err(err) => {
-                // Random delay to mitigate user enumeration attacks
-                #[cfg(not(feature = "test_mode"))]
-                {
-                    use store::rand::{self, Rng};
+                self.authentication_failed(err, req.remote_ip, req.username())
+                    .await
+            }
+        }
+    }
 
-                    let delay = rand::rng().random_range(50..500);
-                    tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
-                }
+    pub(crate) async fn authentication_failed<T>(
+        &self,
+        err: trc::Error,
+        remote_ip: IpAddr,
+        username: Option<&str>,
+    ) -> trc::Result<T> {
+        // Random delay to mitigate user enumeration attacks
+        #[cfg(not(feature = "test_mode"))]
+        {
+            use store::rand::{self, Rng};
 
-                if matches!(
-                    err.as_ref(),
-                    trc::EventType::Auth(trc::AuthEvent::Failed)
-                        | trc::EventType::Security(trc::SecurityEvent::IpUnauthorized)
-                ) && self.has_auth_fail2ban()
-                    && self
-                        .is_auth_fail2banned(req.remote_ip, req.username())
-                        .await?
-                {
-                    Err(trc::SecurityEvent::AuthenticationBan
-                        .into_err()
-                        .ctx(trc::Key::RemoteIp, req.remote_ip)
-                        .ctx_opt(trc::Key::AccountName, req.username().map(|s| s.to_string())))
-                } else {
-                    Err(err.ctx(trc::Key::RemoteIp, req.remote_ip))
-                }
-            }
+            let delay = rand::rng().random_range(50..500);
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+        }
+
+        if matches!(
+            err.as_ref(),
+            trc::EventType::Auth(trc::AuthEvent::Failed)
+                | trc::EventType::Security(trc::SecurityEvent::IpUnauthorized)
+        ) && self.has_auth_fail2ban()
+            && self.is_auth_fail2banned(remote_ip, username).await?
+        {
+            Err(trc::SecurityEvent::AuthenticationBan
+                .into_err()
+                .ctx(trc::Key::RemoteIp, remote_ip)
+                .ctx_opt(trc::Key::AccountName, username.map(|s| s.to_string())))
+        } else {
+            Err(err.ctx(trc::Key::RemoteIp, remote_ip))
         }
     }
 
@@ -480,7 +488,7 @@ impl Server {
         }
     }
 
-    fn add_missing_domain(&self, address: &mut Username) {
+    pub(crate) fn add_missing_domain(&self, address: &mut Username) {
         if address.domain().is_none() {
             trc::event!(
                 Auth(trc::AuthEvent::Warning),
@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AccessToken, authentication::UsernameParts};
+use crate::{Server, network::ClientCertMapping};
+use aws_lc_rs::digest;
+use registry::schema::{
+    enums::{Permission, TlsClientCertMapping},
+    structs,
+};
+use std::{fmt::Write, net::IpAddr};
+use x509_parser::{
+    parse_x509_certificate,
+    prelude::{GeneralName, ParsedExtension},
+};
+
+impl Server {
+    /// Authenticates a verified TLS client certificate, optionally checking
+    /// that the SASL authorization identity matches the mapped account.
+    pub async fn authenticate_certificate(
+        &self,
+        mapping: &ClientCertMapping,
+        certificate: &[u8],
+        authzid: Option<&str>,
+        session_id: u64,
+        remote_ip: IpAddr,
+    ) -> trc::Result<AccessToken> {
+        let identity = certificate_identity(mapping, certificate);
+
+        match self
+            .certificate_verify(identity.as_deref(), authzid, session_id, remote_ip)
+            .await
+            .and_then(|token| token.assert_has_permission(Permission::Authenticate))
+        {
+            Ok(token) => Ok(token),
+            Err(err) => {
+                self.authentication_failed(err, remote_ip, identity.as_deref())
+                    .await
+            }
+        }
+    }
+
+    async fn certificate_verify(
+        &self,
+        identity: Option<&str>,
+        authzid: Option<&str>,
+        session_id: u64,
+        remote_ip: IpAddr,
+    ) -> trc::Result<AccessToken> {
+        let Some(identity) = identity else {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .ctx(trc::Key::SpanId, session_id)
+                .reason("Client certificate does not map to an account"));
+        };
+
+        // Impersonation is not supported through certificates
+        if let Some(authzid) = authzid.filter(|authzid| !authzid.is_empty())
+            && !authzid.eq_ignore_ascii_case(identity)
+        {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .ctx(trc::Key::AccountName, identity.to_string())
+                .ctx(trc::Key::SpanId, session_id)
+                .details(authzid.to_string())
+                .reason("Authorization identity does not match client certificate"));
+        }
+
+        let mut username = UsernameParts::new(identity);
+        self.add_missing_domain(&mut username.account);
+        let account = username.account();
+        let mut account_id = None;
+        if !username.is_master()
+            && let Some(domain) = self.domain(account.domain().unwrap_or_default()).await?
+            && self.get_directory_for_cached_domain(&domain).is_none()
+        {
+            account_id = self
+                .account_id_from_parts(account.local(), domain.id)
+                .await?;
+        }
+        let Some(account_id) = account_id else {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .ctx(trc::Key::AccountName, identity.to_string())
+                .ctx(trc::Key::SpanId, session_id)
+                .reason("Account not found"));
+        };
+
+        let is_alias_login = self
+            .registry()
+            .object::<structs::Account>(account_id.into())
+            .await?
+            .and_then(|account| account.into_user())
+            .is_some_and(|user| user.name != account.local());
+        let token = self
+            .access_token(account_id)
+            .await
+            .and_then(|token| AccessToken::new(token, remote_ip))?;
+        if is_alias_login && !token.has_permission(Permission::AuthenticateWithAlias) {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .ctx(trc::Key::AccountName, identity.to_string())
+                .ctx(trc::Key::AccountId, account_id)
+                .ctx(trc::Key::SpanId, session_id)
+                .reason("Authenticated using an email alias but account does not have AuthenticateAlias permission"));
+        }
+
+        trc::event!(
+            Auth(trc::AuthEvent::Success),
+            AccountName = identity.to_string(),
+            AccountId = account_id,
+            SpanId = session_id,
+            Details = "EXTERNAL",
+        );
+
+        Ok(token)
+    }
+}
+
+fn certificate_identity(mapping: &ClientCertMapping, certificate: &[u8]) -> Option<String> {
+    match mapping.mapping {
+        TlsClientCertMapping::Fingerprint => {
+            let mut fingerprint = String::with_capacity(64);
+            for byte in digest::digest(&digest::SHA256, certificate).as_ref() {
+                let _ = write!(fingerprint, "{byte:02x}");
+            }
+            mapping.fingerprints.get(&fingerprint).cloned()
+        }
+        TlsClientCertMapping::SubjectCommonName => {
+            let (_, cert) = parse_x509_certificate(certificate).ok()?;
+            cert.subject()
+                .iter_common_name()
+                .find_map(|cn| cn.as_str().ok())
+                .map(|cn| cn.trim().to_lowercase())
+        }
+        TlsClientCertMapping::SanEmail => {
+            let (_, cert) = parse_x509_certificate(certificate).ok()?;
+            cert.extensions()
+                .iter()
+                .find_map(|ext| match ext.parsed_extension() {
+                    ParsedExtension::SubjectAlternativeName(san) => {
+                        san.general_names.iter().find_map(|name| match name {
+                            GeneralName::RFC822Name(email) => Some(email.trim().to_lowercase()),
+                            _ => None,
+                        })
+                    }
+                    _ => None,
+                })
+        }
+    }
+}
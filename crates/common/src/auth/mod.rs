@@ -27,10 +27,13 @@ use utils::{cache::CacheItemWeight, map::bitmap::Bitmap};
 
 pub mod access_token;
 pub mod authentication;
+pub mod certificate;
 pub mod credential;
 pub mod oauth;
 pub mod permissions;
 pub mod rate_limit;
+pub mod scram;
+pub mod webauthn;
 
 pub const RECOVERY_ADMIN_ID: u32 = u32::MAX;
 const PERMISSIONS_BITSET_SIZE: usize = Permission::COUNT.div_ceil(std::mem::size_of::<usize>());
@@ -96,6 +99,8 @@ pub const ACCOUNT_FLAG_ENCRYPT_ALGO_AES128: u64 = 1 << 5;
 pub const ACCOUNT_FLAG_ENCRYPT_APPEND: u64 = 1 << 6;
 pub const ACCOUNT_FLAG_ENCRYPT_ALGO_AES256_GCM: u64 = 1 << 7;
 pub const ACCOUNT_FLAG_ENCRYPT_ALGO_CHACHA20_POLY1305: u64 = 1 << 8;
+pub const ACCOUNT_IS_RESOURCE: u64 = 1 << 9;
+pub const ACCOUNT_IS_LOCATION: u64 = 1 << 10;
 
 #[derive(Debug, Clone)]
 pub struct RoleCache {
@@ -107,6 +112,7 @@ pub struct RoleCache {
 pub struct MailingListCache {
     pub addresses: Box<[EmailAddress]>,
     pub recipients: Arc<[Box<str>]>,
+    pub is_managed: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +41,8 @@ pub enum GrantType {
     LiveMetrics,
     LiveDelivery,
     Rsvp,
+    ListUnsubscribe,
+    QuarantineRelease,
 }
 
 impl GrantType {
@@ -52,6 +54,8 @@ impl GrantType {
             GrantType::LiveMetrics => "live_metrics",
             GrantType::LiveDelivery => "live_delivery",
             GrantType::Rsvp => "rsvp",
+            GrantType::ListUnsubscribe => "list_unsubscribe",
+            GrantType::QuarantineRelease => "quarantine_release",
         }
     }
 
@@ -63,6 +67,8 @@ impl GrantType {
             GrantType::LiveMetrics => 3,
             GrantType::LiveDelivery => 4,
             GrantType::Rsvp => 5,
+            GrantType::ListUnsubscribe => 6,
+            GrantType::QuarantineRelease => 7,
         }
     }
 
@@ -74,6 +80,8 @@ impl GrantType {
             3 => Some(GrantType::LiveMetrics),
             4 => Some(GrantType::LiveDelivery),
             5 => Some(GrantType::Rsvp),
+            6 => Some(GrantType::ListUnsubscribe),
+            7 => Some(GrantType::QuarantineRelease),
             _ => None,
         }
     }
@@ -57,7 +57,12 @@ impl Server {
             issued_at,
             expiry: issued_at + expiry_in,
             credential_version: credential_version
-                .filter(|_| !matches!(grant_type, GrantType::Rsvp))
+                .filter(|_| {
+                    !matches!(
+                        grant_type,
+                        GrantType::Rsvp | GrantType::ListUnsubscribe | GrantType::QuarantineRelease
+                    )
+                })
                 .unwrap_or_default(),
         };
 
@@ -250,6 +255,10 @@ mod tests {
                 b"owner@example.org",
             ),
             (sample(GrantType::AccessToken, None, 0), b""),
+            (
+                sample(GrantType::ListUnsubscribe, Some("member@x.org"), 0),
+                b"",
+            ),
             (
                 RawToken {
                     account_id: u32::MAX,
@@ -12,7 +12,7 @@ use ahash::AHashSet;
 use registry::{
     schema::{
         enums::Permission,
-        structs::{self, Account, PermissionsList, UserRoles},
+        structs::{self, Account, GroupAccount, PermissionsList, ResourceAccount, UserRoles},
     },
     types::EnumImpl,
 };
@@ -129,13 +129,29 @@ impl Server {
                 },
                 account.member_tenant_id.map(|t| t.document_id()),
             ),
-            Account::Group(account) => (
-                &account.permissions,
-                account
-                    .roles
+            Account::Group(GroupAccount {
+                permissions,
+                roles,
+                member_tenant_id,
+                ..
+            })
+            | Account::Resource(ResourceAccount {
+                permissions,
+                roles,
+                member_tenant_id,
+                ..
+            })
+            | Account::Location(ResourceAccount {
+                permissions,
+                roles,
+                member_tenant_id,
+                ..
+            }) => (
+                permissions,
+                roles
                     .role_ids()
                     .unwrap_or(self.core.network.security.default_role_ids_group.as_slice()),
-                account.member_tenant_id.map(|t| t.document_id()),
+                member_tenant_id.map(|t| t.document_id()),
             ),
         };
 
@@ -256,7 +272,9 @@ impl Default for DefaultPermissions {
                 | Permission::LiveTracing => {
                     default.superuser.push(permission);
                 }
-                Permission::FetchAnyBlob | Permission::LiveDeliveryTest => {
+                Permission::FetchAnyBlob
+                | Permission::LiveDeliveryTest
+                | Permission::ImapSetQuota => {
                     default.superuser.push(permission);
                     default.tenant.push(permission);
                 }
@@ -274,6 +292,7 @@ impl Default for DefaultPermissions {
                         default.group.push(permission);
                     } else if name.starts_with("sysMaskedEmail")
                         || name.starts_with("sysArchivedItem")
+                        || name.starts_with("sysQuarantinedMessage")
                         || name.starts_with("sysAccountSettings")
                         || name.starts_with("sysPublicKey")
                         || (name.starts_with("sysSpamTrainingSample") && !name.contains("Create"))
@@ -284,6 +303,7 @@ impl Default for DefaultPermissions {
                     } else if name.starts_with("sysAccountPassword")
                         || name.starts_with("sysApiKey")
                         || name.starts_with("sysAppPassword")
+                        || name.starts_with("sysPasskey")
                     {
                         default.user.push(permission);
                         default.superuser.push(permission);
@@ -0,0 +1,168 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AccessToken, authentication::UsernameParts};
+use crate::Server;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use directory::core::scram::{ScramClientFirst, ScramExchange, ScramSecret};
+use registry::schema::{enums::Permission, structs};
+use std::net::IpAddr;
+
+pub struct ScramAuth {
+    exchange: ScramExchange,
+    account_id: Option<u32>,
+    is_alias_login: bool,
+    has_otp_auth: bool,
+}
+
+pub enum ScramState {
+    ClientFinal(Box<ScramAuth>),
+    Success(AccessToken),
+}
+
+impl ScramAuth {
+    /// Base64 encoded server-first message
+    pub fn challenge(&self) -> String {
+        STANDARD.encode(self.exchange.server_first())
+    }
+
+    pub fn username(&self) -> &str {
+        &self.exchange.username
+    }
+}
+
+impl Server {
+    pub async fn scram_begin(&self, client_first: ScramClientFirst) -> trc::Result<ScramAuth> {
+        let mut username = UsernameParts::new(&client_first.username);
+        self.add_missing_domain(&mut username.account);
+
+        // Unknown users, external directories, master users and authorization
+        // identities all continue with a mock secret so that the exchange
+        // only fails at the proof verification step.
+        let auth_as = username.account();
+        let mut account = None;
+        if !username.is_master()
+            && client_first
+                .authzid
+                .as_ref()
+                .is_none_or(|authzid| authzid == &client_first.username)
+            && let Some(domain) = self.domain(auth_as.domain().unwrap_or_default()).await?
+            && self.get_directory_for_cached_domain(&domain).is_none()
+            && let Some(account_id) = self
+                .account_id_from_parts(auth_as.local(), domain.id)
+                .await?
+            && let Some(user) = self
+                .registry()
+                .object::<structs::Account>(account_id.into())
+                .await?
+                .and_then(|account| account.into_user())
+            && let Some(credential) = user.password_credential()
+            && let Some(secret) = credential
+                .scram_secret
+                .as_deref()
+                .and_then(ScramSecret::parse)
+        {
+            account = Some((
+                account_id,
+                secret,
+                user.name != auth_as.local(),
+                credential.otp_auth.is_some(),
+            ));
+        }
+
+        Ok(match account {
+            Some((account_id, secret, is_alias_login, has_otp_auth)) => ScramAuth {
+                exchange: client_first.into_exchange(secret),
+                account_id: Some(account_id),
+                is_alias_login,
+                has_otp_auth,
+            },
+            None => ScramAuth {
+                exchange: client_first.into_exchange(ScramSecret::mock(auth_as.address())),
+                account_id: None,
+                is_alias_login: false,
+                has_otp_auth: false,
+            },
+        })
+    }
+
+    /// Verifies the client-final message and returns the access token along
+    /// with the base64 encoded server-final message.
+    pub async fn scram_authenticate(
+        &self,
+        auth: &ScramAuth,
+        client_final: &[u8],
+        session_id: u64,
+        remote_ip: IpAddr,
+    ) -> trc::Result<(AccessToken, String)> {
+        match self
+            .scram_verify(auth, client_final, session_id, remote_ip)
+            .await
+            .and_then(|(token, server_final)| {
+                token
+                    .assert_has_permission(Permission::Authenticate)
+                    .map(|token| (token, server_final))
+            }) {
+            Ok(result) => Ok(result),
+            Err(err) => {
+                self.authentication_failed(err, remote_ip, Some(auth.username()))
+                    .await
+            }
+        }
+    }
+
+    async fn scram_verify(
+        &self,
+        auth: &ScramAuth,
+        client_final: &[u8],
+        session_id: u64,
+        remote_ip: IpAddr,
+    ) -> trc::Result<(AccessToken, String)> {
+        let (Some(account_id), Some(server_final)) = (
+            auth.account_id,
+            auth.exchange.verify_client_final(client_final),
+        ) else {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .ctx(trc::Key::AccountName, auth.username().to_string())
+                .ctx(trc::Key::SpanId, session_id)
+                .reason("SCRAM authentication failed"));
+        };
+
+        // SCRAM has no room for a second factor
+        if auth.has_otp_auth {
+            return Err(trc::AuthEvent::MfaRequired
+                .into_err()
+                .ctx(trc::Key::AccountName, auth.username().to_string())
+                .ctx(trc::Key::AccountId, account_id)
+                .ctx(trc::Key::SpanId, session_id)
+                .reason("MFA token required"));
+        }
+
+        let token = self
+            .access_token(account_id)
+            .await
+            .and_then(|token| AccessToken::new(token, remote_ip))?;
+        if auth.is_alias_login && !token.has_permission(Permission::AuthenticateWithAlias) {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .ctx(trc::Key::AccountName, auth.username().to_string())
+                .ctx(trc::Key::AccountId, account_id)
+                .ctx(trc::Key::SpanId, session_id)
+                .reason("Authenticated using an email alias but account does not have AuthenticateAlias permission"));
+        }
+
+        trc::event!(
+            Auth(trc::AuthEvent::Success),
+            AccountName = auth.username().to_string(),
+            AccountId = account_id,
+            SpanId = session_id,
+            Details = "SCRAM-SHA-256",
+        );
+
+        Ok((token, STANDARD.encode(server_final)))
+    }
+}
@@ -0,0 +1,722 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AccessToken, AuthRequest, authentication::UsernameParts};
+use crate::{KV_WEBAUTHN, Server, cache::invalidate::CacheInvalidationBuilder};
+use aws_lc_rs::{
+    digest,
+    signature::{
+        ECDSA_P256_SHA256_ASN1, ED25519, RSA_PKCS1_2048_8192_SHA256, UnparsedPublicKey,
+        VerificationAlgorithm,
+    },
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use directory::Credentials;
+use registry::{
+    schema::{
+        enums::{PasskeyAlgorithm, Permission},
+        prelude::{Object, ObjectType},
+        structs::{self, Credential, Passkey},
+    },
+    types::{datetime::UTCDateTime, id::ObjectId},
+};
+use std::net::IpAddr;
+use store::{
+    Serialize,
+    dispatch::lookup::KeyValue,
+    rand,
+    registry::write::{RegistryWrite, RegistryWriteResult},
+    write::{AlignedBytes, Archive, Archiver},
+};
+use trc::AddContext;
+use types::id::Id;
+use x509_parser::{
+    oid_registry::{OID_KEY_TYPE_EC_PUBLIC_KEY, OID_PKCS1_RSAENCRYPTION, OID_SIG_ED25519, Oid},
+    prelude::FromDer,
+    x509::SubjectPublicKeyInfo,
+};
+
+pub const PASSKEY_CHALLENGE_EXPIRY: u64 = 300;
+pub const MAX_PASSKEYS: usize = 32;
+
+const COSE_ES256: i64 = -7;
+const COSE_EDDSA: i64 = -8;
+const COSE_RS256: i64 = -257;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_DATA: u8 = 0x40;
+
+/// Attestation response as produced by the browser, with all binary
+/// fields encoded as base64url.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyRegistration {
+    pub id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub public_key: String,
+    pub public_key_algorithm: i64,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Assertion response as produced by the browser, with all binary
+/// fields encoded as base64url.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyAssertion {
+    pub id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_handle: Option<String>,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug)]
+pub struct PasskeyChallenge {
+    pub account_id: u32,
+    pub registration: bool,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    typ: String,
+    challenge: String,
+    origin: String,
+}
+
+struct AuthenticatorData<'x> {
+    rp_id_hash: &'x [u8],
+    flags: u8,
+    sign_count: u32,
+    credential_id: Option<&'x [u8]>,
+}
+
+impl Server {
+    /// Builds the `PublicKeyCredentialCreationOptions` for registering a new
+    /// passkey on the given account.
+    pub async fn passkey_creation_options(
+        &self,
+        account_id: u32,
+    ) -> trc::Result<serde_json::Value> {
+        let (_, account) = self.passkey_account(account_id).await?;
+        let (_, rp_id) = self.passkey_relying_party();
+        let challenge = self
+            .passkey_challenge(PasskeyChallenge {
+                account_id,
+                registration: true,
+            })
+            .await?;
+        let address = self
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .name()
+            .to_string();
+
+        Ok(serde_json::json!({
+            "challenge": challenge,
+            "rp": {
+                "id": rp_id,
+                "name": self.core.network.server_name,
+            },
+            "user": {
+                "id": URL_SAFE_NO_PAD.encode(account_id.to_be_bytes()),
+                "name": address,
+                "displayName": account.description.as_deref().unwrap_or(address.as_str()),
+            },
+            "pubKeyCredParams": [
+                { "type": "public-key", "alg": COSE_ES256 },
+                { "type": "public-key", "alg": COSE_EDDSA },
+                { "type": "public-key", "alg": COSE_RS256 },
+            ],
+            "excludeCredentials": account
+                .passkeys()
+                .map(|passkey| serde_json::json!({ "type": "public-key", "id": passkey.key_id }))
+                .collect::<Vec<_>>(),
+            "authenticatorSelection": {
+                "residentKey": "required",
+                "requireResidentKey": true,
+                "userVerification": "preferred",
+            },
+            "attestation": "none",
+            "timeout": PASSKEY_CHALLENGE_EXPIRY * 1000,
+        }))
+    }
+
+    /// Verifies an attestation response and stores the new passkey as a
+    /// secondary credential of the account, returning its credential id.
+    pub async fn register_passkey(
+        &self,
+        account_id: u32,
+        registration: PasskeyRegistration,
+    ) -> trc::Result<u64> {
+        // Validate client data
+        let (client_data, _) =
+            self.passkey_client_data(&registration.client_data_json, "webauthn.create")?;
+        match self.take_passkey_challenge(&client_data.challenge).await? {
+            Some(challenge) if challenge.registration && challenge.account_id == account_id => {}
+            _ => {
+                return Err(trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .ctx(trc::Key::AccountId, account_id)
+                    .details("Invalid or expired passkey challenge."));
+            }
+        }
+
+        // Validate authenticator data
+        let auth_data = decode_base64(&registration.authenticator_data)?;
+        let auth_data = AuthenticatorData::parse(&auth_data)?;
+        let key_id = decode_base64(&registration.id)?;
+        self.passkey_verify_rp(&auth_data)?;
+        if auth_data.flags & FLAG_USER_PRESENT == 0 {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("User presence was not confirmed by the authenticator."));
+        } else if auth_data.credential_id != Some(key_id.as_slice()) {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Credential id does not match the attested credential data."));
+        }
+
+        // Validate public key
+        let algorithm = match registration.public_key_algorithm {
+            COSE_ES256 => PasskeyAlgorithm::Es256,
+            COSE_EDDSA => PasskeyAlgorithm::Ed25519,
+            COSE_RS256 => PasskeyAlgorithm::Rs256,
+            _ => {
+                return Err(trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("Unsupported passkey algorithm."));
+            }
+        };
+        let public_key = decode_base64(&registration.public_key)?;
+        if !is_valid_public_key(algorithm, &public_key) {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid passkey public key."));
+        }
+
+        // Add credential
+        let key_id = URL_SAFE_NO_PAD.encode(&key_id);
+        let (current, mut account) = self.passkey_account(account_id).await?;
+        if account.passkeys().count() >= MAX_PASSKEYS {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details(format!(
+                    "You have exceeded your quota of {MAX_PASSKEYS} passkeys."
+                )));
+        } else if account.passkeys().any(|passkey| passkey.key_id == key_id) {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("This passkey is already registered."));
+        }
+        let credential_id = account.next_credential_id();
+        account.credentials.push(Credential::Passkey(Passkey {
+            credential_id: credential_id.into(),
+            description: registration
+                .description
+                .map(|description| description.trim().to_string())
+                .filter(|description| !description.is_empty())
+                .unwrap_or_else(|| "Passkey".to_string()),
+            key_id,
+            public_key: URL_SAFE_NO_PAD.encode(&public_key),
+            algorithm,
+            sign_count: auth_data.sign_count as u64,
+            created_at: UTCDateTime::now(),
+            last_used_at: None,
+        }));
+        self.passkey_update_account(account_id, current, account)
+            .await?;
+
+        trc::event!(
+            Auth(trc::AuthEvent::PasskeyRegistered),
+            AccountId = account_id,
+            Id = credential_id,
+        );
+
+        Ok(credential_id)
+    }
+
+    /// Builds the `PublicKeyCredentialRequestOptions` for a login. Only
+    /// discoverable credentials are registered, so no credentials are listed
+    /// and account names are never disclosed.
+    pub async fn passkey_request_options(
+        &self,
+        user_verification: bool,
+    ) -> trc::Result<serde_json::Value> {
+        let (_, rp_id) = self.passkey_relying_party();
+        let challenge = self
+            .passkey_challenge(PasskeyChallenge {
+                account_id: u32::MAX,
+                registration: false,
+            })
+            .await?;
+
+        Ok(serde_json::json!({
+            "challenge": challenge,
+            "rpId": rp_id,
+            "allowCredentials": [],
+            "userVerification": if user_verification { "required" } else { "preferred" },
+            "timeout": PASSKEY_CHALLENGE_EXPIRY * 1000,
+        }))
+    }
+
+    /// Authenticates an interactive login, accepting a passkey either as a
+    /// passwordless primary factor or as the second factor of a password
+    /// login. Accounts with registered passkeys must present one.
+    pub async fn authenticate_login(
+        &self,
+        req: &AuthRequest,
+        passkey: Option<&PasskeyAssertion>,
+    ) -> trc::Result<AccessToken> {
+        let Credentials::Basic {
+            username, secret, ..
+        } = &req.credentials
+        else {
+            return self.authenticate(req).await;
+        };
+
+        // Passwordless login
+        if let Some(passkey) = passkey
+            && secret.is_empty()
+        {
+            return self
+                .authenticate_passkey(passkey, None, req.session_id, req.remote_ip)
+                .await;
+        }
+
+        if let Some(passkey) = passkey {
+            // Passkeys replace any configured TOTP as the second factor
+            let is_master = UsernameParts::new(username).is_master();
+            let token = match self
+                .authenticate(&AuthRequest {
+                    credentials: Credentials::Basic {
+                        username: username.clone(),
+                        secret: secret.clone(),
+                        mfa_token: None,
+                    },
+                    session_id: req.session_id,
+                    remote_ip: req.remote_ip,
+                })
+                .await
+            {
+                Ok(token) => Some(token),
+                Err(err)
+                    if !is_master
+                        && err.matches(trc::EventType::Auth(trc::AuthEvent::MfaRequired)) =>
+                {
+                    None
+                }
+                Err(err) => return Err(err),
+            };
+
+            let passkey_token = match self.passkey_login_account_id(username).await? {
+                Some(account_id) => {
+                    self.authenticate_passkey(
+                        passkey,
+                        Some(account_id),
+                        req.session_id,
+                        req.remote_ip,
+                    )
+                    .await?
+                }
+                None => {
+                    return self
+                        .authentication_failed(
+                            trc::AuthEvent::Failed
+                                .into_err()
+                                .ctx(trc::Key::AccountName, username.to_string())
+                                .ctx(trc::Key::SpanId, req.session_id)
+                                .reason("Account not found for passkey authentication"),
+                            req.remote_ip,
+                            Some(username),
+                        )
+                        .await;
+                }
+            };
+
+            Ok(token.unwrap_or(passkey_token))
+        } else {
+            let token = self.authenticate(req).await?;
+
+            if let Some(account_id) = self.passkey_login_account_id(username).await?
+                && self.has_passkeys(account_id).await?
+            {
+                Err(trc::AuthEvent::MfaRequired
+                    .into_err()
+                    .ctx(trc::Key::AccountName, username.to_string())
+                    .ctx(trc::Key::AccountId, account_id)
+                    .ctx(trc::Key::SpanId, req.session_id)
+                    .reason("Passkey required"))
+            } else {
+                Ok(token)
+            }
+        }
+    }
+
+    /// Authenticates a passkey assertion. When `account_id` is provided the
+    /// passkey must belong to that account, otherwise the account is obtained
+    /// from the user handle and user verification is required.
+    pub async fn authenticate_passkey(
+        &self,
+        assertion: &PasskeyAssertion,
+        account_id: Option<u32>,
+        session_id: u64,
+        remote_ip: IpAddr,
+    ) -> trc::Result<AccessToken> {
+        match self
+            .passkey_verify(assertion, account_id, session_id, remote_ip)
+            .await
+            .and_then(|token| token.assert_has_permission(Permission::Authenticate))
+        {
+            Ok(token) => Ok(token),
+            Err(err) => self.authentication_failed(err, remote_ip, None).await,
+        }
+    }
+
+    pub async fn has_passkeys(&self, account_id: u32) -> trc::Result<bool> {
+        Ok(self
+            .registry()
+            .object::<structs::Account>(account_id.into())
+            .await?
+            .and_then(|account| account.into_user())
+            .is_some_and(|account| account.passkeys().next().is_some()))
+    }
+
+    async fn passkey_verify(
+        &self,
+        assertion: &PasskeyAssertion,
+        account_id: Option<u32>,
+        session_id: u64,
+        remote_ip: IpAddr,
+    ) -> trc::Result<AccessToken> {
+        let failed = |reason: &'static str| {
+            trc::AuthEvent::Failed
+                .into_err()
+                .ctx(trc::Key::SpanId, session_id)
+                .reason(reason)
+        };
+
+        // Validate client data and challenge
+        let (client_data, client_data_json) = self
+            .passkey_client_data(&assertion.client_data_json, "webauthn.get")
+            .map_err(|_| failed("Invalid passkey client data"))?;
+        if !self
+            .take_passkey_challenge(&client_data.challenge)
+            .await?
+            .is_some_and(|challenge| !challenge.registration)
+        {
+            return Err(failed("Invalid or expired passkey challenge"));
+        }
+
+        // Obtain account
+        let is_passwordless = account_id.is_none();
+        let user_handle = assertion
+            .user_handle
+            .as_deref()
+            .filter(|handle| !handle.is_empty())
+            .and_then(|handle| URL_SAFE_NO_PAD.decode(handle).ok())
+            .and_then(|handle| handle.try_into().ok())
+            .map(u32::from_be_bytes);
+        let account_id = match (account_id, user_handle) {
+            (Some(account_id), Some(user_handle)) if account_id != user_handle => {
+                return Err(failed("Passkey user handle does not match account"));
+            }
+            (Some(account_id), _) | (None, Some(account_id)) => account_id,
+            (None, None) => return Err(failed("Passkey user handle missing")),
+        };
+        let (current, mut account) = self
+            .passkey_account(account_id)
+            .await
+            .map_err(|_| failed("Account not found for passkey"))?;
+        let Some(passkey) = account.passkey_mut(assertion.id.trim_end_matches('=')) else {
+            return Err(
+                failed("Passkey not found for account").ctx(trc::Key::AccountId, account_id)
+            );
+        };
+
+        // Validate authenticator data
+        let auth_data_bytes = decode_base64(&assertion.authenticator_data)
+            .map_err(|_| failed("Invalid passkey authenticator data"))?;
+        let auth_data = AuthenticatorData::parse(&auth_data_bytes)
+            .map_err(|_| failed("Invalid passkey authenticator data"))?;
+        self.passkey_verify_rp(&auth_data)
+            .map_err(|_| failed("Passkey relying party mismatch"))?;
+        if auth_data.flags & FLAG_USER_PRESENT == 0 {
+            return Err(failed("Passkey user presence not confirmed"));
+        } else if is_passwordless && auth_data.flags & FLAG_USER_VERIFIED == 0 {
+            return Err(failed("Passkey user verification required"));
+        }
+
+        // Verify signature over the authenticator data and client data hash
+        let public_key = URL_SAFE_NO_PAD
+            .decode(&passkey.public_key)
+            .map_err(|_| failed("Invalid stored passkey public key"))?;
+        let signature =
+            decode_base64(&assertion.signature).map_err(|_| failed("Invalid passkey signature"))?;
+        let mut message = auth_data_bytes.clone();
+        message.extend_from_slice(digest::digest(&digest::SHA256, &client_data_json).as_ref());
+        if !verify_signature(passkey.algorithm, &public_key, &message, &signature) {
+            return Err(failed("Invalid passkey signature").ctx(trc::Key::AccountId, account_id));
+        }
+
+        // Detect cloned authenticators
+        let sign_count = auth_data.sign_count as u64;
+        if (sign_count != 0 || passkey.sign_count != 0) && sign_count <= passkey.sign_count {
+            return Err(failed("Passkey signature counter did not increase")
+                .ctx(trc::Key::AccountId, account_id));
+        }
+        let credential_id = passkey.credential_id.id();
+        passkey.sign_count = sign_count;
+        passkey.last_used_at = Some(UTCDateTime::now());
+        let account_name = account.name.clone();
+        self.passkey_update_account(account_id, current, account)
+            .await?;
+
+        trc::event!(
+            Auth(trc::AuthEvent::Success),
+            AccountName = account_name,
+            AccountId = account_id,
+            Id = credential_id,
+            SpanId = session_id,
+            Details = "Authenticated with passkey",
+        );
+
+        self.access_token(account_id)
+            .await
+            .and_then(|token| AccessToken::new(token, remote_ip))
+    }
+
+    async fn passkey_challenge(&self, challenge: PasskeyChallenge) -> trc::Result<String> {
+        let key = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>());
+        self.in_memory_store()
+            .key_set(
+                KeyValue::with_prefix(
+                    KV_WEBAUTHN,
+                    key.as_bytes(),
+                    Archiver::new(challenge)
+                        .untrusted()
+                        .serialize()
+                        .caused_by(trc::location!())?,
+                )
+                .expires(PASSKEY_CHALLENGE_EXPIRY),
+            )
+            .await?;
+
+        Ok(key)
+    }
+
+    async fn take_passkey_challenge(&self, key: &str) -> trc::Result<Option<PasskeyChallenge>> {
+        let key = KeyValue::<()>::build_key(KV_WEBAUTHN, key.as_bytes());
+        if let Some(challenge) = self
+            .in_memory_store()
+            .key_get::<Archive<AlignedBytes>>(key.clone())
+            .await?
+        {
+            // Challenges are single use
+            self.in_memory_store().key_delete(key).await?;
+            challenge
+                .deserialize::<PasskeyChallenge>()
+                .caused_by(trc::location!())
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn passkey_client_data(
+        &self,
+        client_data_json: &str,
+        typ: &str,
+    ) -> trc::Result<(ClientData, Vec<u8>)> {
+        let bytes = decode_base64(client_data_json)?;
+        let client_data = serde_json::from_slice::<ClientData>(&bytes).map_err(|_| {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid passkey client data.")
+        })?;
+        let (origin, _) = self.passkey_relying_party();
+
+        if client_data.typ != typ {
+            Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid passkey client data type."))
+        } else if client_data.origin != origin {
+            Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details(format!("Passkey origin does not match {origin}.")))
+        } else {
+            Ok((client_data, bytes))
+        }
+    }
+
+    fn passkey_verify_rp(&self, auth_data: &AuthenticatorData<'_>) -> trc::Result<()> {
+        let (_, rp_id) = self.passkey_relying_party();
+        if digest::digest(&digest::SHA256, rp_id.as_bytes()).as_ref() == auth_data.rp_id_hash {
+            Ok(())
+        } else {
+            Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Passkey relying party does not match."))
+        }
+    }
+
+    fn passkey_relying_party(&self) -> (&str, &str) {
+        let origin = self.core.network.http.url_https.trim_end_matches('/');
+        let host = origin.split_once("://").map_or(origin, |(_, host)| host);
+        let rp_id = host
+            .rsplit_once(':')
+            .filter(|(_, port)| port.bytes().all(|ch| ch.is_ascii_digit()))
+            .map_or(host, |(host, _)| host);
+
+        (origin, rp_id)
+    }
+
+    async fn passkey_login_account_id(&self, username: &str) -> trc::Result<Option<u32>> {
+        let mut username = UsernameParts::new(username);
+        if let Some(master_user) = &mut username.master_user {
+            self.add_missing_domain(master_user);
+        } else {
+            self.add_missing_domain(&mut username.account);
+        }
+        self.account_id_from_email(username.auth_as().address(), false)
+            .await
+    }
+
+    async fn passkey_account(
+        &self,
+        account_id: u32,
+    ) -> trc::Result<(Object, structs::UserAccount)> {
+        let current = self
+            .registry()
+            .get(ObjectId::new(ObjectType::Account, account_id.into()))
+            .await
+            .caused_by(trc::location!())?;
+
+        if let Some(account) = current
+            .as_ref()
+            .and_then(|current| structs::Account::from(current.clone()).into_user())
+        {
+            Ok((current.unwrap(), account))
+        } else {
+            Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .ctx(trc::Key::AccountId, account_id)
+                .details("Passkeys are only supported for user accounts."))
+        }
+    }
+
+    async fn passkey_update_account(
+        &self,
+        account_id: u32,
+        current: Object,
+        account: structs::UserAccount,
+    ) -> trc::Result<()> {
+        let updated = Object::from(structs::Account::User(account));
+        match self
+            .registry()
+            .write(RegistryWrite::update(
+                Id::from(account_id),
+                &updated,
+                &current,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            RegistryWriteResult::Success(id) => {
+                let mut invalidator = CacheInvalidationBuilder::default();
+                invalidator.process_update(id, &current, &updated);
+                self.invalidate_caches(invalidator)
+                    .await
+                    .caused_by(trc::location!())
+            }
+            failure => Err(trc::StoreEvent::UnexpectedError
+                .into_err()
+                .caused_by(trc::location!())
+                .details("Failed to update passkey")
+                .id(account_id)
+                .reason(failure)),
+        }
+    }
+}
+
+impl<'x> AuthenticatorData<'x> {
+    fn parse(data: &'x [u8]) -> trc::Result<Self> {
+        let invalid = || {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid passkey authenticator data.")
+        };
+
+        let flags = *data.get(32).ok_or_else(invalid)?;
+        let sign_count =
+            u32::from_be_bytes(data.get(33..37).ok_or_else(invalid)?.try_into().unwrap());
+        let credential_id = if flags & FLAG_ATTESTED_DATA != 0 {
+            let len = u16::from_be_bytes(data.get(53..55).ok_or_else(invalid)?.try_into().unwrap());
+            Some(data.get(55..55 + len as usize).ok_or_else(invalid)?)
+        } else {
+            None
+        };
+
+        Ok(AuthenticatorData {
+            rp_id_hash: &data[..32],
+            flags,
+            sign_count,
+            credential_id,
+        })
+    }
+}
+
+fn is_valid_public_key(algorithm: PasskeyAlgorithm, spki: &[u8]) -> bool {
+    SubjectPublicKeyInfo::from_der(spki)
+        .is_ok_and(|(_, spki)| spki.algorithm.algorithm == algorithm_oid(algorithm))
+}
+
+fn verify_signature(
+    algorithm: PasskeyAlgorithm,
+    spki: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let Ok((_, spki)) = SubjectPublicKeyInfo::from_der(spki) else {
+        return false;
+    };
+    if spki.algorithm.algorithm != algorithm_oid(algorithm) {
+        return false;
+    }
+
+    let verify_algorithm: &'static dyn VerificationAlgorithm = match algorithm {
+        PasskeyAlgorithm::Es256 => &ECDSA_P256_SHA256_ASN1,
+        PasskeyAlgorithm::Ed25519 => &ED25519,
+        PasskeyAlgorithm::Rs256 => &RSA_PKCS1_2048_8192_SHA256,
+    };
+    UnparsedPublicKey::new(verify_algorithm, &spki.subject_public_key.data)
+        .verify(message, signature)
+        .is_ok()
+}
+
+fn algorithm_oid(algorithm: PasskeyAlgorithm) -> Oid<'static> {
+    match algorithm {
+        PasskeyAlgorithm::Es256 => OID_KEY_TYPE_EC_PUBLIC_KEY,
+        PasskeyAlgorithm::Ed25519 => OID_SIG_ED25519,
+        PasskeyAlgorithm::Rs256 => OID_PKCS1_RSAENCRYPTION,
+    }
+}
+
+fn decode_base64(value: &str) -> trc::Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid base64url encoding.")
+        })
+}
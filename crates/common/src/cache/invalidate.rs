@@ -111,6 +111,46 @@ impl CacheInvalidationBuilder {
                 }
             }
 
+            (
+                ObjectInner::Account(Account::Resource(current)),
+                ObjectInner::Account(Account::Resource(new)),
+            )
+            | (
+                ObjectInner::Account(Account::Location(current)),
+                ObjectInner::Account(Account::Location(new)),
+            ) => {
+                let was_renamed =
+                    (current.name != new.name) || (current.domain_id != new.domain_id);
+                let quota_changed = current.quotas != new.quotas;
+                let permissions_changed = current.permissions != new.permissions;
+                let roles_changed = current.roles != new.roles;
+                let tenant_changed = current.member_tenant_id != new.member_tenant_id;
+                let details_changed =
+                    current.locale != new.locale || current.description != new.description;
+                let aliases_changed = current.aliases != new.aliases;
+
+                if was_renamed
+                    || aliases_changed
+                    || tenant_changed
+                    || quota_changed
+                    || details_changed
+                {
+                    self.invalidate(CacheInvalidation::Account(id));
+                }
+
+                if was_renamed || aliases_changed {
+                    self.invalidate_negative_email(&new_object.inner);
+                }
+
+                if tenant_changed || roles_changed || permissions_changed {
+                    self.invalidate(CacheInvalidation::AccessToken(id));
+                }
+
+                if was_renamed {
+                    self.invalidate(CacheInvalidation::DavResources(id));
+                }
+            }
+
             (ObjectInner::Domain(current), ObjectInner::Domain(new)) => {
                 if (current.name != new.name)
                     || (current.directory_id != new.directory_id)
@@ -163,7 +203,8 @@ impl CacheInvalidationBuilder {
                 if (current.aliases != new.aliases)
                     || (current.name != new.name)
                     || (current.recipients != new.recipients)
-                    || (current.domain_id != new.domain_id) =>
+                    || (current.domain_id != new.domain_id)
+                    || (current.managed != new.managed) =>
             {
                 self.invalidate(CacheInvalidation::List(id));
                 if (current.aliases != new.aliases)
@@ -220,6 +261,9 @@ impl CacheInvalidationBuilder {
             ObjectInner::Account(Account::Group(account)) => {
                 (&account.name, account.domain_id, &account.aliases)
             }
+            ObjectInner::Account(Account::Resource(account) | Account::Location(account)) => {
+                (&account.name, account.domain_id, &account.aliases)
+            }
             ObjectInner::MailingList(list) => (&list.name, list.domain_id, &list.aliases),
             _ => return,
         };
@@ -10,10 +10,11 @@ use crate::{
         ACCOUNT_FLAG_ENCRYPT_ALGO_AES128, ACCOUNT_FLAG_ENCRYPT_ALGO_AES256,
         ACCOUNT_FLAG_ENCRYPT_ALGO_AES256_GCM, ACCOUNT_FLAG_ENCRYPT_ALGO_CHACHA20_POLY1305,
         ACCOUNT_FLAG_ENCRYPT_APPEND, ACCOUNT_FLAG_ENCRYPT_METHOD_PGP,
-        ACCOUNT_FLAG_ENCRYPT_METHOD_SMIME, ACCOUNT_FLAG_ENCRYPT_TRAIN_SPAM_FILTER, ACCOUNT_IS_USER,
-        AccountCache, AccountInfo, AccountTenantIds, DOMAIN_FLAG_RELAY, DOMAIN_FLAG_SUB_ADDRESSING,
-        DomainCache, EmailAddress, EmailAddressRef, EmailCache, MailingListCache, PermissionsGroup,
-        RECOVERY_ADMIN_ID, RoleCache, TenantCache, permissions::BuildPermissions,
+        ACCOUNT_FLAG_ENCRYPT_METHOD_SMIME, ACCOUNT_FLAG_ENCRYPT_TRAIN_SPAM_FILTER,
+        ACCOUNT_IS_LOCATION, ACCOUNT_IS_RESOURCE, ACCOUNT_IS_USER, AccountCache, AccountInfo,
+        AccountTenantIds, DOMAIN_FLAG_RELAY, DOMAIN_FLAG_SUB_ADDRESSING, DomainCache, EmailAddress,
+        EmailAddressRef, EmailCache, MailingListCache, PermissionsGroup, RECOVERY_ADMIN_ID,
+        RoleCache, TenantCache, permissions::BuildPermissions,
     },
     config::smtp::auth::DkimSigners,
     expr::if_block::BootstrapExprExt,
@@ -371,6 +372,7 @@ impl Server {
                     return Ok(None);
                 };
 
+                let is_location = matches!(account, Account::Location(_));
                 let cache = Arc::new(match account {
                     Account::User(account) => {
                         let domain = self
@@ -547,6 +549,70 @@ impl Server {
                             flags: 0,
                         }
                     }
+                    Account::Resource(account) | Account::Location(account) => {
+                        let flags = if is_location {
+                            ACCOUNT_IS_RESOURCE | ACCOUNT_IS_LOCATION
+                        } else {
+                            ACCOUNT_IS_RESOURCE
+                        };
+                        let domain = self
+                            .domain_by_id(account.domain_id.document_id())
+                            .await?
+                            .ok_or_else(|| {
+                                trc::AuthEvent::Error
+                                    .into_err()
+                                    .details("Domain not found for resource account.")
+                                    .ctx(trc::Key::AccountId, account_id)
+                                    .ctx(trc::Key::Id, account.domain_id.document_id())
+                                    .caused_by(trc::location!())
+                            })?;
+                        let mut name =
+                            String::with_capacity(domain.names[0].len() + account.name.len() + 1);
+                        name.push_str(account.name.as_ref());
+                        name.push('@');
+                        name.push_str(domain.names[0].as_ref());
+
+                        let mut quota_objects: Option<ObjectQuota> = None;
+                        let mut quota_disk = 0;
+                        for (resource, limit) in account.quotas {
+                            if resource == StorageQuota::MaxDiskQuota {
+                                quota_disk = limit;
+                            } else {
+                                quota_objects
+                                    .get_or_insert_with(|| self.core.email.max_objects.clone())
+                                    .set(resource, limit as u32);
+                            }
+                        }
+
+                        AccountCache {
+                            id: account_id,
+                            name: name.into_boxed_str(),
+                            addresses: [EmailAddress {
+                                local_part: account.name.into(),
+                                domain_id: account.domain_id.document_id(),
+                            }]
+                            .into_iter()
+                            .chain(
+                                account
+                                    .aliases
+                                    .into_iter()
+                                    .filter(|alias| alias.enabled)
+                                    .map(|alias| EmailAddress {
+                                        local_part: alias.name.into(),
+                                        domain_id: alias.domain_id.document_id(),
+                                    }),
+                            )
+                            .collect(),
+                            id_tenant: account.member_tenant_id.map(|id| id.document_id()),
+                            id_member_of: Default::default(),
+                            quota_disk,
+                            quota_objects: quota_objects.map(Box::new),
+                            description: account.description.map(Into::into),
+                            encryption_key: None,
+                            locale: account.locale,
+                            flags,
+                        }
+                    }
                 });
 
                 let _ = guard.insert(cache.clone());
@@ -854,6 +920,7 @@ impl Server {
                         ))
                         .collect(),
                         recipients: list.recipients.into_iter().map(Into::into).collect(),
+                        is_managed: list.managed,
                     });
                 let _ = guard.insert(cache.clone());
                 Ok(Some(cache))
@@ -894,6 +961,7 @@ impl Server {
                 let mut signers = DkimSigners {
                     dkim1: Vec::with_capacity(ids.len()),
                     dkim2: None,
+                    arc: None,
                 };
                 for id in ids {
                     if let Some(signature) = self.registry().object::<DkimSignature>(id).await?
@@ -953,6 +1021,11 @@ impl AccountInfo {
         self.account.flags & ACCOUNT_IS_USER != 0
     }
 
+    #[inline(always)]
+    pub fn is_resource_account(&self) -> bool {
+        self.account.flags & ACCOUNT_IS_RESOURCE != 0
+    }
+
     #[inline(always)]
     pub fn locale(&self) -> Locale {
         self.account.locale
@@ -995,6 +1068,16 @@ impl AccountCache {
         self.flags & ACCOUNT_IS_USER != 0
     }
 
+    #[inline(always)]
+    pub fn is_resource_account(&self) -> bool {
+        self.flags & ACCOUNT_IS_RESOURCE != 0
+    }
+
+    #[inline(always)]
+    pub fn is_location_account(&self) -> bool {
+        self.flags & ACCOUNT_IS_LOCATION != 0
+    }
+
     #[inline(always)]
     pub fn disk_quota(&self) -> u64 {
         self.quota_disk
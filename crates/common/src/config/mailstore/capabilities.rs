@@ -125,6 +125,16 @@ impl JmapConfig {
             Capabilities::Empty(EmptyCapabilities::default()),
         );
 
+        // Add tasks capabilities
+        self.capabilities.session.append(
+            Capability::Tasks,
+            Capabilities::Empty(EmptyCapabilities::default()),
+        );
+        self.capabilities.account.insert(
+            Capability::Tasks,
+            Capabilities::Empty(EmptyCapabilities::default()),
+        );
+
         // Add contacts capabilities
         self.capabilities.session.append(
             Capability::Contacts,
@@ -222,6 +232,26 @@ impl JmapConfig {
             }),
         );
 
+        // Add MDN capabilities
+        self.capabilities.session.append(
+            Capability::Mdn,
+            Capabilities::Empty(EmptyCapabilities::default()),
+        );
+        self.capabilities.account.insert(
+            Capability::Mdn,
+            Capabilities::Empty(EmptyCapabilities::default()),
+        );
+
+        // Add S/MIME verification capabilities
+        self.capabilities.session.append(
+            Capability::SmimeVerify,
+            Capabilities::Empty(EmptyCapabilities::default()),
+        );
+        self.capabilities.account.insert(
+            Capability::SmimeVerify,
+            Capabilities::Empty(EmptyCapabilities::default()),
+        );
+
         // Add vacation response capabilities
         self.capabilities.session.append(
             Capability::VacationResponse,
@@ -10,12 +10,12 @@ use registry::{
     schema::{
         enums::{
             CompressionAlgo, SearchCalendarField, SearchContactField, SearchEmailField,
-            StorageQuota,
+            SearchFileField, StorageQuota,
         },
         prelude::ObjectType,
         structs::{
             AddressBook, Authentication, Calendar, DataRetention, Domain, Email, FileStorage, Jmap,
-            Search, SieveUserInterpreter, SystemSettings,
+            QuarantineSettings, Search, SieveUserInterpreter, SystemSettings,
         },
     },
     types::EnumImpl,
@@ -23,7 +23,9 @@ use registry::{
 use std::time::Duration;
 use store::{
     registry::bootstrap::Bootstrap,
-    search::{CalendarSearchField, ContactSearchField, EmailSearchField, SearchField},
+    search::{
+        CalendarSearchField, ContactSearchField, EmailSearchField, FileSearchField, SearchField,
+    },
     write::SearchIndex,
 };
 use types::special_use::SpecialUse;
@@ -58,6 +60,7 @@ pub struct EmailConfig {
 
     pub index_batch_size: usize,
     pub index_fields: AHashMap<SearchIndex, AHashSet<SearchField>>,
+    pub extract: ExtractConfig,
 
     pub max_objects: ObjectQuota,
     pub compression: CompressionAlgo,
@@ -65,6 +68,27 @@ pub struct EmailConfig {
     pub account_purge_frequency: SimpleCron,
     pub data_purge_frequency: SimpleCron,
     pub blob_purge_frequency: SimpleCron,
+
+    pub quarantine: QuarantineConfig,
+}
+
+#[derive(Clone)]
+pub struct ExtractConfig {
+    pub enable: bool,
+    pub max_size: usize,
+    pub timeout: Duration,
+    pub cache_ttl: u64,
+}
+
+#[derive(Clone)]
+pub struct QuarantineConfig {
+    pub hold_for: u64,
+    pub quarantine_spam: bool,
+    pub send_digest: bool,
+    pub digest_frequency: SimpleCron,
+    pub from_name: String,
+    pub from_address: String,
+    pub subject: String,
 }
 
 #[derive(Clone, Debug)]
@@ -88,6 +112,7 @@ impl EmailConfig {
         let address_book = bp.setting_infallible::<AddressBook>().await;
         let system = bp.setting_infallible::<SystemSettings>().await;
         let auth = bp.setting_infallible::<Authentication>().await;
+        let quarantine = bp.setting_infallible::<QuarantineSettings>().await;
 
         // Obtain default domain name
         let default_domain_name = if system.default_domain_id.is_valid()
@@ -265,6 +290,39 @@ impl EmailConfig {
                     .collect(),
             );
         }
+        if search.index_files {
+            index_fields.insert(
+                SearchIndex::File,
+                search
+                    .index_file_fields
+                    .into_iter()
+                    .map(|field| {
+                        SearchField::File(match field {
+                            SearchFileField::Name => FileSearchField::Name,
+                            SearchFileField::Content => FileSearchField::Content,
+                        })
+                    })
+                    .collect(),
+            );
+        }
+        let extract = ExtractConfig {
+            enable: search.extract_attachments,
+            max_size: search.extract_max_size as usize,
+            timeout: search.extract_timeout.into_inner(),
+            cache_ttl: search.extract_cache_ttl.into_inner().as_secs(),
+        };
+
+        let quarantine = QuarantineConfig {
+            hold_for: quarantine.hold_for.into_inner().as_secs(),
+            quarantine_spam: quarantine.quarantine_spam,
+            send_digest: quarantine.send_digest,
+            digest_frequency: quarantine.schedule.into(),
+            from_name: quarantine.from_name,
+            from_address: quarantine
+                .from_address
+                .unwrap_or_else(|| format!("postmaster@{default_domain_name}")),
+            subject: quarantine.subject,
+        };
 
         EmailConfig {
             default_language: Language::from_iso_639(search.default_language.as_str())
@@ -284,6 +342,7 @@ impl EmailConfig {
             encrypt_append: email.encrypt_on_append,
             index_batch_size: search.index_batch_size as usize,
             index_fields,
+            extract,
             max_objects,
             default_folders,
             shared_folder,
@@ -293,6 +352,7 @@ impl EmailConfig {
             compression: email.compression_algorithm,
             default_domain_id: system.default_domain_id.id() as u32,
             default_domain_name,
+            quarantine,
         }
     }
 }
@@ -20,6 +20,9 @@ pub struct ImapConfig {
 
     pub rate_requests: Option<Rate>,
     pub rate_concurrent: Option<u64>,
+
+    pub max_metadata_entries: usize,
+    pub max_metadata_size: usize,
 }
 
 impl ImapConfig {
@@ -35,6 +38,8 @@ impl ImapConfig {
             rate_requests: imap.max_request_rate,
             rate_concurrent: imap.max_concurrent,
             allow_plain_auth: imap.allow_plain_text_auth,
+            max_metadata_entries: imap.max_metadata_entries as usize,
+            max_metadata_size: imap.max_metadata_size as usize,
         }
     }
 }
@@ -7,7 +7,8 @@
 use crate::network::webpush::{Vapid, VapidKey};
 use jmap_proto::request::capability::BaseCapabilities;
 use registry::schema::{prelude::ObjectType, structs::Jmap};
-use std::{sync::Arc, time::Duration};
+use rustls_pemfile::certs;
+use std::{io::Cursor, sync::Arc, time::Duration};
 use store::registry::bootstrap::Bootstrap;
 
 #[derive(Default, Clone)]
@@ -49,6 +50,9 @@ pub struct JmapConfig {
 
     pub vapid: Option<Arc<Vapid>>,
 
+    pub smime_trust_anchors: Arc<[rasn_pkix::Certificate]>,
+    pub smime_cache_ttl: u64,
+
     pub capabilities: BaseCapabilities,
 }
 
@@ -69,6 +73,24 @@ impl JmapConfig {
             .map(|k| k.into_owned());
         let web_push_contact = jmap.web_push_contact;
 
+        // Parse S/MIME trust anchors
+        let mut smime_trust_anchors = Vec::new();
+        for cert in certs(&mut Cursor::new(
+            jmap.smime_trust_anchors.as_deref().unwrap_or_default(),
+        )) {
+            match cert.map_err(|err| err.to_string()).and_then(|cert| {
+                rasn::der::decode::<rasn_pkix::Certificate>(&cert).map_err(|err| err.to_string())
+            }) {
+                Ok(cert) => smime_trust_anchors.push(cert),
+                Err(err) => {
+                    bp.build_error(
+                        ObjectType::Jmap.singleton(),
+                        format!("Invalid S/MIME trust anchor: {err}"),
+                    );
+                }
+            }
+        }
+
         let mut jmap = JmapConfig {
             query_max_results: jmap.query_max_results as usize,
             changes_max_results: jmap.changes_max_results as usize,
@@ -98,6 +120,8 @@ impl JmapConfig {
             push_throttle: jmap.push_throttle.into_inner(),
             push_total_shards: jmap.push_shards_total as u32,
             vapid: None,
+            smime_trust_anchors: smime_trust_anchors.into(),
+            smime_cache_ttl: jmap.smime_cache_ttl.into_inner().as_secs(),
             capabilities: BaseCapabilities::default(),
         };
 
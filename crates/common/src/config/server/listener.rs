@@ -10,21 +10,24 @@ use super::{
 };
 use crate::{
     Inner,
-    network::{TcpAcceptor, tls::CertificateResolver},
+    network::{ClientCertMapping, TcpAcceptor, tls::CertificateResolver},
 };
 use registry::{
     schema::{
-        enums::{NetworkListenerProtocol, TlsCipherSuite, TlsVersion},
+        enums::{NetworkListenerProtocol, TlsCipherSuite, TlsClientAuth, TlsVersion},
         prelude::{ObjectType, SocketAddr},
         structs::{ClusterListenerGroup, NetworkListener, SystemSettings},
     },
     types::{id::ObjectId, map::Map},
 };
 use rustls::{
-    ALL_VERSIONS, ServerConfig, SupportedCipherSuite,
+    ALL_VERSIONS, RootCertStore, ServerConfig, SupportedCipherSuite,
     crypto::aws_lc_rs::{ALL_CIPHER_SUITES, cipher_suite::*, default_provider},
+    server::WebPkiClientVerifier,
 };
+use rustls_pemfile::certs;
 use std::{
+    io::Cursor,
     net::{IpAddr, Ipv4Addr, SocketAddr as StdSocketAddr},
     str::FromStr,
     sync::Arc,
@@ -276,9 +279,84 @@ impl Listeners {
                         .copied()
                         .collect();
                 }
+                let provider = Arc::new(provider);
+
+                // Build client certificate verifier
+                let (client_verifier, client_cert_mapping) = match listener.tls_client_auth {
+                    TlsClientAuth::Disabled => (None, None),
+                    client_auth => {
+                        let mut roots = RootCertStore::empty();
+                        for cert in certs(&mut Cursor::new(
+                            listener.tls_client_ca.as_deref().unwrap_or_default(),
+                        )) {
+                            match cert {
+                                Ok(cert) => {
+                                    if let Err(err) = roots.add(cert) {
+                                        bp.build_error(
+                                            id,
+                                            format!("Invalid client CA certificate: {err}"),
+                                        );
+                                    }
+                                }
+                                Err(err) => {
+                                    bp.build_error(
+                                        id,
+                                        format!("Failed to read client CA certificates: {err}"),
+                                    );
+                                }
+                            }
+                        }
+                        if roots.is_empty() {
+                            bp.build_error(
+                                id,
+                                "Client certificate authentication requires at least one trusted CA",
+                            );
+                            return;
+                        }
+
+                        let builder = WebPkiClientVerifier::builder_with_provider(
+                            Arc::new(roots),
+                            provider.clone(),
+                        );
+                        let builder = if client_auth == TlsClientAuth::Optional {
+                            builder.allow_unauthenticated()
+                        } else {
+                            builder
+                        };
+                        match builder.build() {
+                            Ok(verifier) => (
+                                Some(verifier),
+                                Some(Arc::new(ClientCertMapping {
+                                    mapping: listener.tls_client_cert_mapping,
+                                    fingerprints: listener
+                                        .tls_client_cert_fingerprints
+                                        .iter()
+                                        .map(|(fingerprint, account)| {
+                                            (
+                                                fingerprint
+                                                    .chars()
+                                                    .filter(|ch| *ch != ':')
+                                                    .collect::<String>()
+                                                    .to_ascii_lowercase(),
+                                                account.clone(),
+                                            )
+                                        })
+                                        .collect(),
+                                })),
+                            ),
+                            Err(err) => {
+                                bp.build_error(
+                                    id,
+                                    format!("Failed to build client certificate verifier: {err}"),
+                                );
+                                return;
+                            }
+                        }
+                    }
+                };
 
                 // Build server config
-                let mut server_config = match ServerConfig::builder_with_provider(provider.into())
+                let mut server_config = match ServerConfig::builder_with_provider(provider)
                     .with_protocol_versions(if tls_v3 == tls_v2 {
                         ALL_VERSIONS
                     } else if tls_v3 {
@@ -286,9 +364,11 @@ impl Listeners {
                     } else {
                         TLS12_VERSION
                     }) {
-                    Ok(server_config) => server_config
-                        .with_no_client_auth()
-                        .with_cert_resolver(resolver.clone()),
+                    Ok(server_config) => match client_verifier {
+                        Some(verifier) => server_config.with_client_cert_verifier(verifier),
+                        None => server_config.with_no_client_auth(),
+                    }
+                    .with_cert_resolver(resolver.clone()),
                     Err(err) => {
                         bp.build_error(id, format!("Failed to build TLS server config: {err}"));
                         return;
@@ -303,6 +383,7 @@ impl Listeners {
                     acceptor: TlsAcceptor::from(default_config.clone()),
                     config: default_config,
                     implicit: listener.tls_implicit,
+                    client_cert_mapping,
                 }
             } else {
                 TcpAcceptor::Plain
@@ -9,6 +9,8 @@ use crate::expr::{
     if_block::{BootstrapExprExt, IfBlock},
 };
 use mail_auth::{
+    ArcOutput, AuthenticatedMessage, AuthenticationResults,
+    arc::ArcSet,
     common::crypto::{Ed25519Key, HashAlgorithm, RsaKey, Sha256, SigningKey},
     dkim::{Canonicalization, Done},
     dkim2::{Dkim2Signer, Done as Dkim2Done, Flag},
@@ -45,7 +47,7 @@ pub struct DkimAuthConfig {
 #[derive(Clone)]
 pub struct ArcAuthConfig {
     pub verify: IfBlock,
-    //pub seal: IfBlock,
+    pub seal: IfBlock,
 }
 
 #[derive(Clone)]
@@ -77,10 +79,16 @@ pub enum Dkim1Signer {
     Ed25519Sha256(mail_auth::dkim::DkimSigner<Ed25519Key, Done>),
 }
 
+pub enum ArcSealer {
+    RsaSha256(mail_auth::arc::ArcSealer<RsaKey<Sha256>, Done>),
+    Ed25519Sha256(mail_auth::arc::ArcSealer<Ed25519Key, Done>),
+}
+
 #[derive(Default)]
 pub struct DkimSigners {
     pub dkim1: Vec<Dkim1Signer>,
     pub dkim2: Option<Dkim2Signer<Dkim2Done>>,
+    pub arc: Option<ArcSealer>,
 }
 
 impl MailAuthConfig {
@@ -99,7 +107,10 @@ impl MailAuthConfig {
             },
             arc: ArcAuthConfig {
                 verify: bp.compile_expr(ObjectType::SenderAuth.singleton(), &auth.ctx_arc_verify()),
-                //seal: bp.compile_expr(ObjectType::SenderAuth.singleton(), &auth.ctx_arc_seal_domain()),
+                seal: bp.compile_expr(
+                    ObjectType::SenderAuth.singleton(),
+                    &auth.ctx_arc_seal_domain(),
+                ),
             },
             spf: SpfAuthConfig {
                 verify_ehlo: bp.compile_expr(
@@ -151,12 +162,16 @@ impl DkimSigners {
                         .reason("Failed to parse ED25519 private key PEM")
                         .details("Invalid PEM format")
                 })?;
-                let key =
-                    Ed25519Key::from_pkcs8_maybe_unchecked_der(&private_key).map_err(|err| {
-                        trc::DkimEvent::BuildError
-                            .reason(err)
-                            .details("Failed to build ED25519 key")
-                    })?;
+                let key = ed25519_key_parse(&private_key)?;
+
+                // The first DKIM1 key of the domain also seals ARC sets
+                if self.arc.is_none() {
+                    self.arc = Some(ArcSealer::Ed25519Sha256(build_arc_sealer(
+                        domain.clone(),
+                        &signature,
+                        ed25519_key_parse(&private_key)?,
+                    )));
+                }
 
                 self.dkim1
                     .push(Dkim1Signer::Ed25519Sha256(build_dkim1_signer(
@@ -171,6 +186,15 @@ impl DkimSigners {
                     .map_err(|err| trc::DkimEvent::BuildError.reason(err))?;
                 let key = rsa_key_parse(private_key.as_bytes())?;
 
+                // The first DKIM1 key of the domain also seals ARC sets
+                if self.arc.is_none() {
+                    self.arc = Some(ArcSealer::RsaSha256(build_arc_sealer(
+                        domain.clone(),
+                        &signature,
+                        rsa_key_parse(private_key.as_bytes())?,
+                    )));
+                }
+
                 self.dkim1.push(Dkim1Signer::RsaSha256(build_dkim1_signer(
                     domain, signature, key,
                 )));
@@ -186,12 +210,7 @@ impl DkimSigners {
                         .reason("Failed to parse ED25519 private key PEM")
                         .details("Invalid PEM format")
                 })?;
-                let key =
-                    Ed25519Key::from_pkcs8_maybe_unchecked_der(&private_key).map_err(|err| {
-                        trc::DkimEvent::BuildError
-                            .reason(err)
-                            .details("Failed to build ED25519 key")
-                    })?;
+                let key = ed25519_key_parse(&private_key)?;
 
                 self.dkim2 = Some(match self.dkim2.take() {
                     None => Dkim2Signer::from_key(key)
@@ -253,6 +272,14 @@ pub fn rsa_key_parse(private_key: &[u8]) -> trc::Result<RsaKey<Sha256>> {
         })
 }
 
+fn ed25519_key_parse(private_key: &[u8]) -> trc::Result<Ed25519Key> {
+    Ed25519Key::from_pkcs8_maybe_unchecked_der(private_key).map_err(|err| {
+        trc::DkimEvent::BuildError
+            .reason(err)
+            .details("Failed to build ED25519 key")
+    })
+}
+
 pub fn simple_pem_parse(contents: &str) -> Option<Vec<u8>> {
     let mut contents = contents.as_bytes().iter().copied();
     let mut base64 = vec![];
@@ -293,28 +320,10 @@ fn build_dkim1_signer<T: SigningKey>(
         .headers(signature.headers)
         .reporting(signature.report);
 
-    match signature.canonicalization {
-        enums::DkimCanonicalization::RelaxedRelaxed => {
-            signer = signer
-                .body_canonicalization(Canonicalization::Relaxed)
-                .header_canonicalization(Canonicalization::Relaxed);
-        }
-        enums::DkimCanonicalization::SimpleSimple => {
-            signer = signer
-                .body_canonicalization(Canonicalization::Simple)
-                .header_canonicalization(Canonicalization::Simple);
-        }
-        enums::DkimCanonicalization::RelaxedSimple => {
-            signer = signer
-                .body_canonicalization(Canonicalization::Simple)
-                .header_canonicalization(Canonicalization::Relaxed);
-        }
-        enums::DkimCanonicalization::SimpleRelaxed => {
-            signer = signer
-                .body_canonicalization(Canonicalization::Relaxed)
-                .header_canonicalization(Canonicalization::Simple);
-        }
-    }
+    let (header, body) = canonicalization(signature.canonicalization);
+    signer = signer
+        .body_canonicalization(body)
+        .header_canonicalization(header);
 
     if let Some(expire) = signature.expire {
         signer = signer.expiration(expire.into_inner().as_secs());
@@ -337,6 +346,60 @@ fn build_dkim1_signer<T: SigningKey>(
     signer
 }
 
+/// Returns the header and body canonicalization of a DKIM signature
+fn canonicalization(
+    canonicalization: enums::DkimCanonicalization,
+) -> (Canonicalization, Canonicalization) {
+    match canonicalization {
+        enums::DkimCanonicalization::RelaxedRelaxed => {
+            (Canonicalization::Relaxed, Canonicalization::Relaxed)
+        }
+        enums::DkimCanonicalization::SimpleSimple => {
+            (Canonicalization::Simple, Canonicalization::Simple)
+        }
+        enums::DkimCanonicalization::RelaxedSimple => {
+            (Canonicalization::Relaxed, Canonicalization::Simple)
+        }
+        enums::DkimCanonicalization::SimpleRelaxed => {
+            (Canonicalization::Simple, Canonicalization::Relaxed)
+        }
+    }
+}
+
+fn build_arc_sealer<T: SigningKey>(
+    domain: String,
+    signature: &Dkim1Signature,
+    key: T,
+) -> mail_auth::arc::ArcSealer<T, Done> {
+    let (header, body) = canonicalization(signature.canonicalization);
+    let mut sealer = mail_auth::arc::ArcSealer::from_key(key)
+        .domain(domain)
+        .selector(signature.selector.clone())
+        .headers(signature.headers.iter().cloned())
+        .body_canonicalization(body)
+        .header_canonicalization(header);
+
+    if let Some(expire) = signature.expire {
+        sealer = sealer.expiration(expire.into_inner().as_secs());
+    }
+
+    sealer
+}
+
+impl ArcSealer {
+    pub fn seal<'x>(
+        &self,
+        message: &'x AuthenticatedMessage<'x>,
+        results: &'x AuthenticationResults<'x>,
+        arc_output: &'x ArcOutput<'x>,
+    ) -> mail_auth::Result<ArcSet<'x>> {
+        match self {
+            ArcSealer::RsaSha256(sealer) => sealer.seal(message, results, arc_output),
+            ArcSealer::Ed25519Sha256(sealer) => sealer.seal(message, results, arc_output),
+        }
+    }
+}
+
 impl<'x> TryFrom<expr::Variable<'x>> for VerifyStrategy {
     type Error = ();
 
@@ -375,6 +438,7 @@ impl CacheItemWeight for DkimSigners {
     fn weight(&self) -> u64 {
         (std::mem::size_of::<Self>()
             + self.dkim1.len() * std::mem::size_of::<Dkim1Signer>()
-            + std::mem::size_of::<Dkim2Signer<Dkim2Done>>()) as u64
+            + std::mem::size_of::<Dkim2Signer<Dkim2Done>>()
+            + std::mem::size_of::<ArcSealer>()) as u64
     }
 }
@@ -16,13 +16,14 @@ use registry::schema::{
     enums::{self, ExpressionConstant, MtaStage},
     prelude::ObjectType,
     structs::{
-        MtaExtensions, MtaHook, MtaInboundSession, MtaMilter, MtaStageAuth, MtaStageConnect,
-        MtaStageData, MtaStageEhlo, MtaStageMail, MtaStageRcpt,
+        MtaAntivirus, MtaExtensions, MtaHook, MtaInboundSession, MtaMilter, MtaStageAuth,
+        MtaStageConnect, MtaStageData, MtaStageEhlo, MtaStageMail, MtaStageRcpt,
     },
 };
 use smtp_proto::*;
 use std::{
     net::{SocketAddr, ToSocketAddrs},
+    path::PathBuf,
     str::FromStr,
     time::Duration,
 };
@@ -44,6 +45,7 @@ pub struct SessionConfig {
 
     pub milters: Vec<Milter>,
     pub hooks: Vec<MTAHook>,
+    pub antivirus: Option<Antivirus>,
 }
 
 #[derive(Clone)]
@@ -72,6 +74,9 @@ pub struct Extensions {
     pub future_release: IfBlock,
     pub deliver_by: IfBlock,
     pub mt_priority: IfBlock,
+    pub etrn: IfBlock,
+    pub atrn: IfBlock,
+    pub burl: IfBlock,
 }
 
 #[derive(Clone)]
@@ -163,6 +168,22 @@ pub struct MTAHook {
     pub max_response_size: usize,
 }
 
+#[derive(Clone)]
+pub struct Antivirus {
+    pub address: ClamdAddress,
+    pub timeout: Duration,
+    pub max_size: usize,
+    pub scan_mode: enums::AntivirusScanMode,
+    pub action: enums::AntivirusAction,
+    pub tempfail_on_error: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum ClamdAddress {
+    Tcp(Vec<SocketAddr>),
+    Unix(PathBuf),
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Stage {
     Connect,
@@ -367,6 +388,9 @@ impl SessionConfig {
                     ObjectType::MtaExtensions.singleton(),
                     &ext.ctx_mt_priority(),
                 ),
+                etrn: bp.compile_expr(ObjectType::MtaExtensions.singleton(), &ext.ctx_etrn()),
+                atrn: bp.compile_expr(ObjectType::MtaExtensions.singleton(), &ext.ctx_atrn()),
+                burl: bp.compile_expr(ObjectType::MtaExtensions.singleton(), &ext.ctx_burl()),
             },
             mta_sts_policy: Policy::try_parse(bp).await,
             milters: bp
@@ -413,7 +437,46 @@ impl SessionConfig {
                 })
                 .collect(),
             hooks,
+            antivirus: Antivirus::parse(bp).await,
+        }
+    }
+}
+
+impl Antivirus {
+    pub async fn parse(bp: &mut Bootstrap) -> Option<Self> {
+        let antivirus = bp.setting_infallible::<MtaAntivirus>().await;
+
+        if !antivirus.enable {
+            return None;
+        }
+
+        let address = if let Some(path) = antivirus.socket_path.filter(|p| !p.is_empty()) {
+            ClamdAddress::Unix(PathBuf::from(path))
+        } else {
+            match format!("{}:{}", antivirus.host, antivirus.port).to_socket_addrs() {
+                Ok(addrs) => ClamdAddress::Tcp(addrs.collect()),
+                Err(err) => {
+                    bp.build_error(
+                        ObjectType::MtaAntivirus.singleton(),
+                        format!(
+                            "Unable to resolve ClamAV hostname {}: {}",
+                            antivirus.host, err
+                        ),
+                    );
+                    return None;
+                }
+            }
+        };
+
+        Antivirus {
+            address,
+            timeout: antivirus.timeout.into_inner(),
+            max_size: antivirus.max_size as usize,
+            scan_mode: antivirus.scan_mode,
+            action: antivirus.action,
+            tempfail_on_error: antivirus.temp_fail_on_error,
         }
+        .into()
     }
 }
 
@@ -429,9 +492,9 @@ impl FromStr for Mechanism {
             "PLAIN" => AUTH_PLAIN,
             "XOAUTH2" => AUTH_XOAUTH2,
             "OAUTHBEARER" => AUTH_OAUTHBEARER,
-            /*"SCRAM-SHA-256-PLUS" => AUTH_SCRAM_SHA_256_PLUS,
+            "SCRAM-SHA-256-PLUS" => AUTH_SCRAM_SHA_256_PLUS,
             "SCRAM-SHA-256" => AUTH_SCRAM_SHA_256,
-            "SCRAM-SHA-1-PLUS" => AUTH_SCRAM_SHA_1_PLUS,
+            /*"SCRAM-SHA-1-PLUS" => AUTH_SCRAM_SHA_1_PLUS,
             "SCRAM-SHA-1" => AUTH_SCRAM_SHA_1,
             "XOAUTH" => AUTH_XOAUTH,
             "9798-M-DSA-SHA1" => AUTH_9798_M_DSA_SHA1,
@@ -504,6 +567,8 @@ impl TryFrom<ExpressionConstant> for Mechanism {
             ExpressionConstant::Plain => Ok(Mechanism(AUTH_PLAIN)),
             ExpressionConstant::Xoauth2 => Ok(Mechanism(AUTH_XOAUTH2)),
             ExpressionConstant::Oauthbearer => Ok(Mechanism(AUTH_OAUTHBEARER)),
+            ExpressionConstant::ScramSha256 => Ok(Mechanism(AUTH_SCRAM_SHA_256)),
+            ExpressionConstant::ScramSha256Plus => Ok(Mechanism(AUTH_SCRAM_SHA_256_PLUS)),
             _ => Err(()),
         }
     }
@@ -127,6 +127,11 @@ pub const KV_LOCK_QUEUE_MESSAGE: u8 = 21;
 pub const KV_LOCK_TASK: u8 = 23;
 pub const KV_LOCK_DAV: u8 = 25;
 pub const KV_SIEVE_ID: u8 = 26;
+pub const KV_LIST_CONFIRM: u8 = 27;
+pub const KV_LIST_MODERATION: u8 = 28;
+pub const KV_LIST_BOUNCE: u8 = 29;
+pub const KV_EXTRACT_TEXT: u8 = 30;
+pub const KV_WEBAUTHN: u8 = 31;
 
 #[derive(Clone)]
 pub struct Server {
@@ -336,11 +341,13 @@ pub enum DavResourceMetadata {
         name: String,
         acls: TinyVec<[AclGrant; 2]>,
         preferences: TinyVec<[TinyCalendarPreferences; 2]>,
+        supports_tasks: bool,
     },
     CalendarEvent {
         names: TinyVec<[DavName; 2]>,
         start: i64,
         duration: u32,
+        is_task: bool,
     },
     CalendarEventNotification {
         names: TinyVec<[DavName; 2]>,
@@ -4,22 +4,32 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use super::restore::KeyValueReader;
 use crate::Core;
 use ahash::AHashSet;
 use lz4_flex::frame::FrameEncoder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
-    io::{BufWriter, Write},
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{BufWriter, Read, Write},
     path::{Path, PathBuf},
-    sync::mpsc::{self, SyncSender},
+    sync::{
+        Arc,
+        mpsc::{self, SyncSender},
+    },
 };
 use store::{
-    write::{AnyClass, AnyKey, ValueClass},
+    write::{AnyClass, AnyKey, ValueClass, key::DeserializeBigEndian, now},
     *,
 };
 use types::blob_hash::{BLOB_HASH_LEN, BlobHash};
-use utils::{UnwrapFailure, codec::leb128::Leb128_};
+use utils::{HexEncode, UnwrapFailure, codec::leb128::Leb128_, failed};
 
 pub(super) const MAGIC_MARKER: u8 = 123;
+pub(super) const MANIFEST_FILE: &str = "manifest.json";
+pub(super) const BLOB_INDEX_FILE: &str = "blob_index";
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub(super) enum Family {
@@ -33,12 +43,74 @@ pub(super) enum Family {
     Tasks = 8,
 }
 
-type TaskHandle = (tokio::task::JoinHandle<()>, std::thread::JoinHandle<()>);
+type TaskHandle = (
+    tokio::task::JoinHandle<()>,
+    Vec<std::thread::JoinHandle<()>>,
+);
 
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct BackupParams {
     dest: PathBuf,
     families: AHashSet<Family>,
+    mode: BackupMode,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum BackupMode {
+    // Full dump written directly to the destination directory
+    #[default]
+    Export,
+    // New full backup in a backup directory
+    Full,
+    // Incremental backup on top of the latest backup in a backup directory,
+    // or a full backup if there is none
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum BackupKind {
+    Full,
+    Incremental,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct BackupManifest {
+    pub id: String,
+    pub kind: BackupKind,
+    pub parent: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: u32,
+    // Last change id of each account at the time of the backup
+    pub changelog: BTreeMap<u32, u64>,
+    // Revision of each account, bumped by writes that are not in the changelog
+    #[serde(default)]
+    pub revisions: BTreeMap<u32, u64>,
+    // Accounts whose data is replaced when restoring an incremental backup
+    #[serde(default)]
+    pub accounts: Vec<u32>,
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct BackupFile {
+    pub name: String,
+    pub subspace: Option<u8>,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Clone)]
+enum SubspaceScope {
+    All,
+    Accounts(Arc<Vec<u32>>),
+    ChangesSince(Arc<BTreeMap<u32, u64>>),
+    Owners {
+        accounts: Arc<AHashSet<u32>>,
+        skip_blobs: Arc<AHashSet<BlobHash>>,
+    },
 }
 
 impl Core {
@@ -50,6 +122,33 @@ impl Core {
             std::process::exit(1);
         }
 
+        // Locate the backup this one builds upon
+        let created_at = now();
+        let (dest, parent) = match params.mode {
+            BackupMode::Export => (params.dest.clone(), None),
+            BackupMode::Full | BackupMode::Auto => {
+                let parent = if params.mode == BackupMode::Auto {
+                    BackupManifest::read_all(&params.dest).pop()
+                } else {
+                    None
+                };
+                let id = format!(
+                    "{}-{}",
+                    chrono::DateTime::from_timestamp(created_at as i64, 0)
+                        .unwrap_or_default()
+                        .format("%Y%m%dT%H%M%SZ"),
+                    if parent.is_some() {
+                        "incremental"
+                    } else {
+                        "full"
+                    }
+                );
+                let dest = params.dest.join(&id);
+                std::fs::create_dir_all(&dest).failed("Failed to create backup directory");
+                (dest, parent)
+            }
+        };
+
         let mut sync_handles = Vec::new();
         let schema_version = self
             .storage
@@ -77,6 +176,30 @@ impl Core {
             .collect();
         }
 
+        // The changelog position is obtained before exporting any data, changes
+        // committed while the backup runs are included again in the next one.
+        let (changelog, revisions) = self.changelog_position().await;
+        let mut accounts = Vec::new();
+        let mut skip_blobs = AHashSet::new();
+        if let Some(parent) = &parent {
+            accounts = changed_accounts(&parent.changelog, &changelog)
+                .chain(changed_accounts(&parent.revisions, &revisions))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            skip_blobs = BackupManifest::chain_blobs(&params.dest, parent);
+        }
+        let changed_accounts = Arc::new(accounts.clone());
+        let owners = Arc::new(accounts.iter().copied().collect::<AHashSet<_>>());
+        let parent_changelog = Arc::new(
+            parent
+                .as_ref()
+                .map(|parent| parent.changelog.clone())
+                .unwrap_or_default(),
+        );
+        let skip_blobs = Arc::new(skip_blobs);
+
+        let mut files = Vec::new();
         for subspace in params
             .families
             .into_iter()
@@ -84,20 +207,137 @@ impl Core {
             .copied()
         {
             let (async_handle, sync_handle) = if subspace == SUBSPACE_BLOBS {
-                self.backup_blobs(&params.dest, subspace, schema_version)
+                files.push((BLOB_INDEX_FILE.to_string(), None));
+                self.backup_blobs(&dest, subspace, schema_version, skip_blobs.clone())
             } else {
-                self.backup_subspace(&params.dest, subspace, schema_version)
+                let scope = match subspace {
+                    _ if parent.is_none() => SubspaceScope::All,
+                    SUBSPACE_LOGS => SubspaceScope::ChangesSince(parent_changelog.clone()),
+                    SUBSPACE_PROPERTY | SUBSPACE_INDEXES => {
+                        SubspaceScope::Accounts(changed_accounts.clone())
+                    }
+                    SUBSPACE_ACL | SUBSPACE_COUNTER | SUBSPACE_BLOB_LINK => SubspaceScope::Owners {
+                        accounts: owners.clone(),
+                        skip_blobs: skip_blobs.clone(),
+                    },
+                    _ => SubspaceScope::All,
+                };
+                self.backup_subspace(&dest, subspace, schema_version, scope)
             };
+            files.push((format!("subspace_{}", char::from(subspace)), Some(subspace)));
             async_handle.await.failed("Task failed");
-            sync_handles.push(sync_handle);
+            sync_handles.extend(sync_handle);
         }
 
         for handle in sync_handles {
             handle.join().expect("Failed to join thread");
         }
+
+        // Write manifest
+        let manifest = BackupManifest {
+            id: dest
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string(),
+            kind: if parent.is_some() {
+                BackupKind::Incremental
+            } else {
+                BackupKind::Full
+            },
+            parent: parent.map(|parent| parent.id),
+            created_at,
+            schema_version,
+            changelog,
+            revisions,
+            accounts,
+            files: files
+                .into_iter()
+                .map(|(name, subspace)| {
+                    let (size, sha256) = file_checksum(&dest.join(&name));
+                    BackupFile {
+                        name,
+                        subspace,
+                        size,
+                        sha256,
+                    }
+                })
+                .collect(),
+        };
+        std::fs::write(
+            dest.join(MANIFEST_FILE),
+            serde_json::to_vec_pretty(&manifest).failed("Failed to serialize manifest"),
+        )
+        .failed("Failed to write backup manifest");
+    }
+
+    async fn changelog_position(&self) -> (BTreeMap<u32, u64>, BTreeMap<u32, u64>) {
+        // Change ids are per-account counters stored under the account id,
+        // revisions under the account id followed by a marker byte
+        let mut keys = Vec::new();
+        self.storage
+            .data
+            .iterate(
+                IterateParams::new(
+                    AnyKey {
+                        subspace: SUBSPACE_COUNTER,
+                        key: vec![0u8],
+                    },
+                    AnyKey {
+                        subspace: SUBSPACE_COUNTER,
+                        key: vec![u8::MAX; 32],
+                    },
+                )
+                .no_values(),
+                |key, _| {
+                    if key.len() == U32_LEN
+                        || (key.len() == U32_LEN + 1 && key[U32_LEN] == u8::MAX - 2)
+                    {
+                        keys.push(key.to_vec());
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .failed("Failed to iterate over data store");
+
+        let mut position = BTreeMap::new();
+        let mut revisions = BTreeMap::new();
+        for key in keys {
+            let account_id = key
+                .as_slice()
+                .deserialize_be_u32(0)
+                .failed("Failed to deserialize account id");
+            let is_revision = key.len() > U32_LEN;
+            let value = self
+                .storage
+                .data
+                .get_counter(ValueClass::Any(AnyClass {
+                    subspace: SUBSPACE_COUNTER,
+                    key,
+                }))
+                .await
+                .failed("Failed to get counter");
+            if value > 0 {
+                if is_revision {
+                    revisions.insert(account_id, value as u64);
+                } else {
+                    position.insert(account_id, value as u64);
+                }
+            }
+        }
+
+        (position, revisions)
     }
 
-    fn backup_blobs(&self, dest: &Path, subspace: u8, schema_version: u32) -> TaskHandle {
+    fn backup_blobs(
+        &self,
+        dest: &Path,
+        subspace: u8,
+        schema_version: u32,
+        skip_blobs: Arc<AHashSet<BlobHash>>,
+    ) -> TaskHandle {
         let store = self.storage.data.clone();
         let blob_store = self.storage.blob.clone();
         let (handle, writer) = spawn_writer(
@@ -105,6 +345,8 @@ impl Core {
             subspace,
             schema_version,
         );
+        let (index_handle, index_writer) =
+            spawn_writer(dest.join(BLOB_INDEX_FILE), subspace, schema_version);
         (
             tokio::spawn(async move {
                 let mut blobs = Vec::new();
@@ -141,23 +383,35 @@ impl Core {
                     .await
                     .failed("Failed to iterate over data store");
 
+                // Blobs already present in an earlier backup of the chain are skipped,
+                // the index lists every blob this backup depends on.
                 for hash in blobs {
-                    if let Some(blob) = blob_store
-                        .get_blob(hash.as_slice(), 0..usize::MAX)
-                        .await
-                        .failed("Failed to get blob")
+                    if !skip_blobs.contains(&hash)
+                        && let Some(blob) = blob_store
+                            .get_blob(hash.as_slice(), 0..usize::MAX)
+                            .await
+                            .failed("Failed to get blob")
                     {
                         writer
                             .send((hash.as_slice().to_vec(), blob))
                             .failed("Failed to send key");
                     }
+                    index_writer
+                        .send((hash.as_slice().to_vec(), vec![]))
+                        .failed("Failed to send key");
                 }
             }),
-            handle,
+            vec![handle, index_handle],
         )
     }
 
-    fn backup_subspace(&self, dest: &Path, subspace: u8, schema_version: u32) -> TaskHandle {
+    fn backup_subspace(
+        &self,
+        dest: &Path,
+        subspace: u8,
+        schema_version: u32,
+        scope: SubspaceScope,
+    ) -> TaskHandle {
         let store = self.storage.data.clone();
         let (handle, writer) = spawn_writer(
             dest.join(format!("subspace_{}", char::from(subspace))),
@@ -167,31 +421,67 @@ impl Core {
         (
             tokio::spawn(async move {
                 if !store.is_sql() || (subspace != SUBSPACE_COUNTER && subspace != SUBSPACE_QUOTA) {
-                    store
-                        .iterate(
-                            IterateParams::new(
-                                AnyKey {
-                                    subspace,
-                                    key: vec![0u8],
-                                },
-                                AnyKey {
-                                    subspace,
-                                    key: vec![u8::MAX; 32],
+                    let ranges = match &scope {
+                        SubspaceScope::Accounts(accounts) => accounts
+                            .iter()
+                            .map(|account_id| {
+                                let key = account_id.to_be_bytes().to_vec();
+                                let mut end_key = key.clone();
+                                end_key.extend_from_slice(&[u8::MAX; 32]);
+                                (key, end_key)
+                            })
+                            .collect::<Vec<_>>(),
+                        SubspaceScope::All
+                        | SubspaceScope::ChangesSince(_)
+                        | SubspaceScope::Owners { .. } => {
+                            vec![(vec![0u8], vec![u8::MAX; 32])]
+                        }
+                    };
+
+                    for (from_key, to_key) in ranges {
+                        store
+                            .iterate(
+                                IterateParams::new(
+                                    AnyKey {
+                                        subspace,
+                                        key: from_key,
+                                    },
+                                    AnyKey {
+                                        subspace,
+                                        key: to_key,
+                                    },
+                                )
+                                .set_values(
+                                    ![SUBSPACE_INDEXES, SUBSPACE_REGISTRY_IDX].contains(&subspace),
+                                ),
+                                |key, value| {
+                                    // Changelog entries are keyed by account id, collection and change id
+                                    if let SubspaceScope::ChangesSince(changelog) = &scope
+                                        && key.len() == U32_LEN + 1 + U64_LEN
+                                        && changelog.get(&key.deserialize_be_u32(0)?).is_some_and(
+                                            |change_id| {
+                                                key.deserialize_be_u64(U32_LEN + 1)
+                                                    .is_ok_and(|id| id <= *change_id)
+                                            },
+                                        )
+                                    {
+                                        return Ok(true);
+                                    }
+
+                                    if !scope.includes(subspace, key) {
+                                        return Ok(true);
+                                    }
+
+                                    writer
+                                        .send((key.to_vec(), value.to_vec()))
+                                        .failed("Failed to send key");
+
+                                    Ok(true)
                                 },
                             )
-                            .set_values(
-                                ![SUBSPACE_INDEXES, SUBSPACE_REGISTRY_IDX].contains(&subspace),
-                            ),
-                            |key, value| {
-                                writer
-                                    .send((key.to_vec(), value.to_vec()))
-                                    .failed("Failed to send key");
-
-                                Ok(true)
-                            },
-                        )
-                        .await
-                        .failed("Failed to iterate over data store");
+                            .await
+                            .failed("Failed to iterate over data store");
+                    }
                 } else {
                     let mut keys = Vec::with_capacity(128);
                     store
@@ -208,7 +498,9 @@ impl Core {
                             )
                             .no_values(),
                             |key, _| {
-                                keys.push(key.to_vec());
+                                if scope.includes(subspace, key) {
+                                    keys.push(key.to_vec());
+                                }
 
                                 Ok(true)
                             },
@@ -230,11 +522,79 @@ impl Core {
                     }
                 }
             }),
-            handle,
+            vec![handle],
         )
     }
 }
 
+impl SubspaceScope {
+    // Incremental backups include the keys owned by the changed accounts, the keys
+    // not owned by any account and the links to blobs missing from the backup chain.
+    fn includes(&self, subspace: u8, key: &[u8]) -> bool {
+        match self {
+            SubspaceScope::Owners {
+                accounts,
+                skip_blobs,
+            } => match key_owner(subspace, key) {
+                Some(account_id) if accounts.contains(&account_id) => true,
+                _ if subspace == SUBSPACE_BLOB_LINK => key
+                    .get(0..BLOB_HASH_LEN)
+                    .and_then(|hash| BlobHash::try_from_hash_slice(hash).ok())
+                    .is_some_and(|hash| !skip_blobs.contains(&hash)),
+                owner => owner.is_none(),
+            },
+            _ => true,
+        }
+    }
+}
+
+/// Returns the accounts whose counter advanced since the parent backup, or that
+/// no longer exist.
+fn changed_accounts<'x>(
+    parent: &'x BTreeMap<u32, u64>,
+    current: &'x BTreeMap<u32, u64>,
+) -> impl Iterator<Item = u32> + 'x {
+    current
+        .iter()
+        .filter(|(account_id, value)| {
+            parent
+                .get(*account_id)
+                .is_none_or(|parent_value| parent_value < *value)
+        })
+        .map(|(account_id, _)| *account_id)
+        .chain(
+            parent
+                .keys()
+                .filter(|account_id| !current.contains_key(*account_id))
+                .copied(),
+        )
+}
+
+/// Returns the account owning a key of the ACL, counter or blob link subspaces.
+pub(super) fn key_owner(subspace: u8, key: &[u8]) -> Option<u32> {
+    match subspace {
+        // Grantee, owner, collection and document id
+        SUBSPACE_ACL if key.len() == U32_LEN * 3 + 1 => key.deserialize_be_u32(U32_LEN).ok(),
+        // Change id, document id, revision and quota counters, tenant quotas are not account data
+        SUBSPACE_COUNTER
+            if key.len() == U32_LEN
+                || (key.len() == U32_LEN + 1 && key[U32_LEN] != u8::MAX - 1) =>
+        {
+            key.deserialize_be_u32(0)
+                .ok()
+                .filter(|account_id| *account_id != u32::MAX)
+        }
+        // Document and temporary links, followed by the account id
+        SUBSPACE_BLOB_LINK
+            if key.len() == BLOB_HASH_LEN + U32_LEN * 2 + 1
+                || key.len() == BLOB_HASH_LEN + U32_LEN + U64_LEN =>
+        {
+            key.deserialize_be_u32(BLOB_HASH_LEN).ok()
+        }
+        _ => None,
+    }
+}
+
 #[allow(clippy::type_complexity)]
 fn spawn_writer(
     path: PathBuf,
@@ -279,6 +639,25 @@ impl BackupParams {
         let mut params = Self {
             dest,
             families: AHashSet::new(),
+            mode: BackupMode::Export,
+        };
+
+        if let Ok(families) = std::env::var("EXPORT_TYPES") {
+            params.parse_families(&families);
+        }
+
+        params
+    }
+
+    pub fn incremental(dest: PathBuf, force_full: bool) -> Self {
+        let mut params = Self {
+            dest,
+            families: AHashSet::new(),
+            mode: if force_full {
+                BackupMode::Full
+            } else {
+                BackupMode::Auto
+            },
         };
 
         if let Ok(families) = std::env::var("EXPORT_TYPES") {
@@ -343,3 +722,89 @@ impl Family {
         }
     }
 }
+
+impl BackupManifest {
+    pub fn read(path: &Path) -> Self {
+        let manifest = std::fs::read(path.join(MANIFEST_FILE))
+            .failed(&format!("Failed to read backup manifest from {path:?}"));
+        serde_json::from_slice(&manifest)
+            .failed(&format!("Failed to parse backup manifest from {path:?}"))
+    }
+
+    // Returns all backups in a backup directory, oldest first
+    pub fn read_all(path: &Path) -> Vec<Self> {
+        let mut manifests = Vec::new();
+        for entry in std::fs::read_dir(path).failed("Failed to read directory") {
+            let path = entry.failed("Failed to read entry").path();
+            if path.join(MANIFEST_FILE).is_file() {
+                manifests.push(BackupManifest::read(&path));
+            }
+        }
+        manifests.sort_unstable_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        manifests
+    }
+
+    // Returns the chain of backups ending at this one, starting with its full backup
+    pub fn chain(self, path: &Path) -> Vec<Self> {
+        let mut chain = vec![self];
+        while let Some(parent) = chain.last().unwrap().parent.clone() {
+            if chain.len() > 1024 || !path.join(parent).join(MANIFEST_FILE).is_file() {
+                failed(&format!(
+                    "Backup {parent:?} required by {:?} was not found in {path:?}",
+                    chain.last().unwrap().id
+                ));
+            }
+            chain.push(BackupManifest::read(&path.join(&parent)));
+        }
+        chain.reverse();
+        chain
+    }
+
+    fn chain_blobs(path: &Path, parent: &BackupManifest) -> AHashSet<BlobHash> {
+        let mut blobs = AHashSet::new();
+        for manifest in parent.clone().chain(path) {
+            let index = path.join(&manifest.id).join(BLOB_INDEX_FILE);
+            if index.is_file() {
+                let mut reader = KeyValueReader::new(&index);
+                while let Some((key, _)) = reader.next() {
+                    if let Ok(hash) = BlobHash::try_from_hash_slice(&key) {
+                        blobs.insert(hash);
+                    }
+                }
+            }
+        }
+        blobs
+    }
+
+    pub fn verify(&self, path: &Path) -> Result<(), String> {
+        for file in &self.files {
+            let (size, sha256) = file_checksum(&path.join(&file.name));
+            if size != file.size || sha256 != file.sha256 {
+                return Err(format!(
+                    "Checksum mismatch for {:?} in backup {:?}",
+                    file.name, self.id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn file_checksum(path: &Path) -> (u64, String) {
+    let mut file = File::open(path).failed(&format!("Failed to open {path:?}"));
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let bytes_read = file
+            .read(&mut buf)
+            .failed(&format!("Failed to read {path:?}"));
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buf[..bytes_read]);
+        size += bytes_read as u64;
+    }
+    (size, hasher.finalize().hex_encode())
+}
@@ -4,7 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::{backup::BackupParams, console::store_console};
+use super::{backup::BackupParams, console::store_console, restore::RestoreParams};
 use crate::{
     BuildServer, Caches, Core, Data, IPC_CHANNEL_BUFFER, Inner, Ipc,
     config::{
@@ -47,7 +47,10 @@ Usage: stalwart [OPTIONS]
 Options:
   -c, --config <PATH>              Start server with the specified configuration file
   -e, --export <PATH>              Export all store data to a specific path
-  -i, --import <PATH>              Import store data from a specific path
+  -b, --backup <PATH>              Create an incremental backup in a backup directory
+      --backup-full <PATH>         Create a full backup in a backup directory
+  -i, --import <PATH>              Import store data or restore a backup from a specific path
+  -u, --until <TIMESTAMP>          Restore backups taken up to a point in time (RFC 3339 or UNIX time)
   -o, --console                    Open the store console
   -h, --help                       Print help
   -V, --version                    Print version
@@ -57,7 +60,7 @@ Options:
 #[derive(PartialEq, Eq)]
 enum StoreOp {
     Export(BackupParams),
-    Import(PathBuf),
+    Import(RestoreParams),
     Console,
     None,
 }
@@ -66,6 +69,7 @@ impl BootManager {
     pub async fn init() -> Self {
         let mut config_path = std::env::var("CONFIG_PATH").ok();
         let mut import_export = StoreOp::None;
+        let mut restore_until = None;
 
         if config_path.is_none() {
             let mut args = std::env::args().skip(1);
@@ -96,8 +100,29 @@ impl BootManager {
                     ("export" | "e", Some(value)) => {
                         import_export = StoreOp::Export(BackupParams::new(value.into()));
                     }
+                    ("backup" | "b", Some(value)) => {
+                        import_export =
+                            StoreOp::Export(BackupParams::incremental(value.into(), false));
+                    }
+                    ("backup-full", Some(value)) => {
+                        import_export =
+                            StoreOp::Export(BackupParams::incremental(value.into(), true));
+                    }
                     ("import" | "i", Some(value)) => {
-                        import_export = StoreOp::Import(value.into());
+                        import_export = StoreOp::Import(RestoreParams::new(value.into()));
+                    }
+                    ("until" | "u", Some(value)) => {
+                        restore_until = Some(
+                            value
+                                .parse::<u64>()
+                                .ok()
+                                .or_else(|| {
+                                    chrono::DateTime::parse_from_rfc3339(&value)
+                                        .ok()
+                                        .map(|dt| dt.timestamp() as u64)
+                                })
+                                .failed(&format!("Invalid timestamp '{value}'.")),
+                        );
                     }
                     ("console" | "o", None) => {
                         import_export = StoreOp::Console;
@@ -111,6 +136,14 @@ impl BootManager {
                 }
             }
 
+            if let Some(until) = restore_until {
+                if let StoreOp::Import(params) = import_export {
+                    import_export = StoreOp::Import(params.with_until(until));
+                } else {
+                    failed("The '--until' argument requires '--import'.");
+                }
+            }
+
             if config_path.is_none() {
                 if import_export == StoreOp::None {
                     eprintln!("{HELP}");
@@ -252,14 +285,14 @@ impl BootManager {
                     .await;
                 std::process::exit(0);
             }
-            StoreOp::Import(path) => {
+            StoreOp::Import(params) => {
                 // Enable telemetry
                 telemetry.enable(false);
 
                 // Parse settings and restore
                 Box::pin(Core::parse(&mut bootstrap, storage))
                     .await
-                    .restore(path)
+                    .restore(params)
                     .await;
                 std::process::exit(0);
             }
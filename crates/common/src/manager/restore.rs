@@ -4,8 +4,9 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::backup::MAGIC_MARKER;
+use super::backup::{BackupKind, BackupManifest, MAGIC_MARKER, MANIFEST_FILE, key_owner};
 use crate::{Core, DATABASE_SCHEMA_VERSION};
+use ahash::AHashSet;
 use lz4_flex::frame::FrameDecoder;
 use registry::schema::enums::CompressionAlgo;
 use std::{
@@ -14,16 +15,84 @@ use std::{
     path::{Path, PathBuf},
 };
 use store::{
-    BlobStore, SUBSPACE_BLOBS, SUBSPACE_COUNTER, SUBSPACE_INDEXES, SUBSPACE_QUOTA, Store, U32_LEN,
-    write::{AnyClass, BatchBuilder, ValueClass, key::DeserializeBigEndian},
+    BlobStore, IterateParams, SUBSPACE_ACL, SUBSPACE_BLOB_LINK, SUBSPACE_BLOBS, SUBSPACE_COUNTER,
+    SUBSPACE_INDEXES, SUBSPACE_LOGS, SUBSPACE_PROPERTY, SUBSPACE_QUOTA, Store, U32_LEN,
+    write::{AnyClass, AnyKey, BatchBuilder, ValueClass, key::DeserializeBigEndian},
 };
 use types::{collection::Collection, field::Field};
 use utils::{UnwrapFailure, failed};
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct RestoreParams {
+    src: PathBuf,
+    until: Option<u64>,
+}
+
+impl RestoreParams {
+    pub fn new(src: PathBuf) -> Self {
+        Self { src, until: None }
+    }
+
+    pub fn with_until(mut self, until: u64) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Verifies the checksums of every backup that would be restored.
+    pub fn verify(&self) -> Result<(), String> {
+        self.backup_chain().map(|_| ())
+    }
+
+    // Returns the backups to restore, oldest first
+    fn backup_chain(&self) -> Result<Vec<(PathBuf, BackupManifest)>, String> {
+        let src = &self.src;
+
+        if src.join(MANIFEST_FILE).is_file() {
+            // Single backup with a manifest
+            let manifest = BackupManifest::read(src);
+            if manifest.kind == BackupKind::Incremental {
+                return Err(format!(
+                    "{src:?} is an incremental backup, restore its parent directory instead"
+                ));
+            }
+            manifest.verify(src)?;
+            Ok(vec![(src.clone(), manifest)])
+        } else {
+            // Backup directory, restore the latest chain up to the requested point in time
+            let until = self.until.unwrap_or(u64::MAX);
+            let manifest = BackupManifest::read_all(src)
+                .into_iter()
+                .rev()
+                .find(|manifest| manifest.created_at <= until)
+                .ok_or_else(|| {
+                    "No backup found matching the requested point in time".to_string()
+                })?;
+            manifest
+                .chain(src)
+                .into_iter()
+                .map(|manifest| {
+                    let path = src.join(&manifest.id);
+                    manifest.verify(&path)?;
+                    Ok((path, manifest))
+                })
+                .collect()
+        }
+    }
+}
+
 impl Core {
-    pub async fn restore(&self, src: PathBuf) {
-        // Backup the core
-        if src.is_dir() {
+    pub async fn restore(&self, params: RestoreParams) {
+        let src = params.src.clone();
+
+        if src.join(MANIFEST_FILE).is_file()
+            || (src.is_dir() && !BackupManifest::read_all(&src).is_empty())
+        {
+            let chain = params.backup_chain().unwrap_or_else(|err| failed(&err));
+            for (path, manifest) in &chain {
+                println!("Restoring backup {}.", manifest.id);
+                self.restore_backup(path, manifest).await;
+            }
+        } else if src.is_dir() {
             // Iterate directory and spawn a task for each file
             let mut tasks = Vec::new();
             for entry in std::fs::read_dir(&src).failed("Failed to read directory") {
@@ -45,6 +114,123 @@ impl Core {
             restore_file(self.storage.data.clone(), self.storage.blob.clone(), &src).await;
         }
     }
+
+    async fn restore_backup(&self, src: &Path, manifest: &BackupManifest) {
+        let store = &self.storage.data;
+
+        if manifest.kind == BackupKind::Incremental {
+            // Incremental backups contain the full contents of the changed accounts
+            // and of all server-wide subspaces, discard the previous versions first.
+            let accounts = manifest.accounts.iter().copied().collect::<AHashSet<_>>();
+            for file in &manifest.files {
+                match file.subspace {
+                    Some(SUBSPACE_PROPERTY | SUBSPACE_INDEXES) => {
+                        for account_id in &manifest.accounts {
+                            let mut end_key = account_id.to_be_bytes().to_vec();
+                            end_key.extend_from_slice(&[u8::MAX; 32]);
+                            store
+                                .delete_range(
+                                    AnyKey {
+                                        subspace: file.subspace.unwrap(),
+                                        key: account_id.to_be_bytes().to_vec(),
+                                    },
+                                    AnyKey {
+                                        subspace: file.subspace.unwrap(),
+                                        key: end_key,
+                                    },
+                                )
+                                .await
+                                .failed("Failed to delete account data");
+                        }
+                    }
+                    Some(subspace @ (SUBSPACE_ACL | SUBSPACE_COUNTER | SUBSPACE_BLOB_LINK)) => {
+                        delete_owned_keys(store, subspace, &accounts).await;
+                    }
+                    Some(SUBSPACE_LOGS | SUBSPACE_BLOBS) | None => {}
+                    Some(subspace) => {
+                        store
+                            .delete_range(
+                                AnyKey {
+                                    subspace,
+                                    key: vec![0u8],
+                                },
+                                AnyKey {
+                                    subspace,
+                                    key: vec![u8::MAX; 32],
+                                },
+                            )
+                            .await
+                            .failed("Failed to delete subspace");
+                    }
+                }
+            }
+        }
+
+        let mut tasks = Vec::new();
+        for file in &manifest.files {
+            if file.subspace.is_some() {
+                let store = store.clone();
+                let blob_store = self.storage.blob.clone();
+                let path = src.join(&file.name);
+                tasks.push(tokio::spawn(async move {
+                    restore_file(store, blob_store, &path).await;
+                }));
+            }
+        }
+
+        for task in tasks {
+            task.await.failed("Failed to wait for task");
+        }
+    }
+}
+
+// Removes the keys owned by the changed accounts, counters not owned by any
+// account are included in every incremental backup and are removed as well.
+async fn delete_owned_keys(store: &Store, subspace: u8, accounts: &AHashSet<u32>) {
+    let mut keys = Vec::new();
+    store
+        .iterate(
+            IterateParams::new(
+                AnyKey {
+                    subspace,
+                    key: vec![0u8],
+                },
+                AnyKey {
+                    subspace,
+                    key: vec![u8::MAX; 32],
+                },
+            )
+            .no_values(),
+            |key, _| {
+                if key_owner(subspace, key).map_or(subspace == SUBSPACE_COUNTER, |account_id| {
+                    accounts.contains(&account_id)
+                }) {
+                    keys.push(key.to_vec());
+                }
+
+                Ok(true)
+            },
+        )
+        .await
+        .failed("Failed to iterate over data store");
+
+    let mut batch = BatchBuilder::new();
+    for key in keys {
+        batch.clear(ValueClass::Any(AnyClass { subspace, key }));
+        if batch.is_large_batch() {
+            store
+                .write(batch.build_all())
+                .await
+                .failed("Failed to write batch");
+            batch = BatchBuilder::new();
+        }
+    }
+    if !batch.is_empty() {
+        store
+            .write(batch.build_all())
+            .await
+            .failed("Failed to write batch");
+    }
 }
 
 async fn restore_file(store: Store, blob_store: BlobStore, path: &Path) {
@@ -144,13 +330,13 @@ async fn restore_file(store: Store, blob_store: BlobStore, path: &Path) {
     }
 }
 
-struct KeyValueReader {
-    subspace: u8,
+pub(super) struct KeyValueReader {
+    pub subspace: u8,
     file: FrameDecoder<BufReader<File>>,
 }
 
 impl KeyValueReader {
-    fn new(path: &Path) -> Self {
+    pub fn new(path: &Path) -> Self {
         let mut file = FrameDecoder::new(BufReader::new(
             File::open(path).failed("Failed to open file"),
         ));
@@ -180,7 +366,7 @@ impl KeyValueReader {
         Self { file, subspace }
     }
 
-    fn next(&mut self) -> Option<(Vec<u8>, Vec<u8>)> {
+    pub fn next(&mut self) -> Option<(Vec<u8>, Vec<u8>)> {
         let size = self.read_size()?;
 
         let mut key = vec![0; size as usize];
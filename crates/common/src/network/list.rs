@@ -0,0 +1,118 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{Server, auth::EmailCache};
+use trc::AddContext;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListCommand {
+    Subscribe,
+    Unsubscribe,
+    Help,
+    Bounces(Option<String>),
+    Confirm(String),
+    Approve(String),
+    Reject(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListRecipient {
+    pub list_id: u32,
+    pub command: Option<ListCommand>,
+}
+
+impl ListCommand {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "subscribe" | "join" => Some(ListCommand::Subscribe),
+            "unsubscribe" | "leave" => Some(ListCommand::Unsubscribe),
+            "help" | "request" => Some(ListCommand::Help),
+            "bounces" => Some(ListCommand::Bounces(None)),
+            _ => {
+                let (command, token) = value.split_once('-')?;
+                if token.is_empty() || !token.bytes().all(|ch| ch.is_ascii_alphanumeric()) {
+                    return None;
+                }
+                match command {
+                    "bounces" => Some(ListCommand::Bounces(Some(token.to_string()))),
+                    "confirm" => Some(ListCommand::Confirm(token.to_string())),
+                    "approve" => Some(ListCommand::Approve(token.to_string())),
+                    "reject" => Some(ListCommand::Reject(token.to_string())),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListCommand::Subscribe => "subscribe",
+            ListCommand::Unsubscribe => "unsubscribe",
+            ListCommand::Help => "help",
+            ListCommand::Bounces(_) => "bounces",
+            ListCommand::Confirm(_) => "confirm",
+            ListCommand::Approve(_) => "approve",
+            ListCommand::Reject(_) => "reject",
+        }
+    }
+}
+
+impl Server {
+    /// Resolves an address to a managed mailing list, either its posting
+    /// address or one of its `name+command@domain` addresses.
+    pub async fn list_recipient(&self, address: &str) -> trc::Result<Option<ListRecipient>> {
+        let Some((local_part, domain_part)) = address.rsplit_once('@') else {
+            return Ok(None);
+        };
+        let Some(domain) = self.domain(domain_part).await.caused_by(trc::location!())? else {
+            return Ok(None);
+        };
+
+        if let Some(EmailCache::MailingList(list_id)) = self
+            .rcpt_id_from_parts(local_part, domain.id)
+            .await
+            .caused_by(trc::location!())?
+            && self
+                .try_list(list_id)
+                .await
+                .caused_by(trc::location!())?
+                .is_some_and(|list| list.is_managed)
+        {
+            Ok(Some(ListRecipient {
+                list_id,
+                command: None,
+            }))
+        } else {
+            self.list_command_from_parts(local_part, domain.id).await
+        }
+    }
+
+    pub async fn list_command_from_parts(
+        &self,
+        local_part: &str,
+        domain_id: u32,
+    ) -> trc::Result<Option<ListRecipient>> {
+        if let Some((list_name, command)) = local_part.split_once('+')
+            && let Some(command) = ListCommand::parse(command)
+            && let Some(EmailCache::MailingList(list_id)) = self
+                .rcpt_id_from_parts(list_name, domain_id)
+                .await
+                .caused_by(trc::location!())?
+            && self
+                .try_list(list_id)
+                .await
+                .caused_by(trc::location!())?
+                .is_some_and(|list| list.is_managed)
+        {
+            Ok(Some(ListRecipient {
+                list_id,
+                command: Some(command),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
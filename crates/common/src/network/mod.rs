@@ -10,8 +10,13 @@ use crate::{
     config::server::ServerProtocol,
     expr::{functions::ResolveVariable, *},
 };
+use ahash::AHashMap;
 use compact_str::ToCompactString;
-use registry::{schema::enums::ExpressionVariable, types::ipmask::IpAddrOrMask};
+use directory::core::scram::ChannelBindings;
+use registry::{
+    schema::enums::{ExpressionVariable, TlsClientCertMapping},
+    types::ipmask::IpAddrOrMask,
+};
 use rustls::ServerConfig;
 use std::fmt::Debug;
 use std::{borrow::Cow, net::IpAddr, sync::Arc, time::Instant};
@@ -29,6 +34,7 @@ pub mod autoconfig;
 pub mod dkim;
 pub mod dns;
 pub mod limiter;
+pub mod list;
 pub mod listen;
 pub mod mta;
 pub mod security;
@@ -62,11 +68,17 @@ pub enum TcpAcceptor {
         config: Arc<ServerConfig>,
         acceptor: TlsAcceptor,
         implicit: bool,
+        client_cert_mapping: Option<Arc<ClientCertMapping>>,
     },
     #[default]
     Plain,
 }
 
+pub struct ClientCertMapping {
+    pub mapping: TlsClientCertMapping,
+    pub fingerprints: AHashMap<String, String>,
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum TcpAcceptorResult<IO>
 where
@@ -92,6 +104,8 @@ pub struct SessionData<T: SessionStream> {
 pub trait SessionStream: AsyncRead + AsyncWrite + Unpin + 'static + Sync + Send {
     fn is_tls(&self) -> bool;
     fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>);
+    fn channel_bindings(&self, server: &Server) -> ChannelBindings;
+    fn peer_certificate(&self) -> Option<&[u8]>;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -99,6 +113,7 @@ pub enum SessionResult {
     Continue,
     Close,
     UpgradeTls,
+    UpgradeCompress,
 }
 
 pub trait SessionManager: Sync + Send + 'static + Clone {
@@ -159,7 +159,12 @@ impl Server {
                 }
                 EmailCache::MailingList(id) => {
                     if let Some(list) = self.try_list(id).await? {
-                        return Ok(RcptResolution::Expand(list.recipients.clone()));
+                        if !list.is_managed {
+                            return Ok(RcptResolution::Expand(list.recipients.clone()));
+                        } else if local_part.as_ref() == local_part_orig {
+                            // Managed lists are processed at delivery time
+                            return Ok(RcptResolution::Accept);
+                        }
                     } else {
                         self.inner
                             .cache
@@ -171,6 +176,15 @@ impl Server {
             }
         }
 
+        // Mailing list command addresses
+        if self
+            .list_command_from_parts(local_part_orig, domain.id)
+            .await?
+            .is_some()
+        {
+            return Ok(RcptResolution::Accept);
+        }
+
         // Catch-all resolution
         if let Some(catch_all) = &domain.catch_all {
             return Ok(RcptResolution::Rewrite(catch_all.to_string()));
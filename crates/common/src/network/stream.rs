@@ -4,16 +4,23 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
 
+use directory::core::scram::ChannelBindings;
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress};
 use proxy_header::io::ProxiedStream;
 use tokio::{
-    io::{AsyncRead, AsyncWrite},
+    io::{AsyncRead, AsyncWrite, ReadBuf},
     net::TcpStream,
 };
 use tokio_rustls::server::TlsStream;
 
 use super::SessionStream;
+use crate::Server;
 
 impl SessionStream for TcpStream {
     fn is_tls(&self) -> bool {
@@ -23,6 +30,14 @@ impl SessionStream for TcpStream {
     fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>) {
         (Cow::Borrowed(""), Cow::Borrowed(""))
     }
+
+    fn channel_bindings(&self, _: &Server) -> ChannelBindings {
+        ChannelBindings::default()
+    }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
 }
 
 impl<T: SessionStream> SessionStream for TlsStream<T> {
@@ -62,6 +77,30 @@ impl<T: SessionStream> SessionStream for TlsStream<T> {
             .into(),
         )
     }
+
+    fn channel_bindings(&self, server: &Server) -> ChannelBindings {
+        let (_, conn) = self.get_ref();
+
+        ChannelBindings {
+            tls_server_end_point: server.tls_server_end_point(conn.server_name()),
+            // RFC 9266 defines tls-exporter for TLS 1.3 only
+            tls_exporter: if conn.protocol_version() == Some(rustls::ProtocolVersion::TLSv1_3) {
+                conn.export_keying_material([0u8; 32], b"EXPORTER-Channel-Binding", None)
+                    .ok()
+                    .map(|material| material.to_vec())
+            } else {
+                None
+            },
+        }
+    }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        let (_, conn) = self.get_ref();
+
+        conn.peer_certificates()
+            .and_then(|certs| certs.first())
+            .map(|cert| cert.as_ref())
+    }
 }
 
 impl SessionStream for ProxiedStream<TcpStream> {
@@ -82,6 +121,190 @@ impl SessionStream for ProxiedStream<TcpStream> {
             })
             .unwrap_or((Cow::Borrowed("unknown"), Cow::Borrowed("unknown")))
     }
+
+    fn channel_bindings(&self, _: &Server) -> ChannelBindings {
+        // TLS is terminated by the proxy
+        ChannelBindings::default()
+    }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
+pub struct DeflateStream<T: SessionStream> {
+    inner: T,
+    compress: Compress,
+    decompress: Decompress,
+    rx_buf: Vec<u8>,
+    rx_pos: usize,
+    rx_len: usize,
+    tx_buf: Vec<u8>,
+    tx_pos: usize,
+    tx_pending_flush: bool,
+}
+
+impl<T: SessionStream> DeflateStream<T> {
+    pub fn new(inner: T) -> Self {
+        // RFC 4978 uses raw deflate without zlib headers
+        DeflateStream {
+            inner,
+            compress: Compress::new(Compression::default(), false),
+            decompress: Decompress::new(false),
+            rx_buf: vec![0; 8192],
+            rx_pos: 0,
+            rx_len: 0,
+            tx_buf: Vec::with_capacity(8192),
+            tx_pos: 0,
+            tx_pending_flush: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    fn deflate(&mut self, mut input: &[u8], flush: FlushCompress) -> std::io::Result<()> {
+        loop {
+            if self.tx_buf.capacity() - self.tx_buf.len() < 64 {
+                self.tx_buf.reserve(input.len().max(1024));
+            }
+            let total_in = self.compress.total_in();
+            self.compress
+                .compress_vec(input, &mut self.tx_buf, flush)
+                .map_err(std::io::Error::other)?;
+            input = &input[(self.compress.total_in() - total_in) as usize..];
+
+            // Output space left over means all pending data was compressed
+            if input.is_empty() && self.tx_buf.len() < self.tx_buf.capacity() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while self.tx_pos < self.tx_buf.len() {
+            let bytes_written =
+                ready!(Pin::new(&mut self.inner).poll_write(cx, &self.tx_buf[self.tx_pos..]))?;
+            if bytes_written == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()));
+            }
+            self.tx_pos += bytes_written;
+        }
+        self.tx_buf.clear();
+        self.tx_pos = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: SessionStream> AsyncRead for DeflateStream<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = &mut *self;
+
+        loop {
+            if buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+
+            // Inflate any buffered input, the peer sync flushes after each command
+            let (total_in, total_out) = (this.decompress.total_in(), this.decompress.total_out());
+            this.decompress
+                .decompress(
+                    &this.rx_buf[this.rx_pos..this.rx_len],
+                    buf.initialize_unfilled(),
+                    FlushDecompress::None,
+                )
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            let bytes_in = (this.decompress.total_in() - total_in) as usize;
+            let bytes_out = (this.decompress.total_out() - total_out) as usize;
+            this.rx_pos += bytes_in;
+            if bytes_out > 0 {
+                buf.advance(bytes_out);
+                return Poll::Ready(Ok(()));
+            } else if bytes_in > 0 && this.rx_pos < this.rx_len {
+                continue;
+            }
+
+            // Read more compressed data
+            this.rx_buf.copy_within(this.rx_pos..this.rx_len, 0);
+            this.rx_len -= this.rx_pos;
+            this.rx_pos = 0;
+            if this.rx_len == this.rx_buf.len() {
+                return Poll::Ready(Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Invalid deflate stream",
+                )));
+            }
+            let mut read_buf = ReadBuf::new(&mut this.rx_buf[this.rx_len..]);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut read_buf))?;
+            let bytes_read = read_buf.filled().len();
+            if bytes_read == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            this.rx_len += bytes_read;
+        }
+    }
+}
+
+impl<T: SessionStream> AsyncWrite for DeflateStream<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        let this = &mut *self;
+        ready!(this.poll_write_pending(cx))?;
+        this.deflate(buf, FlushCompress::None)?;
+        this.tx_pending_flush = true;
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        let this = &mut *self;
+
+        // Sync flush so the peer can decode everything written so far
+        if this.tx_pending_flush {
+            this.deflate(&[], FlushCompress::Sync)?;
+            this.tx_pending_flush = false;
+        }
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        ready!(self.as_mut().poll_flush(cx))?;
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+impl<T: SessionStream> SessionStream for DeflateStream<T> {
+    fn is_tls(&self) -> bool {
+        self.inner.is_tls()
+    }
+
+    fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>) {
+        self.inner.tls_version_and_cipher()
+    }
+
+    fn channel_bindings(&self, server: &Server) -> ChannelBindings {
+        self.inner.channel_bindings(server)
+    }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        self.inner.peer_certificate()
+    }
 }
 
 #[derive(Default)]
@@ -140,4 +363,12 @@ impl SessionStream for NullIo {
             std::borrow::Cow::Borrowed(""),
         )
     }
+
+    fn channel_bindings(&self, _: &Server) -> ChannelBindings {
+        ChannelBindings::default()
+    }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
 }
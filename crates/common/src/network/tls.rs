@@ -5,10 +5,11 @@
  */
 
 use super::{
-    ServerInstance, SessionStream, TcpAcceptor, TcpAcceptorResult,
+    ClientCertMapping, ServerInstance, SessionStream, TcpAcceptor, TcpAcceptorResult,
     acme::resolver::{IsTlsAlpnChallenge, build_acme_static_resolver},
 };
 use crate::{Inner, Server};
+use aws_lc_rs::digest;
 use rustls::{
     SupportedProtocolVersion,
     server::{ClientHello, ResolvesServerCert},
@@ -22,6 +23,13 @@ use std::{
 };
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio_rustls::{Accept, LazyConfigAcceptor};
+use x509_parser::{
+    oid_registry::{
+        OID_PKCS1_SHA384WITHRSA, OID_PKCS1_SHA512WITHRSA, OID_SIG_ECDSA_WITH_SHA384,
+        OID_SIG_ECDSA_WITH_SHA512,
+    },
+    parse_x509_certificate,
+};
 
 pub static TLS13_VERSION: &[&SupportedProtocolVersion] = &[&TLS13];
 pub static TLS12_VERSION: &[&SupportedProtocolVersion] = &[&TLS12];
@@ -56,6 +64,25 @@ impl Server {
             })
             .cloned()
     }
+
+    pub fn tls_server_end_point(&self, server_name: Option<&str>) -> Option<Vec<u8>> {
+        let key = CertificateResolver::new(self.inner.clone()).resolve_certificate(server_name)?;
+        let cert = key.cert.first()?;
+        let (_, parsed) = parse_x509_certificate(cert.as_ref()).ok()?;
+
+        // RFC 5929 uses the certificate signature hash, with MD5 and SHA-1 upgraded to SHA-256
+        let sig_alg = parsed.signature_algorithm.oid();
+        let algorithm =
+            if sig_alg == &OID_PKCS1_SHA512WITHRSA || sig_alg == &OID_SIG_ECDSA_WITH_SHA512 {
+                &digest::SHA512
+            } else if sig_alg == &OID_PKCS1_SHA384WITHRSA || sig_alg == &OID_SIG_ECDSA_WITH_SHA384 {
+                &digest::SHA384
+            } else {
+                &digest::SHA256
+            };
+
+        Some(digest::digest(algorithm, cert.as_ref()).as_ref().to_vec())
+    }
 }
 
 impl CertificateResolver {
@@ -117,6 +144,7 @@ impl TcpAcceptor {
                 config,
                 acceptor,
                 implicit,
+                ..
             } if *implicit => match enable_acme {
                 None => TcpAcceptorResult::Tls(acceptor.accept(stream)),
                 Some(core) => {
@@ -193,6 +221,16 @@ impl TcpAcceptor {
     pub fn is_tls(&self) -> bool {
         matches!(self, TcpAcceptor::Tls { .. })
     }
+
+    pub fn client_cert_mapping(&self) -> Option<&ClientCertMapping> {
+        match self {
+            TcpAcceptor::Tls {
+                client_cert_mapping,
+                ..
+            } => client_cert_mapping.as_deref(),
+            TcpAcceptor::Plain => None,
+        }
+    }
 }
 
 impl<IO> TcpAcceptorResult<IO>
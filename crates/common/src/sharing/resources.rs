@@ -144,6 +144,17 @@ impl DavResources {
         })
     }
 
+    pub fn task_ids(&self, is_container: bool) -> impl Iterator<Item = u32> {
+        self.resources.iter().filter_map(move |resource| {
+            if (is_container && resource.supports_tasks()) || (!is_container && resource.is_task())
+            {
+                Some(resource.document_id)
+            } else {
+                None
+            }
+        })
+    }
+
     pub fn has_container_id(&self, id: &u32) -> bool {
         self.resources
             .iter()
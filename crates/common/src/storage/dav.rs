@@ -30,6 +30,20 @@ impl DavResourcePath<'_> {
         self.path.path.as_str()
     }
 
+    pub fn is_task(&self) -> bool {
+        match &self.data {
+            DavResourceMetadata::CalendarEvent { is_task, .. } => *is_task,
+            _ => false,
+        }
+    }
+
+    pub fn supports_tasks(&self) -> bool {
+        match &self.data {
+            DavResourceMetadata::Calendar { supports_tasks, .. } => *supports_tasks,
+            _ => false,
+        }
+    }
+
     #[inline(always)]
     pub fn is_container(&self) -> bool {
         self.resource.is_container()
@@ -0,0 +1,470 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{KV_EXTRACT_TEXT, Server};
+use quick_xml::{Reader, XmlVersion, events::Event};
+use std::{
+    io::{Cursor, Read},
+    time::Instant,
+};
+use store::dispatch::lookup::KeyValue;
+use trc::{AddContext, MessageIngestEvent};
+use types::blob_hash::BlobHash;
+use zip::{ZipArchive, result::ZipError};
+
+pub mod odf;
+pub mod ooxml;
+pub mod pdf;
+
+pub const MAX_EXTRACTED_TEXT_LEN: usize = 2 * 1024 * 1024;
+pub const MAX_ARCHIVE_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+pub trait TextExtractor: Sync + Send {
+    fn extract(&self, data: &[u8], text: &mut ExtractedText) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentType {
+    Pdf,
+    Docx,
+    Xlsx,
+    Pptx,
+    Odt,
+    Ods,
+    Odp,
+}
+
+pub struct ExtractedText {
+    text: String,
+    deadline: Instant,
+}
+
+impl Server {
+    pub async fn extract_text(
+        &self,
+        content_type: Option<&str>,
+        file_name: Option<&str>,
+        data: &[u8],
+    ) -> Option<String> {
+        let config = &self.core.email.extract;
+        if !config.enable || data.is_empty() || data.len() > config.max_size {
+            return None;
+        }
+        let document_type = DocumentType::detect(content_type, file_name, data)?;
+
+        // Previously extracted documents are served from the cache
+        let key = KeyValue::<()>::build_key(KV_EXTRACT_TEXT, BlobHash::generate(data).as_slice());
+        match self
+            .in_memory_store()
+            .key_get::<String>(key.clone())
+            .await
+            .caused_by(trc::location!())
+        {
+            Ok(Some(text)) => {
+                return Some(text).filter(|text| !text.is_empty());
+            }
+            Ok(None) => {}
+            Err(err) => {
+                trc::error!(err.details("Failed to fetch extracted text from cache"));
+            }
+        }
+
+        // Extractors stop shortly before the timeout so partial results can still be indexed
+        let started = Instant::now();
+        let deadline = started + config.timeout.mul_f64(0.9);
+        let contents = data.to_vec();
+        let text = match tokio::time::timeout(
+            config.timeout,
+            tokio::task::spawn_blocking(move || document_type.extract(&contents, deadline)),
+        )
+        .await
+        {
+            Ok(Ok(Ok(text))) => {
+                trc::event!(
+                    MessageIngest(MessageIngestEvent::TextExtracted),
+                    Type = document_type.as_str(),
+                    Size = data.len(),
+                    Total = text.len(),
+                    Elapsed = started.elapsed(),
+                );
+
+                text
+            }
+            Ok(Ok(Err(reason))) => {
+                trc::event!(
+                    MessageIngest(MessageIngestEvent::TextExtractFailed),
+                    Type = document_type.as_str(),
+                    Size = data.len(),
+                    Reason = reason,
+                );
+
+                String::new()
+            }
+            Ok(Err(err)) => {
+                trc::event!(
+                    MessageIngest(MessageIngestEvent::TextExtractFailed),
+                    Type = document_type.as_str(),
+                    Size = data.len(),
+                    Reason = err.to_string(),
+                );
+
+                String::new()
+            }
+            Err(_) => {
+                trc::event!(
+                    MessageIngest(MessageIngestEvent::TextExtractFailed),
+                    Type = document_type.as_str(),
+                    Size = data.len(),
+                    Reason = "Extraction timed out",
+                    Elapsed = started.elapsed(),
+                );
+
+                String::new()
+            }
+        };
+
+        // Empty results are cached as well to avoid retrying documents that cannot be parsed
+        if let Err(err) = self
+            .in_memory_store()
+            .key_set(KeyValue::new(key, text.as_bytes().to_vec()).expires(config.cache_ttl))
+            .await
+        {
+            trc::error!(
+                err.caused_by(trc::location!())
+                    .details("Failed to store extracted text in cache")
+            );
+        }
+
+        Some(text).filter(|text| !text.is_empty())
+    }
+}
+
+impl DocumentType {
+    pub fn detect(
+        content_type: Option<&str>,
+        file_name: Option<&str>,
+        data: &[u8],
+    ) -> Option<Self> {
+        content_type
+            .and_then(Self::from_content_type)
+            .or_else(|| {
+                file_name
+                    .and_then(|name| name.rsplit_once('.'))
+                    .and_then(|(_, ext)| Self::from_extension(ext))
+            })
+            .or_else(|| infer::get(data).and_then(|t| Self::from_content_type(t.mime_type())))
+    }
+
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        hashify::tiny_map_ignore_case!(content_type.as_bytes(),
+            "application/pdf" => DocumentType::Pdf,
+            "application/x-pdf" => DocumentType::Pdf,
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => DocumentType::Docx,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => DocumentType::Xlsx,
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation" => DocumentType::Pptx,
+            "application/vnd.oasis.opendocument.text" => DocumentType::Odt,
+            "application/vnd.oasis.opendocument.spreadsheet" => DocumentType::Ods,
+            "application/vnd.oasis.opendocument.presentation" => DocumentType::Odp,
+        )
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        hashify::tiny_map_ignore_case!(extension.as_bytes(),
+            "pdf" => DocumentType::Pdf,
+            "docx" => DocumentType::Docx,
+            "xlsx" => DocumentType::Xlsx,
+            "pptx" => DocumentType::Pptx,
+            "odt" => DocumentType::Odt,
+            "ods" => DocumentType::Ods,
+            "odp" => DocumentType::Odp,
+        )
+    }
+
+    pub fn extractor(&self) -> &'static dyn TextExtractor {
+        match self {
+            DocumentType::Pdf => &pdf::PdfExtractor,
+            DocumentType::Docx => &ooxml::OoxmlExtractor::WORD,
+            DocumentType::Xlsx => &ooxml::OoxmlExtractor::SPREADSHEET,
+            DocumentType::Pptx => &ooxml::OoxmlExtractor::PRESENTATION,
+            DocumentType::Odt | DocumentType::Ods | DocumentType::Odp => &odf::OdfExtractor,
+        }
+    }
+
+    pub fn extract(&self, data: &[u8], deadline: Instant) -> Result<String, String> {
+        let mut text = ExtractedText::new(deadline);
+        self.extractor().extract(data, &mut text)?;
+        Ok(text.into_inner())
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentType::Pdf => "pdf",
+            DocumentType::Docx => "docx",
+            DocumentType::Xlsx => "xlsx",
+            DocumentType::Pptx => "pptx",
+            DocumentType::Odt => "odt",
+            DocumentType::Ods => "ods",
+            DocumentType::Odp => "odp",
+        }
+    }
+}
+
+impl ExtractedText {
+    pub fn new(deadline: Instant) -> Self {
+        Self {
+            text: String::new(),
+            deadline,
+        }
+    }
+
+    pub fn push(&mut self, text: &str) -> bool {
+        let text = text.trim_matches(|c: char| c.is_control() && c != '\t');
+        if !text.is_empty() {
+            let remaining = MAX_EXTRACTED_TEXT_LEN.saturating_sub(self.text.len());
+            if text.len() <= remaining {
+                self.text.push_str(text);
+            } else {
+                let mut end = remaining;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                self.text.push_str(&text[..end]);
+            }
+        }
+        self.can_continue()
+    }
+
+    pub fn push_separator(&mut self, separator: char) {
+        if self
+            .text
+            .chars()
+            .next_back()
+            .is_some_and(|ch| ch != separator && ch != '\n')
+        {
+            self.text.push(separator);
+        }
+    }
+
+    pub fn can_continue(&self) -> bool {
+        self.text.len() < MAX_EXTRACTED_TEXT_LEN && Instant::now() < self.deadline
+    }
+
+    pub fn into_inner(self) -> String {
+        self.text
+    }
+}
+
+pub(crate) fn read_archive_entry(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Option<Vec<u8>>, String> {
+    match archive.by_name(name) {
+        Ok(file) => {
+            let mut contents = Vec::with_capacity(file.size().min(MAX_ARCHIVE_ENTRY_SIZE) as usize);
+            file.take(MAX_ARCHIVE_ENTRY_SIZE)
+                .read_to_end(&mut contents)
+                .map_err(|err| format!("Failed to read {name}: {err}"))?;
+            Ok(Some(contents))
+        }
+        Err(ZipError::FileNotFound) => Ok(None),
+        Err(err) => Err(format!("Failed to read {name}: {err}")),
+    }
+}
+
+// Appends the text enclosed in any of `text_elements`, separating the
+// contents of `block_elements` with line breaks.
+pub(crate) fn extract_xml_text(
+    xml: &[u8],
+    text_elements: &[&[u8]],
+    block_elements: &[&[u8]],
+    text: &mut ExtractedText,
+) -> Result<(), String> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::with_capacity(128);
+    let mut depth = 0usize;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(tag)) => {
+                let name = tag.local_name();
+                if text_elements.contains(&name.as_ref()) {
+                    depth += 1;
+                }
+            }
+            Ok(Event::End(tag)) => {
+                let name = tag.local_name();
+                if text_elements.contains(&name.as_ref()) {
+                    depth = depth.saturating_sub(1);
+                }
+                if block_elements.contains(&name.as_ref()) {
+                    text.push_separator('\n');
+                }
+            }
+            Ok(Event::Empty(tag)) => match tag.local_name().as_ref() {
+                b"tab" | b"s" => {
+                    text.push_separator(' ');
+                }
+                b"br" | b"cr" | b"line-break" => {
+                    text.push_separator('\n');
+                }
+                _ => {}
+            },
+            Ok(Event::Text(value)) if depth > 0 => {
+                if let Ok(value) = value.xml_content(XmlVersion::Implicit1_0)
+                    && !text.push(&value)
+                {
+                    break;
+                }
+            }
+            Ok(Event::CData(value)) if depth > 0 => {
+                if !text.push(&String::from_utf8_lossy(&value)) {
+                    break;
+                }
+            }
+            Ok(Event::GeneralRef(entity)) if depth > 0 => {
+                let value = match entity.as_ref() {
+                    b"lt" => "<".to_string(),
+                    b"gt" => ">".to_string(),
+                    b"amp" => "&".to_string(),
+                    b"apos" => "'".to_string(),
+                    b"quot" => "\"".to_string(),
+                    _ => match entity.resolve_char_ref() {
+                        Ok(Some(ch)) => ch.to_string(),
+                        _ => continue,
+                    },
+                };
+                if !text.push(&value) {
+                    break;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(err) => {
+                return Err(format!(
+                    "Failed to parse XML at position {}: {err}",
+                    reader.buffer_position()
+                ));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DocumentType;
+    use std::{
+        io::{Cursor, Write},
+        time::{Duration, Instant},
+    };
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    fn build_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn detect_document_type() {
+        for (content_type, file_name, expected) in [
+            (Some("application/pdf"), None, Some(DocumentType::Pdf)),
+            (
+                Some("application/octet-stream"),
+                Some("Invoice 2024.DOCX"),
+                Some(DocumentType::Docx),
+            ),
+            (None, Some("budget.xlsx"), Some(DocumentType::Xlsx)),
+            (
+                Some("application/vnd.oasis.opendocument.text"),
+                Some("contract.bin"),
+                Some(DocumentType::Odt),
+            ),
+            (Some("image/png"), Some("image.png"), None),
+        ] {
+            assert_eq!(
+                DocumentType::detect(content_type, file_name, b"not a document"),
+                expected,
+                "{content_type:?} {file_name:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn extract_office_documents() {
+        let deadline = Instant::now() + Duration::from_secs(30);
+
+        let docx = build_zip(&[
+            ("[Content_Types].xml", "<Types/>"),
+            (
+                "word/document.xml",
+                concat!(
+                    "<w:document xmlns:w=\"urn:w\"><w:body>",
+                    "<w:p><w:r><w:t>Invoice</w:t></w:r><w:r><w:tab/><w:t>#1234</w:t></w:r></w:p>",
+                    "<w:p><w:r><w:instrText>PAGE</w:instrText><w:t>Total &amp; taxes</w:t></w:r></w:p>",
+                    "</w:body></w:document>"
+                ),
+            ),
+            (
+                "word/footer1.xml",
+                "<w:ftr xmlns:w=\"urn:w\"><w:p><w:r><w:t>Confidential</w:t></w:r></w:p></w:ftr>",
+            ),
+        ]);
+        assert_eq!(
+            DocumentType::Docx.extract(&docx, deadline).unwrap(),
+            "Invoice #1234\nTotal & taxes\nConfidential\n"
+        );
+
+        let xlsx = build_zip(&[(
+            "xl/sharedStrings.xml",
+            "<sst><si><t>Quarterly</t></si><si><r><t>Revenue</t></r></si></sst>",
+        )]);
+        assert_eq!(
+            DocumentType::Xlsx.extract(&xlsx, deadline).unwrap(),
+            "Quarterly\nRevenue\n"
+        );
+
+        let pptx = build_zip(&[
+            (
+                "ppt/slides/slide10.xml",
+                "<p:sld xmlns:a=\"urn:a\"><a:p><a:r><a:t>Last slide</a:t></a:r></a:p></p:sld>",
+            ),
+            (
+                "ppt/slides/slide2.xml",
+                "<p:sld xmlns:a=\"urn:a\"><a:p><a:r><a:t>First slide</a:t></a:r></a:p></p:sld>",
+            ),
+        ]);
+        assert_eq!(
+            DocumentType::Pptx.extract(&pptx, deadline).unwrap(),
+            "First slide\nLast slide\n"
+        );
+
+        let odt = build_zip(&[
+            ("mimetype", "application/vnd.oasis.opendocument.text"),
+            (
+                "content.xml",
+                concat!(
+                    "<office:document-content xmlns:office=\"urn:o\" xmlns:text=\"urn:t\">",
+                    "<office:body><office:text>",
+                    "<text:h>Contract</text:h>",
+                    "<text:p>Party<text:s/>A <text:span>and</text:span> Party B</text:p>",
+                    "</office:text></office:body></office:document-content>"
+                ),
+            ),
+        ]);
+        assert_eq!(
+            DocumentType::Odt.extract(&odt, deadline).unwrap(),
+            "Contract\nParty A and Party B\n"
+        );
+
+        assert!(DocumentType::Docx.extract(b"garbage", deadline).is_err());
+    }
+}
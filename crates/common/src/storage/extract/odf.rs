@@ -0,0 +1,23 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ExtractedText, TextExtractor, extract_xml_text, read_archive_entry};
+use std::io::Cursor;
+use zip::ZipArchive;
+
+pub struct OdfExtractor;
+
+impl TextExtractor for OdfExtractor {
+    fn extract(&self, data: &[u8], text: &mut ExtractedText) -> Result<(), String> {
+        let mut archive = ZipArchive::new(Cursor::new(data))
+            .map_err(|err| format!("Failed to open document: {err}"))?;
+        let xml = read_archive_entry(&mut archive, "content.xml")?
+            .ok_or_else(|| "Document has no content".to_string())?;
+
+        // Headings and paragraphs hold all text in text documents, spreadsheets and presentations
+        extract_xml_text(&xml, &[b"p", b"h"], &[b"p", b"h"], text)
+    }
+}
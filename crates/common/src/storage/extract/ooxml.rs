@@ -0,0 +1,79 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ExtractedText, TextExtractor, extract_xml_text, read_archive_entry};
+use std::io::Cursor;
+use zip::ZipArchive;
+
+pub struct OoxmlExtractor {
+    parts: &'static [&'static str],
+    numbered_parts: &'static [&'static str],
+    block_elements: &'static [&'static [u8]],
+}
+
+impl OoxmlExtractor {
+    pub const WORD: Self = Self {
+        parts: &[
+            "word/document.xml",
+            "word/footnotes.xml",
+            "word/endnotes.xml",
+            "word/comments.xml",
+        ],
+        numbered_parts: &["word/header", "word/footer"],
+        block_elements: &[b"p"],
+    };
+
+    pub const SPREADSHEET: Self = Self {
+        parts: &["xl/sharedStrings.xml"],
+        numbered_parts: &["xl/worksheets/sheet"],
+        block_elements: &[b"si", b"is"],
+    };
+
+    pub const PRESENTATION: Self = Self {
+        parts: &[],
+        numbered_parts: &["ppt/slides/slide", "ppt/notesSlides/notesSlide"],
+        block_elements: &[b"p"],
+    };
+}
+
+impl TextExtractor for OoxmlExtractor {
+    fn extract(&self, data: &[u8], text: &mut ExtractedText) -> Result<(), String> {
+        let mut archive = ZipArchive::new(Cursor::new(data))
+            .map_err(|err| format!("Failed to open document: {err}"))?;
+
+        // Numbered parts such as slides are read in their natural order
+        let mut names = self
+            .parts
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        for prefix in self.numbered_parts {
+            let mut numbered = archive
+                .file_names()
+                .filter_map(|name| {
+                    name.strip_prefix(prefix)
+                        .and_then(|name| name.strip_suffix(".xml"))
+                        .and_then(|num| num.parse::<u32>().ok())
+                        .map(|num| (num, name.to_string()))
+                })
+                .collect::<Vec<_>>();
+            numbered.sort_unstable();
+            names.extend(numbered.into_iter().map(|(_, name)| name));
+        }
+
+        for name in names {
+            if !text.can_continue() {
+                break;
+            }
+            if let Some(xml) = read_archive_entry(&mut archive, &name)? {
+                extract_xml_text(&xml, &[b"t"], self.block_elements, text)?;
+                text.push_separator('\n');
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ExtractedText, TextExtractor};
+use lopdf::Document;
+
+// Documents are fully parsed before any text is extracted, so parsing time
+// is bounded by the input size rather than by the extraction deadline
+const MAX_PDF_SIZE: usize = 16 * 1024 * 1024;
+
+pub struct PdfExtractor;
+
+impl TextExtractor for PdfExtractor {
+    fn extract(&self, data: &[u8], text: &mut ExtractedText) -> Result<(), String> {
+        if data.len() > MAX_PDF_SIZE {
+            return Err("Document is too large".to_string());
+        }
+        let document =
+            Document::load_mem(data).map_err(|err| format!("Failed to parse document: {err}"))?;
+        if document.is_encrypted() {
+            return Err("Document is encrypted".to_string());
+        }
+
+        for page_number in document.get_pages().into_keys() {
+            if !text.can_continue() {
+                break;
+            }
+
+            // Pages with unsupported fonts or encodings are skipped
+            if let Ok(page_text) = document.extract_text(&[page_number])
+                && !text.push(&page_text)
+            {
+                break;
+            }
+            text.push_separator('\n');
+        }
+
+        Ok(())
+    }
+}
@@ -18,8 +18,8 @@ use std::{borrow::Cow, fmt::Debug};
 use store::{
     Serialize, SerializeInfallible,
     write::{
-        Archive, Archiver, BatchBuilder, BlobLink, BlobOp, IntoOperations, Params, SearchIndex,
-        ValueClass,
+        Archive, Archiver, BatchBuilder, BlobLink, BlobOp, IndexPropertyClass, IntoOperations,
+        Params, SearchIndex, ValueClass, now,
     },
 };
 use types::{
@@ -64,6 +64,11 @@ pub enum IndexValue<'x> {
     Acl {
         value: Cow<'x, [AclGrant]>,
     },
+    // Each value is stored under its own key along with the time it was added
+    AddedAt {
+        property: u8,
+        values: Vec<u64>,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -539,6 +544,17 @@ fn build_index(
                 batch.log_container_property_change(sync_collection, parent_id);
             }
         }
+        IndexValue::AddedAt { property, values } => {
+            for value in values {
+                let class =
+                    ValueClass::IndexProperty(IndexPropertyClass::Integer { property, value });
+                if set {
+                    batch.set(class, now().serialize());
+                } else {
+                    batch.clear(class);
+                }
+            }
+        }
     }
 }
 
@@ -765,6 +781,32 @@ fn merge_index(
                 }
             }
         }
+        (
+            IndexValue::AddedAt {
+                property,
+                values: old_values,
+            },
+            IndexValue::AddedAt {
+                values: new_values, ..
+            },
+        ) => {
+            for value in &old_values {
+                if !new_values.contains(value) {
+                    batch.clear(ValueClass::IndexProperty(IndexPropertyClass::Integer {
+                        property,
+                        value: *value,
+                    }));
+                }
+            }
+            for value in new_values {
+                if !old_values.contains(&value) {
+                    batch.set(
+                        ValueClass::IndexProperty(IndexPropertyClass::Integer { property, value }),
+                        now().serialize(),
+                    );
+                }
+            }
+        }
         _ => unreachable!(),
     }
 
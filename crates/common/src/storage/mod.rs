@@ -21,6 +21,7 @@ pub mod blob;
 pub mod dav;
 pub mod document;
 pub mod encryption;
+pub mod extract;
 pub mod index;
 pub mod quota;
 pub mod state;
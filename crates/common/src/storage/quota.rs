@@ -6,15 +6,25 @@
 
 use crate::{
     Server,
-    auth::AccountCache,
+    auth::{AccessToken, AccountCache},
+    cache::invalidate::CacheInvalidationBuilder,
     storage::{ObjectQuota, TenantQuota},
 };
 use registry::{
-    schema::enums::{StorageQuota, TenantStorageQuota},
-    types::EnumImpl,
+    schema::{
+        enums::{StorageQuota, TenantStorageQuota},
+        prelude::{Object, ObjectType},
+        structs::Account,
+    },
+    types::{EnumImpl, id::ObjectId},
+};
+use store::{
+    ValueKey,
+    registry::write::{RegistryWrite, RegistryWriteResult},
+    write::ValueClass,
 };
-use store::{ValueKey, write::ValueClass};
 use trc::AddContext;
+use types::id::Id;
 
 impl Server {
     pub async fn get_used_quota_account(&self, account_id: u32) -> trc::Result<i64> {
@@ -92,6 +102,62 @@ impl Server {
         Ok(())
     }
 
+    /// Sets or removes (when `limit` is zero) a storage quota of an account on
+    /// behalf of `access_token`. Accounts outside the caller's tenant are reported
+    /// as not found.
+    pub async fn set_account_quota(
+        &self,
+        access_token: &AccessToken,
+        account_id: u32,
+        quota: StorageQuota,
+        limit: u64,
+    ) -> trc::Result<RegistryWriteResult> {
+        let object_id = ObjectId::new(ObjectType::Account, account_id.into());
+        let Some(current_account) = self
+            .registry()
+            .get(object_id)
+            .await
+            .caused_by(trc::location!())?
+            .filter(|account| access_token.can_access_object(account))
+        else {
+            return Ok(RegistryWriteResult::NotFound { object_id });
+        };
+
+        let mut account = Account::from(current_account.clone());
+        let quotas = match &mut account {
+            Account::User(account) => &mut account.quotas,
+            Account::Group(account) => &mut account.quotas,
+            Account::Resource(account) | Account::Location(account) => &mut account.quotas,
+        };
+        if quotas.get(&quota).copied().unwrap_or_default() == limit {
+            return Ok(RegistryWriteResult::Success(Id::from(account_id)));
+        } else if limit > 0 {
+            quotas.set(quota, limit);
+        } else {
+            quotas.remove(&quota);
+        }
+
+        let updated_account = Object::from(account);
+        let result = self
+            .registry()
+            .write(RegistryWrite::update(
+                Id::from(account_id),
+                &updated_account,
+                &current_account,
+            ))
+            .await
+            .caused_by(trc::location!())?;
+        if let RegistryWriteResult::Success(id) = &result {
+            let mut invalidator = CacheInvalidationBuilder::default();
+            invalidator.process_update(*id, &current_account, &updated_account);
+            self.invalidate_caches(invalidator)
+                .await
+                .caused_by(trc::location!())?;
+        }
+
+        Ok(result)
+    }
+
     #[inline(always)]
     pub fn object_quota(&self, user_quotas: Option<&ObjectQuota>, object: StorageQuota) -> u32 {
         user_quotas.unwrap_or(&self.core.email.max_objects).0[object as usize]
@@ -296,6 +296,10 @@ impl PrincipalPropFind for Server {
                                 property.clone(),
                                 if account.is_user_account() {
                                     DavValue::String("INDIVIDUAL".to_string())
+                                } else if account.is_location_account() {
+                                    DavValue::String("ROOM".to_string())
+                                } else if account.is_resource_account() {
+                                    DavValue::String("RESOURCE".to_string())
                                 } else {
                                     DavValue::String("GROUP".to_string())
                                 },
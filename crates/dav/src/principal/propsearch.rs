@@ -7,13 +7,19 @@
 use super::propfind::PrincipalPropFind;
 use common::{Server, auth::AccessToken};
 use dav_proto::schema::{
-    property::{DavProperty, WebDavProperty},
+    property::{DavProperty, PrincipalProperty, WebDavProperty},
     request::{PrincipalPropertySearch, PropFind},
     response::MultiStatus,
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
-use registry::schema::prelude::{ObjectType, Property};
+use registry::{
+    schema::{
+        enums::AccountType,
+        prelude::{ObjectType, Property},
+    },
+    types::EnumImpl,
+};
 use store::{registry::RegistryQuery, roaring::RoaringBitmap};
 use trc::AddContext;
 use types::collection::Collection;
@@ -33,26 +39,44 @@ impl PrincipalPropSearch for Server {
         mut request: PrincipalPropertySearch,
     ) -> crate::Result<HttpResponse> {
         let mut search_for = None;
+        let mut search_type = None;
 
         for prop_search in request.property_search {
-            if matches!(
-                prop_search.property,
+            match prop_search.property {
                 DavProperty::WebDav(WebDavProperty::DisplayName)
-            ) && !prop_search.match_.is_empty()
-            {
-                search_for = Some(prop_search.match_);
+                    if !prop_search.match_.is_empty() =>
+                {
+                    search_for = Some(prop_search.match_);
+                }
+                DavProperty::Principal(PrincipalProperty::CalendarUserType) => {
+                    // Unknown calendar user types match no principals
+                    search_type = Some(hashify::tiny_map_ignore_case!(
+                        prop_search.match_.trim().as_bytes(),
+                        "INDIVIDUAL" => AccountType::User,
+                        "GROUP" => AccountType::Group,
+                        "RESOURCE" => AccountType::Resource,
+                        "ROOM" => AccountType::Location,
+                    ));
+                }
+                _ => {}
             }
         }
 
         let mut response = MultiStatus::new(Vec::with_capacity(16));
-        if let Some(search_for) = search_for {
+        if (search_for.is_some() || search_type.is_some())
+            && search_type.is_none_or(|typ| typ.is_some())
+        {
+            let mut query =
+                RegistryQuery::new(ObjectType::Account).with_tenant(access_token.tenant_id());
+            if let Some(search_for) = search_for {
+                query = query.text(Property::Text, search_for);
+            }
+            if let Some(Some(search_type)) = search_type {
+                query = query.equal(Property::Type, search_type.to_id());
+            }
             let ids = self
                 .registry()
-                .query::<RoaringBitmap>(
-                    RegistryQuery::new(ObjectType::Account)
-                        .with_tenant(access_token.tenant_id())
-                        .text(Property::Text, search_for),
-                )
+                .query::<RoaringBitmap>(query)
                 .await
                 .caused_by(trc::location!())?;
 
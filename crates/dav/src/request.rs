@@ -40,7 +40,7 @@ use dav_proto::{
     parser::{DavParser, tokenizer::Tokenizer},
     schema::{
         Namespace,
-        property::WebDavProperty,
+        property::{DavProperty, PrincipalProperty, WebDavProperty},
         request::{Acl, LockInfo, MkCol, PropFind, PropertyUpdate, Report},
         response::{
             BaseCondition, ErrorResponse, List, PrincipalSearchProperty, PrincipalSearchPropertySet,
@@ -245,10 +245,16 @@ impl DavRequestDispatcher for Server {
                             .assert_has_permission(Permission::DavPrincipalSearchPropSet)?;
 
                         Ok(HttpResponse::new(StatusCode::OK).with_xml_body(
-                            PrincipalSearchPropertySet::new(vec![PrincipalSearchProperty::new(
-                                WebDavProperty::DisplayName,
-                                "Account or Group name",
-                            )])
+                            PrincipalSearchPropertySet::new(vec![
+                                PrincipalSearchProperty::new(
+                                    WebDavProperty::DisplayName,
+                                    "Account or Group name",
+                                ),
+                                PrincipalSearchProperty::new(
+                                    DavProperty::Principal(PrincipalProperty::CalendarUserType),
+                                    "Calendar user type",
+                                ),
+                            ])
                             .to_string(),
                         ))
                     } else {
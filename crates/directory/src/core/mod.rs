@@ -7,4 +7,5 @@
 pub mod config;
 pub mod dispatch;
 pub mod sasl;
+pub mod scram;
 pub mod secret;
@@ -0,0 +1,398 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use aws_lc_rs::{constant_time, digest, hmac, pbkdf2};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use std::{fmt::Display, num::NonZeroU32, sync::LazyLock};
+
+pub const SCRAM_ITERATIONS: u32 = 4096;
+const SCRAM_PREFIX: &str = "SCRAM-SHA-256$";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 18;
+
+static MOCK_SALT_KEY: LazyLock<hmac::Key> = LazyLock::new(|| {
+    let mut key = [0u8; 32];
+    let _ = aws_lc_rs::rand::fill(&mut key);
+    hmac::Key::new(hmac::HMAC_SHA256, &key)
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelBindingType {
+    TlsServerEndPoint,
+    TlsExporter,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelBindings {
+    pub tls_server_end_point: Option<Vec<u8>>,
+    pub tls_exporter: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramSecret {
+    pub iterations: u32,
+    pub salt: Vec<u8>,
+    pub stored_key: Vec<u8>,
+    pub server_key: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramClientFirst {
+    pub username: String,
+    pub authzid: Option<String>,
+    cbind_input: Vec<u8>,
+    client_first_bare: String,
+    client_nonce: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramExchange {
+    pub username: String,
+    pub authzid: Option<String>,
+    cbind_input: Vec<u8>,
+    client_first_bare: String,
+    server_first: String,
+    nonce: String,
+    secret: ScramSecret,
+}
+
+impl ChannelBindingType {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "tls-server-end-point" => Some(ChannelBindingType::TlsServerEndPoint),
+            "tls-exporter" => Some(ChannelBindingType::TlsExporter),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChannelBindingType::TlsServerEndPoint => "tls-server-end-point",
+            ChannelBindingType::TlsExporter => "tls-exporter",
+        }
+    }
+}
+
+impl ChannelBindings {
+    pub fn get(&self, binding: ChannelBindingType) -> Option<&[u8]> {
+        match binding {
+            ChannelBindingType::TlsServerEndPoint => self.tls_server_end_point.as_deref(),
+            ChannelBindingType::TlsExporter => self.tls_exporter.as_deref(),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.tls_server_end_point.is_some() || self.tls_exporter.is_some()
+    }
+}
+
+impl ScramSecret {
+    pub fn generate(secret: &[u8]) -> Self {
+        let mut salt = vec![0u8; SALT_LEN];
+        let _ = aws_lc_rs::rand::fill(&mut salt);
+        Self::derive(secret, salt, SCRAM_ITERATIONS)
+    }
+
+    pub fn derive(secret: &[u8], salt: Vec<u8>, iterations: u32) -> Self {
+        let mut salted_password = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN),
+            &salt,
+            secret,
+            &mut salted_password,
+        );
+        let salted_password = hmac::Key::new(hmac::HMAC_SHA256, &salted_password);
+        let client_key = hmac::sign(&salted_password, b"Client Key");
+
+        ScramSecret {
+            iterations,
+            salt,
+            stored_key: digest::digest(&digest::SHA256, client_key.as_ref())
+                .as_ref()
+                .to_vec(),
+            server_key: hmac::sign(&salted_password, b"Server Key")
+                .as_ref()
+                .to_vec(),
+        }
+    }
+
+    /// Builds a secret for an unknown user so that the exchange proceeds
+    /// normally and only fails at the proof verification step.
+    pub fn mock(username: &str) -> Self {
+        let mut keys = [0u8; 64];
+        let _ = aws_lc_rs::rand::fill(&mut keys);
+
+        ScramSecret {
+            iterations: SCRAM_ITERATIONS,
+            salt: hmac::sign(&MOCK_SALT_KEY, username.as_bytes()).as_ref()[..SALT_LEN].to_vec(),
+            stored_key: keys[..32].to_vec(),
+            server_key: keys[32..].to_vec(),
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let (salt_info, keys) = value.strip_prefix(SCRAM_PREFIX)?.split_once('$')?;
+        let (iterations, salt) = salt_info.split_once(':')?;
+        let (stored_key, server_key) = keys.split_once(':')?;
+
+        Some(ScramSecret {
+            iterations: iterations.parse().ok().filter(|&i| i > 0)?,
+            salt: STANDARD.decode(salt).ok()?,
+            stored_key: STANDARD.decode(stored_key).ok().filter(|k| k.len() == 32)?,
+            server_key: STANDARD.decode(server_key).ok().filter(|k| k.len() == 32)?,
+        })
+    }
+}
+
+impl Display for ScramSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{SCRAM_PREFIX}{}:{}${}:{}",
+            self.iterations,
+            STANDARD.encode(&self.salt),
+            STANDARD.encode(&self.stored_key),
+            STANDARD.encode(&self.server_key)
+        )
+    }
+}
+
+impl ScramClientFirst {
+    pub fn parse(message: &[u8], is_plus: bool, bindings: &ChannelBindings) -> Option<Self> {
+        let message = std::str::from_utf8(message).ok()?;
+        let (cbind_flag, rest) = message.split_once(',')?;
+        let (authzid, client_first_bare) = rest.split_once(',')?;
+        let gs2_header = &message[..message.len() - client_first_bare.len()];
+
+        // Negotiate channel binding
+        let cbind_data = match cbind_flag {
+            "n" if !is_plus => &[][..],
+            "y" if !is_plus && !bindings.is_available() => &[][..],
+            flag if is_plus => {
+                bindings.get(ChannelBindingType::parse(flag.strip_prefix("p=")?)?)?
+            }
+            _ => return None,
+        };
+        let mut cbind_input = gs2_header.as_bytes().to_vec();
+        cbind_input.extend_from_slice(cbind_data);
+
+        let authzid = if !authzid.is_empty() {
+            decode_saslname(authzid.strip_prefix("a=")?)?.into()
+        } else {
+            None
+        };
+
+        // Mandatory extensions are not supported
+        let mut attributes = client_first_bare.split(',');
+        let username = decode_saslname(attributes.next()?.strip_prefix("n=")?)?;
+        let client_nonce = attributes.next()?.strip_prefix("r=")?;
+        if username.is_empty()
+            || client_nonce.is_empty()
+            || client_nonce.bytes().any(|ch| !(0x21..=0x7e).contains(&ch))
+        {
+            return None;
+        }
+
+        Some(ScramClientFirst {
+            username,
+            authzid,
+            cbind_input,
+            client_first_bare: client_first_bare.to_string(),
+            client_nonce: client_nonce.to_string(),
+        })
+    }
+
+    pub fn into_exchange(self, secret: ScramSecret) -> ScramExchange {
+        let mut server_nonce = [0u8; NONCE_LEN];
+        let _ = aws_lc_rs::rand::fill(&mut server_nonce);
+        self.into_exchange_with_nonce(secret, &STANDARD.encode(server_nonce))
+    }
+
+    fn into_exchange_with_nonce(self, secret: ScramSecret, server_nonce: &str) -> ScramExchange {
+        let nonce = format!("{}{server_nonce}", self.client_nonce);
+        let server_first = format!(
+            "r={nonce},s={},i={}",
+            STANDARD.encode(&secret.salt),
+            secret.iterations
+        );
+
+        ScramExchange {
+            username: self.username,
+            authzid: self.authzid,
+            cbind_input: self.cbind_input,
+            client_first_bare: self.client_first_bare,
+            server_first,
+            nonce,
+            secret,
+        }
+    }
+}
+
+impl ScramExchange {
+    pub fn server_first(&self) -> &str {
+        &self.server_first
+    }
+
+    /// Verifies the client proof and returns the server-final message.
+    pub fn verify_client_final(&self, message: &[u8]) -> Option<String> {
+        let message = std::str::from_utf8(message).ok()?;
+        let (client_final_without_proof, proof) = message.rsplit_once(",p=")?;
+        let proof = STANDARD.decode(proof).ok().filter(|p| p.len() == 32)?;
+
+        let mut attributes = client_final_without_proof.split(',');
+        let cbind_input = STANDARD
+            .decode(attributes.next()?.strip_prefix("c=")?)
+            .ok()?;
+        let nonce = attributes.next()?.strip_prefix("r=")?;
+        if cbind_input != self.cbind_input || nonce != self.nonce {
+            return None;
+        }
+
+        let auth_message = format!(
+            "{},{},{client_final_without_proof}",
+            self.client_first_bare, self.server_first
+        );
+        let client_signature = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, &self.secret.stored_key),
+            auth_message.as_bytes(),
+        );
+        let client_key = proof
+            .iter()
+            .zip(client_signature.as_ref())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+
+        constant_time::verify_slices_are_equal(
+            digest::digest(&digest::SHA256, &client_key).as_ref(),
+            &self.secret.stored_key,
+        )
+        .ok()?;
+
+        let server_signature = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, &self.secret.server_key),
+            auth_message.as_bytes(),
+        );
+
+        Some(format!("v={}", STANDARD.encode(server_signature.as_ref())))
+    }
+}
+
+fn decode_saslname(value: &str) -> Option<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch == '=' {
+            match (chars.next()?, chars.next()?) {
+                ('2', 'C') => result.push(','),
+                ('3', 'D') => result.push('='),
+                _ => return None,
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scram_sha256_exchange() {
+        // RFC 7677 test vector
+        let secret = ScramSecret::derive(
+            b"pencil",
+            STANDARD.decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap(),
+            4096,
+        );
+        assert_eq!(
+            ScramSecret::parse(&secret.to_string()),
+            Some(secret.clone())
+        );
+
+        let exchange = ScramClientFirst::parse(
+            b"n,,n=user,r=rOprNGfwEbeRWgbNEkqO",
+            false,
+            &ChannelBindings::default(),
+        )
+        .unwrap()
+        .into_exchange_with_nonce(secret, "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0");
+        assert_eq!(exchange.username, "user");
+        assert_eq!(
+            exchange.server_first(),
+            "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096"
+        );
+        assert_eq!(
+            exchange
+                .verify_client_final(
+                    concat!(
+                        "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,",
+                        "p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+                    )
+                    .as_bytes()
+                )
+                .as_deref(),
+            Some("v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=")
+        );
+        assert_eq!(
+            exchange.verify_client_final(
+                concat!(
+                    "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,",
+                    "p=AHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+                )
+                .as_bytes()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn scram_channel_binding() {
+        let bindings = ChannelBindings {
+            tls_server_end_point: None,
+            tls_exporter: Some(vec![1, 2, 3]),
+        };
+
+        // Downgrade and mismatch detection
+        for (message, is_plus) in [
+            (&b"y,,n=user,r=abc"[..], false),
+            (b"n,,n=user,r=abc", true),
+            (b"p=tls-exporter,,n=user,r=abc", false),
+            (b"p=tls-server-end-point,,n=user,r=abc", true),
+            (b"p=tls-unique,,n=user,r=abc", true),
+            (b"n,,m=ext,n=user,r=abc", false),
+            (b"n,,n=us=2Xer,r=abc", false),
+        ] {
+            assert_eq!(
+                ScramClientFirst::parse(message, is_plus, &bindings),
+                None,
+                "{}",
+                std::str::from_utf8(message).unwrap()
+            );
+        }
+        assert!(
+            ScramClientFirst::parse(b"y,,n=user,r=abc", false, &ChannelBindings::default())
+                .is_some()
+        );
+
+        let client_first = ScramClientFirst::parse(
+            b"p=tls-exporter,a=adm=2Cin,n=us=3Der,r=abc",
+            true,
+            &bindings,
+        )
+        .unwrap();
+        assert_eq!(client_first.username, "us=er");
+        assert_eq!(client_first.authzid.as_deref(), Some("adm,in"));
+        assert_eq!(
+            client_first.cbind_input,
+            b"p=tls-exporter,a=adm=2Cin,\x01\x02\x03".to_vec()
+        );
+    }
+}
@@ -208,6 +208,8 @@ impl MailboxDestroy for Server {
                 .with_collection(Collection::Mailbox)
                 .with_document(document_id)
                 .clear(MailboxField::UidCounter)
+                .clear(MailboxField::AccessKey)
+                .clear(MailboxField::ImapMetadata)
                 .custom(ObjectIndexBuilder::<_, ()>::new().with_current(mailbox))
                 .caused_by(trc::location!())?;
         } else {
@@ -29,6 +29,14 @@ pub struct Mailbox {
     pub acls: Vec<AclGrant>,
 }
 
+#[derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct MailboxMetadata {
+    pub name: String,
+    pub owner_id: Option<u32>,
+    pub value: Vec<u8>,
+}
+
 #[derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Clone, Copy)]
 #[rkyv(derive(Debug, Clone, Copy))]
 pub struct UidMailbox {
@@ -4,7 +4,10 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::ingest::{EmailIngest, IngestEmail, IngestSource};
+use super::{
+    ingest::{EmailIngest, IngestEmail, IngestSource},
+    quarantine::EmailQuarantine,
+};
 use crate::{mailbox::INBOX_ID, sieve::ingest::SieveScriptIngest};
 use common::{
     Server,
@@ -32,6 +35,7 @@ pub struct IngestRecipient {
     pub address: String,
     pub orcpt: Option<String>,
     pub is_spam: bool,
+    pub is_quarantined: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -156,6 +160,16 @@ impl MailDelivery for Server {
                     .build()
                     .assert_has_permission(Permission::EmailReceive)
             }) {
+                Ok(_) if rcpt.is_quarantined => {
+                    // Hold message in the recipient's quarantine
+                    self.email_quarantine(
+                        account_id,
+                        &raw_message,
+                        &message.message_blob,
+                        message.session_id,
+                    )
+                    .await
+                }
                 Ok(access_token) => {
                     // Check if there is an active sieve script
                     match self.sieve_script_get_active(account_id).await {
@@ -49,7 +49,8 @@ impl MessageMetadata {
                     hash: self.blob_hash.clone(),
                     to: BlobLink::Document,
                 })
-                .clear(EmailField::Metadata);
+                .clear(EmailField::Metadata)
+                .clear(EmailField::SmimeVerification);
         }
 
         Ok(())
@@ -82,6 +83,7 @@ impl ArchivedMessageMetadata {
 
         batch
             .clear(EmailField::Metadata)
+            .clear(EmailField::SmimeVerification)
             .clear(ValueClass::IndexProperty(IndexPropertyClass::Hash {
                 property: EmailField::Threading.into(),
                 hash: CheekyHash::new(if !thread_name.is_empty() {
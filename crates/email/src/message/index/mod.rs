@@ -19,13 +19,21 @@ pub mod search;
 pub(super) const MAX_MESSAGE_PARTS: usize = 1000;
 pub const PREVIEW_LENGTH: usize = 256;
 
+// Save dates are keyed by mailbox and UID, as a new UID is assigned
+// every time a message is appended, copied or moved to a mailbox
+pub fn saved_at_id(mailbox_id: u32, uid: u32) -> u64 {
+    ((mailbox_id as u64) << 32) | uid as u64
+}
+
 impl IndexableObject for MessageData {
     fn index_values(&self) -> impl Iterator<Item = IndexValue<'_>> {
         let mut mailboxes = Vec::with_capacity(self.mailboxes.len());
+        let mut saved_ids = Vec::with_capacity(self.mailboxes.len());
         let mut is_in_trash = false;
 
         for mailbox in &self.mailboxes {
             mailboxes.push(mailbox.mailbox_id);
+            saved_ids.push(saved_at_id(mailbox.mailbox_id, mailbox.uid));
             is_in_trash |= mailbox.mailbox_id == TRASH_ID || mailbox.mailbox_id == JUNK_ID;
         }
 
@@ -51,6 +59,10 @@ impl IndexableObject for MessageData {
                 sync_collection: SyncCollection::Email,
                 ids: mailboxes,
             },
+            IndexValue::AddedAt {
+                property: EmailField::SavedAt.into(),
+                values: saved_ids,
+            },
         ]
         .into_iter()
     }
@@ -59,11 +71,13 @@ impl IndexableObject for MessageData {
 impl IndexableObject for &ArchivedMessageData {
     fn index_values(&self) -> impl Iterator<Item = IndexValue<'_>> {
         let mut mailboxes = Vec::with_capacity(self.mailboxes.len());
+        let mut saved_ids = Vec::with_capacity(self.mailboxes.len());
         let mut is_in_trash = false;
 
         for mailbox in self.mailboxes.iter() {
             let mailbox_id = mailbox.mailbox_id.to_native();
             mailboxes.push(mailbox_id);
+            saved_ids.push(saved_at_id(mailbox_id, mailbox.uid.to_native()));
             is_in_trash |= mailbox_id == TRASH_ID || mailbox_id == JUNK_ID;
         }
 
@@ -91,6 +105,10 @@ impl IndexableObject for &ArchivedMessageData {
                 sync_collection: SyncCollection::Email,
                 ids: mailboxes,
             },
+            IndexValue::AddedAt {
+                property: EmailField::SavedAt.into(),
+                values: saved_ids,
+            },
         ]
         .into_iter()
     }
@@ -20,20 +20,59 @@ use nlp::{
     },
     tokenizers::word::WordTokenizer,
 };
+use std::borrow::Cow;
 use store::{
-    ahash::AHashSet,
+    ahash::{AHashMap, AHashSet},
     backend::MAX_TOKEN_LENGTH,
     search::{EmailSearchField, IndexDocument, SearchField},
     write::SearchIndex,
 };
 use utils::chained_bytes::ChainedBytes;
 
+pub struct AttachmentContents<'x> {
+    pub part_id: u16,
+    pub content_type: Option<String>,
+    pub file_name: Option<&'x str>,
+    pub contents: Cow<'x, [u8]>,
+}
+
 impl ArchivedMessageMetadata {
+    pub fn binary_attachments<'x>(&'x self, raw_message: &'x [u8]) -> Vec<AttachmentContents<'x>> {
+        let raw_message = ChainedBytes::new(self.raw_headers.as_ref()).with_last(
+            raw_message
+                .get(self.blob_body_offset.to_native() as usize..)
+                .unwrap_or_default(),
+        );
+
+        self.contents[0]
+            .parts
+            .iter()
+            .take(MAX_MESSAGE_PARTS)
+            .enumerate()
+            .filter(|(_, part)| {
+                matches!(
+                    part.body,
+                    ArchivedMetadataPartType::Binary | ArchivedMetadataPartType::InlineBinary
+                )
+            })
+            .map(|(part_id, part)| AttachmentContents {
+                part_id: part_id as u16,
+                content_type: part.content_type().map(|ct| match ct.subtype() {
+                    Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                    None => ct.ctype().to_string(),
+                }),
+                file_name: part.attachment_name(),
+                contents: part.contents(&raw_message),
+            })
+            .collect()
+    }
+
     pub fn index_document(
         &self,
         account_id: u32,
         document_id: u32,
         raw_message: &[u8],
+        extracted_text: &AHashMap<u16, String>,
         index_fields: &AHashSet<SearchField>,
         default_language: Language,
     ) -> IndexDocument {
@@ -321,6 +360,23 @@ impl ArchivedMessageMetadata {
                         }
                     }
                 }
+                ArchivedMetadataPartType::Binary | ArchivedMetadataPartType::InlineBinary => {
+                    if let Some(text) = extracted_text.get(&part_id)
+                        && (index_fields.is_empty()
+                            || index_fields
+                                .contains(&SearchField::Email(EmailSearchField::Attachment)))
+                    {
+                        if part_language.is_unknown() {
+                            detector.detect(text, MIN_LANGUAGE_SCORE);
+                        }
+
+                        document.index_text(
+                            SearchField::Email(EmailSearchField::Attachment),
+                            text,
+                            part_language,
+                        );
+                    }
+                }
                 _ => {}
             }
         }
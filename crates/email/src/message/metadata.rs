@@ -40,6 +40,21 @@ pub struct MessageMetadata {
     pub raw_headers: Box<[u8]>,
 }
 
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, PartialEq, Eq)]
+pub struct SmimeVerification {
+    pub status: SmimeStatus,
+    pub errors: Box<[Box<str>]>,
+    pub verified_at: u64,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmimeStatus {
+    Unknown,
+    Signed,
+    SignedVerified,
+    SignedFailed,
+}
+
 pub const MESSAGE_HAS_ATTACHMENT: u64 = 1 << 63;
 pub const MESSAGE_RECEIVED_MASK: u64 = !MESSAGE_HAS_ATTACHMENT;
 
@@ -1048,6 +1063,32 @@ impl ArchivedMessageData {
     }
 }
 
+impl SmimeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SmimeStatus::Unknown => "unknown",
+            SmimeStatus::Signed => "signed",
+            SmimeStatus::SignedVerified => "signed/verified",
+            SmimeStatus::SignedFailed => "signed/failed",
+        }
+    }
+}
+
+impl ArchivedSmimeVerification {
+    pub fn to_native(&self) -> SmimeVerification {
+        SmimeVerification {
+            status: match self.status {
+                ArchivedSmimeStatus::Unknown => SmimeStatus::Unknown,
+                ArchivedSmimeStatus::Signed => SmimeStatus::Signed,
+                ArchivedSmimeStatus::SignedVerified => SmimeStatus::SignedVerified,
+                ArchivedSmimeStatus::SignedFailed => SmimeStatus::SignedFailed,
+            },
+            errors: self.errors.iter().map(|e| e.as_ref().into()).collect(),
+            verified_at: self.verified_at.to_native(),
+        }
+    }
+}
+
 impl ArchivedMetadataContentType {
     pub fn ctype(&self) -> &str {
         &self.c_type
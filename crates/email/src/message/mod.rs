@@ -11,3 +11,6 @@ pub mod delivery;
 pub mod index;
 pub mod ingest;
 pub mod metadata;
+pub mod quarantine;
+pub mod smime;
+pub mod urlauth;
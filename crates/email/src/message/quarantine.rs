@@ -0,0 +1,255 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::ingest::IngestedEmail;
+use common::{Server, auth::oauth::GrantType};
+use mail_parser::MessageParser;
+use registry::{
+    schema::{
+        enums::ArchivedItemType,
+        prelude::{ObjectType, Property},
+        structs::{QuarantinedMessage, Task, TaskRestoreArchivedItem, TaskStatus},
+    },
+    types::{EnumImpl, ObjectImpl, datetime::UTCDateTime, id::ObjectId},
+};
+use std::future::Future;
+use store::{
+    SerializeInfallible, ValueKey,
+    write::{BatchBuilder, BlobLink, BlobOp, RegistryClass, ValueClass, now},
+};
+use trc::{AddContext, MessageIngestEvent};
+use types::{
+    blob::{BlobClass, BlobId},
+    blob_hash::BlobHash,
+    id::Id,
+};
+
+pub const QUARANTINE_HEADER: &str = "X-Quarantine";
+
+pub trait EmailQuarantine: Sync + Send {
+    fn email_quarantine(
+        &self,
+        account_id: u32,
+        raw_message: &[u8],
+        blob_hash: &BlobHash,
+        session_id: u64,
+    ) -> impl Future<Output = trc::Result<IngestedEmail>> + Send;
+
+    fn quarantine_release(
+        &self,
+        account_id: Option<u32>,
+        item_id: u64,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+
+    fn quarantine_destroy(
+        &self,
+        account_id: Option<u32>,
+        item_id: u64,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+
+    fn quarantine_release_token(
+        &self,
+        token: &str,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+}
+
+impl EmailQuarantine for Server {
+    async fn email_quarantine(
+        &self,
+        account_id: u32,
+        raw_message: &[u8],
+        blob_hash: &BlobHash,
+        session_id: u64,
+    ) -> trc::Result<IngestedEmail> {
+        let message = MessageParser::new().parse_headers(raw_message);
+        let (from, subject, reason) = message
+            .as_ref()
+            .map(|message| {
+                (
+                    message
+                        .from()
+                        .and_then(|from| from.first())
+                        .and_then(|addr| addr.address())
+                        .unwrap_or_default()
+                        .to_string(),
+                    message.subject().unwrap_or_default().to_string(),
+                    message
+                        .header(QUARANTINE_HEADER)
+                        .and_then(|value| value.as_text())
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                )
+            })
+            .unwrap_or_default();
+
+        let received_at = now();
+        let until = received_at + self.core.email.quarantine.hold_for;
+        let object_id = ObjectType::QuarantinedMessage.to_id();
+        let item_id = self.inner.data.registry_id_gen.generate();
+        let item = QuarantinedMessage {
+            from,
+            subject,
+            reason,
+            received_at: UTCDateTime::from_timestamp(received_at as i64),
+            size: raw_message.len() as u64,
+            account_id: Id::from(account_id),
+            blob_id: BlobId::new(blob_hash.clone(), BlobClass::default()),
+            expires_at: UTCDateTime::from_timestamp(until as i64),
+            notified: false,
+        };
+
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .set(
+                BlobOp::Link {
+                    hash: blob_hash.clone(),
+                    to: BlobLink::Temporary { until },
+                },
+                ObjectId::new(ObjectType::QuarantinedMessage, item_id.into()).serialize(),
+            )
+            .set(
+                ValueClass::Registry(RegistryClass::Item { object_id, item_id }),
+                item.to_pickled_vec(),
+            )
+            .set(
+                ValueClass::Registry(RegistryClass::Index {
+                    index_id: Property::AccountId.to_id(),
+                    object_id,
+                    item_id,
+                    key: (account_id as u64).serialize(),
+                }),
+                vec![],
+            );
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        trc::event!(
+            MessageIngest(MessageIngestEvent::Quarantined),
+            SpanId = session_id,
+            AccountId = account_id,
+            Id = item_id,
+            Reason = item.reason,
+            Size = raw_message.len(),
+            Expires = trc::Value::Timestamp(until),
+        );
+
+        Ok(IngestedEmail {
+            document_id: 0,
+            thread_id: 0,
+            change_id: u64::MAX,
+            blob_id: item.blob_id,
+            size: raw_message.len(),
+            imap_uids: Vec::new(),
+        })
+    }
+
+    async fn quarantine_release(&self, account_id: Option<u32>, item_id: u64) -> trc::Result<bool> {
+        let object_id = ObjectType::QuarantinedMessage.to_id();
+        let Some(item) = self
+            .store()
+            .get_value::<QuarantinedMessage>(ValueKey::from(ValueClass::Registry(
+                RegistryClass::Item { object_id, item_id },
+            )))
+            .await
+            .caused_by(trc::location!())?
+            .filter(|item| account_id.is_none_or(|id| item.account_id.document_id() == id))
+        else {
+            return Ok(false);
+        };
+
+        // The blob remains linked until the restore task ingests the message
+        let mut batch = BatchBuilder::new();
+        batch
+            .clear(ValueClass::Registry(RegistryClass::Index {
+                index_id: Property::AccountId.to_id(),
+                object_id,
+                item_id,
+                key: item.account_id.id().serialize(),
+            }))
+            .clear(ValueClass::Registry(RegistryClass::Item {
+                object_id,
+                item_id,
+            }))
+            .schedule_task(Task::RestoreArchivedItem(TaskRestoreArchivedItem {
+                account_id: item.account_id,
+                archived_item_type: ArchivedItemType::Email,
+                archived_until: item.expires_at,
+                blob_id: item.blob_id,
+                created_at: item.received_at,
+                status: TaskStatus::now(),
+            }));
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+        self.notify_task_queue();
+
+        trc::event!(
+            MessageIngest(MessageIngestEvent::QuarantineReleased),
+            AccountId = item.account_id.document_id(),
+            Id = item_id,
+        );
+
+        Ok(true)
+    }
+
+    async fn quarantine_destroy(&self, account_id: Option<u32>, item_id: u64) -> trc::Result<bool> {
+        let object_id = ObjectType::QuarantinedMessage.to_id();
+        let Some(item) = self
+            .store()
+            .get_value::<QuarantinedMessage>(ValueKey::from(ValueClass::Registry(
+                RegistryClass::Item { object_id, item_id },
+            )))
+            .await
+            .caused_by(trc::location!())?
+            .filter(|item| account_id.is_none_or(|id| item.account_id.document_id() == id))
+        else {
+            return Ok(false);
+        };
+
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(item.account_id.document_id())
+            .clear(BlobOp::Link {
+                hash: item.blob_id.hash,
+                to: BlobLink::Temporary {
+                    until: item.expires_at.timestamp() as u64,
+                },
+            })
+            .clear(ValueClass::Registry(RegistryClass::Index {
+                index_id: Property::AccountId.to_id(),
+                object_id,
+                item_id,
+                key: item.account_id.id().serialize(),
+            }))
+            .clear(ValueClass::Registry(RegistryClass::Item {
+                object_id,
+                item_id,
+            }));
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(true)
+    }
+
+    async fn quarantine_release_token(&self, token: &str) -> trc::Result<bool> {
+        let token = self
+            .validate_access_token(GrantType::QuarantineRelease.into(), token)
+            .await?;
+        let Some(item_id) = token.claims.and_then(|claims| claims.parse::<u64>().ok()) else {
+            return Ok(false);
+        };
+
+        self.quarantine_release(token.account_id.into(), item_id)
+            .await
+    }
+}
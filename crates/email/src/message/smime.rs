@@ -0,0 +1,466 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::metadata::{ArchivedMessageMetadataPart, SmimeStatus, SmimeVerification};
+use aws_lc_rs::{
+    digest,
+    signature::{self, UnparsedPublicKey, VerificationAlgorithm},
+};
+use mail_parser::{MessageParser, MimeHeaders, PartType};
+use rasn::types::{ObjectIdentifier, OctetString};
+use rasn_cms::{
+    AlgorithmIdentifier, CONTENT_SIGNED_DATA, CertificateChoices, ContentInfo, SignedData,
+    SignerIdentifier, SignerInfo,
+};
+use rasn_pkix::{Certificate, GeneralName, SubjectAltName, Time};
+use rustls_pki_types::{CertificateDer, UnixTime};
+use std::{borrow::Cow, time::Duration};
+use webpki::{ALL_VERIFICATION_ALGS, EndEntityCert, KeyUsage, anchor_from_trusted_cert};
+
+// id-kp-emailProtection (1.3.6.1.5.5.7.3.4)
+const EKU_EMAIL_PROTECTION: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x04];
+
+const OID_MESSAGE_DIGEST: &[u32] = &[1, 2, 840, 113549, 1, 9, 4];
+const OID_SUBJECT_KEY_IDENTIFIER: &[u32] = &[2, 5, 29, 14];
+const OID_SUBJECT_ALT_NAME: &[u32] = &[2, 5, 29, 17];
+
+enum Error {
+    Unsupported(String),
+    Invalid(String),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+impl ArchivedMessageMetadataPart {
+    pub fn is_smime_signed(&self) -> bool {
+        self.content_type()
+            .is_some_and(|ct| match (ct.ctype(), ct.subtype()) {
+                (ctype, Some(subtype)) if ctype.eq_ignore_ascii_case("multipart") => {
+                    subtype.eq_ignore_ascii_case("signed")
+                }
+                (ctype, Some(subtype)) if ctype.eq_ignore_ascii_case("application") => {
+                    (subtype.eq_ignore_ascii_case("pkcs7-mime")
+                        || subtype.eq_ignore_ascii_case("x-pkcs7-mime"))
+                        && ct
+                            .attribute("smime-type")
+                            .is_some_and(|t| t.eq_ignore_ascii_case("signed-data"))
+                }
+                _ => false,
+            })
+    }
+}
+
+/// Verifies the S/MIME signature of a message (RFC 8551) against the provided
+/// trust anchors. Returns `None` when the message is not signed.
+pub fn verify_smime(
+    raw_message: &[u8],
+    trust_anchors: &[Certificate],
+    now: u64,
+) -> Option<SmimeVerification> {
+    let message = MessageParser::new().parse(raw_message)?;
+    let root = message.root_part();
+    let content_type = root.content_type()?;
+    let (content, pkcs7) = match (
+        content_type.ctype(),
+        content_type.subtype().unwrap_or_default(),
+    ) {
+        (ctype, subtype)
+            if ctype.eq_ignore_ascii_case("multipart")
+                && subtype.eq_ignore_ascii_case("signed") =>
+        {
+            let PartType::Multipart(sub_parts) = &root.body else {
+                return None;
+            };
+            let content = message.part(*sub_parts.first()?)?;
+            let signature = sub_parts
+                .iter()
+                .skip(1)
+                .filter_map(|id| message.part(*id))
+                .find(|part| {
+                    part.is_content_type("application", "pkcs7-signature")
+                        || part.is_content_type("application", "x-pkcs7-signature")
+                });
+            let content = canonicalize(
+                raw_message
+                    .get(content.offset_header as usize..content.offset_end as usize)
+                    .unwrap_or_default(),
+            );
+            (Some(content), signature.map(|part| part.contents()))
+        }
+        (ctype, subtype)
+            if ctype.eq_ignore_ascii_case("application")
+                && (subtype.eq_ignore_ascii_case("pkcs7-mime")
+                    || subtype.eq_ignore_ascii_case("x-pkcs7-mime"))
+                && content_type
+                    .attribute("smime-type")
+                    .is_some_and(|t| t.eq_ignore_ascii_case("signed-data")) =>
+        {
+            (None, Some(root.contents()))
+        }
+        _ => return None,
+    };
+
+    let sender = message
+        .from()
+        .and_then(|from| from.first())
+        .and_then(|addr| addr.address())
+        .map(|addr| addr.to_lowercase());
+    let mut errors = Vec::new();
+    let status = match pkcs7 {
+        Some(pkcs7) => {
+            match verify_signed_data(
+                pkcs7,
+                content.as_deref(),
+                sender.as_deref(),
+                trust_anchors,
+                now as i64,
+            ) {
+                Ok(()) if trust_anchors.is_empty() => SmimeStatus::Signed,
+                Ok(()) => SmimeStatus::SignedVerified,
+                Err(Error::Unsupported(err)) => {
+                    errors.push(err.into_boxed_str());
+                    SmimeStatus::Unknown
+                }
+                Err(Error::Invalid(err)) => {
+                    errors.push(err.into_boxed_str());
+                    SmimeStatus::SignedFailed
+                }
+            }
+        }
+        None => {
+            errors.push("Signature part not found".into());
+            SmimeStatus::SignedFailed
+        }
+    };
+
+    Some(SmimeVerification {
+        status,
+        errors: errors.into_boxed_slice(),
+        verified_at: now,
+    })
+}
+
+fn verify_signed_data(
+    pkcs7: &[u8],
+    detached_content: Option<&[u8]>,
+    sender: Option<&str>,
+    trust_anchors: &[Certificate],
+    now: i64,
+) -> Result<()> {
+    let content_info = rasn::der::decode::<ContentInfo>(pkcs7)
+        .map_err(|err| Error::Invalid(format!("Failed to decode CMS structure: {err}")))?;
+    if *content_info.content_type != *CONTENT_SIGNED_DATA {
+        return Err(Error::Unsupported(format!(
+            "Unsupported CMS content type {}",
+            oid_to_string(&content_info.content_type)
+        )));
+    }
+    let signed_data = rasn::der::decode::<SignedData>(content_info.content.as_bytes())
+        .map_err(|err| Error::Invalid(format!("Failed to decode SignedData: {err}")))?;
+    let content = detached_content
+        .or_else(|| {
+            signed_data
+                .encap_content_info
+                .content
+                .as_ref()
+                .map(|c| c.as_ref())
+        })
+        .ok_or_else(|| Error::Invalid("Signed content not found".to_string()))?;
+    let certificates = signed_data
+        .certificates
+        .as_ref()
+        .map(|certs| {
+            certs
+                .to_vec()
+                .into_iter()
+                .filter_map(|cert| match cert {
+                    CertificateChoices::Certificate(cert) => Some(cert.as_ref()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let signer_infos = signed_data.signer_infos.to_vec();
+    if signer_infos.is_empty() {
+        return Err(Error::Invalid("Message has no signers".to_string()));
+    }
+
+    for signer_info in signer_infos {
+        let cert = certificates
+            .iter()
+            .find(|cert| is_signer_certificate(cert, &signer_info.sid))
+            .copied()
+            .ok_or_else(|| Error::Invalid("Signer certificate not found".to_string()))?;
+
+        verify_signer(signer_info, cert, content)?;
+        check_validity(cert, now)?;
+
+        if let Some(sender) = sender
+            && !certificate_emails(cert).any(|email| email.eq_ignore_ascii_case(sender))
+        {
+            return Err(Error::Invalid(format!(
+                "Signer certificate does not match sender address {sender}"
+            )));
+        }
+
+        if !trust_anchors.is_empty() {
+            verify_chain(cert, &certificates, trust_anchors, now)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_signer(signer_info: &SignerInfo, cert: &Certificate, content: &[u8]) -> Result<()> {
+    let digest_algorithm = &signer_info.digest_algorithm.algorithm;
+    let digest_alg = match &***digest_algorithm {
+        [1, 3, 14, 3, 2, 26] => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+        [2, 16, 840, 1, 101, 3, 4, 2, 1] => &digest::SHA256,
+        [2, 16, 840, 1, 101, 3, 4, 2, 2] => &digest::SHA384,
+        [2, 16, 840, 1, 101, 3, 4, 2, 3] => &digest::SHA512,
+        _ => {
+            return Err(Error::Unsupported(format!(
+                "Unsupported digest algorithm {}",
+                oid_to_string(digest_algorithm)
+            )));
+        }
+    };
+
+    let signed_attrs;
+    let message = if let Some(attrs) = &signer_info.signed_attrs {
+        // Signed attributes must include the digest of the content
+        let digest = digest::digest(digest_alg, content);
+        let message_digest = attrs
+            .to_vec()
+            .into_iter()
+            .find(|attr| &**attr.r#type == OID_MESSAGE_DIGEST)
+            .and_then(|attr| attr.values.to_vec().into_iter().next())
+            .and_then(|value| rasn::der::decode::<OctetString>(value.as_bytes()).ok())
+            .ok_or_else(|| Error::Invalid("Message digest attribute not found".to_string()))?;
+        if message_digest.as_ref() != digest.as_ref() {
+            return Err(Error::Invalid(
+                "Message digest does not match signed content".to_string(),
+            ));
+        }
+
+        // The signature is computed over the DER encoding of the attributes as a SET OF
+        signed_attrs = rasn::der::encode(attrs)
+            .map_err(|err| Error::Invalid(format!("Failed to encode signed attributes: {err}")))?;
+        signed_attrs.as_slice()
+    } else {
+        content
+    };
+
+    verify_signature(
+        cert,
+        &signer_info.signature_algorithm,
+        Some(digest_algorithm),
+        message,
+        signer_info.signature.as_ref(),
+    )
+    .map_err(|err| match err {
+        Error::Invalid(_) => Error::Invalid("Signature verification failed".to_string()),
+        err => err,
+    })
+}
+
+fn verify_chain(
+    cert: &Certificate,
+    intermediates: &[&Certificate],
+    trust_anchors: &[Certificate],
+    now: i64,
+) -> Result<()> {
+    let encode = |cert: &Certificate| {
+        rasn::der::encode(cert)
+            .map(CertificateDer::from)
+            .map_err(|err| Error::Invalid(format!("Failed to encode certificate: {err}")))
+    };
+    let cert = encode(cert)?;
+    let intermediates = intermediates
+        .iter()
+        .map(|cert| encode(cert))
+        .collect::<Result<Vec<_>>>()?;
+    let anchors = trust_anchors
+        .iter()
+        .map(encode)
+        .collect::<Result<Vec<_>>>()?;
+    let anchors = anchors
+        .iter()
+        .filter_map(|anchor| anchor_from_trusted_cert(anchor).ok())
+        .collect::<Vec<_>>();
+
+    // RFC 8550 - Section 4.4.4: when present, the extended key usage must
+    // allow the certificate to be used for email protection
+    EndEntityCert::try_from(&cert)
+        .map_err(|err| Error::Invalid(format!("Invalid signer certificate: {err}")))?
+        .verify_for_usage(
+            ALL_VERIFICATION_ALGS,
+            &anchors,
+            &intermediates,
+            UnixTime::since_unix_epoch(Duration::from_secs(now.max(0) as u64)),
+            KeyUsage::required_if_present(EKU_EMAIL_PROTECTION),
+            None,
+            None,
+        )
+        .map(|_| ())
+        .map_err(|err| match err {
+            webpki::Error::UnknownIssuer => Error::Invalid(
+                "Signer certificate is not issued by a trusted authority".to_string(),
+            ),
+            err => Error::Invalid(format!("Certificate chain validation failed: {err}")),
+        })
+}
+
+fn verify_signature(
+    cert: &Certificate,
+    algorithm: &AlgorithmIdentifier,
+    digest_algorithm: Option<&ObjectIdentifier>,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    let spki = &cert.tbs_certificate.subject_public_key_info;
+    let digest = digest_algorithm.map(|oid| &***oid);
+    let curve = spki
+        .algorithm
+        .parameters
+        .as_ref()
+        .and_then(|params| rasn::der::decode::<ObjectIdentifier>(params.as_bytes()).ok());
+    let curve = curve.as_ref().map(|oid| &***oid);
+
+    const SHA256: &[u32] = &[2, 16, 840, 1, 101, 3, 4, 2, 1];
+    const SHA384: &[u32] = &[2, 16, 840, 1, 101, 3, 4, 2, 2];
+    const SHA512: &[u32] = &[2, 16, 840, 1, 101, 3, 4, 2, 3];
+    const SHA1: &[u32] = &[1, 3, 14, 3, 2, 26];
+    const P256: &[u32] = &[1, 2, 840, 10045, 3, 1, 7];
+    const P384: &[u32] = &[1, 3, 132, 0, 34];
+
+    let verification_alg: &'static dyn VerificationAlgorithm =
+        match (&**algorithm.algorithm, digest) {
+            // RSA PKCS#1 v1.5
+            ([1, 2, 840, 113549, 1, 1, 5], _) | ([1, 2, 840, 113549, 1, 1, 1], Some(SHA1)) => {
+                &signature::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY
+            }
+            ([1, 2, 840, 113549, 1, 1, 11], _) | ([1, 2, 840, 113549, 1, 1, 1], Some(SHA256)) => {
+                &signature::RSA_PKCS1_2048_8192_SHA256
+            }
+            ([1, 2, 840, 113549, 1, 1, 12], _) | ([1, 2, 840, 113549, 1, 1, 1], Some(SHA384)) => {
+                &signature::RSA_PKCS1_2048_8192_SHA384
+            }
+            ([1, 2, 840, 113549, 1, 1, 13], _) | ([1, 2, 840, 113549, 1, 1, 1], Some(SHA512)) => {
+                &signature::RSA_PKCS1_2048_8192_SHA512
+            }
+            // ECDSA
+            ([1, 2, 840, 10045, 4, 3, 2], _) | ([1, 2, 840, 10045, 2, 1], Some(SHA256)) => {
+                match curve {
+                    Some(P256) => &signature::ECDSA_P256_SHA256_ASN1,
+                    Some(P384) => &signature::ECDSA_P384_SHA256_ASN1,
+                    _ => return Err(Error::Unsupported("Unsupported elliptic curve".to_string())),
+                }
+            }
+            ([1, 2, 840, 10045, 4, 3, 3], _) | ([1, 2, 840, 10045, 2, 1], Some(SHA384)) => {
+                match curve {
+                    Some(P256) => &signature::ECDSA_P256_SHA384_ASN1,
+                    Some(P384) => &signature::ECDSA_P384_SHA384_ASN1,
+                    _ => return Err(Error::Unsupported("Unsupported elliptic curve".to_string())),
+                }
+            }
+            // Ed25519
+            ([1, 3, 101, 112], _) => &signature::ED25519,
+            _ => {
+                return Err(Error::Unsupported(format!(
+                    "Unsupported signature algorithm {}",
+                    oid_to_string(&algorithm.algorithm)
+                )));
+            }
+        };
+
+    UnparsedPublicKey::new(verification_alg, spki.subject_public_key.as_raw_slice())
+        .verify(message, signature)
+        .map_err(|_| Error::Invalid("Invalid signature".to_string()))
+}
+
+fn is_signer_certificate(cert: &Certificate, sid: &SignerIdentifier) -> bool {
+    match sid {
+        SignerIdentifier::IssuerAndSerialNumber(sid) => {
+            cert.tbs_certificate.issuer == sid.issuer
+                && cert.tbs_certificate.serial_number == sid.serial_number
+        }
+        SignerIdentifier::SubjectKeyIdentifier(ski) => {
+            certificate_extension(cert, OID_SUBJECT_KEY_IDENTIFIER)
+                .and_then(|value| rasn::der::decode::<OctetString>(value).ok())
+                .is_some_and(|value| value.as_ref() == ski.as_ref())
+        }
+    }
+}
+
+fn check_validity(cert: &Certificate, now: i64) -> Result<()> {
+    let validity = &cert.tbs_certificate.validity;
+    if now < time_to_timestamp(&validity.not_before) {
+        Err(Error::Invalid("Certificate is not yet valid".to_string()))
+    } else if now > time_to_timestamp(&validity.not_after) {
+        Err(Error::Invalid("Certificate has expired".to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+fn certificate_emails(cert: &Certificate) -> impl Iterator<Item = String> {
+    certificate_extension(cert, OID_SUBJECT_ALT_NAME)
+        .and_then(|value| rasn::der::decode::<SubjectAltName>(value).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|name| match name {
+            GeneralName::Rfc822Name(email) => Some(email.to_string()),
+            _ => None,
+        })
+}
+
+fn certificate_extension<'x>(cert: &'x Certificate, oid: &[u32]) -> Option<&'x [u8]> {
+    cert.tbs_certificate
+        .extensions
+        .as_ref()?
+        .iter()
+        .find(|ext| &**ext.extn_id == oid)
+        .map(|ext| ext.extn_value.as_ref())
+}
+
+fn time_to_timestamp(time: &Time) -> i64 {
+    match time {
+        Time::Utc(time) => time.timestamp(),
+        Time::General(time) => time.timestamp(),
+    }
+}
+
+fn oid_to_string(oid: &ObjectIdentifier) -> String {
+    oid.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Converts bare LF line endings to CRLF, as signatures are computed over the
+/// canonical form of the content.
+fn canonicalize(content: &[u8]) -> Cow<'_, [u8]> {
+    if content
+        .iter()
+        .enumerate()
+        .any(|(pos, &ch)| ch == b'\n' && (pos == 0 || content[pos - 1] != b'\r'))
+    {
+        let mut canonical = Vec::with_capacity(content.len() + 64);
+        let mut last_ch = 0;
+        for &ch in content {
+            if ch == b'\n' && last_ch != b'\r' {
+                canonical.push(b'\r');
+            }
+            canonical.push(ch);
+            last_ch = ch;
+        }
+        Cow::Owned(canonical)
+    } else {
+        Cow::Borrowed(content)
+    }
+}
@@ -0,0 +1,536 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::metadata::{ArchivedMessageMetadata, ArchivedMetadataPartType, MessageMetadata};
+use crate::{
+    cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess},
+    mailbox::INBOX_ID,
+    message::metadata::DecodedParts,
+};
+use aws_lc_rs::hmac;
+use common::Server;
+use mail_parser::DateTime;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{
+    collection::Collection,
+    field::{EmailField, MailboxField},
+};
+use utils::{HexEncode, chained_bytes::ChainedBytes};
+
+pub const URLAUTH_MECHANISM: &str = "INTERNAL";
+
+// IMAP URL as defined in RFC 5092 with the URLAUTH extensions from RFC 4467
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImapUrl {
+    pub user: String,
+    pub host: String,
+    pub mailbox: String,
+    pub uid_validity: Option<u32>,
+    pub uid: u32,
+    pub section: Option<String>,
+    pub partial: Option<(u32, Option<u32>)>,
+    pub expire: Option<i64>,
+    pub auth: Option<UrlAuth>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlAuth {
+    pub access: UrlAccess,
+    pub rump: String,
+    pub mechanism: Option<String>,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlAccess {
+    Anonymous,
+    AuthUser,
+    User(String),
+    Submit(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UrlRequester<'x> {
+    pub account_id: u32,
+    pub name: &'x str,
+    pub is_submit: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImapUrlTarget {
+    pub account_id: u32,
+    pub mailbox_id: u32,
+    pub document_id: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlFetchError {
+    NotFound,
+    Expired,
+    Unauthorized,
+    InvalidSection,
+}
+
+pub trait UrlAuthFetch: Sync + Send {
+    fn mailbox_access_key(
+        &self,
+        account_id: u32,
+        mailbox_id: u32,
+        create: bool,
+    ) -> impl Future<Output = trc::Result<Option<String>>> + Send;
+
+    fn reset_mailbox_access_keys(
+        &self,
+        account_id: u32,
+        mailbox_ids: impl IntoIterator<Item = u32> + Send,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+
+    fn resolve_imap_url(
+        &self,
+        url: &ImapUrl,
+    ) -> impl Future<Output = trc::Result<Option<ImapUrlTarget>>> + Send;
+
+    fn fetch_imap_url(
+        &self,
+        url: &ImapUrl,
+        requester: UrlRequester<'_>,
+    ) -> impl Future<Output = trc::Result<Result<Vec<u8>, UrlFetchError>>> + Send;
+}
+
+impl UrlAuthFetch for Server {
+    async fn mailbox_access_key(
+        &self,
+        account_id: u32,
+        mailbox_id: u32,
+        create: bool,
+    ) -> trc::Result<Option<String>> {
+        let key = self
+            .store()
+            .get_value::<String>(ValueKey::property(
+                account_id,
+                Collection::Mailbox,
+                mailbox_id,
+                MailboxField::AccessKey,
+            ))
+            .await
+            .caused_by(trc::location!())?;
+
+        if key.is_some() || !create {
+            return Ok(key);
+        }
+
+        let key = rand::random::<[u8; 32]>().hex_encode();
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Mailbox)
+            .with_document(mailbox_id)
+            .set(MailboxField::AccessKey, key.as_bytes());
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(Some(key))
+    }
+
+    async fn reset_mailbox_access_keys(
+        &self,
+        account_id: u32,
+        mailbox_ids: impl IntoIterator<Item = u32> + Send,
+    ) -> trc::Result<()> {
+        // Removing the key revokes all the URLs issued so far, a new one is generated on demand
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Mailbox);
+        for mailbox_id in mailbox_ids {
+            batch
+                .with_document(mailbox_id)
+                .clear(MailboxField::AccessKey);
+        }
+
+        if !batch.is_empty() {
+            self.store()
+                .write(batch.build_all())
+                .await
+                .caused_by(trc::location!())?;
+        }
+
+        Ok(())
+    }
+
+    async fn resolve_imap_url(&self, url: &ImapUrl) -> trc::Result<Option<ImapUrlTarget>> {
+        let Some(account_id) = self
+            .account_id_from_email(&url.user, false)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+        let cache = self
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let mailbox = if url.mailbox.eq_ignore_ascii_case("INBOX") {
+            cache.mailbox_by_id(&INBOX_ID)
+        } else {
+            cache.mailbox_by_path(&url.mailbox)
+        };
+        let Some(mailbox) =
+            mailbox.filter(|m| url.uid_validity.is_none_or(|v| v == m.uid_validity))
+        else {
+            return Ok(None);
+        };
+
+        Ok(cache
+            .in_mailbox(mailbox.document_id)
+            .find(|m| {
+                m.mailboxes
+                    .iter()
+                    .any(|m| m.mailbox_id == mailbox.document_id && m.uid == url.uid)
+            })
+            .map(|m| ImapUrlTarget {
+                account_id,
+                mailbox_id: mailbox.document_id,
+                document_id: m.document_id,
+            }))
+    }
+
+    async fn fetch_imap_url(
+        &self,
+        url: &ImapUrl,
+        requester: UrlRequester<'_>,
+    ) -> trc::Result<Result<Vec<u8>, UrlFetchError>> {
+        let Some(target) = self.resolve_imap_url(url).await? else {
+            return Ok(Err(UrlFetchError::NotFound));
+        };
+
+        // Validate authorization
+        if let Some(auth) = &url.auth {
+            if url.expire.is_some_and(|expire| expire <= now() as i64) {
+                return Ok(Err(UrlFetchError::Expired));
+            } else if !auth.access.allows(&requester) {
+                return Ok(Err(UrlFetchError::Unauthorized));
+            }
+            match self
+                .mailbox_access_key(target.account_id, target.mailbox_id, false)
+                .await?
+            {
+                Some(key) if auth.verify(key.as_bytes()) => {}
+                _ => return Ok(Err(UrlFetchError::Unauthorized)),
+            }
+        } else if target.account_id != requester.account_id {
+            // URLs without URLAUTH can only reference the requester's own messages
+            return Ok(Err(UrlFetchError::Unauthorized));
+        }
+
+        // Obtain message
+        let Some(metadata_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                target.account_id,
+                Collection::Email,
+                target.document_id,
+                EmailField::Metadata,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(Err(UrlFetchError::NotFound));
+        };
+        let metadata = metadata_
+            .unarchive::<MessageMetadata>()
+            .caused_by(trc::location!())?;
+        let Some(raw_body) = self
+            .blob_store()
+            .get_blob(metadata.blob_hash.0.as_slice(), 0..usize::MAX)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(Err(UrlFetchError::NotFound));
+        };
+        let mut raw_message = ChainedBytes::new(metadata.raw_headers.as_ref());
+        raw_message.append(
+            raw_body
+                .get(metadata.blob_body_offset.to_native() as usize..)
+                .unwrap_or_default(),
+        );
+        let decoded = metadata.decode_contents(raw_message);
+        let Some(mut contents) = metadata.section(&decoded, url.section.as_deref()) else {
+            return Ok(Err(UrlFetchError::InvalidSection));
+        };
+
+        if let Some((start, length)) = url.partial {
+            let start = (start as usize).min(contents.len());
+            let end = length.map_or(contents.len(), |length| {
+                start.saturating_add(length as usize).min(contents.len())
+            });
+            contents = contents[start..end].to_vec();
+        }
+
+        Ok(Ok(contents))
+    }
+}
+
+impl ArchivedMessageMetadata {
+    fn section(&self, decoded: &DecodedParts<'_>, section: Option<&str>) -> Option<Vec<u8>> {
+        let mut message = &self.contents[0];
+        let mut message_id = 0;
+        let mut part = message.root_part();
+        let Some(section) = section.filter(|section| !section.is_empty()) else {
+            return decoded
+                .raw_message_section(0, part.header_to_end())
+                .map(|bytes| bytes.into_owned());
+        };
+
+        let mut items = section.split('.').peekable();
+        while let Some(item) = items.next() {
+            if let Ok(num) = item.parse::<usize>() {
+                part = if let Some(sub_part_ids) = part.sub_parts() {
+                    sub_part_ids
+                        .as_ref()
+                        .get(num.checked_sub(1)?)
+                        .and_then(|pos| message.parts.as_ref().get(u16::from(*pos) as usize))
+                } else if num == 1 && (items.peek().is_none() || part.is_message()) {
+                    Some(part)
+                } else {
+                    None
+                }?;
+
+                if let ArchivedMetadataPartType::Message(nested_message_id) = &part.body
+                    && items.peek().is_some()
+                {
+                    message = self.message_id(*nested_message_id);
+                    part = message.root_part();
+                    message_id = u16::from(nested_message_id) as usize;
+                }
+            } else if items.peek().is_none() {
+                let range =
+                    if item.eq_ignore_ascii_case("HEADER") || item.eq_ignore_ascii_case("MIME") {
+                        part.header_to_body()
+                    } else if item.eq_ignore_ascii_case("TEXT") {
+                        part.body_to_end()
+                    } else {
+                        return None;
+                    };
+
+                return decoded
+                    .raw_message_section(message_id, range)
+                    .map(|bytes| bytes.into_owned());
+            } else {
+                return None;
+            }
+        }
+
+        decoded
+            .raw_message_section(message_id, part.body_to_end())
+            .map(|bytes| bytes.into_owned())
+    }
+}
+
+impl ImapUrl {
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let url_lcase = url.to_ascii_lowercase();
+        if !url_lcase.starts_with("imap://") {
+            return None;
+        }
+
+        // URLAUTH and EXPIRE can only appear at the end of the URL
+        let (path_end, auth) = if let Some(pos) = url_lcase.find(";urlauth=") {
+            let access_start = pos + 9;
+            let (access_end, verifier) = match url[access_start..].find(':') {
+                Some(idx) => (access_start + idx, Some(&url[access_start + idx + 1..])),
+                None => (url.len(), None),
+            };
+            let (mechanism, token) = match verifier {
+                Some(verifier) => {
+                    let (mechanism, token) = verifier.split_once(':')?;
+                    (
+                        Some(mechanism.to_ascii_uppercase()),
+                        Some(token.to_ascii_lowercase()),
+                    )
+                }
+                None => (None, None),
+            };
+
+            (
+                pos,
+                Some(UrlAuth {
+                    access: UrlAccess::parse(&url[access_start..access_end])?,
+                    rump: url[..access_end].to_string(),
+                    mechanism,
+                    token,
+                }),
+            )
+        } else {
+            (url.len(), None)
+        };
+        let (path_end, expire) = if let Some(pos) = url_lcase[..path_end].find(";expire=") {
+            (
+                pos,
+                Some(DateTime::parse_rfc3339(&url[pos + 8..path_end])?.to_timestamp()),
+            )
+        } else {
+            (path_end, None)
+        };
+
+        // Parse server
+        let (authority, path) = url[7..path_end].split_once('/')?;
+        let (user, host) = authority.rsplit_once('@')?;
+        let user = decode_url_part(user.split(';').next()?)?;
+        let host = host
+            .rsplit_once(':')
+            .filter(|(_, port)| port.chars().all(|ch| ch.is_ascii_digit()))
+            .map_or(host, |(host, _)| host)
+            .to_ascii_lowercase();
+        if user.is_empty() || host.is_empty() {
+            return None;
+        }
+
+        // Parse mailbox and UIDVALIDITY
+        let path_lcase = path.to_ascii_lowercase();
+        let uid_pos = path_lcase.find("/;uid=")?;
+        let (mailbox, uid_validity) = match path_lcase[..uid_pos].find(";uidvalidity=") {
+            Some(pos) => (
+                &path[..pos],
+                Some(path[pos + 13..uid_pos].parse::<u32>().ok()?),
+            ),
+            None => (&path[..uid_pos], None),
+        };
+        let mailbox = decode_url_part(mailbox)?;
+        if mailbox.is_empty() {
+            return None;
+        }
+
+        // Parse UID, SECTION and PARTIAL
+        let mut uid = 0;
+        let mut section = None;
+        let mut partial = None;
+        for (pos, param) in path[uid_pos + 1..].split('/').enumerate() {
+            let (name, value) = param.strip_prefix(';')?.split_once('=')?;
+            let name = name.to_ascii_lowercase();
+            match (pos, name.as_str()) {
+                (0, "uid") => {
+                    uid = value.parse::<u32>().ok().filter(|uid| *uid > 0)?;
+                }
+                (1, "section") => {
+                    section = Some(decode_url_part(value)?);
+                }
+                (1 | 2, "partial") if partial.is_none() => {
+                    partial = Some(match value.split_once('.') {
+                        Some((start, length)) => (
+                            start.parse::<u32>().ok()?,
+                            Some(length.parse::<u32>().ok().filter(|length| *length > 0)?),
+                        ),
+                        None => (value.parse::<u32>().ok()?, None),
+                    });
+                }
+                _ => return None,
+            }
+        }
+
+        Some(ImapUrl {
+            user,
+            host,
+            mailbox,
+            uid_validity,
+            uid,
+            section,
+            partial,
+            expire,
+            auth,
+        })
+    }
+}
+
+impl UrlAuth {
+    pub fn generate_token(&self, access_key: &[u8]) -> String {
+        hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, access_key),
+            self.rump.as_bytes(),
+        )
+        .as_ref()
+        .hex_encode()
+    }
+
+    pub fn verify(&self, access_key: &[u8]) -> bool {
+        match (&self.mechanism, &self.token) {
+            (Some(mechanism), Some(token)) if mechanism == URLAUTH_MECHANISM => decode_hex(token)
+                .is_some_and(|token| {
+                    hmac::verify(
+                        &hmac::Key::new(hmac::HMAC_SHA256, access_key),
+                        self.rump.as_bytes(),
+                        &token,
+                    )
+                    .is_ok()
+                }),
+            _ => false,
+        }
+    }
+}
+
+impl UrlAccess {
+    pub fn parse(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("anonymous") {
+            Some(UrlAccess::Anonymous)
+        } else if value.eq_ignore_ascii_case("authuser") {
+            Some(UrlAccess::AuthUser)
+        } else {
+            let (application, user) = value.split_once('+')?;
+            let user = decode_url_part(user).filter(|user| !user.is_empty())?;
+            if application.eq_ignore_ascii_case("user") {
+                Some(UrlAccess::User(user))
+            } else if application.eq_ignore_ascii_case("submit") {
+                Some(UrlAccess::Submit(user))
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn allows(&self, requester: &UrlRequester<'_>) -> bool {
+        match self {
+            UrlAccess::Anonymous | UrlAccess::AuthUser => true,
+            UrlAccess::User(user) => {
+                !requester.is_submit && user.eq_ignore_ascii_case(requester.name)
+            }
+            UrlAccess::Submit(user) => {
+                requester.is_submit && user.eq_ignore_ascii_case(requester.name)
+            }
+        }
+    }
+}
+
+fn decode_url_part(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(ch) = iter.next() {
+        if ch == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(ch);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if value.len().is_multiple_of(2) {
+        (0..value.len())
+            .step_by(2)
+            .map(|pos| u8::from_str_radix(value.get(pos..pos + 2)?, 16).ok())
+            .collect()
+    } else {
+        None
+    }
+}
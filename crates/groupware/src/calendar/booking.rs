@@ -0,0 +1,245 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    cache::GroupwareCache,
+    calendar::{CalendarEvent, CalendarEventData, expand::CalendarEventExpansion},
+    strip_mailto_scheme,
+};
+use ahash::AHashSet;
+use calcard::{
+    common::timezone::Tz,
+    icalendar::{
+        ArchivedICalendarComponentType, ArchivedICalendarParameterValue,
+        ArchivedICalendarParticipationStatus, ArchivedICalendarProperty, ArchivedICalendarStatus,
+        ArchivedICalendarTransparency, ArchivedICalendarValue, ICalendarComponentType,
+        ICalendarParameterName, ICalendarParticipationStatus,
+    },
+};
+use common::{Server, auth::AccountInfo};
+use registry::schema::{
+    enums::BookingPolicy,
+    structs::{Account, ResourceAccount},
+};
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, now},
+};
+use trc::AddContext;
+use types::{
+    TimeRange,
+    collection::{Collection, SyncCollection},
+    id::Id,
+};
+use utils::sanitize_email;
+
+pub trait ResourceBooking: Sync + Send {
+    fn resource_booking_status(
+        &self,
+        account_info: &AccountInfo,
+        booker_id: Option<u32>,
+        event: &CalendarEventData,
+        document_id: Option<u32>,
+    ) -> impl Future<Output = trc::Result<Option<ICalendarParticipationStatus>>> + Send;
+}
+
+impl ResourceBooking for Server {
+    async fn resource_booking_status(
+        &self,
+        account_info: &AccountInfo,
+        booker_id: Option<u32>,
+        event: &CalendarEventData,
+        document_id: Option<u32>,
+    ) -> trc::Result<Option<ICalendarParticipationStatus>> {
+        let account_id = account_info.account_id();
+        let Some(resource) = self
+            .registry()
+            .object::<Account>(Id::from(account_id))
+            .await
+            .caused_by(trc::location!())?
+            .and_then(|account| account.into_resource())
+        else {
+            return Ok(None);
+        };
+
+        if resource.booking_policy == BookingPolicy::Manual {
+            return Ok(None);
+        } else if !is_allowed_booker(self, &resource, booker_id).await? {
+            return Ok(Some(ICalendarParticipationStatus::Declined));
+        }
+
+        // Obtain the requested instances
+        let now = now() as i64;
+        let instances = event
+            .expand(Tz::Floating, TimeRange::default())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|instance| {
+                instance.end > now
+                    && event.event.components[instance.comp_id as usize].component_type
+                        == ICalendarComponentType::VEvent
+            })
+            .collect::<Vec<_>>();
+        if instances.is_empty() {
+            return Ok(Some(ICalendarParticipationStatus::Accepted));
+        }
+
+        // Validate the booking window and maximum duration
+        if let Some(booking_window) = &resource.booking_window
+            && instances
+                .iter()
+                .any(|instance| instance.start > now + booking_window.as_secs() as i64)
+        {
+            return Ok(Some(ICalendarParticipationStatus::Declined));
+        }
+        if let Some(max_duration) = &resource.max_duration
+            && instances
+                .iter()
+                .any(|instance| instance.end - instance.start > max_duration.as_secs() as i64)
+        {
+            return Ok(Some(ICalendarParticipationStatus::Declined));
+        }
+
+        if resource.booking_policy == BookingPolicy::AcceptIfFree
+            && has_booking_conflict(self, account_info, &instances, document_id).await?
+        {
+            Ok(Some(ICalendarParticipationStatus::Declined))
+        } else {
+            Ok(Some(ICalendarParticipationStatus::Accepted))
+        }
+    }
+}
+
+async fn is_allowed_booker(
+    server: &Server,
+    resource: &ResourceAccount,
+    booker_id: Option<u32>,
+) -> trc::Result<bool> {
+    if resource.allowed_booker_ids.is_empty() {
+        return Ok(true);
+    }
+    let Some(booker_id) = booker_id else {
+        return Ok(false);
+    };
+    let booker = server
+        .account(booker_id)
+        .await
+        .caused_by(trc::location!())?;
+
+    Ok(resource.allowed_booker_ids.iter().any(|id| {
+        let id = id.document_id();
+        id == booker_id || booker.id_member_of.contains(&id)
+    }))
+}
+
+async fn has_booking_conflict(
+    server: &Server,
+    account_info: &AccountInfo,
+    instances: &[CalendarEventExpansion],
+    document_id: Option<u32>,
+) -> trc::Result<bool> {
+    let account_id = account_info.account_id();
+    let range = TimeRange {
+        start: instances.iter().map(|i| i.start).min().unwrap_or_default(),
+        end: instances.iter().map(|i| i.end).max().unwrap_or_default(),
+    };
+    let resources = server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+        .await
+        .caused_by(trc::location!())?;
+
+    for resource in resources.resources.iter().filter(|r| {
+        !r.is_container()
+            && Some(r.document_id) != document_id
+            && r.event_time_range()
+                .is_some_and(|(start, end)| range.is_in_range(false, start, end))
+    }) {
+        let Some(archive) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::CalendarEvent,
+                resource.document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        let event = archive
+            .unarchive::<CalendarEvent>()
+            .caused_by(trc::location!())?;
+
+        // Only opaque events that the resource has not declined block the booking
+        let mut busy_component_ids = AHashSet::new();
+        'next_component: for (component_id, component) in
+            event.data.event.components.iter().enumerate()
+        {
+            if !matches!(
+                component.component_type,
+                ArchivedICalendarComponentType::VEvent
+            ) {
+                continue;
+            }
+
+            for entry in component.entries.iter() {
+                match (&entry.name, entry.values.first()) {
+                    (
+                        ArchivedICalendarProperty::Status,
+                        Some(ArchivedICalendarValue::Status(ArchivedICalendarStatus::Cancelled)),
+                    )
+                    | (
+                        ArchivedICalendarProperty::Transp,
+                        Some(ArchivedICalendarValue::Transparency(
+                            ArchivedICalendarTransparency::Transparent,
+                        )),
+                    ) => continue 'next_component,
+                    (ArchivedICalendarProperty::Attendee, Some(value))
+                        if value
+                            .as_text()
+                            .and_then(|attendee| sanitize_email(strip_mailto_scheme(attendee)))
+                            .is_some_and(|attendee| {
+                                account_info.addresses().contains(&attendee)
+                            })
+                            && entry.parameters(&ICalendarParameterName::Partstat).any(
+                                |partstat| {
+                                    matches!(
+                                        partstat,
+                                        ArchivedICalendarParameterValue::Partstat(
+                                            ArchivedICalendarParticipationStatus::Declined
+                                        )
+                                    )
+                                },
+                            ) =>
+                    {
+                        continue 'next_component;
+                    }
+                    _ => (),
+                }
+            }
+
+            busy_component_ids.insert(component_id as u32);
+        }
+
+        if !busy_component_ids.is_empty()
+            && event
+                .data
+                .expand(Tz::Floating, range)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|busy| busy_component_ids.contains(&busy.comp_id))
+                .any(|busy| {
+                    instances
+                        .iter()
+                        .any(|instance| busy.start < instance.end && busy.end > instance.start)
+                })
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
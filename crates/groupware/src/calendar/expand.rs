@@ -102,6 +102,84 @@ impl ArchivedCalendarEventData {
 }
 
 impl CalendarEventData {
+    pub fn expand(&self, default_tz: Tz, limit: TimeRange) -> Option<Vec<CalendarEventExpansion>> {
+        let mut expansion = Vec::with_capacity(self.time_ranges.len());
+        let base_offset = self.base_offset;
+        let mut base_expansion_id = 0;
+
+        'outer: for range in self.time_ranges.iter() {
+            let instances = range.instances.as_ref();
+            let (offset_or_count, bytes_read) = instances.read_leb128::<u32>()?;
+
+            let comp_id = range.id as u32;
+            let duration = range.duration as i64;
+            let mut start_tz = Tz::from_id(range.start_tz)?;
+            let mut end_tz = Tz::from_id(range.end_tz)?;
+            let is_todo = self.event.components[comp_id as usize]
+                .component_type
+                .is_todo();
+
+            if start_tz.is_floating() && !default_tz.is_floating() {
+                start_tz = default_tz;
+            }
+            if end_tz.is_floating() && !default_tz.is_floating() {
+                end_tz = default_tz;
+            }
+
+            if instances.len() > bytes_read {
+                // Recurring event
+                let unpacker =
+                    BitpackIterator::from_bytes_and_offset(instances, bytes_read, offset_or_count);
+                let mut expansion_id = base_expansion_id;
+                base_expansion_id += offset_or_count;
+                for start_offset in unpacker {
+                    let start_date_naive = start_offset as i64 + base_offset;
+                    let end_date_naive = start_date_naive + duration;
+                    let (Some(start), Some(end)) = (
+                        resolve_local(start_tz, start_date_naive),
+                        resolve_local(end_tz, end_date_naive),
+                    ) else {
+                        expansion_id += 1;
+                        continue;
+                    };
+
+                    if limit.is_in_range(is_todo, start, end) {
+                        expansion.push(CalendarEventExpansion {
+                            comp_id,
+                            expansion_id,
+                            start,
+                            end,
+                        });
+                    } else if start > limit.end {
+                        continue 'outer;
+                    }
+
+                    expansion_id += 1;
+                }
+            } else {
+                // Single event
+                let start_date_naive = offset_or_count as i64 + base_offset;
+                let end_date_naive = start_date_naive + duration;
+                if let (Some(start), Some(end)) = (
+                    resolve_local(start_tz, start_date_naive),
+                    resolve_local(end_tz, end_date_naive),
+                ) && limit.is_in_range(is_todo, start, end)
+                {
+                    expansion.push(CalendarEventExpansion {
+                        comp_id,
+                        expansion_id: base_expansion_id,
+                        start,
+                        end,
+                    });
+                }
+
+                base_expansion_id += 1;
+            }
+        }
+
+        Some(expansion)
+    }
+
     pub fn expand_from_ids(
         &self,
         expansion_ids: &mut AHashSet<u32>,
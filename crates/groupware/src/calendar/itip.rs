@@ -9,12 +9,13 @@ use crate::{
     cache::GroupwareCache,
     calendar::{
         CalendarEvent, CalendarEventData, CalendarEventNotification, ChangedBy,
-        EVENT_NOTIFICATION_IS_CHANGE,
+        EVENT_NOTIFICATION_IS_CHANGE, booking::ResourceBooking,
     },
     scheduling::{
         ItipError, ItipMessage,
         inbound::{
-            MergeResult, itip_import_message, itip_merge_changes, itip_method, itip_process_message,
+            MergeResult, itip_booking_reply, itip_import_message, itip_merge_changes, itip_method,
+            itip_process_message,
         },
         snapshot::itip_snapshot,
     },
@@ -146,6 +147,11 @@ impl ItipIngest for Server {
         } else {
             ChangedBy::CalendarAddress(sender.into())
         };
+        let booker_id = if let ChangedBy::PrincipalId(id) = &changed_by {
+            Some(*id)
+        } else {
+            None
+        };
 
         // Find event by UID
         let account_id = account_info.account_id();
@@ -182,6 +188,9 @@ impl ItipIngest for Server {
                 // Process the iTIP message
                 let snapshots = itip_snapshot(&event.data.event, account_info.addresses(), false)?;
                 let is_organizer_update = !itip_snapshots.organizer.email.is_local;
+                let is_booking_request = is_organizer_update
+                    && account_info.is_resource_account()
+                    && itip_method(&itip)? == &ICalendarMethod::Request;
                 match itip_process_message(
                     &event.data.event,
                     snapshots,
@@ -224,6 +233,28 @@ impl ItipIngest for Server {
                             self.core.groupware.max_ical_instances,
                             &mut next_email_alarm,
                         );
+
+                        // Automatically accept or decline the updated booking
+                        let booking_reply = if is_booking_request
+                            && let Some(part_stat) = self
+                                .resource_booking_status(
+                                    account_info,
+                                    booker_id,
+                                    &event.data,
+                                    Some(document_id),
+                                )
+                                .await?
+                        {
+                            itip_booking_reply(
+                                &mut event.data.event,
+                                account_info.addresses(),
+                                part_stat,
+                            )
+                            .ok()
+                        } else {
+                            None
+                        };
+
                         if is_organizer_update {
                             if let Some(schedule_tag) = &mut event.schedule_tag {
                                 *schedule_tag += 1;
@@ -280,7 +311,7 @@ impl ItipIngest for Server {
                             .caused_by(trc::location!())?;
                         self.commit_batch(batch).await.caused_by(trc::location!())?;
 
-                        Ok(None)
+                        Ok(booking_reply)
                     }
                     MergeResult::Message(itip_message) => Ok(Some(itip_message)),
                     MergeResult::None => Ok(None),
@@ -289,8 +320,9 @@ impl ItipIngest for Server {
                 Err(ItipIngestError::Message(ItipError::EventNotFound))
             }
         } else {
-            // Verify that auto-adding invitations is allowed
+            // Verify that auto-adding invitations is allowed (resources apply their booking policy)
             if !self.core.groupware.itip_auto_add
+                && !account_info.is_resource_account()
                 && !matches!(changed_by, ChangedBy::PrincipalId(_))
                 && !self
                     .document_exists(
@@ -335,7 +367,7 @@ impl ItipIngest for Server {
             // Build event
             let mut next_email_alarm = None;
             let now = now() as i64;
-            let event = CalendarEvent {
+            let mut event = CalendarEvent {
                 names: vec![DavName {
                     name: format!("{}_{}.ics", now, rand::random::<u64>()),
                     parent_id,
@@ -351,6 +383,17 @@ impl ItipIngest for Server {
                 ..Default::default()
             };
 
+            // Automatically accept or decline the booking
+            let booking_reply = if account_info.is_resource_account()
+                && let Some(part_stat) = self
+                    .resource_booking_status(account_info, booker_id, &event.data, None)
+                    .await?
+            {
+                itip_booking_reply(&mut event.data.event, account_info.addresses(), part_stat).ok()
+            } else {
+                None
+            };
+
             // Obtain document ids
             let document_id = self
                 .store()
@@ -391,7 +434,7 @@ impl ItipIngest for Server {
                 .caused_by(trc::location!())?;
             self.commit_batch(batch).await.caused_by(trc::location!())?;
 
-            Ok(None)
+            Ok(booking_reply)
         }
     }
 
@@ -5,6 +5,7 @@
  */
 
 pub mod alarm;
+pub mod booking;
 pub mod dates;
 pub mod expand;
 pub mod index;
@@ -16,7 +17,7 @@ use calcard::icalendar::{
 };
 use common::{DavName, auth::AccessToken};
 use types::{acl::AclGrant, dead_property::DeadProperty};
-use utils::map::bitmap::BitmapItem;
+use utils::map::bitmap::{Bitmap, BitmapItem};
 
 #[derive(
     rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
@@ -245,6 +246,13 @@ impl Calendar {
 }
 
 impl ArchivedCalendar {
+    pub fn supports_tasks(&self) -> bool {
+        let supported_components = self.supported_components.to_native();
+        supported_components == 0
+            || Bitmap::<SupportedComponent>::from(supported_components)
+                .contains(SupportedComponent::VTodo)
+    }
+
     pub fn default_alerts(
         &self,
         access_token: &AccessToken,
@@ -331,6 +339,14 @@ impl CalendarEvent {
 }
 
 impl ArchivedCalendarEvent {
+    pub fn is_task(&self) -> bool {
+        self.data
+            .event
+            .components
+            .iter()
+            .any(|component| component.component_type.is_todo())
+    }
+
     pub fn preferences(&self, access_token: &AccessToken) -> Option<&ArchivedEventPreferences> {
         self.preferences
             .iter()
@@ -5,7 +5,17 @@
  */
 
 use super::{ArchivedFileNode, FileNode};
+use ahash::AHashSet;
 use common::storage::index::{IndexValue, IndexableAndSerializableObject, IndexableObject};
+use nlp::language::{
+    Language,
+    detect::{LanguageDetector, MIN_LANGUAGE_SCORE},
+};
+use store::{
+    search::{FileSearchField, IndexDocument, SearchField},
+    write::SearchIndex,
+    xxhash_rust::xxh3,
+};
 use types::{acl::AclGrant, collection::SyncCollection};
 
 impl IndexableObject for FileNode {
@@ -26,9 +36,16 @@ impl IndexableObject for FileNode {
         ]);
 
         if let Some(file) = &self.file {
-            values.extend([IndexValue::Blob {
-                value: file.blob_hash.clone(),
-            }]);
+            values.extend([
+                IndexValue::Blob {
+                    value: file.blob_hash.clone(),
+                },
+                IndexValue::SearchIndex {
+                    index: SearchIndex::File,
+                    hash: xxh3::xxh3_64(self.name.as_bytes())
+                        ^ xxh3::xxh3_64(file.blob_hash.as_slice()),
+                },
+            ]);
         }
 
         values.into_iter()
@@ -58,9 +75,16 @@ impl IndexableObject for &ArchivedFileNode {
         ]);
 
         if let Some(file) = self.file.as_ref() {
-            values.extend([IndexValue::Blob {
-                value: (&file.blob_hash).into(),
-            }]);
+            values.extend([
+                IndexValue::Blob {
+                    value: (&file.blob_hash).into(),
+                },
+                IndexValue::SearchIndex {
+                    index: SearchIndex::File,
+                    hash: xxh3::xxh3_64(self.name.as_bytes())
+                        ^ xxh3::xxh3_64(file.blob_hash.0.as_slice()),
+                },
+            ]);
         }
 
         values.into_iter()
@@ -84,6 +108,45 @@ impl FileNode {
 }
 
 impl ArchivedFileNode {
+    pub fn index_document(
+        &self,
+        account_id: u32,
+        document_id: u32,
+        contents: Option<&str>,
+        index_fields: &AHashSet<SearchField>,
+        default_language: Language,
+    ) -> IndexDocument {
+        let mut document = IndexDocument::new(SearchIndex::File)
+            .with_account_id(account_id)
+            .with_document_id(document_id);
+        let mut detector = LanguageDetector::new();
+
+        if index_fields.is_empty()
+            || index_fields.contains(&SearchField::File(FileSearchField::Name))
+        {
+            document.index_text(FileSearchField::Name, self.name.as_str(), Language::None);
+            if let Some(display_name) = self.display_name.as_ref() {
+                document.index_text(FileSearchField::Name, display_name.as_str(), Language::None);
+            }
+        }
+
+        if let Some(contents) = contents
+            && (index_fields.is_empty()
+                || index_fields.contains(&SearchField::File(FileSearchField::Content)))
+        {
+            detector.detect(contents, MIN_LANGUAGE_SCORE);
+            document.index_text(FileSearchField::Content, contents, Language::Unknown);
+        }
+
+        document.set_unknown_language(
+            detector
+                .most_frequent_language()
+                .unwrap_or(default_language),
+        );
+
+        document
+    }
+
     pub fn size(&self) -> usize {
         self.dead_properties.size()
             + self.display_name.as_ref().map_or(0, |n| n.len())
@@ -5,13 +5,19 @@
  */
 
 use crate::scheduling::{
-    InstanceId, ItipError, ItipMessage, ItipSnapshots, organizer::organizer_request_full,
+    InstanceId, ItipError, ItipMessage, ItipSnapshots, ItipSummary,
+    itip::{ItipExportAs, itip_add_tz, itip_build_envelope, itip_export_component},
+    organizer::organizer_request_full,
+    snapshot::itip_snapshot,
 };
 use ahash::AHashSet;
-use calcard::icalendar::{
-    ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarEntry, ICalendarMethod,
-    ICalendarParameter, ICalendarParameterName, ICalendarProperty, ICalendarStatus, ICalendarValue,
-    Uri,
+use calcard::{
+    common::PartialDateTime,
+    icalendar::{
+        ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarEntry, ICalendarMethod,
+        ICalendarParameter, ICalendarParameterName, ICalendarParticipationStatus,
+        ICalendarProperty, ICalendarStatus, ICalendarValue, Uri,
+    },
 };
 
 #[derive(Debug)]
@@ -599,6 +605,80 @@ pub fn itip_merge_changes(ical: &mut ICalendar, changes: Vec<MergeAction>) {
     }
 }
 
+pub fn itip_booking_reply(
+    ical: &mut ICalendar,
+    local_addresses: &[String],
+    part_stat: ICalendarParticipationStatus,
+) -> Result<ItipMessage<ICalendar>, ItipError> {
+    // Record the participation status of the resource
+    for comp in ical.components.iter_mut() {
+        if comp.component_type.is_scheduling_object() {
+            for entry in comp.entries.iter_mut() {
+                if entry.name == ICalendarProperty::Attendee
+                    && entry.calendar_address().is_some_and(|addr| {
+                        local_addresses.iter().any(|a| a.eq_ignore_ascii_case(addr))
+                    })
+                {
+                    entry.params.retain(|param| {
+                        !matches!(
+                            param.name,
+                            ICalendarParameterName::Partstat | ICalendarParameterName::Rsvp
+                        )
+                    });
+                    entry
+                        .params
+                        .push(ICalendarParameter::partstat(part_stat.clone()));
+                }
+            }
+        }
+    }
+
+    // Build the reply
+    let itip = itip_snapshot(ical, local_addresses, false)?;
+    let dt_stamp = PartialDateTime::now();
+    let mut message = ICalendar {
+        components: Vec::with_capacity(2),
+    };
+    message
+        .components
+        .push(itip_build_envelope(ICalendarMethod::Reply));
+
+    let mut mail_from = None;
+    for instance in itip.components.values() {
+        if let Some(local_attendee) = instance.local_attendee() {
+            let comp_id = message.components.len() as u32;
+            message.components[0].component_ids.push(comp_id);
+            message.components.push(itip_export_component(
+                instance.comp,
+                itip.uid,
+                &dt_stamp,
+                instance.sequence.unwrap_or_default(),
+                ItipExportAs::Attendee(vec![local_attendee.entry_id]),
+            ));
+            mail_from = Some(&local_attendee.email.email);
+        }
+    }
+
+    if let Some(from) = mail_from {
+        itip_add_tz(&mut message, ical);
+
+        Ok(ItipMessage {
+            from: from.to_string(),
+            from_organizer: false,
+            to: vec![itip.organizer.email.email.clone()],
+            summary: ItipSummary::Rsvp {
+                part_stat,
+                current: itip
+                    .main_instance_or_default()
+                    .build_summary(Some(&itip.organizer), &[]),
+            },
+            message,
+        })
+    } else {
+        Err(ItipError::NothingToSend)
+    }
+}
+
 pub fn itip_method(ical: &ICalendar) -> Result<&ICalendarMethod, ItipError> {
     ical.components
         .first()
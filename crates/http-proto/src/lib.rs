@@ -56,6 +56,7 @@ pub struct HttpSessionData {
     pub remote_port: u16,
     pub is_tls: bool,
     pub session_id: u64,
+    pub client_certificate: Option<Arc<[u8]>>,
 }
 
 pub struct DownloadResponse {
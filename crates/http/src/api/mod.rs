@@ -16,6 +16,7 @@ use crate::{
     api::diagnose::{DeliveryStage, spawn_delivery_diagnose},
     auth::{
         authenticate::Authenticator, oauth::auth::OAuthApiHandler, permissions::AccountApiHandler,
+        webauthn::WebAuthnHandler,
     },
 };
 use common::{
@@ -77,6 +78,9 @@ impl ManagementApi for Server {
                 ))
                 .await
             }
+            "passkey" if is_post => {
+                Box::pin(self.handle_passkey_request(req, session, &path, body)).await
+            }
             "discover" => {
                 if let Some(email) = path.get(1).copied() {
                     self.is_http_anonymous_request_allowed(session.remote_ip)
@@ -102,6 +102,25 @@ impl Authenticator for Server {
                 },
             );
 
+            // Enforce authenticated rate limit
+            self.is_http_authenticated_request_allowed(&access_token, session.remote_ip)
+                .await
+                .map(|in_flight| (in_flight, access_token))
+        } else if let (Some(mapping), Some(certificate)) = (
+            session.instance.acceptor.client_cert_mapping(),
+            session.client_certificate.as_deref(),
+        ) {
+            // Authenticate using the verified TLS client certificate
+            let access_token = self
+                .authenticate_certificate(
+                    mapping,
+                    certificate,
+                    None,
+                    session.session_id,
+                    session.remote_ip,
+                )
+                .await?;
+
             // Enforce authenticated rate limit
             self.is_http_authenticated_request_allowed(&access_token, session.remote_ip)
                 .await
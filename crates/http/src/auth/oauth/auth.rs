@@ -18,6 +18,7 @@ use common::{
             client_id::{decode_client_id, scopes_to_mask},
             registration::redirect_uri_matches,
         },
+        webauthn::PasskeyAssertion,
     },
 };
 use directory::Credentials;
@@ -95,11 +96,16 @@ pub trait OAuthApiHandler: Sync + Send {
 pub enum LoginRequest {
     #[serde(rename_all = "camelCase")]
     AuthCode {
+        #[serde(default)]
         account_name: String,
+        #[serde(default)]
         account_secret: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         #[serde(default)]
         mfa_token: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        passkey: Option<PasskeyAssertion>,
         client_id: String,
         #[serde(default)]
         redirect_uri: Option<String>,
@@ -118,11 +124,16 @@ pub enum LoginRequest {
     },
     #[serde(rename_all = "camelCase")]
     AuthDevice {
+        #[serde(default)]
         account_name: String,
+        #[serde(default)]
         account_secret: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         #[serde(default)]
         mfa_token: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        passkey: Option<PasskeyAssertion>,
         code: String,
     },
 }
@@ -172,6 +183,7 @@ impl OAuthApiHandler for Server {
                 account_name,
                 account_secret,
                 mfa_token,
+                passkey,
                 client_id,
                 redirect_uri,
                 nonce,
@@ -277,15 +289,18 @@ impl OAuthApiHandler for Server {
 
                 // Authenticate
                 match self
-                    .authenticate(&AuthRequest {
-                        credentials: Credentials::Basic {
-                            username: account_name,
-                            secret: account_secret,
-                            mfa_token,
+                    .authenticate_login(
+                        &AuthRequest {
+                            credentials: Credentials::Basic {
+                                username: account_name,
+                                secret: account_secret,
+                                mfa_token,
+                            },
+                            session_id: session.session_id,
+                            remote_ip: session.remote_ip,
                         },
-                        session_id: session.session_id,
-                        remote_ip: session.remote_ip,
-                    })
+                        passkey.as_ref(),
+                    )
                     .await
                 {
                     Ok(access_token) => {
@@ -363,6 +378,7 @@ impl OAuthApiHandler for Server {
                 account_name,
                 account_secret,
                 mfa_token,
+                passkey,
                 code,
             } => {
                 // Obtain code
@@ -381,15 +397,18 @@ impl OAuthApiHandler for Server {
                     if oauth.status == OAuthStatus::Pending {
                         // Authenticate
                         match self
-                            .authenticate(&AuthRequest {
-                                credentials: Credentials::Basic {
-                                    username: account_name,
-                                    secret: account_secret,
-                                    mfa_token,
+                            .authenticate_login(
+                                &AuthRequest {
+                                    credentials: Credentials::Basic {
+                                        username: account_name,
+                                        secret: account_secret,
+                                        mfa_token,
+                                    },
+                                    session_id: session.session_id,
+                                    remote_ip: session.remote_ip,
                                 },
-                                session_id: session.session_id,
-                                remote_ip: session.remote_ip,
-                            })
+                                passkey.as_ref(),
+                            )
                             .await
                         {
                             Ok(access_token) => {
@@ -118,6 +118,11 @@ impl AccountApiHandler for Server {
                     Permission::SysAppPasswordDestroy,
                     Permission::SysAppPasswordQuery,
                     Permission::SysAppPasswordGet,
+                    Permission::SysPasskeyCreate,
+                    Permission::SysPasskeyUpdate,
+                    Permission::SysPasskeyDestroy,
+                    Permission::SysPasskeyQuery,
+                    Permission::SysPasskeyGet,
                 ] {
                     permissions.clear(p.to_id() as usize);
                 }
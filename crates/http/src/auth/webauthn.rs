@@ -0,0 +1,103 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::auth::authenticate::Authenticator;
+use common::{Server, auth::webauthn::PasskeyRegistration};
+use http_proto::*;
+use registry::schema::enums::Permission;
+use std::future::Future;
+use types::id::Id;
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyOptionsRequest {
+    #[serde(default)]
+    pub passwordless: bool,
+}
+
+pub trait WebAuthnHandler: Sync + Send {
+    fn handle_passkey_request(
+        &self,
+        req: &HttpRequest,
+        session: &HttpSessionData,
+        path: &[&str],
+        body: Option<Vec<u8>>,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl WebAuthnHandler for Server {
+    async fn handle_passkey_request(
+        &self,
+        req: &HttpRequest,
+        session: &HttpSessionData,
+        path: &[&str],
+        body: Option<Vec<u8>>,
+    ) -> trc::Result<HttpResponse> {
+        match (path.get(1).copied(), path.get(2).copied()) {
+            (Some("assertion"), Some("options")) => {
+                self.is_http_anonymous_request_allowed(session.remote_ip)
+                    .await?;
+                let request = match body.as_deref() {
+                    Some(body) if !body.is_empty() => {
+                        serde_json::from_slice::<PasskeyOptionsRequest>(body).map_err(|err| {
+                            trc::EventType::Resource(trc::ResourceEvent::BadParameters)
+                                .from_json_error(err)
+                        })?
+                    }
+                    _ => PasskeyOptionsRequest::default(),
+                };
+
+                Ok(
+                    JsonResponse::new(self.passkey_request_options(request.passwordless).await?)
+                        .no_cache()
+                        .into_http_response(),
+                )
+            }
+            (Some("registration"), options) => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+                access_token.enforce_permission(Permission::SysPasskeyCreate)?;
+
+                // App passwords and API keys cannot be used to register passkeys
+                if access_token.credential_id().is_some() {
+                    return Err(trc::SecurityEvent::Unauthorized
+                        .into_err()
+                        .details("Passkeys cannot be registered using a scoped credential.")
+                        .account_id(access_token.account_id()));
+                }
+
+                match options {
+                    Some("options") => Ok(JsonResponse::new(
+                        self.passkey_creation_options(access_token.account_id())
+                            .await?,
+                    )
+                    .no_cache()
+                    .into_http_response()),
+                    None => {
+                        let registration = serde_json::from_slice::<PasskeyRegistration>(
+                            body.as_deref().unwrap_or_default(),
+                        )
+                        .map_err(|err| {
+                            trc::EventType::Resource(trc::ResourceEvent::BadParameters)
+                                .from_json_error(err)
+                        })?;
+                        let credential_id = self
+                            .register_passkey(access_token.account_id(), registration)
+                            .await?;
+
+                        Ok(JsonResponse::new(serde_json::json!({
+                            "id": Id::from(credential_id),
+                        }))
+                        .no_cache()
+                        .into_http_response())
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            _ => Err(trc::ResourceEvent::NotFound.into_err()),
+        }
+    }
+}
@@ -185,6 +185,7 @@ impl FormHandler for Server {
                             address: address.clone(),
                             orcpt: None,
                             is_spam: false,
+                            is_quarantined: false,
                         })
                         .collect(),
                     message_blob,
@@ -15,6 +15,7 @@ use crate::{
         },
     },
     form::FormHandler,
+    scim::ScimHandler,
 };
 use common::{
     BuildServer, Inner, KV_ACME, Server,
@@ -23,6 +24,7 @@ use common::{
     network::{SessionData, SessionManager, SessionStream},
 };
 use dav::{DavMethod, request::DavRequestHandler};
+use email::message::quarantine::EmailQuarantine;
 use groupware::{DavResourceName, calendar::itip::ItipIngest};
 use http_proto::{
     DownloadResponse, HtmlResponse, HttpContext, HttpRequest, HttpResponse, HttpResponseBody,
@@ -46,6 +48,7 @@ use jmap::{
 use jmap_proto::request::{Request, capability::Session};
 use percent_encoding::percent_decode_str;
 use registry::schema::enums::Permission;
+use smtp::lists::MailingListManager;
 use std::{net::IpAddr, str::FromStr, sync::Arc};
 use store::dispatch::lookup::KeyValue;
 use trc::SecurityEvent;
@@ -434,6 +437,9 @@ impl ParseHttp for Server {
 
                 return self.handle_api_request(&mut req, &session).await;
             }
+            "scim" => {
+                return self.handle_scim_request(&mut req, &session).await;
+            }
             "mail" => {
                 if req.method() == Method::GET
                     && path.next().unwrap_or_default() == "config-v1.1.xml"
@@ -477,6 +483,119 @@ impl ParseHttp for Server {
                         });
                 }
             }
+            "list" if path.next().unwrap_or_default() == "unsubscribe" => {
+                // Limit anonymous requests
+                self.is_http_anonymous_request_allowed(session.remote_ip)
+                    .await?;
+
+                let token =
+                    form_urlencoded::parse(req.uri().query().unwrap_or_default().as_bytes())
+                        .find_map(|(key, value)| (key == "t").then(|| value.into_owned()))
+                        .unwrap_or_default();
+
+                match *req.method() {
+                    Method::POST => {
+                        // RFC 8058 one-click unsubscribe
+                        return match self.list_unsubscribe_token(&token, session.session_id).await
+                        {
+                            Ok(true) => Ok(HtmlResponse::new(
+                                "<html><body><p>You have been unsubscribed.</p></body></html>"
+                                    .to_string(),
+                            )
+                            .into_http_response()
+                            .with_no_store()),
+                            Ok(false) => Ok(HtmlResponse::with_status(
+                                StatusCode::NOT_FOUND,
+                                "<html><body><p>This address is not subscribed to the mailing list.</p></body></html>"
+                                    .to_string(),
+                            )
+                            .into_http_response()
+                            .with_no_store()),
+                            Err(_) => Ok(HtmlResponse::with_status(
+                                StatusCode::BAD_REQUEST,
+                                "<html><body><p>Invalid or expired unsubscribe link.</p></body></html>"
+                                    .to_string(),
+                            )
+                            .into_http_response()
+                            .with_no_store()),
+                        };
+                    }
+                    Method::GET => {
+                        // Link scanners must not be able to unsubscribe recipients
+                        let action = format!(
+                            "/list/unsubscribe?t={}",
+                            form_urlencoded::byte_serialize(token.as_bytes()).collect::<String>()
+                        );
+                        return Ok(HtmlResponse::new(format!(
+                            concat!(
+                                "<html><body><form method=\"post\" action=\"{}\">",
+                                "<input type=\"hidden\" name=\"List-Unsubscribe\" value=\"One-Click\">",
+                                "<p><button type=\"submit\">Unsubscribe</button></p>",
+                                "</form></body></html>"
+                            ),
+                            action
+                        ))
+                        .into_http_response()
+                        .with_no_store());
+                    }
+                    _ => {}
+                }
+            }
+            "quarantine" if path.next().unwrap_or_default() == "release" => {
+                // Limit anonymous requests
+                self.is_http_anonymous_request_allowed(session.remote_ip)
+                    .await?;
+
+                let token =
+                    form_urlencoded::parse(req.uri().query().unwrap_or_default().as_bytes())
+                        .find_map(|(key, value)| (key == "t").then(|| value.into_owned()))
+                        .unwrap_or_default();
+
+                match *req.method() {
+                    Method::POST => {
+                        return match self.quarantine_release_token(&token).await {
+                            Ok(true) => Ok(HtmlResponse::new(
+                                "<html><body><p>The message has been released to your inbox.</p></body></html>"
+                                    .to_string(),
+                            )
+                            .into_http_response()
+                            .with_no_store()),
+                            Ok(false) => Ok(HtmlResponse::with_status(
+                                StatusCode::NOT_FOUND,
+                                "<html><body><p>The message was already released or has expired.</p></body></html>"
+                                    .to_string(),
+                            )
+                            .into_http_response()
+                            .with_no_store()),
+                            Err(_) => Ok(HtmlResponse::with_status(
+                                StatusCode::BAD_REQUEST,
+                                "<html><body><p>Invalid or expired release link.</p></body></html>"
+                                    .to_string(),
+                            )
+                            .into_http_response()
+                            .with_no_store()),
+                        };
+                    }
+                    Method::GET => {
+                        // Link scanners must not be able to release messages
+                        let action = format!(
+                            "/quarantine/release?t={}",
+                            form_urlencoded::byte_serialize(token.as_bytes()).collect::<String>()
+                        );
+                        return Ok(HtmlResponse::new(format!(
+                            concat!(
+                                "<html><body><form method=\"post\" action=\"{}\">",
+                                "<p><button type=\"submit\">Release message</button></p>",
+                                "</form></body></html>"
+                            ),
+                            action
+                        ))
+                        .into_http_response()
+                        .with_no_store());
+                    }
+                    _ => {}
+                }
+            }
             "autodiscover" | "Autodiscover" | "AutoDiscover" => {
                 let document_name = path.next().unwrap_or_default();
                 if req.method() == Method::POST
@@ -673,6 +792,12 @@ impl ParseHttp for Server {
 async fn handle_session<T: SessionStream>(inner: Arc<Inner>, session: SessionData<T>) {
     let _in_flight = session.in_flight;
     let is_tls = session.stream.is_tls();
+    let client_certificate: Option<Arc<[u8]>> = session
+        .instance
+        .acceptor
+        .client_cert_mapping()
+        .and(session.stream.peer_certificate())
+        .map(Arc::from);
 
     if let Err(http_err) = http1::Builder::new()
         .keep_alive(true)
@@ -681,6 +806,7 @@ async fn handle_session<T: SessionStream>(inner: Arc<Inner>, session: SessionDat
             service_fn(|req: hyper::Request<body::Incoming>| {
                 let instance = session.instance.clone();
                 let inner = inner.clone();
+                let client_certificate = client_certificate.clone();
 
                 async move {
                     let server = inner.build_server();
@@ -774,6 +900,7 @@ async fn handle_session<T: SessionStream>(inner: Arc<Inner>, session: SessionDat
                             remote_port: session.remote_port,
                             is_tls,
                             session_id: session.session_id,
+                            client_certificate,
                         },
                     ))
                     .await
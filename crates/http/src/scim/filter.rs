@@ -0,0 +1,422 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::ScimError;
+use serde_json::Value;
+use std::{cmp::Ordering, iter::Peekable, vec::IntoIter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScimFilter {
+    And(Box<ScimFilter>, Box<ScimFilter>),
+    Or(Box<ScimFilter>, Box<ScimFilter>),
+    Not(Box<ScimFilter>),
+    Present(AttributePath),
+    Compare {
+        path: AttributePath,
+        op: CompareOp,
+        value: Value,
+    },
+    Value {
+        path: AttributePath,
+        filter: Box<ScimFilter>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Co,
+    Sw,
+    Ew,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributePath {
+    pub name: String,
+    pub sub_attribute: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchPath {
+    pub name: String,
+    pub filter: Option<ScimFilter>,
+    pub sub_attribute: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    String(String),
+    Word(String),
+}
+
+impl ScimFilter {
+    pub fn parse(filter: &str) -> Result<Self, ScimError> {
+        let mut tokens = tokenize(filter)?.into_iter().peekable();
+        let filter = parse_or(&mut tokens)?;
+        if let Some(token) = tokens.next() {
+            Err(ScimError::invalid_filter(format!(
+                "Unexpected token {token:?} in filter."
+            )))
+        } else {
+            Ok(filter)
+        }
+    }
+
+    pub fn matches(&self, resource: &Value) -> bool {
+        match self {
+            ScimFilter::And(left, right) => left.matches(resource) && right.matches(resource),
+            ScimFilter::Or(left, right) => left.matches(resource) || right.matches(resource),
+            ScimFilter::Not(filter) => !filter.matches(resource),
+            ScimFilter::Present(path) => path.values(resource).iter().any(|value| match value {
+                Value::Null => false,
+                Value::String(value) => !value.is_empty(),
+                Value::Array(value) => !value.is_empty(),
+                _ => true,
+            }),
+            ScimFilter::Compare {
+                path,
+                op: CompareOp::Ne,
+                value,
+            } => !path
+                .values(resource)
+                .iter()
+                .any(|item| compare(item, CompareOp::Eq, value)),
+            ScimFilter::Compare { path, op, value } => path
+                .values(resource)
+                .iter()
+                .any(|item| compare(item, *op, value)),
+            ScimFilter::Value { path, filter } => match attribute(resource, &path.name) {
+                Some(Value::Array(items)) => items.iter().any(|item| filter.matches(item)),
+                Some(item @ Value::Object(_)) => filter.matches(item),
+                _ => false,
+            },
+        }
+    }
+
+    /// Returns the attribute and value of a simple `attr eq "value"` filter,
+    /// used to create the element targeted by a PATCH value filter.
+    pub fn as_equality(&self) -> Option<(&str, &Value)> {
+        match self {
+            ScimFilter::Compare {
+                path:
+                    AttributePath {
+                        name,
+                        sub_attribute: None,
+                    },
+                op: CompareOp::Eq,
+                value,
+            } => Some((name.as_str(), value)),
+            _ => None,
+        }
+    }
+}
+
+impl AttributePath {
+    pub fn parse(path: &str) -> Result<Self, ScimError> {
+        // Strip schema URN prefixes such as "urn:ietf:params:scim:schemas:core:2.0:User:"
+        let path = if path
+            .get(..4)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("urn:"))
+        {
+            path.rsplit_once(':').map(|(_, path)| path).unwrap_or(path)
+        } else {
+            path
+        };
+        let (name, sub_attribute) = match path.split_once('.') {
+            Some((name, sub_attribute)) => (name, Some(sub_attribute.to_string())),
+            None => (path, None),
+        };
+
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '$'))
+        {
+            Ok(AttributePath {
+                name: name.to_string(),
+                sub_attribute,
+            })
+        } else {
+            Err(ScimError::invalid_path(format!(
+                "Invalid attribute path {path:?}."
+            )))
+        }
+    }
+
+    fn values<'x>(&self, resource: &'x Value) -> Vec<&'x Value> {
+        let Some(value) = attribute(resource, &self.name) else {
+            return vec![];
+        };
+
+        match (value, &self.sub_attribute) {
+            (Value::Array(items), Some(sub_attribute)) => items
+                .iter()
+                .filter_map(|item| attribute(item, sub_attribute))
+                .collect(),
+            (Value::Array(items), None) => items
+                .iter()
+                .map(|item| attribute(item, "value").unwrap_or(item))
+                .collect(),
+            (value, Some(sub_attribute)) => attribute(value, sub_attribute).into_iter().collect(),
+            (value, None) => vec![value],
+        }
+    }
+}
+
+impl PatchPath {
+    pub fn parse(path: &str) -> Result<Self, ScimError> {
+        let path = path.trim();
+        if let Some((name, rest)) = path.split_once('[') {
+            let (filter, sub_attribute) = rest
+                .rsplit_once(']')
+                .ok_or_else(|| ScimError::invalid_path(format!("Invalid path {path:?}.")))?;
+            let sub_attribute = match sub_attribute {
+                "" => None,
+                sub_attribute => Some(
+                    sub_attribute
+                        .strip_prefix('.')
+                        .filter(|sub_attribute| !sub_attribute.is_empty())
+                        .ok_or_else(|| ScimError::invalid_path(format!("Invalid path {path:?}.")))?
+                        .to_string(),
+                ),
+            };
+            let attribute = AttributePath::parse(name)?;
+            if attribute.sub_attribute.is_some() {
+                return Err(ScimError::invalid_path(format!("Invalid path {path:?}.")));
+            }
+
+            Ok(PatchPath {
+                name: attribute.name,
+                filter: Some(ScimFilter::parse(filter)?),
+                sub_attribute,
+            })
+        } else {
+            let attribute = AttributePath::parse(path)?;
+            Ok(PatchPath {
+                name: attribute.name,
+                filter: None,
+                sub_attribute: attribute.sub_attribute,
+            })
+        }
+    }
+}
+
+impl CompareOp {
+    fn parse(op: &str) -> Option<Self> {
+        hashify::tiny_map_ignore_case!(op.as_bytes(),
+            b"eq" => CompareOp::Eq,
+            b"ne" => CompareOp::Ne,
+            b"co" => CompareOp::Co,
+            b"sw" => CompareOp::Sw,
+            b"ew" => CompareOp::Ew,
+            b"gt" => CompareOp::Gt,
+            b"ge" => CompareOp::Ge,
+            b"lt" => CompareOp::Lt,
+            b"le" => CompareOp::Le,
+        )
+    }
+}
+
+/// Looks up an attribute by name, ignoring case as required by RFC 7643.
+pub fn attribute<'x>(value: &'x Value, name: &str) -> Option<&'x Value> {
+    value
+        .as_object()?
+        .iter()
+        .find_map(|(key, value)| key.eq_ignore_ascii_case(name).then_some(value))
+}
+
+fn compare(item: &Value, op: CompareOp, value: &Value) -> bool {
+    match (item, value) {
+        (Value::String(item), Value::String(value)) => {
+            let item = item.to_lowercase();
+            let value = value.to_lowercase();
+            match op {
+                CompareOp::Eq => item == value,
+                CompareOp::Ne => item != value,
+                CompareOp::Co => item.contains(&value),
+                CompareOp::Sw => item.starts_with(&value),
+                CompareOp::Ew => item.ends_with(&value),
+                CompareOp::Gt => item > value,
+                CompareOp::Ge => item >= value,
+                CompareOp::Lt => item < value,
+                CompareOp::Le => item <= value,
+            }
+        }
+        (Value::Bool(item), Value::Bool(value)) => match op {
+            CompareOp::Eq => item == value,
+            CompareOp::Ne => item != value,
+            _ => false,
+        },
+        (Value::Number(item), Value::Number(value)) => {
+            let (Some(item), Some(value)) = (item.as_f64(), value.as_f64()) else {
+                return false;
+            };
+            match (op, item.partial_cmp(&value)) {
+                (CompareOp::Eq, Some(Ordering::Equal))
+                | (CompareOp::Ge, Some(Ordering::Equal | Ordering::Greater))
+                | (CompareOp::Gt, Some(Ordering::Greater))
+                | (CompareOp::Le, Some(Ordering::Equal | Ordering::Less))
+                | (CompareOp::Lt, Some(Ordering::Less)) => true,
+                (CompareOp::Ne, ordering) => ordering != Some(Ordering::Equal),
+                _ => false,
+            }
+        }
+        (Value::Null, Value::Null) => op == CompareOp::Eq,
+        _ => false,
+    }
+}
+
+fn parse_or(tokens: &mut Peekable<IntoIter<Token>>) -> Result<ScimFilter, ScimError> {
+    let mut filter = parse_and(tokens)?;
+    while matches!(tokens.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("or")) {
+        tokens.next();
+        filter = ScimFilter::Or(Box::new(filter), Box::new(parse_and(tokens)?));
+    }
+    Ok(filter)
+}
+
+fn parse_and(tokens: &mut Peekable<IntoIter<Token>>) -> Result<ScimFilter, ScimError> {
+    let mut filter = parse_unary(tokens)?;
+    while matches!(tokens.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("and")) {
+        tokens.next();
+        filter = ScimFilter::And(Box::new(filter), Box::new(parse_unary(tokens)?));
+    }
+    Ok(filter)
+}
+
+fn parse_unary(tokens: &mut Peekable<IntoIter<Token>>) -> Result<ScimFilter, ScimError> {
+    match tokens.next() {
+        Some(Token::OpenParen) => {
+            let filter = parse_or(tokens)?;
+            expect(tokens, Token::CloseParen)?;
+            Ok(filter)
+        }
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("not") => {
+            expect(tokens, Token::OpenParen)?;
+            let filter = parse_or(tokens)?;
+            expect(tokens, Token::CloseParen)?;
+            Ok(ScimFilter::Not(Box::new(filter)))
+        }
+        Some(Token::Word(word)) => {
+            let path = AttributePath::parse(&word)?;
+            match tokens.next() {
+                Some(Token::OpenBracket) => {
+                    let filter = parse_or(tokens)?;
+                    expect(tokens, Token::CloseBracket)?;
+                    if path.sub_attribute.is_some() {
+                        return Err(ScimError::invalid_filter(format!(
+                            "Invalid value filter on {word:?}."
+                        )));
+                    }
+                    Ok(ScimFilter::Value {
+                        path,
+                        filter: Box::new(filter),
+                    })
+                }
+                Some(Token::Word(op)) if op.eq_ignore_ascii_case("pr") => {
+                    Ok(ScimFilter::Present(path))
+                }
+                Some(Token::Word(op)) => {
+                    let op = CompareOp::parse(&op).ok_or_else(|| {
+                        ScimError::invalid_filter(format!("Unknown operator {op:?}."))
+                    })?;
+                    let value = match tokens.next() {
+                        Some(Token::String(value)) => Value::String(value),
+                        Some(Token::Word(value)) => serde_json::from_str::<Value>(&value)
+                            .ok()
+                            .filter(|value| !value.is_object() && !value.is_array())
+                            .ok_or_else(|| {
+                                ScimError::invalid_filter(format!(
+                                    "Invalid comparison value {value:?}."
+                                ))
+                            })?,
+                        _ => {
+                            return Err(ScimError::invalid_filter("Missing comparison value."));
+                        }
+                    };
+                    Ok(ScimFilter::Compare { path, op, value })
+                }
+                _ => Err(ScimError::invalid_filter(format!(
+                    "Missing operator after {word:?}."
+                ))),
+            }
+        }
+        token => Err(ScimError::invalid_filter(format!(
+            "Unexpected token {token:?} in filter."
+        ))),
+    }
+}
+
+fn expect(tokens: &mut Peekable<IntoIter<Token>>, expected: Token) -> Result<(), ScimError> {
+    match tokens.next() {
+        Some(token) if token == expected => Ok(()),
+        token => Err(ScimError::invalid_filter(format!(
+            "Expected {expected:?}, found {token:?}."
+        ))),
+    }
+}
+
+fn tokenize(filter: &str) -> Result<Vec<Token>, ScimError> {
+    let mut tokens = Vec::new();
+    let mut chars = filter.char_indices().peekable();
+
+    while let Some((pos, ch)) = chars.next() {
+        match ch {
+            '(' => tokens.push(Token::OpenParen),
+            ')' => tokens.push(Token::CloseParen),
+            '[' => tokens.push(Token::OpenBracket),
+            ']' => tokens.push(Token::CloseBracket),
+            '"' => {
+                // String literals follow JSON escaping rules
+                let mut is_escaped = false;
+                let mut end = None;
+                for (end_pos, ch) in chars.by_ref() {
+                    match ch {
+                        '\\' if !is_escaped => is_escaped = true,
+                        '"' if !is_escaped => {
+                            end = Some(end_pos);
+                            break;
+                        }
+                        _ => is_escaped = false,
+                    }
+                }
+                let end =
+                    end.ok_or_else(|| ScimError::invalid_filter("Unterminated string literal."))?;
+                tokens.push(Token::String(
+                    serde_json::from_str::<String>(&filter[pos..=end])
+                        .map_err(|_| ScimError::invalid_filter("Invalid string literal."))?,
+                ));
+            }
+            ch if ch.is_whitespace() => {}
+            _ => {
+                let mut end = pos + ch.len_utf8();
+                while let Some((next_pos, next_ch)) = chars.peek() {
+                    if next_ch.is_whitespace() || matches!(next_ch, '(' | ')' | '[' | ']' | '"') {
+                        break;
+                    }
+                    end = next_pos + next_ch.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Word(filter[pos..end].to_string()));
+            }
+        }
+    }
+
+    if !tokens.is_empty() {
+        Ok(tokens)
+    } else {
+        Err(ScimError::invalid_filter("Empty filter."))
+    }
+}
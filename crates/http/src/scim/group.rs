@@ -0,0 +1,241 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ResourceKind, SCHEMA_GROUP, ScimError, ScimResult, ScimSession, filter::attribute};
+use registry::schema::{
+    prelude::{ObjectType, Property},
+    structs::{Account, GroupAccount},
+};
+use serde_json::{Map, Value, json};
+use std::str::FromStr;
+use store::{registry::RegistryQuery, roaring::RoaringBitmap};
+use trc::AddContext;
+use types::id::Id;
+
+#[derive(Debug)]
+pub(crate) struct ScimGroup {
+    pub display_name: String,
+    pub members: Vec<u32>,
+}
+
+impl ScimGroup {
+    pub fn parse(value: &Value) -> ScimResult<Self> {
+        let display_name = attribute(value, "displayName")
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| ScimError::invalid_value("Attribute displayName is required."))?;
+
+        let mut members = Vec::new();
+        if let Some(Value::Array(items)) = attribute(value, "members") {
+            for item in items {
+                let member_id = attribute(item, "value")
+                    .and_then(|value| value.as_str())
+                    .and_then(|value| Id::from_str(value).ok())
+                    .and_then(|value| u32::try_from(value.id()).ok())
+                    .ok_or_else(|| {
+                        ScimError::invalid_value(format!("Invalid group member {item}."))
+                    })?;
+                if !members.contains(&member_id) {
+                    members.push(member_id);
+                }
+            }
+        }
+
+        Ok(ScimGroup {
+            display_name,
+            members,
+        })
+    }
+}
+
+impl ScimSession<'_> {
+    pub(crate) async fn group_resource(
+        &self,
+        account_id: u32,
+        group: &GroupAccount,
+    ) -> ScimResult<Value> {
+        let display_name = match &group.description {
+            Some(description) => description.clone(),
+            None => self.email_address(&group.name, group.domain_id).await?,
+        };
+        let members = self
+            .group_members(account_id)
+            .await?
+            .into_iter()
+            .map(|member_id| {
+                json!({
+                    "value": Id::from(member_id).to_string(),
+                    "$ref": self.location(ResourceKind::User, member_id),
+                    "type": "User",
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({
+            "schemas": [SCHEMA_GROUP],
+            "id": Id::from(account_id).to_string(),
+            "displayName": display_name,
+            "members": members,
+            "meta": self.meta(ResourceKind::Group, account_id, &group.created_at),
+        }))
+    }
+
+    pub(crate) async fn create_group(&self, request: Value) -> ScimResult<u32> {
+        let group = ScimGroup::parse(&request)?;
+        self.validate_members(&group.members).await?;
+
+        // Groups are addressable, derive a mailbox name from the display name
+        let (name, domain_id) = if group.display_name.contains('@') {
+            self.resolve_address(&group.display_name).await?
+        } else {
+            let name = group
+                .display_name
+                .to_lowercase()
+                .split(|ch: char| !ch.is_alphanumeric() && !matches!(ch, '.' | '_'))
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("-");
+            if name.is_empty() {
+                return Err(ScimError::invalid_value(format!(
+                    "Cannot derive a group name from {:?}.",
+                    group.display_name
+                )));
+            }
+            self.resolve_address(&name).await?
+        };
+
+        let account_id = self
+            .create(json!({
+                "@type": "Group",
+                "name": name,
+                "domainId": domain_id,
+                "description": group.display_name,
+            }))
+            .await?;
+        self.set_group_members(account_id, &group.members, &[])
+            .await?;
+
+        Ok(account_id)
+    }
+
+    pub(crate) async fn replace_group(&self, account_id: u32, request: Value) -> ScimResult<Value> {
+        let Account::Group(current) = self.account(account_id).await? else {
+            return Err(ScimError::not_found(format!(
+                "Group {} not found.",
+                Id::from(account_id)
+            )));
+        };
+        let group = ScimGroup::parse(&request)?;
+        self.validate_members(&group.members).await?;
+
+        if current.description.as_ref() != Some(&group.display_name) {
+            let mut updates = Map::new();
+            updates.insert(
+                Id::from(account_id).to_string(),
+                json!({ "description": group.display_name }),
+            );
+            self.update(updates).await?;
+        }
+
+        let members = self.group_members(account_id).await?;
+        let add = group
+            .members
+            .iter()
+            .copied()
+            .filter(|member_id| !members.contains(*member_id))
+            .collect::<Vec<_>>();
+        let remove = members
+            .iter()
+            .filter(|member_id| !group.members.contains(member_id))
+            .collect::<Vec<_>>();
+        self.set_group_members(account_id, &add, &remove).await?;
+
+        self.resource(ResourceKind::Group, account_id).await
+    }
+
+    pub(crate) async fn delete_group(&self, account_id: u32) -> ScimResult<()> {
+        if !matches!(self.account(account_id).await?, Account::Group(_)) {
+            return Err(ScimError::not_found(format!(
+                "Group {} not found.",
+                Id::from(account_id)
+            )));
+        }
+
+        let members = self
+            .group_members(account_id)
+            .await?
+            .into_iter()
+            .collect::<Vec<_>>();
+        self.set_group_members(account_id, &[], &members).await?;
+        self.destroy(account_id).await
+    }
+
+    async fn group_members(&self, group_id: u32) -> ScimResult<RoaringBitmap> {
+        self.server
+            .registry()
+            .query::<RoaringBitmap>(
+                RegistryQuery::new(ObjectType::Account)
+                    .with_tenant(Some(self.tenant_id))
+                    .equal(Property::MemberGroupIds, group_id),
+            )
+            .await
+            .caused_by(trc::location!())
+            .map_err(Into::into)
+    }
+
+    async fn validate_members(&self, members: &[u32]) -> ScimResult<()> {
+        for member_id in members {
+            match self.account(*member_id).await {
+                Ok(Account::User(_)) => {}
+                Ok(_) | Err(ScimError::Protocol { .. }) => {
+                    return Err(ScimError::invalid_value(format!(
+                        "Group member {} is not a user of this tenant.",
+                        Id::from(*member_id)
+                    )));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Group membership is stored on the member accounts, so adding or removing
+    /// members updates the group list of each affected user.
+    async fn set_group_members(
+        &self,
+        group_id: u32,
+        add: &[u32],
+        remove: &[u32],
+    ) -> ScimResult<()> {
+        let mut updates = Map::new();
+        for (member_id, is_member) in add
+            .iter()
+            .map(|member_id| (*member_id, true))
+            .chain(remove.iter().map(|member_id| (*member_id, false)))
+        {
+            let Account::User(user) = self.account(member_id).await? else {
+                continue;
+            };
+            let mut member_group_ids = user
+                .member_group_ids
+                .iter()
+                .filter(|id| id.id() != group_id as u64)
+                .map(|id| (id.to_string(), Value::Bool(true)))
+                .collect::<Map<_, _>>();
+            if is_member {
+                member_group_ids.insert(Id::from(group_id).to_string(), Value::Bool(true));
+            }
+            updates.insert(
+                Id::from(member_id).to_string(),
+                json!({ "memberGroupIds": member_group_ids }),
+            );
+        }
+
+        self.update(updates).await
+    }
+}
@@ -0,0 +1,701 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+pub mod filter;
+pub mod group;
+pub mod patch;
+pub mod schema;
+pub mod user;
+
+use crate::auth::authenticate::{Authenticator, HttpHeaders};
+use common::{
+    Server,
+    auth::{AccessToken, credential::ApiKey},
+};
+use filter::{AttributePath, ScimFilter};
+use http_proto::{
+    HttpRequest, HttpResponse, HttpSessionData,
+    request::{decode_path_element, fetch_body},
+};
+use hyper::{Method, StatusCode};
+use jmap::registry::set::RegistrySet;
+use jmap_proto::{
+    error::set::{SetError, SetErrorType},
+    method::set::{SetRequest, SetResponse},
+    object::registry::Registry,
+};
+use patch::{PatchRequest, apply_patch};
+use registry::{
+    schema::{
+        enums::AccountType,
+        prelude::{ObjectType, Property, UTCDateTime},
+        structs::Account,
+    },
+    types::{EnumImpl, id::ObjectId},
+};
+use serde_json::{Value, json};
+use std::str::FromStr;
+use store::{registry::RegistryQuery, roaring::RoaringBitmap};
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub const SCHEMA_USER: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub const SCHEMA_GROUP: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+pub const SCHEMA_LIST_RESPONSE: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+pub const SCHEMA_PATCH_OP: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+pub const SCHEMA_ERROR: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+pub const SCHEMA_SERVICE_PROVIDER_CONFIG: &str =
+    "urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig";
+pub const SCHEMA_RESOURCE_TYPE: &str = "urn:ietf:params:scim:schemas:core:2.0:ResourceType";
+pub const SCHEMA_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Schema";
+
+const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+const SCIM_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+pub trait ScimHandler: Sync + Send {
+    fn handle_scim_request(
+        &self,
+        req: &mut HttpRequest,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+pub(crate) struct ScimSession<'x> {
+    pub server: &'x Server,
+    pub access_token: &'x AccessToken,
+    pub session: &'x HttpSessionData,
+    pub tenant_id: u32,
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ResourceKind {
+    User,
+    Group,
+}
+
+#[derive(Debug)]
+pub enum ScimError {
+    Protocol {
+        status: StatusCode,
+        scim_type: Option<&'static str>,
+        detail: String,
+    },
+    Internal(trc::Error),
+}
+
+pub type ScimResult<T> = Result<T, ScimError>;
+
+impl ScimHandler for Server {
+    async fn handle_scim_request(
+        &self,
+        req: &mut HttpRequest,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        // Authenticate request
+        let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+        // Provisioning is restricted to API keys issued to tenant accounts
+        let api_key = req
+            .authorization()
+            .filter(|(mechanism, _)| mechanism.eq_ignore_ascii_case("bearer"))
+            .and_then(|(_, token)| ApiKey::parse(token));
+        let tenant_id = match (api_key, access_token.tenant_id()) {
+            (Some(api_key), Some(tenant_id))
+                if access_token.credential_id() == Some(api_key.credential_id) =>
+            {
+                tenant_id
+            }
+            _ => {
+                return Err(trc::SecurityEvent::Unauthorized
+                    .into_err()
+                    .details("SCIM provisioning requires a tenant API key.")
+                    .account_id(access_token.account_id()));
+            }
+        };
+
+        let method = req.method().clone();
+        let body = if matches!(method, Method::POST | Method::PUT | Method::PATCH) {
+            Some(
+                fetch_body(req, SCIM_MAX_BODY_SIZE, session.session_id)
+                    .await
+                    .ok_or_else(|| trc::LimitEvent::SizeRequest.into_err())?,
+            )
+        } else {
+            None
+        };
+
+        let mut path = req.uri().path().split('/').skip(2);
+        if path.next() != Some("v2") {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        }
+        let path = path
+            .filter(|segment| !segment.is_empty())
+            .map(decode_path_element)
+            .collect::<Vec<_>>();
+        let path = path
+            .iter()
+            .map(|segment| segment.as_ref())
+            .collect::<Vec<_>>();
+        let params = UrlParams::new(req.uri().query());
+        let scim = ScimSession {
+            server: self,
+            access_token: &access_token,
+            session,
+            tenant_id,
+            base_url: format!(
+                "{}/scim/v2",
+                self.core.network.http.url_https.trim_end_matches('/')
+            ),
+        };
+
+        match Box::pin(scim.handle_request(&method, &path, body, &params)).await {
+            Ok(response) => Ok(response),
+            Err(ScimError::Internal(err)) => Err(err),
+            Err(ScimError::Protocol {
+                status,
+                scim_type,
+                detail,
+            }) => {
+                let mut response = json!({
+                    "schemas": [SCHEMA_ERROR],
+                    "status": status.as_u16().to_string(),
+                    "detail": detail,
+                });
+                if let Some(scim_type) = scim_type {
+                    response["scimType"] = scim_type.into();
+                }
+                Ok(scim_response(status, response))
+            }
+        }
+    }
+}
+
+impl ScimSession<'_> {
+    async fn handle_request(
+        &self,
+        method: &Method,
+        path: &[&str],
+        body: Option<Vec<u8>>,
+        params: &UrlParams<'_>,
+    ) -> ScimResult<HttpResponse> {
+        match (path, method) {
+            (["ServiceProviderConfig"], &Method::GET) => Ok(scim_response(
+                StatusCode::OK,
+                schema::service_provider_config(self),
+            )),
+            (["ResourceTypes"], &Method::GET) => Ok(scim_response(
+                StatusCode::OK,
+                list_response(schema::resource_types(self), 1),
+            )),
+            (["Schemas"], &Method::GET) => Ok(scim_response(
+                StatusCode::OK,
+                list_response(schema::schemas(self), 1),
+            )),
+            ([collection @ ("ResourceTypes" | "Schemas"), id], &Method::GET) => {
+                let resources = if *collection == "Schemas" {
+                    schema::schemas(self)
+                } else {
+                    schema::resource_types(self)
+                };
+                resources
+                    .into_iter()
+                    .find(|resource| resource["id"].as_str() == Some(*id))
+                    .map(|resource| scim_response(StatusCode::OK, resource))
+                    .ok_or_else(|| ScimError::not_found(format!("{collection} {id:?} not found.")))
+            }
+            ([endpoint], &Method::GET) => {
+                let kind = ResourceKind::parse(endpoint)?;
+                self.access_token
+                    .enforce_permission(ObjectType::Account.query_permission())?;
+                self.access_token
+                    .enforce_permission(ObjectType::Account.get_permission())?;
+
+                Ok(scim_response(
+                    StatusCode::OK,
+                    self.list(kind, params).await?,
+                ))
+            }
+            ([endpoint], &Method::POST) => {
+                let kind = ResourceKind::parse(endpoint)?;
+                self.access_token
+                    .enforce_permission(ObjectType::Account.set_permission()[0])?;
+
+                let request = parse_body::<Value>(body)?;
+                let account_id = match kind {
+                    ResourceKind::User => self.create_user(request).await?,
+                    ResourceKind::Group => self.create_group(request).await?,
+                };
+                let resource = self.resource(kind, account_id).await?;
+
+                Ok(scim_response(StatusCode::CREATED, resource)
+                    .with_location(self.location(kind, account_id)))
+            }
+            ([endpoint, id], method) => {
+                let kind = ResourceKind::parse(endpoint)?;
+                let account_id = Id::from_str(id)
+                    .ok()
+                    .and_then(|id| u32::try_from(id.id()).ok())
+                    .ok_or_else(|| ScimError::not_found(format!("Resource {id:?} not found.")))?;
+
+                match *method {
+                    Method::GET => {
+                        self.access_token
+                            .enforce_permission(ObjectType::Account.get_permission())?;
+
+                        Ok(scim_response(
+                            StatusCode::OK,
+                            project(self.resource(kind, account_id).await?, params),
+                        ))
+                    }
+                    Method::PUT => {
+                        self.access_token
+                            .enforce_permission(ObjectType::Account.set_permission()[1])?;
+
+                        let request = parse_body::<Value>(body)?;
+                        Ok(scim_response(
+                            StatusCode::OK,
+                            self.replace(kind, account_id, request).await?,
+                        ))
+                    }
+                    Method::PATCH => {
+                        self.access_token
+                            .enforce_permission(ObjectType::Account.set_permission()[1])?;
+
+                        // Patches are applied to the current representation, which is
+                        // then stored using the same logic as a PUT request
+                        let request = parse_body::<PatchRequest>(body)?;
+                        let mut resource = self.resource(kind, account_id).await?;
+                        apply_patch(&mut resource, request.operations)?;
+                        Ok(scim_response(
+                            StatusCode::OK,
+                            self.replace(kind, account_id, resource).await?,
+                        ))
+                    }
+                    Method::DELETE => {
+                        self.access_token
+                            .enforce_permission(ObjectType::Account.set_permission()[2])?;
+
+                        match kind {
+                            ResourceKind::User => self.delete_user(account_id).await?,
+                            ResourceKind::Group => self.delete_group(account_id).await?,
+                        }
+                        Ok(HttpResponse::new(StatusCode::NO_CONTENT))
+                    }
+                    _ => Err(ScimError::new(
+                        StatusCode::METHOD_NOT_ALLOWED,
+                        None,
+                        "Method not allowed.",
+                    )),
+                }
+            }
+            _ => Err(ScimError::not_found("Unknown SCIM endpoint.")),
+        }
+    }
+
+    async fn list(&self, kind: ResourceKind, params: &UrlParams<'_>) -> ScimResult<Value> {
+        let filter = params
+            .get("filter")
+            .filter(|filter| !filter.trim().is_empty())
+            .map(ScimFilter::parse)
+            .transpose()?;
+        let max_results = self.server.core.jmap.query_max_results;
+        let start_index = params.parse::<usize>("startIndex").unwrap_or(1).max(1);
+        let count = params
+            .parse::<usize>("count")
+            .unwrap_or(max_results)
+            .min(max_results);
+
+        let account_ids = self
+            .server
+            .registry()
+            .query::<RoaringBitmap>(
+                RegistryQuery::new(ObjectType::Account)
+                    .with_tenant(Some(self.tenant_id))
+                    .equal(Property::Type, kind.account_type().to_id()),
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        let mut resources = Vec::with_capacity(count);
+        let total_results = if let Some(filter) = filter {
+            // Filters are evaluated against the SCIM representation of each resource
+            let mut total_results = 0;
+            for account_id in account_ids {
+                if let Some(resource) = self.resource_opt(kind, account_id).await?
+                    && filter.matches(&resource)
+                {
+                    total_results += 1;
+                    if total_results >= start_index && resources.len() < count {
+                        resources.push(project(resource, params));
+                    }
+                }
+            }
+            total_results
+        } else {
+            for account_id in account_ids.iter().skip(start_index - 1).take(count) {
+                if let Some(resource) = self.resource_opt(kind, account_id).await? {
+                    resources.push(project(resource, params));
+                }
+            }
+            account_ids.len() as usize
+        };
+
+        let mut response = list_response(resources, start_index);
+        response["totalResults"] = total_results.into();
+        Ok(response)
+    }
+
+    async fn replace(
+        &self,
+        kind: ResourceKind,
+        account_id: u32,
+        request: Value,
+    ) -> ScimResult<Value> {
+        match kind {
+            ResourceKind::User => self.replace_user(account_id, request).await,
+            ResourceKind::Group => self.replace_group(account_id, request).await,
+        }
+    }
+
+    pub(crate) async fn account(&self, account_id: u32) -> ScimResult<Account> {
+        self.server
+            .registry()
+            .get(ObjectId::new(ObjectType::Account, account_id.into()))
+            .await
+            .caused_by(trc::location!())?
+            .filter(|object| object.inner.member_tenant_id() == Some(Id::from(self.tenant_id)))
+            .map(Account::from)
+            .ok_or_else(|| {
+                ScimError::not_found(format!("Resource {} not found.", Id::from(account_id)))
+            })
+    }
+
+    pub(crate) async fn resource(&self, kind: ResourceKind, account_id: u32) -> ScimResult<Value> {
+        self.resource_opt(kind, account_id).await?.ok_or_else(|| {
+            ScimError::not_found(format!(
+                "{} {} not found.",
+                kind.resource_type(),
+                Id::from(account_id)
+            ))
+        })
+    }
+
+    async fn resource_opt(&self, kind: ResourceKind, account_id: u32) -> ScimResult<Option<Value>> {
+        match (kind, self.account(account_id).await) {
+            (ResourceKind::User, Ok(Account::User(user))) => {
+                self.user_resource(account_id, &user).await.map(Some)
+            }
+            (ResourceKind::Group, Ok(Account::Group(group))) => {
+                self.group_resource(account_id, &group).await.map(Some)
+            }
+            (_, Ok(_) | Err(ScimError::Protocol { .. })) => Ok(None),
+            (_, Err(err)) => Err(err),
+        }
+    }
+
+    pub(crate) fn location(&self, kind: ResourceKind, account_id: u32) -> String {
+        format!(
+            "{}/{}/{}",
+            self.base_url,
+            kind.endpoint(),
+            Id::from(account_id)
+        )
+    }
+
+    pub(crate) fn meta(
+        &self,
+        kind: ResourceKind,
+        account_id: u32,
+        created_at: &UTCDateTime,
+    ) -> Value {
+        json!({
+            "resourceType": kind.resource_type(),
+            "created": created_at.to_string(),
+            "location": self.location(kind, account_id),
+        })
+    }
+
+    /// Resolves an e-mail address to its local part and the id of a domain
+    /// owned by the tenant. Bare names are placed in the provisioning account's domain.
+    pub(crate) async fn resolve_address(&self, address: &str) -> ScimResult<(String, Id)> {
+        let address = address.trim().to_lowercase();
+        if let Some((local_part, domain)) = address.rsplit_once('@') {
+            let domain = self
+                .server
+                .domain(domain)
+                .await
+                .caused_by(trc::location!())?
+                .filter(|domain| domain.id_tenant == Some(self.tenant_id))
+                .ok_or_else(|| {
+                    ScimError::invalid_value(format!(
+                        "The domain of {address:?} is not managed by this tenant."
+                    ))
+                })?;
+
+            Ok((local_part.to_string(), Id::from(domain.id)))
+        } else if let Account::User(user) = self.account(self.access_token.account_id()).await? {
+            Ok((address, user.domain_id))
+        } else {
+            Err(ScimError::invalid_value(format!(
+                "Expected an e-mail address, found {address:?}."
+            )))
+        }
+    }
+
+    pub(crate) async fn email_address(&self, name: &str, domain_id: Id) -> ScimResult<String> {
+        Ok(
+            match self
+                .server
+                .domain_by_id(domain_id.document_id())
+                .await
+                .caused_by(trc::location!())?
+            {
+                Some(domain) => format!("{name}@{}", domain.name()),
+                None => name.to_string(),
+            },
+        )
+    }
+
+    pub(crate) async fn create(&self, object: Value) -> ScimResult<u32> {
+        let mut response = self
+            .registry_set(json!({
+                "create": {
+                    "scim": object
+                }
+            }))
+            .await?;
+
+        if let Some(err) = response.not_created.into_values().next() {
+            Err(err.into())
+        } else {
+            response
+                .created
+                .remove("scim")
+                .and_then(|created| serde_json::to_value(created).ok())
+                .and_then(|created| {
+                    created
+                        .get("id")?
+                        .as_str()
+                        .and_then(|id| Id::from_str(id).ok())
+                })
+                .and_then(|id| u32::try_from(id.id()).ok())
+                .ok_or_else(|| {
+                    ScimError::Internal(
+                        trc::StoreEvent::UnexpectedError
+                            .into_err()
+                            .details("Registry did not return the created account id.")
+                            .caused_by(trc::location!()),
+                    )
+                })
+        }
+    }
+
+    pub(crate) async fn update(&self, updates: serde_json::Map<String, Value>) -> ScimResult<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let response = self.registry_set(json!({ "update": updates })).await?;
+        if let Some(err) = response.not_updated.into_values().next() {
+            Err(err.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) async fn destroy(&self, account_id: u32) -> ScimResult<()> {
+        let response = self
+            .registry_set(json!({ "destroy": [Id::from(account_id)] }))
+            .await?;
+        if let Some(err) = response.not_destroyed.into_values().next() {
+            Err(err.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn registry_set(&self, mut request: Value) -> ScimResult<SetResponse<Registry>> {
+        // Writes go through the registry so that they are validated and
+        // scoped to the tenant exactly like any other account change
+        request["accountId"] = Id::from(self.access_token.account_id()).to_string().into();
+        let request = request.to_string();
+        let request =
+            serde_json::from_str::<SetRequest<'_, Registry>>(&request).map_err(|err| {
+                trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+            })?;
+
+        Box::pin(self.server.registry_set(
+            ObjectType::Account,
+            request,
+            self.access_token,
+            self.session.remote_ip,
+        ))
+        .await
+        .map_err(Into::into)
+    }
+}
+
+impl ResourceKind {
+    fn parse(endpoint: &str) -> ScimResult<Self> {
+        match endpoint {
+            "Users" => Ok(ResourceKind::User),
+            "Groups" => Ok(ResourceKind::Group),
+            _ => Err(ScimError::not_found(format!(
+                "Unknown SCIM endpoint {endpoint:?}."
+            ))),
+        }
+    }
+
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            ResourceKind::User => "Users",
+            ResourceKind::Group => "Groups",
+        }
+    }
+
+    pub fn resource_type(&self) -> &'static str {
+        match self {
+            ResourceKind::User => "User",
+            ResourceKind::Group => "Group",
+        }
+    }
+
+    pub fn schema(&self) -> &'static str {
+        match self {
+            ResourceKind::User => SCHEMA_USER,
+            ResourceKind::Group => SCHEMA_GROUP,
+        }
+    }
+
+    fn account_type(&self) -> AccountType {
+        match self {
+            ResourceKind::User => AccountType::User,
+            ResourceKind::Group => AccountType::Group,
+        }
+    }
+}
+
+impl ScimError {
+    pub fn new(
+        status: StatusCode,
+        scim_type: Option<&'static str>,
+        detail: impl Into<String>,
+    ) -> Self {
+        ScimError::Protocol {
+            status,
+            scim_type,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, None, detail)
+    }
+
+    pub fn invalid_value(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidValue"), detail)
+    }
+
+    pub fn invalid_filter(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidFilter"), detail)
+    }
+
+    pub fn invalid_path(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidPath"), detail)
+    }
+
+    pub fn invalid_syntax(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidSyntax"), detail)
+    }
+
+    pub fn no_target(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("noTarget"), detail)
+    }
+
+    pub fn mutability(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("mutability"), detail)
+    }
+}
+
+impl From<trc::Error> for ScimError {
+    fn from(err: trc::Error) -> Self {
+        ScimError::Internal(err)
+    }
+}
+
+impl From<SetError<Property>> for ScimError {
+    fn from(err: SetError<Property>) -> Self {
+        let detail = err
+            .description()
+            .map(|description| description.to_string())
+            .unwrap_or_else(|| serde_json::to_string(&err).unwrap_or_default());
+
+        match err.error_type() {
+            SetErrorType::Forbidden | SetErrorType::OverQuota => {
+                ScimError::new(StatusCode::FORBIDDEN, None, detail)
+            }
+            SetErrorType::AlreadyExists | SetErrorType::PrimaryKeyViolation => {
+                ScimError::new(StatusCode::CONFLICT, Some("uniqueness"), detail)
+            }
+            SetErrorType::NotFound => ScimError::not_found(detail),
+            SetErrorType::ObjectIsLinked => ScimError::new(StatusCode::CONFLICT, None, detail),
+            _ => ScimError::invalid_value(detail),
+        }
+    }
+}
+
+fn parse_body<T: serde::de::DeserializeOwned>(body: Option<Vec<u8>>) -> ScimResult<T> {
+    serde_json::from_slice::<T>(body.as_deref().unwrap_or_default())
+        .map_err(|err| ScimError::invalid_syntax(format!("Invalid request body: {err}")))
+}
+
+fn list_response(resources: Vec<Value>, start_index: usize) -> Value {
+    json!({
+        "schemas": [SCHEMA_LIST_RESPONSE],
+        "totalResults": resources.len(),
+        "startIndex": start_index,
+        "itemsPerPage": resources.len(),
+        "Resources": resources,
+    })
+}
+
+fn project(resource: Value, params: &UrlParams<'_>) -> Value {
+    let attributes = |name: &str| {
+        params.get(name).map(|attributes| {
+            attributes
+                .split(',')
+                .filter_map(|attribute| AttributePath::parse(attribute.trim()).ok())
+                .map(|attribute| attribute.name)
+                .collect::<Vec<_>>()
+        })
+    };
+    let included = attributes("attributes");
+    let excluded = attributes("excludedAttributes");
+
+    match resource {
+        Value::Object(mut resource) if included.is_some() || excluded.is_some() => {
+            resource.retain(|key, _| {
+                matches!(key.as_str(), "id" | "schemas")
+                    || (included.as_ref().is_none_or(|included| {
+                        included.iter().any(|name| name.eq_ignore_ascii_case(key))
+                    }) && excluded.as_ref().is_none_or(|excluded| {
+                        !excluded.iter().any(|name| name.eq_ignore_ascii_case(key))
+                    }))
+            });
+            Value::Object(resource)
+        }
+        resource => resource,
+    }
+}
+
+fn scim_response(status: StatusCode, body: Value) -> HttpResponse {
+    HttpResponse::new(status)
+        .with_content_type(SCIM_CONTENT_TYPE)
+        .with_text_body(body.to_string())
+        .with_no_store()
+}
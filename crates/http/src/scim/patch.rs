@@ -0,0 +1,269 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    ScimError,
+    filter::{PatchPath, attribute},
+};
+use serde_json::{Map, Value};
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PatchRequest {
+    #[serde(rename = "Operations", alias = "operations")]
+    pub operations: Vec<PatchOperation>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PatchOperation {
+    pub op: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub value: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatchOp {
+    Add,
+    Replace,
+    Remove,
+}
+
+/// Applies RFC 7644 PATCH operations to the SCIM representation of a resource.
+pub fn apply_patch(resource: &mut Value, operations: Vec<PatchOperation>) -> Result<(), ScimError> {
+    let resource = resource
+        .as_object_mut()
+        .ok_or_else(|| ScimError::invalid_syntax("Resource is not an object."))?;
+
+    for operation in operations {
+        let op = hashify::tiny_map_ignore_case!(operation.op.as_bytes(),
+            b"add" => PatchOp::Add,
+            b"replace" => PatchOp::Replace,
+            b"remove" => PatchOp::Remove,
+        )
+        .ok_or_else(|| {
+            ScimError::invalid_syntax(format!("Unsupported PATCH operation {:?}.", operation.op))
+        })?;
+
+        match (operation.path, op) {
+            (Some(path), _) => {
+                apply_path(resource, &PatchPath::parse(&path)?, operation.value, op)?;
+            }
+            (None, PatchOp::Add | PatchOp::Replace) => {
+                let Some(Value::Object(values)) = operation.value else {
+                    return Err(ScimError::invalid_value(
+                        "PATCH operations without a path require an object value.",
+                    ));
+                };
+                for (path, value) in values {
+                    apply_path(resource, &PatchPath::parse(&path)?, Some(value), op)?;
+                }
+            }
+            (None, PatchOp::Remove) => {
+                return Err(ScimError::no_target("Remove operations require a path."));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_path(
+    resource: &mut Map<String, Value>,
+    path: &PatchPath,
+    value: Option<Value>,
+    op: PatchOp,
+) -> Result<(), ScimError> {
+    let key = resource
+        .keys()
+        .find(|key| key.eq_ignore_ascii_case(&path.name))
+        .cloned()
+        .unwrap_or_else(|| path.name.clone());
+    let value = match (op, value) {
+        (PatchOp::Remove, value) => value,
+        (_, Some(value)) => Some(value),
+        (_, None) => {
+            return Err(ScimError::invalid_value(format!(
+                "Missing value for {:?}.",
+                path.name
+            )));
+        }
+    };
+
+    let Some(filter) = &path.filter else {
+        return match (op, &path.sub_attribute, value) {
+            (PatchOp::Remove, Some(sub_attribute), _) => {
+                if let Some(Value::Object(target)) = resource.get_mut(&key) {
+                    remove_key(target, sub_attribute);
+                }
+                Ok(())
+            }
+            (PatchOp::Remove, None, Some(Value::Array(items))) => {
+                // Remove the listed values from a multi-valued attribute
+                if let Some(Value::Array(existing)) = resource.get_mut(&key) {
+                    existing.retain(|item| !items.iter().any(|other| same_value(item, other)));
+                }
+                Ok(())
+            }
+            (PatchOp::Remove, None, _) => {
+                resource.remove(&key);
+                Ok(())
+            }
+            (_, Some(sub_attribute), Some(value)) => {
+                let target = resource
+                    .entry(key)
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if !target.is_object() {
+                    *target = Value::Object(Map::new());
+                }
+                if let Value::Object(target) = target {
+                    set_key(target, sub_attribute, value);
+                }
+                Ok(())
+            }
+            (PatchOp::Add, None, Some(value)) => {
+                match resource.get_mut(&key) {
+                    Some(Value::Array(existing)) => match value {
+                        Value::Array(items) => add_values(existing, items),
+                        item => add_values(existing, vec![item]),
+                    },
+                    Some(Value::Object(existing)) if value.is_object() => {
+                        if let Value::Object(values) = value {
+                            for (name, value) in values {
+                                set_key(existing, &name, value);
+                            }
+                        }
+                    }
+                    _ => {
+                        resource.insert(key, value);
+                    }
+                }
+                Ok(())
+            }
+            (_, None, Some(value)) => {
+                resource.insert(key, value);
+                Ok(())
+            }
+            (_, _, None) => Ok(()),
+        };
+    };
+
+    let items = match resource
+        .entry(key)
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(items) => items,
+        _ => {
+            return Err(ScimError::invalid_path(format!(
+                "Attribute {:?} is not multi-valued.",
+                path.name
+            )));
+        }
+    };
+    let matched = items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| filter.matches(item).then_some(idx))
+        .collect::<Vec<_>>();
+
+    match (op, value) {
+        (PatchOp::Remove, _) if matched.is_empty() => Err(ScimError::no_target(format!(
+            "No values of {:?} match the filter.",
+            path.name
+        ))),
+        (PatchOp::Remove, _) => {
+            if let Some(sub_attribute) = &path.sub_attribute {
+                for idx in matched {
+                    if let Value::Object(item) = &mut items[idx] {
+                        remove_key(item, sub_attribute);
+                    }
+                }
+            } else {
+                let mut idx = 0;
+                items.retain(|_| {
+                    idx += 1;
+                    !matched.contains(&(idx - 1))
+                });
+            }
+            Ok(())
+        }
+        (_, Some(value)) if matched.is_empty() => {
+            // Create the element described by a simple equality filter, as sent by
+            // identity providers that address values such as emails[type eq "work"].value
+            let Some((name, filter_value)) = filter.as_equality() else {
+                return Err(ScimError::no_target(format!(
+                    "No values of {:?} match the filter.",
+                    path.name
+                )));
+            };
+            let mut item = Map::new();
+            item.insert(name.to_string(), filter_value.clone());
+            merge_item(&mut item, path.sub_attribute.as_deref(), value);
+            items.push(Value::Object(item));
+            Ok(())
+        }
+        (_, Some(value)) => {
+            for idx in matched {
+                match &mut items[idx] {
+                    Value::Object(item) => {
+                        merge_item(item, path.sub_attribute.as_deref(), value.clone());
+                    }
+                    item => {
+                        *item = value.clone();
+                    }
+                }
+            }
+            Ok(())
+        }
+        (_, None) => Ok(()),
+    }
+}
+
+fn merge_item(item: &mut Map<String, Value>, sub_attribute: Option<&str>, value: Value) {
+    match (sub_attribute, value) {
+        (Some(sub_attribute), value) => set_key(item, sub_attribute, value),
+        (None, Value::Object(values)) => {
+            for (name, value) in values {
+                set_key(item, &name, value);
+            }
+        }
+        (None, value) => set_key(item, "value", value),
+    }
+}
+
+fn add_values(existing: &mut Vec<Value>, items: Vec<Value>) {
+    for item in items {
+        if !existing.iter().any(|other| same_value(other, &item)) {
+            existing.push(item);
+        }
+    }
+}
+
+fn same_value(a: &Value, b: &Value) -> bool {
+    match (attribute(a, "value"), attribute(b, "value")) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn set_key(target: &mut Map<String, Value>, name: &str, value: Value) {
+    let key = target
+        .keys()
+        .find(|key| key.eq_ignore_ascii_case(name))
+        .cloned()
+        .unwrap_or_else(|| name.to_string());
+    target.insert(key, value);
+}
+
+fn remove_key(target: &mut Map<String, Value>, name: &str) {
+    if let Some(key) = target
+        .keys()
+        .find(|key| key.eq_ignore_ascii_case(name))
+        .cloned()
+    {
+        target.remove(&key);
+    }
+}
@@ -0,0 +1,129 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    ResourceKind, SCHEMA_GROUP, SCHEMA_RESOURCE_TYPE, SCHEMA_SCHEMA,
+    SCHEMA_SERVICE_PROVIDER_CONFIG, SCHEMA_USER, ScimSession,
+};
+use serde_json::{Value, json};
+
+pub(crate) fn service_provider_config(scim: &ScimSession<'_>) -> Value {
+    json!({
+        "schemas": [SCHEMA_SERVICE_PROVIDER_CONFIG],
+        "patch": {"supported": true},
+        "bulk": {"supported": false, "maxOperations": 0, "maxPayloadSize": 0},
+        "filter": {
+            "supported": true,
+            "maxResults": scim.server.core.jmap.query_max_results,
+        },
+        "changePassword": {"supported": true},
+        "sort": {"supported": false},
+        "etag": {"supported": false},
+        "authenticationSchemes": [{
+            "type": "oauthbearertoken",
+            "name": "API Key",
+            "description": "Bearer authentication using an API key issued to a tenant account.",
+            "primary": true,
+        }],
+        "meta": {
+            "resourceType": "ServiceProviderConfig",
+            "location": format!("{}/ServiceProviderConfig", scim.base_url),
+        },
+    })
+}
+
+pub(crate) fn resource_types(scim: &ScimSession<'_>) -> Vec<Value> {
+    [ResourceKind::User, ResourceKind::Group]
+        .into_iter()
+        .map(|kind| {
+            json!({
+                "schemas": [SCHEMA_RESOURCE_TYPE],
+                "id": kind.resource_type(),
+                "name": kind.resource_type(),
+                "endpoint": format!("/{}", kind.endpoint()),
+                "schema": kind.schema(),
+                "meta": {
+                    "resourceType": "ResourceType",
+                    "location": format!("{}/ResourceTypes/{}", scim.base_url, kind.resource_type()),
+                },
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn schemas(scim: &ScimSession<'_>) -> Vec<Value> {
+    vec![
+        json!({
+            "schemas": [SCHEMA_SCHEMA],
+            "id": SCHEMA_USER,
+            "name": "User",
+            "description": "User Account",
+            "attributes": [
+                attribute("userName", "string", true, "readWrite", "server"),
+                attribute("displayName", "string", false, "readWrite", "none"),
+                attribute("password", "string", false, "writeOnly", "none"),
+                attribute("active", "boolean", false, "readWrite", "none"),
+                complex_attribute("emails", "readWrite", &[
+                    attribute("value", "string", true, "readWrite", "server"),
+                    attribute("type", "string", false, "readWrite", "none"),
+                    attribute("primary", "boolean", false, "readWrite", "none"),
+                ]),
+                complex_attribute("groups", "readOnly", &[
+                    attribute("value", "string", false, "readOnly", "none"),
+                    attribute("$ref", "reference", false, "readOnly", "none"),
+                    attribute("type", "string", false, "readOnly", "none"),
+                ]),
+            ],
+            "meta": {
+                "resourceType": "Schema",
+                "location": format!("{}/Schemas/{SCHEMA_USER}", scim.base_url),
+            },
+        }),
+        json!({
+            "schemas": [SCHEMA_SCHEMA],
+            "id": SCHEMA_GROUP,
+            "name": "Group",
+            "description": "Group",
+            "attributes": [
+                attribute("displayName", "string", true, "readWrite", "none"),
+                complex_attribute("members", "readWrite", &[
+                    attribute("value", "string", false, "immutable", "none"),
+                    attribute("$ref", "reference", false, "immutable", "none"),
+                    attribute("type", "string", false, "immutable", "none"),
+                ]),
+            ],
+            "meta": {
+                "resourceType": "Schema",
+                "location": format!("{}/Schemas/{SCHEMA_GROUP}", scim.base_url),
+            },
+        }),
+    ]
+}
+
+fn attribute(name: &str, type_: &str, required: bool, mutability: &str, uniqueness: &str) -> Value {
+    json!({
+        "name": name,
+        "type": type_,
+        "multiValued": false,
+        "required": required,
+        "caseExact": false,
+        "mutability": mutability,
+        "returned": if mutability == "writeOnly" { "never" } else { "default" },
+        "uniqueness": uniqueness,
+    })
+}
+
+fn complex_attribute(name: &str, mutability: &str, sub_attributes: &[Value]) -> Value {
+    json!({
+        "name": name,
+        "type": "complex",
+        "multiValued": true,
+        "required": false,
+        "mutability": mutability,
+        "returned": "default",
+        "subAttributes": sub_attributes,
+    })
+}
@@ -0,0 +1,332 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ResourceKind, SCHEMA_USER, ScimError, ScimResult, ScimSession, filter::attribute};
+use registry::schema::{
+    enums::Permission,
+    prelude::List,
+    structs::{Account, Credential, EmailAlias, Permissions, PermissionsList, UserAccount},
+};
+use serde_json::{Map, Value, json};
+use types::id::Id;
+
+#[derive(Debug)]
+pub(crate) struct ScimUser {
+    pub user_name: String,
+    pub display_name: Option<String>,
+    pub emails: Vec<String>,
+    pub password: Option<String>,
+    pub active: bool,
+}
+
+impl ScimUser {
+    pub fn parse(value: &Value) -> ScimResult<Self> {
+        let user_name = attribute(value, "userName")
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_lowercase())
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| ScimError::invalid_value("Attribute userName is required."))?;
+
+        // Fall back to the structured name when no display name is provided
+        let display_name = attribute(value, "displayName")
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_string())
+            .or_else(|| {
+                let name = attribute(value, "name")?;
+                attribute(name, "formatted")
+                    .and_then(|value| value.as_str())
+                    .map(|value| value.trim().to_string())
+                    .or_else(|| {
+                        let name = ["givenName", "familyName"]
+                            .into_iter()
+                            .filter_map(|part| attribute(name, part)?.as_str())
+                            .map(|part| part.trim())
+                            .filter(|part| !part.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        Some(name)
+                    })
+            })
+            .filter(|value| !value.is_empty());
+
+        let mut emails = Vec::new();
+        if let Some(Value::Array(items)) = attribute(value, "emails") {
+            for item in items {
+                let email = match item {
+                    Value::String(email) => email.as_str(),
+                    item => attribute(item, "value")
+                        .and_then(|value| value.as_str())
+                        .unwrap_or_default(),
+                };
+                let email = email.trim().to_lowercase();
+                if !email.is_empty() && email != user_name && !emails.contains(&email) {
+                    emails.push(email);
+                }
+            }
+        }
+
+        let password = attribute(value, "password")
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string());
+
+        // Some identity providers send booleans as strings
+        let active = match attribute(value, "active") {
+            Some(Value::Bool(active)) => *active,
+            Some(Value::String(active)) if active.eq_ignore_ascii_case("true") => true,
+            Some(Value::String(active)) if active.eq_ignore_ascii_case("false") => false,
+            None | Some(Value::Null) => true,
+            Some(_) => {
+                return Err(ScimError::invalid_value(
+                    "Attribute active must be a boolean.",
+                ));
+            }
+        };
+
+        Ok(ScimUser {
+            user_name,
+            display_name,
+            emails,
+            password,
+            active,
+        })
+    }
+}
+
+impl ScimSession<'_> {
+    pub(crate) async fn user_resource(
+        &self,
+        account_id: u32,
+        user: &UserAccount,
+    ) -> ScimResult<Value> {
+        let user_name = self.email_address(&user.name, user.domain_id).await?;
+        let mut emails = vec![json!({
+            "value": user_name,
+            "type": "work",
+            "primary": true,
+        })];
+        for alias in user.aliases.values().filter(|alias| alias.enabled) {
+            emails.push(json!({
+                "value": self.email_address(&alias.name, alias.domain_id).await?,
+                "type": "other",
+            }));
+        }
+        let groups = user
+            .member_group_ids
+            .iter()
+            .filter_map(|group_id| u32::try_from(group_id.id()).ok())
+            .map(|group_id| {
+                json!({
+                    "value": Id::from(group_id).to_string(),
+                    "$ref": self.location(ResourceKind::Group, group_id),
+                    "type": "direct",
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut resource = json!({
+            "schemas": [SCHEMA_USER],
+            "id": Id::from(account_id).to_string(),
+            "userName": user_name,
+            "active": is_active(user),
+            "emails": emails,
+            "groups": groups,
+            "meta": self.meta(ResourceKind::User, account_id, &user.created_at),
+        });
+        if let Some(description) = &user.description {
+            resource["displayName"] = description.as_str().into();
+        }
+
+        Ok(resource)
+    }
+
+    pub(crate) async fn create_user(&self, request: Value) -> ScimResult<u32> {
+        let user = ScimUser::parse(&request)?;
+        let (name, domain_id) = self.resolve_address(&user.user_name).await?;
+        let mut object = json!({
+            "@type": "User",
+            "name": name,
+            "domainId": domain_id,
+            "aliases": self.email_aliases(&user.emails, None).await?,
+        });
+        if let Some(display_name) = user.display_name {
+            object["description"] = display_name.into();
+        }
+        if !user.active {
+            object["permissions"] =
+                serde_json::to_value(set_active(&Permissions::Inherit, false)).unwrap_or_default();
+        }
+        if let Some(password) = user.password {
+            object["credentials"] = json!({
+                "0": {
+                    "@type": "Password",
+                    "secret": password,
+                }
+            });
+        }
+
+        self.create(object).await
+    }
+
+    pub(crate) async fn replace_user(&self, account_id: u32, request: Value) -> ScimResult<Value> {
+        let Account::User(current) = self.account(account_id).await? else {
+            return Err(ScimError::not_found(format!(
+                "User {} not found.",
+                Id::from(account_id)
+            )));
+        };
+        let user = ScimUser::parse(&request)?;
+
+        let mut changes = Map::new();
+        if user.active != is_active(&current) {
+            // Deactivated users keep their data but can no longer log in,
+            // only DELETE requests deprovision an account
+            changes.insert(
+                "permissions".to_string(),
+                serde_json::to_value(set_active(&current.permissions, user.active))
+                    .unwrap_or_default(),
+            );
+        }
+        let (name, domain_id) = self.resolve_address(&user.user_name).await?;
+        if name != current.name || domain_id != current.domain_id {
+            changes.insert("name".to_string(), name.into());
+            changes.insert("domainId".to_string(), domain_id.to_string().into());
+        }
+        if user.display_name != current.description {
+            changes.insert("description".to_string(), user.display_name.into());
+        }
+        let aliases = self.email_aliases(&user.emails, Some(&current)).await?;
+        if aliases != serde_json::to_value(&current.aliases).unwrap_or_default() {
+            changes.insert("aliases".to_string(), aliases);
+        }
+        if let Some(password) = user.password {
+            if let Some(idx) = current.credentials.0.iter().find_map(|(idx, credential)| {
+                matches!(credential, Credential::Password(_)).then_some(*idx)
+            }) {
+                changes.insert(format!("credentials/{idx}/secret"), password.into());
+            } else {
+                let idx = current
+                    .credentials
+                    .0
+                    .keys()
+                    .max()
+                    .map(|idx| idx + 1)
+                    .unwrap_or_default();
+                changes.insert(
+                    format!("credentials/{idx}"),
+                    json!({
+                        "@type": "Password",
+                        "secret": password,
+                    }),
+                );
+            }
+        }
+
+        if !changes.is_empty() {
+            let mut updates = Map::new();
+            updates.insert(Id::from(account_id).to_string(), changes.into());
+            self.update(updates).await?;
+        }
+
+        self.resource(ResourceKind::User, account_id).await
+    }
+
+    pub(crate) async fn delete_user(&self, account_id: u32) -> ScimResult<()> {
+        if account_id == self.access_token.account_id() {
+            return Err(ScimError::mutability(
+                "The provisioning account cannot be deprovisioned.",
+            ));
+        }
+        if !matches!(self.account(account_id).await?, Account::User(_)) {
+            return Err(ScimError::not_found(format!(
+                "User {} not found.",
+                Id::from(account_id)
+            )));
+        }
+
+        // Destroying the account schedules the removal of its data
+        self.destroy(account_id).await
+    }
+
+    /// Builds the alias list for the secondary e-mail addresses of a user,
+    /// keeping the settings of existing and disabled aliases.
+    async fn email_aliases(
+        &self,
+        emails: &[String],
+        current: Option<&UserAccount>,
+    ) -> ScimResult<Value> {
+        let mut aliases = List::with_capacity(emails.len());
+        for email in emails {
+            let (name, domain_id) = self.resolve_address(email).await?;
+            let alias = current
+                .and_then(|current| {
+                    current
+                        .aliases
+                        .values()
+                        .find(|alias| alias.name == name && alias.domain_id == domain_id)
+                })
+                .cloned()
+                .map(|alias| EmailAlias {
+                    enabled: true,
+                    ..alias
+                })
+                .unwrap_or(EmailAlias {
+                    enabled: true,
+                    name,
+                    domain_id,
+                    description: None,
+                });
+            if !aliases.values().any(|other: &EmailAlias| {
+                other.name == alias.name && other.domain_id == alias.domain_id
+            }) {
+                aliases.push(alias);
+            }
+        }
+        if let Some(current) = current {
+            for alias in current.aliases.values().filter(|alias| !alias.enabled) {
+                if !aliases.values().any(|other: &EmailAlias| {
+                    other.name == alias.name && other.domain_id == alias.domain_id
+                }) {
+                    aliases.push(alias.clone());
+                }
+            }
+        }
+
+        Ok(serde_json::to_value(&aliases).unwrap_or_default())
+    }
+}
+
+fn is_active(user: &UserAccount) -> bool {
+    match &user.permissions {
+        Permissions::Inherit => true,
+        Permissions::Merge(list) | Permissions::Replace(list) => !list
+            .disabled_permissions
+            .contains(&Permission::Authenticate),
+    }
+}
+
+/// Suspends or restores the ability of a user to authenticate, leaving any
+/// other permission overrides untouched.
+fn set_active(permissions: &Permissions, active: bool) -> Permissions {
+    let mut list = match permissions {
+        Permissions::Inherit => PermissionsList::default(),
+        Permissions::Merge(list) | Permissions::Replace(list) => list.clone(),
+    };
+    list.disabled_permissions
+        .inner_mut()
+        .retain(|permission| *permission != Permission::Authenticate);
+    if !active {
+        list.disabled_permissions.push(Permission::Authenticate);
+    }
+
+    match permissions {
+        Permissions::Replace(_) => Permissions::Replace(list),
+        _ if list.enabled_permissions.is_empty() && list.disabled_permissions.is_empty() => {
+            Permissions::Inherit
+        }
+        _ => Permissions::Merge(list),
+    }
+}
@@ -6,6 +6,7 @@
 
 use protocol::ObjectId;
 use protocol::capability::Capability;
+use protocol::metadata::MetadataCode;
 use std::borrow::Cow;
 
 pub mod parser;
@@ -75,9 +76,28 @@ pub enum Command {
     // RFC 9208
     GetQuota,
     GetQuotaRoot,
+    SetQuota,
 
     // RFC 9698
     GetJmapAccess,
+
+    // RFC 4467
+    GenUrlAuth,
+    ResetKey,
+    UrlFetch,
+
+    // RFC 5465
+    Notify,
+
+    // RFC 5464
+    GetMetadata,
+    SetMetadata,
+
+    // RFC 4978
+    Compress,
+
+    // RFC 8508
+    Replace(bool),
 }
 
 impl Command {
@@ -92,6 +112,7 @@ impl Command {
                 | Command::Expunge(true)
                 | Command::Sort(true)
                 | Command::Thread(true)
+                | Command::Replace(true)
         )
     }
 }
@@ -107,6 +128,12 @@ pub enum ResponseCode {
     AuthenticationFailed,
     AuthorizationFailed,
     BadCharset,
+    BadEvent {
+        supported: Vec<&'static str>,
+    },
+    BadUrl {
+        url: String,
+    },
     Cannot,
     Capability {
         capabilities: Vec<Capability>,
@@ -134,6 +161,7 @@ pub enum ResponseCode {
     ReadOnly,
     ReadWrite,
     ServerBug,
+    TooBig,
     TryCreate,
     UidNext,
     UidNotSticky,
@@ -154,6 +182,15 @@ pub enum ResponseCode {
 
     // USEATTR
     UseAttr,
+
+    // METADATA
+    Metadata(MetadataCode),
+
+    // COMPRESS
+    CompressionActive,
+
+    // UIDONLY
+    UidRequired,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -9,20 +9,22 @@ use compact_str::ToCompactString;
 use crate::{
     Command,
     protocol::{
-        Flag,
-        append::{self, Message},
+        Flag, Sequence,
+        append::{self, CatenatePart, Message},
     },
     receiver::{Request, Token, bad},
     utf7::utf7_maybe_decode,
 };
 
-use super::parse_datetime;
+use super::{parse_datetime, parse_sequence_set};
 
 enum State {
     None,
     Flags,
     UTF8,
     UTF8Data,
+    Catenate,
+    CatenateData,
 }
 
 impl Request<Command> {
@@ -48,6 +50,7 @@ impl Request<Command> {
                         message: vec![],
                         flags: vec![],
                         received_at: None,
+                        catenate: vec![],
                     };
                     let mut state = State::None;
                     let mut seen_flags = false;
@@ -61,6 +64,7 @@ impl Request<Command> {
                                         State::Flags
                                     }
                                     State::UTF8 => State::UTF8Data,
+                                    State::Catenate => State::CatenateData,
                                     _ => {
                                         return Err(bad(
                                             self.tag.to_compact_string(),
@@ -70,7 +74,7 @@ impl Request<Command> {
                                 };
                             }
                             Token::ParenthesisClose => match state {
-                                State::None | State::UTF8 => {
+                                State::None | State::UTF8 | State::Catenate => {
                                     return Err(bad(
                                         self.tag.to_compact_string(),
                                         "Invalid closing parenthesis found.",
@@ -82,11 +86,24 @@ impl Request<Command> {
                                 State::UTF8Data => {
                                     break;
                                 }
+                                State::CatenateData => {
+                                    if message.catenate.is_empty() {
+                                        return Err(bad(
+                                            self.tag.to_compact_string(),
+                                            "Missing CATENATE parts.",
+                                        ));
+                                    }
+                                    break;
+                                }
                             },
                             Token::Argument(value) => match state {
                                 State::None => {
                                     if value.eq_ignore_ascii_case(b"utf8") {
                                         state = State::UTF8;
+                                    } else if value.eq_ignore_ascii_case(b"catenate")
+                                        && matches!(tokens.peek(), Some(Token::ParenthesisOpen))
+                                    {
+                                        state = State::Catenate;
                                     } else if matches!(tokens.peek(), Some(Token::Argument(_)))
                                         && value.len() <= 28
                                         && !value.contains(&b'\n')
@@ -116,6 +133,40 @@ impl Request<Command> {
                                         "Expected parenthesis after UTF8.",
                                     ));
                                 }
+                                State::Catenate => {
+                                    return Err(bad(
+                                        self.tag.to_compact_string(),
+                                        "Expected parenthesis after CATENATE.",
+                                    ));
+                                }
+                                State::CatenateData => {
+                                    let part = match tokens.next() {
+                                        Some(Token::Argument(data))
+                                            if value.eq_ignore_ascii_case(b"text") =>
+                                        {
+                                            CatenatePart::Text(data)
+                                        }
+                                        Some(Token::Argument(url))
+                                            if value.eq_ignore_ascii_case(b"url") =>
+                                        {
+                                            CatenatePart::Url(String::from_utf8(url).map_err(
+                                                |_| {
+                                                    bad(
+                                                        self.tag.to_compact_string(),
+                                                        "Invalid UTF-8 in URL.",
+                                                    )
+                                                },
+                                            )?)
+                                        }
+                                        _ => {
+                                            return Err(bad(
+                                                self.tag.to_compact_string(),
+                                                "Invalid CATENATE part.",
+                                            ));
+                                        }
+                                    };
+                                    message.catenate.push(part);
+                                }
                                 State::UTF8Data => {
                                     if message.message.is_empty() {
                                         message.message = value;
@@ -147,6 +198,44 @@ impl Request<Command> {
             }
         }
     }
+
+    pub fn parse_replace(mut self, is_utf8: bool) -> trc::Result<append::ReplaceArguments> {
+        if self.tokens.len() < 3 {
+            return Err(self.into_error("Missing arguments."));
+        }
+
+        // A single message number or "*"
+        let sequence = parse_sequence_set(&self.tokens.remove(0).unwrap_bytes())
+            .map_err(|v| bad(self.tag.to_compact_string(), v))?;
+        if !matches!(
+            sequence,
+            Sequence::Number { .. }
+                | Sequence::Range {
+                    start: None,
+                    end: None
+                }
+        ) {
+            return Err(bad(
+                self.tag.to_compact_string(),
+                "Expected a single message number.",
+            ));
+        }
+
+        let arguments = self.parse_append(is_utf8)?;
+        if arguments.messages.len() == 1 {
+            Ok(append::ReplaceArguments {
+                tag: arguments.tag,
+                sequence,
+                mailbox_name: arguments.mailbox_name,
+                message: arguments.messages.into_iter().next().unwrap(),
+            })
+        } else {
+            Err(bad(
+                arguments.tag.to_compact_string(),
+                "Expected a single replacement message.",
+            ))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -154,12 +243,74 @@ mod tests {
 
     use crate::{
         protocol::{
-            Flag,
-            append::{self, Message},
+            Flag, Sequence,
+            append::{self, CatenatePart, Message},
         },
         receiver::{Error, Receiver},
     };
 
+    #[test]
+    fn parse_replace() {
+        let mut receiver = Receiver::new();
+
+        for (command, arguments) in [
+            (
+                "A003 REPLACE 4 Drafts (\\Seen \\Draft) {1+}\r\na\r\n",
+                append::ReplaceArguments {
+                    tag: "A003".into(),
+                    sequence: Sequence::Number { value: 4 },
+                    mailbox_name: "Drafts".into(),
+                    message: Message {
+                        message: vec![b'a'],
+                        flags: vec![Flag::Seen, Flag::Draft],
+                        received_at: None,
+                        catenate: vec![],
+                    },
+                },
+            ),
+            (
+                "A004 UID REPLACE * Drafts {1+}\r\nb\r\n",
+                append::ReplaceArguments {
+                    tag: "A004".into(),
+                    sequence: Sequence::Range {
+                        start: None,
+                        end: None,
+                    },
+                    mailbox_name: "Drafts".into(),
+                    message: Message {
+                        message: vec![b'b'],
+                        flags: vec![],
+                        received_at: None,
+                        catenate: vec![],
+                    },
+                },
+            ),
+        ] {
+            assert_eq!(
+                receiver
+                    .parse(&mut command.as_bytes().iter())
+                    .expect(command)
+                    .parse_replace(false)
+                    .expect(command),
+                arguments,
+                "{:?}",
+                command
+            );
+        }
+
+        for command in [
+            "A005 REPLACE 1:3 Drafts {1+}\r\na\r\n",
+            "A006 REPLACE 1 Drafts\r\n",
+            "A007 REPLACE 1 Drafts {1+}\r\na {1+}\r\nb\r\n",
+        ] {
+            receiver
+                .parse(&mut command.as_bytes().iter())
+                .expect(command)
+                .parse_replace(false)
+                .unwrap_err();
+        }
+    }
+
     #[test]
     fn parse_append() {
         let mut receiver = Receiver::new();
@@ -174,6 +325,7 @@ mod tests {
                         message: vec![b'a'],
                         flags: vec![Flag::Seen],
                         received_at: None,
+                        catenate: vec![],
                     }],
                 },
             ),
@@ -186,6 +338,7 @@ mod tests {
                         message: vec![b'a'],
                         flags: vec![Flag::Seen, Flag::Draft, Flag::MDNSent],
                         received_at: None,
+                        catenate: vec![],
                     }],
                 },
             ),
@@ -198,6 +351,7 @@ mod tests {
                         message: vec![b'a'],
                         flags: vec![Flag::Junk],
                         received_at: Some(760689784),
+                        catenate: vec![],
                     }],
                 },
             ),
@@ -210,6 +364,7 @@ mod tests {
                         message: vec![b'a'],
                         flags: vec![],
                         received_at: Some(1668977999),
+                        catenate: vec![],
                     }],
                 },
             ),
@@ -222,6 +377,7 @@ mod tests {
                         message: vec![b'a'],
                         flags: vec![],
                         received_at: Some(1668977999),
+                        catenate: vec![],
                     }],
                 },
             ),
@@ -234,6 +390,7 @@ mod tests {
                         message: vec![b'h', b'e', b'l', b'l', b'o'],
                         flags: vec![Flag::Draft],
                         received_at: None,
+                        catenate: vec![],
                     }],
                 },
             ),
@@ -246,6 +403,7 @@ mod tests {
                         message: vec![b'h', b'e', b'l', b'l', b'o'],
                         flags: vec![Flag::Draft],
                         received_at: Some(1668977999),
+                        catenate: vec![],
                     }],
                 },
             ),
@@ -258,6 +416,31 @@ mod tests {
                         message: vec![b'a'],
                         flags: vec![Flag::Seen],
                         received_at: Some(760689784),
+                        catenate: vec![],
+                    }],
+                },
+            ),
+            (
+                concat!(
+                    "A003 APPEND Drafts (\\Seen) CATENATE (URL \"/Drafts;UIDVALIDITY=385759045/;UID=20/;section=HEADER\" ",
+                    "TEXT {7+}\r\nhello\r\n URL /Drafts;UIDVALIDITY=385759045/;UID=20/;section=1.MIME)\r\n"
+                ),
+                append::Arguments {
+                    tag: "A003".into(),
+                    mailbox_name: "Drafts".into(),
+                    messages: vec![Message {
+                        message: vec![],
+                        flags: vec![Flag::Seen],
+                        received_at: None,
+                        catenate: vec![
+                            CatenatePart::Url(
+                                "/Drafts;UIDVALIDITY=385759045/;UID=20/;section=HEADER".into(),
+                            ),
+                            CatenatePart::Text(b"hello\r\n".to_vec()),
+                            CatenatePart::Url(
+                                "/Drafts;UIDVALIDITY=385759045/;UID=20/;section=1.MIME".into(),
+                            ),
+                        ],
                     }],
                 },
             ),
@@ -320,6 +503,7 @@ mod tests {
                                     .to_vec(),
                                     flags: vec![Flag::Seen],
                                     received_at: None,
+                                    catenate: vec![],
                                 },
                                 Message {
                                     message: concat!(
@@ -336,6 +520,7 @@ mod tests {
                                     .to_vec(),
                                     flags: vec![Flag::Seen],
                                     received_at: Some(760689784),
+                                    catenate: vec![],
                                 }
                             ],
                         },
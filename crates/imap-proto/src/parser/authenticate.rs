@@ -38,6 +38,7 @@ impl Mechanism {
             "DIGEST-MD5" => Self::DigestMd5,
             "SCRAM-SHA-1" => Self::ScramSha1,
             "SCRAM-SHA-256" => Self::ScramSha256,
+            "SCRAM-SHA-256-PLUS" => Self::ScramSha256Plus,
             "APOP" => Self::Apop,
             "NTLM" => Self::Ntlm,
             "GSSAPI" => Self::Gssapi,
@@ -84,6 +85,14 @@ mod tests {
                     params: vec![],
                 },
             ),
+            (
+                "A02 AUTHENTICATE SCRAM-SHA-256-PLUS cD10bHMtZXhwb3J0ZXIsLG49dXNlcixyPWFiYw==\r\n",
+                authenticate::Arguments {
+                    tag: "A02".into(),
+                    mechanism: Mechanism::ScramSha256Plus,
+                    params: vec!["cD10bHMtZXhwb3J0ZXIsLG49dXNlcixyPWFiYw==".into()],
+                },
+            ),
         ] {
             assert_eq!(
                 receiver
@@ -0,0 +1,86 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Command,
+    protocol::compress::{Algorithm, Arguments},
+    receiver::{Request, bad},
+};
+use compact_str::ToCompactString;
+
+/*
+
+   command-auth =/ compress
+
+   compress    = "COMPRESS" SP algorithm
+
+   algorithm   = "DEFLATE"
+
+*/
+
+impl Request<Command> {
+    pub fn parse_compress(self) -> trc::Result<Arguments> {
+        if self.tokens.len() != 1 {
+            return Err(bad(
+                self.tag.to_compact_string(),
+                "Expected compression algorithm.",
+            ));
+        }
+
+        let algorithm = self.tokens.into_iter().next().unwrap().unwrap_bytes();
+        if algorithm.eq_ignore_ascii_case(b"DEFLATE") {
+            Ok(Arguments {
+                tag: self.tag,
+                algorithm: Algorithm::Deflate,
+            })
+        } else {
+            Err(bad(
+                self.tag.to_compact_string(),
+                format!(
+                    "Unsupported compression algorithm '{}'.",
+                    String::from_utf8_lossy(&algorithm)
+                ),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        protocol::compress::{Algorithm, Arguments},
+        receiver::Receiver,
+    };
+
+    #[test]
+    fn parse_compress() {
+        let mut receiver = Receiver::new();
+
+        assert_eq!(
+            receiver
+                .parse(&mut "a COMPRESS DEFLATE\r\n".as_bytes().iter())
+                .unwrap()
+                .parse_compress()
+                .unwrap(),
+            Arguments {
+                tag: "a".into(),
+                algorithm: Algorithm::Deflate,
+            }
+        );
+
+        for command in [
+            "b COMPRESS\r\n",
+            "c COMPRESS LZMA\r\n",
+            "d COMPRESS DEFLATE DEFLATE\r\n",
+        ] {
+            receiver
+                .parse(&mut command.as_bytes().iter())
+                .unwrap()
+                .parse_compress()
+                .unwrap_err();
+        }
+    }
+}
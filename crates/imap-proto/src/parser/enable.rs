@@ -42,6 +42,7 @@ impl Capability {
             "QRESYNC" => Self::QResync,
             "UTF8=ACCEPT" => Self::Utf8Accept,
             "OBJECTID+" => Self::ObjectIdPlus,
+            "UIDONLY" => Self::UidOnly,
         )
         .ok_or_else(|| {
             format!(
@@ -90,6 +91,13 @@ mod tests {
                     ],
                 },
             ),
+            (
+                "t5 ENABLE UIDONLY\r\n",
+                enable::Arguments {
+                    tag: "t5".into(),
+                    capabilities: vec![Capability::UidOnly],
+                },
+            ),
         ] {
             assert_eq!(
                 receiver
@@ -7,7 +7,10 @@
 use super::{PushUnique, parse_number, parse_sequence_set};
 use crate::{
     Command,
-    protocol::fetch::{self, Attribute, Section},
+    protocol::{
+        fetch::{self, Attribute, Section},
+        search::PartialRange,
+    },
     receiver::{Request, Token, bad},
 };
 use compact_str::{CompactString, ToCompactString, format_compact};
@@ -328,6 +331,9 @@ impl Request<Command> {
                         "OBJECTID" => {
                             attributes.push_unique(Attribute::ObjectId);
                         },
+                        "SAVEDATE" => {
+                            attributes.push_unique(Attribute::SaveDate);
+                        },
                         _ => {
                             return Err(bad(
                                 CompactString::from_string_buffer(self.tag),
@@ -369,9 +375,10 @@ impl Request<Command> {
             }
         }
 
-        // CONDSTORE parameters
+        // CONDSTORE and PARTIAL parameters
         let mut changed_since = None;
         let mut include_vanished = false;
+        let mut partial = None;
         if let Some(Token::ParenthesisOpen) = tokens.peek() {
             tokens.next();
             while let Some(token) = tokens.next() {
@@ -394,6 +401,18 @@ impl Request<Command> {
                     Token::Argument(param) if param.eq_ignore_ascii_case(b"VANISHED") => {
                         include_vanished = true;
                     }
+                    Token::Argument(param) if param.eq_ignore_ascii_case(b"PARTIAL") => {
+                        partial = PartialRange::parse(
+                            &tokens
+                                .next()
+                                .ok_or_else(|| {
+                                    bad(self.tag.to_compact_string(), "Missing PARTIAL range.")
+                                })?
+                                .unwrap_bytes(),
+                        )
+                        .map_err(|v| bad(self.tag.to_compact_string(), v))?
+                        .into();
+                    }
                     Token::ParenthesisClose => {
                         break;
                     }
@@ -414,6 +433,7 @@ impl Request<Command> {
                 attributes,
                 changed_since,
                 include_vanished,
+                partial,
             })
         } else {
             Err(bad(
@@ -507,6 +527,7 @@ mod tests {
         protocol::{
             Sequence,
             fetch::{self, Attribute, Section},
+            search::PartialRange,
         },
         receiver::Receiver,
     };
@@ -534,6 +555,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -548,6 +570,7 @@ mod tests {
                     }],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -562,6 +585,7 @@ mod tests {
                     }],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -582,6 +606,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -599,6 +624,7 @@ mod tests {
                     }],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -621,6 +647,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -637,6 +664,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -652,6 +680,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -725,6 +754,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -740,6 +770,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -756,6 +787,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -770,6 +802,7 @@ mod tests {
                     ],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -780,6 +813,7 @@ mod tests {
                     attributes: vec![Attribute::Flags, Attribute::ModSeq],
                     changed_since: 12345.into(),
                     include_vanished: true,
+                    partial: None,
                 },
             ),
             (
@@ -790,6 +824,7 @@ mod tests {
                     attributes: vec![Attribute::Uid],
                     changed_since: 1.into(),
                     include_vanished: true,
+                    partial: None,
                 },
             ),
             (
@@ -800,6 +835,7 @@ mod tests {
                     attributes: vec![Attribute::ObjectId],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
                 },
             ),
             (
@@ -810,6 +846,22 @@ mod tests {
                     attributes: vec![Attribute::Uid, Attribute::ObjectId, Attribute::Flags],
                     changed_since: None,
                     include_vanished: false,
+                    partial: None,
+                },
+            ),
+            (
+                "A012 UID FETCH 1:* (FLAGS SAVEDATE) (PARTIAL -1:-30)\r\n",
+                fetch::Arguments {
+                    tag: "A012".into(),
+                    sequence_set: Sequence::range(1.into(), None),
+                    attributes: vec![Attribute::Flags, Attribute::SaveDate],
+                    changed_since: None,
+                    include_vanished: false,
+                    partial: Some(PartialRange {
+                        low: 1,
+                        high: 30,
+                        is_last: true,
+                    }),
                 },
             ),
         ] {
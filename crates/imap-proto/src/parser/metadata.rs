@@ -0,0 +1,350 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use compact_str::ToCompactString;
+use std::{iter::Peekable, vec::IntoIter};
+
+use crate::{
+    Command,
+    protocol::metadata::{Depth, GetArguments, SetArguments},
+    receiver::{Request, Token, bad},
+    utf7::utf7_maybe_decode,
+};
+
+use super::parse_number;
+
+/*
+
+   getmetadata     = "GETMETADATA" [SP getmetadata-options]
+                     SP mailbox SP entries
+
+   getmetadata-options = "(" getmetadata-option *(SP getmetadata-option) ")"
+
+   getmetadata-option = "MAXSIZE" SP number / "DEPTH" SP ("0" / "1" / "infinity")
+
+   entries         = entry / "(" entry *(SP entry) ")"
+
+   setmetadata     = "SETMETADATA" SP mailbox SP "(" entry-value *(SP entry-value) ")"
+
+   entry-value     = entry SP value
+
+   entry           = astring
+                     ;; slash-separated path to entry
+                     ;; MUST NOT contain "*" or "%"
+
+   value           = nstring / literal8
+
+*/
+
+impl Request<Command> {
+    pub fn parse_get_metadata(self, is_utf8: bool) -> trc::Result<GetArguments> {
+        let mut tokens = self.tokens.into_iter().peekable();
+        let mut max_size = None;
+        let mut depth = Depth::Zero;
+
+        // Parse options
+        if tokens
+            .peek()
+            .is_some_and(|token| token.is_parenthesis_open())
+        {
+            tokens.next();
+            loop {
+                match tokens.next() {
+                    Some(Token::ParenthesisClose) => break,
+                    Some(token) if token.eq_ignore_ascii_case(b"MAXSIZE") => {
+                        max_size = parse_number::<u32>(
+                            &tokens
+                                .next()
+                                .ok_or_else(|| {
+                                    bad(self.tag.to_compact_string(), "Missing MAXSIZE value.")
+                                })?
+                                .unwrap_bytes(),
+                        )
+                        .map_err(|v| bad(self.tag.to_compact_string(), v))?
+                        .into();
+                    }
+                    Some(token) if token.eq_ignore_ascii_case(b"DEPTH") => {
+                        depth = match tokens.next() {
+                            Some(token) if token.eq_ignore_ascii_case(b"0") => Depth::Zero,
+                            Some(token) if token.eq_ignore_ascii_case(b"1") => Depth::One,
+                            Some(token) if token.eq_ignore_ascii_case(b"infinity") => {
+                                Depth::Infinity
+                            }
+                            _ => {
+                                return Err(bad(
+                                    self.tag.to_compact_string(),
+                                    "Expected DEPTH value of 0, 1 or infinity.",
+                                ));
+                            }
+                        };
+                    }
+                    Some(token) => {
+                        return Err(bad(
+                            self.tag.to_compact_string(),
+                            format!("Unsupported option '{token}'."),
+                        ));
+                    }
+                    None => {
+                        return Err(bad(
+                            self.tag.to_compact_string(),
+                            "Missing closing parenthesis.",
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mailbox_name = parse_mailbox_name(&mut tokens, &self.tag, is_utf8)?;
+
+        // Parse entries
+        let mut entries = Vec::new();
+        match tokens.next() {
+            Some(Token::ParenthesisOpen) => loop {
+                match tokens.next() {
+                    Some(Token::ParenthesisClose) => break,
+                    Some(token) => {
+                        entries.push(parse_entry(token, &self.tag)?);
+                    }
+                    None => {
+                        return Err(bad(
+                            self.tag.to_compact_string(),
+                            "Missing closing parenthesis.",
+                        ));
+                    }
+                }
+            },
+            Some(token) => {
+                entries.push(parse_entry(token, &self.tag)?);
+            }
+            None => (),
+        }
+
+        if !entries.is_empty() {
+            Ok(GetArguments {
+                tag: self.tag,
+                mailbox_name,
+                entries,
+                max_size,
+                depth,
+            })
+        } else {
+            Err(bad(self.tag.to_compact_string(), "Missing entries."))
+        }
+    }
+
+    pub fn parse_set_metadata(self, is_utf8: bool) -> trc::Result<SetArguments> {
+        let mut tokens = self.tokens.into_iter().peekable();
+        let mailbox_name = parse_mailbox_name(&mut tokens, &self.tag, is_utf8)?;
+
+        if !tokens
+            .next()
+            .is_some_and(|token| token.is_parenthesis_open())
+        {
+            return Err(bad(
+                self.tag.to_compact_string(),
+                "Expected parenthesized list of entries.",
+            ));
+        }
+
+        let mut entries: Vec<(String, Option<Vec<u8>>)> = Vec::new();
+        loop {
+            let entry = match tokens.next() {
+                Some(Token::ParenthesisClose) => break,
+                Some(token) => parse_entry(token, &self.tag)?,
+                None => {
+                    return Err(bad(
+                        self.tag.to_compact_string(),
+                        "Missing closing parenthesis.",
+                    ));
+                }
+            };
+            let value = match tokens.next() {
+                Some(Token::Argument(value)) if !value.eq_ignore_ascii_case(b"NIL") => Some(value),
+                Some(Token::Argument(_)) => None,
+                Some(Token::Nil) => Some(vec![]),
+                _ => {
+                    return Err(bad(
+                        self.tag.to_compact_string(),
+                        format!("Missing value for entry '{entry}'."),
+                    ));
+                }
+            };
+
+            // Last value wins
+            entries.retain(|(name, _)| name != &entry);
+            entries.push((entry, value));
+        }
+
+        if !entries.is_empty() {
+            Ok(SetArguments {
+                tag: self.tag,
+                mailbox_name,
+                entries,
+            })
+        } else {
+            Err(bad(self.tag.to_compact_string(), "Missing entries."))
+        }
+    }
+}
+
+fn parse_mailbox_name(
+    tokens: &mut Peekable<IntoIter<Token>>,
+    tag: &str,
+    is_utf8: bool,
+) -> trc::Result<String> {
+    // An empty mailbox name refers to the server
+    Ok(utf7_maybe_decode(
+        tokens
+            .next()
+            .ok_or_else(|| bad(tag.to_compact_string(), "Missing mailbox name."))?
+            .unwrap_string()
+            .map_err(|v| bad(tag.to_compact_string(), v))?,
+        is_utf8,
+    ))
+}
+
+fn parse_entry(token: Token, tag: &str) -> trc::Result<String> {
+    let entry = token
+        .unwrap_string()
+        .map_err(|v| bad(tag.to_compact_string(), v))?
+        .to_lowercase();
+
+    if (entry.starts_with("/private/") || entry.starts_with("/shared/"))
+        && !entry.ends_with('/')
+        && !entry.contains("//")
+        && !entry
+            .chars()
+            .any(|ch| matches!(ch, '*' | '%') || ch.is_ascii_control())
+    {
+        Ok(entry)
+    } else {
+        Err(bad(
+            tag.to_compact_string(),
+            format!("Invalid entry name '{entry}'."),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        protocol::metadata::{Depth, GetArguments, SetArguments},
+        receiver::Receiver,
+    };
+
+    #[test]
+    fn parse_get_metadata() {
+        let mut receiver = Receiver::new();
+
+        for (command, arguments) in [
+            (
+                "A01 GETMETADATA \"\" /shared/comment\r\n",
+                GetArguments {
+                    tag: "A01".into(),
+                    mailbox_name: "".into(),
+                    entries: vec!["/shared/comment".into()],
+                    max_size: None,
+                    depth: Depth::Zero,
+                },
+            ),
+            (
+                "A02 GETMETADATA (MAXSIZE 1024 DEPTH infinity) INBOX (/Shared/Comment /private/vendor)\r\n",
+                GetArguments {
+                    tag: "A02".into(),
+                    mailbox_name: "INBOX".into(),
+                    entries: vec!["/shared/comment".into(), "/private/vendor".into()],
+                    max_size: Some(1024),
+                    depth: Depth::Infinity,
+                },
+            ),
+            (
+                "A03 GETMETADATA (DEPTH 1) \"My Folder\" /private/vendor/acme\r\n",
+                GetArguments {
+                    tag: "A03".into(),
+                    mailbox_name: "My Folder".into(),
+                    entries: vec!["/private/vendor/acme".into()],
+                    max_size: None,
+                    depth: Depth::One,
+                },
+            ),
+        ] {
+            assert_eq!(
+                receiver
+                    .parse(&mut command.as_bytes().iter())
+                    .unwrap()
+                    .parse_get_metadata(true)
+                    .unwrap(),
+                arguments,
+                "{command}"
+            );
+        }
+
+        for command in [
+            "A04 GETMETADATA INBOX /shared/*\r\n",
+            "A05 GETMETADATA INBOX /shared/comment/\r\n",
+            "A06 GETMETADATA INBOX /other/comment\r\n",
+            "A07 GETMETADATA (DEPTH 2) INBOX /shared/comment\r\n",
+            "A08 GETMETADATA INBOX\r\n",
+            "A09 GETMETADATA INBOX /private\r\n",
+        ] {
+            receiver
+                .parse(&mut command.as_bytes().iter())
+                .unwrap()
+                .parse_get_metadata(true)
+                .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn parse_set_metadata() {
+        let mut receiver = Receiver::new();
+
+        for (command, arguments) in [
+            (
+                "A01 SETMETADATA INBOX (/private/comment \"My new comment\")\r\n",
+                SetArguments {
+                    tag: "A01".into(),
+                    mailbox_name: "INBOX".into(),
+                    entries: vec![("/private/comment".into(), Some(b"My new comment".to_vec()))],
+                },
+            ),
+            (
+                "A02 SETMETADATA \"\" (/shared/comment NIL /shared/admin {10+}\r\nmailto:a@b /shared/comment \"\")\r\n",
+                SetArguments {
+                    tag: "A02".into(),
+                    mailbox_name: "".into(),
+                    entries: vec![
+                        ("/shared/admin".into(), Some(b"mailto:a@b".to_vec())),
+                        ("/shared/comment".into(), Some(vec![])),
+                    ],
+                },
+            ),
+        ] {
+            assert_eq!(
+                receiver
+                    .parse(&mut command.as_bytes().iter())
+                    .unwrap()
+                    .parse_set_metadata(true)
+                    .unwrap(),
+                arguments,
+                "{command}"
+            );
+        }
+
+        for command in [
+            "A03 SETMETADATA INBOX /private/comment \"value\"\r\n",
+            "A04 SETMETADATA INBOX (/private/comment)\r\n",
+            "A05 SETMETADATA INBOX ()\r\n",
+            "A06 SETMETADATA INBOX (/private//comment \"value\")\r\n",
+        ] {
+            receiver
+                .parse(&mut command.as_bytes().iter())
+                .unwrap()
+                .parse_set_metadata(true)
+                .unwrap_err();
+        }
+    }
+}
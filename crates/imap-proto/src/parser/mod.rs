@@ -7,6 +7,7 @@
 pub mod acl;
 pub mod append;
 pub mod authenticate;
+pub mod compress;
 pub mod copy_move;
 pub mod create;
 pub mod delete;
@@ -15,6 +16,8 @@ pub mod fetch;
 pub mod list;
 pub mod login;
 pub mod lsub;
+pub mod metadata;
+pub mod notify;
 pub mod quota;
 pub mod rename;
 pub mod search;
@@ -24,6 +27,7 @@ pub mod status;
 pub mod store;
 pub mod subscribe;
 pub mod thread;
+pub mod urlauth;
 
 use std::{borrow::Cow, str::FromStr};
 
@@ -80,13 +84,22 @@ impl CommandParser for Command {
             "ID" => Command::Id,
             "GETQUOTA" => Command::GetQuota,
             "GETQUOTAROOT" => Command::GetQuotaRoot,
+            "SETQUOTA" => Command::SetQuota,
             "GETJMAPACCESS" => Command::GetJmapAccess,
+            "GENURLAUTH" => Command::GenUrlAuth,
+            "RESETKEY" => Command::ResetKey,
+            "URLFETCH" => Command::UrlFetch,
+            "NOTIFY" => Command::Notify,
+            "GETMETADATA" => Command::GetMetadata,
+            "SETMETADATA" => Command::SetMetadata,
+            "COMPRESS" => Command::Compress,
+            "REPLACE" => Command::Replace(uid),
         )
     }
 
     #[inline(always)]
     fn tokenize_brackets(&self) -> bool {
-        matches!(self, Command::Fetch(_))
+        matches!(self, Command::Fetch(_) | Command::Notify)
     }
 }
 
@@ -422,6 +435,31 @@ mod tests {
             Command::parse(b"GETJMAPACCESS", false),
             Some(Command::GetJmapAccess)
         );
+        assert_eq!(
+            Command::parse(b"GENURLAUTH", false),
+            Some(Command::GenUrlAuth)
+        );
+        assert_eq!(Command::parse(b"RESETKEY", false), Some(Command::ResetKey));
+        assert_eq!(Command::parse(b"URLFETCH", false), Some(Command::UrlFetch));
+        assert_eq!(Command::parse(b"NOTIFY", false), Some(Command::Notify));
+        assert_eq!(
+            Command::parse(b"GETMETADATA", false),
+            Some(Command::GetMetadata)
+        );
+        assert_eq!(
+            Command::parse(b"SETMETADATA", false),
+            Some(Command::SetMetadata)
+        );
+        assert_eq!(Command::parse(b"COMPRESS", false), Some(Command::Compress));
+        assert_eq!(
+            Command::parse(b"REPLACE", false),
+            Some(Command::Replace(false))
+        );
+        assert_eq!(
+            Command::parse(b"REPLACE", true),
+            Some(Command::Replace(true))
+        );
+        assert_eq!(Command::parse(b"SETQUOTA", false), Some(Command::SetQuota));
         assert_eq!(Command::parse(b"NOTACOMMAND", false), None);
     }
 
@@ -0,0 +1,417 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use compact_str::ToCompactString;
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use crate::{
+    Command, ResponseCode,
+    protocol::notify::{Arguments, Event, EventGroup, MailboxFilter, NotifySet},
+    receiver::{Request, Token, bad},
+    utf7::utf7_maybe_decode,
+};
+
+/*
+
+   notify          = "NOTIFY" SP (notify-set / notify-none)
+
+   notify-set      = "SET" [status-indicator] SP event-groups
+
+   status-indicator = SP "STATUS"
+
+   event-group     = "(" filter-mailboxes SP events ")"
+
+   filter-mailboxes = "selected" / "selected-delayed" / "inboxes" / "personal" /
+                      "subscribed" / ( "subtree" SP one-or-more-mailbox ) /
+                      ( "mailboxes" SP one-or-more-mailbox )
+
+   events          = ( "(" event *(SP event) ")" ) / "NONE"
+
+   event           = ( "MessageNew" [SP "(" fetch-att *(SP fetch-att) ")" ] ) /
+                     "MessageExpunge" / "FlagChange" / "AnnotationChange" /
+                     "MailboxName" / "SubscriptionChange" / "MailboxMetadataChange" /
+                     "ServerMetadataChange"
+
+*/
+
+pub const SUPPORTED_EVENTS: &[&str] = &[
+    "MessageNew",
+    "MessageExpunge",
+    "FlagChange",
+    "MailboxName",
+    "SubscriptionChange",
+];
+
+impl Request<Command> {
+    pub fn parse_notify(self, is_utf8: bool) -> trc::Result<Arguments> {
+        let mut tokens = self.tokens.into_iter().peekable();
+
+        match tokens.next() {
+            Some(token) if token.eq_ignore_ascii_case(b"NONE") => {
+                return Ok(Arguments {
+                    tag: self.tag,
+                    set: None,
+                });
+            }
+            Some(token) if token.eq_ignore_ascii_case(b"SET") => (),
+            _ => {
+                return Err(bad(
+                    self.tag.to_compact_string(),
+                    "Expected 'SET' or 'NONE'.",
+                ));
+            }
+        }
+
+        let status = if tokens
+            .peek()
+            .is_some_and(|token| token.eq_ignore_ascii_case(b"STATUS"))
+        {
+            tokens.next();
+            true
+        } else {
+            false
+        };
+
+        let mut groups: Vec<EventGroup> = Vec::new();
+        while let Some(token) = tokens.next() {
+            if !token.is_parenthesis_open() {
+                return Err(bad(self.tag.to_compact_string(), "Expected event group."));
+            }
+
+            // Parse filter
+            let filter = tokens
+                .next()
+                .ok_or_else(|| bad(self.tag.to_compact_string(), "Missing mailbox filter."))?
+                .unwrap_bytes();
+            let filter = hashify::tiny_map_ignore_case!(filter.as_slice(),
+                "SELECTED" => MailboxFilter::Selected,
+                "SELECTED-DELAYED" => MailboxFilter::SelectedDelayed,
+                "INBOXES" => MailboxFilter::Inboxes,
+                "PERSONAL" => MailboxFilter::Personal,
+                "SUBSCRIBED" => MailboxFilter::Subscribed,
+                "SUBTREE" => MailboxFilter::Subtree(Vec::new()),
+                "MAILBOXES" => MailboxFilter::Mailboxes(Vec::new()),
+            )
+            .ok_or_else(|| {
+                bad(
+                    self.tag.to_compact_string(),
+                    format!(
+                        "Invalid mailbox filter {:?}.",
+                        String::from_utf8_lossy(&filter)
+                    ),
+                )
+            })?;
+            let filter = match filter {
+                MailboxFilter::Subtree(_) => {
+                    MailboxFilter::Subtree(parse_mailboxes(&mut tokens, &self.tag, is_utf8)?)
+                }
+                MailboxFilter::Mailboxes(_) => {
+                    MailboxFilter::Mailboxes(parse_mailboxes(&mut tokens, &self.tag, is_utf8)?)
+                }
+                filter => filter,
+            };
+            if filter.is_selected() && groups.iter().any(|group| group.filter.is_selected()) {
+                return Err(bad(
+                    self.tag.to_compact_string(),
+                    "Only one selected mailbox filter is allowed.",
+                ));
+            }
+
+            // Parse events
+            let mut events = Vec::new();
+            match tokens.next() {
+                Some(Token::ParenthesisOpen) => loop {
+                    match tokens.next() {
+                        Some(Token::Argument(name)) => {
+                            let event = hashify::tiny_map_ignore_case!(name.as_slice(),
+                                "MessageNew" => Event::MessageNew { attributes: Vec::new() },
+                                "MessageExpunge" => Event::MessageExpunge,
+                                "FlagChange" => Event::FlagChange,
+                                "AnnotationChange" => Event::AnnotationChange,
+                                "MailboxName" => Event::MailboxName,
+                                "SubscriptionChange" => Event::SubscriptionChange,
+                                "MailboxMetadataChange" => Event::MailboxMetadataChange,
+                                "ServerMetadataChange" => Event::ServerMetadataChange,
+                            )
+                            .ok_or_else(|| {
+                                trc::ImapEvent::Error
+                                    .into_err()
+                                    .details(format!(
+                                        "Unsupported event {:?}.",
+                                        String::from_utf8_lossy(&name)
+                                    ))
+                                    .code(ResponseCode::BadEvent {
+                                        supported: SUPPORTED_EVENTS.to_vec(),
+                                    })
+                                    .id(self.tag.clone())
+                            })?;
+                            let event = if matches!(event, Event::MessageNew { .. })
+                                && tokens
+                                    .peek()
+                                    .is_some_and(|token| token.is_parenthesis_open())
+                            {
+                                if !filter.is_selected() {
+                                    return Err(bad(
+                                        self.tag.to_compact_string(),
+                                        "Fetch attributes are only allowed for the selected mailbox.",
+                                    ));
+                                }
+                                Event::MessageNew {
+                                    attributes: parse_fetch_attributes(&mut tokens, &self.tag)?,
+                                }
+                            } else {
+                                event
+                            };
+                            if !events.contains(&event) {
+                                events.push(event);
+                            }
+                        }
+                        Some(Token::ParenthesisClose) if !events.is_empty() => break,
+                        _ => {
+                            return Err(bad(self.tag.to_compact_string(), "Expected event name."));
+                        }
+                    }
+                },
+                Some(token) if token.eq_ignore_ascii_case(b"NONE") => (),
+                _ => {
+                    return Err(bad(
+                        self.tag.to_compact_string(),
+                        "Expected event list or 'NONE'.",
+                    ));
+                }
+            }
+
+            // Message events have to be requested together
+            let has_new = events
+                .iter()
+                .any(|event| matches!(event, Event::MessageNew { .. }));
+            let has_expunge = events.contains(&Event::MessageExpunge);
+            if has_new != has_expunge
+                || (!has_new
+                    && (events.contains(&Event::FlagChange)
+                        || events.contains(&Event::AnnotationChange)))
+            {
+                return Err(bad(
+                    self.tag.to_compact_string(),
+                    "MessageNew and MessageExpunge must be requested together with other message events.",
+                ));
+            }
+
+            if tokens
+                .next()
+                .is_none_or(|token| !token.is_parenthesis_close())
+            {
+                return Err(bad(
+                    self.tag.to_compact_string(),
+                    "Expected ')' after event group.",
+                ));
+            }
+
+            groups.push(EventGroup { filter, events });
+        }
+
+        if !groups.is_empty() {
+            Ok(Arguments {
+                tag: self.tag,
+                set: Some(NotifySet { status, groups }),
+            })
+        } else {
+            Err(bad(
+                self.tag.to_compact_string(),
+                "At least one event group is required.",
+            ))
+        }
+    }
+}
+
+fn parse_mailboxes(
+    tokens: &mut Peekable<IntoIter<Token>>,
+    tag: &str,
+    is_utf8: bool,
+) -> trc::Result<Vec<String>> {
+    let mut mailboxes = Vec::new();
+    match tokens.next() {
+        Some(Token::ParenthesisOpen) => {
+            for token in tokens.by_ref() {
+                match token {
+                    Token::ParenthesisClose => break,
+                    token => {
+                        mailboxes.push(utf7_maybe_decode(
+                            token
+                                .unwrap_string()
+                                .map_err(|v| bad(tag.to_compact_string(), v))?,
+                            is_utf8,
+                        ));
+                    }
+                }
+            }
+        }
+        Some(token) => {
+            mailboxes.push(utf7_maybe_decode(
+                token
+                    .unwrap_string()
+                    .map_err(|v| bad(tag.to_compact_string(), v))?,
+                is_utf8,
+            ));
+        }
+        None => (),
+    }
+
+    if !mailboxes.is_empty() {
+        Ok(mailboxes)
+    } else {
+        Err(bad(
+            tag.to_compact_string(),
+            "Expected one or more mailboxes.",
+        ))
+    }
+}
+
+fn parse_fetch_attributes(
+    tokens: &mut Peekable<IntoIter<Token>>,
+    tag: &str,
+) -> trc::Result<Vec<crate::protocol::fetch::Attribute>> {
+    // Reuse the FETCH parser on the enclosed attribute list
+    let mut fetch_tokens = vec![Token::Argument(b"1".to_vec())];
+    let mut depth = 0;
+    for token in tokens.by_ref() {
+        match token {
+            Token::ParenthesisOpen => depth += 1,
+            Token::ParenthesisClose => depth -= 1,
+            _ => (),
+        }
+        fetch_tokens.push(token);
+        if depth == 0 {
+            break;
+        }
+    }
+
+    Request {
+        tag: tag.to_string(),
+        command: Command::Fetch(false),
+        tokens: fetch_tokens,
+    }
+    .parse_fetch()
+    .map(|arguments| arguments.attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        protocol::{
+            fetch::{Attribute, Section},
+            notify::{Arguments, Event, EventGroup, MailboxFilter, NotifySet},
+        },
+        receiver::Receiver,
+    };
+
+    #[test]
+    fn parse_notify() {
+        let mut receiver = Receiver::new();
+
+        for (command, arguments) in [
+            (
+                "A01 NOTIFY NONE\r\n",
+                Arguments {
+                    tag: "A01".into(),
+                    set: None,
+                },
+            ),
+            (
+                concat!(
+                    "A02 NOTIFY SET STATUS (selected (MessageNew (UID FLAGS BODY.PEEK[HEADER.FIELDS (From Subject)]) ",
+                    "MessageExpunge FlagChange)) (subtree (Lists \"Shared Folders\") (MessageNew MessageExpunge)) ",
+                    "(personal (MailboxName SubscriptionChange)) (mailboxes Junk NONE)\r\n"
+                ),
+                Arguments {
+                    tag: "A02".into(),
+                    set: Some(NotifySet {
+                        status: true,
+                        groups: vec![
+                            EventGroup {
+                                filter: MailboxFilter::Selected,
+                                events: vec![
+                                    Event::MessageNew {
+                                        attributes: vec![
+                                            Attribute::Uid,
+                                            Attribute::Flags,
+                                            Attribute::BodySection {
+                                                peek: true,
+                                                sections: vec![Section::HeaderFields {
+                                                    not: false,
+                                                    fields: vec!["From".into(), "Subject".into()],
+                                                }],
+                                                partial: None,
+                                            },
+                                        ],
+                                    },
+                                    Event::MessageExpunge,
+                                    Event::FlagChange,
+                                ],
+                            },
+                            EventGroup {
+                                filter: MailboxFilter::Subtree(vec![
+                                    "Lists".into(),
+                                    "Shared Folders".into(),
+                                ]),
+                                events: vec![
+                                    Event::MessageNew { attributes: vec![] },
+                                    Event::MessageExpunge,
+                                ],
+                            },
+                            EventGroup {
+                                filter: MailboxFilter::Personal,
+                                events: vec![Event::MailboxName, Event::SubscriptionChange],
+                            },
+                            EventGroup {
+                                filter: MailboxFilter::Mailboxes(vec!["Junk".into()]),
+                                events: vec![],
+                            },
+                        ],
+                    }),
+                },
+            ),
+        ] {
+            assert_eq!(
+                receiver
+                    .parse(&mut command.as_bytes().iter())
+                    .unwrap()
+                    .parse_notify(true)
+                    .unwrap(),
+                arguments
+            );
+        }
+
+        for command in [
+            "A03 NOTIFY SET (selected (MessageNew))\r\n",
+            "A04 NOTIFY SET (inboxes (FlagChange))\r\n",
+            "A05 NOTIFY SET (inboxes (MessageNew (UID) MessageExpunge))\r\n",
+            "A06 NOTIFY SET (selected NONE) (selected-delayed NONE)\r\n",
+            "A07 NOTIFY SET\r\n",
+        ] {
+            receiver
+                .parse(&mut command.as_bytes().iter())
+                .unwrap()
+                .parse_notify(true)
+                .unwrap_err();
+        }
+
+        let err = receiver
+            .parse(
+                &mut "A08 NOTIFY SET (personal (MailboxName Bogus))\r\n"
+                    .as_bytes()
+                    .iter(),
+            )
+            .unwrap()
+            .parse_notify(true)
+            .unwrap_err();
+        assert_eq!(
+            err.value_as_str(trc::Key::Code),
+            Some("BADEVENT (MessageNew MessageExpunge FlagChange MailboxName SubscriptionChange)")
+        );
+    }
+}
@@ -8,11 +8,13 @@ use compact_str::ToCompactString;
 
 use crate::{
     Command,
-    protocol::quota,
-    receiver::{Request, bad},
+    protocol::{capability::QuotaResourceName, quota},
+    receiver::{Request, Token, bad},
     utf7::utf7_maybe_decode,
 };
 
+use super::parse_number;
+
 impl Request<Command> {
     pub fn parse_get_quota_root(self, is_utf8: bool) -> trc::Result<quota::Arguments> {
         match self.tokens.len() {
@@ -49,11 +51,85 @@ impl Request<Command> {
             _ => Err(self.into_error("Too many arguments.")),
         }
     }
+
+    pub fn parse_set_quota(self) -> trc::Result<quota::SetArguments> {
+        if self.tokens.len() < 3 {
+            return Err(self.into_error("Missing arguments."));
+        }
+
+        let mut tokens = self.tokens.into_iter();
+        let name = tokens
+            .next()
+            .unwrap()
+            .unwrap_string()
+            .map_err(|v| bad(self.tag.to_compact_string(), v))?;
+        if !tokens
+            .next()
+            .is_some_and(|token| token.is_parenthesis_open())
+        {
+            return Err(bad(self.tag.to_compact_string(), "Expected resource list."));
+        }
+
+        let mut resources = Vec::new();
+        loop {
+            match tokens.next() {
+                Some(Token::ParenthesisClose) => break,
+                Some(Token::Argument(value)) => {
+                    let resource = QuotaResourceName::parse(&value).ok_or_else(|| {
+                        bad(
+                            self.tag.to_compact_string(),
+                            format!(
+                                "Unknown quota resource '{}'.",
+                                String::from_utf8_lossy(&value)
+                            ),
+                        )
+                    })?;
+                    let limit = parse_number::<u64>(
+                        &tokens
+                            .next()
+                            .ok_or_else(|| {
+                                bad(self.tag.to_compact_string(), "Missing resource limit.")
+                            })?
+                            .unwrap_bytes(),
+                    )
+                    .map_err(|v| bad(self.tag.to_compact_string(), v))?;
+                    resources.push((resource, limit));
+                }
+                _ => {
+                    return Err(bad(self.tag.to_compact_string(), "Invalid resource list."));
+                }
+            }
+        }
+
+        if tokens.next().is_none() {
+            Ok(quota::SetArguments {
+                tag: self.tag,
+                name,
+                resources,
+            })
+        } else {
+            Err(bad(self.tag.to_compact_string(), "Too many arguments."))
+        }
+    }
+}
+
+impl QuotaResourceName {
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        hashify::tiny_map_ignore_case!(value,
+            "STORAGE" => QuotaResourceName::Storage,
+            "MESSAGE" => QuotaResourceName::Message,
+            "MAILBOX" => QuotaResourceName::Mailbox,
+            "ANNOTATION-STORAGE" => QuotaResourceName::AnnotationStorage,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{protocol::quota, receiver::Receiver};
+    use crate::{
+        protocol::{capability::QuotaResourceName, quota},
+        receiver::Receiver,
+    };
 
     #[test]
     fn parse_quota() {
@@ -90,5 +166,59 @@ mod tests {
                 .unwrap(),
             arguments
         );
+
+        for (command, arguments) in [
+            (
+                "A001 SETQUOTA \"#12\" (STORAGE 512)\r\n",
+                quota::SetArguments {
+                    tag: "A001".into(),
+                    name: "#12".into(),
+                    resources: vec![(QuotaResourceName::Storage, 512)],
+                },
+            ),
+            (
+                "A002 SETQUOTA \"#12\" (storage 1024 MESSAGE 10)\r\n",
+                quota::SetArguments {
+                    tag: "A002".into(),
+                    name: "#12".into(),
+                    resources: vec![
+                        (QuotaResourceName::Storage, 1024),
+                        (QuotaResourceName::Message, 10),
+                    ],
+                },
+            ),
+            (
+                "A003 SETQUOTA \"#12\" ()\r\n",
+                quota::SetArguments {
+                    tag: "A003".into(),
+                    name: "#12".into(),
+                    resources: vec![],
+                },
+            ),
+        ] {
+            assert_eq!(
+                receiver
+                    .parse(&mut command.as_bytes().iter())
+                    .unwrap()
+                    .parse_set_quota()
+                    .expect(command),
+                arguments
+            );
+        }
+
+        for command in [
+            "A004 SETQUOTA \"#12\" (STORAGE)\r\n",
+            "A005 SETQUOTA \"#12\" (FLUX 10)\r\n",
+            "A006 SETQUOTA \"#12\"\r\n",
+        ] {
+            assert!(
+                receiver
+                    .parse(&mut command.as_bytes().iter())
+                    .unwrap()
+                    .parse_set_quota()
+                    .is_err(),
+                "{command}"
+            );
+        }
     }
 }
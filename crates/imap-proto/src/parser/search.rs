@@ -14,7 +14,7 @@ use mail_parser::decoders::charsets::map::charset_decoder;
 
 use crate::Command;
 use crate::protocol::search::{self, Filter};
-use crate::protocol::search::{ModSeqEntry, ResultOption};
+use crate::protocol::search::{ModSeqEntry, PartialRange, ResultOption};
 use crate::protocol::{Flag, ProtocolVersion};
 use crate::receiver::{Request, Token, bad};
 
@@ -83,9 +83,17 @@ pub fn parse_result_options(
         return Err(Cow::from("Invalid result option, expected parenthesis."));
     }
 
-    for token in tokens {
+    while let Some(token) = tokens.next() {
         match token {
             Token::ParenthesisClose => break,
+            Token::Argument(value) if value.eq_ignore_ascii_case(b"partial") => {
+                result_options.push(ResultOption::Partial(PartialRange::parse(
+                    &tokens
+                        .next()
+                        .ok_or_else(|| Cow::from("Missing PARTIAL range."))?
+                        .unwrap_bytes(),
+                )?));
+            }
             Token::Argument(value) => {
                 result_options.push(ResultOption::parse(&value)?);
             }
@@ -93,6 +101,16 @@ pub fn parse_result_options(
         }
     }
 
+    if result_options.contains(&ResultOption::All)
+        && result_options
+            .iter()
+            .any(|option| matches!(option, ResultOption::Partial(_)))
+    {
+        return Err(Cow::from(
+            "PARTIAL and ALL result options are mutually exclusive.",
+        ));
+    }
+
     Ok(result_options)
 }
 
@@ -420,6 +438,37 @@ pub fn parse_filters(
                                 .unwrap_string()?,
                         ));
 
+                    },
+                    "SAVEDBEFORE" => {
+                        filters.push(Filter::SavedBefore(parse_date(
+                            &tokens
+                                .next()
+                                .ok_or_else(|| Cow::from("Expected date"))?
+                                .unwrap_bytes(),
+                        )?));
+
+                    },
+                    "SAVEDON" => {
+                        filters.push(Filter::SavedOn(parse_date(
+                            &tokens
+                                .next()
+                                .ok_or_else(|| Cow::from("Expected date"))?
+                                .unwrap_bytes(),
+                        )?));
+
+                    },
+                    "SAVEDSINCE" => {
+                        filters.push(Filter::SavedSince(parse_date(
+                            &tokens
+                                .next()
+                                .ok_or_else(|| Cow::from("Expected date"))?
+                                .unwrap_bytes(),
+                        )?));
+
+                    },
+                    "SAVEDATESUPPORTED" => {
+                        filters.push(Filter::SaveDateSupported);
+
                     },
                     "OR" => {
                         if filters_stack.len() > 10 {
@@ -537,12 +586,43 @@ impl ResultOption {
     }
 }
 
+impl PartialRange {
+    pub fn parse(value: &[u8]) -> super::Result<Self> {
+        let (low, high) = value
+            .iter()
+            .position(|&ch| ch == b':')
+            .map(|pos| (&value[..pos], &value[pos + 1..]))
+            .ok_or_else(|| Cow::from("Invalid PARTIAL range."))?;
+        let is_last = low.first() == Some(&b'-');
+        if is_last != (high.first() == Some(&b'-')) {
+            return Err(Cow::from("Invalid PARTIAL range."));
+        }
+        let (low, high) = if is_last {
+            (&low[1..], &high[1..])
+        } else {
+            (low, high)
+        };
+        let low = parse_number::<u32>(low)?;
+        let high = parse_number::<u32>(high)?;
+
+        if low != 0 && high != 0 {
+            Ok(PartialRange {
+                low: low.min(high),
+                high: low.max(high),
+                is_last,
+            })
+        } else {
+            Err(Cow::from("Invalid PARTIAL range."))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         protocol::{
             Flag, ProtocolVersion, Sequence,
-            search::{self, Filter, ModSeqEntry, ResultOption},
+            search::{self, Filter, ModSeqEntry, PartialRange, ResultOption},
         },
         receiver::Receiver,
     };
@@ -853,6 +933,37 @@ mod tests {
                     sort: None,
                 },
             ),
+            (
+                b"6 UID SEARCH RETURN (COUNT PARTIAL -1:-50) SAVEDSINCE 1-Dec-2023\r\n".to_vec(),
+                search::Arguments {
+                    tag: "6".into(),
+                    result_options: vec![
+                        ResultOption::Count,
+                        ResultOption::Partial(PartialRange {
+                            low: 1,
+                            high: 50,
+                            is_last: true,
+                        }),
+                    ],
+                    filter: vec![Filter::SavedSince(1701388800)],
+                    is_esearch: true,
+                    sort: None,
+                },
+            ),
+            (
+                b"7 UID SEARCH RETURN (PARTIAL 200:101) SAVEDATESUPPORTED\r\n".to_vec(),
+                search::Arguments {
+                    tag: "7".into(),
+                    result_options: vec![ResultOption::Partial(PartialRange {
+                        low: 101,
+                        high: 200,
+                        is_last: false,
+                    })],
+                    filter: vec![Filter::SaveDateSupported],
+                    is_esearch: true,
+                    sort: None,
+                },
+            ),
         ] {
             let command_str = String::from_utf8_lossy(&command).into_owned();
             assert_eq!(
@@ -866,5 +977,21 @@ mod tests {
                 command_str
             );
         }
+
+        for command in [
+            b"8 SEARCH RETURN (ALL PARTIAL 1:10) ALL\r\n".to_vec(),
+            b"9 SEARCH RETURN (PARTIAL -1:10) ALL\r\n".to_vec(),
+            b"10 SEARCH RETURN (PARTIAL 0:10) ALL\r\n".to_vec(),
+        ] {
+            assert!(
+                receiver
+                    .parse(&mut command.iter())
+                    .unwrap()
+                    .parse_search(ProtocolVersion::Rev2)
+                    .is_err(),
+                "{}",
+                String::from_utf8_lossy(&command)
+            );
+        }
     }
 }
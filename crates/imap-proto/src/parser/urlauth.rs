@@ -0,0 +1,179 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use compact_str::ToCompactString;
+
+use crate::{
+    Command,
+    protocol::urlauth::{GenUrlAuthArguments, ResetKeyArguments, UrlFetchArguments, UrlMechanism},
+    receiver::{Request, bad},
+    utf7::utf7_maybe_decode,
+};
+
+/*
+
+   genurlauth      = "GENURLAUTH" 1*(SP url-rump SP mechanism)
+
+   resetkey        = "RESETKEY" [SP mailbox *(SP mechanism)]
+
+   urlfetch        = "URLFETCH" 1*(SP url-full)
+
+*/
+
+impl Request<Command> {
+    pub fn parse_gen_url_auth(self) -> trc::Result<GenUrlAuthArguments> {
+        if self.tokens.is_empty() || !self.tokens.len().is_multiple_of(2) {
+            return Err(self.into_error("Expected URL and mechanism pairs."));
+        }
+
+        let mut urls = Vec::with_capacity(self.tokens.len() / 2);
+        let mut tokens = self.tokens.into_iter();
+        while let (Some(url), Some(mechanism)) = (tokens.next(), tokens.next()) {
+            urls.push(UrlMechanism {
+                url: url
+                    .unwrap_string()
+                    .map_err(|v| bad(self.tag.to_compact_string(), v))?,
+                mechanism: mechanism
+                    .unwrap_string()
+                    .map_err(|v| bad(self.tag.to_compact_string(), v))?
+                    .to_ascii_uppercase(),
+            });
+        }
+
+        Ok(GenUrlAuthArguments {
+            tag: self.tag,
+            urls,
+        })
+    }
+
+    pub fn parse_reset_key(self, is_utf8: bool) -> trc::Result<ResetKeyArguments> {
+        let mut tokens = self.tokens.into_iter();
+        let mailbox_name = tokens
+            .next()
+            .map(|token| {
+                token
+                    .unwrap_string()
+                    .map(|name| utf7_maybe_decode(name, is_utf8))
+                    .map_err(|v| bad(self.tag.to_compact_string(), v))
+            })
+            .transpose()?;
+        let mechanisms = tokens
+            .map(|token| {
+                token
+                    .unwrap_string()
+                    .map(|mechanism| mechanism.to_ascii_uppercase())
+                    .map_err(|v| bad(self.tag.to_compact_string(), v))
+            })
+            .collect::<trc::Result<Vec<_>>>()?;
+
+        Ok(ResetKeyArguments {
+            tag: self.tag,
+            mailbox_name,
+            mechanisms,
+        })
+    }
+
+    pub fn parse_url_fetch(self) -> trc::Result<UrlFetchArguments> {
+        if self.tokens.is_empty() {
+            return Err(self.into_error("Missing URL."));
+        }
+
+        Ok(UrlFetchArguments {
+            urls: self
+                .tokens
+                .into_iter()
+                .map(|token| {
+                    token
+                        .unwrap_string()
+                        .map_err(|v| bad(self.tag.to_compact_string(), v))
+                })
+                .collect::<trc::Result<Vec<_>>>()?,
+            tag: self.tag,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        protocol::urlauth::{
+            GenUrlAuthArguments, ResetKeyArguments, UrlFetchArguments, UrlMechanism,
+        },
+        receiver::Receiver,
+    };
+
+    #[test]
+    fn parse_urlauth() {
+        let mut receiver = Receiver::new();
+
+        assert_eq!(
+            receiver
+                .parse(
+                    &mut "a GENURLAUTH \"imap://joe@example.com/INBOX/;uid=20/;section=1.2;urlauth=submit+fred\" internal\r\n"
+                        .as_bytes()
+                        .iter()
+                )
+                .unwrap()
+                .parse_gen_url_auth()
+                .unwrap(),
+            GenUrlAuthArguments {
+                tag: "a".into(),
+                urls: vec![UrlMechanism {
+                    url: "imap://joe@example.com/INBOX/;uid=20/;section=1.2;urlauth=submit+fred"
+                        .into(),
+                    mechanism: "INTERNAL".into(),
+                }],
+            }
+        );
+
+        for (command, arguments) in [
+            (
+                "b RESETKEY\r\n",
+                ResetKeyArguments {
+                    tag: "b".into(),
+                    mailbox_name: None,
+                    mechanisms: vec![],
+                },
+            ),
+            (
+                "b RESETKEY \"Sent Items\" INTERNAL\r\n",
+                ResetKeyArguments {
+                    tag: "b".into(),
+                    mailbox_name: Some("Sent Items".into()),
+                    mechanisms: vec!["INTERNAL".into()],
+                },
+            ),
+        ] {
+            assert_eq!(
+                receiver
+                    .parse(&mut command.as_bytes().iter())
+                    .unwrap()
+                    .parse_reset_key(true)
+                    .unwrap(),
+                arguments
+            );
+        }
+
+        assert_eq!(
+            receiver
+                .parse(
+                    &mut "c URLFETCH \"imap://joe@example.com/INBOX/;uid=20\" imap://joe@example.com/INBOX/;uid=21\r\n"
+                        .as_bytes()
+                        .iter()
+                )
+                .unwrap()
+                .parse_url_fetch()
+                .unwrap(),
+            UrlFetchArguments {
+                tag: "c".into(),
+                urls: vec![
+                    "imap://joe@example.com/INBOX/;uid=20".into(),
+                    "imap://joe@example.com/INBOX/;uid=21".into()
+                ],
+            }
+        );
+    }
+}
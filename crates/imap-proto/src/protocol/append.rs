@@ -4,7 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::Flag;
+use super::{Flag, Sequence};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Arguments {
@@ -13,9 +13,24 @@ pub struct Arguments {
     pub messages: Vec<Message>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplaceArguments {
+    pub tag: String,
+    pub sequence: Sequence,
+    pub mailbox_name: String,
+    pub message: Message,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Message {
     pub message: Vec<u8>,
     pub flags: Vec<Flag>,
     pub received_at: Option<i64>,
+    pub catenate: Vec<CatenatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatenatePart {
+    Text(Vec<u8>),
+    Url(String),
 }
@@ -54,6 +54,16 @@ pub enum Capability {
     QuotaResource(QuotaResourceName),
     QuotaSet,
     JmapAccess,
+    UrlAuth,
+    Catenate,
+    Notify,
+    Metadata,
+    MetadataServer,
+    CompressDeflate, //COMPRESS=DEFLATE
+    Replace,
+    UidOnly,
+    Partial,
+    SaveDate,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -121,10 +131,25 @@ impl Capability {
             }
             Capability::QuotaSet => b"QUOTA=SET",
             Capability::JmapAccess => b"JMAPACCESS",
+            Capability::UrlAuth => b"URLAUTH",
+            Capability::Catenate => b"CATENATE",
+            Capability::Notify => b"NOTIFY",
+            Capability::Metadata => b"METADATA",
+            Capability::MetadataServer => b"METADATA-SERVER",
+            Capability::CompressDeflate => b"COMPRESS=DEFLATE",
+            Capability::Replace => b"REPLACE",
+            Capability::UidOnly => b"UIDONLY",
+            Capability::Partial => b"PARTIAL",
+            Capability::SaveDate => b"SAVEDATE",
         });
     }
 
-    pub fn all_capabilities(is_authenticated: bool, offer_tls: bool) -> Vec<Capability> {
+    pub fn all_capabilities(
+        is_authenticated: bool,
+        offer_tls: bool,
+        offer_channel_binding: bool,
+        offer_external: bool,
+    ) -> Vec<Capability> {
         let mut capabilities = vec![
             Capability::IMAP4rev2,
             Capability::IMAP4rev1,
@@ -167,13 +192,31 @@ impl Capability {
                 Capability::Rights,
                 Capability::Quota,
                 Capability::QuotaResource(QuotaResourceName::Storage),
+                Capability::QuotaSet,
+                Capability::UrlAuth,
+                Capability::Catenate,
+                Capability::Notify,
+                Capability::Metadata,
+                Capability::MetadataServer,
+                Capability::CompressDeflate,
+                Capability::Replace,
+                Capability::UidOnly,
+                Capability::Partial,
+                Capability::SaveDate,
             ]);
         } else {
             capabilities.extend([
                 Capability::Auth(Mechanism::Plain),
                 Capability::Auth(Mechanism::OAuthBearer),
                 Capability::Auth(Mechanism::XOauth2),
+                Capability::Auth(Mechanism::ScramSha256),
             ]);
+            if offer_channel_binding {
+                capabilities.push(Capability::Auth(Mechanism::ScramSha256Plus));
+            }
+            if offer_external {
+                capabilities.push(Capability::Auth(Mechanism::External));
+            }
         }
         if offer_tls {
             capabilities.push(Capability::StartTLS);
@@ -8,7 +8,7 @@ use super::{
     Flag, ImapResponse, ObjectId, Sequence, literal_string, quoted_or_literal_string,
     quoted_or_literal_string_or_nil, quoted_rfc2822_or_nil, quoted_timestamp,
 };
-use crate::protocol::literal_string_slice;
+use crate::protocol::{literal_string_slice, search::PartialRange};
 use mail_parser::DateTime;
 use std::borrow::Cow;
 use utils::chained_bytes::SliceRange;
@@ -20,10 +20,12 @@ pub struct Arguments {
     pub attributes: Vec<Attribute>,
     pub changed_since: Option<u64>,
     pub include_vanished: bool,
+    pub partial: Option<PartialRange>,
 }
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Response<'x> {
     pub is_uid: bool,
+    pub is_uidonly: bool,
     pub items: Vec<FetchItem<'x>>,
 }
 
@@ -63,6 +65,7 @@ pub enum Attribute {
     },
     ModSeq,
     ObjectId,
+    SaveDate,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -127,6 +130,9 @@ pub enum DataItem<'x> {
         modseq: u64,
     },
     ObjectId(ObjectId),
+    SaveDate {
+        date: Option<i64>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -831,15 +837,32 @@ impl DataItem<'_> {
             DataItem::ObjectId(object_id) => {
                 object_id.serialize(buf);
             }
+            DataItem::SaveDate { date } => {
+                buf.extend_from_slice(b"SAVEDATE ");
+                if let Some(date) = date {
+                    quoted_timestamp(buf, *date);
+                } else {
+                    buf.extend_from_slice(b"NIL");
+                }
+            }
         }
     }
 }
 
 impl FetchItem<'_> {
     pub fn serialize(&self, buf: &mut Vec<u8>) {
+        self.serialize_as(buf, b" FETCH (");
+    }
+
+    // RFC 9586 - the id is a UID rather than a sequence number
+    pub fn serialize_uidonly(&self, buf: &mut Vec<u8>) {
+        self.serialize_as(buf, b" UIDFETCH (");
+    }
+
+    fn serialize_as(&self, buf: &mut Vec<u8>, response: &[u8]) {
         buf.extend_from_slice(b"* ");
         buf.extend_from_slice(self.id.to_string().as_bytes());
-        buf.extend_from_slice(b" FETCH (");
+        buf.extend_from_slice(response);
         for (pos, item) in self.items.iter().enumerate() {
             if pos > 0 {
                 buf.push(b' ');
@@ -854,7 +877,11 @@ impl ImapResponse for Response<'_> {
     fn serialize(self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(128);
         for item in &self.items {
-            item.serialize(&mut buf);
+            if self.is_uidonly {
+                item.serialize_uidonly(&mut buf);
+            } else {
+                item.serialize(&mut buf);
+            }
         }
         buf
     }
@@ -1361,6 +1388,7 @@ mod tests {
             String::from_utf8(
                 Response {
                     is_uid: false,
+                    is_uidonly: false,
                     items: vec![FetchItem {
                         id: 123,
                         items: vec![
@@ -1389,5 +1417,23 @@ mod tests {
                 "RFC822.HEADER {6}\r\nheader)\r\n",
             )
         );
+
+        assert_eq!(
+            String::from_utf8(
+                Response {
+                    is_uid: true,
+                    is_uidonly: true,
+                    items: vec![FetchItem {
+                        id: 983,
+                        items: vec![super::DataItem::Flags {
+                            flags: vec![Flag::Seen],
+                        }],
+                    }],
+                }
+                .serialize(),
+            )
+            .unwrap(),
+            "* 983 UIDFETCH (FLAGS (\\Seen))\r\n"
+        );
     }
 }
@@ -0,0 +1,159 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{literal_string, quoted_string};
+use crate::utf7::utf7_encode;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetArguments {
+    pub tag: String,
+    pub mailbox_name: String,
+    pub entries: Vec<String>,
+    pub max_size: Option<u32>,
+    pub depth: Depth,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetArguments {
+    pub tag: String,
+    pub mailbox_name: String,
+    pub entries: Vec<(String, Option<Vec<u8>>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Depth {
+    #[default]
+    Zero,
+    One,
+    Infinity,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataCode {
+    LongEntries(u32),
+    MaxSize(u32),
+    TooMany,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub mailbox_name: String,
+    pub entries: Vec<(String, Vec<u8>)>,
+}
+
+impl Depth {
+    pub fn matches(&self, entry: &str, name: &str) -> bool {
+        match name.strip_prefix(entry) {
+            Some("") => true,
+            Some(rest) => {
+                rest.starts_with('/')
+                    && match self {
+                        Depth::Zero => false,
+                        Depth::One => !rest[1..].contains('/'),
+                        Depth::Infinity => true,
+                    }
+            }
+            None => false,
+        }
+    }
+}
+
+impl MetadataCode {
+    pub fn serialize(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(b"METADATA ");
+        match self {
+            MetadataCode::LongEntries(size) => {
+                buf.extend_from_slice(b"LONGENTRIES ");
+                buf.extend_from_slice(size.to_string().as_bytes());
+            }
+            MetadataCode::MaxSize(size) => {
+                buf.extend_from_slice(b"MAXSIZE ");
+                buf.extend_from_slice(size.to_string().as_bytes());
+            }
+            MetadataCode::TooMany => {
+                buf.extend_from_slice(b"TOOMANY");
+            }
+        }
+    }
+}
+
+impl Response {
+    pub fn serialize(&self, buf: &mut Vec<u8>, is_utf8: bool) {
+        buf.extend_from_slice(b"* METADATA ");
+        if is_utf8 {
+            quoted_string(buf, &self.mailbox_name);
+        } else {
+            quoted_string(buf, &utf7_encode(&self.mailbox_name));
+        }
+        buf.extend_from_slice(b" (");
+        for (pos, (name, value)) in self.entries.iter().enumerate() {
+            if pos > 0 {
+                buf.push(b' ');
+            }
+            buf.extend_from_slice(name.as_bytes());
+            buf.push(b' ');
+            if value
+                .iter()
+                .all(|ch| ch.is_ascii() && !b"\\\"\r\n\0".contains(ch))
+            {
+                buf.push(b'"');
+                buf.extend_from_slice(value);
+                buf.push(b'"');
+            } else {
+                literal_string(buf, value);
+            }
+        }
+        buf.extend_from_slice(b")\r\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Depth, MetadataCode, Response};
+
+    #[test]
+    fn serialize_metadata() {
+        let mut buf = Vec::new();
+        Response {
+            mailbox_name: "INBOX".into(),
+            entries: vec![
+                ("/private/comment".into(), b"My comment".to_vec()),
+                ("/shared/comment".into(), b"Line 1\r\nLine 2".to_vec()),
+            ],
+        }
+        .serialize(&mut buf, false);
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            concat!(
+                "* METADATA \"INBOX\" (/private/comment \"My comment\" ",
+                "/shared/comment {14}\r\nLine 1\r\nLine 2)\r\n"
+            )
+        );
+
+        let mut buf = Vec::new();
+        MetadataCode::LongEntries(2199).serialize(&mut buf);
+        assert_eq!(buf, b"METADATA LONGENTRIES 2199");
+    }
+
+    #[test]
+    fn depth_matches() {
+        for (depth, entry, name, expected) in [
+            (Depth::Zero, "/shared/comment", "/shared/comment", true),
+            (Depth::Zero, "/shared", "/shared/comment", false),
+            (Depth::One, "/shared", "/shared/comment", true),
+            (Depth::One, "/shared", "/shared/vendor/foo", false),
+            (Depth::Infinity, "/shared", "/shared/vendor/foo", true),
+            (
+                Depth::Infinity,
+                "/shared/comment",
+                "/shared/comments",
+                false,
+            ),
+        ] {
+            assert_eq!(depth.matches(entry, name), expected, "{entry} {name}");
+        }
+    }
+}
@@ -17,6 +17,7 @@ pub mod acl;
 pub mod append;
 pub mod authenticate;
 pub mod capability;
+pub mod compress;
 pub mod copy_move;
 pub mod create;
 pub mod delete;
@@ -25,7 +26,9 @@ pub mod expunge;
 pub mod fetch;
 pub mod list;
 pub mod login;
+pub mod metadata;
 pub mod namespace;
+pub mod notify;
 pub mod quota;
 pub mod rename;
 pub mod search;
@@ -34,6 +37,7 @@ pub mod status;
 pub mod store;
 pub mod subscribe;
 pub mod thread;
+pub mod urlauth;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProtocolVersion {
@@ -469,6 +473,22 @@ impl ResponseCode {
             ResponseCode::AuthenticationFailed => b"AUTHENTICATIONFAILED",
             ResponseCode::AuthorizationFailed => b"AUTHORIZATIONFAILED",
             ResponseCode::BadCharset => b"BADCHARSET",
+            ResponseCode::BadEvent { supported } => {
+                buf.extend_from_slice(b"BADEVENT (");
+                for (pos, event) in supported.iter().enumerate() {
+                    if pos > 0 {
+                        buf.push(b' ');
+                    }
+                    buf.extend_from_slice(event.as_bytes());
+                }
+                buf.push(b')');
+                return;
+            }
+            ResponseCode::BadUrl { url } => {
+                buf.extend_from_slice(b"BADURL ");
+                buf.extend_from_slice(url.as_bytes());
+                return;
+            }
             ResponseCode::Cannot => b"CANNOT",
             ResponseCode::Capability { capabilities } => {
                 buf.extend_from_slice(b"CAPABILITY");
@@ -509,6 +529,7 @@ impl ResponseCode {
             ResponseCode::ReadOnly => b"READ-ONLY",
             ResponseCode::ReadWrite => b"READ-WRITE",
             ResponseCode::ServerBug => b"SERVERBUG",
+            ResponseCode::TooBig => b"TOOBIG",
             ResponseCode::TryCreate => b"TRYCREATE",
             ResponseCode::UidNext => b"UIDNEXT",
             ResponseCode::UidNotSticky => b"UIDNOTSTICKY",
@@ -530,6 +551,12 @@ impl ResponseCode {
                 return;
             }
             ResponseCode::UseAttr => b"USEATTR",
+            ResponseCode::Metadata(code) => {
+                code.serialize(buf);
+                return;
+            }
+            ResponseCode::CompressionActive => b"COMPRESSIONACTIVE",
+            ResponseCode::UidRequired => b"UIDREQUIRED",
         });
     }
 
@@ -542,6 +569,8 @@ impl ResponseCode {
             ResponseCode::AuthenticationFailed => "AUTHENTICATIONFAILED",
             ResponseCode::AuthorizationFailed => "AUTHORIZATIONFAILED",
             ResponseCode::BadCharset => "BADCHARSET",
+            ResponseCode::BadEvent { .. } => "BADEVENT",
+            ResponseCode::BadUrl { .. } => "BADURL",
             ResponseCode::Cannot => "CANNOT",
             ResponseCode::Capability { .. } => "CAPABILITY",
             ResponseCode::ClientBug => "CLIENTBUG",
@@ -563,6 +592,7 @@ impl ResponseCode {
             ResponseCode::ReadOnly => "READ-ONLY",
             ResponseCode::ReadWrite => "READ-WRITE",
             ResponseCode::ServerBug => "SERVERBUG",
+            ResponseCode::TooBig => "TOOBIG",
             ResponseCode::TryCreate => "TRYCREATE",
             ResponseCode::UidNext => "UIDNEXT",
             ResponseCode::UidNotSticky => "UIDNOTSTICKY",
@@ -573,6 +603,9 @@ impl ResponseCode {
             ResponseCode::ObjectId { .. } => "OBJECTID",
             ResponseCode::HighestModseq { .. } => "HIGHESTMODSEQ",
             ResponseCode::UseAttr => "USEATTR",
+            ResponseCode::Metadata(_) => "METADATA",
+            ResponseCode::CompressionActive => "COMPRESSIONACTIVE",
+            ResponseCode::UidRequired => "UIDREQUIRED",
         }
     }
 }
@@ -595,7 +628,18 @@ impl ResponseType {
 
 impl From<ResponseCode> for trc::Value {
     fn from(value: ResponseCode) -> Self {
-        trc::Value::String(CompactString::const_new(value.as_str()))
+        match value {
+            ResponseCode::BadUrl { url } => trc::Value::String(format!("BADURL {url}").into()),
+            ResponseCode::BadEvent { supported } => {
+                trc::Value::String(format!("BADEVENT ({})", supported.join(" ")).into())
+            }
+            ResponseCode::Metadata(code) => {
+                let mut buf = Vec::with_capacity(24);
+                code.serialize(&mut buf);
+                trc::Value::String(String::from_utf8(buf).unwrap_or_default().into())
+            }
+            value => trc::Value::String(CompactString::const_new(value.as_str())),
+        }
     }
 }
 
@@ -764,7 +808,17 @@ impl Display for Command {
             Command::Id => write!(f, "ID"),
             Command::GetQuota => write!(f, "GETQUOTA"),
             Command::GetQuotaRoot => write!(f, "GETQUOTAROOT"),
+            Command::SetQuota => write!(f, "SETQUOTA"),
             Command::GetJmapAccess => write!(f, "GETJMAPACCESS"),
+            Command::GenUrlAuth => write!(f, "GENURLAUTH"),
+            Command::ResetKey => write!(f, "RESETKEY"),
+            Command::UrlFetch => write!(f, "URLFETCH"),
+            Command::Notify => write!(f, "NOTIFY"),
+            Command::GetMetadata => write!(f, "GETMETADATA"),
+            Command::SetMetadata => write!(f, "SETMETADATA"),
+            Command::Compress => write!(f, "COMPRESS"),
+            Command::Replace(false) => write!(f, "REPLACE"),
+            Command::Replace(true) => write!(f, "UID REPLACE"),
         }
     }
 }
@@ -0,0 +1,82 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::fetch::Attribute;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arguments {
+    pub tag: String,
+    pub set: Option<NotifySet>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifySet {
+    pub status: bool,
+    pub groups: Vec<EventGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventGroup {
+    pub filter: MailboxFilter,
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MailboxFilter {
+    Selected,
+    SelectedDelayed,
+    Inboxes,
+    Personal,
+    Subscribed,
+    Subtree(Vec<String>),
+    Mailboxes(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    MessageNew { attributes: Vec<Attribute> },
+    MessageExpunge,
+    FlagChange,
+    AnnotationChange,
+    MailboxName,
+    SubscriptionChange,
+    MailboxMetadataChange,
+    ServerMetadataChange,
+}
+
+impl MailboxFilter {
+    pub fn is_selected(&self) -> bool {
+        matches!(
+            self,
+            MailboxFilter::Selected | MailboxFilter::SelectedDelayed
+        )
+    }
+}
+
+impl Event {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Event::MessageNew { .. } => "MessageNew",
+            Event::MessageExpunge => "MessageExpunge",
+            Event::FlagChange => "FlagChange",
+            Event::AnnotationChange => "AnnotationChange",
+            Event::MailboxName => "MailboxName",
+            Event::SubscriptionChange => "SubscriptionChange",
+            Event::MailboxMetadataChange => "MailboxMetadataChange",
+            Event::ServerMetadataChange => "ServerMetadataChange",
+        }
+    }
+
+    pub fn is_message_event(&self) -> bool {
+        matches!(
+            self,
+            Event::MessageNew { .. }
+                | Event::MessageExpunge
+                | Event::FlagChange
+                | Event::AnnotationChange
+        )
+    }
+}
@@ -44,6 +44,7 @@ pub struct Response {
     pub max: Option<u32>,
     pub count: Option<u32>,
     pub highest_modseq: Option<u64>,
+    pub partial: Option<PartialRange>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -54,6 +55,15 @@ pub enum ResultOption {
     Count,
     Save,
     Context,
+    Partial(PartialRange),
+}
+
+// RFC 9394 - PARTIAL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialRange {
+    pub low: u32,
+    pub high: u32,
+    pub is_last: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -110,6 +120,12 @@ pub enum Filter {
     // RFC 8474 - ObjectID
     EmailId(String),
     ThreadId(String),
+
+    // RFC 8514 - SAVEDATE
+    SavedBefore(i64),
+    SavedOn(i64),
+    SavedSince(i64),
+    SaveDateSupported,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -130,6 +146,32 @@ impl Filter {
     }
 }
 
+impl PartialRange {
+    pub fn slice<'x, T>(&self, items: &'x [T]) -> &'x [T] {
+        let low = self.low as usize;
+        let high = (self.high as usize).min(items.len());
+        if low > high {
+            &[]
+        } else if !self.is_last {
+            &items[low - 1..high]
+        } else {
+            &items[items.len() - high..items.len() - low + 1]
+        }
+    }
+
+    pub fn serialize(&self, buf: &mut Vec<u8>) {
+        if self.is_last {
+            buf.push(b'-');
+        }
+        buf.extend_from_slice(self.low.to_string().as_bytes());
+        buf.push(b':');
+        if self.is_last {
+            buf.push(b'-');
+        }
+        buf.extend_from_slice(self.high.to_string().as_bytes());
+    }
+}
+
 impl Response {
     pub fn serialize(self, tag: &str) -> Vec<u8> {
         let mut buf = Vec::with_capacity(64);
@@ -152,7 +194,17 @@ impl Response {
                 buf.extend_from_slice(b" MAX ");
                 buf.extend_from_slice(max.to_string().as_bytes());
             }
-            if !self.ids.is_empty() {
+            if let Some(partial) = &self.partial {
+                buf.extend_from_slice(b" PARTIAL (");
+                partial.serialize(&mut buf);
+                buf.push(b' ');
+                if !self.ids.is_empty() {
+                    serialize_sequence(&mut buf, &self.ids);
+                } else {
+                    buf.extend_from_slice(b"NIL");
+                }
+                buf.push(b')');
+            } else if !self.ids.is_empty() {
                 buf.extend_from_slice(b" ALL ");
                 serialize_sequence(&mut buf, &self.ids);
             }
@@ -199,6 +251,7 @@ mod tests {
                     max: 11.into(),
                     count: 3.into(),
                     highest_modseq: None,
+                    partial: None,
                 },
                 "A283",
                 "* ESEARCH (TAG \"A283\") COUNT 3 MIN 2 MAX 11 ALL 2,10:11\r\n",
@@ -216,6 +269,7 @@ mod tests {
                     max: None,
                     count: None,
                     highest_modseq: None,
+                    partial: None,
                 },
                 "A283",
                 "* ESEARCH (TAG \"A283\") ALL 1:3,5,10:13,90,92:99\r\n",
@@ -231,6 +285,7 @@ mod tests {
                     max: None,
                     count: None,
                     highest_modseq: None,
+                    partial: None,
                 },
                 "A283",
                 "* ESEARCH (TAG \"A283\")\r\n",
@@ -246,6 +301,7 @@ mod tests {
                     max: None,
                     count: None,
                     highest_modseq: 12345.into(),
+                    partial: None,
                 },
                 "A283",
                 "* ESEARCH (TAG \"A283\") ALL 10:13,21 MODSEQ 12345\r\n",
@@ -260,4 +316,69 @@ mod tests {
             assert_eq!(response_v1, expected_v1);
         }
     }
+
+    #[test]
+    fn serialize_partial() {
+        for (range, ids, expected_ids, expected) in [
+            (
+                super::PartialRange {
+                    low: 1,
+                    high: 3,
+                    is_last: false,
+                },
+                vec![2, 3, 4, 8, 9],
+                vec![2, 3, 4],
+                "* ESEARCH (TAG \"A1\") UID COUNT 5 PARTIAL (1:3 2:4)\r\n",
+            ),
+            (
+                super::PartialRange {
+                    low: 1,
+                    high: 2,
+                    is_last: true,
+                },
+                vec![2, 3, 4, 8, 9],
+                vec![8, 9],
+                "* ESEARCH (TAG \"A1\") UID COUNT 5 PARTIAL (-1:-2 8:9)\r\n",
+            ),
+            (
+                super::PartialRange {
+                    low: 4,
+                    high: 10,
+                    is_last: true,
+                },
+                vec![2, 3, 4, 8, 9],
+                vec![2, 3],
+                "* ESEARCH (TAG \"A1\") UID COUNT 5 PARTIAL (-4:-10 2:3)\r\n",
+            ),
+            (
+                super::PartialRange {
+                    low: 6,
+                    high: 10,
+                    is_last: false,
+                },
+                vec![2, 3, 4, 8, 9],
+                vec![],
+                "* ESEARCH (TAG \"A1\") UID COUNT 5 PARTIAL (6:10 NIL)\r\n",
+            ),
+        ] {
+            let result = range.slice(&ids).to_vec();
+            assert_eq!(result, expected_ids);
+
+            let response = super::Response {
+                is_uid: true,
+                is_esearch: true,
+                is_sort: false,
+                ids: result,
+                min: None,
+                max: None,
+                count: Some(ids.len() as u32),
+                highest_modseq: None,
+                partial: Some(range),
+            };
+            assert_eq!(
+                String::from_utf8(response.serialize("A1")).unwrap(),
+                expected
+            );
+        }
+    }
 }
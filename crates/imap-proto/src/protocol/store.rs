@@ -25,6 +25,7 @@ pub enum Operation {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Response<'x> {
+    pub is_uidonly: bool,
     pub items: Vec<FetchItem<'x>>,
 }
 
@@ -32,7 +33,11 @@ impl ImapResponse for Response<'_> {
     fn serialize(self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(64);
         for item in &self.items {
-            item.serialize(&mut buf);
+            if self.is_uidonly {
+                item.serialize_uidonly(&mut buf);
+            } else {
+                item.serialize(&mut buf);
+            }
         }
         buf
     }
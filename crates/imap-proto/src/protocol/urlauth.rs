@@ -0,0 +1,129 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ImapResponse, literal_string, quoted_string};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenUrlAuthArguments {
+    pub tag: String,
+    pub urls: Vec<UrlMechanism>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlMechanism {
+    pub url: String,
+    pub mechanism: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResetKeyArguments {
+    pub tag: String,
+    pub mailbox_name: Option<String>,
+    pub mechanisms: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlFetchArguments {
+    pub tag: String,
+    pub urls: Vec<String>,
+}
+
+pub struct GenUrlAuthResponse {
+    pub urls: Vec<String>,
+}
+
+pub struct UrlFetchResponse {
+    pub items: Vec<UrlFetchItem>,
+}
+
+pub struct UrlFetchItem {
+    pub url: String,
+    pub contents: Option<Vec<u8>>,
+}
+
+impl ImapResponse for GenUrlAuthResponse {
+    fn serialize(self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(64);
+        buf.extend_from_slice(b"* GENURLAUTH");
+        for url in &self.urls {
+            buf.push(b' ');
+            quoted_string(&mut buf, url);
+        }
+        buf.extend_from_slice(b"\r\n");
+        buf
+    }
+}
+
+impl ImapResponse for UrlFetchResponse {
+    fn serialize(self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(
+            64 + self
+                .items
+                .iter()
+                .map(|item| item.contents.as_ref().map_or(0, |c| c.len()))
+                .sum::<usize>(),
+        );
+        buf.extend_from_slice(b"* URLFETCH");
+        for item in &self.items {
+            buf.push(b' ');
+            quoted_string(&mut buf, &item.url);
+            buf.push(b' ');
+            if let Some(contents) = &item.contents {
+                literal_string(&mut buf, contents);
+            } else {
+                buf.extend_from_slice(b"NIL");
+            }
+        }
+        buf.extend_from_slice(b"\r\n");
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::ImapResponse;
+
+    use super::{GenUrlAuthResponse, UrlFetchItem, UrlFetchResponse};
+
+    #[test]
+    fn serialize_urlauth() {
+        assert_eq!(
+            String::from_utf8(
+                GenUrlAuthResponse {
+                    urls: vec![
+                        "imap://joe@example.com/INBOX/;uid=20/;section=1.2;urlauth=submit+fred:internal:91354a473744909de610943775f92038".into()
+                    ],
+                }
+                .serialize()
+            )
+            .unwrap(),
+            "* GENURLAUTH \"imap://joe@example.com/INBOX/;uid=20/;section=1.2;urlauth=submit+fred:internal:91354a473744909de610943775f92038\"\r\n"
+        );
+
+        assert_eq!(
+            String::from_utf8(
+                UrlFetchResponse {
+                    items: vec![
+                        UrlFetchItem {
+                            url: "imap://joe@example.com/INBOX/;uid=20/;section=1.2".into(),
+                            contents: Some(b"Hello world\r\n".to_vec()),
+                        },
+                        UrlFetchItem {
+                            url: "imap://joe@example.com/INBOX/;uid=21".into(),
+                            contents: None,
+                        }
+                    ],
+                }
+                .serialize()
+            )
+            .unwrap(),
+            concat!(
+                "* URLFETCH \"imap://joe@example.com/INBOX/;uid=20/;section=1.2\" {13}\r\n",
+                "Hello world\r\n \"imap://joe@example.com/INBOX/;uid=21\" NIL\r\n"
+            )
+        );
+    }
+}
@@ -4,19 +4,23 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::{iter::Peekable, sync::Arc, vec::IntoIter};
+use std::{
+    iter::Peekable,
+    sync::{Arc, atomic::Ordering},
+    vec::IntoIter,
+};
 
 use common::{
     KV_RATE_LIMIT_IMAP,
     network::{SessionResult, SessionStream},
 };
 use imap_proto::{
-    Command, ResponseType, StatusResponse,
+    Command, ResponseCode, ResponseType, StatusResponse,
     receiver::{self, Request},
 };
 use trc::SecurityEvent;
 
-use super::{SelectedMailbox, Session, SessionData, State};
+use super::{RunningTask, RunningTasks, SelectedMailbox, Session, SessionData, State};
 
 impl<T: SessionStream> Session<T> {
     pub async fn ingest(&mut self, bytes: &[u8]) -> SessionResult {
@@ -36,8 +40,10 @@ impl<T: SessionStream> Session<T> {
             match self.receiver.parse(&mut bytes) {
                 Ok(request) => match self.is_allowed(request).await {
                     Ok(request) => {
-                        has_expunge |=
-                            matches!(request.command, Command::Expunge(_) | Command::Close);
+                        has_expunge |= matches!(
+                            request.command,
+                            Command::Expunge(_) | Command::Close | Command::Replace(_)
+                        );
                         requests.push(request);
                     }
                     Err(err) => {
@@ -162,6 +168,10 @@ impl<T: SessionStream> Session<T> {
                     .handle_thread(request, is_uid)
                     .await
                     .map(|_| SessionResult::Continue),
+                Command::Replace(is_uid) => self
+                    .handle_replace(request, is_uid)
+                    .await
+                    .map(|_| SessionResult::Continue),
                 Command::Idle => self
                     .handle_idle(request)
                     .await
@@ -240,6 +250,10 @@ impl<T: SessionStream> Session<T> {
                     .handle_get_quota_root(request)
                     .await
                     .map(|_| SessionResult::Continue),
+                Command::SetQuota => self
+                    .handle_set_quota(request)
+                    .await
+                    .map(|_| SessionResult::Continue),
                 Command::Unauthenticate => self
                     .handle_unauthenticate(request)
                     .await
@@ -252,6 +266,34 @@ impl<T: SessionStream> Session<T> {
                     .handle_jmap_access(request)
                     .await
                     .map(|_| SessionResult::Continue),
+                Command::GenUrlAuth => self
+                    .handle_gen_url_auth(request)
+                    .await
+                    .map(|_| SessionResult::Continue),
+                Command::ResetKey => self
+                    .handle_reset_key(request)
+                    .await
+                    .map(|_| SessionResult::Continue),
+                Command::UrlFetch => self
+                    .handle_url_fetch(request)
+                    .await
+                    .map(|_| SessionResult::Continue),
+                Command::Notify => self
+                    .handle_notify(request)
+                    .await
+                    .map(|_| SessionResult::Continue),
+                Command::GetMetadata => self
+                    .handle_get_metadata(request)
+                    .await
+                    .map(|_| SessionResult::Continue),
+                Command::SetMetadata => self
+                    .handle_set_metadata(request)
+                    .await
+                    .map(|_| SessionResult::Continue),
+                Command::Compress => self
+                    .handle_compress(request)
+                    .await
+                    .map(|_| SessionResult::UpgradeCompress),
             };
 
             match result {
@@ -318,7 +360,12 @@ impl<T: SessionStream> Session<T> {
         match &request.command {
             Command::Capability | Command::Noop | Command::Logout | Command::Id => Ok(request),
             Command::StartTls => {
-                if !self.is_tls {
+                if self.is_compressed {
+                    Err(trc::ImapEvent::Error
+                        .into_err()
+                        .details("STARTTLS is not allowed after COMPRESS.")
+                        .id(request.tag))
+                } else if !self.is_tls {
                     if self.instance.acceptor.is_tls() {
                         Ok(request)
                     } else {
@@ -383,7 +430,15 @@ impl<T: SessionStream> Session<T> {
             | Command::Unauthenticate
             | Command::GetQuota
             | Command::GetQuotaRoot
-            | Command::GetJmapAccess => {
+            | Command::SetQuota
+            | Command::GetJmapAccess
+            | Command::GenUrlAuth
+            | Command::ResetKey
+            | Command::UrlFetch
+            | Command::Notify
+            | Command::GetMetadata
+            | Command::SetMetadata
+            | Command::Compress => {
                 if let State::Authenticated { .. } | State::Selected { .. } = state {
                     Ok(request)
                 } else {
@@ -403,12 +458,32 @@ impl<T: SessionStream> Session<T> {
             | Command::Move(_)
             | Command::Check
             | Command::Sort(_)
-            | Command::Thread(_) => match state {
+            | Command::Thread(_)
+            | Command::Replace(_) => match state {
                 State::Selected { mailbox, .. } => {
-                    if mailbox.is_select
+                    if self.is_uidonly
+                        && !request.command.is_uid()
+                        && !matches!(
+                            request.command,
+                            Command::Close
+                                | Command::Unselect
+                                | Command::Expunge(_)
+                                | Command::Check
+                        )
+                    {
+                        Err(trc::ImapEvent::Error
+                            .into_err()
+                            .details("Use the UID variant of this command in UIDONLY mode.")
+                            .code(ResponseCode::UidRequired)
+                            .ctx(trc::Key::Type, ResponseType::Bad)
+                            .id(request.tag))
+                    } else if mailbox.is_select
                         || !matches!(
                             request.command,
-                            Command::Store(_) | Command::Expunge(_) | Command::Move(_),
+                            Command::Store(_)
+                                | Command::Expunge(_)
+                                | Command::Move(_)
+                                | Command::Replace(_),
                         )
                     {
                         Ok(request)
@@ -478,8 +553,10 @@ impl<T: SessionStream> State<T> {
         R: std::future::Future<Output = trc::Result<()>> + Send + 'static,
     {
         let data = self.session_data();
+        let task = data.tasks.start();
 
         tokio::spawn(async move {
+            let _task = task;
             if let Err(err) = fnc(params, &data).await {
                 let _ = data.write_error(err).await;
             }
@@ -488,6 +565,15 @@ impl<T: SessionStream> State<T> {
         Ok(())
     }
 
+    pub fn running_tasks(&self) -> Option<Arc<RunningTasks>> {
+        match self {
+            State::Authenticated { data } | State::Selected { data, .. } => {
+                Some(data.tasks.clone())
+            }
+            State::NotAuthenticated { .. } => None,
+        }
+    }
+
     pub fn is_authenticated(&self) -> bool {
         matches!(self, State::Authenticated { .. } | State::Selected { .. })
     }
@@ -496,3 +582,32 @@ impl<T: SessionStream> State<T> {
         matches!(self, State::Selected { .. })
     }
 }
+
+impl RunningTasks {
+    pub fn start(self: &Arc<Self>) -> RunningTask {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        RunningTask(self.clone())
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.count.load(Ordering::Relaxed) == 0
+    }
+
+    pub async fn wait_idle(&self) {
+        loop {
+            let idle = self.idle.notified();
+            if self.is_idle() {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+impl Drop for RunningTask {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
@@ -42,6 +42,7 @@ impl<T: SessionStream> SessionData<T> {
             remote_addr: session.remote_addr,
             access_token,
             in_flight,
+            tasks: Default::default(),
         };
 
         // Fetch mailboxes for the main account
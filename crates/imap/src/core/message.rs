@@ -22,6 +22,7 @@ impl<T: SessionStream> SessionData<T> {
         &self,
         mailbox: &MailboxId,
         current_state: Option<u64>,
+        is_uidonly: bool,
     ) -> trc::Result<Option<MailboxState>> {
         let cached_messages = self
             .server
@@ -34,36 +35,37 @@ impl<T: SessionStream> SessionData<T> {
         }
 
         // Obtain UID next and assign UIDs
-        let uid_map = cached_messages
-            .emails
-            .items
-            .iter()
-            .filter_map(|item| {
-                item.mailboxes.iter().find_map(|m| {
-                    if m.mailbox_id == mailbox.mailbox_id {
-                        Some((m.uid, item.document_id))
-                    } else {
-                        None
-                    }
-                })
+        let uids = cached_messages.emails.items.iter().filter_map(|item| {
+            item.mailboxes.iter().find_map(|m| {
+                if m.mailbox_id == mailbox.mailbox_id {
+                    Some((m.uid, item.document_id))
+                } else {
+                    None
+                }
             })
-            .collect::<BTreeMap<u32, u32>>();
+        });
         let mut uid_max = 0;
-        let mut id_to_imap = AHashMap::with_capacity(uid_map.len());
-        let mut uid_to_id = AHashMap::with_capacity(uid_map.len());
-
-        for (seqnum, (uid, message_id)) in uid_map.into_iter().enumerate() {
+        let mut id_to_imap = AHashMap::new();
+        let mut uid_to_id = AHashMap::new();
+        let mut insert_id = |uid: u32, message_id: u32, seqnum: u32| {
             if uid > uid_max {
                 uid_max = uid;
             }
-            id_to_imap.insert(
-                message_id,
-                ImapId {
-                    uid,
-                    seqnum: seqnum as u32 + 1,
-                },
-            );
+            id_to_imap.insert(message_id, ImapId { uid, seqnum });
             uid_to_id.insert(uid, message_id);
+        };
+
+        if !is_uidonly {
+            for (seqnum, (uid, message_id)) in
+                uids.collect::<BTreeMap<u32, u32>>().into_iter().enumerate()
+            {
+                insert_id(uid, message_id, seqnum as u32 + 1);
+            }
+        } else {
+            // UIDONLY sessions never reference sequence numbers
+            for (uid, message_id) in uids {
+                insert_id(uid, message_id, 0);
+            }
         }
 
         Ok(Some(MailboxState {
@@ -80,7 +82,7 @@ impl<T: SessionStream> SessionData<T> {
         // Obtain current modseq
         let mut current_modseq = mailbox.state.lock().modseq;
         if let Some(new_state) = self
-            .fetch_messages(&mailbox.id, current_modseq.into())
+            .fetch_messages(&mailbox.id, current_modseq.into(), mailbox.is_uidonly)
             .await?
         {
             // Synchronize messages
@@ -130,6 +132,7 @@ impl<T: SessionStream> SessionData<T> {
             let mut current_state = mailbox.state.lock();
             if let Some(next_state) = current_state.next_state.take() {
                 if !next_state.deletions.is_empty() {
+                    let is_qresync = is_qresync || mailbox.is_uidonly;
                     let mut ids = next_state
                         .deletions
                         .into_iter()
@@ -7,22 +7,27 @@
 use ahash::AHashMap;
 use common::{
     Inner, Server,
-    auth::AccessToken,
+    auth::{AccessToken, scram::ScramState},
+    ipc::PushNotification,
     network::{ServerInstance, SessionStream, limiter::InFlight},
 };
+use directory::core::scram::ChannelBindings;
 use imap_proto::{
     Command,
-    protocol::{ProtocolVersion, list::Attribute},
+    protocol::{ProtocolVersion, list::Attribute, notify::EventGroup},
     receiver::Receiver,
 };
 use std::{
     collections::BTreeMap,
     net::IpAddr,
-    sync::{Arc, atomic::AtomicU32},
+    sync::{
+        Arc,
+        atomic::{AtomicU32, AtomicUsize},
+    },
 };
 use tokio::{
     io::{ReadHalf, WriteHalf},
-    sync::watch,
+    sync::{Notify, mpsc, watch},
 };
 use trc::AddContext;
 
@@ -53,11 +58,34 @@ pub struct Session<T: SessionStream> {
     pub is_qresync: bool,
     pub is_utf8: bool,
     pub is_objectid: bool,
+    pub is_compressed: bool,
+    pub is_uidonly: bool,
     pub stream_rx: ReadHalf<T>,
     pub stream_tx: Arc<tokio::sync::Mutex<WriteHalf<T>>>,
     pub in_flight: InFlight,
     pub remote_addr: IpAddr,
     pub session_id: u64,
+    pub notify: Option<NotifyState>,
+    pub channel_bindings: ChannelBindings,
+    pub scram: Option<ScramState>,
+    pub client_certificate: Option<Box<[u8]>>,
+}
+
+pub struct NotifyState {
+    pub groups: Vec<EventGroup>,
+    pub mailboxes: AHashMap<String, NotifyMailbox>,
+    pub push_rx: mpsc::Receiver<PushNotification>,
+    pub pending_mailbox_changes: bool,
+    pub pending_email_changes: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifyMailbox {
+    pub id: MailboxId,
+    pub is_subscribed: bool,
+    pub total_messages: u64,
+    pub total_unseen: u64,
+    pub uid_next: u64,
 }
 
 pub struct SessionData<T: SessionStream> {
@@ -70,14 +98,24 @@ pub struct SessionData<T: SessionStream> {
     pub state: AtomicU32,
     pub remote_addr: IpAddr,
     pub in_flight: Option<InFlight>,
+    pub tasks: Arc<RunningTasks>,
+}
+
+#[derive(Default)]
+pub struct RunningTasks {
+    pub count: AtomicUsize,
+    pub idle: Notify,
 }
 
+pub struct RunningTask(Arc<RunningTasks>);
+
 pub struct SelectedMailbox {
     pub id: MailboxId,
     pub state: parking_lot::Mutex<MailboxState>,
     pub saved_search: parking_lot::Mutex<SavedSearch>,
     pub is_select: bool,
     pub is_condstore: bool,
+    pub is_uidonly: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -214,12 +252,19 @@ impl<T: SessionStream> SessionData<T> {
             stream_tx: new_stream,
             state: self.state,
             in_flight: self.in_flight,
+            tasks: self.tasks,
             access_token: self.access_token,
             remote_addr: self.remote_addr,
         }
     }
 }
 
+impl ImapId {
+    pub fn response_id(&self, is_uidonly: bool) -> u32 {
+        if is_uidonly { self.uid } else { self.seqnum }
+    }
+}
+
 impl MailboxState {
     pub fn map_result_id(&self, document_id: u32, is_uid: bool) -> Option<(u32, ImapId)> {
         if let Some(imap_id) = self.id_to_imap.get(&document_id) {
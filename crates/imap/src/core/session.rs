@@ -5,10 +5,16 @@
  */
 
 use super::{ImapSessionManager, Session, State};
-use crate::{GREETING_WITH_TLS, GREETING_WITHOUT_TLS};
+use crate::{
+    GREETING_WITH_CHANNEL_BINDING, GREETING_WITH_TLS, GREETING_WITHOUT_TLS, greeting_with_external,
+    op::notify::{NotifyEvent, next_notify_event},
+};
 use common::{
     BuildServer,
-    network::{SessionData, SessionManager, SessionResult, SessionStream, stream::NullIo},
+    network::{
+        ServerInstance, SessionData, SessionManager, SessionResult, SessionStream,
+        stream::{DeflateStream, NullIo},
+    },
 };
 use imap_proto::{
     protocol::{ProtocolVersion, SerializeResponse},
@@ -25,12 +31,23 @@ impl SessionManager for ImapSessionManager {
         session: SessionData<T>,
     ) -> impl std::future::Future<Output = ()> + Send {
         async move {
-            if let Ok(mut session) = Session::new(session, self).await
-                && session.handle_conn().await
-                && session.instance.acceptor.is_tls()
-                && let Ok(mut session) = session.into_tls().await
-            {
-                session.handle_conn().await;
+            if let Ok(mut session) = Session::new(session, self).await {
+                match session.handle_conn().await {
+                    SessionResult::UpgradeTls if session.instance.acceptor.is_tls() => {
+                        if let Ok(mut session) = session.into_tls().await
+                            && session.handle_conn().await == SessionResult::UpgradeCompress
+                            && let Ok(mut session) = session.into_compressed()
+                        {
+                            session.handle_conn().await;
+                        }
+                    }
+                    SessionResult::UpgradeCompress => {
+                        if let Ok(mut session) = session.into_compressed() {
+                            session.handle_conn().await;
+                        }
+                    }
+                    _ => (),
+                }
             }
         }
     }
@@ -42,7 +59,7 @@ impl SessionManager for ImapSessionManager {
 }
 
 impl<T: SessionStream> Session<T> {
-    pub async fn handle_conn(&mut self) -> bool {
+    pub async fn handle_conn(&mut self) -> SessionResult {
         let mut buf = vec![0; 8192];
         let mut shutdown_rx = self.instance.shutdown_rx.clone();
 
@@ -60,12 +77,12 @@ impl<T: SessionStream> Session<T> {
                             if bytes_read > 0 {
                                 match self.ingest(&buf[..bytes_read]).await {
                                     SessionResult::Continue => (),
-                                    SessionResult::UpgradeTls => {
-                                        return true;
-                                    }
                                     SessionResult::Close => {
                                         break;
                                     }
+                                    result => {
+                                        return result;
+                                    }
                                 }
                             } else {
                                 trc::event!(
@@ -96,6 +113,23 @@ impl<T: SessionStream> Session<T> {
                         }
                     }
                 },
+                event = next_notify_event(&mut self.notify, self.state.running_tasks()) => {
+                    match event {
+                        Some(NotifyEvent::Push(notification)) => {
+                            self.queue_notify_event(notification);
+                        }
+                        Some(NotifyEvent::Flush) => {
+                            if let Err(err) = self.flush_notify_events(false).await
+                                && !self.write_error(err).await
+                            {
+                                break;
+                            }
+                        }
+                        None => {
+                            self.notify = None;
+                        }
+                    }
+                },
                 _ = shutdown_rx.changed() => {
                     trc::event!(
                         Network(trc::NetworkEvent::Closed),
@@ -109,7 +143,7 @@ impl<T: SessionStream> Session<T> {
             };
         }
 
-        false
+        SessionResult::Close
     }
 
     pub async fn new(
@@ -117,9 +151,18 @@ impl<T: SessionStream> Session<T> {
         manager: ImapSessionManager,
     ) -> Result<Session<T>, ()> {
         // Write greeting
+        let server = manager.inner.build_server();
         let is_tls = session.stream.is_tls();
-        let greeting = if !is_tls && session.instance.acceptor.is_tls() {
+        let channel_bindings = session.stream.channel_bindings(&server);
+        let client_certificate = client_certificate(&session.instance, &session.stream);
+        let external_greeting;
+        let greeting: &[u8] = if !is_tls && session.instance.acceptor.is_tls() {
             &GREETING_WITH_TLS
+        } else if client_certificate.is_some() {
+            external_greeting = greeting_with_external(channel_bindings.is_available());
+            &external_greeting
+        } else if channel_bindings.is_available() {
+            &GREETING_WITH_CHANNEL_BINDING
         } else {
             &GREETING_WITHOUT_TLS
         };
@@ -137,7 +180,6 @@ impl<T: SessionStream> Session<T> {
 
         // Split stream into read and write halves
         let (stream_rx, stream_tx) = tokio::io::split(session.stream);
-        let server = manager.inner.build_server();
 
         Ok(Session {
             receiver: Receiver::with_max_request_size(server.core.imap.max_request_size),
@@ -148,6 +190,8 @@ impl<T: SessionStream> Session<T> {
             is_qresync: false,
             is_utf8: false,
             is_objectid: false,
+            is_compressed: false,
+            is_uidonly: false,
             server,
             instance: session.instance,
             session_id: session.session_id,
@@ -155,16 +199,42 @@ impl<T: SessionStream> Session<T> {
             remote_addr: session.remote_ip,
             stream_rx,
             stream_tx: Arc::new(tokio::sync::Mutex::new(stream_tx)),
+            notify: None,
+            channel_bindings,
+            scram: None,
+            client_certificate,
         })
     }
 
     pub async fn into_tls(self) -> Result<Session<TlsStream<T>>, ()> {
+        let (session, stream) = self.unsplit_stream()?;
+
+        // Upgrade to TLS
+        let stream = session
+            .instance
+            .tls_accept(stream, session.session_id)
+            .await?;
+        let mut session = session.with_stream(stream);
+        session.is_tls = true;
+
+        Ok(session)
+    }
+
+    pub fn into_compressed(self) -> Result<Session<DeflateStream<T>>, ()> {
+        let (session, stream) = self.unsplit_stream()?;
+
+        // Wrap stream in a deflate layer
+        let mut session = session.with_stream(DeflateStream::new(stream));
+        session.is_compressed = true;
+
+        Ok(session)
+    }
+
+    fn unsplit_stream(self) -> Result<(Session<NullIo>, T), ()> {
         // Drop references to write half from state
-        let state = if let Some(state) =
-            self.state
-                .try_replace_stream_tx(Arc::new(tokio::sync::Mutex::new(
-                    tokio::io::split(NullIo::default()).1,
-                ))) {
+        let (null_rx, null_tx) = tokio::io::split(NullIo::default());
+        let null_tx = Arc::new(tokio::sync::Mutex::new(null_tx));
+        let state = if let Some(state) = self.state.try_replace_stream_tx(null_tx.clone()) {
             state
         } else {
             trc::event!(
@@ -190,31 +260,77 @@ impl<T: SessionStream> Session<T> {
             return Err(());
         };
 
-        // Upgrade to TLS
-        let (stream_rx, stream_tx) =
-            tokio::io::split(self.instance.tls_accept(stream, self.session_id).await?);
+        Ok((
+            Session {
+                server: self.server,
+                instance: self.instance,
+                receiver: self.receiver,
+                version: self.version,
+                state,
+                is_tls: self.is_tls,
+                is_condstore: self.is_condstore,
+                is_qresync: self.is_qresync,
+                is_utf8: self.is_utf8,
+                is_objectid: self.is_objectid,
+                is_compressed: self.is_compressed,
+                is_uidonly: self.is_uidonly,
+                session_id: self.session_id,
+                in_flight: self.in_flight,
+                remote_addr: self.remote_addr,
+                stream_rx: null_rx,
+                stream_tx: null_tx,
+                notify: self.notify,
+                channel_bindings: self.channel_bindings,
+                scram: self.scram,
+                client_certificate: self.client_certificate,
+            },
+            stream,
+        ))
+    }
+}
+
+impl Session<NullIo> {
+    fn with_stream<U: SessionStream>(self, stream: U) -> Session<U> {
+        let channel_bindings = stream.channel_bindings(&self.server);
+        let client_certificate = client_certificate(&self.instance, &stream);
+        let (stream_rx, stream_tx) = tokio::io::split(stream);
         let stream_tx = Arc::new(tokio::sync::Mutex::new(stream_tx));
 
-        Ok(Session {
+        Session {
             server: self.server,
             instance: self.instance,
             receiver: self.receiver,
             version: self.version,
-            state: state.try_replace_stream_tx(stream_tx.clone()).unwrap(),
-            is_tls: true,
+            state: self.state.try_replace_stream_tx(stream_tx.clone()).unwrap(),
+            is_tls: self.is_tls,
             is_condstore: self.is_condstore,
             is_qresync: self.is_qresync,
             is_utf8: self.is_utf8,
             is_objectid: self.is_objectid,
+            is_compressed: self.is_compressed,
+            is_uidonly: self.is_uidonly,
             session_id: self.session_id,
             in_flight: self.in_flight,
             remote_addr: self.remote_addr,
             stream_rx,
             stream_tx,
-        })
+            notify: self.notify,
+            channel_bindings,
+            scram: None,
+            client_certificate,
+        }
     }
 }
 
+/// Keeps the peer certificate only when the listener maps certificates to accounts
+fn client_certificate(instance: &ServerInstance, stream: &impl SessionStream) -> Option<Box<[u8]>> {
+    instance
+        .acceptor
+        .client_cert_mapping()
+        .and(stream.peer_certificate())
+        .map(Box::from)
+}
+
 impl<T: SessionStream> Session<T> {
     pub fn activate_objectid(&mut self) -> Option<&'static [u8]> {
         if self.is_objectid {
@@ -18,7 +18,7 @@ static SERVER_GREETING: &str = "Stalwart IMAP4rev2 at your service.";
 pub(crate) static GREETING_WITH_TLS: LazyLock<Vec<u8>> = LazyLock::new(|| {
     StatusResponse::ok(SERVER_GREETING)
         .with_code(ResponseCode::Capability {
-            capabilities: Capability::all_capabilities(false, true),
+            capabilities: Capability::all_capabilities(false, true, false, false),
         })
         .into_bytes()
 });
@@ -26,9 +26,25 @@ pub(crate) static GREETING_WITH_TLS: LazyLock<Vec<u8>> = LazyLock::new(|| {
 pub(crate) static GREETING_WITHOUT_TLS: LazyLock<Vec<u8>> = LazyLock::new(|| {
     StatusResponse::ok(SERVER_GREETING)
         .with_code(ResponseCode::Capability {
-            capabilities: Capability::all_capabilities(false, false),
+            capabilities: Capability::all_capabilities(false, false, false, false),
         })
         .into_bytes()
 });
 
+pub(crate) static GREETING_WITH_CHANNEL_BINDING: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    StatusResponse::ok(SERVER_GREETING)
+        .with_code(ResponseCode::Capability {
+            capabilities: Capability::all_capabilities(false, false, true, false),
+        })
+        .into_bytes()
+});
+
+pub(crate) fn greeting_with_external(offer_channel_binding: bool) -> Vec<u8> {
+    StatusResponse::ok(SERVER_GREETING)
+        .with_code(ResponseCode::Capability {
+            capabilities: Capability::all_capabilities(false, false, offer_channel_binding, true),
+        })
+        .into_bytes()
+}
+
 pub struct ImapError;
@@ -63,9 +63,9 @@ impl<T: SessionStream> Session<T> {
 }
 
 impl<T: SessionStream> SessionData<T> {
-    async fn append_messages(
+    pub async fn append_messages(
         &self,
-        arguments: Arguments,
+        mut arguments: Arguments,
         selected_mailbox: Option<Arc<SelectedMailbox>>,
         mailbox: MailboxId,
         is_qresync: bool,
@@ -101,6 +101,19 @@ impl<T: SessionStream> SessionData<T> {
                 .build()
         };
 
+        // Assemble CATENATE messages
+        for message in arguments.messages.iter_mut() {
+            if !message.catenate.is_empty() {
+                message.message = self
+                    .catenate(
+                        std::mem::take(&mut message.catenate),
+                        selected_mailbox.as_deref(),
+                        &arguments.tag,
+                    )
+                    .await?;
+            }
+        }
+
         // Append messages
         let mut response = StatusResponse::completed(Command::Append);
         let mut created_ids = Vec::with_capacity(arguments.messages.len());
@@ -6,13 +6,16 @@
 
 use crate::core::{Session, SessionData, State};
 use common::{
-    auth::AuthRequest,
+    auth::{AccessToken, AuthRequest, scram::ScramState},
     network::{SessionStream, limiter::LimiterResult},
 };
-use directory::Credentials;
+use directory::{Credentials, core::scram::ScramClientFirst};
 use imap_proto::{
     Command, ResponseCode, StatusResponse,
-    protocol::{authenticate::Mechanism, capability::Capability},
+    protocol::{
+        authenticate::{self, Mechanism},
+        capability::Capability,
+    },
     receiver::{self, Request},
 };
 use mail_parser::decoders::base64::base64_decode;
@@ -49,15 +52,12 @@ impl<T: SessionStream> Session<T> {
 
                     self.authenticate(credentials, args.tag).await
                 } else {
-                    self.receiver.request = receiver::Request {
-                        tag: args.tag,
-                        command: Command::Authenticate,
-                        tokens: vec![receiver::Token::Argument(args.mechanism.into_bytes())],
-                    };
-                    self.receiver.state = receiver::State::Argument { last_ch: b' ' };
-                    self.write_bytes(b"+ \r\n".to_vec()).await
+                    self.continue_authenticate(args.tag, args.mechanism, "")
+                        .await
                 }
             }
+            Mechanism::ScramSha256 | Mechanism::ScramSha256Plus => self.handle_scram(args).await,
+            Mechanism::External => self.handle_external(args).await,
             _ => Err(trc::AuthEvent::Error
                 .into_err()
                 .details("Authentication mechanism not supported.")
@@ -66,9 +66,174 @@ impl<T: SessionStream> Session<T> {
         }
     }
 
+    async fn handle_scram(&mut self, mut args: authenticate::Arguments) -> trc::Result<()> {
+        let state = self.scram.take();
+        let response = args
+            .params
+            .pop()
+            .filter(|response| !response.is_empty() && response != "=")
+            .map(|response| {
+                if response == "*" {
+                    return Err(trc::AuthEvent::Error
+                        .into_err()
+                        .details("Authentication cancelled.")
+                        .id(args.tag.clone()));
+                }
+
+                base64_decode(response.as_bytes()).ok_or_else(|| {
+                    trc::AuthEvent::Error
+                        .into_err()
+                        .details("Failed to decode challenge.")
+                        .id(args.tag.clone())
+                        .code(ResponseCode::Parse)
+                })
+            })
+            .transpose()?;
+
+        match (state, response) {
+            (None, None) => {
+                self.continue_authenticate(args.tag, args.mechanism, "")
+                    .await
+            }
+            (None, Some(client_first)) => {
+                let client_first = ScramClientFirst::parse(
+                    &client_first,
+                    args.mechanism == Mechanism::ScramSha256Plus,
+                    &self.channel_bindings,
+                )
+                .ok_or_else(|| {
+                    trc::AuthEvent::Error
+                        .into_err()
+                        .details("Invalid SASL challenge.")
+                        .id(args.tag.clone())
+                })?;
+                let auth = self
+                    .server
+                    .scram_begin(client_first)
+                    .await
+                    .map_err(|err| err.id(args.tag.clone()))?;
+                let challenge = auth.challenge();
+                self.scram = Some(ScramState::ClientFinal(Box::new(auth)));
+                self.continue_authenticate(args.tag, args.mechanism, &challenge)
+                    .await
+            }
+            (Some(ScramState::ClientFinal(auth)), Some(client_final)) => {
+                let (access_token, server_final) = self
+                    .server
+                    .scram_authenticate(&auth, &client_final, self.session_id, self.remote_addr)
+                    .await
+                    .map_err(|err| self.auth_failure(err, &args.tag))?;
+                self.scram = Some(ScramState::Success(access_token));
+                self.continue_authenticate(args.tag, args.mechanism, &server_final)
+                    .await
+            }
+            (Some(ScramState::Success(access_token)), None) => {
+                self.authenticated(access_token, args.tag).await
+            }
+            _ => Err(trc::AuthEvent::Error
+                .into_err()
+                .details("Unexpected SASL response.")
+                .id(args.tag)),
+        }
+    }
+
+    async fn handle_external(&mut self, mut args: authenticate::Arguments) -> trc::Result<()> {
+        let (Some(mapping), Some(certificate)) = (
+            self.instance.acceptor.client_cert_mapping(),
+            self.client_certificate.as_deref(),
+        ) else {
+            return Err(trc::AuthEvent::Error
+                .into_err()
+                .details("No client certificate was presented.")
+                .id(args.tag)
+                .code(ResponseCode::Cannot));
+        };
+
+        let Some(response) = args.params.pop() else {
+            // Placeholder argument so that an empty client response completes the exchange
+            self.receiver.request = receiver::Request {
+                tag: args.tag,
+                command: Command::Authenticate,
+                tokens: vec![
+                    receiver::Token::Argument(args.mechanism.into_bytes()),
+                    receiver::Token::Argument(b"=".to_vec()),
+                ],
+            };
+            self.receiver.state = receiver::State::Argument { last_ch: b' ' };
+            return self.write_bytes(b"+ \r\n".to_vec()).await;
+        };
+
+        let authzid = match response.as_str() {
+            "" | "=" => None,
+            "*" => {
+                return Err(trc::AuthEvent::Error
+                    .into_err()
+                    .details("Authentication cancelled.")
+                    .id(args.tag));
+            }
+            response => Some(
+                base64_decode(response.as_bytes())
+                    .and_then(|authzid| String::from_utf8(authzid).ok())
+                    .ok_or_else(|| {
+                        trc::AuthEvent::Error
+                            .into_err()
+                            .details("Failed to decode challenge.")
+                            .id(args.tag.clone())
+                            .code(ResponseCode::Parse)
+                    })?,
+            ),
+        };
+
+        match self
+            .server
+            .authenticate_certificate(
+                mapping,
+                certificate,
+                authzid.as_deref(),
+                self.session_id,
+                self.remote_addr,
+            )
+            .await
+        {
+            Ok(access_token) => self.authenticated(access_token, args.tag).await,
+            Err(err) => Err(self.auth_failure(err, &args.tag)),
+        }
+    }
+
+    async fn continue_authenticate(
+        &mut self,
+        tag: String,
+        mechanism: Mechanism,
+        challenge: &str,
+    ) -> trc::Result<()> {
+        self.receiver.request = receiver::Request {
+            tag,
+            command: Command::Authenticate,
+            tokens: vec![receiver::Token::Argument(mechanism.into_bytes())],
+        };
+        self.receiver.state = receiver::State::Argument { last_ch: b' ' };
+        self.write_bytes(format!("+ {challenge}\r\n").into_bytes())
+            .await
+    }
+
+    fn auth_failure(&mut self, err: trc::Error, tag: &str) -> trc::Error {
+        if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
+            let auth_failures = self.state.auth_failures();
+            if auth_failures < self.server.core.imap.max_auth_failures {
+                self.state = State::NotAuthenticated {
+                    auth_failures: auth_failures + 1,
+                };
+            } else {
+                return trc::AuthEvent::TooManyAttempts.into_err().caused_by(err);
+            }
+        }
+
+        err.id(tag.to_string())
+    }
+
     pub async fn authenticate(&mut self, credentials: Credentials, tag: String) -> trc::Result<()> {
         // Authenticate
-        let access_token = self
+        match self
             .server
             .authenticate(&AuthRequest::from_credentials(
                 credentials,
@@ -76,21 +241,16 @@ impl<T: SessionStream> Session<T> {
                 self.remote_addr,
             ))
             .await
-            .map_err(|err| {
-                if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
-                    let auth_failures = self.state.auth_failures();
-                    if auth_failures < self.server.core.imap.max_auth_failures {
-                        self.state = State::NotAuthenticated {
-                            auth_failures: auth_failures + 1,
-                        };
-                    } else {
-                        return trc::AuthEvent::TooManyAttempts.into_err().caused_by(err);
-                    }
-                }
+        {
+            Ok(access_token) => self.authenticated(access_token, tag).await,
+            Err(err) => Err(self.auth_failure(err, &tag)),
+        }
+    }
 
-                err.id(tag.clone())
-            })
-            .and_then(|token| token.assert_has_permission(Permission::ImapAuthenticate))?;
+    async fn authenticated(&mut self, access_token: AccessToken, tag: String) -> trc::Result<()> {
+        let access_token = access_token
+            .assert_has_permission(Permission::ImapAuthenticate)
+            .map_err(|err| err.id(tag.clone()))?;
 
         // Enforce concurrency limits
         let in_flight = match access_token.is_imap_request_allowed() {
@@ -117,6 +277,8 @@ impl<T: SessionStream> Session<T> {
                     capabilities: Capability::all_capabilities(
                         true,
                         !self.is_tls && self.instance.acceptor.is_tls(),
+                        false,
+                        false,
                     ),
                 })
                 .with_tag(tag)
@@ -127,6 +289,7 @@ impl<T: SessionStream> Session<T> {
 
     pub async fn handle_unauthenticate(&mut self, request: Request<Command>) -> trc::Result<()> {
         self.state = State::NotAuthenticated { auth_failures: 0 };
+        self.notify = None;
 
         self.write_bytes(
             StatusResponse::completed(Command::Unauthenticate)
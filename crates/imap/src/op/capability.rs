@@ -41,6 +41,8 @@ impl<T: SessionStream> Session<T> {
                         capabilities: Capability::all_capabilities(
                             self.state.is_authenticated(),
                             !self.is_tls && self.instance.acceptor.is_tls(),
+                            self.channel_bindings.is_available(),
+                            self.client_certificate.is_some(),
                         ),
                     }
                     .serialize(),
@@ -0,0 +1,44 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::time::Instant;
+
+use crate::core::Session;
+use common::network::SessionStream;
+use imap_proto::{Command, ResponseCode, StatusResponse, receiver::Request};
+use registry::schema::enums::Permission;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn handle_compress(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapCompress)?;
+
+        let op_start = Instant::now();
+        let arguments = request.parse_compress()?;
+
+        if self.is_compressed {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details("Compression is already active.")
+                .code(ResponseCode::CompressionActive)
+                .id(arguments.tag));
+        }
+
+        trc::event!(
+            Imap(trc::ImapEvent::Compress),
+            SpanId = self.session_id,
+            Elapsed = op_start.elapsed()
+        );
+
+        // The tagged response is the last data sent uncompressed
+        self.write_bytes(
+            StatusResponse::ok("DEFLATE active")
+                .with_tag(arguments.tag)
+                .into_bytes(),
+        )
+        .await
+    }
+}
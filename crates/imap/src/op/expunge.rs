@@ -5,7 +5,7 @@
  */
 
 use super::{ImapContext, ToModSeq};
-use crate::core::{ImapId, SavedSearch, SelectedMailbox, Session, SessionData};
+use crate::core::{ImapId, MailboxId, SavedSearch, SelectedMailbox, Session, SessionData};
 use ahash::AHashMap;
 use common::{network::SessionStream, storage::index::ObjectIndexBuilder};
 use email::{
@@ -132,10 +132,20 @@ impl<T: SessionStream> SessionData<T> {
             deleted_ids &= RoaringBitmap::from_iter(sequence.keys());
         }
 
+        self.expunge_ids(&mailbox.id, deleted_ids, op_start).await
+    }
+
+    pub async fn expunge_ids(
+        &self,
+        mailbox: &MailboxId,
+        deleted_ids: RoaringBitmap,
+        op_start: Instant,
+    ) -> trc::Result<()> {
         // Delete ids
+        let account_id = mailbox.account_id;
         let mut batch = BatchBuilder::new();
         let (fully_deleted, thread_ids) = self
-            .email_untag_or_delete(account_id, mailbox.id.mailbox_id, &deleted_ids, &mut batch)
+            .email_untag_or_delete(account_id, mailbox.mailbox_id, &deleted_ids, &mut batch)
             .await
             .caused_by(trc::location!())?;
         self.server
@@ -147,7 +157,7 @@ impl<T: SessionStream> SessionData<T> {
             Imap(trc::ImapEvent::Expunge),
             SpanId = self.session_id,
             AccountId = account_id,
-            MailboxId = mailbox.id.mailbox_id,
+            MailboxId = mailbox.mailbox_id,
             DocumentId = deleted_ids.iter().map(trc::Value::from).collect::<Vec<_>>(),
             Elapsed = op_start.elapsed()
         );
@@ -13,6 +13,7 @@ use ahash::AHashMap;
 use common::{network::SessionStream, storage::index::ObjectIndexBuilder};
 use email::{
     cache::{MessageCacheFetch, email::MessageCacheAccess},
+    message::index::saved_at_id,
     message::metadata::{
         ArchivedMessageMetadata, ArchivedMessageMetadataContents, ArchivedMetadataHeaderValue,
         ArchivedMetadataPartType, DecodedParts, MESSAGE_RECEIVED_MASK, MessageData,
@@ -36,7 +37,7 @@ use registry::schema::enums::Permission;
 use std::{borrow::Cow, sync::Arc, time::Instant};
 use store::{
     ValueKey,
-    write::{AlignedBytes, Archive},
+    write::{AlignedBytes, Archive, IndexPropertyClass, ValueClass},
 };
 use store::{
     query::log::{Change, Query},
@@ -310,7 +311,7 @@ impl<T: SessionStream> SessionData<T> {
         if is_uid {
             if arguments.attributes.is_empty() {
                 arguments.attributes.push(Attribute::Flags);
-            } else if !arguments.attributes.contains(&Attribute::Uid) {
+            } else if !arguments.attributes.contains(&Attribute::Uid) && !mailbox.is_uidonly {
                 arguments.attributes.insert(0, Attribute::Uid);
             }
         }
@@ -321,7 +322,10 @@ impl<T: SessionStream> SessionData<T> {
             .into_iter()
             .map(|(id, imap_id)| (imap_id.seqnum, imap_id.uid, id))
             .collect::<Vec<_>>();
-        ids.sort_unstable_by_key(|(seqnum, _, _)| *seqnum);
+        ids.sort_unstable_by_key(|(seqnum, uid, _)| (*seqnum, *uid));
+        if let Some(partial) = &arguments.partial {
+            ids = partial.slice(&ids).to_vec();
+        }
         let fetched_ids = ids
             .iter()
             .map(|id| trc::Value::from(id.2))
@@ -527,6 +531,27 @@ impl<T: SessionStream> SessionData<T> {
                             modseq: data.change_id + 1,
                         });
                     }
+                    Attribute::SaveDate => {
+                        // Messages saved before save dates were recorded use the received date
+                        let saved_at = self
+                            .server
+                            .store()
+                            .get_value::<u64>(ValueKey {
+                                account_id,
+                                collection: Collection::Email.into(),
+                                document_id: id,
+                                class: ValueClass::IndexProperty(IndexPropertyClass::Integer {
+                                    property: EmailField::SavedAt.into(),
+                                    value: saved_at_id(mailbox.id.mailbox_id, uid),
+                                }),
+                            })
+                            .await
+                            .imap_ctx(&arguments.tag, trc::location!())?
+                            .unwrap_or(metadata.rcvd_attach.to_native() & MESSAGE_RECEIVED_MASK);
+                        items.push(DataItem::SaveDate {
+                            date: Some(saved_at as i64),
+                        });
+                    }
                     Attribute::ObjectId => {
                         items.push(DataItem::ObjectId(ObjectId {
                             email_id: Some(Id::from_parts(data.thread_id, id)),
@@ -549,7 +574,11 @@ impl<T: SessionStream> SessionData<T> {
 
             // Serialize fetch item
             let mut buf = Vec::with_capacity(128);
-            FetchItem { id: seqnum, items }.serialize(&mut buf);
+            if !mailbox.is_uidonly {
+                FetchItem { id: seqnum, items }.serialize(&mut buf);
+            } else {
+                FetchItem { id: uid, items }.serialize_uidonly(&mut buf);
+            }
             self.write_bytes(buf).await?;
 
             // Add to set flags
@@ -59,6 +59,9 @@ impl<T: SessionStream> Session<T> {
         self.write_bytes(b"+ Idling, send 'DONE' to stop.\r\n".to_vec())
             .await?;
 
+        // Report any events queued by NOTIFY while commands were running
+        self.flush_notify_events(true).await?;
+
         trc::event!(
             Imap(trc::ImapEvent::IdleStart),
             SpanId = self.session_id,
@@ -249,6 +252,7 @@ impl<T: SessionStream> SessionData<T> {
                                 attributes: vec![fetch::Attribute::Flags, fetch::Attribute::Uid],
                                 changed_since: None,
                                 include_vanished: false,
+                                partial: None,
                             },
                             mailbox.clone(),
                             true,
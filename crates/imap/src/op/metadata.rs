@@ -0,0 +1,481 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    core::{MailboxId, Session, SessionData},
+    op::ImapContext,
+    spawn_op,
+};
+use common::{network::SessionStream, sharing::EffectiveAcl};
+use email::mailbox::{Mailbox, MailboxMetadata};
+use imap_proto::{
+    Command, ResponseCode, StatusResponse,
+    protocol::metadata::{GetArguments, MetadataCode, Response, SetArguments},
+    receiver::Request,
+};
+use registry::schema::enums::Permission;
+use std::time::Instant;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, ValueClass},
+};
+use trc::AddContext;
+use types::{
+    acl::Acl,
+    collection::Collection,
+    field::{MailboxField, PrincipalField},
+};
+use utils::map::bitmap::Bitmap;
+
+// Shared server entries are not owned by any account
+const SHARED_SERVER_ID: u32 = u32::MAX;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn handle_get_metadata(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapGetMetadata)?;
+
+        let op_start = Instant::now();
+        let arguments = request.parse_get_metadata(self.is_utf8)?;
+        let is_utf8 = self.version.is_rev2() || self.is_utf8;
+        let data = self.state.session_data();
+
+        spawn_op!(data, {
+            let response = data.get_metadata(arguments, is_utf8, op_start).await?;
+
+            data.write_bytes(response).await
+        })
+    }
+
+    pub async fn handle_set_metadata(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapSetMetadata)?;
+
+        let op_start = Instant::now();
+        let arguments = request.parse_set_metadata(self.is_utf8)?;
+        let data = self.state.session_data();
+
+        spawn_op!(data, {
+            let response = data.set_metadata(arguments, op_start).await?;
+
+            data.write_bytes(response).await
+        })
+    }
+}
+
+impl<T: SessionStream> SessionData<T> {
+    async fn get_metadata(
+        &self,
+        arguments: GetArguments,
+        is_utf8: bool,
+        op_start: Instant,
+    ) -> trc::Result<Vec<u8>> {
+        // Obtain entries visible to the current user
+        let mut metadata = Vec::new();
+        let mailbox_id = if arguments.mailbox_name.is_empty() {
+            for account_id in [self.account_id, SHARED_SERVER_ID] {
+                let (_, entries) = self
+                    .stored_metadata(
+                        account_id,
+                        Collection::Principal,
+                        0,
+                        PrincipalField::ImapMetadata.into(),
+                    )
+                    .await
+                    .imap_ctx(&arguments.tag, trc::location!())?;
+                metadata.extend(entries);
+            }
+            None
+        } else {
+            let (mailbox_id, acl) = self
+                .metadata_mailbox(&arguments.mailbox_name, &arguments.tag)
+                .await?;
+            let (_, entries) = self
+                .stored_metadata(
+                    mailbox_id.account_id,
+                    Collection::Mailbox,
+                    mailbox_id.mailbox_id,
+                    MailboxField::ImapMetadata.into(),
+                )
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?;
+            let can_read_shared = acl.is_none_or(|acl| acl.contains(Acl::ReadItems));
+            metadata.extend(entries.into_iter().filter(|entry| {
+                entry
+                    .owner_id
+                    .map_or(can_read_shared, |owner_id| owner_id == self.account_id)
+            }));
+            Some(mailbox_id)
+        };
+
+        // Filter entries
+        let mut entries = Vec::new();
+        let mut long_entries = 0;
+        for entry in metadata {
+            let name = entry_name(&entry);
+            if arguments
+                .entries
+                .iter()
+                .any(|requested| arguments.depth.matches(requested, &name))
+            {
+                if arguments
+                    .max_size
+                    .is_some_and(|max_size| entry.value.len() > max_size as usize)
+                {
+                    long_entries = long_entries.max(entry.value.len() as u32);
+                } else {
+                    entries.push((name, entry.value));
+                }
+            }
+        }
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        trc::event!(
+            Imap(trc::ImapEvent::GetMetadata),
+            SpanId = self.session_id,
+            MailboxName = arguments.mailbox_name.clone(),
+            AccountId = mailbox_id.map(|id| id.account_id),
+            MailboxId = mailbox_id.map(|id| id.mailbox_id),
+            Total = entries.len(),
+            Elapsed = op_start.elapsed()
+        );
+
+        let mut buf = Vec::with_capacity(64);
+        if !entries.is_empty() {
+            Response {
+                mailbox_name: arguments.mailbox_name,
+                entries,
+            }
+            .serialize(&mut buf, is_utf8);
+        }
+
+        let mut response = StatusResponse::completed(Command::GetMetadata).with_tag(arguments.tag);
+        if long_entries > 0 {
+            response = response.with_code(ResponseCode::Metadata(MetadataCode::LongEntries(
+                long_entries,
+            )));
+        }
+
+        Ok(response.serialize(buf))
+    }
+
+    async fn set_metadata(
+        &self,
+        arguments: SetArguments,
+        op_start: Instant,
+    ) -> trc::Result<Vec<u8>> {
+        // Validate value sizes
+        let max_size = self.server.core.imap.max_metadata_size;
+        if arguments
+            .entries
+            .iter()
+            .any(|(_, value)| value.as_ref().is_some_and(|value| value.len() > max_size))
+        {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details("Metadata value exceeds maximum size.")
+                .code(ResponseCode::Metadata(MetadataCode::MaxSize(
+                    max_size as u32,
+                )))
+                .id(arguments.tag));
+        }
+
+        let has_shared = arguments
+            .entries
+            .iter()
+            .any(|(name, _)| name.starts_with("/shared/"));
+        let mailbox_id = if arguments.mailbox_name.is_empty() {
+            // Shared server entries are part of the server configuration
+            if has_shared && !self.access_token.has_permission(Permission::SysImapUpdate) {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("You do not have enough permissions to modify shared server entries.")
+                    .code(ResponseCode::NoPerm)
+                    .id(arguments.tag));
+            }
+
+            let mut batch = BatchBuilder::new();
+            for (account_id, owner_id) in [
+                (self.account_id, Some(self.account_id)),
+                (SHARED_SERVER_ID, None),
+            ] {
+                if !arguments
+                    .entries
+                    .iter()
+                    .any(|(name, _)| owner_id.is_some() == name.starts_with("/private/"))
+                {
+                    continue;
+                }
+
+                let (archive, mut metadata) = self
+                    .stored_metadata(
+                        account_id,
+                        Collection::Principal,
+                        0,
+                        PrincipalField::ImapMetadata.into(),
+                    )
+                    .await
+                    .imap_ctx(&arguments.tag, trc::location!())?;
+                self.apply_metadata(&mut metadata, &arguments, owner_id)?;
+
+                batch
+                    .with_account_id(account_id)
+                    .with_collection(Collection::Principal)
+                    .with_document(0);
+                write_metadata(
+                    &mut batch,
+                    PrincipalField::ImapMetadata.into(),
+                    archive,
+                    metadata,
+                )
+                .imap_ctx(&arguments.tag, trc::location!())?;
+            }
+            self.server
+                .commit_batch(batch)
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?;
+
+            None
+        } else {
+            let (mailbox_id, acl) = self
+                .metadata_mailbox(&arguments.mailbox_name, &arguments.tag)
+                .await?;
+
+            // Shared entries require the write right, private entries only lookup
+            if has_shared && acl.is_some_and(|acl| !acl.contains(Acl::ModifyItems)) {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("You do not have enough permissions to modify shared entries.")
+                    .code(ResponseCode::NoPerm)
+                    .id(arguments.tag));
+            }
+
+            let (archive, current_metadata) = self
+                .stored_metadata(
+                    mailbox_id.account_id,
+                    Collection::Mailbox,
+                    mailbox_id.mailbox_id,
+                    MailboxField::ImapMetadata.into(),
+                )
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?;
+            let mut metadata = current_metadata.clone();
+            self.apply_metadata(&mut metadata, &arguments, Some(self.account_id))?;
+            self.apply_metadata(&mut metadata, &arguments, None)?;
+
+            if metadata != current_metadata {
+                let mut batch = BatchBuilder::new();
+                batch
+                    .with_account_id(mailbox_id.account_id)
+                    .with_collection(Collection::Mailbox)
+                    .with_document(mailbox_id.mailbox_id);
+                write_metadata(
+                    &mut batch,
+                    MailboxField::ImapMetadata.into(),
+                    archive,
+                    metadata,
+                )
+                .imap_ctx(&arguments.tag, trc::location!())?;
+                self.server
+                    .commit_batch(batch)
+                    .await
+                    .imap_ctx(&arguments.tag, trc::location!())?;
+            }
+
+            Some(mailbox_id)
+        };
+
+        trc::event!(
+            Imap(trc::ImapEvent::SetMetadata),
+            SpanId = self.session_id,
+            MailboxName = arguments.mailbox_name.clone(),
+            AccountId = mailbox_id.map(|id| id.account_id),
+            MailboxId = mailbox_id.map(|id| id.mailbox_id),
+            Total = arguments.entries.len(),
+            Elapsed = op_start.elapsed()
+        );
+
+        Ok(StatusResponse::completed(Command::SetMetadata)
+            .with_tag(arguments.tag)
+            .into_bytes())
+    }
+
+    fn apply_metadata(
+        &self,
+        metadata: &mut Vec<MailboxMetadata>,
+        arguments: &SetArguments,
+        owner_id: Option<u32>,
+    ) -> trc::Result<()> {
+        let prefix = if owner_id.is_some() {
+            "/private"
+        } else {
+            "/shared"
+        };
+        let num_entries = metadata
+            .iter()
+            .filter(|entry| entry.owner_id == owner_id)
+            .count();
+
+        for (name, value) in &arguments.entries {
+            let Some(name) = name
+                .strip_prefix(prefix)
+                .filter(|name| name.starts_with('/'))
+            else {
+                continue;
+            };
+
+            let entry = metadata
+                .iter_mut()
+                .find(|entry| entry.owner_id == owner_id && entry.name == name);
+            match (entry, value) {
+                (Some(entry), Some(value)) => {
+                    entry.value = value.clone();
+                }
+                (None, Some(value)) => {
+                    metadata.push(MailboxMetadata {
+                        name: name.to_string(),
+                        owner_id,
+                        value: value.clone(),
+                    });
+                }
+                (Some(_), None) => {
+                    metadata.retain(|entry| entry.owner_id != owner_id || entry.name != name);
+                }
+                (None, None) => (),
+            }
+        }
+
+        // Allow removing entries even if the limit was lowered
+        let new_num_entries = metadata
+            .iter()
+            .filter(|entry| entry.owner_id == owner_id)
+            .count();
+        if new_num_entries > self.server.core.imap.max_metadata_entries
+            && new_num_entries > num_entries
+        {
+            Err(trc::ImapEvent::Error
+                .into_err()
+                .details("Too many metadata entries.")
+                .code(ResponseCode::Metadata(MetadataCode::TooMany))
+                .id(arguments.tag.clone()))
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn stored_metadata(
+        &self,
+        account_id: u32,
+        collection: Collection,
+        document_id: u32,
+        field: u8,
+    ) -> trc::Result<(Option<Archive<AlignedBytes>>, Vec<MailboxMetadata>)> {
+        let archive = self
+            .server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                collection,
+                document_id,
+                field,
+            ))
+            .await
+            .caused_by(trc::location!())?;
+        let metadata = if let Some(archive) = &archive {
+            archive
+                .deserialize::<Vec<MailboxMetadata>>()
+                .caused_by(trc::location!())?
+        } else {
+            Vec::new()
+        };
+
+        Ok((archive, metadata))
+    }
+
+    async fn metadata_mailbox(
+        &self,
+        mailbox_name: &str,
+        tag: &str,
+    ) -> trc::Result<(MailboxId, Option<Bitmap<Acl>>)> {
+        let mailbox_id = self.get_mailbox_by_name(mailbox_name).ok_or_else(|| {
+            trc::ImapEvent::Error
+                .into_err()
+                .details("Mailbox does not exist.")
+                .code(ResponseCode::NonExistent)
+                .id(tag.to_string())
+        })?;
+        let mailbox = self
+            .server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                mailbox_id.account_id,
+                Collection::Mailbox,
+                mailbox_id.mailbox_id,
+            ))
+            .await
+            .imap_ctx(tag, trc::location!())?
+            .ok_or_else(|| {
+                trc::ImapEvent::Error
+                    .into_err()
+                    .details("Mailbox does not exist.")
+                    .code(ResponseCode::NonExistent)
+                    .id(tag.to_string())
+            })?
+            .into_deserialized::<Mailbox>()
+            .imap_ctx(tag, trc::location!())?;
+
+        // Owners and group members have full access, otherwise the lookup right is required
+        let access_token = self
+            .refresh_access_token()
+            .await
+            .imap_ctx(tag, trc::location!())?;
+        let acl = if !access_token.is_member(mailbox_id.account_id) {
+            let acl = mailbox.inner.acls.effective_acl(&access_token);
+            if !acl.contains(Acl::Read) {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("You do not have enough permissions to perform this operation.")
+                    .code(ResponseCode::NoPerm)
+                    .id(tag.to_string()));
+            }
+            Some(acl)
+        } else {
+            None
+        };
+
+        Ok((mailbox_id, acl))
+    }
+}
+
+fn write_metadata(
+    batch: &mut BatchBuilder,
+    field: u8,
+    archive: Option<Archive<AlignedBytes>>,
+    metadata: Vec<MailboxMetadata>,
+) -> trc::Result<()> {
+    if let Some(archive) = archive {
+        batch.assert_value(ValueClass::Property(field), archive);
+    }
+    if !metadata.is_empty() {
+        batch.set(
+            ValueClass::Property(field),
+            Archiver::new(metadata).serialize()?,
+        );
+    } else {
+        batch.clear(ValueClass::Property(field));
+    }
+    batch.log_unversioned_change();
+
+    Ok(())
+}
+
+fn entry_name(entry: &MailboxMetadata) -> String {
+    if entry.owner_id.is_some() {
+        format!("/private{}", entry.name)
+    } else {
+        format!("/shared{}", entry.name)
+    }
+}
@@ -12,6 +12,7 @@ pub mod append;
 pub mod authenticate;
 pub mod capability;
 pub mod close;
+pub mod compress;
 pub mod copy_move;
 pub mod create;
 pub mod delete;
@@ -22,16 +23,20 @@ pub mod idle;
 pub mod list;
 pub mod login;
 pub mod logout;
+pub mod metadata;
 pub mod namespace;
 pub mod noop;
+pub mod notify;
 pub mod quota;
 pub mod rename;
+pub mod replace;
 pub mod search;
 pub mod select;
 pub mod status;
 pub mod store;
 pub mod subscribe;
 pub mod thread;
+pub mod urlauth;
 
 trait FromModSeq {
     fn from_modseq(modseq: u64) -> Self;
@@ -61,8 +66,10 @@ impl ToModSeq for u64 {
 macro_rules! spawn_op {
     ($data:expr, $($code:tt)*) => {
         {
+        let task = $data.tasks.start();
 
         tokio::spawn(async move {
+            let _task = task;
             let data = &($data);
 
             if let Err(err) = (async {
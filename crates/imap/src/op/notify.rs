@@ -0,0 +1,460 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    core::{
+        MailboxId, NotifyMailbox, NotifyState, RunningTasks, SelectedMailbox, Session, SessionData,
+        State,
+    },
+    op::ImapContext,
+};
+use ahash::AHashMap;
+use common::{ipc::PushNotification, network::SessionStream};
+use imap_proto::{
+    Command, ResponseCode, StatusResponse,
+    parser::notify::SUPPORTED_EVENTS,
+    protocol::{
+        Sequence, fetch,
+        list::{Attribute, ListItem},
+        notify::{Event, EventGroup, MailboxFilter},
+        status::Status,
+    },
+    receiver::Request,
+};
+use registry::schema::enums::Permission;
+use std::{sync::Arc, time::Instant};
+use trc::AddContext;
+use types::type_state::DataType;
+use utils::map::bitmap::Bitmap;
+
+const STATUS_ITEMS: [Status; 4] = [
+    Status::Messages,
+    Status::UidNext,
+    Status::UidValidity,
+    Status::Unseen,
+];
+
+impl<T: SessionStream> Session<T> {
+    pub async fn handle_notify(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapNotify)?;
+
+        let op_start = Instant::now();
+        let arguments = request.parse_notify(self.is_utf8)?;
+        let Some(set) = arguments.set else {
+            // NOTIFY NONE
+            self.notify = None;
+
+            trc::event!(
+                Imap(trc::ImapEvent::Notify),
+                SpanId = self.session_id,
+                Total = 0,
+                Elapsed = op_start.elapsed()
+            );
+
+            return self
+                .write_bytes(
+                    StatusResponse::completed(Command::Notify)
+                        .with_tag(arguments.tag)
+                        .into_bytes(),
+                )
+                .await;
+        };
+
+        // Validate events
+        if let Some(event) = set
+            .groups
+            .iter()
+            .flat_map(|group| group.events.iter())
+            .find(|event| !SUPPORTED_EVENTS.contains(&event.as_str()))
+        {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details(format!("Event {} is not supported.", event.as_str()))
+                .code(ResponseCode::BadEvent {
+                    supported: SUPPORTED_EVENTS.to_vec(),
+                })
+                .id(arguments.tag));
+        }
+
+        // Register with push manager
+        let data = self.state.session_data();
+        let push_rx = self
+            .server
+            .subscribe_push_manager(
+                &data.access_token,
+                Bitmap::from_iter([DataType::Email, DataType::Mailbox, DataType::EmailDelivery]),
+            )
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?;
+        data.synchronize_mailboxes(false)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?;
+        let mailboxes = data.notify_mailboxes();
+
+        // Send the initial status of all monitored mailboxes
+        let mut buf = Vec::with_capacity(64);
+        if set.status {
+            let selected_id = self.state.selected_mailbox_id();
+            let mut mailbox_names = mailboxes
+                .iter()
+                .filter(|(mailbox_name, mailbox)| {
+                    Some(mailbox.id) != selected_id
+                        && find_group(&set.groups, data.account_id, mailbox_name, mailbox)
+                            .is_some_and(|group| {
+                                group.events.iter().any(|event| event.is_message_event())
+                            })
+                })
+                .map(|(mailbox_name, _)| mailbox_name.clone())
+                .collect::<Vec<_>>();
+            mailbox_names.sort_unstable();
+
+            for mailbox_name in mailbox_names {
+                if let Ok(status) = data.status(mailbox_name, &STATUS_ITEMS).await {
+                    status.serialize(&mut buf, self.is_utf8);
+                }
+            }
+        }
+
+        trc::event!(
+            Imap(trc::ImapEvent::Notify),
+            SpanId = self.session_id,
+            Total = set.groups.len(),
+            Elapsed = op_start.elapsed()
+        );
+
+        self.notify = Some(NotifyState {
+            groups: set.groups,
+            mailboxes,
+            push_rx,
+            pending_mailbox_changes: false,
+            pending_email_changes: false,
+        });
+
+        self.write_bytes(
+            StatusResponse::completed(Command::Notify)
+                .with_tag(arguments.tag)
+                .serialize(buf),
+        )
+        .await
+    }
+
+    pub fn queue_notify_event(&mut self, notification: PushNotification) {
+        let Some(notify) = &mut self.notify else {
+            return;
+        };
+
+        match notification {
+            PushNotification::StateChange(state_change) => {
+                for type_state in state_change.types {
+                    match type_state {
+                        DataType::Email | DataType::EmailDelivery => {
+                            notify.pending_email_changes = true;
+                        }
+                        DataType::Mailbox => {
+                            notify.pending_mailbox_changes = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            PushNotification::EmailPush(_) => {
+                notify.pending_email_changes = true;
+                notify.pending_mailbox_changes = true;
+            }
+            PushNotification::CalendarAlert(_) => (),
+        }
+    }
+
+    pub async fn flush_notify_events(&mut self, allow_expunges: bool) -> trc::Result<()> {
+        let Some(notify) = &mut self.notify else {
+            return Ok(());
+        };
+        let has_mailbox_changes = std::mem::take(&mut notify.pending_mailbox_changes);
+        let has_email_changes = std::mem::take(&mut notify.pending_email_changes);
+        let (data, selected) = match &self.state {
+            State::Authenticated { data } => (data.clone(), None),
+            State::Selected { data, mailbox } => (data.clone(), Some(mailbox.clone())),
+            State::NotAuthenticated { .. } => return Ok(()),
+        };
+        let is_rev2 = self.version.is_rev2();
+        let is_utf8 = self.is_utf8;
+        let is_qresync = self.is_qresync;
+
+        // Report changes to mailboxes other than the selected one
+        if has_mailbox_changes {
+            data.synchronize_mailboxes(false)
+                .await
+                .caused_by(trc::location!())?;
+            let mailboxes = data.notify_mailboxes();
+            let selected_id = selected.as_ref().map(|mailbox| mailbox.id);
+            let mut buf = Vec::with_capacity(64);
+            let mut changed = Vec::new();
+
+            // List deleted mailboxes
+            for (mailbox_name, mailbox) in &notify.mailboxes {
+                if !mailboxes.contains_key(mailbox_name)
+                    && find_group(&notify.groups, data.account_id, mailbox_name, mailbox)
+                        .is_some_and(|group| group.events.contains(&Event::MailboxName))
+                {
+                    ListItem {
+                        mailbox_name: mailbox_name.clone(),
+                        attributes: vec![Attribute::NonExistent],
+                        tags: vec![],
+                    }
+                    .serialize(&mut buf, is_rev2, is_utf8, false);
+                }
+            }
+
+            for (mailbox_name, mailbox) in &mailboxes {
+                let Some(group) =
+                    find_group(&notify.groups, data.account_id, mailbox_name, mailbox)
+                else {
+                    continue;
+                };
+
+                match notify.mailboxes.get(mailbox_name) {
+                    Some(old_mailbox) => {
+                        // List subscription changes
+                        if old_mailbox.is_subscribed != mailbox.is_subscribed
+                            && group.events.contains(&Event::SubscriptionChange)
+                        {
+                            ListItem {
+                                mailbox_name: mailbox_name.clone(),
+                                attributes: if mailbox.is_subscribed {
+                                    vec![Attribute::Subscribed]
+                                } else {
+                                    vec![]
+                                },
+                                tags: vec![],
+                            }
+                            .serialize(&mut buf, is_rev2, is_utf8, false);
+                        }
+
+                        // Message changes are reported with STATUS
+                        if Some(mailbox.id) != selected_id
+                            && (old_mailbox.total_messages != mailbox.total_messages
+                                || old_mailbox.total_unseen != mailbox.total_unseen
+                                || old_mailbox.uid_next != mailbox.uid_next)
+                            && group.events.iter().any(|event| event.is_message_event())
+                        {
+                            changed.push(mailbox_name.clone());
+                        }
+                    }
+                    None => {
+                        // List added mailboxes
+                        if group.events.contains(&Event::MailboxName) {
+                            ListItem {
+                                mailbox_name: mailbox_name.clone(),
+                                attributes: vec![],
+                                tags: vec![],
+                            }
+                            .serialize(&mut buf, is_rev2, is_utf8, false);
+                        }
+                    }
+                }
+            }
+            notify.mailboxes = mailboxes;
+
+            // Obtain status of changed mailboxes
+            changed.sort_unstable();
+            for mailbox_name in changed {
+                if let Ok(status) = data.status(mailbox_name, &STATUS_ITEMS).await {
+                    status.serialize(&mut buf, is_utf8);
+                }
+            }
+
+            if !buf.is_empty() {
+                data.write_bytes(buf).await?;
+            }
+        }
+
+        // Report changes to the selected mailbox
+        if has_email_changes
+            && let Some(mailbox) = selected
+            && let Some(group) = notify.groups.iter().find(|group| {
+                matches!(
+                    group.filter,
+                    MailboxFilter::Selected | MailboxFilter::SelectedDelayed
+                )
+            })
+            && !group.events.is_empty()
+        {
+            // With SELECTED-DELAYED, expunges wait for a command that allows them
+            // (such as NOOP or IDLE), which then reports all pending changes
+            if !allow_expunges
+                && group.filter == MailboxFilter::SelectedDelayed
+                && has_pending_expunges(&data, &mailbox)
+                    .await
+                    .caused_by(trc::location!())?
+            {
+                return Ok(());
+            }
+
+            let uid_max = mailbox.state.lock().uid_max;
+            data.write_changes(
+                &Some(mailbox.clone()),
+                false,
+                true,
+                is_qresync,
+                is_rev2,
+                is_utf8,
+            )
+            .await?;
+
+            // Fetch the requested attributes of new messages
+            if let Some(mut attributes) = group.events.iter().find_map(|event| match event {
+                Event::MessageNew { attributes } if !attributes.is_empty() => {
+                    Some(attributes.clone())
+                }
+                _ => None,
+            }) {
+                let new_uids = mailbox
+                    .state
+                    .lock()
+                    .id_to_imap
+                    .values()
+                    .filter(|id| id.uid > uid_max)
+                    .map(|id| Sequence::Number { value: id.uid })
+                    .collect::<Vec<_>>();
+
+                if !new_uids.is_empty() {
+                    if !attributes.contains(&fetch::Attribute::Uid) {
+                        attributes.push(fetch::Attribute::Uid);
+                    }
+
+                    data.fetch(
+                        fetch::Arguments {
+                            tag: "".into(),
+                            sequence_set: Sequence::List { items: new_uids },
+                            attributes,
+                            changed_since: None,
+                            include_vanished: false,
+                            partial: None,
+                        },
+                        mailbox,
+                        true,
+                        is_qresync,
+                        false,
+                        Instant::now(),
+                    )
+                    .await
+                    .caused_by(trc::location!())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: SessionStream> SessionData<T> {
+    pub fn notify_mailboxes(&self) -> AHashMap<String, NotifyMailbox> {
+        let mut mailboxes = AHashMap::new();
+        for account in self.mailboxes.lock().iter() {
+            for (mailbox_name, mailbox_id) in &account.mailbox_names {
+                if let Some(mailbox) = account.mailbox_state.get(mailbox_id) {
+                    mailboxes.insert(
+                        mailbox_name.clone(),
+                        NotifyMailbox {
+                            id: MailboxId {
+                                account_id: account.account_id,
+                                mailbox_id: *mailbox_id,
+                            },
+                            is_subscribed: mailbox.is_subscribed,
+                            total_messages: mailbox.total_messages,
+                            total_unseen: mailbox.total_unseen,
+                            uid_next: mailbox.uid_next,
+                        },
+                    );
+                }
+            }
+        }
+        mailboxes
+    }
+}
+
+impl<T: SessionStream> State<T> {
+    pub fn selected_mailbox_id(&self) -> Option<MailboxId> {
+        match self {
+            State::Selected { mailbox, .. } => Some(mailbox.id),
+            _ => None,
+        }
+    }
+}
+
+fn find_group<'x>(
+    groups: &'x [EventGroup],
+    account_id: u32,
+    mailbox_name: &str,
+    mailbox: &NotifyMailbox,
+) -> Option<&'x EventGroup> {
+    // The first matching filter determines which events are reported
+    groups.iter().find(|group| match &group.filter {
+        MailboxFilter::Selected | MailboxFilter::SelectedDelayed => false,
+        MailboxFilter::Inboxes => {
+            mailbox.id.account_id == account_id && mailbox_name.eq_ignore_ascii_case("INBOX")
+        }
+        MailboxFilter::Personal => mailbox.id.account_id == account_id,
+        MailboxFilter::Subscribed => mailbox.is_subscribed,
+        MailboxFilter::Subtree(names) => names.iter().any(|name| {
+            mailbox_name
+                .strip_prefix(name.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        }),
+        MailboxFilter::Mailboxes(names) => names.iter().any(|name| name == mailbox_name),
+    })
+}
+
+async fn has_pending_expunges<T: SessionStream>(
+    data: &SessionData<T>,
+    mailbox: &SelectedMailbox,
+) -> trc::Result<bool> {
+    let modseq = mailbox.state.lock().modseq;
+    let new_state = data
+        .fetch_messages(&mailbox.id, modseq.into(), mailbox.is_uidonly)
+        .await?;
+    let state = mailbox.state.lock();
+
+    Ok(state
+        .next_state
+        .as_ref()
+        .is_some_and(|next_state| !next_state.deletions.is_empty())
+        || new_state.is_some_and(|new_state| {
+            state
+                .id_to_imap
+                .values()
+                .any(|id| !new_state.uid_to_id.contains_key(&id.uid))
+        }))
+}
+
+pub(crate) enum NotifyEvent {
+    Push(PushNotification),
+    Flush,
+}
+
+pub(crate) async fn next_notify_event(
+    notify: &mut Option<NotifyState>,
+    tasks: Option<Arc<RunningTasks>>,
+) -> Option<NotifyEvent> {
+    let Some(notify) = notify else {
+        return std::future::pending().await;
+    };
+
+    // Queued events are only written between commands, as untagged EXPUNGE and
+    // FETCH responses sent while a command is running could change the
+    // sequence numbers it is working with (RFC 5465, section 5)
+    if !notify.pending_mailbox_changes && !notify.pending_email_changes {
+        notify.push_rx.recv().await.map(NotifyEvent::Push)
+    } else if let Some(tasks) = tasks {
+        tokio::select! {
+            notification = notify.push_rx.recv() => notification.map(NotifyEvent::Push),
+            _ = tasks.wait_idle() => Some(NotifyEvent::Flush),
+        }
+    } else {
+        Some(NotifyEvent::Flush)
+    }
+}
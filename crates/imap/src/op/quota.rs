@@ -4,12 +4,6 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-/*
- * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
- *
- * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
- */
-
 use crate::{
     core::{Session, SessionData},
     op::ImapContext,
@@ -21,12 +15,13 @@ use imap_proto::{
     protocol::{
         ImapResponse,
         capability::QuotaResourceName,
-        quota::{Arguments, QuotaItem, QuotaResource, Response},
+        quota::{Arguments, QuotaItem, QuotaResource, Response, SetArguments},
     },
     receiver::Request,
 };
-use registry::schema::enums::Permission;
+use registry::schema::enums::{Permission, StorageQuota};
 use std::time::Instant;
+use store::registry::write::RegistryWriteResult;
 
 impl<T: SessionStream> Session<T> {
     pub async fn handle_get_quota(&mut self, request: Request<Command>) -> trc::Result<()> {
@@ -75,6 +70,29 @@ impl<T: SessionStream> Session<T> {
             Ok(())
         })
     }
+
+    pub async fn handle_set_quota(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapSetQuota)?;
+
+        let data = self.state.session_data();
+
+        spawn_op!(data, {
+            match request.parse_set_quota() {
+                Ok(argument) => match data.set_quota(argument).await {
+                    Ok(response) => {
+                        data.write_bytes(response).await?;
+                    }
+                    Err(error) => {
+                        data.write_error(error).await?;
+                    }
+                },
+                Err(err) => data.write_error(err).await?,
+            }
+
+            Ok(())
+        })
+    }
 }
 
 impl<T: SessionStream> SessionData<T> {
@@ -207,4 +225,94 @@ impl<T: SessionStream> SessionData<T> {
             .with_tag(arguments.tag)
             .serialize(response.serialize()))
     }
+
+    pub async fn set_quota(&self, arguments: SetArguments) -> trc::Result<Vec<u8>> {
+        let op_start = Instant::now();
+
+        // Only storage limits can be changed, an empty list removes the limit
+        let mut disk_quota = 0;
+        for (resource, limit) in &arguments.resources {
+            if *resource == QuotaResourceName::Storage {
+                disk_quota = limit.saturating_mul(1024);
+            } else {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("Only the STORAGE resource can be set.")
+                    .id(arguments.tag));
+            }
+        }
+
+        // Update the account quota, tenant administrators can only manage their own accounts
+        let account_id = arguments
+            .name
+            .strip_prefix("#")
+            .and_then(|id| id.parse::<u32>().ok())
+            .ok_or_else(|| {
+                trc::ImapEvent::Error
+                    .into_err()
+                    .details("Invalid quota root parameter.")
+                    .id(arguments.tag.to_string())
+            })?;
+        match self
+            .server
+            .set_account_quota(
+                &self.access_token,
+                account_id,
+                StorageQuota::MaxDiskQuota,
+                disk_quota,
+            )
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?
+        {
+            RegistryWriteResult::Success(_) => {}
+            RegistryWriteResult::NotFound { .. } => {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("Invalid quota root parameter.")
+                    .id(arguments.tag));
+            }
+            err => {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details(err.to_string())
+                    .id(arguments.tag));
+            }
+        }
+
+        let used_quota = self
+            .server
+            .get_used_quota_account(account_id)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?;
+
+        trc::event!(
+            Imap(trc::ImapEvent::SetQuota),
+            SpanId = self.session_id,
+            Id = arguments.name.clone(),
+            AccountId = account_id,
+            Details = vec![trc::Value::from(used_quota), trc::Value::from(disk_quota)],
+            Elapsed = op_start.elapsed()
+        );
+
+        // Build response
+        let response = Response {
+            quota_root_items: vec![],
+            quota_items: vec![QuotaItem {
+                name: arguments.name,
+                resources: if disk_quota > 0 {
+                    vec![QuotaResource {
+                        resource: QuotaResourceName::Storage,
+                        total: disk_quota,
+                        used: used_quota as u64,
+                    }]
+                } else {
+                    vec![]
+                },
+            }],
+        };
+
+        Ok(StatusResponse::ok("SETQUOTA successful.")
+            .with_tag(arguments.tag)
+            .serialize(response.serialize()))
+    }
 }
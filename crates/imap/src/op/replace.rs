@@ -0,0 +1,176 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ImapContext, ToModSeq};
+use crate::{
+    core::{MailboxId, SavedSearch, SelectedMailbox, Session, SessionData},
+    spawn_op,
+};
+use common::network::SessionStream;
+use imap_proto::{
+    Command, ResponseCode, StatusResponse,
+    protocol::append::{self, ReplaceArguments},
+    receiver::Request,
+};
+use registry::schema::enums::Permission;
+use std::{sync::Arc, time::Instant};
+use store::roaring::RoaringBitmap;
+use types::acl::Acl;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn handle_replace(
+        &mut self,
+        request: Request<Command>,
+        is_uid: bool,
+    ) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapReplace)?;
+
+        let op_start = Instant::now();
+        let arguments = request.parse_replace(self.is_utf8)?;
+        let (data, src_mailbox) = self.state.mailbox_state();
+
+        // Refresh mailboxes
+        data.synchronize_mailboxes(false)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?;
+
+        // Obtain destination mailbox
+        let dest_mailbox = if let Some(mailbox) = data.get_mailbox_by_name(&arguments.mailbox_name)
+        {
+            mailbox
+        } else {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details("Mailbox does not exist.")
+                .code(ResponseCode::TryCreate)
+                .id(arguments.tag));
+        };
+        let is_qresync = self.is_qresync;
+        let is_condstore = self.is_condstore;
+
+        spawn_op!(data, {
+            let response = data
+                .replace_message(
+                    arguments,
+                    src_mailbox,
+                    dest_mailbox,
+                    is_uid,
+                    is_qresync,
+                    is_condstore,
+                    op_start,
+                )
+                .await?;
+
+            data.write_bytes(response).await
+        })
+    }
+}
+
+impl<T: SessionStream> SessionData<T> {
+    #[allow(clippy::too_many_arguments)]
+    async fn replace_message(
+        &self,
+        arguments: ReplaceArguments,
+        src_mailbox: Arc<SelectedMailbox>,
+        dest_mailbox: MailboxId,
+        is_uid: bool,
+        is_qresync: bool,
+        is_condstore: bool,
+        op_start: Instant,
+    ) -> trc::Result<Vec<u8>> {
+        // Resync messages if needed
+        self.synchronize_messages(&src_mailbox)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?;
+
+        // Obtain the message to replace
+        let src_id = src_mailbox
+            .sequence_to_ids(&arguments.sequence, is_uid)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?
+            .into_keys()
+            .next()
+            .ok_or_else(|| {
+                trc::ImapEvent::Error
+                    .into_err()
+                    .details("The message to replace no longer exists.")
+                    .id(arguments.tag.clone())
+            })?;
+
+        // The source message has to be expunged from the selected mailbox
+        if !self
+            .check_mailbox_acl(
+                src_mailbox.id.account_id,
+                src_mailbox.id.mailbox_id,
+                Acl::RemoveItems,
+            )
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?
+        {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details(concat!(
+                    "You do not have the required permissions ",
+                    "to remove messages from this mailbox."
+                ))
+                .code(ResponseCode::NoPerm)
+                .id(arguments.tag));
+        }
+
+        // Append the replacement message
+        let tag = arguments.tag;
+        let append_response = self
+            .append_messages(
+                append::Arguments {
+                    tag: tag.clone(),
+                    mailbox_name: arguments.mailbox_name,
+                    messages: vec![arguments.message],
+                },
+                Some(src_mailbox.clone()),
+                dest_mailbox,
+                is_qresync,
+                op_start,
+            )
+            .await?;
+        let mut appended = StatusResponse::ok("Replacement message ready.");
+        appended.code = append_response.code;
+
+        // Expunge the original message
+        self.expunge_ids(
+            &src_mailbox.id,
+            RoaringBitmap::from_iter([src_id]),
+            op_start,
+        )
+        .await
+        .imap_ctx(&tag, trc::location!())?;
+        *src_mailbox.saved_search.lock() = SavedSearch::None;
+
+        trc::event!(
+            Imap(trc::ImapEvent::Replace),
+            SpanId = self.session_id,
+            AccountId = src_mailbox.id.account_id,
+            MailboxId = src_mailbox.id.mailbox_id,
+            DocumentId = src_id,
+            Elapsed = op_start.elapsed()
+        );
+
+        self.write_bytes(appended.into_bytes()).await?;
+        let modseq = self
+            .write_mailbox_changes(&src_mailbox, is_qresync)
+            .await
+            .imap_ctx(&tag, trc::location!())?;
+
+        let mut response = StatusResponse::completed(Command::Replace(is_uid)).with_tag(tag);
+        if is_condstore {
+            response = response.with_code(ResponseCode::HighestModseq {
+                modseq: modseq.to_modseq(),
+            });
+        }
+
+        Ok(response.into_bytes())
+    }
+}
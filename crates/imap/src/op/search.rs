@@ -9,10 +9,14 @@ use crate::{
     core::{ImapId, SavedSearch, SelectedMailbox, Session, SessionData},
     spawn_op,
 };
+use ahash::AHashMap;
 use common::network::SessionStream;
-use email::cache::{MessageCacheFetch, email::MessageCacheAccess};
+use email::{
+    cache::{MessageCacheFetch, email::MessageCacheAccess},
+    message::index::saved_at_id,
+};
 use imap_proto::{
-    Command, StatusResponse,
+    Command, ResponseCode, ResponseType, StatusResponse,
     protocol::{
         Sequence,
         search::{self, Arguments, Comparator, Filter, Response, ResultOption},
@@ -24,16 +28,22 @@ use nlp::language::Language;
 use registry::schema::enums::Permission;
 use std::{str::FromStr, sync::Arc, time::Instant};
 use store::{
+    IterateParams, U32_LEN, ValueKey,
     query::log::Query,
     roaring::RoaringBitmap,
     search::{
         EmailSearchField, SearchComparator, SearchFilter, SearchOperator, SearchQuery, SearchValue,
     },
-    write::{SearchIndex, now},
+    write::{IndexPropertyClass, SearchIndex, ValueClass, key::DeserializeBigEndian, now},
 };
 use tokio::sync::watch;
 use trc::AddContext;
-use types::{collection::SyncCollection, id::Id, keyword::Keyword};
+use types::{
+    collection::{Collection, SyncCollection},
+    field::EmailField,
+    id::Id,
+    keyword::Keyword,
+};
 use utils::map::vec_map::VecMap;
 
 impl<T: SessionStream> Session<T> {
@@ -56,6 +66,12 @@ impl<T: SessionStream> Session<T> {
             request.parse_sort()
         }?;
 
+        // UIDONLY results are always returned as ESEARCH
+        if self.is_uidonly {
+            self.assert_uid_filter(&arguments.filter, &arguments.tag)?;
+            arguments.is_esearch = true;
+        }
+
         let (data, mailbox) = self.state.mailbox_state();
 
         // Create channel for results
@@ -105,6 +121,25 @@ impl<T: SessionStream> Session<T> {
     }
 }
 
+impl<T: SessionStream> Session<T> {
+    pub fn assert_uid_filter(&self, filter: &[Filter], tag: &str) -> trc::Result<()> {
+        if self.is_uidonly
+            && filter.iter().any(|filter| {
+                matches!(filter, Filter::Sequence(sequence, false) if !sequence.is_saved_search())
+            })
+        {
+            Err(trc::ImapEvent::Error
+                .into_err()
+                .details("Message sequence numbers are not allowed in UIDONLY mode.")
+                .code(ResponseCode::UidRequired)
+                .ctx(trc::Key::Type, ResponseType::Bad)
+                .id(tag.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 impl<T: SessionStream> SessionData<T> {
     pub async fn search(
         &self,
@@ -136,6 +171,17 @@ impl<T: SessionStream> SessionData<T> {
             None
         };
 
+        // Partial results require the full list of ids
+        let partial = arguments
+            .result_options
+            .iter()
+            .find_map(|option| match option {
+                ResultOption::Partial(partial) => Some(*partial),
+                _ => None,
+            });
+        let find_min = arguments.result_options.contains(&ResultOption::Min);
+        let find_max = arguments.result_options.contains(&ResultOption::Max);
+
         // Sort and map ids
         let mut min: Option<(u32, ImapId)> = None;
         let mut max: Option<(u32, ImapId)> = None;
@@ -150,8 +196,8 @@ impl<T: SessionStream> SessionData<T> {
         mailbox.map_search_results(
             result_set.into_iter(),
             is_uid,
-            arguments.result_options.contains(&ResultOption::Min),
-            arguments.result_options.contains(&ResultOption::Max),
+            find_min && partial.is_none(),
+            find_max && partial.is_none(),
             &mut min,
             &mut max,
             &mut total,
@@ -161,6 +207,14 @@ impl<T: SessionStream> SessionData<T> {
         if !is_sort {
             imap_ids.sort_unstable();
         }
+        let (min, max) = if partial.is_none() {
+            (min.map(|(id, _)| id), max.map(|(id, _)| id))
+        } else {
+            (
+                imap_ids.iter().min().copied().filter(|_| find_min),
+                imap_ids.iter().max().copied().filter(|_| find_max),
+            )
+        };
 
         // Save results
         if let (Some(results_tx), Some(saved_results)) = (results_tx, saved_results) {
@@ -187,14 +241,16 @@ impl<T: SessionStream> SessionData<T> {
         // Build response
         Ok(Response {
             is_uid,
-            min: min.map(|(id, _)| id),
-            max: max.map(|(id, _)| id),
+            min,
+            max,
             count: if arguments.result_options.contains(&ResultOption::Count) {
                 Some(total)
             } else {
                 None
             },
-            ids: if arguments.result_options.is_empty()
+            ids: if let Some(partial) = &partial {
+                partial.slice(&imap_ids).to_vec()
+            } else if arguments.result_options.is_empty()
                 || arguments.result_options.contains(&ResultOption::All)
             {
                 imap_ids
@@ -204,6 +260,7 @@ impl<T: SessionStream> SessionData<T> {
             is_sort,
             is_esearch: arguments.is_esearch,
             highest_modseq,
+            partial,
         })
     }
 
@@ -229,6 +286,7 @@ impl<T: SessionStream> SessionData<T> {
 
         // Convert query
         let mut include_highest_modseq = false;
+        let mut saved_dates = None;
         for filter in imap_filter {
             match filter {
                 Filter::Sequence(sequence, uid_filter) => {
@@ -393,6 +451,45 @@ impl<T: SessionStream> SessionData<T> {
                         now().saturating_sub(secs as u64),
                     ));
                 }
+                Filter::SavedBefore(date) => {
+                    if saved_dates.is_none() {
+                        saved_dates = Some(self.saved_dates(mailbox).await?);
+                    }
+                    saved_date_filter(
+                        &mut filters,
+                        saved_dates.as_ref().unwrap(),
+                        &message_ids,
+                        0,
+                        Some(date),
+                    );
+                }
+                Filter::SavedOn(date) => {
+                    if saved_dates.is_none() {
+                        saved_dates = Some(self.saved_dates(mailbox).await?);
+                    }
+                    saved_date_filter(
+                        &mut filters,
+                        saved_dates.as_ref().unwrap(),
+                        &message_ids,
+                        date,
+                        Some(date + 86400),
+                    );
+                }
+                Filter::SavedSince(date) => {
+                    if saved_dates.is_none() {
+                        saved_dates = Some(self.saved_dates(mailbox).await?);
+                    }
+                    saved_date_filter(
+                        &mut filters,
+                        saved_dates.as_ref().unwrap(),
+                        &message_ids,
+                        date,
+                        None,
+                    );
+                }
+                Filter::SaveDateSupported => {
+                    filters.push(SearchFilter::is_in_set(message_ids.clone()));
+                }
                 Filter::ModSeq((modseq, _)) => {
                     let mut set = RoaringBitmap::new();
                     for id in self
@@ -633,6 +730,37 @@ impl<T: SessionStream> SessionData<T> {
             .map(|res| (res, include_highest_modseq))
             .caused_by(trc::location!())
     }
+
+    async fn saved_dates(&self, mailbox: &SelectedMailbox) -> trc::Result<AHashMap<u32, u64>> {
+        let mut saved_dates = AHashMap::new();
+        let key = |document_id: u32, uid: u32| ValueKey {
+            account_id: mailbox.id.account_id,
+            collection: Collection::Email.into(),
+            document_id,
+            class: ValueClass::IndexProperty(IndexPropertyClass::Integer {
+                property: EmailField::SavedAt.into(),
+                value: saved_at_id(mailbox.id.mailbox_id, uid),
+            }),
+        };
+
+        self.server
+            .store()
+            .iterate(
+                IterateParams::new(key(0, 0), key(u32::MAX, u32::MAX)).ascending(),
+                |key, value| {
+                    saved_dates.insert(
+                        key.deserialize_be_u32(key.len() - U32_LEN)?,
+                        value.deserialize_be_u64(0)?,
+                    );
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(saved_dates)
+    }
 }
 
 impl SelectedMailbox {
@@ -721,3 +849,33 @@ impl SavedSearch {
         }
     }
 }
+
+// Messages saved before save dates were recorded are matched by their received date
+fn saved_date_filter(
+    filters: &mut Vec<SearchFilter>,
+    saved_dates: &AHashMap<u32, u64>,
+    message_ids: &RoaringBitmap,
+    from: i64,
+    to: Option<i64>,
+) {
+    let mut saved_ids = RoaringBitmap::new();
+    let mut unsaved_ids = message_ids.clone();
+    for (document_id, saved_at) in saved_dates {
+        let saved_at = *saved_at as i64;
+        if saved_at >= from && to.is_none_or(|to| saved_at < to) {
+            saved_ids.insert(*document_id);
+        }
+        unsaved_ids.remove(*document_id);
+    }
+
+    filters.push(SearchFilter::Or);
+    filters.push(SearchFilter::is_in_set(saved_ids));
+    filters.push(SearchFilter::And);
+    filters.push(SearchFilter::is_in_set(unsaved_ids));
+    filters.push(SearchFilter::ge(EmailSearchField::ReceivedAt, from));
+    if let Some(to) = to {
+        filters.push(SearchFilter::lt(EmailSearchField::ReceivedAt, to));
+    }
+    filters.push(SearchFilter::End);
+    filters.push(SearchFilter::End);
+}
@@ -67,7 +67,7 @@ impl<T: SessionStream> Session<T> {
         if let Some(mailbox) = mailbox {
             // Try obtaining the mailbox from the cache
             let state = data
-                .fetch_messages(&mailbox, None)
+                .fetch_messages(&mailbox, None, self.is_uidonly)
                 .await
                 .imap_ctx(&arguments.tag, trc::location!())?
                 .unwrap();
@@ -92,6 +92,7 @@ impl<T: SessionStream> Session<T> {
                 saved_search: parking_lot::Mutex::new(SavedSearch::None),
                 is_select,
                 is_condstore,
+                is_uidonly: self.is_uidonly,
             });
 
             // Validate QRESYNC arguments
@@ -117,6 +118,7 @@ impl<T: SessionStream> Session<T> {
                             attributes: vec![fetch::Attribute::Flags],
                             changed_since: qresync.modseq.into(),
                             include_vanished: true,
+                            partial: None,
                         },
                         mailbox.clone(),
                         true,
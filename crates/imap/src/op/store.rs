@@ -192,6 +192,7 @@ impl<T: SessionStream> SessionData<T> {
             return Ok(response.into_bytes());
         }
         let mut items = Response {
+            is_uidonly: mailbox.is_uidonly,
             items: Vec::with_capacity(ids.len()),
         };
 
@@ -330,17 +331,17 @@ impl<T: SessionStream> SessionData<T> {
             // Add item to response
             if !arguments.is_silent {
                 let mut data_items = vec![DataItem::Flags { flags }];
-                if is_uid {
+                if is_uid && !mailbox.is_uidonly {
                     data_items.push(DataItem::Uid { uid: imap_id.uid });
                 }
                 items.items.push(FetchItem {
-                    id: imap_id.seqnum,
+                    id: imap_id.response_id(mailbox.is_uidonly),
                     items: data_items,
                 });
             } else if is_condstore {
                 items.items.push(FetchItem {
-                    id: imap_id.seqnum,
-                    items: if is_uid {
+                    id: imap_id.response_id(mailbox.is_uidonly),
+                    items: if is_uid && !mailbox.is_uidonly {
                         vec![DataItem::Uid { uid: imap_id.uid }]
                     } else {
                         vec![]
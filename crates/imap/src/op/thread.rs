@@ -35,6 +35,7 @@ impl<T: SessionStream> Session<T> {
         let op_start = Instant::now();
         let command = request.command;
         let mut arguments = request.parse_thread()?;
+        self.assert_uid_filter(&arguments.filter, &arguments.tag)?;
         let (data, mailbox) = self.state.mailbox_state();
 
         spawn_op!(data, {
@@ -0,0 +1,352 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    core::{SelectedMailbox, Session, SessionData},
+    op::ImapContext,
+    spawn_op,
+};
+use common::network::SessionStream;
+use email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    mailbox::INBOX_ID,
+    message::urlauth::{ImapUrl, URLAUTH_MECHANISM, UrlAuthFetch, UrlFetchError, UrlRequester},
+};
+use imap_proto::{
+    Command, ResponseCode, StatusResponse,
+    protocol::{
+        ImapResponse,
+        append::CatenatePart,
+        urlauth::{
+            GenUrlAuthArguments, GenUrlAuthResponse, ResetKeyArguments, UrlFetchArguments,
+            UrlFetchItem, UrlFetchResponse,
+        },
+    },
+    receiver::Request,
+};
+use registry::schema::enums::Permission;
+use std::time::Instant;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn handle_gen_url_auth(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapGenUrlAuth)?;
+
+        let data = self.state.session_data();
+
+        spawn_op!(data, {
+            let response = data.gen_url_auth(request.parse_gen_url_auth()?).await?;
+            data.write_bytes(response).await
+        })
+    }
+
+    pub async fn handle_reset_key(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapResetKey)?;
+
+        let data = self.state.session_data();
+        let is_utf8 = self.is_utf8;
+
+        spawn_op!(data, {
+            let response = data
+                .reset_key(request.parse_reset_key(is_utf8)?)
+                .await?
+                .into_bytes();
+            data.write_bytes(response).await
+        })
+    }
+
+    pub async fn handle_url_fetch(&mut self, request: Request<Command>) -> trc::Result<()> {
+        // Validate access
+        self.assert_has_permission(Permission::ImapUrlFetch)?;
+
+        let data = self.state.session_data();
+
+        spawn_op!(data, {
+            let response = data.url_fetch(request.parse_url_fetch()?).await?;
+            data.write_bytes(response).await
+        })
+    }
+}
+
+impl<T: SessionStream> SessionData<T> {
+    async fn gen_url_auth(&self, arguments: GenUrlAuthArguments) -> trc::Result<Vec<u8>> {
+        let op_start = Instant::now();
+        let mut urls = Vec::with_capacity(arguments.urls.len());
+
+        for item in &arguments.urls {
+            if item.mechanism != URLAUTH_MECHANISM {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details(format!(
+                        "Unsupported URLAUTH mechanism {:?}.",
+                        item.mechanism
+                    ))
+                    .id(arguments.tag));
+            }
+
+            // Only URL rumps pointing to the user's own mailboxes can be authorized
+            let Some((url, auth)) = ImapUrl::parse(&item.url).and_then(|url| {
+                url.auth
+                    .clone()
+                    .filter(|auth| auth.token.is_none())
+                    .map(|auth| (url, auth))
+            }) else {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("Invalid URL rump.")
+                    .code(ResponseCode::BadUrl {
+                        url: item.url.clone(),
+                    })
+                    .id(arguments.tag));
+            };
+            let Some(target) = self
+                .server
+                .resolve_imap_url(&url)
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?
+                .filter(|target| target.account_id == self.account_id)
+            else {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("URL does not reference a message owned by the user.")
+                    .code(ResponseCode::BadUrl {
+                        url: item.url.clone(),
+                    })
+                    .id(arguments.tag));
+            };
+
+            let access_key = self
+                .server
+                .mailbox_access_key(target.account_id, target.mailbox_id, true)
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?
+                .unwrap_or_default();
+            urls.push(format!(
+                "{}:{}:{}",
+                auth.rump,
+                URLAUTH_MECHANISM.to_ascii_lowercase(),
+                auth.generate_token(access_key.as_bytes())
+            ));
+        }
+
+        trc::event!(
+            Imap(trc::ImapEvent::GenUrlAuth),
+            SpanId = self.session_id,
+            Url = arguments
+                .urls
+                .iter()
+                .map(|item| trc::Value::from(item.url.clone()))
+                .collect::<Vec<_>>(),
+            Elapsed = op_start.elapsed()
+        );
+
+        Ok(StatusResponse::completed(Command::GenUrlAuth)
+            .with_tag(arguments.tag)
+            .serialize(GenUrlAuthResponse { urls }.serialize()))
+    }
+
+    async fn reset_key(&self, arguments: ResetKeyArguments) -> trc::Result<StatusResponse> {
+        let op_start = Instant::now();
+
+        if let Some(mechanism) = arguments
+            .mechanisms
+            .iter()
+            .find(|mechanism| *mechanism != URLAUTH_MECHANISM)
+        {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details(format!("Unsupported URLAUTH mechanism {mechanism:?}."))
+                .id(arguments.tag));
+        }
+
+        // Obtain the mailboxes to reset
+        let mailbox_ids = if let Some(mailbox_name) = &arguments.mailbox_name {
+            self.synchronize_mailboxes(false)
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?;
+
+            match self.get_mailbox_by_name(mailbox_name) {
+                Some(mailbox) if mailbox.account_id == self.account_id => {
+                    vec![mailbox.mailbox_id]
+                }
+                Some(_) => {
+                    return Err(trc::ImapEvent::Error
+                        .into_err()
+                        .details("Access keys can only be reset on your own mailboxes.")
+                        .code(ResponseCode::NoPerm)
+                        .id(arguments.tag));
+                }
+                None => {
+                    return Err(trc::ImapEvent::Error
+                        .into_err()
+                        .details("Mailbox does not exist.")
+                        .code(ResponseCode::NonExistent)
+                        .id(arguments.tag));
+                }
+            }
+        } else {
+            self.server
+                .get_cached_messages(self.account_id)
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?
+                .mailboxes
+                .items
+                .iter()
+                .map(|mailbox| mailbox.document_id)
+                .collect()
+        };
+
+        self.server
+            .reset_mailbox_access_keys(self.account_id, mailbox_ids)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?;
+
+        trc::event!(
+            Imap(trc::ImapEvent::ResetKey),
+            SpanId = self.session_id,
+            AccountId = self.account_id,
+            MailboxName = arguments.mailbox_name.unwrap_or_default(),
+            Elapsed = op_start.elapsed()
+        );
+
+        Ok(StatusResponse::completed(Command::ResetKey).with_tag(arguments.tag))
+    }
+
+    async fn url_fetch(&self, arguments: UrlFetchArguments) -> trc::Result<Vec<u8>> {
+        let op_start = Instant::now();
+        let account = self
+            .server
+            .account(self.account_id)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?;
+        let requester = UrlRequester {
+            account_id: self.account_id,
+            name: &account.name,
+            is_submit: false,
+        };
+
+        let mut items = Vec::with_capacity(arguments.urls.len());
+        for url in arguments.urls {
+            let contents = if let Some(imap_url) = ImapUrl::parse(&url) {
+                self.server
+                    .fetch_imap_url(&imap_url, requester)
+                    .await
+                    .imap_ctx(&arguments.tag, trc::location!())?
+                    .ok()
+            } else {
+                None
+            };
+
+            items.push(UrlFetchItem { url, contents });
+        }
+
+        trc::event!(
+            Imap(trc::ImapEvent::UrlFetch),
+            SpanId = self.session_id,
+            Url = items
+                .iter()
+                .map(|item| trc::Value::from(item.url.clone()))
+                .collect::<Vec<_>>(),
+            Size = items
+                .iter()
+                .map(|item| item.contents.as_ref().map_or(0, |c| c.len()))
+                .sum::<usize>(),
+            Elapsed = op_start.elapsed()
+        );
+
+        Ok(StatusResponse::completed(Command::UrlFetch)
+            .with_tag(arguments.tag)
+            .serialize(UrlFetchResponse { items }.serialize()))
+    }
+
+    pub(crate) async fn catenate(
+        &self,
+        parts: Vec<CatenatePart>,
+        selected_mailbox: Option<&SelectedMailbox>,
+        tag: &str,
+    ) -> trc::Result<Vec<u8>> {
+        let account = self
+            .server
+            .account(self.account_id)
+            .await
+            .imap_ctx(tag, trc::location!())?;
+        let requester = UrlRequester {
+            account_id: self.account_id,
+            name: &account.name,
+            is_submit: false,
+        };
+        let max_size = self.server.core.imap.max_request_size;
+
+        let mut raw_message = Vec::new();
+        for part in parts {
+            match part {
+                CatenatePart::Text(text) => {
+                    raw_message.extend_from_slice(&text);
+                }
+                CatenatePart::Url(url) => {
+                    // Relative URLs reference the user's own mailboxes, or the
+                    // selected mailbox when no mailbox name is given
+                    let imap_url = if url.starts_with(';') || url.starts_with("/;") {
+                        match selected_mailbox.filter(|m| m.id.account_id == self.account_id) {
+                            Some(selected) => self
+                                .server
+                                .get_cached_messages(self.account_id)
+                                .await
+                                .imap_ctx(tag, trc::location!())?
+                                .mailbox_by_id(&selected.id.mailbox_id)
+                                .and_then(|mailbox| {
+                                    let mut imap_url = ImapUrl::parse(&format!(
+                                        "imap://{}@localhost/INBOX/{}",
+                                        account.name,
+                                        url.trim_start_matches('/')
+                                    ))?;
+                                    if mailbox.document_id != INBOX_ID {
+                                        imap_url.mailbox = mailbox.path.clone();
+                                    }
+                                    Some(imap_url)
+                                }),
+                            None => None,
+                        }
+                    } else if url.starts_with('/') {
+                        ImapUrl::parse(&format!("imap://{}@localhost{url}", account.name))
+                    } else {
+                        ImapUrl::parse(&url)
+                    };
+                    let result = if let Some(imap_url) = &imap_url {
+                        self.server
+                            .fetch_imap_url(imap_url, requester)
+                            .await
+                            .imap_ctx(tag, trc::location!())?
+                    } else {
+                        Err(UrlFetchError::NotFound)
+                    };
+
+                    match result {
+                        Ok(contents) => raw_message.extend_from_slice(&contents),
+                        Err(_) => {
+                            return Err(trc::ImapEvent::Error
+                                .into_err()
+                                .details("Unable to fetch CATENATE URL.")
+                                .code(ResponseCode::BadUrl { url })
+                                .id(tag.to_string()));
+                        }
+                    }
+                }
+            }
+
+            if raw_message.len() > max_size {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("Message exceeds the maximum allowed size.")
+                    .code(ResponseCode::TooBig)
+                    .id(tag.to_string()));
+            }
+        }
+
+        Ok(raw_message)
+    }
+}
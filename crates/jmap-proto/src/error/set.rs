@@ -111,6 +111,8 @@ pub enum SetErrorType {
     NodeHasChildren,
     #[serde(rename = "calendarHasEvent")]
     CalendarHasEvent,
+    #[serde(rename = "mdnAlreadySent")]
+    MdnAlreadySent,
     // Stalwart registry errors
     #[serde(rename = "objectIsLinked")]
     ObjectIsLinked,
@@ -153,6 +155,7 @@ impl SetErrorType {
             SetErrorType::AddressBookHasContents => "addressBookHasContents",
             SetErrorType::NodeHasChildren => "nodeHasChildren",
             SetErrorType::CalendarHasEvent => "calendarHasEvent",
+            SetErrorType::MdnAlreadySent => "mdnAlreadySent",
             SetErrorType::ObjectIsLinked => "objectIsLinked",
             SetErrorType::InvalidForeignKey => "invalidForeignKey",
             SetErrorType::PrimaryKeyViolation => "primaryKeyViolation",
@@ -221,6 +224,14 @@ impl<T: Property> SetError<T> {
         self
     }
 
+    pub fn error_type(&self) -> &SetErrorType {
+        &self.0.type_
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
     pub fn invalid_properties() -> Self {
         Self::new(SetErrorType::InvalidProperties)
     }
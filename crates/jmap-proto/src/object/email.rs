@@ -74,6 +74,11 @@ pub enum EmailProperty {
     HasAttachment,
     Preview,
 
+    // S/MIME
+    SmimeStatus,
+    SmimeErrors,
+    SmimeVerifiedAt,
+
     // Other
     Keyword(Keyword),
     IdValue(Id),
@@ -167,6 +172,9 @@ impl Property for EmailProperty {
             EmailProperty::Value => "value",
             EmailProperty::IsEncodingProblem => "isEncodingProblem",
             EmailProperty::IsTruncated => "isTruncated",
+            EmailProperty::SmimeStatus => "smimeStatus",
+            EmailProperty::SmimeErrors => "smimeErrors",
+            EmailProperty::SmimeVerifiedAt => "smimeVerifiedAt",
             EmailProperty::Header(header) => return header.to_string().into(),
             EmailProperty::Keyword(keyword) => return keyword.to_string().into(),
             EmailProperty::IdValue(id) => return id.to_string().into(),
@@ -200,7 +208,10 @@ impl Element for EmailValue {
                     ..
                 })
                 | EmailProperty::ReceivedAt
-                | EmailProperty::SentAt => UTCDate::from_str(value).ok().map(EmailValue::Date),
+                | EmailProperty::SentAt
+                | EmailProperty::SmimeVerifiedAt => {
+                    UTCDate::from_str(value).ok().map(EmailValue::Date)
+                }
                 _ => None,
             }
         } else {
@@ -260,7 +271,10 @@ impl EmailProperty {
                 "isEncodingProblem" => EmailProperty::IsEncodingProblem,
                 "isTruncated" => EmailProperty::IsTruncated,
                 "hasAttachment" => EmailProperty::HasAttachment,
-                "preview" => EmailProperty::Preview
+                "preview" => EmailProperty::Preview,
+                "smimeStatus" => EmailProperty::SmimeStatus,
+                "smimeErrors" => EmailProperty::SmimeErrors,
+                "smimeVerifiedAt" => EmailProperty::SmimeVerifiedAt
         )
         .or_else(|| {
             if let Some(header) = value.strip_prefix("header:") {
@@ -0,0 +1,222 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use jmap_tools::{Key, Property};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use types::id::Id;
+use utils::map::vec_map::VecMap;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mdn {
+    #[serde(rename = "forEmailId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub for_email_id: Option<Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub subject: Option<String>,
+
+    #[serde(rename = "textBody")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub text_body: Option<String>,
+
+    #[serde(rename = "includeOriginalMessage")]
+    #[serde(default)]
+    pub include_original_message: bool,
+
+    #[serde(rename = "reportingUA")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub reporting_ua: Option<String>,
+
+    pub disposition: Disposition,
+
+    #[serde(rename = "mdnGateway")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub mdn_gateway: Option<String>,
+
+    #[serde(rename = "originalRecipient")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub original_recipient: Option<String>,
+
+    #[serde(rename = "finalRecipient")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub final_recipient: Option<String>,
+
+    #[serde(rename = "originalMessageId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub original_message_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub error: Option<Vec<String>>,
+
+    #[serde(rename = "extensionFields")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub extension_fields: Option<VecMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Disposition {
+    #[serde(rename = "actionMode")]
+    pub action_mode: ActionMode,
+
+    #[serde(rename = "sendingMode")]
+    pub sending_mode: SendingMode,
+
+    #[serde(rename = "type")]
+    pub type_: DispositionType,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionMode {
+    #[default]
+    #[serde(rename = "manual-action")]
+    Manual,
+    #[serde(rename = "automatic-action")]
+    Automatic,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SendingMode {
+    #[default]
+    #[serde(rename = "mdn-sent-manually")]
+    Manually,
+    #[serde(rename = "mdn-sent-automatically")]
+    Automatically,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DispositionType {
+    #[serde(rename = "deleted")]
+    Deleted,
+    #[default]
+    #[serde(rename = "dispatched")]
+    Dispatched,
+    #[serde(rename = "displayed")]
+    Displayed,
+    #[serde(rename = "processed")]
+    Processed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MdnProperty {
+    ForEmailId,
+    Subject,
+    TextBody,
+    IncludeOriginalMessage,
+    ReportingUA,
+    Disposition,
+    MdnGateway,
+    OriginalRecipient,
+    FinalRecipient,
+    OriginalMessageId,
+    Error,
+    ExtensionFields,
+}
+
+impl Property for MdnProperty {
+    fn try_parse(_: Option<&Key<'_, Self>>, value: &str) -> Option<Self> {
+        MdnProperty::parse(value)
+    }
+
+    fn to_cow(&self) -> Cow<'static, str> {
+        match self {
+            MdnProperty::ForEmailId => "forEmailId",
+            MdnProperty::Subject => "subject",
+            MdnProperty::TextBody => "textBody",
+            MdnProperty::IncludeOriginalMessage => "includeOriginalMessage",
+            MdnProperty::ReportingUA => "reportingUA",
+            MdnProperty::Disposition => "disposition",
+            MdnProperty::MdnGateway => "mdnGateway",
+            MdnProperty::OriginalRecipient => "originalRecipient",
+            MdnProperty::FinalRecipient => "finalRecipient",
+            MdnProperty::OriginalMessageId => "originalMessageId",
+            MdnProperty::Error => "error",
+            MdnProperty::ExtensionFields => "extensionFields",
+        }
+        .into()
+    }
+}
+
+impl MdnProperty {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map!(value.as_bytes(),
+            b"forEmailId" => MdnProperty::ForEmailId,
+            b"subject" => MdnProperty::Subject,
+            b"textBody" => MdnProperty::TextBody,
+            b"includeOriginalMessage" => MdnProperty::IncludeOriginalMessage,
+            b"reportingUA" => MdnProperty::ReportingUA,
+            b"disposition" => MdnProperty::Disposition,
+            b"mdnGateway" => MdnProperty::MdnGateway,
+            b"originalRecipient" => MdnProperty::OriginalRecipient,
+            b"finalRecipient" => MdnProperty::FinalRecipient,
+            b"originalMessageId" => MdnProperty::OriginalMessageId,
+            b"error" => MdnProperty::Error,
+            b"extensionFields" => MdnProperty::ExtensionFields,
+        )
+    }
+}
+
+impl ActionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionMode::Manual => "manual-action",
+            ActionMode::Automatic => "automatic-action",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map_ignore_case!(value.as_bytes(),
+            b"manual-action" => ActionMode::Manual,
+            b"automatic-action" => ActionMode::Automatic,
+        )
+    }
+}
+
+impl SendingMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SendingMode::Manually => "MDN-sent-manually",
+            SendingMode::Automatically => "MDN-sent-automatically",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map_ignore_case!(value.as_bytes(),
+            b"mdn-sent-manually" => SendingMode::Manually,
+            b"mdn-sent-automatically" => SendingMode::Automatically,
+        )
+    }
+}
+
+impl DispositionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DispositionType::Deleted => "deleted",
+            DispositionType::Dispatched => "dispatched",
+            DispositionType::Displayed => "displayed",
+            DispositionType::Processed => "processed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map_ignore_case!(value.as_bytes(),
+            b"deleted" => DispositionType::Deleted,
+            b"dispatched" => DispositionType::Dispatched,
+            b"displayed" => DispositionType::Displayed,
+            b"processed" => DispositionType::Processed,
+        )
+    }
+}
@@ -21,6 +21,7 @@ pub mod email_submission;
 pub mod file_node;
 pub mod identity;
 pub mod mailbox;
+pub mod mdn;
 pub mod participant_identity;
 pub mod principal;
 pub mod push_subscription;
@@ -29,6 +30,8 @@ pub mod registry;
 pub mod search_snippet;
 pub mod share_notification;
 pub mod sieve;
+pub mod task;
+pub mod task_list;
 pub mod thread;
 pub mod vacation_response;
 
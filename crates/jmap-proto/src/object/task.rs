@@ -0,0 +1,200 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    object::{
+        JmapObject,
+        calendar_event::{CalendarEventGetArguments, CalendarEventSetArguments, LocalTime},
+    },
+    request::{MaybeInvalid, deserialize::DeserializeArguments},
+};
+use calcard::{
+    common::timezone::Tz,
+    jscalendar::{JSCalendarDateTime, JSCalendarProperty, JSCalendarValue},
+};
+use std::{borrow::Cow, str::FromStr};
+use types::{blob::BlobId, id::Id};
+
+// Tasks are stored as VTODO calendar resources and share the JSCalendar
+// property set with calendar events, task list membership is exposed
+// through the "taskListId" property.
+#[derive(Debug, Clone, Default)]
+pub struct Task;
+
+impl JmapObject for Task {
+    type Property = JSCalendarProperty<Id>;
+
+    type Element = JSCalendarValue<Id, BlobId>;
+
+    type Id = Id;
+
+    type Filter = TaskFilter;
+
+    type Comparator = TaskComparator;
+
+    type GetArguments = CalendarEventGetArguments;
+
+    type SetArguments<'de> = CalendarEventSetArguments;
+
+    type QueryArguments = TaskQueryArguments;
+
+    type CopyArguments = ();
+
+    type ParseArguments = ();
+
+    const ID_PROPERTY: Self::Property = JSCalendarProperty::Id;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskFilter {
+    InTaskList(MaybeInvalid<Id>),
+    After(JSCalendarDateTime),
+    Before(JSCalendarDateTime),
+    Text(String),
+    Title(String),
+    Description(String),
+    Uid(String),
+    _T(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskComparator {
+    Start,
+    Uid,
+    Created,
+    Updated,
+    _T(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TaskQueryArguments {
+    pub time_zone: Option<Tz>,
+}
+
+impl<'de> DeserializeArguments<'de> for TaskFilter {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"inTaskList" => {
+                *self = TaskFilter::InTaskList(map.next_value()?);
+            },
+            b"after" => {
+                *self = TaskFilter::After(map.next_value::<LocalTime>()?.0);
+            },
+            b"before" => {
+                *self = TaskFilter::Before(map.next_value::<LocalTime>()?.0);
+            },
+            b"text" => {
+                *self = TaskFilter::Text(map.next_value::<Cow<str>>()?.to_lowercase());
+            },
+            b"title" => {
+                *self = TaskFilter::Title(map.next_value::<Cow<str>>()?.to_lowercase());
+            },
+            b"description" => {
+                *self = TaskFilter::Description(map.next_value::<Cow<str>>()?.to_lowercase());
+            },
+            b"uid" => {
+                *self = TaskFilter::Uid(map.next_value()?);
+            },
+            _ => {
+                *self = TaskFilter::_T(key.to_string());
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeArguments<'de> for TaskComparator {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        if key == "property" {
+            let value = map.next_value::<Cow<str>>()?;
+            hashify::fnc_map!(value.as_bytes(),
+                b"start" => {
+                    *self = TaskComparator::Start;
+                },
+                b"uid" => {
+                    *self = TaskComparator::Uid;
+                },
+                b"created" => {
+                    *self = TaskComparator::Created;
+                },
+                b"updated" => {
+                    *self = TaskComparator::Updated;
+                },
+                _ => {
+                    *self = TaskComparator::_T(value.to_string());
+                }
+            );
+        } else {
+            let _ = map.next_value::<serde::de::IgnoredAny>()?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeArguments<'de> for TaskQueryArguments {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"timeZone" => {
+                self.time_zone = map.next_value::<Option<&str>>()?.and_then(|s| Tz::from_str(s).ok());
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+        Ok(())
+    }
+}
+
+impl TaskFilter {
+    pub fn into_string(self) -> Cow<'static, str> {
+        match self {
+            TaskFilter::InTaskList(_) => "inTaskList",
+            TaskFilter::After(_) => "after",
+            TaskFilter::Before(_) => "before",
+            TaskFilter::Text(_) => "text",
+            TaskFilter::Title(_) => "title",
+            TaskFilter::Description(_) => "description",
+            TaskFilter::Uid(_) => "uid",
+            TaskFilter::_T(s) => return Cow::Owned(s),
+        }
+        .into()
+    }
+}
+
+impl TaskComparator {
+    pub fn into_string(self) -> Cow<'static, str> {
+        match self {
+            TaskComparator::Start => "start",
+            TaskComparator::Uid => "uid",
+            TaskComparator::Created => "created",
+            TaskComparator::Updated => "updated",
+            TaskComparator::_T(s) => return Cow::Owned(s),
+        }
+        .into()
+    }
+}
+
+impl Default for TaskFilter {
+    fn default() -> Self {
+        TaskFilter::_T(String::new())
+    }
+}
+
+impl Default for TaskComparator {
+    fn default() -> Self {
+        TaskComparator::_T(String::new())
+    }
+}
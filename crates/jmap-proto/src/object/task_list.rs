@@ -0,0 +1,72 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    object::{
+        JmapObject, JmapSharedObject,
+        calendar::{CalendarProperty, CalendarRight, CalendarValue},
+    },
+    request::deserialize::DeserializeArguments,
+};
+use types::id::Id;
+
+// Task lists are stored as calendars that accept VTODO components, so they
+// share the calendar property set.
+#[derive(Debug, Clone, Default)]
+pub struct TaskList;
+
+#[derive(Debug, Clone, Default)]
+pub struct TaskListSetArguments {
+    pub on_destroy_remove_tasks: Option<bool>,
+}
+
+impl<'de> DeserializeArguments<'de> for TaskListSetArguments {
+    fn deserialize_argument<A>(&mut self, key: &str, map: &mut A) -> Result<(), A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        hashify::fnc_map!(key.as_bytes(),
+            b"onDestroyRemoveTasks" => {
+                self.on_destroy_remove_tasks = map.next_value()?;
+            },
+            _ => {
+                let _ = map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+impl JmapObject for TaskList {
+    type Property = CalendarProperty;
+
+    type Element = CalendarValue;
+
+    type Id = Id;
+
+    type Filter = ();
+
+    type Comparator = ();
+
+    type GetArguments = ();
+
+    type SetArguments<'de> = TaskListSetArguments;
+
+    type QueryArguments = ();
+
+    type CopyArguments = ();
+
+    type ParseArguments = ();
+
+    const ID_PROPERTY: Self::Property = CalendarProperty::Id;
+}
+
+impl JmapSharedObject for TaskList {
+    type Right = CalendarRight;
+
+    const SHARE_WITH_PROPERTY: Self::Property = CalendarProperty::ShareWith;
+}
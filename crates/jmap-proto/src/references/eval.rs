@@ -75,6 +75,10 @@ impl Response<'_> {
                         GetResponseMethod::CalendarEventNotification(response) => {
                             response.eval_jptr(path, &mut results)
                         }
+                        GetResponseMethod::TaskList(response) => {
+                            response.eval_jptr(path, &mut results)
+                        }
+                        GetResponseMethod::Task(response) => response.eval_jptr(path, &mut results),
                         GetResponseMethod::ParticipantIdentity(response) => {
                             response.eval_jptr(path, &mut results)
                         }
@@ -125,6 +129,12 @@ impl Response<'_> {
                         ChangesResponseMethod::CalendarEventNotification(response) => {
                             response.eval_jptr(path, &mut results)
                         }
+                        ChangesResponseMethod::TaskList(response) => {
+                            response.eval_jptr(path, &mut results)
+                        }
+                        ChangesResponseMethod::Task(response) => {
+                            response.eval_jptr(path, &mut results)
+                        }
                         ChangesResponseMethod::ShareNotification(response) => {
                             response.eval_jptr(path, &mut results)
                         }
@@ -53,6 +53,8 @@ impl Response<'_> {
                 GetRequestMethod::CalendarEventNotification(request) => {
                     request.resolve_references(self)?
                 }
+                GetRequestMethod::TaskList(request) => request.resolve_references(self)?,
+                GetRequestMethod::Task(request) => request.resolve_references(self)?,
                 GetRequestMethod::ParticipantIdentity(request) => {
                     request.resolve_references(self)?
                 }
@@ -96,6 +98,10 @@ impl Response<'_> {
                 SetRequestMethod::CalendarEventNotification(request) => {
                     request.resolve_references(self, 1, false)?
                 }
+                SetRequestMethod::TaskList(request) => {
+                    request.resolve_references(self, 1, false)?
+                }
+                SetRequestMethod::Task(request) => request.resolve_references(self, 1, false)?,
                 SetRequestMethod::ParticipantIdentity(request) => {
                     request.resolve_references(self, 1, false)?
                 }
@@ -93,6 +93,12 @@ pub enum Capability {
     Stalwart = 1 << 17,
     #[serde(rename(serialize = "urn:ietf:params:jmap:webpush-vapid"))]
     WebPushVapid = 1 << 18,
+    #[serde(rename(serialize = "urn:ietf:params:jmap:mdn"))]
+    Mdn = 1 << 19,
+    #[serde(rename(serialize = "urn:ietf:params:jmap:smimeverify"))]
+    SmimeVerify = 1 << 20,
+    #[serde(rename(serialize = "urn:ietf:params:jmap:tasks"))]
+    Tasks = 1 << 21,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -331,6 +337,9 @@ impl Capability {
             Capability::MailShare => "urn:ietf:params:jmap:mail:share",
             Capability::Stalwart => "urn:stalwart:jmap",
             Capability::WebPushVapid => "urn:ietf:params:jmap:webpush-vapid",
+            Capability::Mdn => "urn:ietf:params:jmap:mdn",
+            Capability::SmimeVerify => "urn:ietf:params:jmap:smimeverify",
+            Capability::Tasks => "urn:ietf:params:jmap:tasks",
         }
     }
 
@@ -354,6 +363,9 @@ impl Capability {
             Capability::MailShare,
             Capability::Stalwart,
             Capability::WebPushVapid,
+            Capability::Mdn,
+            Capability::SmimeVerify,
+            Capability::Tasks,
         ]
     }
 }
@@ -475,6 +487,9 @@ impl Capability {
             "urn:ietf:params:jmap:mail:share" => Capability::MailShare,
             "urn:stalwart:jmap" => Capability::Stalwart,
             "urn:ietf:params:jmap:webpush-vapid" => Capability::WebPushVapid,
+            "urn:ietf:params:jmap:mdn" => Capability::Mdn,
+            "urn:ietf:params:jmap:smimeverify" => Capability::SmimeVerify,
+            "urn:ietf:params:jmap:tasks" => Capability::Tasks,
         )
     }
 }
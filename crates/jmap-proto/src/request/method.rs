@@ -35,11 +35,14 @@ pub enum MethodObject {
     Calendar,
     CalendarEvent,
     CalendarEventNotification,
+    TaskList,
+    Task,
     AddressBook,
     ContactCard,
     FileNode,
     ParticipantIdentity,
     ShareNotification,
+    Mdn,
     Registry(ObjectType),
 }
 
@@ -61,8 +64,10 @@ impl MethodObject {
             | MethodObject::CalendarEvent
             | MethodObject::CalendarEventNotification
             | MethodObject::ParticipantIdentity => Capability::Calendars,
+            MethodObject::TaskList | MethodObject::Task => Capability::Tasks,
             MethodObject::AddressBook | MethodObject::ContactCard => Capability::Contacts,
             MethodObject::FileNode => Capability::FileNode,
+            MethodObject::Mdn => Capability::Mdn,
             MethodObject::Registry(_) => Capability::Stalwart,
         }
     }
@@ -83,6 +88,7 @@ pub enum MethodFunction {
     Upload,
     Echo,
     GetAvailability,
+    Send,
 }
 
 impl Display for MethodName {
@@ -212,6 +218,15 @@ impl MethodName {
             (MethodFunction::Copy, MethodObject::CalendarEvent) => "CalendarEvent/copy",
             (MethodFunction::Parse, MethodObject::CalendarEvent) => "CalendarEvent/parse",
 
+            (MethodFunction::Get, MethodObject::TaskList) => "TaskList/get",
+            (MethodFunction::Changes, MethodObject::TaskList) => "TaskList/changes",
+            (MethodFunction::Set, MethodObject::TaskList) => "TaskList/set",
+
+            (MethodFunction::Get, MethodObject::Task) => "Task/get",
+            (MethodFunction::Changes, MethodObject::Task) => "Task/changes",
+            (MethodFunction::Query, MethodObject::Task) => "Task/query",
+            (MethodFunction::Set, MethodObject::Task) => "Task/set",
+
             (MethodFunction::Get, MethodObject::CalendarEventNotification) => {
                 "CalendarEventNotification/get"
             }
@@ -234,6 +249,9 @@ impl MethodName {
             }
             (MethodFunction::Set, MethodObject::ParticipantIdentity) => "ParticipantIdentity/set",
 
+            (MethodFunction::Send, MethodObject::Mdn) => "MDN/send",
+            (MethodFunction::Parse, MethodObject::Mdn) => "MDN/parse",
+
             (MethodFunction::Echo, MethodObject::Core) => "Core/echo",
             (method, MethodObject::Registry(obj)) => {
                 return Cow::Owned(format!("x:{}/{}", obj.as_str(), method.as_str()));
@@ -342,6 +360,15 @@ impl MethodName {
             "CalendarEvent/copy" => (MethodObject::CalendarEvent, MethodFunction::Copy),
             "CalendarEvent/parse" => (MethodObject::CalendarEvent, MethodFunction::Parse),
 
+            "TaskList/get" => (MethodObject::TaskList, MethodFunction::Get),
+            "TaskList/changes" => (MethodObject::TaskList, MethodFunction::Changes),
+            "TaskList/set" => (MethodObject::TaskList, MethodFunction::Set),
+
+            "Task/get" => (MethodObject::Task, MethodFunction::Get),
+            "Task/changes" => (MethodObject::Task, MethodFunction::Changes),
+            "Task/query" => (MethodObject::Task, MethodFunction::Query),
+            "Task/set" => (MethodObject::Task, MethodFunction::Set),
+
             "CalendarEventNotification/get" => (MethodObject::CalendarEventNotification, MethodFunction::Get),
             "CalendarEventNotification/changes" => (MethodObject::CalendarEventNotification, MethodFunction::Changes),
             "CalendarEventNotification/set" => (MethodObject::CalendarEventNotification, MethodFunction::Set),
@@ -352,6 +379,9 @@ impl MethodName {
             "ParticipantIdentity/changes" => (MethodObject::ParticipantIdentity, MethodFunction::Changes),
             "ParticipantIdentity/set" => (MethodObject::ParticipantIdentity, MethodFunction::Set),
 
+            "MDN/send" => (MethodObject::Mdn, MethodFunction::Send),
+            "MDN/parse" => (MethodObject::Mdn, MethodFunction::Parse),
+
             "Core/echo" => (MethodObject::Core, MethodFunction::Echo),
 
         ).or_else(|| {
@@ -395,7 +425,10 @@ impl Display for MethodObject {
             MethodObject::Calendar => "Calendar",
             MethodObject::CalendarEvent => "CalendarEvent",
             MethodObject::CalendarEventNotification => "CalendarEventNotification",
+            MethodObject::TaskList => "TaskList",
+            MethodObject::Task => "Task",
             MethodObject::ShareNotification => "ShareNotification",
+            MethodObject::Mdn => "MDN",
             MethodObject::Registry(obj) => {
                 f.write_str("x:")?;
                 return f.write_str(obj.as_str());
@@ -420,6 +453,7 @@ impl MethodFunction {
             MethodFunction::Upload => "upload",
             MethodFunction::Echo => "echo",
             MethodFunction::GetAvailability => "getAvailability",
+            MethodFunction::Send => "send",
         }
     }
 }
@@ -20,6 +20,7 @@ use crate::{
         get::GetRequest,
         import::ImportEmailRequest,
         lookup::BlobLookupRequest,
+        mdn::{MdnParseRequest, MdnSendRequest},
         parse::ParseRequest,
         query::QueryRequest,
         query_changes::QueryChangesRequest,
@@ -34,8 +35,8 @@ use crate::{
         contact::ContactCard, email::Email, email_submission::EmailSubmission, file_node::FileNode,
         identity::Identity, mailbox::Mailbox, participant_identity::ParticipantIdentity,
         principal::Principal, push_subscription::PushSubscription, quota::Quota,
-        registry::Registry, share_notification::ShareNotification, sieve::Sieve, thread::Thread,
-        vacation_response::VacationResponse,
+        registry::Registry, share_notification::ShareNotification, sieve::Sieve, task::Task,
+        task_list::TaskList, thread::Thread, vacation_response::VacationResponse,
     },
     request::{capability::CapabilityIds, reference::MaybeIdReference},
 };
@@ -82,6 +83,8 @@ pub enum RequestMethod<'x> {
     QueryChanges(QueryChangesRequestMethod),
     SearchSnippet(Box<GetSearchSnippetRequest>),
     ValidateScript(Box<ValidateSieveScriptRequest>),
+    SendMdn(Box<MdnSendRequest<'x>>),
+    ParseMdn(Box<MdnParseRequest>),
     LookupBlob(Box<BlobLookupRequest>),
     UploadBlob(Box<BlobUploadRequest>),
     Echo(Value<'x, Null, Null>),
@@ -108,6 +111,8 @@ pub enum GetRequestMethod {
     Calendar(Box<GetRequest<Calendar>>),
     CalendarEvent(Box<GetRequest<CalendarEvent>>),
     CalendarEventNotification(Box<GetRequest<CalendarEventNotification>>),
+    TaskList(Box<GetRequest<TaskList>>),
+    Task(Box<GetRequest<Task>>),
     ParticipantIdentity(Box<GetRequest<ParticipantIdentity>>),
     ShareNotification(Box<GetRequest<ShareNotification>>),
     Registry(Box<GetRequest<Registry>>),
@@ -129,6 +134,8 @@ pub enum SetRequestMethod<'x> {
     Calendar(Box<SetRequest<'x, Calendar>>),
     CalendarEvent(Box<SetRequest<'x, CalendarEvent>>),
     CalendarEventNotification(Box<SetRequest<'x, CalendarEventNotification>>),
+    TaskList(Box<SetRequest<'x, TaskList>>),
+    Task(Box<SetRequest<'x, Task>>),
     ParticipantIdentity(Box<SetRequest<'x, ParticipantIdentity>>),
     Registry(Box<SetRequest<'x, Registry>>),
 }
@@ -156,6 +163,7 @@ pub enum QueryRequestMethod {
     Calendar(Box<QueryRequest<Calendar>>),
     CalendarEvent(Box<QueryRequest<CalendarEvent>>),
     CalendarEventNotification(Box<QueryRequest<CalendarEventNotification>>),
+    Task(Box<QueryRequest<Task>>),
     ShareNotification(Box<QueryRequest<ShareNotification>>),
     Registry(Box<QueryRequest<Registry>>),
 }
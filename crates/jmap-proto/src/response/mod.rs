@@ -17,6 +17,7 @@ use crate::{
         get::GetResponse,
         import::ImportEmailResponse,
         lookup::BlobLookupResponse,
+        mdn::{MdnParseResponse, MdnSendResponse},
         parse::ParseResponse,
         query::QueryResponse,
         query_changes::QueryChangesResponse,
@@ -47,6 +48,8 @@ use crate::{
         registry::Registry,
         share_notification::ShareNotification,
         sieve::Sieve,
+        task::Task,
+        task_list::TaskList,
         thread::Thread,
         vacation_response::VacationResponse,
     },
@@ -68,6 +71,8 @@ pub enum ResponseMethod<'x> {
     Query(QueryResponse),
     SearchSnippet(GetSearchSnippetResponse),
     ValidateScript(ValidateSieveScriptResponse),
+    SendMdn(MdnSendResponse),
+    ParseMdn(MdnParseResponse),
     LookupBlob(BlobLookupResponse),
     UploadBlob(BlobUploadResponse),
     Echo(Value<'x, Null, Null>),
@@ -95,6 +100,8 @@ pub enum GetResponseMethod {
     Calendar(GetResponse<Calendar>),
     CalendarEvent(GetResponse<CalendarEvent>),
     CalendarEventNotification(CalendarEventNotificationGetResponse),
+    TaskList(GetResponse<TaskList>),
+    Task(GetResponse<Task>),
     ParticipantIdentity(GetResponse<ParticipantIdentity>),
     ShareNotification(GetResponse<ShareNotification>),
     Registry(GetResponse<Registry>),
@@ -117,6 +124,8 @@ pub enum SetResponseMethod {
     Calendar(Box<SetResponse<Calendar>>),
     CalendarEvent(Box<SetResponse<CalendarEvent>>),
     CalendarEventNotification(Box<SetResponse<CalendarEventNotification>>),
+    TaskList(Box<SetResponse<TaskList>>),
+    Task(Box<SetResponse<Task>>),
     ParticipantIdentity(Box<SetResponse<ParticipantIdentity>>),
     Registry(Box<SetResponse<Registry>>),
 }
@@ -136,6 +145,8 @@ pub enum ChangesResponseMethod {
     Calendar(Box<ChangesResponse<Calendar>>),
     CalendarEvent(Box<ChangesResponse<CalendarEvent>>),
     CalendarEventNotification(Box<ChangesResponse<CalendarEventNotification>>),
+    TaskList(Box<ChangesResponse<TaskList>>),
+    Task(Box<ChangesResponse<Task>>),
     ShareNotification(Box<ChangesResponse<ShareNotification>>),
 }
 
@@ -467,6 +478,18 @@ impl<'x> From<ValidateSieveScriptResponse> for ResponseMethod<'x> {
     }
 }
 
+impl<'x> From<MdnSendResponse> for ResponseMethod<'x> {
+    fn from(value: MdnSendResponse) -> Self {
+        ResponseMethod::SendMdn(value)
+    }
+}
+
+impl<'x> From<MdnParseResponse> for ResponseMethod<'x> {
+    fn from(value: MdnParseResponse) -> Self {
+        ResponseMethod::ParseMdn(value)
+    }
+}
+
 impl<'x> From<BlobLookupResponse> for ResponseMethod<'x> {
     fn from(value: BlobLookupResponse) -> Self {
         ResponseMethod::LookupBlob(value)
@@ -547,6 +570,30 @@ impl From<SetResponse<CalendarEvent>> for ResponseMethod<'_> {
     }
 }
 
+impl From<GetResponse<TaskList>> for ResponseMethod<'_> {
+    fn from(response: GetResponse<TaskList>) -> Self {
+        ResponseMethod::Get(GetResponseMethod::TaskList(response))
+    }
+}
+
+impl From<SetResponse<TaskList>> for ResponseMethod<'_> {
+    fn from(response: SetResponse<TaskList>) -> Self {
+        ResponseMethod::Set(SetResponseMethod::TaskList(Box::new(response)))
+    }
+}
+
+impl From<GetResponse<Task>> for ResponseMethod<'_> {
+    fn from(response: GetResponse<Task>) -> Self {
+        ResponseMethod::Get(GetResponseMethod::Task(response))
+    }
+}
+
+impl From<SetResponse<Task>> for ResponseMethod<'_> {
+    fn from(response: SetResponse<Task>) -> Self {
+        ResponseMethod::Set(SetResponseMethod::Task(Box::new(response)))
+    }
+}
+
 impl From<SetResponse<ParticipantIdentity>> for ResponseMethod<'_> {
     fn from(response: SetResponse<ParticipantIdentity>) -> Self {
         ResponseMethod::Set(SetResponseMethod::ParticipantIdentity(Box::new(response)))
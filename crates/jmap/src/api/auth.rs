@@ -84,6 +84,8 @@ impl JmapAuthorization for AccessToken {
                     Permission::JmapCalendarEventNotificationGet
                 }
                 GetRequestMethod::ParticipantIdentity(_) => Permission::JmapParticipantIdentityGet,
+                GetRequestMethod::TaskList(_) => Permission::JmapTaskListGet,
+                GetRequestMethod::Task(_) => Permission::JmapTaskGet,
                 GetRequestMethod::ShareNotification(_) => Permission::JmapShareNotificationGet,
                 GetRequestMethod::Registry(_) => {
                     let MethodObject::Registry(object_type) = object else {
@@ -199,6 +201,20 @@ impl JmapAuthorization for AccessToken {
                         Permission::JmapParticipantIdentityUpdate,
                         Permission::JmapParticipantIdentityDestroy,
                     ),
+                    SetRequestMethod::TaskList(s) => validate_set(
+                        s,
+                        self,
+                        Permission::JmapTaskListCreate,
+                        Permission::JmapTaskListUpdate,
+                        Permission::JmapTaskListDestroy,
+                    ),
+                    SetRequestMethod::Task(s) => validate_set(
+                        s,
+                        self,
+                        Permission::JmapTaskCreate,
+                        Permission::JmapTaskUpdate,
+                        Permission::JmapTaskDestroy,
+                    ),
                     SetRequestMethod::Registry(s) => {
                         let MethodObject::Registry(object_type) = object else {
                             unreachable!()
@@ -229,6 +245,8 @@ impl JmapAuthorization for AccessToken {
                     Permission::JmapCalendarEventNotificationChanges
                 }
                 MethodObject::ParticipantIdentity => Permission::JmapParticipantIdentityChanges,
+                MethodObject::TaskList => Permission::JmapTaskListChanges,
+                MethodObject::Task => Permission::JmapTaskChanges,
                 MethodObject::ShareNotification => Permission::JmapShareNotificationChanges,
                 MethodObject::Principal => Permission::JmapPrincipalChanges,
                 MethodObject::AddressBook => Permission::JmapAddressBookChanges,
@@ -238,6 +256,7 @@ impl JmapAuthorization for AccessToken {
                 | MethodObject::SearchSnippet
                 | MethodObject::VacationResponse
                 | MethodObject::SieveScript
+                | MethodObject::Mdn
                 | MethodObject::Registry(_) => Permission::JmapEmailChanges,
             },
             RequestMethod::Copy(m) => match &m {
@@ -287,6 +306,7 @@ impl JmapAuthorization for AccessToken {
                 QueryRequestMethod::FileNode(_) => Permission::JmapFileNodeQuery,
                 QueryRequestMethod::Calendar(_) => Permission::JmapCalendarGet,
                 QueryRequestMethod::CalendarEvent(_) => Permission::JmapCalendarEventQuery,
+                QueryRequestMethod::Task(_) => Permission::JmapTaskQuery,
                 QueryRequestMethod::CalendarEventNotification(_) => {
                     Permission::JmapCalendarEventNotificationQuery
                 }
@@ -300,6 +320,8 @@ impl JmapAuthorization for AccessToken {
             },
             RequestMethod::SearchSnippet(_) => Permission::JmapSearchSnippetGet,
             RequestMethod::ValidateScript(_) => Permission::JmapSieveScriptValidate,
+            RequestMethod::SendMdn(_) => Permission::JmapMdnSend,
+            RequestMethod::ParseMdn(_) => Permission::JmapMdnParse,
             RequestMethod::LookupBlob(_) => Permission::JmapBlobLookup,
             RequestMethod::UploadBlob(_) => Permission::JmapBlobUpload,
             RequestMethod::Echo(_) => Permission::JmapCoreEcho,
@@ -29,6 +29,7 @@ use crate::{
     file::{copy::FileNodeCopy, get::FileNodeGet, query::FileNodeQuery, set::FileNodeSet},
     identity::{get::IdentityGet, set::IdentitySet},
     mailbox::{get::MailboxGet, query::MailboxQuery, set::MailboxSet},
+    mdn::{parse::MdnParse, send::MdnSend},
     participant_identity::{get::ParticipantIdentityGet, set::ParticipantIdentitySet},
     principal::{availability::PrincipalGetAvailability, get::PrincipalGet, query::PrincipalQuery},
     push::{get::PushSubscriptionFetch, set::PushSubscriptionSet},
@@ -42,6 +43,8 @@ use crate::{
         validate::SieveScriptValidate,
     },
     submission::{get::EmailSubmissionGet, query::EmailSubmissionQuery, set::EmailSubmissionSet},
+    task::{get::TaskGet, query::TaskQuery, set::TaskSet},
+    task_list::{get::TaskListGet, set::TaskListSet},
     thread::get::ThreadGet,
     vacation::{get::VacationResponseGet, set::VacationResponseSet},
 };
@@ -183,6 +186,12 @@ impl RequestHandler for Server {
                                     SetResponseMethod::ParticipantIdentity(set_response) => {
                                         set_response.update_created_ids(&mut response);
                                     }
+                                    SetResponseMethod::TaskList(set_response) => {
+                                        set_response.update_created_ids(&mut response);
+                                    }
+                                    SetResponseMethod::Task(set_response) => {
+                                        set_response.update_created_ids(&mut response);
+                                    }
                                     SetResponseMethod::CalendarEventNotification(_) => {}
                                     SetResponseMethod::Registry(set_response) => {
                                         set_response.update_created_ids(&mut response);
@@ -345,6 +354,18 @@ impl RequestHandler for Server {
 
                     self.calendar_event_get(*req, access_token).await?.into()
                 }
+                GetRequestMethod::TaskList(mut req) => {
+                    resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                    access_token.assert_has_access(req.account_id, Collection::Calendar)?;
+
+                    self.task_list_get(*req, access_token).await?.into()
+                }
+                GetRequestMethod::Task(mut req) => {
+                    resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                    access_token.assert_has_access(req.account_id, Collection::CalendarEvent)?;
+
+                    self.task_get(*req, access_token).await?.into()
+                }
                 GetRequestMethod::CalendarEventNotification(mut req) => {
                     resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                     access_token.assert_is_member(req.account_id)?;
@@ -442,6 +463,12 @@ impl RequestHandler for Server {
 
                     self.calendar_event_query(*req, access_token).await?.into()
                 }
+                QueryRequestMethod::Task(mut req) => {
+                    resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                    access_token.assert_has_access(req.account_id, Collection::CalendarEvent)?;
+
+                    self.task_query(*req, access_token).await?.into()
+                }
                 QueryRequestMethod::CalendarEventNotification(mut req) => {
                     resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                     access_token.assert_is_member(req.account_id)?;
@@ -558,6 +585,20 @@ impl RequestHandler for Server {
                         .await?
                         .into()
                 }
+                SetRequestMethod::TaskList(mut req) => {
+                    resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                    access_token.assert_has_access(req.account_id, Collection::Calendar)?;
+
+                    self.task_list_set(*req, access_token, session)
+                        .await?
+                        .into()
+                }
+                SetRequestMethod::Task(mut req) => {
+                    resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                    access_token.assert_has_access(req.account_id, Collection::CalendarEvent)?;
+
+                    self.task_set(*req, access_token, session).await?.into()
+                }
                 SetRequestMethod::CalendarEventNotification(mut req) => {
                     resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                     access_token.assert_is_member(req.account_id)?;
@@ -580,7 +621,7 @@ impl RequestHandler for Server {
                         method_name.obj.unwrap_registry(),
                         *req,
                         access_token,
-                        session,
+                        session.remote_ip,
                     ))
                     .await?
                     .into()
@@ -688,6 +729,20 @@ impl RequestHandler for Server {
 
                 self.sieve_script_validate(*req, access_token).await?.into()
             }
+            RequestMethod::SendMdn(mut req) => {
+                resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                access_token.assert_is_member(req.account_id)?;
+
+                self.mdn_send(*req, &session.instance, next_call)
+                    .await?
+                    .into()
+            }
+            RequestMethod::ParseMdn(mut req) => {
+                resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
+                access_token.assert_has_access(req.account_id, Collection::Email)?;
+
+                self.mdn_parse(*req, access_token).await?.into()
+            }
             RequestMethod::LookupBlob(mut req) => {
                 resolve_account_id(&mut req.account_id, method_name.obj, access_token)?;
                 access_token.assert_is_member(req.account_id)?;
@@ -108,11 +108,14 @@ impl AccountCapabilities for AccessToken {
                 let permission = match capability {
                     Capability::Mail | Capability::MailShare => Permission::JmapEmailGet,
                     Capability::Submission => Permission::JmapEmailSubmissionCreate,
+                    Capability::Mdn => Permission::JmapMdnSend,
+                    Capability::SmimeVerify => Permission::JmapEmailGet,
                     Capability::VacationResponse => Permission::JmapVacationResponseGet,
                     Capability::Contacts => Permission::JmapContactCardGet,
                     Capability::ContactsParse => Permission::JmapContactCardParse,
                     Capability::Calendars => Permission::JmapCalendarEventGet,
                     Capability::CalendarsParse => Permission::JmapCalendarEventParse,
+                    Capability::Tasks => Permission::JmapTaskGet,
                     Capability::Sieve => Permission::JmapSieveScriptGet,
                     Capability::Blob => Permission::JmapBlobGet,
                     Capability::Quota => Permission::JmapQuotaGet,
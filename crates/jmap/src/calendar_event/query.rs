@@ -407,7 +407,7 @@ impl CalendarEventQuery for Server {
     }
 }
 
-fn local_timestamp(dt: &JSCalendarDateTime, tz: Tz) -> Option<i64> {
+pub(crate) fn local_timestamp(dt: &JSCalendarDateTime, tz: Tz) -> Option<i64> {
     tz.from_local_datetime(&dt.to_naive_date_time()?)
         .single()
         .map(|dt| dt.timestamp())
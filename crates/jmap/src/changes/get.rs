@@ -99,6 +99,16 @@ impl ChangesLookup for Server {
 
                 (SyncCollection::Calendar, false)
             }
+            MethodObject::TaskList => {
+                access_token.assert_has_access(request.account_id, Collection::Calendar)?;
+
+                (SyncCollection::Calendar, true)
+            }
+            MethodObject::Task => {
+                access_token.assert_has_access(request.account_id, Collection::CalendarEvent)?;
+
+                (SyncCollection::Calendar, false)
+            }
             MethodObject::CalendarEventNotification => {
                 access_token.assert_is_member(request.account_id)?;
 
@@ -131,8 +141,28 @@ impl ChangesLookup for Server {
             updated_properties: None,
         };
         let account_id = request.account_id.document_id();
+        let is_member = access_token.is_member(account_id);
+        let is_task = matches!(object, MethodObject::TaskList | MethodObject::Task);
 
-        let allowed_ids: Option<RoaringBitmap> = if access_token.is_member(account_id) {
+        let allowed_ids: Option<RoaringBitmap> = if is_task {
+            // Tasks share the calendar change log, only report task lists and VTODO resources
+            let cache = self
+                .fetch_dav_resources(
+                    access_token.account_id(),
+                    account_id,
+                    SyncCollection::Calendar,
+                )
+                .await?;
+            let mut task_ids = cache.task_ids(is_container).collect::<RoaringBitmap>();
+            if !is_member {
+                task_ids &= if is_container {
+                    cache.shared_containers(access_token, [Acl::Read, Acl::ReadItems], true)
+                } else {
+                    cache.shared_items(access_token, [Acl::ReadItems], true)
+                };
+            }
+            Some(task_ids)
+        } else if is_member {
             None
         } else {
             Some(match object {
@@ -300,14 +330,18 @@ impl ChangesLookup for Server {
                     || (!is_container && change.is_item_change())
             })
             .filter(|change| {
-                allowed_ids.as_ref().is_none_or(|allowed| {
-                    let id = if is_container {
-                        change.container_id()
-                    } else {
-                        change.item_id()
-                    };
-                    id.is_some_and(|id| allowed.contains(id as u32))
-                })
+                // Destroyed tasks are no longer cached, so they cannot be told apart from events
+                (is_task
+                    && is_member
+                    && matches!(change, Change::DeleteContainer(_) | Change::DeleteItem(_)))
+                    || allowed_ids.as_ref().is_none_or(|allowed| {
+                        let id = if is_container {
+                            change.container_id()
+                        } else {
+                            change.item_id()
+                        };
+                        id.is_some_and(|id| allowed.contains(id as u32))
+                    })
             })
             .skip(items_sent)
             .peekable();
@@ -401,6 +435,10 @@ impl IntermediateChangesResponse {
             MethodObject::CalendarEventNotification => {
                 ChangesResponseMethod::CalendarEventNotification(transmute_response(self.response))
             }
+            MethodObject::TaskList => {
+                ChangesResponseMethod::TaskList(transmute_response(self.response))
+            }
+            MethodObject::Task => ChangesResponseMethod::Task(transmute_response(self.response)),
             MethodObject::ShareNotification => {
                 ChangesResponseMethod::ShareNotification(transmute_response(self.response))
             }
@@ -413,6 +451,7 @@ impl IntermediateChangesResponse {
             | MethodObject::SieveScript
             | MethodObject::Principal
             | MethodObject::Quota
+            | MethodObject::Mdn
             | MethodObject::Registry(_) => unreachable!(),
         })
     }
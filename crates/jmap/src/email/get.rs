@@ -12,9 +12,12 @@ use crate::{changes::state::JmapCacheState, email::headers::HeaderToValue};
 use common::{Server, auth::AccessToken};
 use email::{
     cache::{MessageCacheFetch, email::MessageCacheAccess},
-    message::metadata::{
-        ArchivedMetadataPartType, MESSAGE_HAS_ATTACHMENT, MESSAGE_RECEIVED_MASK, MessageMetadata,
-        MetadataHeaderName, PART_ENCODING_PROBLEM,
+    message::{
+        metadata::{
+            ArchivedMetadataPartType, MESSAGE_HAS_ATTACHMENT, MESSAGE_RECEIVED_MASK,
+            MessageMetadata, MetadataHeaderName, PART_ENCODING_PROBLEM, SmimeVerification,
+        },
+        smime::verify_smime,
     },
 };
 use jmap_proto::{
@@ -28,7 +31,7 @@ use mail_parser::HeaderValue;
 use std::future::Future;
 use store::{
     ValueKey,
-    write::{AlignedBytes, Archive},
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, now},
 };
 use trc::{AddContext, StoreEvent};
 use types::{
@@ -149,6 +152,15 @@ impl EmailGet for Server {
                 break;
             }
         }
+        let needs_smime = properties.iter().any(|property| {
+            matches!(
+                property,
+                EmailProperty::SmimeStatus
+                    | EmailProperty::SmimeErrors
+                    | EmailProperty::SmimeVerifiedAt
+            )
+        });
+        let mut smime_batch = BatchBuilder::new();
 
         for id in ids {
             // Obtain the email object
@@ -185,16 +197,43 @@ impl EmailGet for Server {
                 }
             };
 
+            // Obtain cached S/MIME verification results
+            let mut smime = None;
+            let mut verify_signature = false;
+            if needs_smime && metadata.root_part().is_smime_signed() {
+                smime = self
+                    .store()
+                    .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                        account_id,
+                        Collection::Email,
+                        id.document_id(),
+                        EmailField::SmimeVerification,
+                    ))
+                    .await?
+                    .map(|archive| {
+                        archive
+                            .unarchive::<SmimeVerification>()
+                            .map(|verification| verification.to_native())
+                    })
+                    .transpose()
+                    .caused_by(trc::location!())?
+                    .filter(|verification| {
+                        verification.verified_at + self.core.jmap.smime_cache_ttl > now()
+                    });
+                verify_signature = smime.is_none();
+            }
+
             // Retrieve raw message if needed
             let blob_hash = BlobHash::from(&metadata.blob_hash);
-            let raw_body;
+            let raw_body = if needs_body || verify_signature {
+                self.blob_store()
+                    .get_blob(blob_hash.as_slice(), 0..usize::MAX)
+                    .await?
+            } else {
+                None
+            };
             let mut raw_message = ChainedBytes::new(metadata.raw_headers.as_ref());
             if needs_body {
-                raw_body = self
-                    .blob_store()
-                    .get_blob(blob_hash.as_slice(), 0..usize::MAX)
-                    .await?;
-
                 if let Some(raw_body) = &raw_body {
                     raw_message.append(
                         raw_body
@@ -216,6 +255,27 @@ impl EmailGet for Server {
                     continue;
                 }
             }
+
+            // Verify the S/MIME signature and cache the result
+            if verify_signature
+                && let Some(raw_body) = &raw_body
+                && let Some(verification) =
+                    verify_smime(raw_body, &self.core.jmap.smime_trust_anchors, now())
+            {
+                smime_batch
+                    .with_account_id(account_id)
+                    .with_collection(Collection::Email)
+                    .with_document(id.document_id())
+                    .set(
+                        EmailField::SmimeVerification,
+                        Archiver::new(verification.clone())
+                            .serialize()
+                            .caused_by(trc::location!())?,
+                    )
+                    .log_unversioned_change();
+                smime = Some(verification);
+            }
+
             let blob_id = BlobId {
                 hash: blob_hash,
                 class: BlobClass::Linked {
@@ -282,6 +342,41 @@ impl EmailGet for Server {
                             );
                         }
                     }
+                    EmailProperty::SmimeStatus => {
+                        email.insert_unchecked(
+                            EmailProperty::SmimeStatus,
+                            smime
+                                .as_ref()
+                                .map_or(Value::Null, |smime| smime.status.as_str().into()),
+                        );
+                    }
+                    EmailProperty::SmimeErrors => {
+                        email.insert_unchecked(
+                            EmailProperty::SmimeErrors,
+                            smime
+                                .as_ref()
+                                .filter(|smime| !smime.errors.is_empty())
+                                .map_or(Value::Null, |smime| {
+                                    Value::Array(
+                                        smime
+                                            .errors
+                                            .iter()
+                                            .map(|err| err.to_string().into())
+                                            .collect(),
+                                    )
+                                }),
+                        );
+                    }
+                    EmailProperty::SmimeVerifiedAt => {
+                        email.insert_unchecked(
+                            EmailProperty::SmimeVerifiedAt,
+                            smime.as_ref().map_or(Value::Null, |smime| {
+                                Value::Element(EmailValue::Date(UTCDate::from_timestamp(
+                                    smime.verified_at as i64,
+                                )))
+                            }),
+                        );
+                    }
                     EmailProperty::HasAttachment => {
                         email.insert_unchecked(
                             EmailProperty::HasAttachment,
@@ -446,6 +541,12 @@ impl EmailGet for Server {
             response.list.push(email.into());
         }
 
+        if !smime_batch.is_empty() {
+            self.commit_batch(smime_batch)
+                .await
+                .caused_by(trc::location!())?;
+        }
+
         Ok(response)
     }
 }
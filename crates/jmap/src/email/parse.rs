@@ -10,11 +10,12 @@ use super::{
 };
 use crate::blob::download::BlobDownload;
 use common::{Server, auth::AccessToken};
-use email::message::index::PREVIEW_LENGTH;
+use email::message::{index::PREVIEW_LENGTH, smime::verify_smime};
 use jmap_proto::{
     method::parse::{ParseRequest, ParseResponse},
-    object::email::{Email, EmailProperty},
+    object::email::{Email, EmailProperty, EmailValue},
     request::{IntoValid, MaybeInvalid, reference::MaybeIdReference},
+    types::date::UTCDate,
 };
 use jmap_tools::{Key, Map, Value};
 use mail_parser::{
@@ -22,6 +23,7 @@ use mail_parser::{
     parsers::preview::preview_text,
 };
 use std::future::Future;
+use store::write::now;
 use utils::{chained_bytes::ChainedBytes, map::vec_map::VecMap};
 
 pub trait EmailParse: Sync + Send {
@@ -127,6 +129,18 @@ impl EmailParse for Server {
                     continue;
                 }
             };
+            let smime = properties
+                .iter()
+                .any(|property| {
+                    matches!(
+                        property,
+                        EmailProperty::SmimeStatus
+                            | EmailProperty::SmimeErrors
+                            | EmailProperty::SmimeVerifiedAt
+                    )
+                })
+                .then(|| verify_smime(&raw_message, &self.core.jmap.smime_trust_anchors, now()))
+                .flatten();
             let raw_message = ChainedBytes::new(&raw_message);
 
             // Prepare response
@@ -269,6 +283,41 @@ impl EmailParse for Server {
                         }
                         email.insert_unchecked(EmailProperty::BodyValues, body_values);
                     }
+                    EmailProperty::SmimeStatus => {
+                        email.insert_unchecked(
+                            EmailProperty::SmimeStatus,
+                            smime
+                                .as_ref()
+                                .map_or(Value::Null, |smime| smime.status.as_str().into()),
+                        );
+                    }
+                    EmailProperty::SmimeErrors => {
+                        email.insert_unchecked(
+                            EmailProperty::SmimeErrors,
+                            smime
+                                .as_ref()
+                                .filter(|smime| !smime.errors.is_empty())
+                                .map_or(Value::Null, |smime| {
+                                    Value::Array(
+                                        smime
+                                            .errors
+                                            .iter()
+                                            .map(|err| err.to_string().into())
+                                            .collect(),
+                                    )
+                                }),
+                        );
+                    }
+                    EmailProperty::SmimeVerifiedAt => {
+                        email.insert_unchecked(
+                            EmailProperty::SmimeVerifiedAt,
+                            smime.as_ref().map_or(Value::Null, |smime| {
+                                Value::Element(EmailValue::Date(UTCDate::from_timestamp(
+                                    smime.verified_at as i64,
+                                )))
+                            }),
+                        );
+                    }
                     EmailProperty::Id
                     | EmailProperty::ThreadId
                     | EmailProperty::Keywords
@@ -0,0 +1,135 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::blob::download::BlobDownload;
+use common::{Server, auth::AccessToken};
+use jmap_proto::{
+    method::mdn::{MdnParseRequest, MdnParseResponse},
+    object::mdn::{ActionMode, Disposition, DispositionType, Mdn, SendingMode},
+    request::MaybeInvalid,
+};
+use mail_parser::{MessageParser, MimeHeaders};
+use std::future::Future;
+use utils::map::vec_map::VecMap;
+
+pub trait MdnParse: Sync + Send {
+    fn mdn_parse(
+        &self,
+        request: MdnParseRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<MdnParseResponse>> + Send;
+}
+
+impl MdnParse for Server {
+    async fn mdn_parse(
+        &self,
+        request: MdnParseRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<MdnParseResponse> {
+        if request.blob_ids.len() > self.core.jmap.mail_parse_max_items {
+            return Err(trc::JmapEvent::RequestTooLarge.into_err());
+        }
+
+        let mut response = MdnParseResponse {
+            account_id: request.account_id,
+            parsed: VecMap::with_capacity(request.blob_ids.len()),
+            not_parsable: vec![],
+            not_found: vec![],
+        };
+
+        for blob_id in request.blob_ids {
+            let blob_id = match blob_id {
+                MaybeInvalid::Value(blob_id) => blob_id,
+                invalid => {
+                    response.not_found.push(invalid);
+                    continue;
+                }
+            };
+            let Some(raw_message) = self.blob_download(&blob_id, access_token).await? else {
+                response.not_found.push(MaybeInvalid::Value(blob_id));
+                continue;
+            };
+
+            match parse_mdn(&raw_message) {
+                Some(mdn) => {
+                    response.parsed.append(blob_id, mdn);
+                }
+                None => {
+                    response.not_parsable.push(blob_id);
+                }
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+/// Parses a message containing a message/disposition-notification part (RFC 8098).
+fn parse_mdn(raw_message: &[u8]) -> Option<Mdn> {
+    let message = MessageParser::new().parse(raw_message)?;
+    let part = message
+        .parts
+        .iter()
+        .find(|part| part.is_content_type("message", "disposition-notification"))?;
+    let fields = raw_message.get(part.offset_body as usize..part.offset_end as usize)?;
+    let fields = String::from_utf8_lossy(fields);
+
+    let mut mdn = Mdn {
+        subject: message.subject().map(Into::into),
+        text_body: message.body_text(0).map(|text| text.into_owned()),
+        ..Default::default()
+    };
+    let mut disposition = None;
+
+    // Unfold continuation lines
+    let mut lines: Vec<String> = Vec::new();
+    for line in fields.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push(' ');
+                last.push_str(line.trim());
+            }
+        } else if !line.trim().is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "reporting-ua" => mdn.reporting_ua = Some(value),
+            "mdn-gateway" => mdn.mdn_gateway = Some(value),
+            "original-recipient" => mdn.original_recipient = Some(value),
+            "final-recipient" => mdn.final_recipient = Some(value),
+            "original-message-id" => mdn.original_message_id = Some(value),
+            "disposition" => disposition = parse_disposition(&value),
+            "error" => mdn.error.get_or_insert_with(Vec::new).push(value),
+            _ => {
+                mdn.extension_fields
+                    .get_or_insert_with(VecMap::new)
+                    .append(name.trim().to_string(), value);
+            }
+        }
+    }
+
+    mdn.disposition = disposition?;
+    Some(mdn)
+}
+
+fn parse_disposition(value: &str) -> Option<Disposition> {
+    let (modes, type_) = value.split_once(';')?;
+    let (action_mode, sending_mode) = modes.split_once('/')?;
+    let type_ = type_.split_once('/').map_or(type_, |(type_, _)| type_);
+
+    Some(Disposition {
+        action_mode: ActionMode::parse(action_mode.trim())?,
+        sending_mode: SendingMode::parse(sending_mode.trim())?,
+        type_: DispositionType::parse(type_.trim())?,
+    })
+}
@@ -0,0 +1,479 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    Server,
+    network::{ServerInstance, stream::NullIo},
+    storage::index::ObjectIndexBuilder,
+};
+use email::{
+    identity::Identity,
+    message::metadata::{MessageData, MessageMetadata, MetadataHeaderName},
+};
+use jmap_proto::{
+    error::set::{SetError, SetErrorType},
+    method::{
+        mdn::{MdnSendRequest, MdnSendResponse, MdnSent},
+        set::SetRequest,
+    },
+    object::mdn::{Mdn, MdnProperty},
+    request::{
+        Call, MaybeInvalid, RequestMethod, SetRequestMethod,
+        method::{MethodFunction, MethodName, MethodObject},
+        reference::MaybeIdReference,
+    },
+};
+use mail_builder::{
+    MessageBuilder,
+    headers::{HeaderType, content_type::ContentType},
+    mime::{BodyPart, MimePart, make_boundary},
+};
+use smtp::core::{Session, SessionData};
+use smtp_proto::{MailFrom, RcptTo};
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, BatchBuilder},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::EmailField, keyword::Keyword};
+use utils::{map::vec_map::VecMap, sanitize_email};
+
+pub trait MdnSend: Sync + Send {
+    fn mdn_send<'x>(
+        &self,
+        request: MdnSendRequest<'x>,
+        instance: &Arc<ServerInstance>,
+        next_call: &mut Option<Call<RequestMethod<'x>>>,
+    ) -> impl Future<Output = trc::Result<MdnSendResponse>> + Send;
+
+    fn send_mdn(
+        &self,
+        account_id: u32,
+        identity: &Identity,
+        instance: &Arc<ServerInstance>,
+        mdn: Mdn,
+        batch: &mut BatchBuilder,
+    ) -> impl Future<Output = trc::Result<Result<MdnSent, SetError<MdnProperty>>>> + Send;
+}
+
+impl MdnSend for Server {
+    async fn mdn_send<'x>(
+        &self,
+        request: MdnSendRequest<'x>,
+        instance: &Arc<ServerInstance>,
+        next_call: &mut Option<Call<RequestMethod<'x>>>,
+    ) -> trc::Result<MdnSendResponse> {
+        if request.send.len() > self.core.jmap.set_max_objects {
+            return Err(trc::JmapEvent::RequestTooLarge.into_err());
+        }
+
+        // Obtain identity
+        let account_id = request.account_id.document_id();
+        let identity = if let MaybeInvalid::Value(identity_id) = &request.identity_id
+            && let Some(identity) = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::Identity,
+                    identity_id.document_id(),
+                ))
+                .await?
+        {
+            identity
+                .deserialize::<Identity>()
+                .caused_by(trc::location!())?
+        } else {
+            return Err(trc::JmapEvent::InvalidArguments
+                .into_err()
+                .details("Identity not found."));
+        };
+
+        let mut response = MdnSendResponse {
+            account_id: request.account_id,
+            sent: VecMap::with_capacity(request.send.len()),
+            not_sent: VecMap::new(),
+        };
+        let mut success_email_ids = HashMap::new();
+        let mut batch = BatchBuilder::new();
+
+        for (id, mdn) in request.send {
+            let email_id = mdn.for_email_id;
+            match self
+                .send_mdn(account_id, &identity, instance, mdn, &mut batch)
+                .await?
+            {
+                Ok(sent) => {
+                    if let Some(email_id) = email_id {
+                        success_email_ids.insert(id.clone(), email_id);
+                    }
+                    response.sent.append(id, sent);
+                }
+                Err(err) => {
+                    response.not_sent.append(id, err);
+                }
+            }
+        }
+
+        // Write $mdnsent keywords
+        if !batch.is_empty() {
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+        }
+
+        // On success
+        if let Some(update) = request.on_success_update_email
+            && !update.is_empty()
+            && !response.sent.is_empty()
+        {
+            *next_call = Call {
+                id: String::new(),
+                name: MethodName::new(MethodObject::Email, MethodFunction::Set),
+                method: RequestMethod::Set(SetRequestMethod::Email(Box::new(SetRequest {
+                    account_id: request.account_id,
+                    if_in_state: None,
+                    create: None,
+                    update: Some(
+                        update
+                            .into_iter()
+                            .filter_map(|(id, value)| {
+                                (
+                                    match id {
+                                        MaybeIdReference::Id(id) => MaybeInvalid::Value(id),
+                                        MaybeIdReference::Reference(id_ref) => {
+                                            MaybeInvalid::Value(*(success_email_ids.get(&id_ref)?))
+                                        }
+                                        MaybeIdReference::Invalid(id) => MaybeInvalid::Invalid(id),
+                                    },
+                                    value,
+                                )
+                                    .into()
+                            })
+                            .collect(),
+                    ),
+                    destroy: None,
+                    arguments: Default::default(),
+                }))),
+            }
+            .into();
+        }
+
+        Ok(response)
+    }
+
+    async fn send_mdn(
+        &self,
+        account_id: u32,
+        identity: &Identity,
+        instance: &Arc<ServerInstance>,
+        mdn: Mdn,
+        batch: &mut BatchBuilder,
+    ) -> trc::Result<Result<MdnSent, SetError<MdnProperty>>> {
+        let Some(email_id) = mdn.for_email_id.map(|id| id.document_id()) else {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(MdnProperty::ForEmailId)
+                .with_description("forEmailId property is required.")));
+        };
+
+        // Field values are written verbatim to the report, reject line breaks
+        let has_line_break = |value: &str| value.contains(['\r', '\n']);
+        for (property, value) in [
+            (MdnProperty::ReportingUA, &mdn.reporting_ua),
+            (MdnProperty::MdnGateway, &mdn.mdn_gateway),
+            (MdnProperty::OriginalRecipient, &mdn.original_recipient),
+            (MdnProperty::FinalRecipient, &mdn.final_recipient),
+            (MdnProperty::OriginalMessageId, &mdn.original_message_id),
+        ] {
+            if value.as_deref().is_some_and(has_line_break) {
+                return Ok(Err(SetError::invalid_properties()
+                    .with_property(property)
+                    .with_description("Field values cannot contain line breaks.")));
+            }
+        }
+        if mdn
+            .error
+            .iter()
+            .flatten()
+            .any(|error| has_line_break(error))
+        {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(MdnProperty::Error)
+                .with_description("Field values cannot contain line breaks.")));
+        }
+        if mdn
+            .extension_fields
+            .iter()
+            .flat_map(|fields| fields.iter())
+            .any(|(name, value)| has_line_break(name) || has_line_break(value))
+        {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(MdnProperty::ExtensionFields)
+                .with_description("Field values cannot contain line breaks.")));
+        }
+
+        // Obtain message data
+        let (Some(data_), Some(metadata_)) = (
+            self.store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::Email,
+                    email_id,
+                ))
+                .await?,
+            self.store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                    account_id,
+                    Collection::Email,
+                    email_id,
+                    EmailField::Metadata,
+                ))
+                .await?,
+        ) else {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(MdnProperty::ForEmailId)
+                .with_description("Email not found.")));
+        };
+        let data = data_
+            .to_unarchived::<MessageData>()
+            .caused_by(trc::location!())?;
+        let mut new_data = data.inner.to_builder();
+        if new_data.has_keyword(&Keyword::MdnSent) {
+            return Ok(Err(SetError::new(SetErrorType::MdnAlreadySent)
+                .with_description("A disposition notification was already sent.")));
+        }
+        let metadata = metadata_
+            .unarchive::<MessageMetadata>()
+            .caused_by(trc::location!())?;
+        let root_part = &metadata.contents[0].parts[0];
+
+        // Obtain the address requesting the notification
+        let Some(rcpt) = root_part
+            .header_value(&MetadataHeaderName::DispositionNotificationTo)
+            .and_then(|value| {
+                value
+                    .as_single_address()
+                    .and_then(|addr| addr.address.as_deref())
+                    .or_else(|| value.as_text())
+            })
+            .and_then(|addr| sanitize_email(addr.trim_matches(|c| c == '<' || c == '>')))
+        else {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(MdnProperty::ForEmailId)
+                .with_description(
+                    "Email does not request a disposition notification.",
+                )));
+        };
+
+        // Obtain original headers or message
+        let Some(raw_message) = self
+            .blob_store()
+            .get_blob(metadata.blob_hash.0.as_slice(), 0..usize::MAX)
+            .await?
+        else {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(MdnProperty::ForEmailId)
+                .with_description("Blob for email not found.")));
+        };
+        let original = if mdn.include_original_message {
+            MimePart::new(
+                ContentType::new("message/rfc822"),
+                BodyPart::Text(String::from_utf8_lossy(&raw_message).into_owned().into()),
+            )
+        } else {
+            let headers = raw_message
+                .get(
+                    u32::from(root_part.offset_header) as usize
+                        ..u32::from(root_part.offset_body) as usize,
+                )
+                .unwrap_or_default();
+            MimePart::new(
+                ContentType::new("text/rfc822-headers"),
+                BodyPart::Text(String::from_utf8_lossy(headers).into_owned().into()),
+            )
+        };
+
+        // Build disposition notification fields (RFC 8098)
+        let original_message_id = mdn
+            .original_message_id
+            .or_else(|| root_part.message_id().map(|id| format!("<{id}>")));
+        let reporting_ua = mdn
+            .reporting_ua
+            .unwrap_or_else(|| format!("{}; Stalwart", self.core.network.server_name));
+        let final_recipient = mdn
+            .final_recipient
+            .unwrap_or_else(|| format!("rfc822; {}", identity.email));
+        let mut fields = format!("Reporting-UA: {reporting_ua}\r\n");
+        if let Some(mdn_gateway) = &mdn.mdn_gateway {
+            fields.push_str(&format!("MDN-Gateway: {mdn_gateway}\r\n"));
+        }
+        if let Some(original_recipient) = &mdn.original_recipient {
+            fields.push_str(&format!("Original-Recipient: {original_recipient}\r\n"));
+        }
+        fields.push_str(&format!("Final-Recipient: {final_recipient}\r\n"));
+        if let Some(original_message_id) = &original_message_id {
+            fields.push_str(&format!("Original-Message-ID: {original_message_id}\r\n"));
+        }
+        fields.push_str(&format!(
+            "Disposition: {}/{}; {}\r\n",
+            mdn.disposition.action_mode.as_str(),
+            mdn.disposition.sending_mode.as_str(),
+            mdn.disposition.type_.as_str()
+        ));
+        for error in mdn.error.iter().flatten() {
+            fields.push_str(&format!("Error: {error}\r\n"));
+        }
+        for (name, value) in mdn.extension_fields.iter().flat_map(|fields| fields.iter()) {
+            if !name.is_empty()
+                && name
+                    .bytes()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == b'-')
+            {
+                fields.push_str(&format!("{name}: {value}\r\n"));
+            }
+        }
+
+        let subject = mdn.subject.unwrap_or_else(|| {
+            format!(
+                "Disposition notification: {}",
+                root_part.subject().unwrap_or_default()
+            )
+        });
+        let text_body = mdn.text_body.unwrap_or_else(|| {
+            format!(
+                "The message sent to {} has been {}.\r\n",
+                identity.email,
+                mdn.disposition.type_.as_str()
+            )
+        });
+        let domain = identity
+            .email
+            .rsplit_once('@')
+            .map_or(self.core.network.server_name.as_str(), |(_, domain)| domain);
+        let mut builder = MessageBuilder::new()
+            .from((identity.name.as_str(), identity.email.as_str()))
+            .header("To", HeaderType::Text(rcpt.as_str().into()))
+            .header("Auto-Submitted", HeaderType::Text("auto-replied".into()))
+            .message_id(format!("<{}@{}>", make_boundary("."), domain))
+            .subject(subject);
+        if let Some(original_message_id) = &original_message_id {
+            builder = builder
+                .header(
+                    "In-Reply-To",
+                    HeaderType::Text(original_message_id.as_str().into()),
+                )
+                .header(
+                    "References",
+                    HeaderType::Text(original_message_id.as_str().into()),
+                );
+        }
+        let message = builder
+            .body(MimePart::new(
+                ContentType::new("multipart/report")
+                    .attribute("report-type", "disposition-notification"),
+                BodyPart::Multipart(vec![
+                    MimePart::new(
+                        ContentType::new("text/plain"),
+                        BodyPart::Text(text_body.into()),
+                    ),
+                    MimePart::new(
+                        ContentType::new("message/disposition-notification"),
+                        BodyPart::Text(fields.into()),
+                    ),
+                    original,
+                ]),
+            ))
+            .write_to_vec()
+            .unwrap_or_default();
+
+        // Begin local SMTP session
+        let mut session = Session::<NullIo>::local(
+            self.clone(),
+            instance.clone(),
+            SessionData::local(
+                self.account_info(account_id)
+                    .await
+                    .caused_by(trc::location!())?,
+                None,
+                vec![],
+                vec![],
+                0,
+            ),
+        );
+        let mail_from = identity.email.clone();
+
+        // Spawn SMTP session to avoid overflowing the stack
+        let result = tokio::spawn(async move {
+            // MAIL FROM
+            let _ = session
+                .handle_mail_from(MailFrom {
+                    address: mail_from.into(),
+                    ..Default::default()
+                })
+                .await;
+            if let Some(error) = session.has_failed() {
+                return Err(SetError::new(SetErrorType::ForbiddenFrom)
+                    .with_description(format!("Server rejected MAIL-FROM: {}", error.trim())));
+            }
+
+            // RCPT TO
+            session.params.rcpt_errors_wait = Duration::from_secs(0);
+            let _ = session
+                .handle_rcpt_to(RcptTo {
+                    address: rcpt.into(),
+                    ..Default::default()
+                })
+                .await;
+            if let Some(error) = session.has_failed() {
+                return Err(SetError::new(SetErrorType::ForbiddenToSend)
+                    .with_description(format!("Server rejected RCPT-TO: {}", error.trim())));
+            }
+
+            // DATA
+            session.data.message = message;
+            let response = session.queue_message().await;
+            if let smtp::core::State::Accepted(_) = session.state {
+                Ok(())
+            } else {
+                Err(
+                    SetError::new(SetErrorType::ForbiddenToSend).with_description(format!(
+                        "Server rejected DATA: {}",
+                        std::str::from_utf8(&response).unwrap_or_default().trim()
+                    )),
+                )
+            }
+        })
+        .await
+        .map_err(|err| {
+            trc::EventType::Server(trc::ServerEvent::ThreadError)
+                .reason(err)
+                .caused_by(trc::location!())
+                .details("Join Error")
+        })?;
+        if let Err(err) = result {
+            return Ok(Err(err));
+        }
+
+        // Flag the original message
+        new_data.add_keyword(Keyword::MdnSent);
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .with_document(email_id)
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_current(data)
+                    .with_changes(new_data.seal()),
+            )
+            .caused_by(trc::location!())?
+            .commit_point();
+
+        Ok(Ok(MdnSent {
+            reporting_ua: reporting_ua.into(),
+            final_recipient: final_recipient.into(),
+            original_recipient: mdn.original_recipient,
+            original_message_id,
+        }))
+    }
+}
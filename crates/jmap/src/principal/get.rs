@@ -92,6 +92,10 @@ impl PrincipalGet for Server {
                     PrincipalProperty::Type => {
                         Value::Element(PrincipalValue::Type(if principal.is_user_account() {
                             PrincipalType::Individual
+                        } else if principal.is_location_account() {
+                            PrincipalType::Location
+                        } else if principal.is_resource_account() {
+                            PrincipalType::Resource
                         } else {
                             PrincipalType::Group
                         }))
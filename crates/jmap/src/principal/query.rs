@@ -101,6 +101,8 @@ impl PrincipalQuery for Server {
                         let typ = match principal_type {
                             PrincipalType::Individual => AccountType::User,
                             PrincipalType::Group => AccountType::Group,
+                            PrincipalType::Resource => AccountType::Resource,
+                            PrincipalType::Location => AccountType::Location,
                             _ => {
                                 filters.push(SearchFilter::is_in_set(Default::default()));
                                 continue;
@@ -8,8 +8,9 @@ use crate::registry::{
     EnterpriseRegistry,
     mapping::{
         RegistryGetResponse, account::account_get, bootstrap::bootstrap_get,
-        cluster::cluster_node_get, log::log_get, queued_message::queued_message_get,
-        report::report_get, spam_sample::spam_sample_get, task::task_get,
+        cluster::cluster_node_get, log::log_get, quarantine::quarantine_get,
+        queued_message::queued_message_get, report::report_get, spam_sample::spam_sample_get,
+        task::task_get,
     },
 };
 use common::{Server, auth::AccessToken, network::dkim::generate_dkim_public_key};
@@ -133,6 +134,7 @@ impl RegistryGet for Server {
             | ObjectType::MemoryLookupKeyValue
             | ObjectType::Metrics
             | ObjectType::MetricsStore
+            | ObjectType::MtaAntivirus
             | ObjectType::MtaConnectionStrategy
             | ObjectType::MtaDeliverySchedule
             | ObjectType::MtaExtensions
@@ -156,6 +158,7 @@ impl RegistryGet for Server {
             | ObjectType::NetworkListener
             | ObjectType::ClusterRole
             | ObjectType::OidcProvider
+            | ObjectType::QuarantineSettings
             | ObjectType::ReportSettings
             | ObjectType::Search
             | ObjectType::SearchStore
@@ -261,6 +264,9 @@ impl RegistryGet for Server {
                                 let (name, domain_id) = match &obj {
                                     Account::User(obj) => (obj.name.as_str(), obj.domain_id),
                                     Account::Group(obj) => (obj.name.as_str(), obj.domain_id),
+                                    Account::Resource(obj) | Account::Location(obj) => {
+                                        (obj.name.as_str(), obj.domain_id)
+                                    }
                                 };
                                 let domain = self.domain_by_id(domain_id.document_id()).await?;
                                 let email = format!(
@@ -351,12 +357,16 @@ impl RegistryGet for Server {
             ObjectType::SpamTrainingSample => {
                 spam_sample_get(get).await.map(|get| get.into_response())
             }
+            ObjectType::QuarantinedMessage => {
+                quarantine_get(get).await.map(|get| get.into_response())
+            }
             ObjectType::Log => log_get(get).await.map(|get| get.into_response()),
             ObjectType::Bootstrap => bootstrap_get(get).await.map(|get| get.into_response()),
             ObjectType::AccountSettings
             | ObjectType::ApiKey
             | ObjectType::AccountPassword
-            | ObjectType::AppPassword => account_get(get).await.map(|get| get.into_response()),
+            | ObjectType::AppPassword
+            | ObjectType::Passkey => account_get(get).await.map(|get| get.into_response()),
             ObjectType::Action => Ok(get.not_found_any().into_response()),
             #[cfg(not(feature = "enterprise"))]
             _ => Ok(get.not_found_any().into_response()),
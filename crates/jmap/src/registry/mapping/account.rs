@@ -26,7 +26,10 @@ use common::{
     ipc::CacheInvalidation,
     storage::encryption::{EncryptionMethod, parse_public_key},
 };
-use directory::core::secret::{SecretVerificationResult, hash_secret, verify_mfa_secret_hash};
+use directory::core::{
+    scram::ScramSecret,
+    secret::{SecretVerificationResult, hash_secret, verify_mfa_secret_hash},
+};
 use jmap_proto::{error::set::SetError, request::MaybeInvalid, types::state::State};
 use jmap_tools::{JsonPointer, JsonPointerItem, Key, Map, Value};
 use registry::{
@@ -309,6 +312,10 @@ pub(crate) async fn account_set(
                                         old_credential.expires_at = None;
                                     }
 
+                                    old_credential.scram_secret =
+                                        ScramSecret::generate(user_provided_secret.as_bytes())
+                                            .to_string()
+                                            .into();
                                     old_credential.secret = hash_secret(
                                         set.server.core.network.security.password_hash_algorithm,
                                         user_provided_secret.as_bytes().to_vec(),
@@ -343,7 +350,18 @@ pub(crate) async fn account_set(
             }
         }
 
-        ObjectType::AppPassword | ObjectType::ApiKey => {
+        ObjectType::AppPassword | ObjectType::ApiKey | ObjectType::Passkey => {
+            // Passkeys are registered through the WebAuthn ceremony
+            if set.object_type == ObjectType::Passkey {
+                for (id, _) in set.create.drain() {
+                    set.response.not_created.append(
+                        id,
+                        SetError::forbidden()
+                            .with_description("Passkeys must be registered using WebAuthn."),
+                    );
+                }
+            }
+
             // Process creations
             if !set.create.is_empty() {
                 let account_cache = set.server.account(set.account_id).await?;
@@ -379,6 +397,12 @@ pub(crate) async fn account_set(
                             }
                             api_key_total += 1;
                         }
+                        Credential::Passkey(c) => {
+                            let credential_id = c.credential_id.id();
+                            if credential_id > last_credential_id {
+                                last_credential_id = credential_id;
+                            }
+                        }
                     }
                 }
 
@@ -778,7 +802,7 @@ pub(crate) async fn account_get(
 
             get.response.not_found.extend(ids.map(MaybeInvalid::Value));
         }
-        ObjectType::ApiKey | ObjectType::AppPassword => {
+        ObjectType::ApiKey | ObjectType::AppPassword | ObjectType::Passkey => {
             let mut ids = if let Some(ids) = get.ids.take() {
                 ids
             } else {
@@ -805,6 +829,19 @@ pub(crate) async fn account_get(
                         get.insert(id, credential);
                         ids.retain(|i| i != &id);
                     }
+                    (Credential::Passkey(passkey), ObjectType::Passkey)
+                        if ids.contains(&passkey.credential_id) =>
+                    {
+                        let id = passkey.credential_id;
+                        let mut credential = passkey.into_value();
+                        credential
+                            .as_object_mut()
+                            .unwrap()
+                            .as_mut_vec()
+                            .retain(|(k, _)| !matches!(k, Key::Property(Property::CredentialId)));
+                        get.insert(id, credential);
+                        ids.retain(|i| i != &id);
+                    }
                     _ => {}
                 }
             }
@@ -836,6 +873,7 @@ pub(crate) async fn credential_query(
     let credential_type = match query.object_type {
         ObjectType::AppPassword => CredentialType::AppPassword,
         ObjectType::ApiKey => CredentialType::ApiKey,
+        ObjectType::Passkey => CredentialType::Passkey,
         _ => unreachable!(),
     };
     let mut expires_at_filter = None;
@@ -865,6 +903,7 @@ pub(crate) async fn credential_query(
                     (credential.credential_id, credential.expires_at)
                 }
                 Credential::ApiKey(credential) => (credential.credential_id, credential.expires_at),
+                Credential::Passkey(credential) => (credential.credential_id, None),
                 _ => unreachable!(),
             };
             if expires_at_filter.is_none_or(|(op, filter_value)| {
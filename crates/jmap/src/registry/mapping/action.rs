@@ -379,6 +379,7 @@ async fn classify_spam(server: &Server, mut request: SpamClassify) -> Option<Spa
         authenticated_as: request.authenticated_as.as_deref(),
         asn: asn_geo.asn.as_ref().map(|a| a.id),
         country: asn_geo.country.as_ref().map(|c| c.as_str()),
+        virus: None,
         is_tls: request.is_tls,
         env_from: &request.env_from,
         env_from_flags: match request.env_from_parameters {
@@ -12,7 +12,7 @@ use common::{
     DATABASE_SCHEMA_VERSION, Server, config::storage::Storage,
     network::acme::account::acme_create_account, psl,
 };
-use directory::core::secret::hash_secret;
+use directory::core::{scram::ScramSecret, secret::hash_secret};
 use jmap_proto::{
     error::set::{SetError, SetErrorType},
     request::MaybeInvalid,
@@ -500,6 +500,7 @@ pub(crate) async fn bootstrap_set(
                     )
                     .await
                     .unwrap_or_default(),
+                    scram_secret: ScramSecret::generate(secret.as_bytes()).to_string().into(),
                     ..Default::default()
                 })]),
                 roles: UserRoles::Admin,
@@ -30,6 +30,7 @@ pub mod domain;
 pub mod log;
 pub mod principal;
 pub mod public_key;
+pub mod quarantine;
 pub mod queued_message;
 pub mod report;
 pub mod sieve;
@@ -9,7 +9,10 @@ use common::{
     Server,
     auth::{Permissions, PermissionsGroup, permissions::BuildPermissions},
 };
-use directory::core::secret::{hash_secret, is_password_hash};
+use directory::core::{
+    scram::ScramSecret,
+    secret::{hash_secret, is_password_hash},
+};
 use jmap_proto::error::set::SetError;
 use registry::{schema::structs::TaskStatus, types::datetime::UTCDateTime};
 use registry::{
@@ -117,7 +120,9 @@ pub(crate) async fn validate_account(
                                     credential.otp_auth = old_credential.otp_auth.clone();
                                 }
 
-                                if credential.secret != old_credential.secret {
+                                if credential.secret == old_credential.secret {
+                                    credential.scram_secret = old_credential.scram_secret.clone();
+                                } else {
                                     if credential.expires_at == old_credential.expires_at
                                         && credential
                                             .expires_at
@@ -147,6 +152,10 @@ pub(crate) async fn validate_account(
                                                 .with_description(err)));
                                         }
 
+                                        credential.scram_secret =
+                                            ScramSecret::generate(credential.secret.as_bytes())
+                                                .to_string()
+                                                .into();
                                         credential.secret = hash_secret(
                                             set.server
                                                 .core
@@ -157,6 +166,8 @@ pub(crate) async fn validate_account(
                                         )
                                         .await
                                         .caused_by(trc::location!())?;
+                                    } else {
+                                        credential.scram_secret = None;
                                     }
                                 }
                             }
@@ -179,6 +190,7 @@ pub(crate) async fn validate_account(
                                     )));
                                 }
                             }
+                            (Credential::Passkey(_), Credential::Passkey(_)) => {}
                             _ => {
                                 return Ok(Err(SetError::invalid_properties()
                                     .with_property(Property::Credentials)
@@ -214,6 +226,10 @@ pub(crate) async fn validate_account(
         (Account::Group(account), AccountUpdate::Update(Account::Group(old_account))) => {
             account.permissions != old_account.permissions || account.roles != old_account.roles
         }
+        (Account::Resource(account), AccountUpdate::Update(Account::Resource(old_account)))
+        | (Account::Location(account), AccountUpdate::Update(Account::Location(old_account))) => {
+            account.permissions != old_account.permissions || account.roles != old_account.roles
+        }
         (Account::User(account), AccountUpdate::Create(_)) => {
             // Validate tenant quotas
             if let Err(err) = validate_tenant_quota(set, TenantStorageQuota::MaxAccounts).await? {
@@ -245,8 +261,15 @@ pub(crate) async fn validate_account(
 
             true
         }
-        (Account::User(_), AccountUpdate::Update(Account::Group(_)))
-        | (Account::Group(_), AccountUpdate::Update(Account::User(_))) => {
+        (Account::Resource(_) | Account::Location(_), AccountUpdate::Create(_)) => {
+            // Resources count towards the tenant's account quota
+            if let Err(err) = validate_tenant_quota(set, TenantStorageQuota::MaxAccounts).await? {
+                return Ok(Err(err));
+            }
+
+            true
+        }
+        (_, AccountUpdate::Update(_)) => {
             return Ok(Err(SetError::invalid_properties()
                 .with_property(Property::Type)
                 .with_description(
@@ -304,12 +327,16 @@ async fn validate_credential_creation(
             if matches!(credential.secret.as_bytes().first(), Some(&b'$' | &b'{'))
                 && is_password_hash(&credential.secret)
             {
+                credential.scram_secret = None;
                 Ok(Ok(()))
             } else if let Err(err) = server.is_secure_password(&credential.secret, &[]) {
                 Ok(Err(SetError::invalid_properties()
                     .with_property(Property::Secret)
                     .with_description(err)))
             } else {
+                credential.scram_secret = ScramSecret::generate(credential.secret.as_bytes())
+                    .to_string()
+                    .into();
                 credential.secret = hash_secret(
                     server.core.network.security.password_hash_algorithm,
                     std::mem::take(&mut credential.secret).into_bytes(),
@@ -319,7 +346,7 @@ async fn validate_credential_creation(
                 Ok(Ok(()))
             }
         }
-        Credential::AppPassword(_) | Credential::ApiKey(_) => {
+        Credential::AppPassword(_) | Credential::ApiKey(_) | Credential::Passkey(_) => {
             Ok(Err(SetError::invalid_properties()
                 .with_property(Property::Credentials)
                 .with_description(
@@ -379,37 +406,52 @@ pub(crate) async fn validate_tenant_quota(
             .map(|quotas| quotas.get(quota))
             .filter(|quota| *quota != u32::MAX)
         {
-            let (object_type, type_filter, description) = match quota {
-                TenantStorageQuota::MaxAccounts => {
-                    (ObjectType::Account, Some(AccountType::User), "accounts")
-                }
+            let (object_type, type_filter, description): (_, &[AccountType], _) = match quota {
+                TenantStorageQuota::MaxAccounts => (
+                    ObjectType::Account,
+                    &[
+                        AccountType::User,
+                        AccountType::Resource,
+                        AccountType::Location,
+                    ],
+                    "accounts",
+                ),
                 TenantStorageQuota::MaxGroups => {
-                    (ObjectType::Account, Some(AccountType::Group), "groups")
+                    (ObjectType::Account, &[AccountType::Group], "groups")
                 }
-                TenantStorageQuota::MaxDomains => (ObjectType::Domain, None, "domains"),
+                TenantStorageQuota::MaxDomains => (ObjectType::Domain, &[], "domains"),
                 TenantStorageQuota::MaxMailingLists => {
-                    (ObjectType::MailingList, None, "mailing lists")
+                    (ObjectType::MailingList, &[], "mailing lists")
                 }
-                TenantStorageQuota::MaxRoles => (ObjectType::Role, None, "roles"),
+                TenantStorageQuota::MaxRoles => (ObjectType::Role, &[], "roles"),
                 TenantStorageQuota::MaxOauthClients => {
-                    (ObjectType::OAuthClient, None, "OAuth clients")
+                    (ObjectType::OAuthClient, &[], "OAuth clients")
                 }
-                TenantStorageQuota::MaxDkimKeys => (ObjectType::DkimSignature, None, "DKIM keys"),
-                TenantStorageQuota::MaxDnsServers => (ObjectType::DnsServer, None, "DNS servers"),
-                TenantStorageQuota::MaxDirectories => (ObjectType::Directory, None, "directories"),
+                TenantStorageQuota::MaxDkimKeys => (ObjectType::DkimSignature, &[], "DKIM keys"),
+                TenantStorageQuota::MaxDnsServers => (ObjectType::DnsServer, &[], "DNS servers"),
+                TenantStorageQuota::MaxDirectories => (ObjectType::Directory, &[], "directories"),
                 TenantStorageQuota::MaxAcmeProviders => {
-                    (ObjectType::AcmeProvider, None, "ACME providers")
+                    (ObjectType::AcmeProvider, &[], "ACME providers")
                 }
                 TenantStorageQuota::MaxDiskQuota => unreachable!(),
             };
-            let query = RegistryQuery::new(object_type).with_tenant(tenant_id.into());
-            let count = if let Some(type_filter) = type_filter {
-                set.server
-                    .registry()
-                    .query::<Vec<Id>>(query.equal(Property::Type, type_filter.to_id()))
-                    .await?
-                    .len() as u32
+            let count = if !type_filter.is_empty() {
+                let mut count = 0;
+                for account_type in type_filter {
+                    count += set
+                        .server
+                        .registry()
+                        .query::<Vec<Id>>(
+                            RegistryQuery::new(object_type)
+                                .with_tenant(tenant_id.into())
+                                .equal(Property::Type, account_type.to_id()),
+                        )
+                        .await?
+                        .len() as u32;
+                }
+                count
             } else {
+                let query = RegistryQuery::new(object_type).with_tenant(tenant_id.into());
                 set.server
                     .registry()
                     .query::<RegistryObjectCounter>(query)
@@ -474,6 +516,11 @@ pub(crate) async fn schedule_account_destruction(
             account_name = account.name.clone();
             account_type = AccountType::Group;
         }
+        Account::Resource(resource) | Account::Location(resource) => {
+            account_domain_id = resource.domain_id;
+            account_name = resource.name.clone();
+            account_type = account.object_type();
+        }
     }
 
     let mut batch = BatchBuilder::new();
@@ -33,8 +33,8 @@ use registry::{
 use smtp::queue::{
     self, ArchivedError, ArchivedErrorDetails, ArchivedMessage, ArchivedStatus, ErrorDetails,
     FROM_AUTHENTICATED, FROM_AUTOGENERATED, FROM_DSN, FROM_REPORT, FROM_UNAUTHENTICATED,
-    FROM_UNAUTHENTICATED_DMARC, Message, MessageWrapper, RCPT_DSN_SENT, RCPT_SPAM_PAYLOAD,
-    Schedule, Status, spool::SmtpSpool,
+    FROM_UNAUTHENTICATED_DMARC, Message, MessageWrapper, RCPT_DSN_SENT, RCPT_QUARANTINE,
+    RCPT_SPAM_PAYLOAD, Schedule, Status, spool::SmtpSpool,
 };
 use std::str::FromStr;
 use store::{
@@ -682,6 +682,7 @@ fn map_message(message_in: &ArchivedMessage) -> QueuedMessage {
         for (bit, flag) in [
             (RCPT_DSN_SENT, RecipientFlag::DsnSent),
             (RCPT_SPAM_PAYLOAD, RecipientFlag::SpamPayload),
+            (RCPT_QUARANTINE, RecipientFlag::Quarantined),
         ] {
             if rcpt_flags & bit != 0 {
                 rcpt_out.flags.push(flag);
@@ -87,6 +87,9 @@ pub(crate) async fn task_set(
             | TaskType::UnindexDocument
             | TaskType::IndexTrace
             | TaskType::AccountMaintenance
+            | TaskType::AccountExport
+            | TaskType::AccountImport
+            | TaskType::QuarantineDigest
             | TaskType::TenantMaintenance
             | TaskType::StoreMaintenance
             | TaskType::SpamFilterMaintenance
@@ -271,7 +274,7 @@ pub(crate) async fn task_set(
             use crate::registry::set::map_write_error;
             use registry::schema::{
                 enums::AccountType,
-                structs::{Account, GroupAccount, UserAccount},
+                structs::{Account, GroupAccount, ResourceAccount, UserAccount},
             };
             use store::registry::write::{RegistryWrite, RegistryWriteResult};
 
@@ -296,6 +299,16 @@ pub(crate) async fn task_set(
                     domain_id: task.account_domain_id,
                     ..Default::default()
                 }),
+                AccountType::Resource => Account::Resource(ResourceAccount {
+                    name: task.account_name,
+                    domain_id: task.account_domain_id,
+                    ..Default::default()
+                }),
+                AccountType::Location => Account::Location(ResourceAccount {
+                    name: task.account_name,
+                    domain_id: task.account_domain_id,
+                    ..Default::default()
+                }),
             }
             .into();
 
@@ -10,8 +10,8 @@ use crate::{
         EnterpriseRegistry,
         mapping::{
             RegistryQueryResponse, account::credential_query, cluster::cluster_node_query,
-            log::log_query, queued_message::queued_message_query, report::report_query,
-            spam_sample::spam_sample_query, task::task_query,
+            log::log_query, quarantine::quarantine_query, queued_message::queued_message_query,
+            report::report_query, spam_sample::spam_sample_query, task::task_query,
         },
     },
 };
@@ -119,6 +119,15 @@ impl RegistryQuery for Server {
             .await
             .and_then(|response| response.build()),
 
+            ObjectType::QuarantinedMessage => quarantine_query(RegistryQueryResponse {
+                server: self,
+                access_token,
+                object_type,
+                request,
+            })
+            .await
+            .and_then(|response| response.build()),
+
             ObjectType::QueuedMessage => queued_message_query(RegistryQueryResponse {
                 server: self,
                 access_token,
@@ -137,7 +146,7 @@ impl RegistryQuery for Server {
             .await
             .and_then(|response| response.build()),
 
-            ObjectType::ApiKey | ObjectType::AppPassword => {
+            ObjectType::ApiKey | ObjectType::AppPassword | ObjectType::Passkey => {
                 credential_query(RegistryQueryResponse {
                     server: self,
                     access_token,
@@ -19,6 +19,7 @@ use crate::registry::{
             validate_tenant_quota,
         },
         public_key::validate_public_key,
+        quarantine::quarantine_set,
         queued_message::queued_message_set,
         report::report_set,
         sieve::validate_sieve_script,
@@ -32,7 +33,6 @@ use common::{
     expr::if_block::BootstrapExprExt, ipc::CacheInvalidation,
 };
 use directory::core::secret::{hash_secret, is_password_hash};
-use http_proto::HttpSessionData;
 use jmap_proto::{
     error::set::{SetError, SetErrorType},
     method::set::{SetRequest, SetResponse},
@@ -56,7 +56,7 @@ use registry::{
     },
     types::id::ObjectId,
 };
-use std::borrow::Cow;
+use std::{borrow::Cow, net::IpAddr};
 use store::{
     registry::{
         bootstrap::Bootstrap,
@@ -74,7 +74,7 @@ pub trait RegistrySet: Sync + Send {
         object_type: ObjectType,
         request: SetRequest<'_, Registry>,
         access_token: &AccessToken,
-        session: &HttpSessionData,
+        remote_ip: IpAddr,
     ) -> impl Future<Output = trc::Result<SetResponse<Registry>>> + Send;
 }
 
@@ -96,7 +96,7 @@ impl RegistrySet for Server {
         object_type: ObjectType,
         mut request: SetRequest<'_, Registry>,
         access_token: &AccessToken,
-        session: &HttpSessionData,
+        remote_ip: IpAddr,
     ) -> trc::Result<SetResponse<Registry>> {
         // Initial assertions
         if self.registry().is_bootstrap_mode() && !matches!(object_type, ObjectType::Bootstrap) {
@@ -156,7 +156,7 @@ impl RegistrySet for Server {
         let mut set = RegistrySetResponse {
             access_token,
             server: self,
-            remote_ip: session.remote_ip,
+            remote_ip,
             account_id: request.account_id.document_id(),
             object_type,
             response,
@@ -192,6 +192,7 @@ impl RegistrySet for Server {
             | ObjectType::SystemSettings
             | ObjectType::Metrics
             | ObjectType::MetricsStore
+            | ObjectType::MtaAntivirus
             | ObjectType::MtaConnectionStrategy
             | ObjectType::MtaExtensions
             | ObjectType::MtaInboundSession
@@ -205,6 +206,7 @@ impl RegistrySet for Server {
             | ObjectType::MtaStageRcpt
             | ObjectType::MtaSts
             | ObjectType::OidcProvider
+            | ObjectType::QuarantineSettings
             | ObjectType::ReportSettings
             | ObjectType::Search
             | ObjectType::SearchStore
@@ -301,9 +303,7 @@ impl RegistrySet for Server {
                         .await
                         .caused_by(trc::location!())?
                     {
-                        if (is_tenant_filtered
-                            && access_token.tenant_id().map(Id::from)
-                                != object.inner.member_tenant_id())
+                        if !access_token.can_access_object(&object)
                             || (is_account_filtered
                                 && object.inner.account_id() != Some(Id::from(set.account_id)))
                         {
@@ -649,11 +649,9 @@ impl RegistrySet for Server {
                         .await
                         .caused_by(trc::location!())?
                         .filter(|object| {
-                            !((is_tenant_filtered
-                                && access_token.tenant_id().map(Id::from)
-                                    != object.inner.member_tenant_id())
-                                || (is_account_filtered
-                                    && object.inner.account_id() != Some(Id::from(set.account_id))))
+                            access_token.can_access_object(object)
+                                && !(is_account_filtered
+                                    && object.inner.account_id() != Some(Id::from(set.account_id)))
                         })
                     {
                         match self
@@ -718,11 +716,15 @@ impl RegistrySet for Server {
             ObjectType::SpamTrainingSample => {
                 spam_sample_set(set).await.map(|set| set.into_response())
             }
+            ObjectType::QuarantinedMessage => {
+                quarantine_set(set).await.map(|set| set.into_response())
+            }
 
             ObjectType::AccountSettings
             | ObjectType::ApiKey
             | ObjectType::AccountPassword
-            | ObjectType::AppPassword => Box::pin(account_set(set))
+            | ObjectType::AppPassword
+            | ObjectType::Passkey => Box::pin(account_set(set))
                 .await
                 .map(|set| set.into_response()),
 
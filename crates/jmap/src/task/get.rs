@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::calendar_event::get::CalendarEventGet;
+use calcard::jscalendar::{JSCalendarProperty, JSCalendarValue};
+use common::{Server, auth::AccessToken};
+use groupware::cache::GroupwareCache;
+use jmap_proto::{
+    method::get::{GetRequest, GetResponse},
+    object::task::Task,
+    request::{
+        MaybeInvalid,
+        reference::{MaybeIdReference, MaybeResultReference},
+    },
+};
+use jmap_tools::{Key, Map, Value};
+use store::roaring::RoaringBitmap;
+use types::{acl::Acl, collection::SyncCollection};
+
+pub trait TaskGet: Sync + Send {
+    fn task_get(
+        &self,
+        request: GetRequest<Task>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<GetResponse<Task>>> + Send;
+}
+
+impl TaskGet for Server {
+    async fn task_get(
+        &self,
+        mut request: GetRequest<Task>,
+        access_token: &AccessToken,
+    ) -> trc::Result<GetResponse<Task>> {
+        let (ids, mut not_found) = request.unwrap_ids(self.core.jmap.get_max_objects)?;
+        let account_id = request.account_id.document_id();
+        let cache = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                account_id,
+                SyncCollection::Calendar,
+            )
+            .await?;
+
+        // Only VTODO resources are exposed as tasks
+        let mut task_ids = cache.task_ids(false).collect::<RoaringBitmap>();
+        if !access_token.is_member(account_id) {
+            task_ids &= cache.shared_items(access_token, [Acl::ReadItems], true);
+        }
+        let ids = if let Some(ids) = ids {
+            let mut valid_ids = Vec::with_capacity(ids.len());
+            for id in ids {
+                if task_ids.contains(id.document_id()) {
+                    valid_ids.push(MaybeIdReference::Id(id));
+                } else {
+                    not_found.push(MaybeInvalid::Value(id));
+                }
+            }
+            valid_ids
+        } else {
+            task_ids
+                .iter()
+                .take(self.core.jmap.get_max_objects)
+                .map(|document_id| MaybeIdReference::Id(document_id.into()))
+                .collect()
+        };
+
+        // Task list membership is exposed as "taskListId" rather than "calendarIds"
+        let properties = request.properties.map(|properties| match properties {
+            MaybeResultReference::Value(properties) => MaybeResultReference::Value(
+                properties
+                    .into_iter()
+                    .filter_map(|property| match property {
+                        MaybeInvalid::Invalid(name) if name == "taskListId" => {
+                            Some(MaybeInvalid::Value(JSCalendarProperty::CalendarIds))
+                        }
+                        MaybeInvalid::Value(JSCalendarProperty::CalendarIds) => None,
+                        property => Some(property),
+                    })
+                    .collect(),
+            ),
+            reference => reference,
+        });
+
+        let response = self
+            .calendar_event_get(
+                GetRequest {
+                    account_id: request.account_id,
+                    ids: Some(MaybeResultReference::Value(ids)),
+                    properties,
+                    arguments: request.arguments,
+                },
+                access_token,
+            )
+            .await?;
+        not_found.extend(response.not_found);
+
+        Ok(GetResponse {
+            account_id: response.account_id,
+            state: response.state,
+            list: response
+                .list
+                .into_iter()
+                .map(|task| {
+                    if !matches!(task, Value::Object(_)) {
+                        return task;
+                    }
+
+                    let mut entries = Map::new();
+                    for (key, value) in task.into_expanded_object() {
+                        if let Key::Property(JSCalendarProperty::CalendarIds) = key {
+                            let task_list_id = value
+                                .as_object()
+                                .and_then(|ids| {
+                                    ids.keys().find_map(|key| match key {
+                                        Key::Property(JSCalendarProperty::IdValue(id)) => {
+                                            Some(Value::Element(JSCalendarValue::Id(*id)))
+                                        }
+                                        _ => None,
+                                    })
+                                })
+                                .unwrap_or(Value::Null);
+                            entries.insert_unchecked(Key::Borrowed("taskListId"), task_list_id);
+                        } else {
+                            entries.insert_unchecked(key, value);
+                        }
+                    }
+                    Value::Object(entries)
+                })
+                .collect(),
+            not_found,
+        })
+    }
+}
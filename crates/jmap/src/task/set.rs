@@ -0,0 +1,216 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::calendar_event::set::CalendarEventSet;
+use calcard::jscalendar::{JSCalendarProperty, JSCalendarType, JSCalendarValue};
+use common::{Server, auth::AccessToken};
+use groupware::cache::GroupwareCache;
+use http_proto::HttpSessionData;
+use jmap_proto::{
+    error::set::SetError,
+    method::set::{SetRequest, SetResponse},
+    object::{JmapObjectId, task::Task},
+    request::{MaybeInvalid, reference::MaybeResultReference},
+};
+use jmap_tools::{JsonPointerItem, Key, Map, Value};
+use std::str::FromStr;
+use store::roaring::RoaringBitmap;
+use types::{blob::BlobId, collection::SyncCollection, id::Id};
+use utils::map::vec_map::VecMap;
+
+pub trait TaskSet: Sync + Send {
+    fn task_set(
+        &self,
+        request: SetRequest<'_, Task>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<SetResponse<Task>>> + Send;
+}
+
+impl TaskSet for Server {
+    async fn task_set(
+        &self,
+        mut request: SetRequest<'_, Task>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<SetResponse<Task>> {
+        request.validate(self.core.jmap.set_max_objects)?;
+        let cache = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                request.account_id.document_id(),
+                SyncCollection::Calendar,
+            )
+            .await?;
+        let task_ids = cache.task_ids(false).collect::<RoaringBitmap>();
+        let task_list_ids = cache.task_ids(true).collect::<RoaringBitmap>();
+        let mut not_created = VecMap::new();
+        let mut not_updated = VecMap::new();
+        let mut not_destroyed = VecMap::new();
+
+        let mut create = VecMap::new();
+        for (id, object) in request.unwrap_create() {
+            match map_task_properties(object, &task_list_ids, true) {
+                Ok(object) => {
+                    create.append(id, object);
+                }
+                Err(err) => {
+                    not_created.append(id, err);
+                }
+            }
+        }
+
+        // Calendar events that are not VTODO resources are not visible as tasks
+        let mut update = VecMap::new();
+        for (id, object) in request.unwrap_update() {
+            match id {
+                MaybeInvalid::Value(id) if !task_ids.contains(id.document_id()) => {
+                    not_updated.append(MaybeInvalid::Value(id), SetError::not_found());
+                }
+                _ => match map_task_properties(object, &task_list_ids, false) {
+                    Ok(object) => {
+                        update.append(id, object);
+                    }
+                    Err(err) => {
+                        not_updated.append(id, err);
+                    }
+                },
+            }
+        }
+        let destroy = request
+            .unwrap_destroy()
+            .into_iter()
+            .filter(|id| match id {
+                MaybeInvalid::Value(id) if !task_ids.contains(id.document_id()) => {
+                    not_destroyed.append(MaybeInvalid::Value(*id), SetError::not_found());
+                    false
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        let response = self
+            .calendar_event_set(
+                SetRequest {
+                    account_id: request.account_id,
+                    if_in_state: request.if_in_state,
+                    create: Some(create),
+                    update: Some(update),
+                    destroy: Some(MaybeResultReference::Value(destroy)),
+                    arguments: request.arguments,
+                },
+                access_token,
+                session,
+            )
+            .await?;
+        not_created.extend(response.not_created);
+        not_updated.extend(response.not_updated);
+        not_destroyed.extend(response.not_destroyed);
+
+        Ok(SetResponse {
+            account_id: response.account_id,
+            old_state: response.old_state,
+            new_state: response.new_state,
+            created: response.created,
+            updated: response.updated,
+            destroyed: response.destroyed,
+            not_created,
+            not_updated,
+            not_destroyed,
+        })
+    }
+}
+
+// Tasks belong to a single task list referenced by "taskListId", which maps
+// to the calendarIds membership of the underlying calendar resource.
+#[allow(clippy::type_complexity)]
+fn map_task_properties<'x>(
+    object: Value<'x, JSCalendarProperty<Id>, JSCalendarValue<Id, BlobId>>,
+    task_list_ids: &RoaringBitmap,
+    is_create: bool,
+) -> Result<
+    Value<'x, JSCalendarProperty<Id>, JSCalendarValue<Id, BlobId>>,
+    SetError<JSCalendarProperty<Id>>,
+> {
+    if !matches!(object, Value::Object(_)) {
+        return Ok(object);
+    }
+
+    let mut entries = Map::new();
+    let mut has_type = false;
+    for (key, value) in object.into_expanded_object() {
+        match key {
+            Key::Property(JSCalendarProperty::Type) => {
+                if !matches!(
+                    value,
+                    Value::Element(JSCalendarValue::Type(JSCalendarType::Task))
+                ) {
+                    return Err(SetError::invalid_properties()
+                        .with_property(JSCalendarProperty::Type)
+                        .with_description("Only Task objects can be stored as tasks."));
+                }
+                has_type = true;
+                entries.insert_unchecked(JSCalendarProperty::Type, value);
+            }
+            Key::Property(JSCalendarProperty::CalendarIds) => {
+                return Err(SetError::invalid_properties()
+                    .with_property(JSCalendarProperty::CalendarIds)
+                    .with_description("Use taskListId to assign a task list."));
+            }
+            Key::Property(JSCalendarProperty::Pointer(pointer))
+                if matches!(
+                    pointer.first(),
+                    Some(JsonPointerItem::Key(Key::Property(
+                        JSCalendarProperty::CalendarIds
+                    )))
+                ) =>
+            {
+                return Err(SetError::invalid_properties()
+                    .with_property(JSCalendarProperty::Pointer(pointer))
+                    .with_description("Use taskListId to assign a task list."));
+            }
+            key if matches!(&key, Key::Borrowed("taskListId"))
+                || matches!(&key, Key::Owned(name) if name == "taskListId") =>
+            {
+                let task_list_id = match &value {
+                    Value::Element(element) => element.as_id(),
+                    Value::Str(value) => Id::from_str(value.as_ref()).ok(),
+                    _ => None,
+                };
+                let Some(task_list_id) = task_list_id else {
+                    return Err(SetError::invalid_properties()
+                        .with_property(key.to_owned())
+                        .with_description("Invalid task list id."));
+                };
+                if !task_list_ids.contains(task_list_id.document_id()) {
+                    return Err(SetError::invalid_properties()
+                        .with_property(key.to_owned())
+                        .with_description(format!(
+                            "Task list {task_list_id} does not exist or does not support tasks."
+                        )));
+                }
+
+                let mut calendar_ids = Map::with_capacity(1);
+                calendar_ids.insert_unchecked(JSCalendarProperty::IdValue(task_list_id), true);
+                entries
+                    .insert_unchecked(JSCalendarProperty::CalendarIds, Value::Object(calendar_ids));
+            }
+            key => {
+                entries.insert_unchecked(key, value);
+            }
+        }
+    }
+
+    // Objects created through Task/set default to the JSCalendar Task type
+    if is_create && !has_type {
+        entries.insert_unchecked(
+            JSCalendarProperty::Type,
+            Value::Element(JSCalendarValue::Type(JSCalendarType::Task)),
+        );
+    }
+
+    Ok(Value::Object(entries))
+}
@@ -0,0 +1,101 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::calendar::get::CalendarGet;
+use common::{Server, auth::AccessToken};
+use groupware::cache::GroupwareCache;
+use jmap_proto::{
+    method::get::{GetRequest, GetResponse},
+    object::{calendar::CalendarProperty, task_list::TaskList},
+    request::{
+        MaybeInvalid,
+        reference::{MaybeIdReference, MaybeResultReference},
+    },
+};
+use store::roaring::RoaringBitmap;
+use types::{acl::Acl, collection::SyncCollection};
+
+pub trait TaskListGet: Sync + Send {
+    fn task_list_get(
+        &self,
+        request: GetRequest<TaskList>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<GetResponse<TaskList>>> + Send;
+}
+
+impl TaskListGet for Server {
+    async fn task_list_get(
+        &self,
+        mut request: GetRequest<TaskList>,
+        access_token: &AccessToken,
+    ) -> trc::Result<GetResponse<TaskList>> {
+        let (ids, mut not_found) = request.unwrap_ids(self.core.jmap.get_max_objects)?;
+        let properties = request.unwrap_properties(&[
+            CalendarProperty::Id,
+            CalendarProperty::Name,
+            CalendarProperty::Description,
+            CalendarProperty::Color,
+            CalendarProperty::TimeZone,
+            CalendarProperty::SortOrder,
+            CalendarProperty::IsSubscribed,
+            CalendarProperty::MyRights,
+        ]);
+        let account_id = request.account_id.document_id();
+        let cache = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                account_id,
+                SyncCollection::Calendar,
+            )
+            .await?;
+
+        // Only calendars accepting VTODO components are exposed as task lists
+        let mut task_list_ids = cache.task_ids(true).collect::<RoaringBitmap>();
+        if !access_token.is_member(account_id) {
+            task_list_ids &=
+                cache.shared_containers(access_token, [Acl::Read, Acl::ReadItems], true);
+        }
+        let ids = if let Some(ids) = ids {
+            let mut valid_ids = Vec::with_capacity(ids.len());
+            for id in ids {
+                if task_list_ids.contains(id.document_id()) {
+                    valid_ids.push(MaybeIdReference::Id(id));
+                } else {
+                    not_found.push(MaybeInvalid::Value(id));
+                }
+            }
+            valid_ids
+        } else {
+            task_list_ids
+                .iter()
+                .take(self.core.jmap.get_max_objects)
+                .map(|document_id| MaybeIdReference::Id(document_id.into()))
+                .collect()
+        };
+
+        let response = self
+            .calendar_get(
+                GetRequest {
+                    account_id: request.account_id,
+                    ids: Some(MaybeResultReference::Value(ids)),
+                    properties: Some(MaybeResultReference::Value(
+                        properties.into_iter().map(MaybeInvalid::Value).collect(),
+                    )),
+                    arguments: (),
+                },
+                access_token,
+            )
+            .await?;
+        not_found.extend(response.not_found);
+
+        Ok(GetResponse {
+            account_id: response.account_id,
+            state: response.state,
+            list: response.list,
+            not_found,
+        })
+    }
+}
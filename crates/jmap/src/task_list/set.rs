@@ -0,0 +1,107 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::calendar::set::CalendarSet;
+use common::{Server, auth::AccessToken};
+use groupware::cache::GroupwareCache;
+use http_proto::HttpSessionData;
+use jmap_proto::{
+    error::set::SetError,
+    method::set::{SetRequest, SetResponse},
+    object::{calendar::CalendarSetArguments, task_list::TaskList},
+    request::{MaybeInvalid, reference::MaybeResultReference},
+};
+use store::roaring::RoaringBitmap;
+use types::collection::SyncCollection;
+use utils::map::vec_map::VecMap;
+
+pub trait TaskListSet: Sync + Send {
+    fn task_list_set(
+        &self,
+        request: SetRequest<'_, TaskList>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<SetResponse<TaskList>>> + Send;
+}
+
+impl TaskListSet for Server {
+    async fn task_list_set(
+        &self,
+        mut request: SetRequest<'_, TaskList>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<SetResponse<TaskList>> {
+        request.validate(self.core.jmap.set_max_objects)?;
+        let account_id = request.account_id.document_id();
+        let task_list_ids = self
+            .fetch_dav_resources(
+                access_token.account_id(),
+                account_id,
+                SyncCollection::Calendar,
+            )
+            .await?
+            .task_ids(true)
+            .collect::<RoaringBitmap>();
+        let mut not_updated = VecMap::new();
+        let mut not_destroyed = VecMap::new();
+
+        // Calendars that do not accept VTODO components are not visible as task lists
+        let update = request
+            .unwrap_update()
+            .into_iter()
+            .filter(|(id, _)| match id {
+                MaybeInvalid::Value(id) if !task_list_ids.contains(id.document_id()) => {
+                    not_updated.append(MaybeInvalid::Value(*id), SetError::not_found());
+                    false
+                }
+                _ => true,
+            })
+            .collect::<VecMap<_, _>>();
+        let destroy = request
+            .unwrap_destroy()
+            .into_iter()
+            .filter(|id| match id {
+                MaybeInvalid::Value(id) if !task_list_ids.contains(id.document_id()) => {
+                    not_destroyed.append(MaybeInvalid::Value(*id), SetError::not_found());
+                    false
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        let response = self
+            .calendar_set(
+                SetRequest {
+                    account_id: request.account_id,
+                    if_in_state: request.if_in_state,
+                    create: request.create,
+                    update: Some(update),
+                    destroy: Some(MaybeResultReference::Value(destroy)),
+                    arguments: CalendarSetArguments {
+                        on_destroy_remove_events: request.arguments.on_destroy_remove_tasks,
+                        on_success_set_is_default: None,
+                    },
+                },
+                access_token,
+                session,
+            )
+            .await?;
+        not_updated.extend(response.not_updated);
+        not_destroyed.extend(response.not_destroyed);
+
+        Ok(SetResponse {
+            account_id: response.account_id,
+            old_state: response.old_state,
+            new_state: response.new_state,
+            created: response.created,
+            updated: response.updated,
+            destroyed: response.destroyed,
+            not_created: response.not_created,
+            not_updated,
+            not_destroyed,
+        })
+    }
+}
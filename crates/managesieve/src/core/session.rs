@@ -32,6 +32,7 @@ impl SessionManager for ManageSieveSessionManager {
                 stream: session.stream,
                 in_flight: session.in_flight,
                 remote_addr: session.remote_ip,
+                scram: None,
             };
 
             if session
@@ -78,7 +79,7 @@ impl<T: SessionStream> Session<T> {
                                         SessionResult::UpgradeTls => {
                                             return true;
                                         }
-                                        SessionResult::Close => {
+                                        SessionResult::Close | SessionResult::UpgradeCompress => {
                                             break;
                                         }
                                     }
@@ -143,6 +144,7 @@ impl<T: SessionStream> Session<T> {
             server: self.server,
             receiver: self.receiver,
             remote_addr: self.remote_addr,
+            scram: None,
         })
     }
 }
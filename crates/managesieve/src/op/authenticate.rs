@@ -4,12 +4,12 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::core::{Command, Session, State, StatusResponse};
+use crate::core::{Command, ResponseCode, Session, State, StatusResponse};
 use common::{
-    auth::AuthRequest,
+    auth::{AccessToken, AuthRequest, scram::ScramState},
     network::{SessionStream, limiter::LimiterResult},
 };
-use directory::Credentials;
+use directory::{Credentials, core::scram::ScramClientFirst};
 use imap_proto::{
     protocol::authenticate::Mechanism,
     receiver::{self, Request},
@@ -49,15 +49,16 @@ impl<T: SessionStream> Session<T> {
                                 .details("Failed to decode challenge.")
                         })?
                 } else {
-                    self.receiver.request = receiver::Request {
-                        tag: "".into(),
-                        command: Command::Authenticate,
-                        tokens: vec![receiver::Token::Argument(mechanism.into_bytes())],
-                    };
-                    self.receiver.state = receiver::State::Argument { last_ch: b' ' };
+                    self.continue_authenticate(mechanism);
                     return Ok(b"{0}\r\n".to_vec());
                 }
             }
+            Mechanism::ScramSha256 | Mechanism::ScramSha256Plus => {
+                return self.handle_scram(mechanism, params.pop()).await;
+            }
+            Mechanism::External => {
+                return self.handle_external(params.pop()).await;
+            }
             _ => {
                 return Err(trc::AuthEvent::Error
                     .into_err()
@@ -66,7 +67,7 @@ impl<T: SessionStream> Session<T> {
         };
 
         // Authenticate
-        let access_token = self
+        match self
             .server
             .authenticate(&AuthRequest::from_credentials(
                 credentials,
@@ -74,25 +75,165 @@ impl<T: SessionStream> Session<T> {
                 self.remote_addr,
             ))
             .await
-            .map_err(|err| {
-                if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
-                    match &self.state {
-                        State::NotAuthenticated { auth_failures }
-                            if *auth_failures < self.server.core.imap.max_auth_failures =>
-                        {
-                            self.state = State::NotAuthenticated {
-                                auth_failures: auth_failures + 1,
-                            };
-                        }
-                        _ => {
-                            return trc::AuthEvent::TooManyAttempts.into_err().caused_by(err);
-                        }
-                    }
+        {
+            Ok(access_token) => self.authenticated(access_token),
+            Err(err) => Err(self.auth_failure(err)),
+        }
+    }
+
+    async fn handle_scram(
+        &mut self,
+        mechanism: Mechanism,
+        response: Option<String>,
+    ) -> trc::Result<Vec<u8>> {
+        let state = self.scram.take();
+        let response = response
+            .filter(|response| !response.is_empty() && response != "=")
+            .map(|response| {
+                if response == "*" {
+                    return Err(trc::AuthEvent::Error
+                        .into_err()
+                        .details("Authentication cancelled."));
                 }
 
-                err
+                base64_decode(response.as_bytes()).ok_or_else(|| {
+                    trc::AuthEvent::Error
+                        .into_err()
+                        .details("Failed to decode challenge.")
+                })
             })
-            .and_then(|token| token.assert_has_permission(Permission::SieveAuthenticate))?;
+            .transpose()?;
+
+        match (state, response) {
+            (None, None) => {
+                self.continue_authenticate(mechanism);
+                Ok(b"{0}\r\n".to_vec())
+            }
+            (None, Some(client_first)) => {
+                let client_first = ScramClientFirst::parse(
+                    &client_first,
+                    mechanism == Mechanism::ScramSha256Plus,
+                    &self.stream.channel_bindings(&self.server),
+                )
+                .ok_or_else(|| {
+                    trc::AuthEvent::Error
+                        .into_err()
+                        .details("Failed to decode challenge.")
+                })?;
+                let auth = self.server.scram_begin(client_first).await?;
+                let challenge = format!("\"{}\"\r\n", auth.challenge()).into_bytes();
+                self.scram = Some(ScramState::ClientFinal(Box::new(auth)));
+                self.continue_authenticate(mechanism);
+                Ok(challenge)
+            }
+            (Some(ScramState::ClientFinal(auth)), Some(client_final)) => {
+                let (access_token, server_final) = self
+                    .server
+                    .scram_authenticate(&auth, &client_final, self.session_id, self.remote_addr)
+                    .await
+                    .map_err(|err| self.auth_failure(err))?;
+
+                // Send the server signature as part of the OK response (RFC 5804, section 2.1)
+                self.authenticated(access_token).map(|_| {
+                    StatusResponse::ok("Authentication successful")
+                        .with_code(ResponseCode::Sasl(server_final))
+                        .into_bytes()
+                })
+            }
+            _ => Err(trc::AuthEvent::Error
+                .into_err()
+                .details("Unexpected SASL response.")),
+        }
+    }
+
+    async fn handle_external(&mut self, response: Option<String>) -> trc::Result<Vec<u8>> {
+        let (Some(mapping), Some(certificate)) = (
+            self.instance.acceptor.client_cert_mapping(),
+            self.stream.peer_certificate(),
+        ) else {
+            return Err(trc::AuthEvent::Error
+                .into_err()
+                .details("No client certificate was presented."));
+        };
+
+        let Some(response) = response else {
+            // Placeholder argument so that an empty client response completes the exchange
+            self.receiver.request = receiver::Request {
+                tag: "".into(),
+                command: Command::Authenticate,
+                tokens: vec![
+                    receiver::Token::Argument(Mechanism::External.into_bytes()),
+                    receiver::Token::Argument(b"=".to_vec()),
+                ],
+            };
+            self.receiver.state = receiver::State::Argument { last_ch: b' ' };
+            return Ok(b"{0}\r\n".to_vec());
+        };
+
+        let authzid = match response.as_str() {
+            "" | "=" => None,
+            "*" => {
+                return Err(trc::AuthEvent::Error
+                    .into_err()
+                    .details("Authentication cancelled."));
+            }
+            response => Some(
+                base64_decode(response.as_bytes())
+                    .and_then(|authzid| String::from_utf8(authzid).ok())
+                    .ok_or_else(|| {
+                        trc::AuthEvent::Error
+                            .into_err()
+                            .details("Failed to decode challenge.")
+                    })?,
+            ),
+        };
+
+        match self
+            .server
+            .authenticate_certificate(
+                mapping,
+                certificate,
+                authzid.as_deref(),
+                self.session_id,
+                self.remote_addr,
+            )
+            .await
+        {
+            Ok(access_token) => self.authenticated(access_token),
+            Err(err) => Err(self.auth_failure(err)),
+        }
+    }
+
+    fn continue_authenticate(&mut self, mechanism: Mechanism) {
+        self.receiver.request = receiver::Request {
+            tag: "".into(),
+            command: Command::Authenticate,
+            tokens: vec![receiver::Token::Argument(mechanism.into_bytes())],
+        };
+        self.receiver.state = receiver::State::Argument { last_ch: b' ' };
+    }
+
+    fn auth_failure(&mut self, err: trc::Error) -> trc::Error {
+        if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
+            match &self.state {
+                State::NotAuthenticated { auth_failures }
+                    if *auth_failures < self.server.core.imap.max_auth_failures =>
+                {
+                    self.state = State::NotAuthenticated {
+                        auth_failures: auth_failures + 1,
+                    };
+                }
+                _ => {
+                    return trc::AuthEvent::TooManyAttempts.into_err().caused_by(err);
+                }
+            }
+        }
+
+        err
+    }
+
+    fn authenticated(&mut self, access_token: AccessToken) -> trc::Result<Vec<u8>> {
+        let access_token = access_token.assert_has_permission(Permission::SieveAuthenticate)?;
 
         // Enforce concurrency limits
         let in_flight = match access_token.is_imap_request_allowed() {
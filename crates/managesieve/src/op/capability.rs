@@ -20,10 +20,19 @@ impl<T: SessionStream> Session<T> {
             response.extend_from_slice(b"\"STARTTLS\"\r\n");
         }
         if self.stream.is_tls() || self.server.core.imap.allow_plain_auth {
-            response.extend_from_slice(b"\"SASL\" \"PLAIN OAUTHBEARER XOAUTH2\"\r\n");
+            response.extend_from_slice(b"\"SASL\" \"PLAIN OAUTHBEARER XOAUTH2 SCRAM-SHA-256");
         } else {
-            response.extend_from_slice(b"\"SASL\" \"OAUTHBEARER XOAUTH2\"\r\n");
+            response.extend_from_slice(b"\"SASL\" \"OAUTHBEARER XOAUTH2");
         };
+        if self.stream.channel_bindings(&self.server).is_available() {
+            response.extend_from_slice(b" SCRAM-SHA-256-PLUS");
+        }
+        if self.instance.acceptor.client_cert_mapping().is_some()
+            && self.stream.peer_certificate().is_some()
+        {
+            response.extend_from_slice(b" EXTERNAL");
+        }
+        response.extend_from_slice(b"\"\r\n");
         if let Some(sieve) =
             self.server
                 .core
@@ -11,8 +11,8 @@ pub mod stopwords;
 
 use self::detect::LanguageDetector;
 use crate::tokenizers::{
-    Token, chinese::ChineseTokenizer, japanese::JapaneseTokenizer, space::SpaceTokenizer,
-    word::WordTokenizer,
+    Token, chinese::ChineseTokenizer, japanese::JapaneseTokenizer, korean::KoreanTokenizer,
+    space::SpaceTokenizer, thai::ThaiTokenizer, word::WordTokenizer,
 };
 use std::borrow::Cow;
 
@@ -33,6 +33,14 @@ impl Language {
                 ChineseTokenizer::new(WordTokenizer::new(text, usize::MAX))
                     .filter(move |t| t.word.len() <= max_token_length),
             ),
+            Language::Korean => Box::new(
+                KoreanTokenizer::new(WordTokenizer::new(text, usize::MAX))
+                    .filter(move |t| t.word.len() <= max_token_length),
+            ),
+            Language::Thai => Box::new(
+                ThaiTokenizer::new(WordTokenizer::new(text, usize::MAX))
+                    .filter(move |t| t.word.len() <= max_token_length),
+            ),
             Language::None => {
                 Box::new(
                     SpaceTokenizer::new(text, max_token_length).map(|word| Token {
@@ -0,0 +1,675 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{InnerToken, Token};
+use std::{borrow::Cow, collections::HashSet, sync::LazyLock, vec::IntoIter};
+
+pub struct KoreanTokenizer<'x, T, I>
+where
+    T: Iterator<Item = Token<I>>,
+    I: InnerToken<'x>,
+{
+    tokenizer: T,
+    tokens: IntoIter<Token<I>>,
+    phantom: std::marker::PhantomData<&'x str>,
+}
+
+impl<'x, T, I> KoreanTokenizer<'x, T, I>
+where
+    T: Iterator<Item = Token<I>>,
+    I: InnerToken<'x>,
+{
+    pub fn new(tokenizer: T) -> Self {
+        KoreanTokenizer {
+            tokenizer,
+            tokens: Vec::new().into_iter(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'x, T, I> Iterator for KoreanTokenizer<'x, T, I>
+where
+    T: Iterator<Item = Token<I>>,
+    I: InnerToken<'x>,
+{
+    type Item = Token<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.tokens.next() {
+                return Some(token);
+            } else {
+                let token = self.tokenizer.next()?;
+                if token.word.is_alphabetic_8bit() {
+                    let from = token.from;
+                    match token.word.unwrap_alphabetic() {
+                        Cow::Borrowed(word) => {
+                            self.tokens = segment(word)
+                                .into_iter()
+                                .map(|(offset, word)| Token {
+                                    word: I::new_alphabetic(word),
+                                    from: from + offset,
+                                    to: from + offset + word.len(),
+                                })
+                                .collect::<Vec<_>>()
+                                .into_iter();
+                        }
+                        Cow::Owned(word) => {
+                            self.tokens = segment(&word)
+                                .into_iter()
+                                .map(|(offset, word)| Token {
+                                    word: I::new_alphabetic(word.to_string()),
+                                    from: from + offset,
+                                    to: from + offset + word.len(),
+                                })
+                                .collect::<Vec<_>>()
+                                .into_iter();
+                        }
+                    }
+                } else {
+                    return token.into();
+                }
+            }
+        }
+    }
+}
+
+/// Splits a word into its Hangul and non-Hangul parts, separating the
+/// trailing particle or verb ending from each Hangul word.
+pub fn tokenize(word: &str) -> Vec<&str> {
+    segment(word).into_iter().map(|(_, word)| word).collect()
+}
+
+/// Same as [`tokenize`], returning the byte offset of each token.
+fn segment(word: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut run_start = 0;
+    let mut run_is_hangul = None;
+
+    for (pos, ch) in word.char_indices() {
+        let is_hangul = is_hangul(ch);
+        match run_is_hangul {
+            Some(was_hangul) if was_hangul != is_hangul => {
+                if was_hangul {
+                    split_eojeol(&word[run_start..pos], run_start, &mut tokens);
+                } else {
+                    tokens.push((run_start, &word[run_start..pos]));
+                }
+                run_start = pos;
+            }
+            _ => {}
+        }
+        run_is_hangul = Some(is_hangul);
+    }
+
+    match run_is_hangul {
+        Some(true) => split_eojeol(&word[run_start..], run_start, &mut tokens),
+        Some(false) => tokens.push((run_start, &word[run_start..])),
+        None => {}
+    }
+
+    tokens
+}
+
+/// Splits a space delimited word (eojeol) into its stem and the longest
+/// known suffix. Words are only split when the stem is a dictionary word,
+/// unknown words are kept whole and followed by their suffix-stripped form.
+fn split_eojeol<'x>(word: &'x str, offset: usize, tokens: &mut Vec<(usize, &'x str)>) {
+    if !WORDS.contains(word) && !SUFFIXES.contains(word) {
+        let mut stripped = None;
+        for (pos, _) in word.char_indices().skip(1) {
+            if SUFFIXES.contains(&word[pos..]) {
+                if WORDS.contains(&word[..pos]) {
+                    tokens.push((offset, &word[..pos]));
+                    tokens.push((offset + pos, &word[pos..]));
+                    return;
+                }
+                stripped.get_or_insert(&word[..pos]);
+            }
+        }
+
+        tokens.push((offset, word));
+        if let Some(stem) = stripped {
+            tokens.push((offset, stem));
+        }
+        return;
+    }
+
+    tokens.push((offset, word));
+}
+
+#[inline(always)]
+fn is_hangul(ch: char) -> bool {
+    matches!(ch, '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}')
+}
+
+// Particles (josa), copulas and common verb endings (eomi)
+static SUFFIXES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    HashSet::from([
+        "이",
+        "가",
+        "은",
+        "는",
+        "을",
+        "를",
+        "의",
+        "에",
+        "에서",
+        "에게",
+        "에게서",
+        "께",
+        "께서",
+        "한테",
+        "한테서",
+        "로",
+        "으로",
+        "로서",
+        "으로서",
+        "로써",
+        "으로써",
+        "와",
+        "과",
+        "도",
+        "만",
+        "까지",
+        "부터",
+        "보다",
+        "처럼",
+        "같이",
+        "마다",
+        "조차",
+        "마저",
+        "밖에",
+        "만큼",
+        "이나",
+        "나",
+        "이나마",
+        "이든",
+        "든",
+        "이라도",
+        "라도",
+        "이랑",
+        "랑",
+        "에는",
+        "에서는",
+        "에게는",
+        "으로는",
+        "로는",
+        "와는",
+        "과는",
+        "에도",
+        "에서도",
+        "에게도",
+        "으로도",
+        "로도",
+        "까지는",
+        "까지도",
+        "부터는",
+        "에서부터",
+        "만은",
+        "만이",
+        "만을",
+        "이다",
+        "이며",
+        "이고",
+        "이면",
+        "이지만",
+        "이라",
+        "이라고",
+        "이라는",
+        "라는",
+        "입니다",
+        "입니까",
+        "이에요",
+        "예요",
+        "이었다",
+        "였다",
+        "이었습니다",
+        "였습니다",
+        "이야",
+        "야",
+        "요",
+        "하다",
+        "한다",
+        "했다",
+        "합니다",
+        "했습니다",
+        "하는",
+        "하고",
+        "하며",
+        "하면",
+        "하여",
+        "해서",
+        "해요",
+        "하세요",
+        "하십시오",
+        "하시기",
+        "하지",
+        "하지만",
+        "하려면",
+        "하려고",
+        "하기",
+        "하시면",
+        "해",
+        "해주세요",
+        "해주십시오",
+        "하게",
+        "할",
+        "한",
+        "함",
+        "해야",
+        "되다",
+        "된다",
+        "됐다",
+        "되었다",
+        "됩니다",
+        "되었습니다",
+        "되는",
+        "되어",
+        "돼",
+        "되면",
+        "되지",
+        "되었으며",
+        "될",
+        "된",
+        "됨",
+        "시키다",
+        "시킨다",
+        "시켰다",
+        "습니다",
+        "었다",
+        "았다",
+        "었습니다",
+        "았습니다",
+        "겠다",
+        "겠습니다",
+        "어요",
+        "아요",
+        "세요",
+        "시오",
+        "십시오",
+        "다",
+        "고",
+        "서",
+        "며",
+        "면",
+        "지만",
+        "니까",
+        "는데",
+        "은데",
+        "던",
+    ])
+});
+
+// Common stems, particles and endings are only split from these words
+static WORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    HashSet::from([
+        "아이",
+        "나이",
+        "오이",
+        "고양이",
+        "어린이",
+        "종이",
+        "놀이",
+        "높이",
+        "길이",
+        "깊이",
+        "넓이",
+        "차이",
+        "사이",
+        "하나",
+        "누나",
+        "언제나",
+        "회의",
+        "문의",
+        "동의",
+        "주의",
+        "합의",
+        "논의",
+        "정의",
+        "강의",
+        "건의",
+        "편의",
+        "의의",
+        "결과",
+        "효과",
+        "사과",
+        "학과",
+        "통과",
+        "초과",
+        "부과",
+        "정도",
+        "속도",
+        "제도",
+        "의도",
+        "지도",
+        "태도",
+        "한도",
+        "온도",
+        "강도",
+        "빈도",
+        "용도",
+        "인도",
+        "부도",
+        "불만",
+        "백만",
+        "천만",
+        "십만",
+        "국가",
+        "평가",
+        "작가",
+        "전문가",
+        "휴가",
+        "추가",
+        "증가",
+        "참가",
+        "물가",
+        "주가",
+        "중요",
+        "필요",
+        "수요",
+        "주요",
+        "분야",
+        "아시아",
+        "사랑",
+        "함께",
+        "바다",
+        "그리고",
+        "최고",
+        "광고",
+        "참고",
+        "경고",
+        "재고",
+        "보고",
+        "문서",
+        "보고서",
+        "계약서",
+        "신청서",
+        "증명서",
+        "설명서",
+        "견적서",
+        "청구서",
+        "주문서",
+        "그래서",
+        "따라서",
+        "화면",
+        "측면",
+        "이면",
+        "양면",
+        "라면",
+        "그러면",
+        "마을",
+        "가을",
+        "도로",
+        "경로",
+        "통로",
+        "진로",
+        "제한",
+        "기한",
+        "권한",
+        "무한",
+        "대한",
+        "가격",
+        "가족",
+        "감사",
+        "개인정보",
+        "검토",
+        "결제",
+        "계정",
+        "계좌",
+        "고객",
+        "고객님",
+        "공지",
+        "관리",
+        "관리자",
+        "구매",
+        "금액",
+        "기간",
+        "내일",
+        "내용",
+        "다운로드",
+        "답변",
+        "답장",
+        "당첨",
+        "대출",
+        "등록",
+        "로그인",
+        "링크",
+        "메시지",
+        "메일",
+        "메일함",
+        "모임",
+        "무료",
+        "문제",
+        "반",
+        "발송",
+        "배송",
+        "버전",
+        "번호",
+        "변경",
+        "보안",
+        "본인",
+        "비밀번호",
+        "사람",
+        "사용",
+        "사용자",
+        "사진",
+        "상품",
+        "서명",
+        "서버",
+        "서비스",
+        "선생님",
+        "설정",
+        "설치",
+        "송금",
+        "수신",
+        "승인",
+        "시간",
+        "시작",
+        "신청",
+        "아래",
+        "안내",
+        "업데이트",
+        "연락",
+        "연락처",
+        "오늘",
+        "오전",
+        "오후",
+        "요청",
+        "우리",
+        "은행",
+        "이름",
+        "이메일",
+        "이번",
+        "인증",
+        "일시적",
+        "일정",
+        "입금",
+        "전송",
+        "전화",
+        "정보",
+        "정지",
+        "제목",
+        "제품",
+        "주문",
+        "주소",
+        "준비",
+        "진행",
+        "질문",
+        "참석",
+        "첨부",
+        "첨부파일",
+        "초대",
+        "취소",
+        "친구",
+        "카드",
+        "코드",
+        "클릭",
+        "파일",
+        "프로젝트",
+        "학교",
+        "학생",
+        "할인",
+        "해지",
+        "확인",
+        "환불",
+        "활성화",
+        "회사",
+        "회신",
+        "회원",
+        "회의실",
+        "휴대폰",
+        "이벤트",
+        "계획",
+        "문자",
+        "번역",
+        "예약",
+        "일",
+        "집",
+        "돈",
+        "일요일",
+        "월요일",
+        "화요일",
+        "수요일",
+        "목요일",
+        "금요일",
+        "토요일",
+        "어제",
+        "지금",
+        "다음",
+        "지난",
+        "마감",
+    ])
+});
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizers::{Token, korean::KoreanTokenizer, word::WordTokenizer};
+
+    #[test]
+    fn korean_tokenizer() {
+        assert_eq!(
+            KoreanTokenizer::new(WordTokenizer::new("회의는 내일입니다 abc", 40))
+                .collect::<Vec<_>>(),
+            vec![
+                Token {
+                    word: "회의".into(),
+                    from: 0,
+                    to: 6
+                },
+                Token {
+                    word: "는".into(),
+                    from: 6,
+                    to: 9
+                },
+                Token {
+                    word: "내일".into(),
+                    from: 10,
+                    to: 16
+                },
+                Token {
+                    word: "입니다".into(),
+                    from: 16,
+                    to: 25
+                },
+                Token {
+                    word: "abc".into(),
+                    from: 26,
+                    to: 29
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn korean_segmentation() {
+        for (input, expect) in [
+            ("시작이", vec!["시작", "이"]),
+            ("반이다", vec!["반", "이다"]),
+            ("학교에서는", vec!["학교", "에서는"]),
+            ("비밀번호를", vec!["비밀번호", "를"]),
+            ("확인하세요", vec!["확인", "하세요"]),
+            ("결과", vec!["결과"]),
+            ("결과가", vec!["결과", "가"]),
+            ("입니다", vec!["입니다"]),
+            ("이메일을", vec!["이메일", "을"]),
+            ("pdf파일을", vec!["pdf", "파일", "을"]),
+            ("강아지가", vec!["강아지가", "강아지"]),
+            ("서울에서", vec!["서울에서", "서울"]),
+        ] {
+            assert_eq!(super::tokenize(input), expect, "{input}");
+        }
+    }
+
+    #[test]
+    fn korean_sentences() {
+        for (input, expect) in [
+            (
+                "비밀번호를 변경하려면 아래 링크를 클릭하세요",
+                vec![
+                    "비밀번호",
+                    "를",
+                    "변경",
+                    "하려면",
+                    "아래",
+                    "링크",
+                    "를",
+                    "클릭",
+                    "하세요",
+                ],
+            ),
+            (
+                "회의는 내일 오후에 회의실에서 시작합니다",
+                vec![
+                    "회의",
+                    "는",
+                    "내일",
+                    "오후",
+                    "에",
+                    "회의실",
+                    "에서",
+                    "시작",
+                    "합니다",
+                ],
+            ),
+            (
+                "고객님의 계정이 일시적으로 정지되었습니다",
+                vec![
+                    "고객님",
+                    "의",
+                    "계정",
+                    "이",
+                    "일시적",
+                    "으로",
+                    "정지",
+                    "되었습니다",
+                ],
+            ),
+            (
+                "첨부파일을 확인하고 답장을 보내주세요",
+                vec![
+                    "첨부파일",
+                    "을",
+                    "확인",
+                    "하고",
+                    "답장",
+                    "을",
+                    "보내주세요",
+                    "보내주",
+                ],
+            ),
+        ] {
+            assert_eq!(
+                KoreanTokenizer::new(WordTokenizer::new(input, 40))
+                    .map(|token| token.word)
+                    .collect::<Vec<_>>(),
+                expect,
+                "{input}"
+            );
+        }
+    }
+}
@@ -6,8 +6,10 @@
 
 pub mod chinese;
 pub mod japanese;
+pub mod korean;
 pub mod space;
 pub mod stream;
+pub mod thai;
 pub mod types;
 pub mod word;
 
@@ -11,7 +11,7 @@ use crate::{
         stemmer::STEMMER_MAP,
         stopwords::{STOP_WORDS, StopwordFnc},
     },
-    tokenizers::{chinese::JIEBA, japanese},
+    tokenizers::{chinese::JIEBA, japanese, korean, thai},
 };
 use std::borrow::Cow;
 
@@ -24,6 +24,8 @@ enum Stemmer {
     IndoEuropean(rust_stemmers::Stemmer),
     Mandarin,
     Japanese,
+    Korean,
+    Thai,
     None,
 }
 
@@ -40,6 +42,8 @@ impl WordStemTokenizer {
             stemmer: match language {
                 Language::Mandarin => Stemmer::Mandarin,
                 Language::Japanese => Stemmer::Japanese,
+                Language::Korean => Stemmer::Korean,
+                Language::Thai => Stemmer::Thai,
                 _ => STEMMER_MAP[language as usize]
                     .map(|algo| Stemmer::IndoEuropean(rust_stemmers::Stemmer::create(algo)))
                     .unwrap_or(Stemmer::None),
@@ -66,6 +70,16 @@ impl WordStemTokenizer {
                     cb(Cow::from(word));
                 }
             }
+            Stemmer::Korean => {
+                for word in korean::tokenize(word) {
+                    cb(Cow::from(word));
+                }
+            }
+            Stemmer::Thai => {
+                for word in thai::tokenize(word) {
+                    cb(Cow::from(word));
+                }
+            }
             Stemmer::None => {
                 cb(Cow::from(word));
             }
@@ -160,7 +174,11 @@ pub mod tests {
                 "井の中の蛙大海を知らず",
                 vec!["井", "の", "中", "の", "蛙大", "海", "を", "知ら", "ず"],
             ),
-            ("시작이 반이다", vec!["시작이", "반이다"]),
+            ("시작이 반이다", vec!["시작", "이", "반", "이다"]),
+            (
+                "กรุณายืนยันรหัสผ่านของคุณ",
+                vec!["กรุณา", "ยืนยัน", "รหัสผ่าน", "ของ", "คุณ"],
+            ),
         ];
 
         for (input, expect) in inputs.iter() {
@@ -0,0 +1,350 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{InnerToken, Token};
+use std::{borrow::Cow, collections::HashSet, sync::LazyLock, vec::IntoIter};
+
+pub struct ThaiTokenizer<'x, T, I>
+where
+    T: Iterator<Item = Token<I>>,
+    I: InnerToken<'x>,
+{
+    tokenizer: T,
+    tokens: IntoIter<Token<I>>,
+    phantom: std::marker::PhantomData<&'x str>,
+}
+
+impl<'x, T, I> ThaiTokenizer<'x, T, I>
+where
+    T: Iterator<Item = Token<I>>,
+    I: InnerToken<'x>,
+{
+    pub fn new(tokenizer: T) -> Self {
+        ThaiTokenizer {
+            tokenizer,
+            tokens: Vec::new().into_iter(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'x, T, I> Iterator for ThaiTokenizer<'x, T, I>
+where
+    T: Iterator<Item = Token<I>>,
+    I: InnerToken<'x>,
+{
+    type Item = Token<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.tokens.next() {
+                return Some(token);
+            } else {
+                let token = self.tokenizer.next()?;
+                if token.word.is_alphabetic_8bit() {
+                    let mut token_to = token.from;
+                    match token.word.unwrap_alphabetic() {
+                        Cow::Borrowed(word) => {
+                            self.tokens = tokenize(word)
+                                .into_iter()
+                                .map(|word| {
+                                    let token_from = token_to;
+                                    token_to += word.len();
+                                    Token {
+                                        word: I::new_alphabetic(word),
+                                        from: token_from,
+                                        to: token_to,
+                                    }
+                                })
+                                .collect::<Vec<_>>()
+                                .into_iter();
+                        }
+                        Cow::Owned(word) => {
+                            self.tokens = tokenize(&word)
+                                .into_iter()
+                                .map(|word| {
+                                    let token_from = token_to;
+                                    token_to += word.len();
+                                    Token {
+                                        word: I::new_alphabetic(word.to_string()),
+                                        from: token_from,
+                                        to: token_to,
+                                    }
+                                })
+                                .collect::<Vec<_>>()
+                                .into_iter();
+                        }
+                    }
+                } else {
+                    return token.into();
+                }
+            }
+        }
+    }
+}
+
+// Longest dictionary entry, in characters
+const MAX_WORD_LEN: usize = 20;
+
+// Word list based on the ICU Thai dictionary, extended with common
+// compounds found in email (Unicode-3.0, see thai.txt.license)
+static DICTIONARY: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    include_str!("thai.txt")
+        .lines()
+        .filter(|word| !word.is_empty())
+        .collect()
+});
+
+/// Splits a word into its Thai and non-Thai parts, segmenting the Thai
+/// text into dictionary words.
+pub fn tokenize(word: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut run_start = 0;
+    let mut run_is_thai = None;
+
+    for (pos, ch) in word.char_indices() {
+        let is_thai = is_thai(ch);
+        match run_is_thai {
+            Some(was_thai) if was_thai != is_thai => {
+                if was_thai {
+                    segment(&word[run_start..pos], &mut tokens);
+                } else {
+                    tokens.push(&word[run_start..pos]);
+                }
+                run_start = pos;
+            }
+            _ => {}
+        }
+        run_is_thai = Some(is_thai);
+    }
+
+    match run_is_thai {
+        Some(true) => segment(&word[run_start..], &mut tokens),
+        Some(false) => tokens.push(&word[run_start..]),
+        None => {}
+    }
+
+    tokens
+}
+
+/// Maximal matching segmentation: picks the split with the fewest characters
+/// outside the dictionary and, among those, the fewest words. Characters that
+/// do not match any dictionary word are grouped into a single token.
+fn segment<'x>(text: &'x str, tokens: &mut Vec<&'x str>) {
+    let offsets = text
+        .char_indices()
+        .map(|(pos, _)| pos)
+        .chain([text.len()])
+        .collect::<Vec<_>>();
+    let chars = text.chars().collect::<Vec<_>>();
+    let len = chars.len();
+
+    // Cheapest path to each position: (unknown chars, words, start, is_known)
+    let mut paths: Vec<Option<(usize, usize, usize, bool)>> = vec![None; len + 1];
+    paths[0] = Some((0, 0, 0, true));
+
+    for start in 0..len {
+        let Some((unknown, words, _, _)) = paths[start] else {
+            continue;
+        };
+        let mut add_path = |end: usize, unknown: usize, is_known: bool| {
+            if paths[end].is_none_or(|(best_unknown, best_words, _, _)| {
+                (unknown, words + 1) < (best_unknown, best_words)
+            }) {
+                paths[end] = Some((unknown, words + 1, start, is_known));
+            }
+        };
+
+        for end in start + 1..=(start + MAX_WORD_LEN).min(len) {
+            if is_boundary(&chars, end) && DICTIONARY.contains(&text[offsets[start]..offsets[end]])
+            {
+                add_path(end, unknown, true);
+            }
+        }
+
+        // Skip a single character cluster
+        let mut end = start + 1;
+        while !is_boundary(&chars, end) {
+            end += 1;
+        }
+        add_path(end, unknown + end - start, false);
+    }
+
+    // Walk back from the end, merging adjacent unknown clusters
+    let mut words: Vec<(usize, usize, bool)> = Vec::new();
+    let mut end = len;
+    while end > 0 {
+        let Some((_, _, start, is_known)) = paths[end] else {
+            break;
+        };
+        match words.last_mut() {
+            Some((from, _, false)) if !is_known => *from = start,
+            _ => words.push((start, end, is_known)),
+        }
+        end = start;
+    }
+
+    tokens.extend(
+        words
+            .into_iter()
+            .rev()
+            .map(|(from, to, _)| &text[offsets[from]..offsets[to]]),
+    );
+}
+
+/// A word cannot end on a leading vowel nor start with a following vowel,
+/// tone mark or diacritic.
+fn is_boundary(chars: &[char], pos: usize) -> bool {
+    pos == 0
+        || pos == chars.len()
+        || (!matches!(chars[pos - 1], '\u{0E40}'..='\u{0E44}')
+            && !matches!(chars[pos], '\u{0E30}'..='\u{0E3A}' | '\u{0E45}' | '\u{0E47}'..='\u{0E4E}'))
+}
+
+#[inline(always)]
+fn is_thai(ch: char) -> bool {
+    matches!(ch, '\u{0E01}'..='\u{0E4E}')
+}
+
+/// Thai vowel signs, tone marks and diacritics are not alphanumeric but are
+/// part of the word they appear in.
+#[inline(always)]
+pub(crate) fn is_thai_mark(ch: char) -> bool {
+    matches!(ch, '\u{0E31}' | '\u{0E34}'..='\u{0E3A}' | '\u{0E47}'..='\u{0E4E}')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizers::{Token, thai::ThaiTokenizer, word::WordTokenizer};
+
+    #[test]
+    fn thai_tokenizer() {
+        assert_eq!(
+            ThaiTokenizer::new(WordTokenizer::new("ผมชอบกินข้าว 123 อีเมลsmtp", 40))
+                .collect::<Vec<_>>(),
+            vec![
+                Token {
+                    word: "ผม".into(),
+                    from: 0,
+                    to: 6
+                },
+                Token {
+                    word: "ชอบ".into(),
+                    from: 6,
+                    to: 15
+                },
+                Token {
+                    word: "กิน".into(),
+                    from: 15,
+                    to: 24
+                },
+                Token {
+                    word: "ข้าว".into(),
+                    from: 24,
+                    to: 36
+                },
+                Token {
+                    word: "123".into(),
+                    from: 37,
+                    to: 40
+                },
+                Token {
+                    word: "อีเมล".into(),
+                    from: 41,
+                    to: 56
+                },
+                Token {
+                    word: "smtp".into(),
+                    from: 56,
+                    to: 60
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn thai_segmentation() {
+        for (input, expect) in [
+            (
+                "กรุณายืนยันรหัสผ่านของคุณ",
+                vec!["กรุณา", "ยืนยัน", "รหัสผ่าน", "ของ", "คุณ"],
+            ),
+            (
+                "บัญชีของคุณถูกระงับชั่วคราว",
+                vec!["บัญชี", "ของ", "คุณ", "ถูกระงับ", "ชั่วคราว"],
+            ),
+            ("ภาษาไทยง่ายนิดเดียว", vec!["ภาษาไทย", "ง่าย", "นิดเดียว"]),
+            ("และ", vec!["และ"]),
+        ] {
+            assert_eq!(super::tokenize(input), expect, "{input}");
+        }
+    }
+
+    #[test]
+    fn thai_sentences() {
+        for (input, expect) in [
+            ("เรียนลูกค้าที่เคารพ", vec!["เรียน", "ลูกค้า", "ที่", "เคารพ"]),
+            (
+                "กรุณาคลิกลิงก์ด้านล่างเพื่อตรวจสอบบัญชีของคุณ",
+                vec![
+                    "กรุณา",
+                    "คลิก",
+                    "ลิงก์",
+                    "ด้านล่าง",
+                    "เพื่อ",
+                    "ตรวจสอบ",
+                    "บัญชี",
+                    "ของ",
+                    "คุณ",
+                ],
+            ),
+            (
+                "รหัสผ่านของคุณจะหมดอายุภายในสามวัน",
+                vec!["รหัสผ่าน", "ของ", "คุณ", "จะ", "หมดอายุ", "ภายใน", "สาม", "วัน"],
+            ),
+            (
+                "การประชุมจะเริ่มเวลาสิบโมงเช้าวันพรุ่งนี้",
+                vec![
+                    "การประชุม",
+                    "จะ",
+                    "เริ่ม",
+                    "เวลา",
+                    "สิบ",
+                    "โมง",
+                    "เช้า",
+                    "วัน",
+                    "พรุ่งนี้",
+                ],
+            ),
+            (
+                "คุณได้รับรางวัลเป็นเงินสดหนึ่งล้านบาท",
+                vec!["คุณ", "ได้รับ", "รางวัล", "เป็น", "เงินสด", "หนึ่ง", "ล้าน", "บาท"],
+            ),
+            (
+                "โปรดแนบไฟล์เอกสารมาในอีเมลฉบับนี้",
+                vec![
+                    "โปรด",
+                    "แนบ",
+                    "ไฟล์",
+                    "เอกสาร",
+                    "มา",
+                    "ใน",
+                    "อีเมล",
+                    "ฉบับ",
+                    "นี้",
+                ],
+            ),
+        ] {
+            assert_eq!(
+                ThaiTokenizer::new(WordTokenizer::new(input, 200))
+                    .map(|token| token.word)
+                    .collect::<Vec<_>>(),
+                expect,
+                "{input}"
+            );
+        }
+    }
+}
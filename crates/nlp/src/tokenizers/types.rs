@@ -6,7 +6,7 @@
 
 use std::str::CharIndices;
 
-use super::Token;
+use super::{Token, thai::is_thai_mark};
 
 #[derive(Debug)]
 pub struct TypesTokenizer<'x> {
@@ -152,6 +152,8 @@ impl<'x> TypesTokenizer<'x> {
                 has_alpha = true;
             } else if ch.is_ascii_digit() {
                 has_number = true;
+            } else if start_pos != usize::MAX && is_thai_mark(ch) {
+                // Thai tone marks and vowel signs are not alphabetic
             } else {
                 let last_was_space = self.last_ch_is_space;
                 self.last_ch_is_space = ch.is_whitespace();
@@ -6,7 +6,7 @@
 
 use std::{borrow::Cow, str::CharIndices};
 
-use super::Token;
+use super::{Token, thai::is_thai_mark};
 
 pub struct WordTokenizer<'x> {
     max_token_length: usize,
@@ -34,7 +34,7 @@ impl<'x> Iterator for WordTokenizer<'x> {
                 let mut is_uppercase = ch.is_uppercase();
                 let token_end = (&mut self.iterator)
                     .filter_map(|(pos, ch)| {
-                        if ch.is_alphanumeric() {
+                        if ch.is_alphanumeric() || is_thai_mark(ch) {
                             if !is_uppercase && ch.is_uppercase() {
                                 is_uppercase = true;
                             }
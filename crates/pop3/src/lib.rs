@@ -10,7 +10,7 @@ use std::{net::IpAddr, sync::Arc};
 
 use common::{
     Inner, Server,
-    auth::AccessToken,
+    auth::{AccessToken, scram::ScramState},
     network::{ServerInstance, SessionStream, limiter::InFlight},
 };
 use mailbox::Mailbox;
@@ -44,6 +44,7 @@ pub struct Session<T: SessionStream> {
     pub in_flight: InFlight,
     pub remote_addr: IpAddr,
     pub session_id: u64,
+    pub scram: Option<ScramState>,
 }
 
 pub enum State {
@@ -9,10 +9,10 @@ use crate::{
     protocol::{Command, Mechanism, request},
 };
 use common::{
-    auth::AuthRequest,
+    auth::{AccessToken, AuthRequest, scram::ScramState},
     network::{SessionStream, limiter::LimiterResult},
 };
-use directory::Credentials;
+use directory::{Credentials, core::scram::ScramClientFirst};
 use mail_parser::decoders::base64::base64_decode;
 use registry::schema::enums::Permission;
 
@@ -41,28 +41,178 @@ impl<T: SessionStream> Session<T> {
 
                     Box::pin(self.handle_auth(credentials)).await
                 } else {
-                    // TODO: This hack is temporary until the SASL library is developed
-                    self.receiver.state = request::State::Argument {
-                        request: Command::Auth {
-                            mechanism: mechanism.as_str().as_bytes().to_vec(),
-                            params: vec![],
-                        },
-                        num: 1,
-                        last_is_space: true,
-                    };
-
-                    self.write_bytes("+\r\n").await
+                    self.continue_sasl(mechanism, "").await
                 }
             }
+            Mechanism::ScramSha256 | Mechanism::ScramSha256Plus => {
+                self.handle_scram(mechanism, params).await
+            }
+            Mechanism::External => self.handle_external(params).await,
             _ => Err(trc::AuthEvent::Error
                 .into_err()
                 .details("Authentication mechanism not supported.")),
         }
     }
 
+    async fn handle_scram(
+        &mut self,
+        mechanism: Mechanism,
+        mut params: Vec<String>,
+    ) -> trc::Result<()> {
+        let state = self.scram.take();
+        let response = params
+            .pop()
+            .filter(|response| !response.is_empty() && response != "=")
+            .map(|response| {
+                if response == "*" {
+                    return Err(trc::AuthEvent::Error
+                        .into_err()
+                        .details("Authentication cancelled"));
+                }
+
+                base64_decode(response.as_bytes()).ok_or_else(|| {
+                    trc::AuthEvent::Error
+                        .into_err()
+                        .details("Invalid SASL challenge")
+                })
+            })
+            .transpose()?;
+
+        match (state, response) {
+            (None, None) => self.continue_sasl(mechanism, "").await,
+            (None, Some(client_first)) => {
+                let client_first = ScramClientFirst::parse(
+                    &client_first,
+                    mechanism == Mechanism::ScramSha256Plus,
+                    &self.stream.channel_bindings(&self.server),
+                )
+                .ok_or_else(|| {
+                    trc::AuthEvent::Error
+                        .into_err()
+                        .details("Invalid SASL challenge")
+                })?;
+                let auth = self.server.scram_begin(client_first).await?;
+                let challenge = auth.challenge();
+                self.scram = Some(ScramState::ClientFinal(Box::new(auth)));
+                self.continue_sasl(mechanism, &challenge).await
+            }
+            (Some(ScramState::ClientFinal(auth)), Some(client_final)) => {
+                let (access_token, server_final) = self
+                    .server
+                    .scram_authenticate(&auth, &client_final, self.session_id, self.remote_addr)
+                    .await
+                    .map_err(|err| self.auth_failure(err))?;
+                self.scram = Some(ScramState::Success(access_token));
+                self.continue_sasl(mechanism, &server_final).await
+            }
+            (Some(ScramState::Success(access_token)), None) => {
+                Box::pin(self.authenticated(access_token)).await
+            }
+            _ => Err(trc::AuthEvent::Error
+                .into_err()
+                .details("Unexpected SASL response")),
+        }
+    }
+
+    async fn handle_external(&mut self, mut params: Vec<String>) -> trc::Result<()> {
+        let (Some(mapping), Some(certificate)) = (
+            self.instance.acceptor.client_cert_mapping(),
+            self.stream.peer_certificate(),
+        ) else {
+            return Err(trc::AuthEvent::Error
+                .into_err()
+                .details("No client certificate was presented"));
+        };
+
+        let Some(response) = params.pop() else {
+            // Placeholder argument so that an empty client response completes the exchange
+            self.receiver.state = request::State::Argument {
+                request: Command::Auth {
+                    mechanism: Mechanism::External.as_str().as_bytes().to_vec(),
+                    params: vec![b"=".to_vec()],
+                },
+                num: 2,
+                last_is_space: true,
+            };
+            return self.write_bytes("+\r\n").await;
+        };
+
+        let authzid = match response.as_str() {
+            "" | "=" => None,
+            "*" => {
+                return Err(trc::AuthEvent::Error
+                    .into_err()
+                    .details("Authentication cancelled"));
+            }
+            response => Some(
+                base64_decode(response.as_bytes())
+                    .and_then(|authzid| String::from_utf8(authzid).ok())
+                    .ok_or_else(|| {
+                        trc::AuthEvent::Error
+                            .into_err()
+                            .details("Invalid SASL challenge")
+                    })?,
+            ),
+        };
+
+        match self
+            .server
+            .authenticate_certificate(
+                mapping,
+                certificate,
+                authzid.as_deref(),
+                self.session_id,
+                self.remote_addr,
+            )
+            .await
+        {
+            Ok(access_token) => Box::pin(self.authenticated(access_token)).await,
+            Err(err) => Err(self.auth_failure(err)),
+        }
+    }
+
+    async fn continue_sasl(&mut self, mechanism: Mechanism, challenge: &str) -> trc::Result<()> {
+        // TODO: This hack is temporary until the SASL library is developed
+        self.receiver.state = request::State::Argument {
+            request: Command::Auth {
+                mechanism: mechanism.as_str().as_bytes().to_vec(),
+                params: vec![],
+            },
+            num: 1,
+            last_is_space: true,
+        };
+
+        if challenge.is_empty() {
+            self.write_bytes("+\r\n").await
+        } else {
+            self.write_bytes(format!("+ {challenge}\r\n")).await
+        }
+    }
+
+    fn auth_failure(&mut self, err: trc::Error) -> trc::Error {
+        if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
+            match &self.state {
+                State::NotAuthenticated {
+                    auth_failures,
+                    username,
+                } if *auth_failures < self.server.core.imap.max_auth_failures => {
+                    self.state = State::NotAuthenticated {
+                        auth_failures: auth_failures + 1,
+                        username: username.clone(),
+                    };
+                }
+                _ => {
+                    return trc::AuthEvent::TooManyAttempts.into_err().caused_by(err);
+                }
+            }
+        }
+
+        err
+    }
+
     pub async fn handle_auth(&mut self, credentials: Credentials) -> trc::Result<()> {
         // Authenticate
-        let access_token = self
+        match self
             .server
             .authenticate(&AuthRequest::from_credentials(
                 credentials,
@@ -70,27 +220,14 @@ impl<T: SessionStream> Session<T> {
                 self.remote_addr,
             ))
             .await
-            .map_err(|err| {
-                if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
-                    match &self.state {
-                        State::NotAuthenticated {
-                            auth_failures,
-                            username,
-                        } if *auth_failures < self.server.core.imap.max_auth_failures => {
-                            self.state = State::NotAuthenticated {
-                                auth_failures: auth_failures + 1,
-                                username: username.clone(),
-                            };
-                        }
-                        _ => {
-                            return trc::AuthEvent::TooManyAttempts.into_err().caused_by(err);
-                        }
-                    }
-                }
+        {
+            Ok(access_token) => self.authenticated(access_token).await,
+            Err(err) => Err(self.auth_failure(err)),
+        }
+    }
 
-                err
-            })
-            .and_then(|token| token.assert_has_permission(Permission::Pop3Authenticate))?;
+    async fn authenticated(&mut self, access_token: AccessToken) -> trc::Result<()> {
+        let access_token = access_token.assert_has_permission(Permission::Pop3Authenticate)?;
 
         // Enforce concurrency limits
         let in_flight = match access_token.is_imap_request_allowed() {
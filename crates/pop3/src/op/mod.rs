@@ -18,11 +18,20 @@ pub mod list;
 
 impl<T: SessionStream> Session<T> {
     pub async fn handle_capa(&mut self) -> trc::Result<()> {
-        let mechanisms = if self.stream.is_tls() || self.server.core.imap.allow_plain_auth {
+        let mut mechanisms = if self.stream.is_tls() || self.server.core.imap.allow_plain_auth {
             vec![Mechanism::Plain, Mechanism::OAuthBearer, Mechanism::XOauth2]
         } else {
             vec![Mechanism::OAuthBearer, Mechanism::XOauth2]
         };
+        mechanisms.push(Mechanism::ScramSha256);
+        if self.stream.channel_bindings(&self.server).is_available() {
+            mechanisms.push(Mechanism::ScramSha256Plus);
+        }
+        if self.instance.acceptor.client_cert_mapping().is_some()
+            && self.stream.peer_certificate().is_some()
+        {
+            mechanisms.push(Mechanism::External);
+        }
 
         trc::event!(
             Pop3(trc::Pop3Event::Capabilities),
@@ -135,6 +135,7 @@ impl Mechanism {
             Mechanism::DigestMd5 => "DIGEST-MD5",
             Mechanism::ScramSha1 => "SCRAM-SHA-1",
             Mechanism::ScramSha256 => "SCRAM-SHA-256",
+            Mechanism::ScramSha256Plus => "SCRAM-SHA-256-PLUS",
             Mechanism::Apop => "APOP",
             Mechanism::Ntlm => "NTLM",
             Mechanism::Gssapi => "GSSAPI",
@@ -6,12 +6,23 @@
 
 // This file is auto-generated. Do not edit directly.
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum AccountArchiveFormat {
+    #[default]
+    Maildir = 0,
+    Mbox = 1,
+    EmlZip = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum AccountType {
     #[default]
     User = 0,
     Group = 1,
+    Resource = 2,
+    Location = 3,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -75,6 +86,24 @@ pub enum AlertEventType {
     Enabled = 1,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum AntivirusAction {
+    #[default]
+    Reject = 0,
+    Discard = 1,
+    Tag = 2,
+    Quarantine = 3,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum AntivirusScanMode {
+    #[default]
+    Message = 0,
+    Attachments = 1,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum ArchivedItemStatus {
@@ -198,6 +227,15 @@ pub enum BlockReason {
     Other = 5,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum BookingPolicy {
+    #[default]
+    AcceptIfFree = 0,
+    AcceptAll = 1,
+    Manual = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum CertificateManagementType {
@@ -289,6 +327,7 @@ pub enum CredentialType {
     Password = 0,
     AppPassword = 1,
     ApiKey = 2,
+    Passkey = 3,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -738,6 +777,8 @@ pub enum ExpressionConstant {
     Mixer = 16,
     Stanag4406 = 17,
     Nsep = 18,
+    ScramSha256 = 19,
+    ScramSha256Plus = 20,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -1292,6 +1333,34 @@ pub enum LookupStoreType {
     RedisSentinel = 6,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum MailingListFromRewrite {
+    #[default]
+    Dmarc = 0,
+    Always = 1,
+    Never = 2,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum MailingListPostingPolicy {
+    #[default]
+    Moderated = 0,
+    Subscribers = 1,
+    Moderators = 2,
+    Anyone = 3,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum MailingListSubscriptionPolicy {
+    #[default]
+    Confirm = 0,
+    Open = 1,
+    Closed = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum MessageFlag {
@@ -1493,6 +1562,15 @@ pub enum OvhEndpoint {
     SoyoustartCa = 5,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum PasskeyAlgorithm {
+    #[default]
+    Es256 = 0,
+    Ed25519 = 1,
+    Rs256 = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum PasswordHashAlgorithm {
@@ -1563,6 +1641,8 @@ pub enum Permission {
     JmapEmailSubmissionChanges = 42,
     JmapEmailSubmissionQuery = 43,
     JmapEmailSubmissionQueryChanges = 44,
+    JmapMdnSend = 669,
+    JmapMdnParse = 670,
     JmapEmailSubmissionCreate = 45,
     JmapEmailSubmissionUpdate = 46,
     JmapEmailSubmissionDestroy = 47,
@@ -1642,6 +1722,17 @@ pub enum Permission {
     JmapCalendarEventNotificationCreate = 120,
     JmapCalendarEventNotificationUpdate = 121,
     JmapCalendarEventNotificationDestroy = 122,
+    JmapTaskListGet = 671,
+    JmapTaskListChanges = 672,
+    JmapTaskListCreate = 673,
+    JmapTaskListUpdate = 674,
+    JmapTaskListDestroy = 675,
+    JmapTaskGet = 676,
+    JmapTaskChanges = 677,
+    JmapTaskQuery = 678,
+    JmapTaskCreate = 679,
+    JmapTaskUpdate = 680,
+    JmapTaskDestroy = 681,
     JmapParticipantIdentityGet = 123,
     JmapParticipantIdentityChanges = 124,
     JmapParticipantIdentityCreate = 125,
@@ -1676,6 +1767,15 @@ pub enum Permission {
     ImapStore = 154,
     ImapSubscribe = 155,
     ImapThread = 156,
+    ImapGenUrlAuth = 660,
+    ImapResetKey = 661,
+    ImapUrlFetch = 662,
+    ImapNotify = 663,
+    ImapGetMetadata = 664,
+    ImapSetMetadata = 665,
+    ImapCompress = 666,
+    ImapReplace = 667,
+    ImapSetQuota = 668,
     Pop3Authenticate = 157,
     Pop3List = 158,
     Pop3Uidl = 159,
@@ -1805,6 +1905,11 @@ pub enum Permission {
     SysArchivedItemUpdate = 283,
     SysArchivedItemDestroy = 284,
     SysArchivedItemQuery = 285,
+    SysQuarantinedMessageGet = 688,
+    SysQuarantinedMessageCreate = 689,
+    SysQuarantinedMessageUpdate = 690,
+    SysQuarantinedMessageDestroy = 691,
+    SysQuarantinedMessageQuery = 692,
     SysArfExternalReportGet = 286,
     SysArfExternalReportCreate = 287,
     SysArfExternalReportUpdate = 288,
@@ -1950,6 +2055,10 @@ pub enum Permission {
     SysMetricsUpdate = 428,
     SysMetricsStoreGet = 429,
     SysMetricsStoreUpdate = 430,
+    SysMtaAntivirusGet = 684,
+    SysMtaAntivirusUpdate = 685,
+    SysQuarantineSettingsGet = 686,
+    SysQuarantineSettingsUpdate = 687,
     SysMtaConnectionStrategyGet = 431,
     SysMtaConnectionStrategyCreate = 432,
     SysMtaConnectionStrategyUpdate = 433,
@@ -2032,6 +2141,11 @@ pub enum Permission {
     SysOAuthClientQuery = 510,
     SysOidcProviderGet = 511,
     SysOidcProviderUpdate = 512,
+    SysPasskeyGet = 694,
+    SysPasskeyCreate = 695,
+    SysPasskeyUpdate = 696,
+    SysPasskeyDestroy = 697,
+    SysPasskeyQuery = 698,
     SysPublicKeyGet = 513,
     SysPublicKeyCreate = 514,
     SysPublicKeyUpdate = 515,
@@ -2135,6 +2249,9 @@ pub enum Permission {
     TaskAcmeRenewal = 613,
     TaskDkimManagement = 614,
     TaskDnsManagement = 615,
+    TaskAccountExport = 682,
+    TaskAccountImport = 683,
+    TaskQuarantineDigest = 693,
     SysTaskGet = 616,
     SysTaskCreate = 617,
     SysTaskUpdate = 618,
@@ -2230,6 +2347,14 @@ pub enum PublicTextType {
     File = 2,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum QuarantineStatus {
+    #[default]
+    Quarantined = 0,
+    RequestRelease = 1,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum QueueExpiryType {
@@ -2244,6 +2369,7 @@ pub enum RecipientFlag {
     #[default]
     DsnSent = 0,
     SpamPayload = 1,
+    Quarantined = 2,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2766,6 +2892,9 @@ pub enum TaskType {
     AcmeRenewal = 15,
     DkimManagement = 16,
     DnsManagement = 17,
+    AccountExport = 18,
+    AccountImport = 19,
+    QuarantineDigest = 20,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -3404,6 +3533,24 @@ pub enum TlsCipherSuite {
     TlsEcdheRsaWithChacha20Poly1305Sha256 = 8,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum TlsClientAuth {
+    #[default]
+    Disabled = 0,
+    Optional = 1,
+    Required = 2,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum TlsClientCertMapping {
+    #[default]
+    SanEmail = 0,
+    SubjectCommonName = 1,
+    Fingerprint = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum TlsPolicyType {
@@ -3960,6 +4107,8 @@ pub static MTA_AUTH_TYPE_CONSTANT: &[ExpressionConstant] = &[
     ExpressionConstant::Plain,
     ExpressionConstant::Xoauth2,
     ExpressionConstant::Oauthbearer,
+    ExpressionConstant::ScramSha256,
+    ExpressionConstant::ScramSha256Plus,
 ];
 
 pub static MTA_IP_STRATEGY_CONSTANT: &[ExpressionConstant] = &[
@@ -8,12 +8,67 @@
 
 use crate::schema::prelude::*;
 
+impl EnumImpl for AccountArchiveFormat {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"maildir" => AccountArchiveFormat::Maildir,
+            b"mbox" => AccountArchiveFormat::Mbox,
+            b"emlZip" => AccountArchiveFormat::EmlZip,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AccountArchiveFormat::Maildir => "maildir",
+            AccountArchiveFormat::Mbox => "mbox",
+            AccountArchiveFormat::EmlZip => "emlZip",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(AccountArchiveFormat::Maildir),
+            1 => Some(AccountArchiveFormat::Mbox),
+            2 => Some(AccountArchiveFormat::EmlZip),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for AccountArchiveFormat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AccountArchiveFormat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for AccountType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
             value.as_bytes(),
             b"User" => AccountType::User,
             b"Group" => AccountType::Group,
+            b"Resource" => AccountType::Resource,
+            b"Location" => AccountType::Location,
         }
     }
 
@@ -21,6 +76,8 @@ impl EnumImpl for AccountType {
         match self {
             AccountType::User => "User",
             AccountType::Group => "Group",
+            AccountType::Resource => "Resource",
+            AccountType::Location => "Location",
         }
     }
 
@@ -32,11 +89,13 @@ impl EnumImpl for AccountType {
         match id {
             0 => Some(AccountType::User),
             1 => Some(AccountType::Group),
+            2 => Some(AccountType::Resource),
+            3 => Some(AccountType::Location),
             _ => None,
         }
     }
 
-    const COUNT: usize = 2;
+    const COUNT: usize = 4;
 }
 
 impl serde::Serialize for AccountType {
@@ -397,6 +456,112 @@ impl<'de> serde::Deserialize<'de> for AlertEventType {
     }
 }
 
+impl EnumImpl for AntivirusAction {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"reject" => AntivirusAction::Reject,
+            b"discard" => AntivirusAction::Discard,
+            b"tag" => AntivirusAction::Tag,
+            b"quarantine" => AntivirusAction::Quarantine,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AntivirusAction::Reject => "reject",
+            AntivirusAction::Discard => "discard",
+            AntivirusAction::Tag => "tag",
+            AntivirusAction::Quarantine => "quarantine",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(AntivirusAction::Reject),
+            1 => Some(AntivirusAction::Discard),
+            2 => Some(AntivirusAction::Tag),
+            3 => Some(AntivirusAction::Quarantine),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 4;
+}
+
+impl serde::Serialize for AntivirusAction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AntivirusAction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for AntivirusScanMode {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"message" => AntivirusScanMode::Message,
+            b"attachments" => AntivirusScanMode::Attachments,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AntivirusScanMode::Message => "message",
+            AntivirusScanMode::Attachments => "attachments",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(AntivirusScanMode::Message),
+            1 => Some(AntivirusScanMode::Attachments),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for AntivirusScanMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AntivirusScanMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for ArchivedItemStatus {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -1052,6 +1217,59 @@ impl<'de> serde::Deserialize<'de> for BlockReason {
     }
 }
 
+impl EnumImpl for BookingPolicy {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"acceptIfFree" => BookingPolicy::AcceptIfFree,
+            b"acceptAll" => BookingPolicy::AcceptAll,
+            b"manual" => BookingPolicy::Manual,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            BookingPolicy::AcceptIfFree => "acceptIfFree",
+            BookingPolicy::AcceptAll => "acceptAll",
+            BookingPolicy::Manual => "manual",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(BookingPolicy::AcceptIfFree),
+            1 => Some(BookingPolicy::AcceptAll),
+            2 => Some(BookingPolicy::Manual),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for BookingPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BookingPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for CertificateManagementType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -1519,6 +1737,7 @@ impl EnumImpl for CredentialType {
             b"Password" => CredentialType::Password,
             b"AppPassword" => CredentialType::AppPassword,
             b"ApiKey" => CredentialType::ApiKey,
+            b"Passkey" => CredentialType::Passkey,
         }
     }
 
@@ -1527,6 +1746,7 @@ impl EnumImpl for CredentialType {
             CredentialType::Password => "Password",
             CredentialType::AppPassword => "AppPassword",
             CredentialType::ApiKey => "ApiKey",
+            CredentialType::Passkey => "Passkey",
         }
     }
 
@@ -1539,11 +1759,12 @@ impl EnumImpl for CredentialType {
             0 => Some(CredentialType::Password),
             1 => Some(CredentialType::AppPassword),
             2 => Some(CredentialType::ApiKey),
+            3 => Some(CredentialType::Passkey),
             _ => None,
         }
     }
 
-    const COUNT: usize = 3;
+    const COUNT: usize = 4;
 }
 
 impl serde::Serialize for CredentialType {
@@ -3592,6 +3813,8 @@ impl EnumImpl for ExpressionConstant {
             b"mixer" => ExpressionConstant::Mixer,
             b"stanag4406" => ExpressionConstant::Stanag4406,
             b"nsep" => ExpressionConstant::Nsep,
+            b"scram_sha_256" => ExpressionConstant::ScramSha256,
+            b"scram_sha_256_plus" => ExpressionConstant::ScramSha256Plus,
         }
     }
 
@@ -3616,6 +3839,8 @@ impl EnumImpl for ExpressionConstant {
             ExpressionConstant::Mixer => "mixer",
             ExpressionConstant::Stanag4406 => "stanag4406",
             ExpressionConstant::Nsep => "nsep",
+            ExpressionConstant::ScramSha256 => "scram_sha_256",
+            ExpressionConstant::ScramSha256Plus => "scram_sha_256_plus",
         }
     }
 
@@ -3644,11 +3869,13 @@ impl EnumImpl for ExpressionConstant {
             16 => Some(ExpressionConstant::Mixer),
             17 => Some(ExpressionConstant::Stanag4406),
             18 => Some(ExpressionConstant::Nsep),
+            19 => Some(ExpressionConstant::ScramSha256),
+            20 => Some(ExpressionConstant::ScramSha256Plus),
             _ => None,
         }
     }
 
-    const COUNT: usize = 19;
+    const COUNT: usize = 21;
 }
 
 impl serde::Serialize for ExpressionConstant {
@@ -5668,6 +5895,168 @@ impl<'de> serde::Deserialize<'de> for LookupStoreType {
     }
 }
 
+impl EnumImpl for MailingListFromRewrite {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"dmarc" => MailingListFromRewrite::Dmarc,
+            b"always" => MailingListFromRewrite::Always,
+            b"never" => MailingListFromRewrite::Never,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MailingListFromRewrite::Dmarc => "dmarc",
+            MailingListFromRewrite::Always => "always",
+            MailingListFromRewrite::Never => "never",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(MailingListFromRewrite::Dmarc),
+            1 => Some(MailingListFromRewrite::Always),
+            2 => Some(MailingListFromRewrite::Never),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for MailingListFromRewrite {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MailingListFromRewrite {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for MailingListPostingPolicy {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"moderated" => MailingListPostingPolicy::Moderated,
+            b"subscribers" => MailingListPostingPolicy::Subscribers,
+            b"moderators" => MailingListPostingPolicy::Moderators,
+            b"anyone" => MailingListPostingPolicy::Anyone,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MailingListPostingPolicy::Moderated => "moderated",
+            MailingListPostingPolicy::Subscribers => "subscribers",
+            MailingListPostingPolicy::Moderators => "moderators",
+            MailingListPostingPolicy::Anyone => "anyone",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(MailingListPostingPolicy::Moderated),
+            1 => Some(MailingListPostingPolicy::Subscribers),
+            2 => Some(MailingListPostingPolicy::Moderators),
+            3 => Some(MailingListPostingPolicy::Anyone),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 4;
+}
+
+impl serde::Serialize for MailingListPostingPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MailingListPostingPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for MailingListSubscriptionPolicy {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"confirm" => MailingListSubscriptionPolicy::Confirm,
+            b"open" => MailingListSubscriptionPolicy::Open,
+            b"closed" => MailingListSubscriptionPolicy::Closed,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MailingListSubscriptionPolicy::Confirm => "confirm",
+            MailingListSubscriptionPolicy::Open => "open",
+            MailingListSubscriptionPolicy::Closed => "closed",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(MailingListSubscriptionPolicy::Confirm),
+            1 => Some(MailingListSubscriptionPolicy::Open),
+            2 => Some(MailingListSubscriptionPolicy::Closed),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for MailingListSubscriptionPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MailingListSubscriptionPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for MessageFlag {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -6765,6 +7154,59 @@ impl<'de> serde::Deserialize<'de> for OvhEndpoint {
     }
 }
 
+impl EnumImpl for PasskeyAlgorithm {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"es256" => PasskeyAlgorithm::Es256,
+            b"ed25519" => PasskeyAlgorithm::Ed25519,
+            b"rs256" => PasskeyAlgorithm::Rs256,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            PasskeyAlgorithm::Es256 => "es256",
+            PasskeyAlgorithm::Ed25519 => "ed25519",
+            PasskeyAlgorithm::Rs256 => "rs256",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(PasskeyAlgorithm::Es256),
+            1 => Some(PasskeyAlgorithm::Ed25519),
+            2 => Some(PasskeyAlgorithm::Rs256),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for PasskeyAlgorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PasskeyAlgorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for PasswordHashAlgorithm {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -6930,6 +7372,8 @@ impl EnumImpl for Permission {
             b"jmapEmailSubmissionChanges" => Permission::JmapEmailSubmissionChanges,
             b"jmapEmailSubmissionQuery" => Permission::JmapEmailSubmissionQuery,
             b"jmapEmailSubmissionQueryChanges" => Permission::JmapEmailSubmissionQueryChanges,
+            b"jmapMdnSend" => Permission::JmapMdnSend,
+            b"jmapMdnParse" => Permission::JmapMdnParse,
             b"jmapEmailSubmissionCreate" => Permission::JmapEmailSubmissionCreate,
             b"jmapEmailSubmissionUpdate" => Permission::JmapEmailSubmissionUpdate,
             b"jmapEmailSubmissionDestroy" => Permission::JmapEmailSubmissionDestroy,
@@ -7009,6 +7453,17 @@ impl EnumImpl for Permission {
             b"jmapCalendarEventNotificationCreate" => Permission::JmapCalendarEventNotificationCreate,
             b"jmapCalendarEventNotificationUpdate" => Permission::JmapCalendarEventNotificationUpdate,
             b"jmapCalendarEventNotificationDestroy" => Permission::JmapCalendarEventNotificationDestroy,
+            b"jmapTaskListGet" => Permission::JmapTaskListGet,
+            b"jmapTaskListChanges" => Permission::JmapTaskListChanges,
+            b"jmapTaskListCreate" => Permission::JmapTaskListCreate,
+            b"jmapTaskListUpdate" => Permission::JmapTaskListUpdate,
+            b"jmapTaskListDestroy" => Permission::JmapTaskListDestroy,
+            b"jmapTaskGet" => Permission::JmapTaskGet,
+            b"jmapTaskChanges" => Permission::JmapTaskChanges,
+            b"jmapTaskQuery" => Permission::JmapTaskQuery,
+            b"jmapTaskCreate" => Permission::JmapTaskCreate,
+            b"jmapTaskUpdate" => Permission::JmapTaskUpdate,
+            b"jmapTaskDestroy" => Permission::JmapTaskDestroy,
             b"jmapParticipantIdentityGet" => Permission::JmapParticipantIdentityGet,
             b"jmapParticipantIdentityChanges" => Permission::JmapParticipantIdentityChanges,
             b"jmapParticipantIdentityCreate" => Permission::JmapParticipantIdentityCreate,
@@ -7043,6 +7498,15 @@ impl EnumImpl for Permission {
             b"imapStore" => Permission::ImapStore,
             b"imapSubscribe" => Permission::ImapSubscribe,
             b"imapThread" => Permission::ImapThread,
+            b"imapGenUrlAuth" => Permission::ImapGenUrlAuth,
+            b"imapResetKey" => Permission::ImapResetKey,
+            b"imapUrlFetch" => Permission::ImapUrlFetch,
+            b"imapNotify" => Permission::ImapNotify,
+            b"imapGetMetadata" => Permission::ImapGetMetadata,
+            b"imapSetMetadata" => Permission::ImapSetMetadata,
+            b"imapCompress" => Permission::ImapCompress,
+            b"imapReplace" => Permission::ImapReplace,
+            b"imapSetQuota" => Permission::ImapSetQuota,
             b"pop3Authenticate" => Permission::Pop3Authenticate,
             b"pop3List" => Permission::Pop3List,
             b"pop3Uidl" => Permission::Pop3Uidl,
@@ -7172,6 +7636,11 @@ impl EnumImpl for Permission {
             b"sysArchivedItemUpdate" => Permission::SysArchivedItemUpdate,
             b"sysArchivedItemDestroy" => Permission::SysArchivedItemDestroy,
             b"sysArchivedItemQuery" => Permission::SysArchivedItemQuery,
+            b"sysQuarantinedMessageGet" => Permission::SysQuarantinedMessageGet,
+            b"sysQuarantinedMessageCreate" => Permission::SysQuarantinedMessageCreate,
+            b"sysQuarantinedMessageUpdate" => Permission::SysQuarantinedMessageUpdate,
+            b"sysQuarantinedMessageDestroy" => Permission::SysQuarantinedMessageDestroy,
+            b"sysQuarantinedMessageQuery" => Permission::SysQuarantinedMessageQuery,
             b"sysArfExternalReportGet" => Permission::SysArfExternalReportGet,
             b"sysArfExternalReportCreate" => Permission::SysArfExternalReportCreate,
             b"sysArfExternalReportUpdate" => Permission::SysArfExternalReportUpdate,
@@ -7317,6 +7786,10 @@ impl EnumImpl for Permission {
             b"sysMetricsUpdate" => Permission::SysMetricsUpdate,
             b"sysMetricsStoreGet" => Permission::SysMetricsStoreGet,
             b"sysMetricsStoreUpdate" => Permission::SysMetricsStoreUpdate,
+            b"sysMtaAntivirusGet" => Permission::SysMtaAntivirusGet,
+            b"sysMtaAntivirusUpdate" => Permission::SysMtaAntivirusUpdate,
+            b"sysQuarantineSettingsGet" => Permission::SysQuarantineSettingsGet,
+            b"sysQuarantineSettingsUpdate" => Permission::SysQuarantineSettingsUpdate,
             b"sysMtaConnectionStrategyGet" => Permission::SysMtaConnectionStrategyGet,
             b"sysMtaConnectionStrategyCreate" => Permission::SysMtaConnectionStrategyCreate,
             b"sysMtaConnectionStrategyUpdate" => Permission::SysMtaConnectionStrategyUpdate,
@@ -7399,6 +7872,11 @@ impl EnumImpl for Permission {
             b"sysOAuthClientQuery" => Permission::SysOAuthClientQuery,
             b"sysOidcProviderGet" => Permission::SysOidcProviderGet,
             b"sysOidcProviderUpdate" => Permission::SysOidcProviderUpdate,
+            b"sysPasskeyGet" => Permission::SysPasskeyGet,
+            b"sysPasskeyCreate" => Permission::SysPasskeyCreate,
+            b"sysPasskeyUpdate" => Permission::SysPasskeyUpdate,
+            b"sysPasskeyDestroy" => Permission::SysPasskeyDestroy,
+            b"sysPasskeyQuery" => Permission::SysPasskeyQuery,
             b"sysPublicKeyGet" => Permission::SysPublicKeyGet,
             b"sysPublicKeyCreate" => Permission::SysPublicKeyCreate,
             b"sysPublicKeyUpdate" => Permission::SysPublicKeyUpdate,
@@ -7502,6 +7980,9 @@ impl EnumImpl for Permission {
             b"taskAcmeRenewal" => Permission::TaskAcmeRenewal,
             b"taskDkimManagement" => Permission::TaskDkimManagement,
             b"taskDnsManagement" => Permission::TaskDnsManagement,
+            b"taskAccountExport" => Permission::TaskAccountExport,
+            b"taskAccountImport" => Permission::TaskAccountImport,
+            b"taskQuarantineDigest" => Permission::TaskQuarantineDigest,
             b"sysTaskGet" => Permission::SysTaskGet,
             b"sysTaskCreate" => Permission::SysTaskCreate,
             b"sysTaskUpdate" => Permission::SysTaskUpdate,
@@ -7596,6 +8077,8 @@ impl EnumImpl for Permission {
             Permission::JmapEmailSubmissionChanges => "jmapEmailSubmissionChanges",
             Permission::JmapEmailSubmissionQuery => "jmapEmailSubmissionQuery",
             Permission::JmapEmailSubmissionQueryChanges => "jmapEmailSubmissionQueryChanges",
+            Permission::JmapMdnSend => "jmapMdnSend",
+            Permission::JmapMdnParse => "jmapMdnParse",
             Permission::JmapEmailSubmissionCreate => "jmapEmailSubmissionCreate",
             Permission::JmapEmailSubmissionUpdate => "jmapEmailSubmissionUpdate",
             Permission::JmapEmailSubmissionDestroy => "jmapEmailSubmissionDestroy",
@@ -7685,6 +8168,17 @@ impl EnumImpl for Permission {
             Permission::JmapCalendarEventNotificationDestroy => {
                 "jmapCalendarEventNotificationDestroy"
             }
+            Permission::JmapTaskListGet => "jmapTaskListGet",
+            Permission::JmapTaskListChanges => "jmapTaskListChanges",
+            Permission::JmapTaskListCreate => "jmapTaskListCreate",
+            Permission::JmapTaskListUpdate => "jmapTaskListUpdate",
+            Permission::JmapTaskListDestroy => "jmapTaskListDestroy",
+            Permission::JmapTaskGet => "jmapTaskGet",
+            Permission::JmapTaskChanges => "jmapTaskChanges",
+            Permission::JmapTaskQuery => "jmapTaskQuery",
+            Permission::JmapTaskCreate => "jmapTaskCreate",
+            Permission::JmapTaskUpdate => "jmapTaskUpdate",
+            Permission::JmapTaskDestroy => "jmapTaskDestroy",
             Permission::JmapParticipantIdentityGet => "jmapParticipantIdentityGet",
             Permission::JmapParticipantIdentityChanges => "jmapParticipantIdentityChanges",
             Permission::JmapParticipantIdentityCreate => "jmapParticipantIdentityCreate",
@@ -7719,6 +8213,15 @@ impl EnumImpl for Permission {
             Permission::ImapStore => "imapStore",
             Permission::ImapSubscribe => "imapSubscribe",
             Permission::ImapThread => "imapThread",
+            Permission::ImapGenUrlAuth => "imapGenUrlAuth",
+            Permission::ImapResetKey => "imapResetKey",
+            Permission::ImapUrlFetch => "imapUrlFetch",
+            Permission::ImapNotify => "imapNotify",
+            Permission::ImapGetMetadata => "imapGetMetadata",
+            Permission::ImapSetMetadata => "imapSetMetadata",
+            Permission::ImapCompress => "imapCompress",
+            Permission::ImapReplace => "imapReplace",
+            Permission::ImapSetQuota => "imapSetQuota",
             Permission::Pop3Authenticate => "pop3Authenticate",
             Permission::Pop3List => "pop3List",
             Permission::Pop3Uidl => "pop3Uidl",
@@ -7848,6 +8351,11 @@ impl EnumImpl for Permission {
             Permission::SysArchivedItemUpdate => "sysArchivedItemUpdate",
             Permission::SysArchivedItemDestroy => "sysArchivedItemDestroy",
             Permission::SysArchivedItemQuery => "sysArchivedItemQuery",
+            Permission::SysQuarantinedMessageGet => "sysQuarantinedMessageGet",
+            Permission::SysQuarantinedMessageCreate => "sysQuarantinedMessageCreate",
+            Permission::SysQuarantinedMessageUpdate => "sysQuarantinedMessageUpdate",
+            Permission::SysQuarantinedMessageDestroy => "sysQuarantinedMessageDestroy",
+            Permission::SysQuarantinedMessageQuery => "sysQuarantinedMessageQuery",
             Permission::SysArfExternalReportGet => "sysArfExternalReportGet",
             Permission::SysArfExternalReportCreate => "sysArfExternalReportCreate",
             Permission::SysArfExternalReportUpdate => "sysArfExternalReportUpdate",
@@ -7993,6 +8501,10 @@ impl EnumImpl for Permission {
             Permission::SysMetricsUpdate => "sysMetricsUpdate",
             Permission::SysMetricsStoreGet => "sysMetricsStoreGet",
             Permission::SysMetricsStoreUpdate => "sysMetricsStoreUpdate",
+            Permission::SysMtaAntivirusGet => "sysMtaAntivirusGet",
+            Permission::SysMtaAntivirusUpdate => "sysMtaAntivirusUpdate",
+            Permission::SysQuarantineSettingsGet => "sysQuarantineSettingsGet",
+            Permission::SysQuarantineSettingsUpdate => "sysQuarantineSettingsUpdate",
             Permission::SysMtaConnectionStrategyGet => "sysMtaConnectionStrategyGet",
             Permission::SysMtaConnectionStrategyCreate => "sysMtaConnectionStrategyCreate",
             Permission::SysMtaConnectionStrategyUpdate => "sysMtaConnectionStrategyUpdate",
@@ -8075,6 +8587,11 @@ impl EnumImpl for Permission {
             Permission::SysOAuthClientQuery => "sysOAuthClientQuery",
             Permission::SysOidcProviderGet => "sysOidcProviderGet",
             Permission::SysOidcProviderUpdate => "sysOidcProviderUpdate",
+            Permission::SysPasskeyGet => "sysPasskeyGet",
+            Permission::SysPasskeyCreate => "sysPasskeyCreate",
+            Permission::SysPasskeyUpdate => "sysPasskeyUpdate",
+            Permission::SysPasskeyDestroy => "sysPasskeyDestroy",
+            Permission::SysPasskeyQuery => "sysPasskeyQuery",
             Permission::SysPublicKeyGet => "sysPublicKeyGet",
             Permission::SysPublicKeyCreate => "sysPublicKeyCreate",
             Permission::SysPublicKeyUpdate => "sysPublicKeyUpdate",
@@ -8178,6 +8695,9 @@ impl EnumImpl for Permission {
             Permission::TaskAcmeRenewal => "taskAcmeRenewal",
             Permission::TaskDkimManagement => "taskDkimManagement",
             Permission::TaskDnsManagement => "taskDnsManagement",
+            Permission::TaskAccountExport => "taskAccountExport",
+            Permission::TaskAccountImport => "taskAccountImport",
+            Permission::TaskQuarantineDigest => "taskQuarantineDigest",
             Permission::SysTaskGet => "sysTaskGet",
             Permission::SysTaskCreate => "sysTaskCreate",
             Permission::SysTaskUpdate => "sysTaskUpdate",
@@ -8275,6 +8795,8 @@ impl EnumImpl for Permission {
             42 => Some(Permission::JmapEmailSubmissionChanges),
             43 => Some(Permission::JmapEmailSubmissionQuery),
             44 => Some(Permission::JmapEmailSubmissionQueryChanges),
+            669 => Some(Permission::JmapMdnSend),
+            670 => Some(Permission::JmapMdnParse),
             45 => Some(Permission::JmapEmailSubmissionCreate),
             46 => Some(Permission::JmapEmailSubmissionUpdate),
             47 => Some(Permission::JmapEmailSubmissionDestroy),
@@ -8354,6 +8876,17 @@ impl EnumImpl for Permission {
             120 => Some(Permission::JmapCalendarEventNotificationCreate),
             121 => Some(Permission::JmapCalendarEventNotificationUpdate),
             122 => Some(Permission::JmapCalendarEventNotificationDestroy),
+            671 => Some(Permission::JmapTaskListGet),
+            672 => Some(Permission::JmapTaskListChanges),
+            673 => Some(Permission::JmapTaskListCreate),
+            674 => Some(Permission::JmapTaskListUpdate),
+            675 => Some(Permission::JmapTaskListDestroy),
+            676 => Some(Permission::JmapTaskGet),
+            677 => Some(Permission::JmapTaskChanges),
+            678 => Some(Permission::JmapTaskQuery),
+            679 => Some(Permission::JmapTaskCreate),
+            680 => Some(Permission::JmapTaskUpdate),
+            681 => Some(Permission::JmapTaskDestroy),
             123 => Some(Permission::JmapParticipantIdentityGet),
             124 => Some(Permission::JmapParticipantIdentityChanges),
             125 => Some(Permission::JmapParticipantIdentityCreate),
@@ -8388,6 +8921,15 @@ impl EnumImpl for Permission {
             154 => Some(Permission::ImapStore),
             155 => Some(Permission::ImapSubscribe),
             156 => Some(Permission::ImapThread),
+            660 => Some(Permission::ImapGenUrlAuth),
+            661 => Some(Permission::ImapResetKey),
+            662 => Some(Permission::ImapUrlFetch),
+            663 => Some(Permission::ImapNotify),
+            664 => Some(Permission::ImapGetMetadata),
+            665 => Some(Permission::ImapSetMetadata),
+            666 => Some(Permission::ImapCompress),
+            667 => Some(Permission::ImapReplace),
+            668 => Some(Permission::ImapSetQuota),
             157 => Some(Permission::Pop3Authenticate),
             158 => Some(Permission::Pop3List),
             159 => Some(Permission::Pop3Uidl),
@@ -8517,6 +9059,11 @@ impl EnumImpl for Permission {
             283 => Some(Permission::SysArchivedItemUpdate),
             284 => Some(Permission::SysArchivedItemDestroy),
             285 => Some(Permission::SysArchivedItemQuery),
+            688 => Some(Permission::SysQuarantinedMessageGet),
+            689 => Some(Permission::SysQuarantinedMessageCreate),
+            690 => Some(Permission::SysQuarantinedMessageUpdate),
+            691 => Some(Permission::SysQuarantinedMessageDestroy),
+            692 => Some(Permission::SysQuarantinedMessageQuery),
             286 => Some(Permission::SysArfExternalReportGet),
             287 => Some(Permission::SysArfExternalReportCreate),
             288 => Some(Permission::SysArfExternalReportUpdate),
@@ -8662,6 +9209,10 @@ impl EnumImpl for Permission {
             428 => Some(Permission::SysMetricsUpdate),
             429 => Some(Permission::SysMetricsStoreGet),
             430 => Some(Permission::SysMetricsStoreUpdate),
+            684 => Some(Permission::SysMtaAntivirusGet),
+            685 => Some(Permission::SysMtaAntivirusUpdate),
+            686 => Some(Permission::SysQuarantineSettingsGet),
+            687 => Some(Permission::SysQuarantineSettingsUpdate),
             431 => Some(Permission::SysMtaConnectionStrategyGet),
             432 => Some(Permission::SysMtaConnectionStrategyCreate),
             433 => Some(Permission::SysMtaConnectionStrategyUpdate),
@@ -8744,6 +9295,11 @@ impl EnumImpl for Permission {
             510 => Some(Permission::SysOAuthClientQuery),
             511 => Some(Permission::SysOidcProviderGet),
             512 => Some(Permission::SysOidcProviderUpdate),
+            694 => Some(Permission::SysPasskeyGet),
+            695 => Some(Permission::SysPasskeyCreate),
+            696 => Some(Permission::SysPasskeyUpdate),
+            697 => Some(Permission::SysPasskeyDestroy),
+            698 => Some(Permission::SysPasskeyQuery),
             513 => Some(Permission::SysPublicKeyGet),
             514 => Some(Permission::SysPublicKeyCreate),
             515 => Some(Permission::SysPublicKeyUpdate),
@@ -8847,6 +9403,9 @@ impl EnumImpl for Permission {
             613 => Some(Permission::TaskAcmeRenewal),
             614 => Some(Permission::TaskDkimManagement),
             615 => Some(Permission::TaskDnsManagement),
+            682 => Some(Permission::TaskAccountExport),
+            683 => Some(Permission::TaskAccountImport),
+            693 => Some(Permission::TaskQuarantineDigest),
             616 => Some(Permission::SysTaskGet),
             617 => Some(Permission::SysTaskCreate),
             618 => Some(Permission::SysTaskUpdate),
@@ -8894,7 +9453,7 @@ impl EnumImpl for Permission {
         }
     }
 
-    const COUNT: usize = 660;
+    const COUNT: usize = 699;
 }
 
 impl serde::Serialize for Permission {
@@ -9196,6 +9755,56 @@ impl<'de> serde::Deserialize<'de> for PublicTextType {
     }
 }
 
+impl EnumImpl for QuarantineStatus {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"quarantined" => QuarantineStatus::Quarantined,
+            b"requestRelease" => QuarantineStatus::RequestRelease,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            QuarantineStatus::Quarantined => "quarantined",
+            QuarantineStatus::RequestRelease => "requestRelease",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(QuarantineStatus::Quarantined),
+            1 => Some(QuarantineStatus::RequestRelease),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for QuarantineStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for QuarantineStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for QueueExpiryType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -9252,6 +9861,7 @@ impl EnumImpl for RecipientFlag {
             value.as_bytes(),
             b"dsnSent" => RecipientFlag::DsnSent,
             b"spamPayload" => RecipientFlag::SpamPayload,
+            b"quarantined" => RecipientFlag::Quarantined,
         }
     }
 
@@ -9259,6 +9869,7 @@ impl EnumImpl for RecipientFlag {
         match self {
             RecipientFlag::DsnSent => "dsnSent",
             RecipientFlag::SpamPayload => "spamPayload",
+            RecipientFlag::Quarantined => "quarantined",
         }
     }
 
@@ -9270,11 +9881,12 @@ impl EnumImpl for RecipientFlag {
         match id {
             0 => Some(RecipientFlag::DsnSent),
             1 => Some(RecipientFlag::SpamPayload),
+            2 => Some(RecipientFlag::Quarantined),
             _ => None,
         }
     }
 
-    const COUNT: usize = 2;
+    const COUNT: usize = 3;
 }
 
 impl serde::Serialize for RecipientFlag {
@@ -11776,6 +12388,9 @@ impl EnumImpl for TaskType {
             b"AcmeRenewal" => TaskType::AcmeRenewal,
             b"DkimManagement" => TaskType::DkimManagement,
             b"DnsManagement" => TaskType::DnsManagement,
+            b"AccountExport" => TaskType::AccountExport,
+            b"AccountImport" => TaskType::AccountImport,
+            b"QuarantineDigest" => TaskType::QuarantineDigest,
         }
     }
 
@@ -11799,6 +12414,9 @@ impl EnumImpl for TaskType {
             TaskType::AcmeRenewal => "AcmeRenewal",
             TaskType::DkimManagement => "DkimManagement",
             TaskType::DnsManagement => "DnsManagement",
+            TaskType::AccountExport => "AccountExport",
+            TaskType::AccountImport => "AccountImport",
+            TaskType::QuarantineDigest => "QuarantineDigest",
         }
     }
 
@@ -11826,11 +12444,14 @@ impl EnumImpl for TaskType {
             15 => Some(TaskType::AcmeRenewal),
             16 => Some(TaskType::DkimManagement),
             17 => Some(TaskType::DnsManagement),
+            18 => Some(TaskType::AccountExport),
+            19 => Some(TaskType::AccountImport),
+            20 => Some(TaskType::QuarantineDigest),
             _ => None,
         }
     }
 
-    const COUNT: usize = 18;
+    const COUNT: usize = 21;
 }
 
 impl serde::Serialize for TaskType {
@@ -13852,6 +14473,112 @@ impl<'de> serde::Deserialize<'de> for TlsCipherSuite {
     }
 }
 
+impl EnumImpl for TlsClientAuth {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"disabled" => TlsClientAuth::Disabled,
+            b"optional" => TlsClientAuth::Optional,
+            b"required" => TlsClientAuth::Required,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TlsClientAuth::Disabled => "disabled",
+            TlsClientAuth::Optional => "optional",
+            TlsClientAuth::Required => "required",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(TlsClientAuth::Disabled),
+            1 => Some(TlsClientAuth::Optional),
+            2 => Some(TlsClientAuth::Required),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for TlsClientAuth {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TlsClientAuth {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for TlsClientCertMapping {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"sanEmail" => TlsClientCertMapping::SanEmail,
+            b"subjectCommonName" => TlsClientCertMapping::SubjectCommonName,
+            b"fingerprint" => TlsClientCertMapping::Fingerprint,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TlsClientCertMapping::SanEmail => "sanEmail",
+            TlsClientCertMapping::SubjectCommonName => "subjectCommonName",
+            TlsClientCertMapping::Fingerprint => "fingerprint",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(TlsClientCertMapping::SanEmail),
+            1 => Some(TlsClientCertMapping::SubjectCommonName),
+            2 => Some(TlsClientCertMapping::Fingerprint),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for TlsClientCertMapping {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TlsClientCertMapping {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for TlsPolicyType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -67,6 +67,7 @@ pub enum ObjectInner {
     Metric(Metric),
     Metrics(Metrics),
     MetricsStore(MetricsStore),
+    MtaAntivirus(MtaAntivirus),
     MtaConnectionStrategy(MtaConnectionStrategy),
     MtaDeliverySchedule(MtaDeliverySchedule),
     MtaExtensions(MtaExtensions),
@@ -90,7 +91,10 @@ pub enum ObjectInner {
     NetworkListener(NetworkListener),
     OAuthClient(OAuthClient),
     OidcProvider(OidcProvider),
+    Passkey(Passkey),
     PublicKey(PublicKey),
+    QuarantineSettings(QuarantineSettings),
+    QuarantinedMessage(QuarantinedMessage),
     QueuedMessage(QueuedMessage),
     ReportSettings(ReportSettings),
     Role(Role),
@@ -249,6 +253,10 @@ pub enum ObjectType {
     TracingStore = 114,
     WebDav = 115,
     WebHook = 116,
+    MtaAntivirus = 117,
+    QuarantinedMessage = 118,
+    QuarantineSettings = 119,
+    Passkey = 120,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -291,6 +299,7 @@ pub enum Property {
     AggregateSendFrequency = 273,
     AggregateSubject = 275,
     AlarmId = 798,
+    Algorithm = 960,
     Algorithms = 225,
     Aliases = 339,
     AllowCount = 768,
@@ -300,6 +309,7 @@ pub enum Property {
     AllowPlainTextAuth = 424,
     AllowRelaying = 348,
     AllowSpamTraining = 369,
+    AllowedBookerIds = 958,
     AllowedEndpoints = 398,
     AllowedIps = 49,
     AllowedNotifyUris = 712,
@@ -311,7 +321,9 @@ pub enum Property {
     ApplicationKey = 321,
     ApplicationSecret = 322,
     ArcResult = 292,
+    ArcSealDomain = 933,
     ArcVerify = 690,
+    ArchiveAddress = 939,
     ArchiveDeletedAccountsFor = 203,
     ArchiveDeletedItemsFor = 202,
     ArchivedAt = 58,
@@ -319,6 +331,7 @@ pub enum Property {
     ArchivedUntil = 59,
     ArrivalDate = 68,
     AsnUrls = 102,
+    Atrn = 924,
     AttemptNumber = 829,
     Attempts = 303,
     AttrClass = 470,
@@ -357,10 +370,14 @@ pub enum Property {
     BlobStore = 126,
     BlockCount = 766,
     Body = 38,
+    BookingPolicy = 956,
+    BookingWindow = 957,
+    BounceThreshold = 940,
     Brokers = 459,
     Bucket = 658,
     BufferSize = 656,
     Buffered = 863,
+    Burl = 925,
     Canonicalization = 216,
     CapacityClient = 584,
     CapacityReadBuffer = 585,
@@ -549,6 +566,7 @@ pub enum Property {
     ErrorMessage = 209,
     ErrorType = 208,
     Errors = 247,
+    Etrn = 923,
     EvaluatedDisposition = 259,
     EvaluatedDkim = 260,
     EvaluatedSpf = 261,
@@ -577,6 +595,10 @@ pub enum Property {
     Extension = 754,
     Extensions = 257,
     ExtraContactInfo = 243,
+    ExtractAttachments = 952,
+    ExtractCacheTtl = 955,
+    ExtractMaxSize = 953,
+    ExtractTimeout = 954,
     Factor = 821,
     FailOnTimeout = 490,
     FailedAt = 826,
@@ -613,6 +635,7 @@ pub enum Property {
     FromAddress = 39,
     FromEmail = 165,
     FromName = 40,
+    FromRewrite = 938,
     FutureRelease = 521,
     GenerateDkimKeys = 124,
     Generator = 918,
@@ -624,6 +647,7 @@ pub enum Property {
     GroupId = 460,
     HeaderFrom = 265,
     Headers = 93,
+    HoldFor = 946,
     HoldMetricsFor = 206,
     HoldMtaReportsFor = 204,
     HoldSamplesFor = 730,
@@ -662,6 +686,8 @@ pub enum Property {
     IndexCountry = 96,
     IndexEmail = 671,
     IndexEmailFields = 672,
+    IndexFileFields = 951,
+    IndexFiles = 950,
     IndexKey = 421,
     IndexTelemetry = 673,
     IndexTracingFields = 674,
@@ -690,12 +716,14 @@ pub enum Property {
     ItipMaxSize = 172,
     Jitter = 824,
     Key = 334,
+    KeyId = 959,
     KeyName = 337,
     KeyPrefix = 120,
     KeyValues = 853,
     L1Ratio = 391,
     L2Ratio = 392,
     LastRenewal = 186,
+    LastUsedAt = 962,
     LearnHamFromCard = 727,
     LearnHamFromReply = 735,
     LearnSpamFromRblHits = 728,
@@ -719,6 +747,7 @@ pub enum Property {
     MailRua = 841,
     MailingLists = 154,
     MaintenanceType = 796,
+    Managed = 934,
     ManagedZone = 318,
     Match = 374,
     MaxAddressBooks = 23,
@@ -761,6 +790,8 @@ pub enum Property {
     MaxMatchVars = 718,
     MaxMessageSize = 354,
     MaxMessages = 361,
+    MaxMetadataEntries = 926,
+    MaxMetadataSize = 927,
     MaxMethodCalls = 438,
     MaxMultihomed = 544,
     MaxMxHosts = 545,
@@ -815,6 +846,7 @@ pub enum Property {
     Model = 28,
     ModelId = 764,
     ModelType = 30,
+    Moderators = 937,
     MtPriority = 522,
     MtaSts = 570,
     MtaStsTimeout = 572,
@@ -833,6 +865,7 @@ pub enum Property {
     NodeId = 184,
     NotValidAfter = 179,
     NotValidBefore = 180,
+    Notified = 947,
     Notify = 513,
     NotifyCount = 642,
     NotifyDue = 643,
@@ -898,6 +931,7 @@ pub enum Property {
     PoolTimeoutWait = 481,
     PoolWorkers = 657,
     Port = 299,
+    PostingPolicy = 936,
     PreferredChain = 910,
     Prefix = 856,
     PreserveIntermediates = 306,
@@ -927,6 +961,7 @@ pub enum Property {
     PushShardsTotal = 454,
     PushThrottle = 451,
     PushVerifyTimeout = 453,
+    QuarantineSpam = 948,
     QueryEmailAliases = 786,
     QueryLogin = 783,
     QueryMaxResults = 437,
@@ -1008,6 +1043,7 @@ pub enum Property {
     ScanBanPaths = 683,
     ScanBanPeriod = 685,
     ScanBanRate = 684,
+    ScanMode = 945,
     Schedule = 541,
     Scheduling = 143,
     Scheme = 908,
@@ -1016,6 +1052,7 @@ pub enum Property {
     ScoreDiscard = 771,
     ScoreReject = 772,
     ScoreSpam = 773,
+    ScramSecret = 928,
     Script = 553,
     SearchStore = 127,
     Secret = 3,
@@ -1026,6 +1063,7 @@ pub enum Property {
     SecurityToken = 660,
     Selector = 222,
     SelectorTemplate = 226,
+    SendDigest = 949,
     SendFrequency = 230,
     SendingMtaIp = 833,
     SentinelSecret = 915,
@@ -1041,16 +1079,20 @@ pub enum Property {
     ShardIndex = 830,
     SharedSecret = 895,
     Sig0Algorithm = 336,
+    SignCount = 961,
     SignatureAlgorithm = 623,
     SignatureKey = 624,
     SignerName = 335,
     Size = 64,
     SkipDeploy = 885,
     SkipFirst = 423,
+    SmimeCacheTtl = 943,
+    SmimeTrustAnchors = 942,
     SmtpGreeting = 552,
     SnippetMaxResults = 441,
     SocketBacklog = 591,
     SocketNoDelay = 592,
+    SocketPath = 944,
     SocketReceiveBufferSize = 593,
     SocketReuseAddress = 594,
     SocketReusePort = 595,
@@ -1084,9 +1126,11 @@ pub enum Property {
     SubjectAlternativeNames = 178,
     Subscribe = 368,
     SubscriptionId = 879,
+    SubscriptionPolicy = 935,
     Sum = 494,
     Summary = 808,
     SupportedLanguages = 666,
+    SuspendedRecipients = 941,
     Tag = 748,
     Tags = 746,
     TaskTypes = 189,
@@ -1122,6 +1166,10 @@ pub enum Property {
     Timestamp = 482,
     Title = 55,
     Tls = 542,
+    TlsClientAuth = 929,
+    TlsClientCa = 930,
+    TlsClientCertFingerprints = 931,
+    TlsClientCertMapping = 932,
     TlsDisableCipherSuites = 599,
     TlsDisableProtocols = 600,
     TlsIgnoreClientOrder = 601,
@@ -69,6 +69,7 @@ impl EnumImpl for ObjectType {
             b"Metric" => ObjectType::Metric,
             b"Metrics" => ObjectType::Metrics,
             b"MetricsStore" => ObjectType::MetricsStore,
+            b"MtaAntivirus" => ObjectType::MtaAntivirus,
             b"MtaConnectionStrategy" => ObjectType::MtaConnectionStrategy,
             b"MtaDeliverySchedule" => ObjectType::MtaDeliverySchedule,
             b"MtaExtensions" => ObjectType::MtaExtensions,
@@ -92,7 +93,10 @@ impl EnumImpl for ObjectType {
             b"NetworkListener" => ObjectType::NetworkListener,
             b"OAuthClient" => ObjectType::OAuthClient,
             b"OidcProvider" => ObjectType::OidcProvider,
+            b"Passkey" => ObjectType::Passkey,
             b"PublicKey" => ObjectType::PublicKey,
+            b"QuarantineSettings" => ObjectType::QuarantineSettings,
+            b"QuarantinedMessage" => ObjectType::QuarantinedMessage,
             b"QueuedMessage" => ObjectType::QueuedMessage,
             b"ReportSettings" => ObjectType::ReportSettings,
             b"Role" => ObjectType::Role,
@@ -191,6 +195,7 @@ impl EnumImpl for ObjectType {
             ObjectType::Metric => "Metric",
             ObjectType::Metrics => "Metrics",
             ObjectType::MetricsStore => "MetricsStore",
+            ObjectType::MtaAntivirus => "MtaAntivirus",
             ObjectType::MtaConnectionStrategy => "MtaConnectionStrategy",
             ObjectType::MtaDeliverySchedule => "MtaDeliverySchedule",
             ObjectType::MtaExtensions => "MtaExtensions",
@@ -214,7 +219,10 @@ impl EnumImpl for ObjectType {
             ObjectType::NetworkListener => "NetworkListener",
             ObjectType::OAuthClient => "OAuthClient",
             ObjectType::OidcProvider => "OidcProvider",
+            ObjectType::Passkey => "Passkey",
             ObjectType::PublicKey => "PublicKey",
+            ObjectType::QuarantineSettings => "QuarantineSettings",
+            ObjectType::QuarantinedMessage => "QuarantinedMessage",
             ObjectType::QueuedMessage => "QueuedMessage",
             ObjectType::ReportSettings => "ReportSettings",
             ObjectType::Role => "Role",
@@ -377,11 +385,15 @@ impl EnumImpl for ObjectType {
             114 => Some(ObjectType::TracingStore),
             115 => Some(ObjectType::WebDav),
             116 => Some(ObjectType::WebHook),
+            117 => Some(ObjectType::MtaAntivirus),
+            118 => Some(ObjectType::QuarantinedMessage),
+            119 => Some(ObjectType::QuarantineSettings),
+            120 => Some(ObjectType::Passkey),
             _ => None,
         }
     }
 
-    const COUNT: usize = 117;
+    const COUNT: usize = 121;
 }
 
 impl serde::Serialize for ObjectType {
@@ -444,6 +456,7 @@ impl EnumImpl for Property {
             b"aggregateSendFrequency" => Property::AggregateSendFrequency,
             b"aggregateSubject" => Property::AggregateSubject,
             b"alarmId" => Property::AlarmId,
+            b"algorithm" => Property::Algorithm,
             b"algorithms" => Property::Algorithms,
             b"aliases" => Property::Aliases,
             b"allowCount" => Property::AllowCount,
@@ -453,6 +466,7 @@ impl EnumImpl for Property {
             b"allowPlainTextAuth" => Property::AllowPlainTextAuth,
             b"allowRelaying" => Property::AllowRelaying,
             b"allowSpamTraining" => Property::AllowSpamTraining,
+            b"allowedBookerIds" => Property::AllowedBookerIds,
             b"allowedEndpoints" => Property::AllowedEndpoints,
             b"allowedIps" => Property::AllowedIps,
             b"allowedNotifyUris" => Property::AllowedNotifyUris,
@@ -464,7 +478,9 @@ impl EnumImpl for Property {
             b"applicationKey" => Property::ApplicationKey,
             b"applicationSecret" => Property::ApplicationSecret,
             b"arcResult" => Property::ArcResult,
+            b"arcSealDomain" => Property::ArcSealDomain,
             b"arcVerify" => Property::ArcVerify,
+            b"archiveAddress" => Property::ArchiveAddress,
             b"archiveDeletedAccountsFor" => Property::ArchiveDeletedAccountsFor,
             b"archiveDeletedItemsFor" => Property::ArchiveDeletedItemsFor,
             b"archivedAt" => Property::ArchivedAt,
@@ -472,6 +488,7 @@ impl EnumImpl for Property {
             b"archivedUntil" => Property::ArchivedUntil,
             b"arrivalDate" => Property::ArrivalDate,
             b"asnUrls" => Property::AsnUrls,
+            b"atrn" => Property::Atrn,
             b"attemptNumber" => Property::AttemptNumber,
             b"attempts" => Property::Attempts,
             b"attrClass" => Property::AttrClass,
@@ -510,10 +527,14 @@ impl EnumImpl for Property {
             b"blobStore" => Property::BlobStore,
             b"blockCount" => Property::BlockCount,
             b"body" => Property::Body,
+            b"bookingPolicy" => Property::BookingPolicy,
+            b"bookingWindow" => Property::BookingWindow,
+            b"bounceThreshold" => Property::BounceThreshold,
             b"brokers" => Property::Brokers,
             b"bucket" => Property::Bucket,
             b"bufferSize" => Property::BufferSize,
             b"buffered" => Property::Buffered,
+            b"burl" => Property::Burl,
             b"canonicalization" => Property::Canonicalization,
             b"capacityClient" => Property::CapacityClient,
             b"capacityReadBuffer" => Property::CapacityReadBuffer,
@@ -702,6 +723,7 @@ impl EnumImpl for Property {
             b"errorMessage" => Property::ErrorMessage,
             b"errorType" => Property::ErrorType,
             b"errors" => Property::Errors,
+            b"etrn" => Property::Etrn,
             b"evaluatedDisposition" => Property::EvaluatedDisposition,
             b"evaluatedDkim" => Property::EvaluatedDkim,
             b"evaluatedSpf" => Property::EvaluatedSpf,
@@ -730,6 +752,10 @@ impl EnumImpl for Property {
             b"extension" => Property::Extension,
             b"extensions" => Property::Extensions,
             b"extraContactInfo" => Property::ExtraContactInfo,
+            b"extractAttachments" => Property::ExtractAttachments,
+            b"extractCacheTtl" => Property::ExtractCacheTtl,
+            b"extractMaxSize" => Property::ExtractMaxSize,
+            b"extractTimeout" => Property::ExtractTimeout,
             b"factor" => Property::Factor,
             b"failOnTimeout" => Property::FailOnTimeout,
             b"failedAt" => Property::FailedAt,
@@ -766,6 +792,7 @@ impl EnumImpl for Property {
             b"fromAddress" => Property::FromAddress,
             b"fromEmail" => Property::FromEmail,
             b"fromName" => Property::FromName,
+            b"fromRewrite" => Property::FromRewrite,
             b"futureRelease" => Property::FutureRelease,
             b"generateDkimKeys" => Property::GenerateDkimKeys,
             b"generator" => Property::Generator,
@@ -777,6 +804,7 @@ impl EnumImpl for Property {
             b"groupId" => Property::GroupId,
             b"headerFrom" => Property::HeaderFrom,
             b"headers" => Property::Headers,
+            b"holdFor" => Property::HoldFor,
             b"holdMetricsFor" => Property::HoldMetricsFor,
             b"holdMtaReportsFor" => Property::HoldMtaReportsFor,
             b"holdSamplesFor" => Property::HoldSamplesFor,
@@ -815,6 +843,8 @@ impl EnumImpl for Property {
             b"indexCountry" => Property::IndexCountry,
             b"indexEmail" => Property::IndexEmail,
             b"indexEmailFields" => Property::IndexEmailFields,
+            b"indexFileFields" => Property::IndexFileFields,
+            b"indexFiles" => Property::IndexFiles,
             b"indexKey" => Property::IndexKey,
             b"indexTelemetry" => Property::IndexTelemetry,
             b"indexTracingFields" => Property::IndexTracingFields,
@@ -843,12 +873,14 @@ impl EnumImpl for Property {
             b"itipMaxSize" => Property::ItipMaxSize,
             b"jitter" => Property::Jitter,
             b"key" => Property::Key,
+            b"keyId" => Property::KeyId,
             b"keyName" => Property::KeyName,
             b"keyPrefix" => Property::KeyPrefix,
             b"keyValues" => Property::KeyValues,
             b"l1Ratio" => Property::L1Ratio,
             b"l2Ratio" => Property::L2Ratio,
             b"lastRenewal" => Property::LastRenewal,
+            b"lastUsedAt" => Property::LastUsedAt,
             b"learnHamFromCard" => Property::LearnHamFromCard,
             b"learnHamFromReply" => Property::LearnHamFromReply,
             b"learnSpamFromRblHits" => Property::LearnSpamFromRblHits,
@@ -872,6 +904,7 @@ impl EnumImpl for Property {
             b"mailRua" => Property::MailRua,
             b"mailingLists" => Property::MailingLists,
             b"maintenanceType" => Property::MaintenanceType,
+            b"managed" => Property::Managed,
             b"managedZone" => Property::ManagedZone,
             b"match" => Property::Match,
             b"maxAddressBooks" => Property::MaxAddressBooks,
@@ -914,6 +947,8 @@ impl EnumImpl for Property {
             b"maxMatchVars" => Property::MaxMatchVars,
             b"maxMessageSize" => Property::MaxMessageSize,
             b"maxMessages" => Property::MaxMessages,
+            b"maxMetadataEntries" => Property::MaxMetadataEntries,
+            b"maxMetadataSize" => Property::MaxMetadataSize,
             b"maxMethodCalls" => Property::MaxMethodCalls,
             b"maxMultihomed" => Property::MaxMultihomed,
             b"maxMxHosts" => Property::MaxMxHosts,
@@ -968,6 +1003,7 @@ impl EnumImpl for Property {
             b"model" => Property::Model,
             b"modelId" => Property::ModelId,
             b"modelType" => Property::ModelType,
+            b"moderators" => Property::Moderators,
             b"mtPriority" => Property::MtPriority,
             b"mtaSts" => Property::MtaSts,
             b"mtaStsTimeout" => Property::MtaStsTimeout,
@@ -986,6 +1022,7 @@ impl EnumImpl for Property {
             b"nodeId" => Property::NodeId,
             b"notValidAfter" => Property::NotValidAfter,
             b"notValidBefore" => Property::NotValidBefore,
+            b"notified" => Property::Notified,
             b"notify" => Property::Notify,
             b"notifyCount" => Property::NotifyCount,
             b"notifyDue" => Property::NotifyDue,
@@ -1051,6 +1088,7 @@ impl EnumImpl for Property {
             b"poolTimeoutWait" => Property::PoolTimeoutWait,
             b"poolWorkers" => Property::PoolWorkers,
             b"port" => Property::Port,
+            b"postingPolicy" => Property::PostingPolicy,
             b"preferredChain" => Property::PreferredChain,
             b"prefix" => Property::Prefix,
             b"preserveIntermediates" => Property::PreserveIntermediates,
@@ -1080,6 +1118,7 @@ impl EnumImpl for Property {
             b"pushShardsTotal" => Property::PushShardsTotal,
             b"pushThrottle" => Property::PushThrottle,
             b"pushVerifyTimeout" => Property::PushVerifyTimeout,
+            b"quarantineSpam" => Property::QuarantineSpam,
             b"queryEmailAliases" => Property::QueryEmailAliases,
             b"queryLogin" => Property::QueryLogin,
             b"queryMaxResults" => Property::QueryMaxResults,
@@ -1161,6 +1200,7 @@ impl EnumImpl for Property {
             b"scanBanPaths" => Property::ScanBanPaths,
             b"scanBanPeriod" => Property::ScanBanPeriod,
             b"scanBanRate" => Property::ScanBanRate,
+            b"scanMode" => Property::ScanMode,
             b"schedule" => Property::Schedule,
             b"scheduling" => Property::Scheduling,
             b"scheme" => Property::Scheme,
@@ -1169,6 +1209,7 @@ impl EnumImpl for Property {
             b"scoreDiscard" => Property::ScoreDiscard,
             b"scoreReject" => Property::ScoreReject,
             b"scoreSpam" => Property::ScoreSpam,
+            b"scramSecret" => Property::ScramSecret,
             b"script" => Property::Script,
             b"searchStore" => Property::SearchStore,
             b"secret" => Property::Secret,
@@ -1179,6 +1220,7 @@ impl EnumImpl for Property {
             b"securityToken" => Property::SecurityToken,
             b"selector" => Property::Selector,
             b"selectorTemplate" => Property::SelectorTemplate,
+            b"sendDigest" => Property::SendDigest,
             b"sendFrequency" => Property::SendFrequency,
             b"sendingMtaIp" => Property::SendingMtaIp,
             b"sentinelSecret" => Property::SentinelSecret,
@@ -1194,16 +1236,20 @@ impl EnumImpl for Property {
             b"shardIndex" => Property::ShardIndex,
             b"sharedSecret" => Property::SharedSecret,
             b"sig0Algorithm" => Property::Sig0Algorithm,
+            b"signCount" => Property::SignCount,
             b"signatureAlgorithm" => Property::SignatureAlgorithm,
             b"signatureKey" => Property::SignatureKey,
             b"signerName" => Property::SignerName,
             b"size" => Property::Size,
             b"skipDeploy" => Property::SkipDeploy,
             b"skipFirst" => Property::SkipFirst,
+            b"smimeCacheTtl" => Property::SmimeCacheTtl,
+            b"smimeTrustAnchors" => Property::SmimeTrustAnchors,
             b"smtpGreeting" => Property::SmtpGreeting,
             b"snippetMaxResults" => Property::SnippetMaxResults,
             b"socketBacklog" => Property::SocketBacklog,
             b"socketNoDelay" => Property::SocketNoDelay,
+            b"socketPath" => Property::SocketPath,
             b"socketReceiveBufferSize" => Property::SocketReceiveBufferSize,
             b"socketReuseAddress" => Property::SocketReuseAddress,
             b"socketReusePort" => Property::SocketReusePort,
@@ -1237,9 +1283,11 @@ impl EnumImpl for Property {
             b"subjectAlternativeNames" => Property::SubjectAlternativeNames,
             b"subscribe" => Property::Subscribe,
             b"subscriptionId" => Property::SubscriptionId,
+            b"subscriptionPolicy" => Property::SubscriptionPolicy,
             b"sum" => Property::Sum,
             b"summary" => Property::Summary,
             b"supportedLanguages" => Property::SupportedLanguages,
+            b"suspendedRecipients" => Property::SuspendedRecipients,
             b"tag" => Property::Tag,
             b"tags" => Property::Tags,
             b"taskTypes" => Property::TaskTypes,
@@ -1275,6 +1323,10 @@ impl EnumImpl for Property {
             b"timestamp" => Property::Timestamp,
             b"title" => Property::Title,
             b"tls" => Property::Tls,
+            b"tlsClientAuth" => Property::TlsClientAuth,
+            b"tlsClientCa" => Property::TlsClientCa,
+            b"tlsClientCertFingerprints" => Property::TlsClientCertFingerprints,
+            b"tlsClientCertMapping" => Property::TlsClientCertMapping,
             b"tlsDisableCipherSuites" => Property::TlsDisableCipherSuites,
             b"tlsDisableProtocols" => Property::TlsDisableProtocols,
             b"tlsIgnoreClientOrder" => Property::TlsIgnoreClientOrder,
@@ -1372,6 +1424,7 @@ impl EnumImpl for Property {
             Property::AggregateSendFrequency => "aggregateSendFrequency",
             Property::AggregateSubject => "aggregateSubject",
             Property::AlarmId => "alarmId",
+            Property::Algorithm => "algorithm",
             Property::Algorithms => "algorithms",
             Property::Aliases => "aliases",
             Property::AllowCount => "allowCount",
@@ -1381,6 +1434,7 @@ impl EnumImpl for Property {
             Property::AllowPlainTextAuth => "allowPlainTextAuth",
             Property::AllowRelaying => "allowRelaying",
             Property::AllowSpamTraining => "allowSpamTraining",
+            Property::AllowedBookerIds => "allowedBookerIds",
             Property::AllowedEndpoints => "allowedEndpoints",
             Property::AllowedIps => "allowedIps",
             Property::AllowedNotifyUris => "allowedNotifyUris",
@@ -1392,7 +1446,9 @@ impl EnumImpl for Property {
             Property::ApplicationKey => "applicationKey",
             Property::ApplicationSecret => "applicationSecret",
             Property::ArcResult => "arcResult",
+            Property::ArcSealDomain => "arcSealDomain",
             Property::ArcVerify => "arcVerify",
+            Property::ArchiveAddress => "archiveAddress",
             Property::ArchiveDeletedAccountsFor => "archiveDeletedAccountsFor",
             Property::ArchiveDeletedItemsFor => "archiveDeletedItemsFor",
             Property::ArchivedAt => "archivedAt",
@@ -1400,6 +1456,7 @@ impl EnumImpl for Property {
             Property::ArchivedUntil => "archivedUntil",
             Property::ArrivalDate => "arrivalDate",
             Property::AsnUrls => "asnUrls",
+            Property::Atrn => "atrn",
             Property::AttemptNumber => "attemptNumber",
             Property::Attempts => "attempts",
             Property::AttrClass => "attrClass",
@@ -1438,10 +1495,14 @@ impl EnumImpl for Property {
             Property::BlobStore => "blobStore",
             Property::BlockCount => "blockCount",
             Property::Body => "body",
+            Property::BookingPolicy => "bookingPolicy",
+            Property::BookingWindow => "bookingWindow",
+            Property::BounceThreshold => "bounceThreshold",
             Property::Brokers => "brokers",
             Property::Bucket => "bucket",
             Property::BufferSize => "bufferSize",
             Property::Buffered => "buffered",
+            Property::Burl => "burl",
             Property::Canonicalization => "canonicalization",
             Property::CapacityClient => "capacityClient",
             Property::CapacityReadBuffer => "capacityReadBuffer",
@@ -1630,6 +1691,7 @@ impl EnumImpl for Property {
             Property::ErrorMessage => "errorMessage",
             Property::ErrorType => "errorType",
             Property::Errors => "errors",
+            Property::Etrn => "etrn",
             Property::EvaluatedDisposition => "evaluatedDisposition",
             Property::EvaluatedDkim => "evaluatedDkim",
             Property::EvaluatedSpf => "evaluatedSpf",
@@ -1658,6 +1720,10 @@ impl EnumImpl for Property {
             Property::Extension => "extension",
             Property::Extensions => "extensions",
             Property::ExtraContactInfo => "extraContactInfo",
+            Property::ExtractAttachments => "extractAttachments",
+            Property::ExtractCacheTtl => "extractCacheTtl",
+            Property::ExtractMaxSize => "extractMaxSize",
+            Property::ExtractTimeout => "extractTimeout",
             Property::Factor => "factor",
             Property::FailOnTimeout => "failOnTimeout",
             Property::FailedAt => "failedAt",
@@ -1694,6 +1760,7 @@ impl EnumImpl for Property {
             Property::FromAddress => "fromAddress",
             Property::FromEmail => "fromEmail",
             Property::FromName => "fromName",
+            Property::FromRewrite => "fromRewrite",
             Property::FutureRelease => "futureRelease",
             Property::GenerateDkimKeys => "generateDkimKeys",
             Property::Generator => "generator",
@@ -1705,6 +1772,7 @@ impl EnumImpl for Property {
             Property::GroupId => "groupId",
             Property::HeaderFrom => "headerFrom",
             Property::Headers => "headers",
+            Property::HoldFor => "holdFor",
             Property::HoldMetricsFor => "holdMetricsFor",
             Property::HoldMtaReportsFor => "holdMtaReportsFor",
             Property::HoldSamplesFor => "holdSamplesFor",
@@ -1743,6 +1811,8 @@ impl EnumImpl for Property {
             Property::IndexCountry => "indexCountry",
             Property::IndexEmail => "indexEmail",
             Property::IndexEmailFields => "indexEmailFields",
+            Property::IndexFileFields => "indexFileFields",
+            Property::IndexFiles => "indexFiles",
             Property::IndexKey => "indexKey",
             Property::IndexTelemetry => "indexTelemetry",
             Property::IndexTracingFields => "indexTracingFields",
@@ -1771,12 +1841,14 @@ impl EnumImpl for Property {
             Property::ItipMaxSize => "itipMaxSize",
             Property::Jitter => "jitter",
             Property::Key => "key",
+            Property::KeyId => "keyId",
             Property::KeyName => "keyName",
             Property::KeyPrefix => "keyPrefix",
             Property::KeyValues => "keyValues",
             Property::L1Ratio => "l1Ratio",
             Property::L2Ratio => "l2Ratio",
             Property::LastRenewal => "lastRenewal",
+            Property::LastUsedAt => "lastUsedAt",
             Property::LearnHamFromCard => "learnHamFromCard",
             Property::LearnHamFromReply => "learnHamFromReply",
             Property::LearnSpamFromRblHits => "learnSpamFromRblHits",
@@ -1800,6 +1872,7 @@ impl EnumImpl for Property {
             Property::MailRua => "mailRua",
             Property::MailingLists => "mailingLists",
             Property::MaintenanceType => "maintenanceType",
+            Property::Managed => "managed",
             Property::ManagedZone => "managedZone",
             Property::Match => "match",
             Property::MaxAddressBooks => "maxAddressBooks",
@@ -1842,6 +1915,8 @@ impl EnumImpl for Property {
             Property::MaxMatchVars => "maxMatchVars",
             Property::MaxMessageSize => "maxMessageSize",
             Property::MaxMessages => "maxMessages",
+            Property::MaxMetadataEntries => "maxMetadataEntries",
+            Property::MaxMetadataSize => "maxMetadataSize",
             Property::MaxMethodCalls => "maxMethodCalls",
             Property::MaxMultihomed => "maxMultihomed",
             Property::MaxMxHosts => "maxMxHosts",
@@ -1896,6 +1971,7 @@ impl EnumImpl for Property {
             Property::Model => "model",
             Property::ModelId => "modelId",
             Property::ModelType => "modelType",
+            Property::Moderators => "moderators",
             Property::MtPriority => "mtPriority",
             Property::MtaSts => "mtaSts",
             Property::MtaStsTimeout => "mtaStsTimeout",
@@ -1914,6 +1990,7 @@ impl EnumImpl for Property {
             Property::NodeId => "nodeId",
             Property::NotValidAfter => "notValidAfter",
             Property::NotValidBefore => "notValidBefore",
+            Property::Notified => "notified",
             Property::Notify => "notify",
             Property::NotifyCount => "notifyCount",
             Property::NotifyDue => "notifyDue",
@@ -1979,6 +2056,7 @@ impl EnumImpl for Property {
             Property::PoolTimeoutWait => "poolTimeoutWait",
             Property::PoolWorkers => "poolWorkers",
             Property::Port => "port",
+            Property::PostingPolicy => "postingPolicy",
             Property::PreferredChain => "preferredChain",
             Property::Prefix => "prefix",
             Property::PreserveIntermediates => "preserveIntermediates",
@@ -2008,6 +2086,7 @@ impl EnumImpl for Property {
             Property::PushShardsTotal => "pushShardsTotal",
             Property::PushThrottle => "pushThrottle",
             Property::PushVerifyTimeout => "pushVerifyTimeout",
+            Property::QuarantineSpam => "quarantineSpam",
             Property::QueryEmailAliases => "queryEmailAliases",
             Property::QueryLogin => "queryLogin",
             Property::QueryMaxResults => "queryMaxResults",
@@ -2089,6 +2168,7 @@ impl EnumImpl for Property {
             Property::ScanBanPaths => "scanBanPaths",
             Property::ScanBanPeriod => "scanBanPeriod",
             Property::ScanBanRate => "scanBanRate",
+            Property::ScanMode => "scanMode",
             Property::Schedule => "schedule",
             Property::Scheduling => "scheduling",
             Property::Scheme => "scheme",
@@ -2097,6 +2177,7 @@ impl EnumImpl for Property {
             Property::ScoreDiscard => "scoreDiscard",
             Property::ScoreReject => "scoreReject",
             Property::ScoreSpam => "scoreSpam",
+            Property::ScramSecret => "scramSecret",
             Property::Script => "script",
             Property::SearchStore => "searchStore",
             Property::Secret => "secret",
@@ -2107,6 +2188,7 @@ impl EnumImpl for Property {
             Property::SecurityToken => "securityToken",
             Property::Selector => "selector",
             Property::SelectorTemplate => "selectorTemplate",
+            Property::SendDigest => "sendDigest",
             Property::SendFrequency => "sendFrequency",
             Property::SendingMtaIp => "sendingMtaIp",
             Property::SentinelSecret => "sentinelSecret",
@@ -2122,16 +2204,20 @@ impl EnumImpl for Property {
             Property::ShardIndex => "shardIndex",
             Property::SharedSecret => "sharedSecret",
             Property::Sig0Algorithm => "sig0Algorithm",
+            Property::SignCount => "signCount",
             Property::SignatureAlgorithm => "signatureAlgorithm",
             Property::SignatureKey => "signatureKey",
             Property::SignerName => "signerName",
             Property::Size => "size",
             Property::SkipDeploy => "skipDeploy",
             Property::SkipFirst => "skipFirst",
+            Property::SmimeCacheTtl => "smimeCacheTtl",
+            Property::SmimeTrustAnchors => "smimeTrustAnchors",
             Property::SmtpGreeting => "smtpGreeting",
             Property::SnippetMaxResults => "snippetMaxResults",
             Property::SocketBacklog => "socketBacklog",
             Property::SocketNoDelay => "socketNoDelay",
+            Property::SocketPath => "socketPath",
             Property::SocketReceiveBufferSize => "socketReceiveBufferSize",
             Property::SocketReuseAddress => "socketReuseAddress",
             Property::SocketReusePort => "socketReusePort",
@@ -2165,9 +2251,11 @@ impl EnumImpl for Property {
             Property::SubjectAlternativeNames => "subjectAlternativeNames",
             Property::Subscribe => "subscribe",
             Property::SubscriptionId => "subscriptionId",
+            Property::SubscriptionPolicy => "subscriptionPolicy",
             Property::Sum => "sum",
             Property::Summary => "summary",
             Property::SupportedLanguages => "supportedLanguages",
+            Property::SuspendedRecipients => "suspendedRecipients",
             Property::Tag => "tag",
             Property::Tags => "tags",
             Property::TaskTypes => "taskTypes",
@@ -2203,6 +2291,10 @@ impl EnumImpl for Property {
             Property::Timestamp => "timestamp",
             Property::Title => "title",
             Property::Tls => "tls",
+            Property::TlsClientAuth => "tlsClientAuth",
+            Property::TlsClientCa => "tlsClientCa",
+            Property::TlsClientCertFingerprints => "tlsClientCertFingerprints",
+            Property::TlsClientCertMapping => "tlsClientCertMapping",
             Property::TlsDisableCipherSuites => "tlsDisableCipherSuites",
             Property::TlsDisableProtocols => "tlsDisableProtocols",
             Property::TlsIgnoreClientOrder => "tlsIgnoreClientOrder",
@@ -2304,6 +2396,7 @@ impl EnumImpl for Property {
             273 => Some(Property::AggregateSendFrequency),
             275 => Some(Property::AggregateSubject),
             798 => Some(Property::AlarmId),
+            960 => Some(Property::Algorithm),
             225 => Some(Property::Algorithms),
             339 => Some(Property::Aliases),
             768 => Some(Property::AllowCount),
@@ -2313,6 +2406,7 @@ impl EnumImpl for Property {
             424 => Some(Property::AllowPlainTextAuth),
             348 => Some(Property::AllowRelaying),
             369 => Some(Property::AllowSpamTraining),
+            958 => Some(Property::AllowedBookerIds),
             398 => Some(Property::AllowedEndpoints),
             49 => Some(Property::AllowedIps),
             712 => Some(Property::AllowedNotifyUris),
@@ -2324,7 +2418,9 @@ impl EnumImpl for Property {
             321 => Some(Property::ApplicationKey),
             322 => Some(Property::ApplicationSecret),
             292 => Some(Property::ArcResult),
+            933 => Some(Property::ArcSealDomain),
             690 => Some(Property::ArcVerify),
+            939 => Some(Property::ArchiveAddress),
             203 => Some(Property::ArchiveDeletedAccountsFor),
             202 => Some(Property::ArchiveDeletedItemsFor),
             58 => Some(Property::ArchivedAt),
@@ -2332,6 +2428,7 @@ impl EnumImpl for Property {
             59 => Some(Property::ArchivedUntil),
             68 => Some(Property::ArrivalDate),
             102 => Some(Property::AsnUrls),
+            924 => Some(Property::Atrn),
             829 => Some(Property::AttemptNumber),
             303 => Some(Property::Attempts),
             470 => Some(Property::AttrClass),
@@ -2370,10 +2467,14 @@ impl EnumImpl for Property {
             126 => Some(Property::BlobStore),
             766 => Some(Property::BlockCount),
             38 => Some(Property::Body),
+            956 => Some(Property::BookingPolicy),
+            957 => Some(Property::BookingWindow),
+            940 => Some(Property::BounceThreshold),
             459 => Some(Property::Brokers),
             658 => Some(Property::Bucket),
             656 => Some(Property::BufferSize),
             863 => Some(Property::Buffered),
+            925 => Some(Property::Burl),
             216 => Some(Property::Canonicalization),
             584 => Some(Property::CapacityClient),
             585 => Some(Property::CapacityReadBuffer),
@@ -2562,6 +2663,7 @@ impl EnumImpl for Property {
             209 => Some(Property::ErrorMessage),
             208 => Some(Property::ErrorType),
             247 => Some(Property::Errors),
+            923 => Some(Property::Etrn),
             259 => Some(Property::EvaluatedDisposition),
             260 => Some(Property::EvaluatedDkim),
             261 => Some(Property::EvaluatedSpf),
@@ -2590,6 +2692,10 @@ impl EnumImpl for Property {
             754 => Some(Property::Extension),
             257 => Some(Property::Extensions),
             243 => Some(Property::ExtraContactInfo),
+            952 => Some(Property::ExtractAttachments),
+            955 => Some(Property::ExtractCacheTtl),
+            953 => Some(Property::ExtractMaxSize),
+            954 => Some(Property::ExtractTimeout),
             821 => Some(Property::Factor),
             490 => Some(Property::FailOnTimeout),
             826 => Some(Property::FailedAt),
@@ -2626,6 +2732,7 @@ impl EnumImpl for Property {
             39 => Some(Property::FromAddress),
             165 => Some(Property::FromEmail),
             40 => Some(Property::FromName),
+            938 => Some(Property::FromRewrite),
             521 => Some(Property::FutureRelease),
             124 => Some(Property::GenerateDkimKeys),
             918 => Some(Property::Generator),
@@ -2637,6 +2744,7 @@ impl EnumImpl for Property {
             460 => Some(Property::GroupId),
             265 => Some(Property::HeaderFrom),
             93 => Some(Property::Headers),
+            946 => Some(Property::HoldFor),
             206 => Some(Property::HoldMetricsFor),
             204 => Some(Property::HoldMtaReportsFor),
             730 => Some(Property::HoldSamplesFor),
@@ -2675,6 +2783,8 @@ impl EnumImpl for Property {
             96 => Some(Property::IndexCountry),
             671 => Some(Property::IndexEmail),
             672 => Some(Property::IndexEmailFields),
+            951 => Some(Property::IndexFileFields),
+            950 => Some(Property::IndexFiles),
             421 => Some(Property::IndexKey),
             673 => Some(Property::IndexTelemetry),
             674 => Some(Property::IndexTracingFields),
@@ -2703,12 +2813,14 @@ impl EnumImpl for Property {
             172 => Some(Property::ItipMaxSize),
             824 => Some(Property::Jitter),
             334 => Some(Property::Key),
+            959 => Some(Property::KeyId),
             337 => Some(Property::KeyName),
             120 => Some(Property::KeyPrefix),
             853 => Some(Property::KeyValues),
             391 => Some(Property::L1Ratio),
             392 => Some(Property::L2Ratio),
             186 => Some(Property::LastRenewal),
+            962 => Some(Property::LastUsedAt),
             727 => Some(Property::LearnHamFromCard),
             735 => Some(Property::LearnHamFromReply),
             728 => Some(Property::LearnSpamFromRblHits),
@@ -2732,6 +2844,7 @@ impl EnumImpl for Property {
             841 => Some(Property::MailRua),
             154 => Some(Property::MailingLists),
             796 => Some(Property::MaintenanceType),
+            934 => Some(Property::Managed),
             318 => Some(Property::ManagedZone),
             374 => Some(Property::Match),
             23 => Some(Property::MaxAddressBooks),
@@ -2774,6 +2887,8 @@ impl EnumImpl for Property {
             718 => Some(Property::MaxMatchVars),
             354 => Some(Property::MaxMessageSize),
             361 => Some(Property::MaxMessages),
+            926 => Some(Property::MaxMetadataEntries),
+            927 => Some(Property::MaxMetadataSize),
             438 => Some(Property::MaxMethodCalls),
             544 => Some(Property::MaxMultihomed),
             545 => Some(Property::MaxMxHosts),
@@ -2828,6 +2943,7 @@ impl EnumImpl for Property {
             28 => Some(Property::Model),
             764 => Some(Property::ModelId),
             30 => Some(Property::ModelType),
+            937 => Some(Property::Moderators),
             522 => Some(Property::MtPriority),
             570 => Some(Property::MtaSts),
             572 => Some(Property::MtaStsTimeout),
@@ -2846,6 +2962,7 @@ impl EnumImpl for Property {
             184 => Some(Property::NodeId),
             179 => Some(Property::NotValidAfter),
             180 => Some(Property::NotValidBefore),
+            947 => Some(Property::Notified),
             513 => Some(Property::Notify),
             642 => Some(Property::NotifyCount),
             643 => Some(Property::NotifyDue),
@@ -2911,6 +3028,7 @@ impl EnumImpl for Property {
             481 => Some(Property::PoolTimeoutWait),
             657 => Some(Property::PoolWorkers),
             299 => Some(Property::Port),
+            936 => Some(Property::PostingPolicy),
             910 => Some(Property::PreferredChain),
             856 => Some(Property::Prefix),
             306 => Some(Property::PreserveIntermediates),
@@ -2940,6 +3058,7 @@ impl EnumImpl for Property {
             454 => Some(Property::PushShardsTotal),
             451 => Some(Property::PushThrottle),
             453 => Some(Property::PushVerifyTimeout),
+            948 => Some(Property::QuarantineSpam),
             786 => Some(Property::QueryEmailAliases),
             783 => Some(Property::QueryLogin),
             437 => Some(Property::QueryMaxResults),
@@ -3021,6 +3140,7 @@ impl EnumImpl for Property {
             683 => Some(Property::ScanBanPaths),
             685 => Some(Property::ScanBanPeriod),
             684 => Some(Property::ScanBanRate),
+            945 => Some(Property::ScanMode),
             541 => Some(Property::Schedule),
             143 => Some(Property::Scheduling),
             908 => Some(Property::Scheme),
@@ -3029,6 +3149,7 @@ impl EnumImpl for Property {
             771 => Some(Property::ScoreDiscard),
             772 => Some(Property::ScoreReject),
             773 => Some(Property::ScoreSpam),
+            928 => Some(Property::ScramSecret),
             553 => Some(Property::Script),
             127 => Some(Property::SearchStore),
             3 => Some(Property::Secret),
@@ -3039,6 +3160,7 @@ impl EnumImpl for Property {
             660 => Some(Property::SecurityToken),
             222 => Some(Property::Selector),
             226 => Some(Property::SelectorTemplate),
+            949 => Some(Property::SendDigest),
             230 => Some(Property::SendFrequency),
             833 => Some(Property::SendingMtaIp),
             915 => Some(Property::SentinelSecret),
@@ -3054,16 +3176,20 @@ impl EnumImpl for Property {
             830 => Some(Property::ShardIndex),
             895 => Some(Property::SharedSecret),
             336 => Some(Property::Sig0Algorithm),
+            961 => Some(Property::SignCount),
             623 => Some(Property::SignatureAlgorithm),
             624 => Some(Property::SignatureKey),
             335 => Some(Property::SignerName),
             64 => Some(Property::Size),
             885 => Some(Property::SkipDeploy),
             423 => Some(Property::SkipFirst),
+            943 => Some(Property::SmimeCacheTtl),
+            942 => Some(Property::SmimeTrustAnchors),
             552 => Some(Property::SmtpGreeting),
             441 => Some(Property::SnippetMaxResults),
             591 => Some(Property::SocketBacklog),
             592 => Some(Property::SocketNoDelay),
+            944 => Some(Property::SocketPath),
             593 => Some(Property::SocketReceiveBufferSize),
             594 => Some(Property::SocketReuseAddress),
             595 => Some(Property::SocketReusePort),
@@ -3097,9 +3223,11 @@ impl EnumImpl for Property {
             178 => Some(Property::SubjectAlternativeNames),
             368 => Some(Property::Subscribe),
             879 => Some(Property::SubscriptionId),
+            935 => Some(Property::SubscriptionPolicy),
             494 => Some(Property::Sum),
             808 => Some(Property::Summary),
             666 => Some(Property::SupportedLanguages),
+            941 => Some(Property::SuspendedRecipients),
             748 => Some(Property::Tag),
             746 => Some(Property::Tags),
             189 => Some(Property::TaskTypes),
@@ -3135,6 +3263,10 @@ impl EnumImpl for Property {
             482 => Some(Property::Timestamp),
             55 => Some(Property::Title),
             542 => Some(Property::Tls),
+            929 => Some(Property::TlsClientAuth),
+            930 => Some(Property::TlsClientCa),
+            931 => Some(Property::TlsClientCertFingerprints),
+            932 => Some(Property::TlsClientCertMapping),
             599 => Some(Property::TlsDisableCipherSuites),
             600 => Some(Property::TlsDisableProtocols),
             601 => Some(Property::TlsIgnoreClientOrder),
@@ -3194,7 +3326,7 @@ impl EnumImpl for Property {
         }
     }
 
-    const COUNT: usize = 923;
+    const COUNT: usize = 963;
 }
 
 impl serde::Serialize for Property {
@@ -3276,6 +3408,7 @@ impl ObjectType {
             ObjectType::Metric => Metric::FLAGS,
             ObjectType::Metrics => Metrics::FLAGS,
             ObjectType::MetricsStore => MetricsStore::FLAGS,
+            ObjectType::MtaAntivirus => MtaAntivirus::FLAGS,
             ObjectType::MtaConnectionStrategy => MtaConnectionStrategy::FLAGS,
             ObjectType::MtaDeliverySchedule => MtaDeliverySchedule::FLAGS,
             ObjectType::MtaExtensions => MtaExtensions::FLAGS,
@@ -3299,7 +3432,10 @@ impl ObjectType {
             ObjectType::NetworkListener => NetworkListener::FLAGS,
             ObjectType::OAuthClient => OAuthClient::FLAGS,
             ObjectType::OidcProvider => OidcProvider::FLAGS,
+            ObjectType::Passkey => Passkey::FLAGS,
             ObjectType::PublicKey => PublicKey::FLAGS,
+            ObjectType::QuarantineSettings => QuarantineSettings::FLAGS,
+            ObjectType::QuarantinedMessage => QuarantinedMessage::FLAGS,
             ObjectType::QueuedMessage => QueuedMessage::FLAGS,
             ObjectType::ReportSettings => ReportSettings::FLAGS,
             ObjectType::Role => Role::FLAGS,
@@ -3528,6 +3664,11 @@ impl ObjectType {
                 IndexSchemaType::Search,
                 IndexSchemaValueType::Id,
             )],
+            ObjectType::QuarantinedMessage => vec![IndexSchema::new(
+                Property::AccountId,
+                IndexSchemaType::Search,
+                IndexSchemaValueType::Id,
+            )],
             ObjectType::Role => vec![
                 IndexSchema::new(
                     Property::Description,
@@ -3643,6 +3784,7 @@ impl ObjectType {
             ObjectType::Metric => Permission::SysMetricGet,
             ObjectType::Metrics => Permission::SysMetricsGet,
             ObjectType::MetricsStore => Permission::SysMetricsStoreGet,
+            ObjectType::MtaAntivirus => Permission::SysMtaAntivirusGet,
             ObjectType::MtaConnectionStrategy => Permission::SysMtaConnectionStrategyGet,
             ObjectType::MtaDeliverySchedule => Permission::SysMtaDeliveryScheduleGet,
             ObjectType::MtaExtensions => Permission::SysMtaExtensionsGet,
@@ -3666,7 +3808,10 @@ impl ObjectType {
             ObjectType::NetworkListener => Permission::SysNetworkListenerGet,
             ObjectType::OAuthClient => Permission::SysOAuthClientGet,
             ObjectType::OidcProvider => Permission::SysOidcProviderGet,
+            ObjectType::Passkey => Permission::SysPasskeyGet,
             ObjectType::PublicKey => Permission::SysPublicKeyGet,
+            ObjectType::QuarantineSettings => Permission::SysQuarantineSettingsGet,
+            ObjectType::QuarantinedMessage => Permission::SysQuarantinedMessageGet,
             ObjectType::QueuedMessage => Permission::SysQueuedMessageGet,
             ObjectType::ReportSettings => Permission::SysReportSettingsGet,
             ObjectType::Role => Permission::SysRoleGet,
@@ -3749,7 +3894,9 @@ impl ObjectType {
             ObjectType::MtaVirtualQueue => Permission::SysMtaVirtualQueueQuery,
             ObjectType::NetworkListener => Permission::SysNetworkListenerQuery,
             ObjectType::OAuthClient => Permission::SysOAuthClientQuery,
+            ObjectType::Passkey => Permission::SysPasskeyQuery,
             ObjectType::PublicKey => Permission::SysPublicKeyQuery,
+            ObjectType::QuarantinedMessage => Permission::SysQuarantinedMessageQuery,
             ObjectType::QueuedMessage => Permission::SysQueuedMessageQuery,
             ObjectType::Role => Permission::SysRoleQuery,
             ObjectType::SieveSystemScript => Permission::SysSieveSystemScriptQuery,
@@ -4058,6 +4205,11 @@ impl ObjectType {
                 Permission::SysMetricsStoreUpdate,
                 Permission::SysMetricsStoreUpdate,
             ],
+            ObjectType::MtaAntivirus => [
+                Permission::SysMtaAntivirusUpdate,
+                Permission::SysMtaAntivirusUpdate,
+                Permission::SysMtaAntivirusUpdate,
+            ],
             ObjectType::MtaConnectionStrategy => [
                 Permission::SysMtaConnectionStrategyCreate,
                 Permission::SysMtaConnectionStrategyUpdate,
@@ -4173,11 +4325,26 @@ impl ObjectType {
                 Permission::SysOidcProviderUpdate,
                 Permission::SysOidcProviderUpdate,
             ],
+            ObjectType::Passkey => [
+                Permission::SysPasskeyCreate,
+                Permission::SysPasskeyUpdate,
+                Permission::SysPasskeyDestroy,
+            ],
             ObjectType::PublicKey => [
                 Permission::SysPublicKeyCreate,
                 Permission::SysPublicKeyUpdate,
                 Permission::SysPublicKeyDestroy,
             ],
+            ObjectType::QuarantineSettings => [
+                Permission::SysQuarantineSettingsUpdate,
+                Permission::SysQuarantineSettingsUpdate,
+                Permission::SysQuarantineSettingsUpdate,
+            ],
+            ObjectType::QuarantinedMessage => [
+                Permission::SysQuarantinedMessageCreate,
+                Permission::SysQuarantinedMessageUpdate,
+                Permission::SysQuarantinedMessageDestroy,
+            ],
             ObjectType::QueuedMessage => [
                 Permission::SysQueuedMessageCreate,
                 Permission::SysQueuedMessageUpdate,
@@ -4367,6 +4534,8 @@ impl ObjectInner {
         match self {
             ObjectInner::Account(Account::User(obj)) => obj.member_tenant_id,
             ObjectInner::Account(Account::Group(obj)) => obj.member_tenant_id,
+            ObjectInner::Account(Account::Resource(obj)) => obj.member_tenant_id,
+            ObjectInner::Account(Account::Location(obj)) => obj.member_tenant_id,
             ObjectInner::AcmeProvider(obj) => obj.member_tenant_id,
             ObjectInner::ArfExternalReport(obj) => obj.member_tenant_id,
             ObjectInner::Directory(Directory::Ldap(obj)) => obj.member_tenant_id,
@@ -4463,6 +4632,8 @@ impl ObjectInner {
         match self {
             ObjectInner::Account(Account::User(obj)) => obj.member_tenant_id = Some(id),
             ObjectInner::Account(Account::Group(obj)) => obj.member_tenant_id = Some(id),
+            ObjectInner::Account(Account::Resource(obj)) => obj.member_tenant_id = Some(id),
+            ObjectInner::Account(Account::Location(obj)) => obj.member_tenant_id = Some(id),
             ObjectInner::AcmeProvider(obj) => obj.member_tenant_id = Some(id),
             ObjectInner::ArfExternalReport(obj) => obj.member_tenant_id = Some(id),
             ObjectInner::Directory(Directory::Ldap(obj)) => obj.member_tenant_id = Some(id),
@@ -4570,6 +4741,7 @@ impl ObjectInner {
             ObjectInner::ArchivedItem(ArchivedItem::SieveScript(obj)) => Some(obj.account_id),
             ObjectInner::MaskedEmail(obj) => Some(obj.account_id),
             ObjectInner::PublicKey(obj) => Some(obj.account_id),
+            ObjectInner::QuarantinedMessage(obj) => Some(obj.account_id),
             ObjectInner::SpamTrainingSample(obj) => obj.account_id,
             ObjectInner::Task(Task::IndexDocument(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::UnindexDocument(obj)) => Some(obj.account_id),
@@ -4580,6 +4752,8 @@ impl ObjectInner {
             ObjectInner::Task(Task::RestoreArchivedItem(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::DestroyAccount(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::AccountMaintenance(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::AccountExport(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::AccountImport(obj)) => Some(obj.account_id),
             _ => None,
         }
     }
@@ -4593,6 +4767,7 @@ impl ObjectInner {
             ObjectInner::ArchivedItem(ArchivedItem::SieveScript(obj)) => obj.account_id = id,
             ObjectInner::MaskedEmail(obj) => obj.account_id = id,
             ObjectInner::PublicKey(obj) => obj.account_id = id,
+            ObjectInner::QuarantinedMessage(obj) => obj.account_id = id,
             ObjectInner::SpamTrainingSample(obj) => obj.account_id = Some(id),
             ObjectInner::Task(Task::IndexDocument(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::UnindexDocument(obj)) => obj.account_id = id,
@@ -4603,6 +4778,8 @@ impl ObjectInner {
             ObjectInner::Task(Task::RestoreArchivedItem(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::DestroyAccount(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::AccountMaintenance(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::AccountExport(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::AccountImport(obj)) => obj.account_id = id,
             _ => {}
         }
     }
@@ -4666,6 +4843,7 @@ impl ObjectInner {
             ObjectInner::Metric(obj) => obj.to_pickled_vec(),
             ObjectInner::Metrics(obj) => obj.to_pickled_vec(),
             ObjectInner::MetricsStore(obj) => obj.to_pickled_vec(),
+            ObjectInner::MtaAntivirus(obj) => obj.to_pickled_vec(),
             ObjectInner::MtaConnectionStrategy(obj) => obj.to_pickled_vec(),
             ObjectInner::MtaDeliverySchedule(obj) => obj.to_pickled_vec(),
             ObjectInner::MtaExtensions(obj) => obj.to_pickled_vec(),
@@ -4689,7 +4867,10 @@ impl ObjectInner {
             ObjectInner::NetworkListener(obj) => obj.to_pickled_vec(),
             ObjectInner::OAuthClient(obj) => obj.to_pickled_vec(),
             ObjectInner::OidcProvider(obj) => obj.to_pickled_vec(),
+            ObjectInner::Passkey(obj) => obj.to_pickled_vec(),
             ObjectInner::PublicKey(obj) => obj.to_pickled_vec(),
+            ObjectInner::QuarantineSettings(obj) => obj.to_pickled_vec(),
+            ObjectInner::QuarantinedMessage(obj) => obj.to_pickled_vec(),
             ObjectInner::QueuedMessage(obj) => obj.to_pickled_vec(),
             ObjectInner::ReportSettings(obj) => obj.to_pickled_vec(),
             ObjectInner::Role(obj) => obj.to_pickled_vec(),
@@ -4815,6 +4996,7 @@ impl ObjectInner {
             ObjectType::Metric => Pickle::unpickle(stream).map(ObjectInner::Metric),
             ObjectType::Metrics => Pickle::unpickle(stream).map(ObjectInner::Metrics),
             ObjectType::MetricsStore => Pickle::unpickle(stream).map(ObjectInner::MetricsStore),
+            ObjectType::MtaAntivirus => Pickle::unpickle(stream).map(ObjectInner::MtaAntivirus),
             ObjectType::MtaConnectionStrategy => {
                 Pickle::unpickle(stream).map(ObjectInner::MtaConnectionStrategy)
             }
@@ -4856,7 +5038,14 @@ impl ObjectInner {
             }
             ObjectType::OAuthClient => Pickle::unpickle(stream).map(ObjectInner::OAuthClient),
             ObjectType::OidcProvider => Pickle::unpickle(stream).map(ObjectInner::OidcProvider),
+            ObjectType::Passkey => Pickle::unpickle(stream).map(ObjectInner::Passkey),
             ObjectType::PublicKey => Pickle::unpickle(stream).map(ObjectInner::PublicKey),
+            ObjectType::QuarantineSettings => {
+                Pickle::unpickle(stream).map(ObjectInner::QuarantineSettings)
+            }
+            ObjectType::QuarantinedMessage => {
+                Pickle::unpickle(stream).map(ObjectInner::QuarantinedMessage)
+            }
             ObjectType::QueuedMessage => Pickle::unpickle(stream).map(ObjectInner::QueuedMessage),
             ObjectType::ReportSettings => Pickle::unpickle(stream).map(ObjectInner::ReportSettings),
             ObjectType::Role => Pickle::unpickle(stream).map(ObjectInner::Role),
@@ -5061,6 +5250,9 @@ impl ObjectInner {
             ObjectType::MetricsStore => {
                 MetricsStore::deserialize(deserializer).map(ObjectInner::MetricsStore)
             }
+            ObjectType::MtaAntivirus => {
+                MtaAntivirus::deserialize(deserializer).map(ObjectInner::MtaAntivirus)
+            }
             ObjectType::MtaConnectionStrategy => MtaConnectionStrategy::deserialize(deserializer)
                 .map(ObjectInner::MtaConnectionStrategy),
             ObjectType::MtaDeliverySchedule => {
@@ -5123,9 +5315,16 @@ impl ObjectInner {
             ObjectType::OidcProvider => {
                 OidcProvider::deserialize(deserializer).map(ObjectInner::OidcProvider)
             }
+            ObjectType::Passkey => Passkey::deserialize(deserializer).map(ObjectInner::Passkey),
             ObjectType::PublicKey => {
                 PublicKey::deserialize(deserializer).map(ObjectInner::PublicKey)
             }
+            ObjectType::QuarantineSettings => {
+                QuarantineSettings::deserialize(deserializer).map(ObjectInner::QuarantineSettings)
+            }
+            ObjectType::QuarantinedMessage => {
+                QuarantinedMessage::deserialize(deserializer).map(ObjectInner::QuarantinedMessage)
+            }
             ObjectType::QueuedMessage => {
                 QueuedMessage::deserialize(deserializer).map(ObjectInner::QueuedMessage)
             }
@@ -5302,6 +5501,7 @@ impl Object {
             ObjectInner::Metric(_) => Metric::FLAGS,
             ObjectInner::Metrics(_) => Metrics::FLAGS,
             ObjectInner::MetricsStore(_) => MetricsStore::FLAGS,
+            ObjectInner::MtaAntivirus(_) => MtaAntivirus::FLAGS,
             ObjectInner::MtaConnectionStrategy(_) => MtaConnectionStrategy::FLAGS,
             ObjectInner::MtaDeliverySchedule(_) => MtaDeliverySchedule::FLAGS,
             ObjectInner::MtaExtensions(_) => MtaExtensions::FLAGS,
@@ -5325,7 +5525,10 @@ impl Object {
             ObjectInner::NetworkListener(_) => NetworkListener::FLAGS,
             ObjectInner::OAuthClient(_) => OAuthClient::FLAGS,
             ObjectInner::OidcProvider(_) => OidcProvider::FLAGS,
+            ObjectInner::Passkey(_) => Passkey::FLAGS,
             ObjectInner::PublicKey(_) => PublicKey::FLAGS,
+            ObjectInner::QuarantineSettings(_) => QuarantineSettings::FLAGS,
+            ObjectInner::QuarantinedMessage(_) => QuarantinedMessage::FLAGS,
             ObjectInner::QueuedMessage(_) => QueuedMessage::FLAGS,
             ObjectInner::ReportSettings(_) => ReportSettings::FLAGS,
             ObjectInner::Role(_) => Role::FLAGS,
@@ -5424,6 +5627,7 @@ impl Object {
             ObjectInner::Metric(_) => ObjectType::Metric,
             ObjectInner::Metrics(_) => ObjectType::Metrics,
             ObjectInner::MetricsStore(_) => ObjectType::MetricsStore,
+            ObjectInner::MtaAntivirus(_) => ObjectType::MtaAntivirus,
             ObjectInner::MtaConnectionStrategy(_) => ObjectType::MtaConnectionStrategy,
             ObjectInner::MtaDeliverySchedule(_) => ObjectType::MtaDeliverySchedule,
             ObjectInner::MtaExtensions(_) => ObjectType::MtaExtensions,
@@ -5447,7 +5651,10 @@ impl Object {
             ObjectInner::NetworkListener(_) => ObjectType::NetworkListener,
             ObjectInner::OAuthClient(_) => ObjectType::OAuthClient,
             ObjectInner::OidcProvider(_) => ObjectType::OidcProvider,
+            ObjectInner::Passkey(_) => ObjectType::Passkey,
             ObjectInner::PublicKey(_) => ObjectType::PublicKey,
+            ObjectInner::QuarantineSettings(_) => ObjectType::QuarantineSettings,
+            ObjectInner::QuarantinedMessage(_) => ObjectType::QuarantinedMessage,
             ObjectInner::QueuedMessage(_) => ObjectType::QueuedMessage,
             ObjectInner::ReportSettings(_) => ObjectType::ReportSettings,
             ObjectInner::Role(_) => ObjectType::Role,
@@ -5575,6 +5782,7 @@ impl Object {
             ObjectInner::Metric(obj) => obj.validate(errors),
             ObjectInner::Metrics(obj) => obj.validate(errors),
             ObjectInner::MetricsStore(obj) => obj.validate(errors),
+            ObjectInner::MtaAntivirus(obj) => obj.validate(errors),
             ObjectInner::MtaConnectionStrategy(obj) => obj.validate(errors),
             ObjectInner::MtaDeliverySchedule(obj) => obj.validate(errors),
             ObjectInner::MtaExtensions(obj) => obj.validate(errors),
@@ -5598,7 +5806,10 @@ impl Object {
             ObjectInner::NetworkListener(obj) => obj.validate(errors),
             ObjectInner::OAuthClient(obj) => obj.validate(errors),
             ObjectInner::OidcProvider(obj) => obj.validate(errors),
+            ObjectInner::Passkey(obj) => obj.validate(errors),
             ObjectInner::PublicKey(obj) => obj.validate(errors),
+            ObjectInner::QuarantineSettings(obj) => obj.validate(errors),
+            ObjectInner::QuarantinedMessage(obj) => obj.validate(errors),
             ObjectInner::QueuedMessage(obj) => obj.validate(errors),
             ObjectInner::ReportSettings(obj) => obj.validate(errors),
             ObjectInner::Role(obj) => obj.validate(errors),
@@ -5697,6 +5908,7 @@ impl Object {
             ObjectInner::Metric(obj) => obj.index(i),
             ObjectInner::Metrics(obj) => obj.index(i),
             ObjectInner::MetricsStore(obj) => obj.index(i),
+            ObjectInner::MtaAntivirus(obj) => obj.index(i),
             ObjectInner::MtaConnectionStrategy(obj) => obj.index(i),
             ObjectInner::MtaDeliverySchedule(obj) => obj.index(i),
             ObjectInner::MtaExtensions(obj) => obj.index(i),
@@ -5720,7 +5932,10 @@ impl Object {
             ObjectInner::NetworkListener(obj) => obj.index(i),
             ObjectInner::OAuthClient(obj) => obj.index(i),
             ObjectInner::OidcProvider(obj) => obj.index(i),
+            ObjectInner::Passkey(obj) => obj.index(i),
             ObjectInner::PublicKey(obj) => obj.index(i),
+            ObjectInner::QuarantineSettings(obj) => obj.index(i),
+            ObjectInner::QuarantinedMessage(obj) => obj.index(i),
             ObjectInner::QueuedMessage(obj) => obj.index(i),
             ObjectInner::ReportSettings(obj) => obj.index(i),
             ObjectInner::Role(obj) => obj.index(i),
@@ -5823,6 +6038,7 @@ impl Object {
             ObjectInner::Metric(obj) => obj.patch(pointer, value),
             ObjectInner::Metrics(obj) => obj.patch(pointer, value),
             ObjectInner::MetricsStore(obj) => obj.patch(pointer, value),
+            ObjectInner::MtaAntivirus(obj) => obj.patch(pointer, value),
             ObjectInner::MtaConnectionStrategy(obj) => obj.patch(pointer, value),
             ObjectInner::MtaDeliverySchedule(obj) => obj.patch(pointer, value),
             ObjectInner::MtaExtensions(obj) => obj.patch(pointer, value),
@@ -5846,7 +6062,10 @@ impl Object {
             ObjectInner::NetworkListener(obj) => obj.patch(pointer, value),
             ObjectInner::OAuthClient(obj) => obj.patch(pointer, value),
             ObjectInner::OidcProvider(obj) => obj.patch(pointer, value),
+            ObjectInner::Passkey(obj) => obj.patch(pointer, value),
             ObjectInner::PublicKey(obj) => obj.patch(pointer, value),
+            ObjectInner::QuarantineSettings(obj) => obj.patch(pointer, value),
+            ObjectInner::QuarantinedMessage(obj) => obj.patch(pointer, value),
             ObjectInner::QueuedMessage(obj) => obj.patch(pointer, value),
             ObjectInner::ReportSettings(obj) => obj.patch(pointer, value),
             ObjectInner::Role(obj) => obj.patch(pointer, value),
@@ -5947,6 +6166,7 @@ impl IntoValue for Object {
             ObjectInner::Metric(obj) => obj.into_value(),
             ObjectInner::Metrics(obj) => obj.into_value(),
             ObjectInner::MetricsStore(obj) => obj.into_value(),
+            ObjectInner::MtaAntivirus(obj) => obj.into_value(),
             ObjectInner::MtaConnectionStrategy(obj) => obj.into_value(),
             ObjectInner::MtaDeliverySchedule(obj) => obj.into_value(),
             ObjectInner::MtaExtensions(obj) => obj.into_value(),
@@ -5970,7 +6190,10 @@ impl IntoValue for Object {
             ObjectInner::NetworkListener(obj) => obj.into_value(),
             ObjectInner::OAuthClient(obj) => obj.into_value(),
             ObjectInner::OidcProvider(obj) => obj.into_value(),
+            ObjectInner::Passkey(obj) => obj.into_value(),
             ObjectInner::PublicKey(obj) => obj.into_value(),
+            ObjectInner::QuarantineSettings(obj) => obj.into_value(),
+            ObjectInner::QuarantinedMessage(obj) => obj.into_value(),
             ObjectInner::QueuedMessage(obj) => obj.into_value(),
             ObjectInner::ReportSettings(obj) => obj.into_value(),
             ObjectInner::Role(obj) => obj.into_value(),
@@ -6073,6 +6296,7 @@ impl From<ObjectType> for ObjectInner {
             ObjectType::Metric => ObjectInner::Metric(Default::default()),
             ObjectType::Metrics => ObjectInner::Metrics(Default::default()),
             ObjectType::MetricsStore => ObjectInner::MetricsStore(Default::default()),
+            ObjectType::MtaAntivirus => ObjectInner::MtaAntivirus(Default::default()),
             ObjectType::MtaConnectionStrategy => {
                 ObjectInner::MtaConnectionStrategy(Default::default())
             }
@@ -6998,6 +7222,21 @@ impl From<Object> for MetricsStore {
     }
 }
 
+impl From<MtaAntivirus> for ObjectInner {
+    fn from(value: MtaAntivirus) -> Self {
+        ObjectInner::MtaAntivirus(value)
+    }
+}
+
+impl From<Object> for MtaAntivirus {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::MtaAntivirus(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<MtaConnectionStrategy> for ObjectInner {
     fn from(value: MtaConnectionStrategy) -> Self {
         ObjectInner::MtaConnectionStrategy(value)
@@ -7343,6 +7582,21 @@ impl From<Object> for OidcProvider {
     }
 }
 
+impl From<Passkey> for ObjectInner {
+    fn from(value: Passkey) -> Self {
+        ObjectInner::Passkey(value)
+    }
+}
+
+impl From<Object> for Passkey {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::Passkey(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<PublicKey> for ObjectInner {
     fn from(value: PublicKey) -> Self {
         ObjectInner::PublicKey(value)
@@ -7358,6 +7612,36 @@ impl From<Object> for PublicKey {
     }
 }
 
+impl From<QuarantineSettings> for ObjectInner {
+    fn from(value: QuarantineSettings) -> Self {
+        ObjectInner::QuarantineSettings(value)
+    }
+}
+
+impl From<Object> for QuarantineSettings {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::QuarantineSettings(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<QuarantinedMessage> for ObjectInner {
+    fn from(value: QuarantinedMessage) -> Self {
+        ObjectInner::QuarantinedMessage(value)
+    }
+}
+
+impl From<Object> for QuarantinedMessage {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::QuarantinedMessage(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<QueuedMessage> for ObjectInner {
     fn from(value: QueuedMessage) -> Self {
         ObjectInner::QueuedMessage(value)
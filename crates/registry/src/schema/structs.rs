@@ -12,6 +12,8 @@ use crate::schema::prelude::*;
 pub enum Account {
     User(UserAccount),
     Group(GroupAccount),
+    Resource(ResourceAccount),
+    Location(ResourceAccount),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -797,6 +799,7 @@ pub enum Credential {
     Password(PasswordCredential),
     AppPassword(SecondaryCredential),
     ApiKey(SecondaryCredential),
+    Passkey(Passkey),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3092,6 +3095,10 @@ pub struct Imap {
     pub max_auth_failures: u64,
     #[serde(rename = "maxConcurrent")]
     pub max_concurrent: Option<u64>,
+    #[serde(rename = "maxMetadataEntries")]
+    pub max_metadata_entries: u64,
+    #[serde(rename = "maxMetadataSize")]
+    pub max_metadata_size: u64,
     #[serde(rename = "maxRequestRate")]
     pub max_request_rate: Option<Rate>,
     #[serde(rename = "maxRequestSize")]
@@ -3185,6 +3192,10 @@ pub struct Jmap {
     pub web_push_key: SecretTextOptional,
     #[serde(rename = "webPushContact")]
     pub web_push_contact: Option<String>,
+    #[serde(rename = "smimeTrustAnchors")]
+    pub smime_trust_anchors: Option<String>,
+    #[serde(rename = "smimeCacheTtl")]
+    pub smime_cache_ttl: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3327,6 +3338,22 @@ pub struct MailingList {
     pub member_tenant_id: Option<Id>,
     #[serde(rename = "recipients")]
     pub recipients: Map<String>,
+    #[serde(rename = "managed")]
+    pub managed: bool,
+    #[serde(rename = "subscriptionPolicy")]
+    pub subscription_policy: MailingListSubscriptionPolicy,
+    #[serde(rename = "postingPolicy")]
+    pub posting_policy: MailingListPostingPolicy,
+    #[serde(rename = "moderators")]
+    pub moderators: Map<String>,
+    #[serde(rename = "fromRewrite")]
+    pub from_rewrite: MailingListFromRewrite,
+    #[serde(rename = "archiveAddress")]
+    pub archive_address: Option<String>,
+    #[serde(rename = "bounceThreshold")]
+    pub bounce_threshold: u64,
+    #[serde(rename = "suspendedRecipients")]
+    pub suspended_recipients: Map<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3507,6 +3534,29 @@ pub struct MtaConnectionIpHost {
     pub source_ip: IpAddr,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MtaAntivirus {
+    #[serde(rename = "action")]
+    pub action: AntivirusAction,
+    #[serde(rename = "enable")]
+    pub enable: bool,
+    #[serde(rename = "host")]
+    pub host: String,
+    #[serde(rename = "maxSize")]
+    pub max_size: u64,
+    #[serde(rename = "port")]
+    pub port: u64,
+    #[serde(rename = "scanMode")]
+    pub scan_mode: AntivirusScanMode,
+    #[serde(rename = "socketPath")]
+    pub socket_path: Option<String>,
+    #[serde(rename = "tempFailOnError")]
+    pub temp_fail_on_error: bool,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct MtaConnectionStrategy {
@@ -3614,6 +3664,12 @@ pub struct MtaExtensions {
     pub require_tls: Expression,
     #[serde(rename = "vrfy")]
     pub vrfy: Expression,
+    #[serde(rename = "etrn")]
+    pub etrn: Expression,
+    #[serde(rename = "atrn")]
+    pub atrn: Expression,
+    #[serde(rename = "burl")]
+    pub burl: Expression,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4051,6 +4107,14 @@ pub struct NetworkListener {
     pub tls_timeout: Option<Duration>,
     #[serde(rename = "maxConnections")]
     pub max_connections: Option<u64>,
+    #[serde(rename = "tlsClientAuth")]
+    pub tls_client_auth: TlsClientAuth,
+    #[serde(rename = "tlsClientCa")]
+    pub tls_client_ca: Option<String>,
+    #[serde(rename = "tlsClientCertMapping")]
+    pub tls_client_cert_mapping: TlsClientCertMapping,
+    #[serde(rename = "tlsClientCertFingerprints")]
+    pub tls_client_cert_fingerprints: VecMap<String, String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4137,6 +4201,27 @@ pub struct OtpAuth {
     pub otp_url: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Passkey {
+    #[serde(rename = "credentialId")]
+    pub credential_id: Id,
+    #[serde(rename = "description")]
+    pub description: String,
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    #[serde(rename = "algorithm")]
+    pub algorithm: PasskeyAlgorithm,
+    #[serde(rename = "signCount")]
+    pub sign_count: u64,
+    #[serde(rename = "createdAt")]
+    pub created_at: UTCDateTime,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<UTCDateTime>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct PasswordCredential {
@@ -4150,6 +4235,8 @@ pub struct PasswordCredential {
     pub expires_at: Option<UTCDateTime>,
     #[serde(rename = "allowedIps")]
     pub allowed_ips: Map<IpAddrOrMask>,
+    #[serde(rename = "scramSecret")]
+    pub scram_secret: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4247,6 +4334,48 @@ pub struct PublicTextValue {
     pub value: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuarantineSettings {
+    #[serde(rename = "holdFor")]
+    pub hold_for: Duration,
+    #[serde(rename = "quarantineSpam")]
+    pub quarantine_spam: bool,
+    #[serde(rename = "sendDigest")]
+    pub send_digest: bool,
+    #[serde(rename = "schedule")]
+    pub schedule: Cron,
+    #[serde(rename = "fromName")]
+    pub from_name: String,
+    #[serde(rename = "fromAddress")]
+    pub from_address: Option<String>,
+    #[serde(rename = "subject")]
+    pub subject: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuarantinedMessage {
+    #[serde(rename = "from")]
+    pub from: String,
+    #[serde(rename = "subject")]
+    pub subject: String,
+    #[serde(rename = "reason")]
+    pub reason: String,
+    #[serde(rename = "receivedAt")]
+    pub received_at: UTCDateTime,
+    #[serde(rename = "size")]
+    pub size: u64,
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "blobId")]
+    pub blob_id: BlobId,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: UTCDateTime,
+    #[serde(rename = "notified")]
+    pub notified: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "@type")]
 pub enum QueueExpiry {
@@ -4428,6 +4557,41 @@ pub struct ReportSettings {
     pub outbound_report_submitter: Expression,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceAccount {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "domainId")]
+    pub domain_id: Id,
+    #[serde(rename = "description")]
+    pub description: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: UTCDateTime,
+    #[serde(rename = "memberTenantId")]
+    pub member_tenant_id: Option<Id>,
+    #[serde(rename = "bookingPolicy")]
+    pub booking_policy: BookingPolicy,
+    #[serde(rename = "bookingWindow")]
+    pub booking_window: Option<Duration>,
+    #[serde(rename = "maxDuration")]
+    pub max_duration: Option<Duration>,
+    #[serde(rename = "allowedBookerIds")]
+    pub allowed_booker_ids: Map<Id>,
+    #[serde(rename = "roles")]
+    pub roles: Roles,
+    #[serde(rename = "quotas")]
+    pub quotas: VecMap<StorageQuota, u64>,
+    #[serde(rename = "permissions")]
+    pub permissions: Permissions,
+    #[serde(rename = "aliases")]
+    pub aliases: List<EmailAlias>,
+    #[serde(rename = "locale")]
+    pub locale: Locale,
+    #[serde(rename = "timeZone")]
+    pub time_zone: Option<TimeZone>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RocksDbStore {
@@ -4574,6 +4738,18 @@ pub struct Search {
     pub index_telemetry: bool,
     #[serde(rename = "indexTracingFields")]
     pub index_tracing_fields: Map<SearchTracingField>,
+    #[serde(rename = "indexFiles")]
+    pub index_files: bool,
+    #[serde(rename = "indexFileFields")]
+    pub index_file_fields: Map<SearchFileField>,
+    #[serde(rename = "extractAttachments")]
+    pub extract_attachments: bool,
+    #[serde(rename = "extractMaxSize")]
+    pub extract_max_size: u64,
+    #[serde(rename = "extractTimeout")]
+    pub extract_timeout: Duration,
+    #[serde(rename = "extractCacheTtl")]
+    pub extract_cache_ttl: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4710,6 +4886,8 @@ pub struct SenderAuth {
     pub dmarc_verify: Expression,
     #[serde(rename = "reverseIpVerify")]
     pub reverse_ip_verify: Expression,
+    #[serde(rename = "arcSealDomain")]
+    pub arc_seal_domain: Expression,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -5467,6 +5645,35 @@ pub enum Task {
     AcmeRenewal(TaskDomainManagement),
     DkimManagement(TaskDomainManagement),
     DnsManagement(TaskDnsManagement),
+    AccountExport(TaskAccountExport),
+    AccountImport(TaskAccountImport),
+    QuarantineDigest(TaskQuarantineDigest),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskAccountExport {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "format")]
+    pub format: AccountArchiveFormat,
+    #[serde(rename = "path")]
+    pub path: String,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskAccountImport {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "format")]
+    pub format: AccountArchiveFormat,
+    #[serde(rename = "path")]
+    pub path: String,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -5640,6 +5847,13 @@ pub struct TaskMergeThreads {
     pub status: TaskStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskQuarantineDigest {
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TaskRestoreArchivedItem {
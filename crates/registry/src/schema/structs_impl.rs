@@ -10,13 +10,15 @@ use crate::schema::prelude::*;
 
 impl ObjectImpl for Account {
     const FLAGS: u64 = OBJ_FILTER_TENANT | OBJ_SEQ_ID;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::Account;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         match self {
             Account::User(inner) => inner.validate(errors),
             Account::Group(inner) => inner.validate(errors),
+            Account::Resource(inner) => inner.validate(errors),
+            Account::Location(inner) => inner.validate(errors),
         }
     }
 
@@ -30,6 +32,14 @@ impl ObjectImpl for Account {
                 i.typ(1);
                 object.index(i);
             }
+            Account::Resource(object) => {
+                i.typ(2);
+                object.index(i);
+            }
+            Account::Location(object) => {
+                i.typ(3);
+                object.index(i);
+            }
         }
     }
 }
@@ -51,6 +61,14 @@ impl Pickle for Account {
                 1u16.pickle(out);
                 inner.pickle(out);
             }
+            Account::Resource(inner) => {
+                2u16.pickle(out);
+                inner.pickle(out);
+            }
+            Account::Location(inner) => {
+                3u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -58,6 +76,8 @@ impl Pickle for Account {
         match u16::unpickle(stream)? {
             0 => Pickle::unpickle(stream).map(Account::User),
             1 => Pickle::unpickle(stream).map(Account::Group),
+            2 => Pickle::unpickle(stream).map(Account::Resource),
+            3 => Pickle::unpickle(stream).map(Account::Location),
             _ => None,
         }
     }
@@ -80,6 +100,20 @@ impl IntoValue for Account {
                     .insert_unchecked(Property::Type, JmapValue::Str("Group".into()));
                 obj
             }
+            Account::Resource(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Resource".into()));
+                obj
+            }
+            Account::Location(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Location".into()));
+                obj
+            }
         }
     }
 }
@@ -94,11 +128,15 @@ impl RegistryJsonPatch for Account {
             match object_type(&pointer, &value)? {
                 AccountType::User => *self = Account::User(Default::default()),
                 AccountType::Group => *self = Account::Group(Default::default()),
+                AccountType::Resource => *self = Account::Resource(Default::default()),
+                AccountType::Location => *self = Account::Location(Default::default()),
             }
         }
         match self {
             Account::User(inner) => inner.patch(pointer, value),
             Account::Group(inner) => inner.patch(pointer, value),
+            Account::Resource(inner) => inner.patch(pointer, value),
+            Account::Location(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -108,6 +146,8 @@ impl Account {
         match self {
             Account::User(_) => AccountType::User,
             Account::Group(_) => AccountType::Group,
+            Account::Resource(_) => AccountType::Resource,
+            Account::Location(_) => AccountType::Location,
         }
     }
 }
@@ -5902,6 +5942,7 @@ impl Credential {
             Credential::Password(inner) => inner.validate(errors),
             Credential::AppPassword(inner) => inner.validate(errors),
             Credential::ApiKey(inner) => inner.validate(errors),
+            Credential::Passkey(inner) => inner.validate(errors),
         }
     }
 }
@@ -5927,6 +5968,10 @@ impl Pickle for Credential {
                 2u16.pickle(out);
                 inner.pickle(out);
             }
+            Credential::Passkey(inner) => {
+                3u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -5935,6 +5980,7 @@ impl Pickle for Credential {
             0 => Pickle::unpickle(stream).map(Credential::Password),
             1 => Pickle::unpickle(stream).map(Credential::AppPassword),
             2 => Pickle::unpickle(stream).map(Credential::ApiKey),
+            3 => Pickle::unpickle(stream).map(Credential::Passkey),
             _ => None,
         }
     }
@@ -5964,6 +6010,13 @@ impl IntoValue for Credential {
                     .insert_unchecked(Property::Type, JmapValue::Str("ApiKey".into()));
                 obj
             }
+            Credential::Passkey(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Passkey".into()));
+                obj
+            }
         }
     }
 }
@@ -5979,12 +6032,14 @@ impl RegistryJsonPatch for Credential {
                 CredentialType::Password => *self = Credential::Password(Default::default()),
                 CredentialType::AppPassword => *self = Credential::AppPassword(Default::default()),
                 CredentialType::ApiKey => *self = Credential::ApiKey(Default::default()),
+                CredentialType::Passkey => *self = Credential::Passkey(Default::default()),
             }
         }
         match self {
             Credential::Password(inner) => inner.patch(pointer, value),
             Credential::AppPassword(inner) => inner.patch(pointer, value),
             Credential::ApiKey(inner) => inner.patch(pointer, value),
+            Credential::Passkey(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -5995,6 +6050,7 @@ impl Credential {
             Credential::Password(_) => CredentialType::Password,
             Credential::AppPassword(_) => CredentialType::AppPassword,
             Credential::ApiKey(_) => CredentialType::ApiKey,
+            Credential::Passkey(_) => CredentialType::Passkey,
         }
     }
 }
@@ -22425,7 +22481,7 @@ impl RegistryJsonPropertyPatch for HurricaneCredential {
 
 impl ObjectImpl for Imap {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::Imap;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -22439,6 +22495,10 @@ impl ObjectImpl for Imap {
                 errors.push(ValidationError::min_value(Property::MaxConcurrent, 1));
             }
         }
+        let value = &self.max_metadata_entries;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::MaxMetadataEntries, 1));
+        }
         if let Some(value) = &self.max_request_rate {
             value.validate(errors);
         }
@@ -22458,6 +22518,8 @@ impl Pickle for Imap {
         self.timeout_anonymous.pickle(out);
         self.timeout_authenticated.pickle(out);
         self.timeout_idle.pickle(out);
+        self.max_metadata_entries.pickle(out);
+        self.max_metadata_size.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -22470,6 +22532,12 @@ impl Pickle for Imap {
         this.timeout_anonymous = Pickle::unpickle(stream)?;
         this.timeout_authenticated = Pickle::unpickle(stream)?;
         this.timeout_idle = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.max_metadata_entries = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.max_metadata_size = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -22480,6 +22548,8 @@ impl Default for Imap {
             allow_plain_text_auth: false,
             max_auth_failures: 3u64,
             max_concurrent: Some(16u64),
+            max_metadata_entries: 100u64,
+            max_metadata_size: 65536,
             max_request_rate: Some(Rate {
                 count: 2000u64,
                 period: Duration::from_millis(60000),
@@ -22494,7 +22564,7 @@ impl Default for Imap {
 
 impl IntoValue for Imap {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(10);
+        let mut map = jmap_tools::Map::with_capacity(12);
         map.insert_unchecked(
             Property::AllowPlainTextAuth,
             self.allow_plain_text_auth.into_value(),
@@ -22504,6 +22574,14 @@ impl IntoValue for Imap {
             self.max_auth_failures.into_value(),
         );
         map.insert_unchecked(Property::MaxConcurrent, self.max_concurrent.into_value());
+        map.insert_unchecked(
+            Property::MaxMetadataEntries,
+            self.max_metadata_entries.into_value(),
+        );
+        map.insert_unchecked(
+            Property::MaxMetadataSize,
+            self.max_metadata_size.into_value(),
+        );
         map.insert_unchecked(Property::MaxRequestRate, self.max_request_rate.into_value());
         map.insert_unchecked(Property::MaxRequestSize, self.max_request_size.into_value());
         map.insert_unchecked(
@@ -22529,6 +22607,8 @@ impl RegistryJsonPropertyPatch for Imap {
             Some(Property::AllowPlainTextAuth) => self.allow_plain_text_auth.patch(pointer, value),
             Some(Property::MaxAuthFailures) => self.max_auth_failures.patch(pointer, value),
             Some(Property::MaxConcurrent) => self.max_concurrent.patch(pointer, value),
+            Some(Property::MaxMetadataEntries) => self.max_metadata_entries.patch(pointer, value),
+            Some(Property::MaxMetadataSize) => self.max_metadata_size.patch(pointer, value),
             Some(Property::MaxRequestRate) => self.max_request_rate.patch(pointer, value),
             Some(Property::MaxRequestSize) => self.max_request_size.patch(pointer, value),
             Some(Property::TimeoutAnonymous) => self.timeout_anonymous.patch(pointer, value),
@@ -22798,7 +22878,7 @@ impl InMemoryStoreBase {
 
 impl ObjectImpl for Jmap {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 1;
+    const VERSION: u8 = 2;
     const OBJECT: ObjectType = ObjectType::Jmap;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -22891,6 +22971,11 @@ impl ObjectImpl for Jmap {
                 errors.push(ValidationError::required(Property::WebPushContact));
             }
         }
+        if let Some(value) = &self.smime_trust_anchors {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::SmimeTrustAnchors));
+            }
+        }
         errors.len() == neb
     }
 
@@ -22929,6 +23014,8 @@ impl Pickle for Jmap {
         self.max_subscriptions.pickle(out);
         self.web_push_key.pickle(out);
         self.web_push_contact.pickle(out);
+        self.smime_trust_anchors.pickle(out);
+        self.smime_cache_ttl.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -22967,6 +23054,12 @@ impl Pickle for Jmap {
         if stream.version() >= 1 {
             this.web_push_contact = Pickle::unpickle(stream)?;
         }
+        if stream.version() >= 2 {
+            this.smime_trust_anchors = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 2 {
+            this.smime_cache_ttl = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -23004,13 +23097,15 @@ impl Default for Jmap {
             max_subscriptions: Some(15u64),
             web_push_key: Default::default(),
             web_push_contact: Default::default(),
+            smime_trust_anchors: Default::default(),
+            smime_cache_ttl: Duration::from_millis(86400000),
         }
     }
 }
 
 impl IntoValue for Jmap {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(32);
+        let mut map = jmap_tools::Map::with_capacity(34);
         map.insert_unchecked(
             Property::ParseLimitEvent,
             self.parse_limit_event.into_value(),
@@ -23095,6 +23190,11 @@ impl IntoValue for Jmap {
         );
         map.insert_unchecked(Property::WebPushKey, self.web_push_key.into_value());
         map.insert_unchecked(Property::WebPushContact, self.web_push_contact.into_value());
+        map.insert_unchecked(
+            Property::SmimeTrustAnchors,
+            self.smime_trust_anchors.into_value(),
+        );
+        map.insert_unchecked(Property::SmimeCacheTtl, self.smime_cache_ttl.into_value());
         JmapValue::Object(map)
     }
 }
@@ -23142,6 +23242,8 @@ impl RegistryJsonPropertyPatch for Jmap {
             Some(Property::WebPushContact) => self
                 .web_push_contact
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::SmimeTrustAnchors) => self.smime_trust_anchors.patch(pointer, value),
+            Some(Property::SmimeCacheTtl) => self.smime_cache_ttl.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -24091,7 +24193,7 @@ impl RegistryJsonPropertyPatch for MailExchanger {
 
 impl ObjectImpl for MailingList {
     const FLAGS: u64 = OBJ_FILTER_TENANT | OBJ_SEQ_ID;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::MailingList;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -24124,6 +24226,23 @@ impl ObjectImpl for MailingList {
                 errors.push(ValidationError::required(Property::Recipients));
             }
         }
+        let value = &self.moderators;
+        for value in value.iter() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Moderators));
+            }
+        }
+        if let Some(value) = &self.archive_address {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::ArchiveAddress));
+            }
+        }
+        let value = &self.suspended_recipients;
+        for value in value.iter() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::SuspendedRecipients));
+            }
+        }
         errors.len() == neb
     }
 
@@ -24144,6 +24263,9 @@ impl ObjectImpl for MailingList {
         for value in self.recipients.iter() {
             i.text(Property::Text, value);
         }
+        for value in self.moderators.iter() {
+            i.text(Property::Text, value);
+        }
     }
 }
 
@@ -24155,6 +24277,14 @@ impl Pickle for MailingList {
         self.aliases.pickle(out);
         self.member_tenant_id.pickle(out);
         self.recipients.pickle(out);
+        self.managed.pickle(out);
+        self.subscription_policy.pickle(out);
+        self.posting_policy.pickle(out);
+        self.moderators.pickle(out);
+        self.from_rewrite.pickle(out);
+        self.archive_address.pickle(out);
+        self.bounce_threshold.pickle(out);
+        self.suspended_recipients.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -24165,6 +24295,30 @@ impl Pickle for MailingList {
         this.aliases = Pickle::unpickle(stream)?;
         this.member_tenant_id = Pickle::unpickle(stream)?;
         this.recipients = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.managed = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.subscription_policy = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.posting_policy = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.moderators = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.from_rewrite = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.archive_address = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.bounce_threshold = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.suspended_recipients = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -24178,19 +24332,44 @@ impl Default for MailingList {
             aliases: Default::default(),
             member_tenant_id: Default::default(),
             recipients: Default::default(),
+            managed: false,
+            subscription_policy: MailingListSubscriptionPolicy::Confirm,
+            posting_policy: MailingListPostingPolicy::Moderated,
+            moderators: Default::default(),
+            from_rewrite: MailingListFromRewrite::Dmarc,
+            archive_address: Default::default(),
+            bounce_threshold: 5,
+            suspended_recipients: Default::default(),
         }
     }
 }
 
 impl IntoValue for MailingList {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(8);
+        let mut map = jmap_tools::Map::with_capacity(16);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::DomainId, self.domain_id.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Aliases, self.aliases.into_value());
         map.insert_unchecked(Property::MemberTenantId, self.member_tenant_id.into_value());
         map.insert_unchecked(Property::Recipients, self.recipients.into_value());
+        map.insert_unchecked(Property::Managed, self.managed.into_value());
+        map.insert_unchecked(
+            Property::SubscriptionPolicy,
+            self.subscription_policy.into_value(),
+        );
+        map.insert_unchecked(Property::PostingPolicy, self.posting_policy.into_value());
+        map.insert_unchecked(Property::Moderators, self.moderators.into_value());
+        map.insert_unchecked(Property::FromRewrite, self.from_rewrite.into_value());
+        map.insert_unchecked(Property::ArchiveAddress, self.archive_address.into_value());
+        map.insert_unchecked(
+            Property::BounceThreshold,
+            self.bounce_threshold.into_value(),
+        );
+        map.insert_unchecked(
+            Property::SuspendedRecipients,
+            self.suspended_recipients.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -24216,6 +24395,20 @@ impl RegistryJsonPropertyPatch for MailingList {
             Some(Property::Recipients) => self
                 .recipients
                 .patch(pointer.with_validators(&[StringValidator::Email]), value),
+            Some(Property::Managed) => self.managed.patch(pointer, value),
+            Some(Property::SubscriptionPolicy) => self.subscription_policy.patch(pointer, value),
+            Some(Property::PostingPolicy) => self.posting_policy.patch(pointer, value),
+            Some(Property::Moderators) => self
+                .moderators
+                .patch(pointer.with_validators(&[StringValidator::Email]), value),
+            Some(Property::FromRewrite) => self.from_rewrite.patch(pointer, value),
+            Some(Property::ArchiveAddress) => self
+                .archive_address
+                .patch(pointer.with_validators(&[StringValidator::Email]), value),
+            Some(Property::BounceThreshold) => self.bounce_threshold.patch(pointer, value),
+            Some(Property::SuspendedRecipients) => self
+                .suspended_recipients
+                .patch(pointer.with_validators(&[StringValidator::Email]), value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -25582,6 +25775,124 @@ impl RegistryJsonPropertyPatch for MtaConnectionIpHost {
     }
 }
 
+impl ObjectImpl for MtaAntivirus {
+    const FLAGS: u64 = OBJ_SINGLETON;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::MtaAntivirus;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.host;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Host));
+        }
+        let value = &self.max_size;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::MaxSize, 1));
+        }
+        let value = &self.port;
+        if *value > 65535 {
+            errors.push(ValidationError::max_value(Property::Port, 65535));
+        }
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::Port, 1));
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Pickle for MtaAntivirus {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.action.pickle(out);
+        self.enable.pickle(out);
+        self.host.pickle(out);
+        self.max_size.pickle(out);
+        self.port.pickle(out);
+        self.scan_mode.pickle(out);
+        self.socket_path.pickle(out);
+        self.temp_fail_on_error.pickle(out);
+        self.timeout.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.action = Pickle::unpickle(stream)?;
+        this.enable = Pickle::unpickle(stream)?;
+        this.host = Pickle::unpickle(stream)?;
+        this.max_size = Pickle::unpickle(stream)?;
+        this.port = Pickle::unpickle(stream)?;
+        this.scan_mode = Pickle::unpickle(stream)?;
+        this.socket_path = Pickle::unpickle(stream)?;
+        this.temp_fail_on_error = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for MtaAntivirus {
+    fn default() -> Self {
+        Self {
+            action: AntivirusAction::Reject,
+            enable: false,
+            host: "127.0.0.1".to_string(),
+            max_size: 26214400u64,
+            port: 3310u64,
+            scan_mode: AntivirusScanMode::Message,
+            socket_path: Default::default(),
+            temp_fail_on_error: false,
+            timeout: Duration::from_millis(30000),
+        }
+    }
+}
+
+impl IntoValue for MtaAntivirus {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(11);
+        map.insert_unchecked(Property::Action, self.action.into_value());
+        map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Host, self.host.into_value());
+        map.insert_unchecked(Property::MaxSize, self.max_size.into_value());
+        map.insert_unchecked(Property::Port, self.port.into_value());
+        map.insert_unchecked(Property::ScanMode, self.scan_mode.into_value());
+        map.insert_unchecked(Property::SocketPath, self.socket_path.into_value());
+        map.insert_unchecked(
+            Property::TempFailOnError,
+            self.temp_fail_on_error.into_value(),
+        );
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for MtaAntivirus {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Action) => self.action.patch(pointer, value),
+            Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Host) => self
+                .host
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::MaxSize) => self.max_size.patch(pointer, value),
+            Some(Property::Port) => self.port.patch(pointer, value),
+            Some(Property::ScanMode) => self.scan_mode.patch(pointer, value),
+            Some(Property::SocketPath) => self.socket_path.patch(pointer, value),
+            Some(Property::TempFailOnError) => self.temp_fail_on_error.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for MtaConnectionStrategy {
     const FLAGS: u64 = 0;
     const VERSION: u8 = 0;
@@ -26218,7 +26529,7 @@ impl MtaDeliveryScheduleIntervalsOrDefault {
 
 impl ObjectImpl for MtaExtensions {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 2;
     const OBJECT: ObjectType = ObjectType::MtaExtensions;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -26243,6 +26554,12 @@ impl ObjectImpl for MtaExtensions {
         value.validate(errors);
         let value = &self.vrfy;
         value.validate(errors);
+        let value = &self.etrn;
+        value.validate(errors);
+        let value = &self.atrn;
+        value.validate(errors);
+        let value = &self.burl;
+        value.validate(errors);
         errors.len() == neb
     }
 
@@ -26398,6 +26715,48 @@ impl MtaExtensions {
         }
     }
 
+    pub fn ctx_etrn(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.etrn,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            }),
+            property: Property::Etrn,
+            allowed_variables: MTA_MAIL_FROM_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
+    pub fn ctx_atrn(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.atrn,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            }),
+            property: Property::Atrn,
+            allowed_variables: MTA_MAIL_FROM_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
+    pub fn ctx_burl(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.burl,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                match_: List::from_iter([ExpressionMatch {
+                    if_: "!is_empty(authenticated_as)".to_string(),
+                    then: "true".to_string(),
+                }]),
+            }),
+            property: Property::Burl,
+            allowed_variables: MTA_MAIL_FROM_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
     pub fn expression_ctxs(&self) -> Vec<ExpressionContext<'_>> {
         vec![
             self.ctx_chunking(),
@@ -26410,6 +26769,9 @@ impl MtaExtensions {
             self.ctx_pipelining(),
             self.ctx_require_tls(),
             self.ctx_vrfy(),
+            self.ctx_etrn(),
+            self.ctx_atrn(),
+            self.ctx_burl(),
         ]
     }
 }
@@ -26426,6 +26788,9 @@ impl Pickle for MtaExtensions {
         self.pipelining.pickle(out);
         self.require_tls.pickle(out);
         self.vrfy.pickle(out);
+        self.etrn.pickle(out);
+        self.atrn.pickle(out);
+        self.burl.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -26440,6 +26805,13 @@ impl Pickle for MtaExtensions {
         this.pipelining = Pickle::unpickle(stream)?;
         this.require_tls = Pickle::unpickle(stream)?;
         this.vrfy = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.etrn = Pickle::unpickle(stream)?;
+            this.atrn = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 2 {
+            this.burl = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -26505,13 +26877,28 @@ impl Default for MtaExtensions {
                     then: "true".to_string(),
                 }]),
             },
+            etrn: Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            },
+            atrn: Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            },
+            burl: Expression {
+                else_: "false".to_string(),
+                match_: List::from_iter([ExpressionMatch {
+                    if_: "!is_empty(authenticated_as)".to_string(),
+                    then: "true".to_string(),
+                }]),
+            },
         }
     }
 }
 
 impl IntoValue for MtaExtensions {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(12);
+        let mut map = jmap_tools::Map::with_capacity(15);
         map.insert_unchecked(Property::Chunking, self.chunking.into_value());
         map.insert_unchecked(Property::DeliverBy, self.deliver_by.into_value());
         map.insert_unchecked(Property::Dsn, self.dsn.into_value());
@@ -26522,6 +26909,9 @@ impl IntoValue for MtaExtensions {
         map.insert_unchecked(Property::Pipelining, self.pipelining.into_value());
         map.insert_unchecked(Property::RequireTls, self.require_tls.into_value());
         map.insert_unchecked(Property::Vrfy, self.vrfy.into_value());
+        map.insert_unchecked(Property::Etrn, self.etrn.into_value());
+        map.insert_unchecked(Property::Atrn, self.atrn.into_value());
+        map.insert_unchecked(Property::Burl, self.burl.into_value());
         JmapValue::Object(map)
     }
 }
@@ -26543,6 +26933,9 @@ impl RegistryJsonPropertyPatch for MtaExtensions {
             Some(Property::Pipelining) => self.pipelining.patch(pointer, value),
             Some(Property::RequireTls) => self.require_tls.patch(pointer, value),
             Some(Property::Vrfy) => self.vrfy.patch(pointer, value),
+            Some(Property::Etrn) => self.etrn.patch(pointer, value),
+            Some(Property::Atrn) => self.atrn.patch(pointer, value),
+            Some(Property::Burl) => self.burl.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -28011,11 +28404,11 @@ impl MtaStageAuth {
                 match_: List::from_iter([
                     ExpressionMatch {
                         if_: "local_port != 25 && is_tls".to_string(),
-                        then: "[plain, login, oauthbearer, xoauth2]".to_string(),
+                        then: "[plain, login, scram_sha_256_plus, scram_sha_256, oauthbearer, xoauth2]".to_string(),
                     },
                     ExpressionMatch {
                         if_: "local_port != 25".to_string(),
-                        then: "[oauthbearer, xoauth2]".to_string(),
+                        then: "[scram_sha_256, oauthbearer, xoauth2]".to_string(),
                     },
                 ]),
             }),
@@ -28098,11 +28491,11 @@ impl Default for MtaStageAuth {
                 match_: List::from_iter([
                     ExpressionMatch {
                         if_: "local_port != 25 && is_tls".to_string(),
-                        then: "[plain, login, oauthbearer, xoauth2]".to_string(),
+                        then: "[plain, login, scram_sha_256_plus, scram_sha_256, oauthbearer, xoauth2]".to_string(),
                     },
                     ExpressionMatch {
                         if_: "local_port != 25".to_string(),
-                        then: "[oauthbearer, xoauth2]".to_string(),
+                        then: "[scram_sha_256, oauthbearer, xoauth2]".to_string(),
                     },
                 ]),
             },
@@ -29880,7 +30273,7 @@ impl RegistryJsonPropertyPatch for NatsCoordinator {
 
 impl ObjectImpl for NetworkListener {
     const FLAGS: u64 = 0;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::NetworkListener;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -29943,6 +30336,19 @@ impl ObjectImpl for NetworkListener {
                 errors.push(ValidationError::min_value(Property::MaxConnections, 1));
             }
         }
+        if let Some(value) = &self.tls_client_ca {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::TlsClientCa));
+            }
+        }
+        let value = &self.tls_client_cert_fingerprints;
+        for value in value.values() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(
+                    Property::TlsClientCertFingerprints,
+                ));
+            }
+        }
         errors.len() == neb
     }
 
@@ -29972,6 +30378,10 @@ impl Pickle for NetworkListener {
         self.tls_implicit.pickle(out);
         self.tls_timeout.pickle(out);
         self.max_connections.pickle(out);
+        self.tls_client_auth.pickle(out);
+        self.tls_client_ca.pickle(out);
+        self.tls_client_cert_mapping.pickle(out);
+        self.tls_client_cert_fingerprints.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -29995,6 +30405,18 @@ impl Pickle for NetworkListener {
         this.tls_implicit = Pickle::unpickle(stream)?;
         this.tls_timeout = Pickle::unpickle(stream)?;
         this.max_connections = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.tls_client_auth = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.tls_client_ca = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.tls_client_cert_mapping = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.tls_client_cert_fingerprints = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -30021,13 +30443,17 @@ impl Default for NetworkListener {
             tls_implicit: false,
             tls_timeout: Some(Duration::from_millis(60000)),
             max_connections: Some(8192u64),
+            tls_client_auth: TlsClientAuth::Disabled,
+            tls_client_ca: Default::default(),
+            tls_client_cert_mapping: TlsClientCertMapping::SanEmail,
+            tls_client_cert_fingerprints: Default::default(),
         }
     }
 }
 
 impl IntoValue for NetworkListener {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(21);
+        let mut map = jmap_tools::Map::with_capacity(25);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Bind, self.bind.into_value());
         map.insert_unchecked(Property::Protocol, self.protocol.into_value());
@@ -30071,6 +30497,16 @@ impl IntoValue for NetworkListener {
         map.insert_unchecked(Property::TlsImplicit, self.tls_implicit.into_value());
         map.insert_unchecked(Property::TlsTimeout, self.tls_timeout.into_value());
         map.insert_unchecked(Property::MaxConnections, self.max_connections.into_value());
+        map.insert_unchecked(Property::TlsClientAuth, self.tls_client_auth.into_value());
+        map.insert_unchecked(Property::TlsClientCa, self.tls_client_ca.into_value());
+        map.insert_unchecked(
+            Property::TlsClientCertMapping,
+            self.tls_client_cert_mapping.into_value(),
+        );
+        map.insert_unchecked(
+            Property::TlsClientCertFingerprints,
+            self.tls_client_cert_fingerprints.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -30113,6 +30549,14 @@ impl RegistryJsonPropertyPatch for NetworkListener {
             Some(Property::TlsImplicit) => self.tls_implicit.patch(pointer, value),
             Some(Property::TlsTimeout) => self.tls_timeout.patch(pointer, value),
             Some(Property::MaxConnections) => self.max_connections.patch(pointer, value),
+            Some(Property::TlsClientAuth) => self.tls_client_auth.patch(pointer, value),
+            Some(Property::TlsClientCa) => self.tls_client_ca.patch(pointer, value),
+            Some(Property::TlsClientCertMapping) => {
+                self.tls_client_cert_mapping.patch(pointer, value)
+            }
+            Some(Property::TlsClientCertFingerprints) => self
+                .tls_client_cert_fingerprints
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -30669,6 +31113,124 @@ impl RegistryJsonPropertyPatch for OtpAuth {
     }
 }
 
+impl ObjectImpl for Passkey {
+    const FLAGS: u64 = 0;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::Passkey;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.credential_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CredentialId, value));
+        }
+        let value = &self.description;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Description));
+        }
+        let value = &self.key_id;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::KeyId));
+        }
+        let value = &self.public_key;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::PublicKey));
+        }
+        let value = &self.created_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CreatedAt, value));
+        }
+        if let Some(value) = &self.last_used_at {
+            if !value.is_valid() {
+                errors.push(ValidationError::invalid(Property::LastUsedAt, value));
+            }
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Pickle for Passkey {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.credential_id.pickle(out);
+        self.description.pickle(out);
+        self.key_id.pickle(out);
+        self.public_key.pickle(out);
+        self.algorithm.pickle(out);
+        self.sign_count.pickle(out);
+        self.created_at.pickle(out);
+        self.last_used_at.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.credential_id = Pickle::unpickle(stream)?;
+        this.description = Pickle::unpickle(stream)?;
+        this.key_id = Pickle::unpickle(stream)?;
+        this.public_key = Pickle::unpickle(stream)?;
+        this.algorithm = Pickle::unpickle(stream)?;
+        this.sign_count = Pickle::unpickle(stream)?;
+        this.created_at = Pickle::unpickle(stream)?;
+        this.last_used_at = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for Passkey {
+    fn default() -> Self {
+        Self {
+            credential_id: Default::default(),
+            description: Default::default(),
+            key_id: Default::default(),
+            public_key: Default::default(),
+            algorithm: Default::default(),
+            sign_count: Default::default(),
+            created_at: Default::default(),
+            last_used_at: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for Passkey {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(10);
+        map.insert_unchecked(Property::CredentialId, self.credential_id.into_value());
+        map.insert_unchecked(Property::Description, self.description.into_value());
+        map.insert_unchecked(Property::KeyId, self.key_id.into_value());
+        map.insert_unchecked(Property::PublicKey, self.public_key.into_value());
+        map.insert_unchecked(Property::Algorithm, self.algorithm.into_value());
+        map.insert_unchecked(Property::SignCount, self.sign_count.into_value());
+        map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
+        map.insert_unchecked(Property::LastUsedAt, self.last_used_at.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for Passkey {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::CredentialId) => pointer.assert_server_set(),
+            Some(Property::Description) => self.description.patch(pointer, value),
+            Some(Property::KeyId) => pointer.assert_server_set(),
+            Some(Property::PublicKey) => pointer.assert_server_set(),
+            Some(Property::Algorithm) => pointer.assert_server_set(),
+            Some(Property::SignCount) => pointer.assert_server_set(),
+            Some(Property::CreatedAt) => pointer.assert_server_set(),
+            Some(Property::LastUsedAt) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl PasswordCredential {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -30707,6 +31269,7 @@ impl Pickle for PasswordCredential {
         self.otp_auth.pickle(out);
         self.expires_at.pickle(out);
         self.allowed_ips.pickle(out);
+        self.scram_secret.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -30716,6 +31279,9 @@ impl Pickle for PasswordCredential {
         this.otp_auth = Pickle::unpickle(stream)?;
         this.expires_at = Pickle::unpickle(stream)?;
         this.allowed_ips = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.scram_secret = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -30728,6 +31294,7 @@ impl Default for PasswordCredential {
             otp_auth: Default::default(),
             expires_at: Default::default(),
             allowed_ips: Default::default(),
+            scram_secret: Default::default(),
         }
     }
 }
@@ -30758,6 +31325,7 @@ impl RegistryJsonPropertyPatch for PasswordCredential {
             Some(Property::OtpAuth) => self.otp_auth.patch(pointer, value),
             Some(Property::ExpiresAt) => self.expires_at.patch(pointer, value),
             Some(Property::AllowedIps) => self.allowed_ips.patch(pointer, value),
+            Some(Property::ScramSecret) => pointer.assert_server_set(),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -31487,6 +32055,234 @@ impl RegistryJsonPropertyPatch for PublicTextValue {
     }
 }
 
+impl ObjectImpl for QuarantineSettings {
+    const FLAGS: u64 = OBJ_SINGLETON;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::QuarantineSettings;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.schedule;
+        value.validate(errors);
+        let value = &self.from_name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::FromName));
+        }
+        if let Some(value) = &self.from_address {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::FromAddress));
+            }
+        }
+        let value = &self.subject;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Subject));
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Pickle for QuarantineSettings {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.hold_for.pickle(out);
+        self.quarantine_spam.pickle(out);
+        self.send_digest.pickle(out);
+        self.schedule.pickle(out);
+        self.from_name.pickle(out);
+        self.from_address.pickle(out);
+        self.subject.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.hold_for = Pickle::unpickle(stream)?;
+        this.quarantine_spam = Pickle::unpickle(stream)?;
+        this.send_digest = Pickle::unpickle(stream)?;
+        this.schedule = Pickle::unpickle(stream)?;
+        this.from_name = Pickle::unpickle(stream)?;
+        this.from_address = Pickle::unpickle(stream)?;
+        this.subject = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for QuarantineSettings {
+    fn default() -> Self {
+        Self {
+            hold_for: Duration::from_millis(2592000000),
+            quarantine_spam: false,
+            send_digest: true,
+            schedule: Cron::Daily(CronDaily {
+                hour: 8u64,
+                minute: 0u64,
+            }),
+            from_name: "Quarantine".to_string(),
+            from_address: Default::default(),
+            subject: "Quarantined messages".to_string(),
+        }
+    }
+}
+
+impl IntoValue for QuarantineSettings {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(9);
+        map.insert_unchecked(Property::HoldFor, self.hold_for.into_value());
+        map.insert_unchecked(Property::QuarantineSpam, self.quarantine_spam.into_value());
+        map.insert_unchecked(Property::SendDigest, self.send_digest.into_value());
+        map.insert_unchecked(Property::Schedule, self.schedule.into_value());
+        map.insert_unchecked(Property::FromName, self.from_name.into_value());
+        map.insert_unchecked(Property::FromAddress, self.from_address.into_value());
+        map.insert_unchecked(Property::Subject, self.subject.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for QuarantineSettings {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::HoldFor) => self.hold_for.patch(pointer, value),
+            Some(Property::QuarantineSpam) => self.quarantine_spam.patch(pointer, value),
+            Some(Property::SendDigest) => self.send_digest.patch(pointer, value),
+            Some(Property::Schedule) => self.schedule.patch(pointer, value),
+            Some(Property::FromName) => self.from_name.patch(pointer, value),
+            Some(Property::FromAddress) => self
+                .from_address
+                .patch(pointer.with_validators(&[StringValidator::Email]), value),
+            Some(Property::Subject) => self.subject.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl ObjectImpl for QuarantinedMessage {
+    const FLAGS: u64 = OBJ_FILTER_ACCOUNT;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::QuarantinedMessage;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.received_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::ReceivedAt, value));
+        }
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.blob_id;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::BlobId));
+        }
+        let value = &self.expires_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::ExpiresAt, value));
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+        i.search(Property::AccountId, &self.account_id);
+    }
+}
+
+impl Pickle for QuarantinedMessage {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.from.pickle(out);
+        self.subject.pickle(out);
+        self.reason.pickle(out);
+        self.received_at.pickle(out);
+        self.size.pickle(out);
+        self.account_id.pickle(out);
+        self.blob_id.pickle(out);
+        self.expires_at.pickle(out);
+        self.notified.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.from = Pickle::unpickle(stream)?;
+        this.subject = Pickle::unpickle(stream)?;
+        this.reason = Pickle::unpickle(stream)?;
+        this.received_at = Pickle::unpickle(stream)?;
+        this.size = Pickle::unpickle(stream)?;
+        this.account_id = Pickle::unpickle(stream)?;
+        this.blob_id = Pickle::unpickle(stream)?;
+        this.expires_at = Pickle::unpickle(stream)?;
+        this.notified = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for QuarantinedMessage {
+    fn default() -> Self {
+        Self {
+            from: Default::default(),
+            subject: Default::default(),
+            reason: Default::default(),
+            received_at: Default::default(),
+            size: 0u64,
+            account_id: Default::default(),
+            blob_id: Default::default(),
+            expires_at: Default::default(),
+            notified: false,
+        }
+    }
+}
+
+impl IntoValue for QuarantinedMessage {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(11);
+        map.insert_unchecked(Property::From, self.from.into_value());
+        map.insert_unchecked(Property::Subject, self.subject.into_value());
+        map.insert_unchecked(Property::Reason, self.reason.into_value());
+        map.insert_unchecked(Property::ReceivedAt, self.received_at.into_value());
+        map.insert_unchecked(Property::Size, self.size.into_value());
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::BlobId, self.blob_id.into_value());
+        map.insert_unchecked(Property::ExpiresAt, self.expires_at.into_value());
+        map.insert_unchecked(Property::Notified, self.notified.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for QuarantinedMessage {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::From) => pointer.assert_server_set(),
+            Some(Property::Subject) => pointer.assert_server_set(),
+            Some(Property::Reason) => pointer.assert_server_set(),
+            Some(Property::ReceivedAt) => pointer.assert_server_set(),
+            Some(Property::Size) => self.size.patch(pointer.assert_read_only()?, value),
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::BlobId) => self.blob_id.patch(pointer.assert_read_only()?, value),
+            Some(Property::ExpiresAt) => pointer.assert_server_set(),
+            Some(Property::Notified) => pointer.assert_server_set(),
+            Some(property @ Property::Status) => Ok(MaybeUnpatched::Unpatched { property, value }),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl QueueExpiry {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         match self {
@@ -32745,6 +33541,196 @@ impl RegistryJsonPropertyPatch for ReportSettings {
     }
 }
 
+impl ResourceAccount {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Name));
+        }
+        let value = &self.domain_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::DomainId));
+        }
+        if let Some(value) = &self.description {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Description));
+            }
+        }
+        let value = &self.created_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CreatedAt, value));
+        }
+        if let Some(value) = &self.member_tenant_id {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::MemberTenantId));
+            }
+        }
+        let value = &self.allowed_booker_ids;
+        for value in value.iter() {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::AllowedBookerIds));
+            }
+        }
+        let value = &self.roles;
+        value.validate(errors);
+        let value = &self.permissions;
+        value.validate(errors);
+        let value = &self.aliases;
+        for value in value.values() {
+            value.validate(errors);
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.unique_global_composite(Property::Email, &self.name, &self.domain_id);
+        i.text(Property::Text, &self.name);
+        i.search(Property::Name, &self.name);
+        i.foreign_key(ObjectType::Domain, self.domain_id.into(), None);
+        i.search(Property::DomainId, &self.domain_id);
+        if let Some(value) = &self.description {
+            i.text(Property::Text, value);
+        }
+        i.foreign_key(ObjectType::Tenant, self.member_tenant_id, None);
+        if let Some(value) = &self.member_tenant_id {
+            i.search(Property::MemberTenantId, value);
+        }
+        for id in self.allowed_booker_ids.iter() {
+            i.foreign_key(ObjectType::Account, Some(*id), None);
+        }
+        self.roles.index(i);
+        for item in self.aliases.values() {
+            item.index(i);
+        }
+    }
+}
+
+impl Pickle for ResourceAccount {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.name.pickle(out);
+        self.domain_id.pickle(out);
+        self.description.pickle(out);
+        self.created_at.pickle(out);
+        self.member_tenant_id.pickle(out);
+        self.booking_policy.pickle(out);
+        self.booking_window.pickle(out);
+        self.max_duration.pickle(out);
+        self.allowed_booker_ids.pickle(out);
+        self.roles.pickle(out);
+        self.quotas.pickle(out);
+        self.permissions.pickle(out);
+        self.aliases.pickle(out);
+        self.locale.pickle(out);
+        self.time_zone.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.name = Pickle::unpickle(stream)?;
+        this.domain_id = Pickle::unpickle(stream)?;
+        this.description = Pickle::unpickle(stream)?;
+        this.created_at = Pickle::unpickle(stream)?;
+        this.member_tenant_id = Pickle::unpickle(stream)?;
+        this.booking_policy = Pickle::unpickle(stream)?;
+        this.booking_window = Pickle::unpickle(stream)?;
+        this.max_duration = Pickle::unpickle(stream)?;
+        this.allowed_booker_ids = Pickle::unpickle(stream)?;
+        this.roles = Pickle::unpickle(stream)?;
+        this.quotas = Pickle::unpickle(stream)?;
+        this.permissions = Pickle::unpickle(stream)?;
+        this.aliases = Pickle::unpickle(stream)?;
+        this.locale = Pickle::unpickle(stream)?;
+        this.time_zone = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for ResourceAccount {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            domain_id: Default::default(),
+            description: Default::default(),
+            created_at: Default::default(),
+            member_tenant_id: Default::default(),
+            booking_policy: BookingPolicy::AcceptIfFree,
+            booking_window: Some(Duration::from_millis(31536000000)),
+            max_duration: Default::default(),
+            allowed_booker_ids: Default::default(),
+            roles: Default::default(),
+            quotas: Default::default(),
+            permissions: Default::default(),
+            aliases: Default::default(),
+            locale: Locale::EnUS,
+            time_zone: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for ResourceAccount {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(17);
+        map.insert_unchecked(Property::Name, self.name.into_value());
+        map.insert_unchecked(Property::DomainId, self.domain_id.into_value());
+        map.insert_unchecked(Property::Description, self.description.into_value());
+        map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
+        map.insert_unchecked(Property::MemberTenantId, self.member_tenant_id.into_value());
+        map.insert_unchecked(Property::BookingPolicy, self.booking_policy.into_value());
+        map.insert_unchecked(Property::BookingWindow, self.booking_window.into_value());
+        map.insert_unchecked(Property::MaxDuration, self.max_duration.into_value());
+        map.insert_unchecked(
+            Property::AllowedBookerIds,
+            self.allowed_booker_ids.into_value(),
+        );
+        map.insert_unchecked(Property::Roles, self.roles.into_value());
+        map.insert_unchecked(Property::Quotas, self.quotas.into_value());
+        map.insert_unchecked(Property::Permissions, self.permissions.into_value());
+        map.insert_unchecked(Property::Aliases, self.aliases.into_value());
+        map.insert_unchecked(Property::Locale, self.locale.into_value());
+        map.insert_unchecked(Property::TimeZone, self.time_zone.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for ResourceAccount {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Name) => self.name.patch(
+                pointer.with_validators(&[StringValidator::EmailLocalPart]),
+                value,
+            ),
+            Some(Property::DomainId) => self.domain_id.patch(pointer, value),
+            Some(Property::EmailAddress) => pointer.assert_server_set(),
+            Some(Property::Description) => self.description.patch(pointer, value),
+            Some(Property::CreatedAt) => pointer.assert_server_set(),
+            Some(Property::MemberTenantId) => self
+                .member_tenant_id
+                .patch(pointer.assert_can_set_tenant()?, value),
+            Some(Property::BookingPolicy) => self.booking_policy.patch(pointer, value),
+            Some(Property::BookingWindow) => self.booking_window.patch(pointer, value),
+            Some(Property::MaxDuration) => self.max_duration.patch(pointer, value),
+            Some(Property::AllowedBookerIds) => self.allowed_booker_ids.patch(pointer, value),
+            Some(Property::Roles) => self.roles.patch(pointer, value),
+            Some(Property::Quotas) => self.quotas.patch(pointer, value),
+            Some(Property::UsedDiskQuota) => pointer.assert_server_set(),
+            Some(Property::Permissions) => self.permissions.patch(pointer, value),
+            Some(Property::Aliases) => self.aliases.patch(pointer, value),
+            Some(Property::Locale) => self.locale.patch(pointer, value),
+            Some(Property::TimeZone) => self.time_zone.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl RocksDbStore {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -33888,7 +34874,7 @@ impl S3StoreRegion {
 
 impl ObjectImpl for Search {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::Search;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -33916,6 +34902,12 @@ impl Pickle for Search {
         self.index_email_fields.pickle(out);
         self.index_telemetry.pickle(out);
         self.index_tracing_fields.pickle(out);
+        self.index_files.pickle(out);
+        self.index_file_fields.pickle(out);
+        self.extract_attachments.pickle(out);
+        self.extract_max_size.pickle(out);
+        self.extract_timeout.pickle(out);
+        self.extract_cache_ttl.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -33931,6 +34923,24 @@ impl Pickle for Search {
         this.index_email_fields = Pickle::unpickle(stream)?;
         this.index_telemetry = Pickle::unpickle(stream)?;
         this.index_tracing_fields = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.index_files = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.index_file_fields = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.extract_attachments = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.extract_max_size = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.extract_timeout = Pickle::unpickle(stream)?;
+        }
+        if stream.version() >= 1 {
+            this.extract_cache_ttl = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -33985,13 +34995,19 @@ impl Default for Search {
                 SearchTracingField::QueueId,
                 SearchTracingField::Keywords,
             ]),
+            index_files: true,
+            index_file_fields: Map::new(vec![SearchFileField::Name, SearchFileField::Content]),
+            extract_attachments: true,
+            extract_max_size: 20971520u64,
+            extract_timeout: Duration::from_millis(30000),
+            extract_cache_ttl: Duration::from_millis(2592000000),
         }
     }
 }
 
 impl IntoValue for Search {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(13);
+        let mut map = jmap_tools::Map::with_capacity(19);
         map.insert_unchecked(Property::IndexBatchSize, self.index_batch_size.into_value());
         map.insert_unchecked(
             Property::DefaultLanguage,
@@ -34021,6 +35037,21 @@ impl IntoValue for Search {
             Property::IndexTracingFields,
             self.index_tracing_fields.into_value(),
         );
+        map.insert_unchecked(Property::IndexFiles, self.index_files.into_value());
+        map.insert_unchecked(
+            Property::IndexFileFields,
+            self.index_file_fields.into_value(),
+        );
+        map.insert_unchecked(
+            Property::ExtractAttachments,
+            self.extract_attachments.into_value(),
+        );
+        map.insert_unchecked(Property::ExtractMaxSize, self.extract_max_size.into_value());
+        map.insert_unchecked(Property::ExtractTimeout, self.extract_timeout.into_value());
+        map.insert_unchecked(
+            Property::ExtractCacheTtl,
+            self.extract_cache_ttl.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -34043,6 +35074,12 @@ impl RegistryJsonPropertyPatch for Search {
             Some(Property::IndexEmailFields) => self.index_email_fields.patch(pointer, value),
             Some(Property::IndexTelemetry) => self.index_telemetry.patch(pointer, value),
             Some(Property::IndexTracingFields) => self.index_tracing_fields.patch(pointer, value),
+            Some(Property::IndexFiles) => self.index_files.patch(pointer, value),
+            Some(Property::IndexFileFields) => self.index_file_fields.patch(pointer, value),
+            Some(Property::ExtractAttachments) => self.extract_attachments.patch(pointer, value),
+            Some(Property::ExtractMaxSize) => self.extract_max_size.patch(pointer, value),
+            Some(Property::ExtractTimeout) => self.extract_timeout.patch(pointer, value),
+            Some(Property::ExtractCacheTtl) => self.extract_cache_ttl.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -35149,7 +36186,7 @@ impl RegistryJsonPropertyPatch for Security {
 
 impl ObjectImpl for SenderAuth {
     const FLAGS: u64 = OBJ_SINGLETON;
-    const VERSION: u8 = 0;
+    const VERSION: u8 = 1;
     const OBJECT: ObjectType = ObjectType::SenderAuth;
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
@@ -35168,6 +36205,8 @@ impl ObjectImpl for SenderAuth {
         value.validate(errors);
         let value = &self.reverse_ip_verify;
         value.validate(errors);
+        let value = &self.arc_seal_domain;
+        value.validate(errors);
         errors.len() == neb
     }
 
@@ -35282,6 +36321,19 @@ impl SenderAuth {
         }
     }
 
+    pub fn ctx_arc_seal_domain(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.arc_seal_domain,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                match_: List::from_iter([]),
+            }),
+            property: Property::ArcSealDomain,
+            allowed_variables: MTA_RCPT_TO_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
     pub fn expression_ctxs(&self) -> Vec<ExpressionContext<'_>> {
         vec![
             self.ctx_dkim_sign_domain(),
@@ -35291,6 +36343,7 @@ impl SenderAuth {
             self.ctx_arc_verify(),
             self.ctx_dmarc_verify(),
             self.ctx_reverse_ip_verify(),
+            self.ctx_arc_seal_domain(),
         ]
     }
 }
@@ -35305,6 +36358,7 @@ impl Pickle for SenderAuth {
         self.arc_verify.pickle(out);
         self.dmarc_verify.pickle(out);
         self.reverse_ip_verify.pickle(out);
+        self.arc_seal_domain.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -35317,6 +36371,9 @@ impl Pickle for SenderAuth {
         this.arc_verify = Pickle::unpickle(stream)?;
         this.dmarc_verify = Pickle::unpickle(stream)?;
         this.reverse_ip_verify = Pickle::unpickle(stream)?;
+        if stream.version() >= 1 {
+            this.arc_seal_domain = Pickle::unpickle(stream)?;
+        }
         Some(this)
     }
 }
@@ -35369,13 +36426,17 @@ impl Default for SenderAuth {
                     then: "relaxed".to_string(),
                 }]),
             },
+            arc_seal_domain: Expression {
+                else_: "false".to_string(),
+                match_: List::from_iter([]),
+            },
         }
     }
 }
 
 impl IntoValue for SenderAuth {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(10);
+        let mut map = jmap_tools::Map::with_capacity(11);
         map.insert_unchecked(Property::DkimSignDomain, self.dkim_sign_domain.into_value());
         map.insert_unchecked(Property::DkimStrict, self.dkim_strict.into_value());
         map.insert_unchecked(Property::DkimVerify, self.dkim_verify.into_value());
@@ -35387,6 +36448,7 @@ impl IntoValue for SenderAuth {
             Property::ReverseIpVerify,
             self.reverse_ip_verify.into_value(),
         );
+        map.insert_unchecked(Property::ArcSealDomain, self.arc_seal_domain.into_value());
         JmapValue::Object(map)
     }
 }
@@ -35406,6 +36468,7 @@ impl RegistryJsonPropertyPatch for SenderAuth {
             Some(Property::ArcVerify) => self.arc_verify.patch(pointer, value),
             Some(Property::DmarcVerify) => self.dmarc_verify.patch(pointer, value),
             Some(Property::ReverseIpVerify) => self.reverse_ip_verify.patch(pointer, value),
+            Some(Property::ArcSealDomain) => self.arc_seal_domain.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -41329,6 +42392,9 @@ impl ObjectImpl for Task {
             Task::AcmeRenewal(inner) => inner.validate(errors),
             Task::DkimManagement(inner) => inner.validate(errors),
             Task::DnsManagement(inner) => inner.validate(errors),
+            Task::AccountExport(inner) => inner.validate(errors),
+            Task::AccountImport(inner) => inner.validate(errors),
+            Task::QuarantineDigest(inner) => inner.validate(errors),
         }
     }
 
@@ -41384,6 +42450,13 @@ impl ObjectImpl for Task {
             Task::DnsManagement(object) => {
                 object.index(i);
             }
+            Task::AccountExport(object) => {
+                object.index(i);
+            }
+            Task::AccountImport(object) => {
+                object.index(i);
+            }
+            Task::QuarantineDigest(_) => {}
         }
     }
 }
@@ -41469,6 +42542,18 @@ impl Pickle for Task {
                 17u16.pickle(out);
                 inner.pickle(out);
             }
+            Task::AccountExport(inner) => {
+                18u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::AccountImport(inner) => {
+                19u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::QuarantineDigest(inner) => {
+                20u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -41492,6 +42577,9 @@ impl Pickle for Task {
             15 => Pickle::unpickle(stream).map(Task::AcmeRenewal),
             16 => Pickle::unpickle(stream).map(Task::DkimManagement),
             17 => Pickle::unpickle(stream).map(Task::DnsManagement),
+            18 => Pickle::unpickle(stream).map(Task::AccountExport),
+            19 => Pickle::unpickle(stream).map(Task::AccountImport),
+            20 => Pickle::unpickle(stream).map(Task::QuarantineDigest),
             _ => None,
         }
     }
@@ -41628,6 +42716,27 @@ impl IntoValue for Task {
                     .insert_unchecked(Property::Type, JmapValue::Str("DnsManagement".into()));
                 obj
             }
+            Task::AccountExport(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("AccountExport".into()));
+                obj
+            }
+            Task::AccountImport(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("AccountImport".into()));
+                obj
+            }
+            Task::QuarantineDigest(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("QuarantineDigest".into()));
+                obj
+            }
         }
     }
 }
@@ -41670,6 +42779,9 @@ impl RegistryJsonPatch for Task {
                 TaskType::AcmeRenewal => *self = Task::AcmeRenewal(Default::default()),
                 TaskType::DkimManagement => *self = Task::DkimManagement(Default::default()),
                 TaskType::DnsManagement => *self = Task::DnsManagement(Default::default()),
+                TaskType::AccountExport => *self = Task::AccountExport(Default::default()),
+                TaskType::AccountImport => *self = Task::AccountImport(Default::default()),
+                TaskType::QuarantineDigest => *self = Task::QuarantineDigest(Default::default()),
             }
         }
         match self {
@@ -41691,6 +42803,9 @@ impl RegistryJsonPatch for Task {
             Task::AcmeRenewal(inner) => inner.patch(pointer, value),
             Task::DkimManagement(inner) => inner.patch(pointer, value),
             Task::DnsManagement(inner) => inner.patch(pointer, value),
+            Task::AccountExport(inner) => inner.patch(pointer, value),
+            Task::AccountImport(inner) => inner.patch(pointer, value),
+            Task::QuarantineDigest(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -41716,6 +42831,177 @@ impl Task {
             Task::AcmeRenewal(_) => TaskType::AcmeRenewal,
             Task::DkimManagement(_) => TaskType::DkimManagement,
             Task::DnsManagement(_) => TaskType::DnsManagement,
+            Task::AccountExport(_) => TaskType::AccountExport,
+            Task::AccountImport(_) => TaskType::AccountImport,
+            Task::QuarantineDigest(_) => TaskType::QuarantineDigest,
+        }
+    }
+}
+
+impl TaskAccountExport {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.path;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Path));
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskAccountExport {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.format.pickle(out);
+        self.path.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.format = Pickle::unpickle(stream)?;
+        this.path = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskAccountExport {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            format: Default::default(),
+            path: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskAccountExport {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(6);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::Format, self.format.into_value());
+        map.insert_unchecked(Property::Path, self.path.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskAccountExport {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::Format) => self.format.patch(pointer.assert_read_only()?, value),
+            Some(Property::Path) => self.path.patch(pointer.assert_read_only()?, value),
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl TaskAccountImport {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.path;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Path));
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskAccountImport {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.format.pickle(out);
+        self.path.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.format = Pickle::unpickle(stream)?;
+        this.path = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskAccountImport {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            format: Default::default(),
+            path: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskAccountImport {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(6);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::Format, self.format.into_value());
+        map.insert_unchecked(Property::Path, self.path.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskAccountImport {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::Format) => self.format.patch(pointer.assert_read_only()?, value),
+            Some(Property::Path) => self.path.patch(pointer.assert_read_only()?, value),
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
         }
     }
 }
@@ -42830,6 +44116,61 @@ impl RegistryJsonPropertyPatch for TaskMergeThreads {
     }
 }
 
+impl TaskQuarantineDigest {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+}
+
+impl Pickle for TaskQuarantineDigest {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskQuarantineDigest {
+    fn default() -> Self {
+        Self {
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskQuarantineDigest {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(3);
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskQuarantineDigest {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl TaskRestoreArchivedItem {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
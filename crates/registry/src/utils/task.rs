@@ -30,6 +30,9 @@ impl Task {
             Task::DkimManagement(task) => task.status = status,
             Task::DnsManagement(task) => task.status = status,
             Task::TenantMaintenance(task) => task.status = status,
+            Task::AccountExport(task) => task.status = status,
+            Task::AccountImport(task) => task.status = status,
+            Task::QuarantineDigest(task) => task.status = status,
         }
     }
 
@@ -53,6 +56,9 @@ impl Task {
             Task::DkimManagement(task) => &task.status,
             Task::DnsManagement(task) => &task.status,
             Task::TenantMaintenance(task) => &task.status,
+            Task::AccountExport(task) => &task.status,
+            Task::AccountImport(task) => &task.status,
+            Task::QuarantineDigest(task) => &task.status,
         }
     }
 
@@ -92,6 +98,9 @@ impl Task {
             Task::DkimManagement(_) => Permission::TaskDkimManagement,
             Task::DnsManagement(_) => Permission::TaskDnsManagement,
             Task::TenantMaintenance(_) => Permission::TaskTenantMaintenance,
+            Task::AccountExport(_) => Permission::TaskAccountExport,
+            Task::AccountImport(_) => Permission::TaskAccountImport,
+            Task::QuarantineDigest(_) => Permission::TaskQuarantineDigest,
         }
     }
 }
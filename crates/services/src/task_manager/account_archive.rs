@@ -0,0 +1,1025 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use chrono::{DateTime, NaiveDateTime};
+use common::{
+    MessageCache, MessageStoreCache, Server,
+    auth::{AccessToken, BuildAccessToken},
+};
+use email::{
+    cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess},
+    mailbox::{INBOX_ID, manage::MailboxFnc},
+    message::{
+        ingest::{EmailIngest, IngestEmail, IngestSource},
+        metadata::{MESSAGE_RECEIVED_MASK, MessageMetadata},
+    },
+};
+use mail_parser::MessageParser;
+use registry::schema::{
+    enums::AccountArchiveFormat,
+    structs::{TaskAccountExport, TaskAccountImport},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+use store::{
+    ValueKey,
+    ahash::AHashMap,
+    write::{AlignedBytes, Archive},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt},
+    sync::mpsc,
+};
+use trc::AddContext;
+use types::{collection::Collection, field::EmailField, keyword::Keyword};
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DOVECOT_KEYWORDS_FILE: &str = "dovecot-keywords";
+const EXTRA_KEYWORDS_FILE: &str = "stalwart-keywords";
+const MAX_MAILDIR_KEYWORDS: usize = 26;
+
+pub(crate) trait AccountArchiveTask: Sync + Send {
+    fn account_export(&self, task: &TaskAccountExport) -> impl Future<Output = TaskResult> + Send;
+    fn account_import(&self, task: &TaskAccountImport) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl AccountArchiveTask for Server {
+    async fn account_export(&self, task: &TaskAccountExport) -> TaskResult {
+        match account_export(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .details("Failed to export account")
+                );
+                result
+            }
+        }
+    }
+
+    async fn account_import(&self, task: &TaskAccountImport) -> TaskResult {
+        match account_import(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                // Retrying would ingest the messages imported so far a second time
+                let result = TaskResult::permanent(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .details("Failed to import account")
+                );
+                result
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArchiveManifest {
+    mailboxes: Vec<String>,
+    messages: Vec<ArchiveManifestMessage>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArchiveManifestMessage {
+    file: String,
+    mailboxes: Vec<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(rename = "receivedAt")]
+    #[serde(default)]
+    received_at: Option<u64>,
+}
+
+struct ExportedMessage {
+    raw_message: Vec<u8>,
+    received_at: u64,
+}
+
+enum ZipImportItem {
+    Mailbox(String),
+    Message {
+        raw_message: Option<Vec<u8>>,
+        mailboxes: Vec<String>,
+        keywords: Vec<Keyword>,
+        received_at: Option<u64>,
+    },
+}
+
+struct Importer<'x> {
+    server: &'x Server,
+    account_id: u32,
+    access_token: &'x AccessToken,
+    mailboxes: AHashMap<String, u32>,
+    imported: usize,
+    failed: usize,
+}
+
+async fn account_export(server: &Server, task: &TaskAccountExport) -> trc::Result<TaskResult> {
+    let account_id = task.account_id.document_id();
+    let cache = server
+        .get_cached_messages(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let dest = Path::new(&task.path);
+
+    match task.format {
+        AccountArchiveFormat::Maildir => export_maildir(server, account_id, &cache, dest).await,
+        AccountArchiveFormat::Mbox => export_mbox(server, account_id, &cache, dest).await,
+        AccountArchiveFormat::EmlZip => export_zip(server, account_id, &cache, dest).await,
+    }
+    .caused_by(trc::location!())?;
+
+    Ok(TaskResult::Success(vec![]))
+}
+
+async fn account_import(server: &Server, task: &TaskAccountImport) -> trc::Result<TaskResult> {
+    let account_id = task.account_id.document_id();
+    let access_token = server
+        .access_token(account_id)
+        .await
+        .caused_by(trc::location!())?
+        .build();
+    let mut importer = Importer {
+        server,
+        account_id,
+        access_token: &access_token,
+        mailboxes: AHashMap::new(),
+        imported: 0,
+        failed: 0,
+    };
+    let src = Path::new(&task.path);
+
+    match task.format {
+        AccountArchiveFormat::Maildir => import_maildir(&mut importer, src).await,
+        AccountArchiveFormat::Mbox => import_mbox(&mut importer, src).await,
+        AccountArchiveFormat::EmlZip => import_zip(&mut importer, src).await,
+    }
+    .caused_by(trc::location!())?;
+
+    if importer.failed == 0 {
+        Ok(TaskResult::Success(vec![]))
+    } else {
+        Ok(TaskResult::permanent(format!(
+            "Imported {} messages, {} messages could not be imported",
+            importer.imported, importer.failed
+        )))
+    }
+}
+
+async fn export_maildir(
+    server: &Server,
+    account_id: u32,
+    cache: &MessageStoreCache,
+    dest: &Path,
+) -> trc::Result<()> {
+    let hostname = &server.core.network.server_name;
+
+    for mailbox in cache.mailboxes.items.iter() {
+        // Maildir++ layout, the inbox lives at the root and every other
+        // mailbox in a ".Parent.Child" folder.
+        let folder = if mailbox.document_id == INBOX_ID {
+            dest.to_path_buf()
+        } else {
+            dest.join(maildir_folder_name(&mailbox.path))
+        };
+        for dir in ["cur", "new", "tmp"] {
+            tokio::fs::create_dir_all(folder.join(dir))
+                .await
+                .map_err(into_error)?;
+        }
+        if mailbox.document_id != INBOX_ID {
+            tokio::fs::write(folder.join("maildirfolder"), b"")
+                .await
+                .map_err(into_error)?;
+        }
+
+        // Keywords without a standard flag are mapped to the letters a-z, those
+        // that do not fit are listed per message in a separate file
+        let messages = mailbox_messages(cache, mailbox.document_id);
+        let mut custom_keywords: Vec<Keyword> = Vec::new();
+        for message in &messages {
+            for keyword in cache.expand_keywords(message) {
+                if maildir_flag(&keyword).is_none() && !custom_keywords.contains(&keyword) {
+                    custom_keywords.push(keyword);
+                }
+            }
+        }
+        if !custom_keywords.is_empty() {
+            let mut contents = String::new();
+            for (idx, keyword) in custom_keywords
+                .iter()
+                .take(MAX_MAILDIR_KEYWORDS)
+                .enumerate()
+            {
+                contents.push_str(&format!("{idx} {keyword}\n"));
+            }
+            tokio::fs::write(folder.join(DOVECOT_KEYWORDS_FILE), contents)
+                .await
+                .map_err(into_error)?;
+        }
+
+        let mut extra_keywords = String::new();
+        for message in messages {
+            let Some(exported) = fetch_message(server, account_id, message.document_id).await?
+            else {
+                continue;
+            };
+            let base_name = format!(
+                "{}.M{}.{}",
+                exported.received_at, message.document_id, hostname
+            );
+            let mut flags = Vec::new();
+            let mut extra = String::new();
+            for keyword in cache.expand_keywords(message) {
+                if let Some(flag) = maildir_flag(&keyword) {
+                    flags.push(flag);
+                } else if let Some(idx) =
+                    custom_keywords.iter().position(|custom| custom == &keyword)
+                {
+                    if idx < MAX_MAILDIR_KEYWORDS {
+                        flags.push((b'a' + idx as u8) as char);
+                    } else {
+                        extra.push_str(&format!(" {keyword}"));
+                    }
+                }
+            }
+            flags.sort_unstable();
+            if !extra.is_empty() {
+                extra_keywords.push_str(&format!("{base_name}{extra}\n"));
+            }
+
+            let file_name = format!("{base_name}:2,{}", flags.into_iter().collect::<String>());
+            tokio::fs::write(folder.join("cur").join(file_name), &exported.raw_message)
+                .await
+                .map_err(into_error)?;
+        }
+        if !extra_keywords.is_empty() {
+            tokio::fs::write(folder.join(EXTRA_KEYWORDS_FILE), extra_keywords)
+                .await
+                .map_err(into_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn export_mbox(
+    server: &Server,
+    account_id: u32,
+    cache: &MessageStoreCache,
+    dest: &Path,
+) -> trc::Result<()> {
+    let mut buf = Vec::new();
+    for mailbox in cache.mailboxes.items.iter() {
+        let path = mbox_file_path(dest, &mailbox.path);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(into_error)?;
+        }
+        let mut writer =
+            tokio::io::BufWriter::new(tokio::fs::File::create(&path).await.map_err(into_error)?);
+
+        for message in mailbox_messages(cache, mailbox.document_id) {
+            let Some(exported) = fetch_message(server, account_id, message.document_id).await?
+            else {
+                continue;
+            };
+            let keywords = cache.expand_keywords(message).collect::<Vec<_>>();
+            buf.clear();
+            write_mbox_message(&mut buf, &exported, &keywords).map_err(into_error)?;
+            writer.write_all(&buf).await.map_err(into_error)?;
+        }
+
+        writer.flush().await.map_err(into_error)?;
+    }
+
+    Ok(())
+}
+
+async fn export_zip(
+    server: &Server,
+    account_id: u32,
+    cache: &MessageStoreCache,
+    dest: &Path,
+) -> trc::Result<()> {
+    // The archive is written from a blocking thread, messages are sent over a channel
+    let (tx, mut rx) = mpsc::channel::<(String, Vec<u8>)>(10);
+    let dest = dest.to_path_buf();
+    let writer = tokio::task::spawn_blocking(move || -> trc::Result<()> {
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(into_error)?;
+        }
+        let mut zip = ZipWriter::new(BufWriter::new(File::create(&dest).map_err(into_error)?));
+        let options = SimpleFileOptions::default();
+        while let Some((file, contents)) = rx.blocking_recv() {
+            zip.start_file(file, options).map_err(into_error)?;
+            zip.write_all(&contents).map_err(into_error)?;
+        }
+        zip.finish()
+            .map_err(into_error)?
+            .flush()
+            .map_err(into_error)
+    });
+
+    let mut manifest = ArchiveManifest {
+        mailboxes: cache
+            .mailboxes
+            .items
+            .iter()
+            .map(|mailbox| mailbox.path.clone())
+            .collect(),
+        messages: Vec::with_capacity(cache.emails.items.len()),
+    };
+
+    for message in cache.emails.items.iter() {
+        let mailboxes = message
+            .mailboxes
+            .iter()
+            .filter_map(|item| cache.mailbox_by_id(&item.mailbox_id))
+            .map(|mailbox| mailbox.path.clone())
+            .collect::<Vec<_>>();
+        let Some(exported) = fetch_message(server, account_id, message.document_id).await? else {
+            continue;
+        };
+
+        // Messages that belong to several mailboxes are stored once, under the first one
+        let file = format!(
+            "{}/{}.eml",
+            mailboxes
+                .first()
+                .map(|path| path.split('/').map(file_name).collect::<Vec<_>>().join("/"))
+                .unwrap_or_else(|| "Inbox".to_string()),
+            message.document_id
+        );
+        if tx.send((file.clone(), exported.raw_message)).await.is_err() {
+            // The writer failed, its error is returned below
+            break;
+        }
+
+        manifest.messages.push(ArchiveManifestMessage {
+            file,
+            mailboxes,
+            keywords: cache
+                .expand_keywords(message)
+                .map(|keyword| keyword.to_string())
+                .collect(),
+            received_at: Some(exported.received_at),
+        });
+    }
+
+    let manifest = serde_json::to_vec(&manifest).map_err(into_error)?;
+    let _ = tx.send((MANIFEST_FILE.to_string(), manifest)).await;
+    drop(tx);
+
+    writer.await.map_err(into_error)?
+}
+
+async fn import_maildir(importer: &mut Importer<'_>, src: &Path) -> trc::Result<()> {
+    let mut folders = vec![(String::new(), src.to_path_buf())];
+    let mut entries = tokio::fs::read_dir(src).await.map_err(into_error)?;
+    while let Some(entry) = entries.next_entry().await.map_err(into_error)? {
+        let path = entry.path();
+        if let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix('.'))
+            .filter(|name| !name.is_empty())
+            && tokio::fs::metadata(path.join("cur"))
+                .await
+                .is_ok_and(|metadata| metadata.is_dir())
+        {
+            folders.push((name.replace('.', "/"), path));
+        }
+    }
+
+    // Parent mailboxes sort before their children
+    folders.sort_unstable();
+
+    for (mailbox_path, folder) in folders {
+        importer.mailbox_id(&mailbox_path).await?;
+        let mailbox_paths = [mailbox_path];
+        let custom_keywords = read_dovecot_keywords(&folder).await;
+        let extra_keywords = read_extra_keywords(&folder).await;
+
+        for dir in ["new", "cur"] {
+            let Ok(mut entries) = tokio::fs::read_dir(folder.join(dir)).await else {
+                continue;
+            };
+            while let Some(entry) = entries.next_entry().await.map_err(into_error)? {
+                let path = entry.path();
+                let metadata = match tokio::fs::metadata(&path).await {
+                    Ok(metadata) if metadata.is_file() => metadata,
+                    _ => continue,
+                };
+                let file_name = entry.file_name().to_string_lossy().into_owned();
+                let (base_name, flags) = file_name
+                    .split_once(":2,")
+                    .unwrap_or((file_name.as_str(), ""));
+
+                let mut keywords = Vec::new();
+                for flag in flags.chars() {
+                    let keyword = match flag {
+                        'a'..='z' => custom_keywords[(flag as u8 - b'a') as usize].clone(),
+                        _ => maildir_keyword(flag),
+                    };
+                    if let Some(keyword) = keyword
+                        && !keywords.contains(&keyword)
+                    {
+                        keywords.push(keyword);
+                    }
+                }
+                for keyword in extra_keywords.get(base_name).into_iter().flatten() {
+                    if !keywords.contains(keyword) {
+                        keywords.push(keyword.clone());
+                    }
+                }
+
+                let received_at = base_name
+                    .split_once('.')
+                    .and_then(|(timestamp, _)| timestamp.parse::<u64>().ok())
+                    .or_else(|| {
+                        metadata
+                            .modified()
+                            .ok()
+                            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                            .map(|duration| duration.as_secs())
+                    });
+                let raw_message = tokio::fs::read(&path).await.map_err(into_error)?;
+
+                importer
+                    .ingest(&raw_message, &mailbox_paths, keywords, received_at)
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn import_mbox(importer: &mut Importer<'_>, src: &Path) -> trc::Result<()> {
+    let mut files = Vec::new();
+    if tokio::fs::metadata(src)
+        .await
+        .is_ok_and(|metadata| metadata.is_file())
+    {
+        files.push((String::new(), src.to_path_buf()));
+    } else {
+        collect_mbox_files(src, &mut files).await?;
+        files.sort_unstable();
+    }
+
+    for (mailbox_path, file) in files {
+        importer.mailbox_id(&mailbox_path).await?;
+        let mailbox_paths = [mailbox_path];
+        let mut reader =
+            tokio::io::BufReader::new(tokio::fs::File::open(&file).await.map_err(into_error)?);
+        let mut line = Vec::new();
+        let mut message = Vec::new();
+        let mut received_at = None;
+        let mut in_message = false;
+
+        loop {
+            line.clear();
+            let is_eof = reader
+                .read_until(b'\n', &mut line)
+                .await
+                .map_err(into_error)?
+                == 0;
+
+            if is_eof || line.starts_with(b"From ") {
+                if in_message {
+                    // Remove the empty line that separates messages
+                    if message.ends_with(b"\n\r\n") {
+                        message.truncate(message.len() - 2);
+                    } else if message.ends_with(b"\n\n") {
+                        message.truncate(message.len() - 1);
+                    }
+
+                    let (raw_message, keywords) = parse_mbox_status(&message);
+                    importer
+                        .ingest(&raw_message, &mailbox_paths, keywords, received_at)
+                        .await?;
+                    message.clear();
+                }
+                if is_eof {
+                    break;
+                }
+
+                in_message = true;
+                received_at = parse_from_line(&line);
+            } else if in_message {
+                // Undo mboxrd ">From " quoting
+                if is_from_line(&line) {
+                    message.extend_from_slice(&line[1..]);
+                } else {
+                    message.extend_from_slice(&line);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn import_zip(importer: &mut Importer<'_>, src: &Path) -> trc::Result<()> {
+    // The archive is read from a blocking thread, entries are sent over a channel
+    let (tx, mut rx) = mpsc::channel::<ZipImportItem>(10);
+    let src = src.to_path_buf();
+    let reader = tokio::task::spawn_blocking(move || read_zip(&src, tx));
+
+    while let Some(item) = rx.recv().await {
+        match item {
+            ZipImportItem::Mailbox(mailbox_path) => {
+                importer.mailbox_id(&mailbox_path).await?;
+            }
+            ZipImportItem::Message {
+                raw_message: Some(raw_message),
+                mailboxes,
+                keywords,
+                received_at,
+            } => {
+                importer
+                    .ingest(&raw_message, &mailboxes, keywords, received_at)
+                    .await?;
+            }
+            ZipImportItem::Message {
+                raw_message: None, ..
+            } => {
+                importer.failed += 1;
+            }
+        }
+    }
+
+    reader.await.map_err(into_error)?
+}
+
+fn read_zip(src: &Path, tx: mpsc::Sender<ZipImportItem>) -> trc::Result<()> {
+    let mut archive = ZipArchive::new(BufReader::new(File::open(src).map_err(into_error)?))
+        .map_err(into_error)?;
+
+    if let Some(manifest) = read_zip_entry(&mut archive, MANIFEST_FILE)? {
+        let manifest = serde_json::from_slice::<ArchiveManifest>(&manifest).map_err(into_error)?;
+        for mailbox_path in manifest.mailboxes {
+            if tx
+                .blocking_send(ZipImportItem::Mailbox(mailbox_path))
+                .is_err()
+            {
+                return Ok(());
+            }
+        }
+
+        for message in manifest.messages {
+            let item = ZipImportItem::Message {
+                raw_message: read_zip_entry(&mut archive, &message.file)?,
+                mailboxes: message.mailboxes,
+                keywords: message
+                    .keywords
+                    .iter()
+                    .map(|keyword| Keyword::parse(keyword))
+                    .collect(),
+                received_at: message.received_at,
+            };
+            if tx.blocking_send(item).is_err() {
+                return Ok(());
+            }
+        }
+    } else {
+        // Without a manifest, the directory of each entry is used as the mailbox path
+        let file_names = archive
+            .file_names()
+            .filter(|name| name.ends_with(".eml"))
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+
+        for file_name in file_names {
+            let Some(raw_message) = read_zip_entry(&mut archive, &file_name)? else {
+                continue;
+            };
+            let item = ZipImportItem::Message {
+                raw_message: Some(raw_message),
+                mailboxes: vec![
+                    file_name
+                        .rsplit_once('/')
+                        .map(|(path, _)| path.to_string())
+                        .unwrap_or_default(),
+                ],
+                keywords: vec![],
+                received_at: None,
+            };
+            if tx.blocking_send(item).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Importer<'_> {
+    async fn mailbox_id(&mut self, path: &str) -> trc::Result<Option<u32>> {
+        let path = path.trim_matches('/');
+        if path.is_empty() || path.eq_ignore_ascii_case("inbox") {
+            return Ok(Some(INBOX_ID));
+        }
+
+        let key = path.to_lowercase();
+        if let Some(mailbox_id) = self.mailboxes.get(&key) {
+            return Ok(Some(*mailbox_id));
+        }
+
+        let mailbox_id = self
+            .server
+            .mailbox_create_path(self.account_id, path)
+            .await
+            .caused_by(trc::location!())?;
+        if let Some(mailbox_id) = mailbox_id {
+            self.mailboxes.insert(key, mailbox_id);
+        }
+
+        Ok(mailbox_id)
+    }
+
+    async fn ingest(
+        &mut self,
+        raw_message: &[u8],
+        mailbox_paths: &[String],
+        keywords: Vec<Keyword>,
+        received_at: Option<u64>,
+    ) -> trc::Result<()> {
+        let mut mailbox_ids = Vec::with_capacity(mailbox_paths.len());
+        for mailbox_path in mailbox_paths {
+            if let Some(mailbox_id) = self.mailbox_id(mailbox_path).await?
+                && !mailbox_ids.contains(&mailbox_id)
+            {
+                mailbox_ids.push(mailbox_id);
+            }
+        }
+        if mailbox_ids.is_empty() {
+            self.failed += 1;
+            return Ok(());
+        }
+
+        match self
+            .server
+            .email_ingest(IngestEmail {
+                raw_message,
+                message: MessageParser::new().parse(raw_message),
+                blob_hash: None,
+                access_token: self.access_token,
+                mailbox_ids,
+                keywords,
+                received_at,
+                source: IngestSource::Jmap {
+                    train_classifier: false,
+                },
+                session_id: 0,
+            })
+            .await
+        {
+            Ok(_) => {
+                self.imported += 1;
+            }
+            Err(err)
+                if err.matches(trc::EventType::MessageIngest(
+                    trc::MessageIngestEvent::Error,
+                )) =>
+            {
+                trc::error!(
+                    err.account_id(self.account_id)
+                        .details("Failed to import message")
+                );
+                self.failed += 1;
+            }
+            Err(err) => return Err(err.caused_by(trc::location!())),
+        }
+
+        Ok(())
+    }
+}
+
+async fn fetch_message(
+    server: &Server,
+    account_id: u32,
+    document_id: u32,
+) -> trc::Result<Option<ExportedMessage>> {
+    let Some(metadata_) = server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+            account_id,
+            Collection::Email,
+            document_id,
+            EmailField::Metadata,
+        ))
+        .await
+        .caused_by(trc::location!())?
+    else {
+        return Ok(None);
+    };
+    let metadata = metadata_
+        .unarchive::<MessageMetadata>()
+        .caused_by(trc::location!())?;
+    let Some(bytes) = server
+        .blob_store()
+        .get_blob(metadata.blob_hash.0.as_slice(), 0..usize::MAX)
+        .await
+        .caused_by(trc::location!())?
+    else {
+        return Ok(None);
+    };
+    let body = bytes
+        .get(metadata.blob_body_offset.to_native() as usize..)
+        .unwrap_or_default();
+    let mut raw_message = Vec::with_capacity(metadata.raw_headers.len() + body.len());
+    raw_message.extend_from_slice(metadata.raw_headers.as_ref());
+    raw_message.extend_from_slice(body);
+
+    Ok(Some(ExportedMessage {
+        raw_message,
+        received_at: metadata.rcvd_attach.to_native() & MESSAGE_RECEIVED_MASK,
+    }))
+}
+
+fn mailbox_messages(cache: &MessageStoreCache, mailbox_id: u32) -> Vec<&MessageCache> {
+    let mut messages = cache
+        .in_mailbox(mailbox_id)
+        .filter_map(|message| {
+            message
+                .mailboxes
+                .iter()
+                .find(|item| item.mailbox_id == mailbox_id)
+                .map(|item| (item.uid, message))
+        })
+        .collect::<Vec<_>>();
+    messages.sort_unstable_by_key(|(uid, _)| *uid);
+    messages.into_iter().map(|(_, message)| message).collect()
+}
+
+fn write_mbox_message(
+    writer: &mut impl Write,
+    message: &ExportedMessage,
+    keywords: &[Keyword],
+) -> std::io::Result<()> {
+    let received_at = DateTime::from_timestamp(message.received_at as i64, 0).unwrap_or_default();
+    let mut headers = format!(
+        "From MAILER-DAEMON {}\r\nStatus: {}\r\n",
+        received_at.format("%a %b %e %H:%M:%S %Y"),
+        if keywords.contains(&Keyword::Seen) {
+            "RO"
+        } else {
+            "O"
+        }
+    );
+    let x_status = keywords.iter().filter_map(mbox_flag).collect::<String>();
+    if !x_status.is_empty() {
+        headers.push_str(&format!("X-Status: {x_status}\r\n"));
+    }
+    let x_keywords = keywords
+        .iter()
+        .filter(|keyword| **keyword != Keyword::Seen && mbox_flag(keyword).is_none())
+        .map(|keyword| keyword.to_string())
+        .collect::<Vec<_>>();
+    if !x_keywords.is_empty() {
+        headers.push_str(&format!("X-Keywords: {}\r\n", x_keywords.join(" ")));
+    }
+    writer.write_all(headers.as_bytes())?;
+
+    // mboxrd quoting of "From " lines
+    for line in message.raw_message.split_inclusive(|&ch| ch == b'\n') {
+        if line.starts_with(b"From ") || is_from_line(line) {
+            writer.write_all(b">")?;
+        }
+        writer.write_all(line)?;
+    }
+    if !message.raw_message.ends_with(b"\n") {
+        writer.write_all(b"\r\n")?;
+    }
+    writer.write_all(b"\r\n")
+}
+
+fn parse_mbox_status(message: &[u8]) -> (Vec<u8>, Vec<Keyword>) {
+    let mut raw_message = Vec::with_capacity(message.len());
+    let mut keywords = Vec::new();
+    let mut offset = 0;
+    let mut skip = false;
+
+    for line in message.split_inclusive(|&ch| ch == b'\n') {
+        if line.trim_ascii().is_empty() {
+            break;
+        }
+        offset += line.len();
+
+        if !line.starts_with(b" ") && !line.starts_with(b"\t") {
+            let (name, value) = line
+                .iter()
+                .position(|&ch| ch == b':')
+                .map(|pos| (&line[..pos], String::from_utf8_lossy(&line[pos + 1..])))
+                .unwrap_or_default();
+            skip = true;
+            if name.eq_ignore_ascii_case(b"Status") {
+                if value.contains('R') {
+                    keywords.push(Keyword::Seen);
+                }
+            } else if name.eq_ignore_ascii_case(b"X-Status") {
+                keywords.extend(value.chars().filter_map(mbox_keyword));
+            } else if name.eq_ignore_ascii_case(b"X-Keywords") {
+                keywords.extend(
+                    value
+                        .split([' ', ',', '\t', '\r', '\n'])
+                        .filter(|keyword| !keyword.is_empty())
+                        .map(Keyword::parse),
+                );
+            } else {
+                skip = false;
+            }
+        }
+
+        if !skip {
+            raw_message.extend_from_slice(line);
+        }
+    }
+    raw_message.extend_from_slice(&message[offset..]);
+    keywords.dedup();
+
+    (raw_message, keywords)
+}
+
+fn parse_from_line(line: &[u8]) -> Option<u64> {
+    // From sender Mon Jan  1 00:00:00 2024
+    let line = std::str::from_utf8(line).ok()?;
+    let (_, date) = line.strip_prefix("From ")?.trim().split_once(' ')?;
+    let date = date.split_whitespace().collect::<Vec<_>>().join(" ");
+    NaiveDateTime::parse_from_str(&date, "%a %b %d %H:%M:%S %Y")
+        .ok()
+        .map(|date| date.and_utc().timestamp() as u64)
+}
+
+fn is_from_line(line: &[u8]) -> bool {
+    line.starts_with(b">")
+        && line
+            .iter()
+            .skip_while(|&&ch| ch == b'>')
+            .take(5)
+            .eq(b"From ".iter())
+}
+
+async fn read_dovecot_keywords(folder: &Path) -> Vec<Option<Keyword>> {
+    let mut keywords = vec![None; MAX_MAILDIR_KEYWORDS];
+    if let Ok(contents) = tokio::fs::read_to_string(folder.join(DOVECOT_KEYWORDS_FILE)).await {
+        for line in contents.lines() {
+            if let Some((idx, keyword)) = line.split_once(' ')
+                && let Ok(idx) = idx.parse::<usize>()
+                && idx < keywords.len()
+                && !keyword.trim().is_empty()
+            {
+                keywords[idx] = Some(Keyword::parse(keyword.trim()));
+            }
+        }
+    }
+    keywords
+}
+
+async fn read_extra_keywords(folder: &Path) -> AHashMap<String, Vec<Keyword>> {
+    let mut keywords = AHashMap::new();
+    if let Ok(contents) = tokio::fs::read_to_string(folder.join(EXTRA_KEYWORDS_FILE)).await {
+        for line in contents.lines() {
+            let mut parts = line.split_whitespace();
+            if let Some(base_name) = parts.next() {
+                keywords.insert(base_name.to_string(), parts.map(Keyword::parse).collect());
+            }
+        }
+    }
+    keywords
+}
+
+fn read_zip_entry(
+    archive: &mut ZipArchive<BufReader<File>>,
+    name: &str,
+) -> trc::Result<Option<Vec<u8>>> {
+    match archive.by_name(name) {
+        Ok(mut file) => {
+            let mut contents = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut contents).map_err(into_error)?;
+            Ok(Some(contents))
+        }
+        Err(zip::result::ZipError::FileNotFound) => Ok(None),
+        Err(err) => Err(into_error(err)),
+    }
+}
+
+async fn collect_mbox_files(root: &Path, files: &mut Vec<(String, PathBuf)>) -> trc::Result<()> {
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await.map_err(into_error)?;
+        while let Some(entry) = entries.next_entry().await.map_err(into_error)? {
+            let path = entry.path();
+            if tokio::fs::metadata(&path)
+                .await
+                .is_ok_and(|metadata| metadata.is_dir())
+            {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "mbox")
+                && let Ok(relative) = path.with_extension("").strip_prefix(root)
+            {
+                let mailbox_path = relative
+                    .components()
+                    .filter_map(|component| component.as_os_str().to_str())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((mailbox_path, path));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn maildir_folder_name(path: &str) -> String {
+    // Dots separate hierarchy levels in Maildir++
+    let mut name = String::with_capacity(path.len() + 1);
+    for part in path.split('/') {
+        name.push('.');
+        name.push_str(&part.replace(['.', '\\', '\0'], "_"));
+    }
+    name
+}
+
+fn mbox_file_path(dest: &Path, mailbox_path: &str) -> PathBuf {
+    let mut path = dest.to_path_buf();
+    let mut names = mailbox_path.split('/').peekable();
+    while let Some(name) = names.next() {
+        let name = file_name(name);
+        if names.peek().is_some() {
+            path.push(name);
+        } else {
+            path.push(format!("{name}.mbox"));
+        }
+    }
+    path
+}
+
+fn file_name(name: &str) -> String {
+    if matches!(name, "" | "." | "..") {
+        "_".to_string()
+    } else {
+        name.replace(['\\', '\0'], "_")
+    }
+}
+
+fn maildir_flag(keyword: &Keyword) -> Option<char> {
+    match keyword {
+        Keyword::Draft => Some('D'),
+        Keyword::Flagged => Some('F'),
+        Keyword::Forwarded => Some('P'),
+        Keyword::Answered => Some('R'),
+        Keyword::Seen => Some('S'),
+        Keyword::Deleted => Some('T'),
+        _ => None,
+    }
+}
+
+fn maildir_keyword(flag: char) -> Option<Keyword> {
+    match flag {
+        'D' => Some(Keyword::Draft),
+        'F' => Some(Keyword::Flagged),
+        'P' => Some(Keyword::Forwarded),
+        'R' => Some(Keyword::Answered),
+        'S' => Some(Keyword::Seen),
+        'T' => Some(Keyword::Deleted),
+        _ => None,
+    }
+}
+
+fn mbox_flag(keyword: &Keyword) -> Option<char> {
+    match keyword {
+        Keyword::Answered => Some('A'),
+        Keyword::Flagged => Some('F'),
+        Keyword::Draft => Some('T'),
+        Keyword::Deleted => Some('D'),
+        _ => None,
+    }
+}
+
+fn mbox_keyword(flag: char) -> Option<Keyword> {
+    match flag {
+        'A' => Some(Keyword::Answered),
+        'F' => Some(Keyword::Flagged),
+        'T' => Some(Keyword::Draft),
+        'D' => Some(Keyword::Deleted),
+        _ => None,
+    }
+}
+
+fn into_error(err: impl std::fmt::Display) -> trc::Error {
+    trc::StoreEvent::FilesystemError.reason(err)
+}
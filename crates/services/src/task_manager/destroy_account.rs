@@ -6,7 +6,10 @@
 
 use crate::task_manager::TaskResult;
 use common::Server;
-use email::{message::metadata::MessageMetadata, sieve::SieveScript};
+use email::{
+    message::{metadata::MessageMetadata, quarantine::EmailQuarantine},
+    sieve::SieveScript,
+};
 use groupware::file::FileNode;
 use registry::{
     schema::{
@@ -134,11 +137,25 @@ async fn destroy_account(server: &Server, task: &TaskDestroyAccount) -> trc::Res
         server.store().write(batch.build_all()).await?;
     }
 
+    // Remove quarantined messages
+    for id in server
+        .registry()
+        .query::<Vec<Id>>(
+            RegistryQuery::new(ObjectType::QuarantinedMessage).with_account(account_id),
+        )
+        .await?
+    {
+        server
+            .quarantine_destroy(account_id.into(), id.id())
+            .await?;
+    }
+
     // Remove search index
     for index in [
         SearchIndex::Email,
         SearchIndex::Contacts,
         SearchIndex::Calendar,
+        SearchIndex::File,
     ] {
         server
             .search_store()
@@ -7,7 +7,9 @@
 use crate::task_manager::{Task, TaskDetails, TaskResult};
 use common::Server;
 use email::{cache::MessageCacheFetch, message::metadata::MessageMetadata};
-use groupware::{cache::GroupwareCache, calendar::CalendarEvent, contact::ContactCard};
+use groupware::{
+    cache::GroupwareCache, calendar::CalendarEvent, contact::ContactCard, file::FileNode,
+};
 use registry::{
     schema::{
         enums::IndexDocumentType,
@@ -21,7 +23,9 @@ use store::{
     IterateParams, ValueKey,
     ahash::AHashMap,
     rand::{self, Rng},
-    search::{IndexDocument, SearchField, SearchFilter, SearchQuery},
+    search::{
+        EmailSearchField, FileSearchField, IndexDocument, SearchField, SearchFilter, SearchQuery,
+    },
     write::{
         AlignedBytes, Archive, BatchBuilder, SearchIndex, TelemetryClass, ValueClass,
         key::DeserializeBigEndian, now,
@@ -78,8 +82,7 @@ impl SearchIndexTask for Server {
                             build_contact_document(self, account_id, document_id).await
                         }
                         IndexDocumentType::File => {
-                            // File indexing not implemented yet
-                            continue;
+                            build_file_document(self, account_id, document_id).await
                         }
                     };
 
@@ -235,6 +238,7 @@ impl SearchIndexTask for Server {
             SearchIndex::Email,
             SearchIndex::Calendar,
             SearchIndex::Contacts,
+            SearchIndex::File,
         ]) {
             let multi_account = match accounts.len().cmp(&1) {
                 Ordering::Greater => true,
@@ -354,17 +358,13 @@ pub(crate) async fn reindex_account(server: &Server, account_id: u32) -> trc::Re
         }
     }
 
-    for document_type in [IndexDocumentType::Calendar, IndexDocumentType::Contacts] {
+    for (document_type, sync_collection) in [
+        (IndexDocumentType::Calendar, SyncCollection::Calendar),
+        (IndexDocumentType::Contacts, SyncCollection::AddressBook),
+        (IndexDocumentType::File, SyncCollection::FileNode),
+    ] {
         let cache = server
-            .fetch_dav_resources(
-                account_id,
-                account_id,
-                if document_type == IndexDocumentType::Calendar {
-                    SyncCollection::Calendar
-                } else {
-                    SyncCollection::AddressBook
-                },
-            )
+            .fetch_dav_resources(account_id, account_id, sync_collection)
             .await
             .caused_by(trc::location!())?;
         let mut batch = BatchBuilder::new();
@@ -429,10 +429,31 @@ async fn build_email_document(
                         .details("Blob not found")
                 })?;
 
+            // Extract text from PDF and Office attachments
+            let mut extracted_text = AHashMap::new();
+            if server.core.email.extract.enable
+                && (index_fields.is_empty()
+                    || index_fields.contains(&SearchField::Email(EmailSearchField::Attachment)))
+            {
+                for attachment in metadata.binary_attachments(&raw_message) {
+                    if let Some(text) = server
+                        .extract_text(
+                            attachment.content_type.as_deref(),
+                            attachment.file_name,
+                            &attachment.contents,
+                        )
+                        .await
+                    {
+                        extracted_text.insert(attachment.part_id, text);
+                    }
+                }
+            }
+
             Ok(Some(metadata.index_document(
                 account_id,
                 document_id,
                 &raw_message,
+                &extracted_text,
                 index_fields,
                 server.core.email.default_language,
             )))
@@ -507,6 +528,67 @@ async fn build_contact_document(
     }
 }
 
+async fn build_file_document(
+    server: &Server,
+    account_id: u32,
+    document_id: u32,
+) -> trc::Result<Option<IndexDocument>> {
+    let Some(index_fields) = server.core.email.index_fields.get(&SearchIndex::File) else {
+        return Ok(None);
+    };
+
+    match server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+            account_id,
+            Collection::FileNode,
+            document_id,
+        ))
+        .await?
+    {
+        Some(node_) => {
+            let node = node_.unarchive::<FileNode>().caused_by(trc::location!())?;
+            let Some(file) = node.file.as_ref() else {
+                return Ok(None);
+            };
+
+            // Extract text from PDF and Office documents
+            let extract = &server.core.email.extract;
+            let contents = if extract.enable
+                && file.size.to_native() as usize <= extract.max_size
+                && (index_fields.is_empty()
+                    || index_fields.contains(&SearchField::File(FileSearchField::Content)))
+                && let Some(data) = server
+                    .blob_store()
+                    .get_blob(file.blob_hash.0.as_slice(), 0..usize::MAX)
+                    .await
+                    .caused_by(trc::location!())?
+            {
+                server
+                    .extract_text(
+                        file.media_type
+                            .as_ref()
+                            .map(|media_type| media_type.as_str()),
+                        Some(node.name.as_str()),
+                        &data,
+                    )
+                    .await
+            } else {
+                None
+            };
+
+            Ok(Some(node.index_document(
+                account_id,
+                document_id,
+                contents.as_deref(),
+                index_fields,
+                server.core.email.default_language,
+            )))
+        }
+        None => Ok(None),
+    }
+}
+
 // SPDX-SnippetBegin
 // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
 // SPDX-License-Identifier: LicenseRef-SEL
@@ -19,7 +19,9 @@ use common::{
 };
 use email::{
     cache::MessageCacheFetch,
-    message::{delete::EmailDeletion, ingest::EmailIngest, metadata::MessageData},
+    message::{
+        delete::EmailDeletion, index::saved_at_id, ingest::EmailIngest, metadata::MessageData,
+    },
     sieve::SieveScript,
 };
 use groupware::{
@@ -39,11 +41,14 @@ use registry::{
 };
 use smtp::reporting::index::ExternalReportIndex;
 use store::{
-    Serialize, ValueKey,
+    Serialize, SerializeInfallible, ValueKey,
     rand::{self},
     registry::{RegistryFilter, RegistryQuery},
     roaring::RoaringBitmap,
-    write::{AlignedBytes, Archive, Archiver, BatchBuilder, RegistryClass, ValueClass, now},
+    write::{
+        AlignedBytes, Archive, Archiver, BatchBuilder, IndexPropertyClass, RegistryClass,
+        ValueClass, now,
+    },
 };
 use trc::{AddContext, StoreEvent};
 use types::{
@@ -573,7 +578,8 @@ async fn reset_imap_uids(server: &Server, account_id: u32) -> trc::Result<(u32,
                     .with_changes(new_mailbox),
             )
             .caused_by(trc::location!())?
-            .clear(MailboxField::UidCounter);
+            .clear(MailboxField::UidCounter)
+            .log_unversioned_change();
         server
             .store()
             .write(batch.build_all())
@@ -614,7 +620,12 @@ async fn reset_imap_uids(server: &Server, account_id: u32) -> trc::Result<(u32,
             .await
             .caused_by(trc::location!())?;
 
+        let mut saved_ids = Vec::with_capacity(new_data.mailboxes.len());
         for (uid_mailbox, uid) in new_data.mailboxes.iter_mut().zip(ids) {
+            saved_ids.push((
+                saved_at_id(uid_mailbox.mailbox_id, uid_mailbox.uid),
+                saved_at_id(uid_mailbox.mailbox_id, uid),
+            ));
             uid_mailbox.uid = uid;
         }
 
@@ -631,6 +642,34 @@ async fn reset_imap_uids(server: &Server, account_id: u32) -> trc::Result<(u32,
                     .serialize()
                     .caused_by(trc::location!())?,
             );
+
+        batch.log_unversioned_change();
+
+        // Save dates are keyed by UID
+        for (old_id, new_id) in saved_ids {
+            let saved_at_class = |value| {
+                ValueClass::IndexProperty(IndexPropertyClass::Integer {
+                    property: EmailField::SavedAt.into(),
+                    value,
+                })
+            };
+            if let Some(saved_at) = server
+                .store()
+                .get_value::<u64>(ValueKey {
+                    account_id,
+                    collection: Collection::Email.into(),
+                    document_id: message_id,
+                    class: saved_at_class(old_id),
+                })
+                .await
+                .caused_by(trc::location!())?
+            {
+                batch
+                    .clear(saved_at_class(old_id))
+                    .set(saved_at_class(new_id), saved_at.serialize());
+            }
+        }
+
         server
             .store()
             .write(batch.build_all())
@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use crate::task_manager::account_archive::AccountArchiveTask;
 use crate::task_manager::acme::AcmeTask;
 use crate::task_manager::alarm::SendAlarmTask;
 use crate::task_manager::destroy_account::DestroyAccountTask;
@@ -14,6 +15,7 @@ use crate::task_manager::index::SearchIndexTask;
 use crate::task_manager::lock::TaskLockManager;
 use crate::task_manager::maintenance::MaintenanceTask;
 use crate::task_manager::merge_threads::MergeThreadsTask;
+use crate::task_manager::quarantine::QuarantineDigestTask;
 use crate::task_manager::report::{self, SubmitReportTask};
 use crate::task_manager::restore_item::RestoreItemTask;
 use crate::task_manager::spam_classifier::SpamFilterMaintenanceTask;
@@ -92,6 +94,9 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
             }
             TaskType::DestroyAccount
             | TaskType::AccountMaintenance
+            | TaskType::AccountExport
+            | TaskType::AccountImport
+            | TaskType::QuarantineDigest
             | TaskType::TenantMaintenance
             | TaskType::StoreMaintenance => 1,
             TaskType::SpamFilterMaintenance => 2,
@@ -234,6 +239,11 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
                                 Task::AccountMaintenance(task) => {
                                     server.account_maintenance(task).await
                                 }
+                                Task::AccountExport(task) => server.account_export(task).await,
+                                Task::AccountImport(task) => server.account_import(task).await,
+                                Task::QuarantineDigest(task) => {
+                                    server.quarantine_digest(task).await
+                                }
                                 Task::TenantMaintenance(task) => {
                                     server.tenant_maintenance(task).await
                                 }
@@ -359,6 +369,9 @@ impl TaskQueueManager for Server {
                                 | TaskType::UnindexDocument
                                 | TaskType::IndexTrace => roles.search_indexing,
                                 TaskType::AccountMaintenance
+                                | TaskType::AccountExport
+                                | TaskType::AccountImport
+                                | TaskType::QuarantineDigest
                                 | TaskType::TenantMaintenance
                                 | TaskType::DestroyAccount => roles.account_maintenance,
                                 TaskType::StoreMaintenance => roles.store_maintenance,
@@ -15,6 +15,7 @@ use store::write::Operation;
 use tokio::sync::mpsc;
 use trc::TaskManagerEvent;
 
+pub mod account_archive;
 pub mod acme;
 pub mod alarm;
 pub mod destroy_account;
@@ -26,6 +27,7 @@ pub mod lock;
 pub mod maintenance;
 pub mod manager;
 pub mod merge_threads;
+pub mod quarantine;
 pub mod report;
 pub mod restore_item;
 pub mod scheduler;
@@ -105,6 +107,9 @@ impl TaskInfo for Task {
             Task::DkimManagement(_) => "DkimManagement",
             Task::DnsManagement(_) => "DnsManagement",
             Task::TenantMaintenance(_) => "TenantMaintenance",
+            Task::AccountExport(_) => "AccountExport",
+            Task::AccountImport(_) => "AccountImport",
+            Task::QuarantineDigest(_) => "QuarantineDigest",
         }
     }
 }
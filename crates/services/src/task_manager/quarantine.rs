@@ -0,0 +1,230 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use common::{Server, auth::oauth::GrantType};
+use mail_builder::{
+    MessageBuilder,
+    headers::{HeaderType, content_type::ContentType},
+    mime::{BodyPart, MimePart},
+};
+use registry::{
+    schema::{
+        prelude::{ObjectType, Property},
+        structs::{QuarantinedMessage, TaskQuarantineDigest},
+    },
+    types::{EnumImpl, ObjectImpl},
+};
+use smtp::reporting::send::MtaReportSend;
+use std::fmt::Write;
+use store::{
+    ValueKey,
+    ahash::AHashMap,
+    registry::RegistryQuery,
+    write::{BatchBuilder, RegistryClass, ValueClass, now},
+};
+use trc::{AddContext, MessageIngestEvent};
+use types::id::Id;
+
+pub(crate) trait QuarantineDigestTask: Sync + Send {
+    fn quarantine_digest(
+        &self,
+        task: &TaskQuarantineDigest,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl QuarantineDigestTask for Server {
+    async fn quarantine_digest(&self, _: &TaskQuarantineDigest) -> TaskResult {
+        match quarantine_digest(self).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(err.details("Failed to send quarantine digests"));
+                result
+            }
+        }
+    }
+}
+
+async fn quarantine_digest(server: &Server) -> trc::Result<TaskResult> {
+    // Group messages that have not been notified yet by account
+    let object_id = ObjectType::QuarantinedMessage.to_id();
+    let mut pending: AHashMap<u32, Vec<(u64, QuarantinedMessage)>> = AHashMap::new();
+    for id in server
+        .registry()
+        .query::<Vec<Id>>(
+            RegistryQuery::new(ObjectType::QuarantinedMessage)
+                .greater_than_or_equal(Property::AccountId, 0u64),
+        )
+        .await
+        .caused_by(trc::location!())?
+    {
+        let item_id = id.id();
+        if let Some(item) = server
+            .store()
+            .get_value::<QuarantinedMessage>(ValueKey::from(ValueClass::Registry(
+                RegistryClass::Item { object_id, item_id },
+            )))
+            .await
+            .caused_by(trc::location!())?
+            .filter(|item| !item.notified)
+        {
+            pending
+                .entry(item.account_id.document_id())
+                .or_default()
+                .push((item_id, item));
+        }
+    }
+
+    let config = &server.core.email.quarantine;
+    let base_url = server.core.network.http.url_https.trim_end_matches('/');
+    let now = now();
+    for (account_id, mut items) in pending {
+        let account_info = server
+            .account_info(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let rcpt = account_info.name();
+        if rcpt.is_empty() {
+            continue;
+        }
+        items.sort_unstable_by_key(|(_, item)| item.received_at);
+
+        // Build digest
+        let mut txt_body = format!(
+            "The following {} message(s) addressed to {rcpt} were placed in quarantine.\r\n\r\n",
+            items.len()
+        );
+        let mut html_body = format!(
+            concat!(
+                "<html><body><p>The following {} message(s) addressed to {} ",
+                "were placed in quarantine.</p><table>",
+                "<tr><th>Received</th><th>From</th><th>Subject</th>",
+                "<th>Reason</th><th></th></tr>"
+            ),
+            items.len(),
+            html_escape(rcpt)
+        );
+        for (item_id, item) in &items {
+            let expires_in = (item.expires_at.timestamp() as u64).saturating_sub(now);
+            let release_url = if !base_url.is_empty() && expires_in > 0 {
+                server
+                    .encode_access_token(
+                        GrantType::QuarantineRelease,
+                        account_id,
+                        "",
+                        expires_in,
+                        Some(&item_id.to_string()),
+                        None,
+                    )
+                    .await
+                    .map(|token| format!("{base_url}/quarantine/release?t={token}"))
+                    .ok()
+            } else {
+                None
+            };
+
+            let _ = write!(
+                txt_body,
+                "Received: {}\r\nFrom: {}\r\nSubject: {}\r\nReason: {}\r\n",
+                item.received_at, item.from, item.subject, item.reason
+            );
+            if let Some(release_url) = &release_url {
+                let _ = write!(txt_body, "Release: {release_url}\r\n");
+            }
+            txt_body.push_str("\r\n");
+
+            let _ = write!(
+                html_body,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>",
+                item.received_at,
+                html_escape(&item.from),
+                html_escape(&item.subject),
+                html_escape(&item.reason)
+            );
+            if let Some(release_url) = &release_url {
+                let _ = write!(
+                    html_body,
+                    "<a href=\"{}\">Release</a>",
+                    html_escape(release_url)
+                );
+            }
+            html_body.push_str("</td></tr>");
+        }
+        html_body.push_str("</table></body></html>");
+
+        let message = MessageBuilder::new()
+            .from((config.from_name.as_str(), config.from_address.as_str()))
+            .header("To", HeaderType::Text(rcpt.into()))
+            .header("Auto-Submitted", HeaderType::Text("auto-generated".into()))
+            .subject(config.subject.as_str())
+            .body(MimePart::new(
+                ContentType::new("multipart/alternative"),
+                BodyPart::Multipart(vec![
+                    MimePart::new(
+                        ContentType::new("text/plain"),
+                        BodyPart::Text(txt_body.into()),
+                    ),
+                    MimePart::new(
+                        ContentType::new("text/html"),
+                        BodyPart::Text(html_body.into()),
+                    ),
+                ]),
+            ))
+            .write_to_vec()
+            .unwrap_or_default();
+
+        server
+            .send_autogenerated(
+                config.from_address.as_str(),
+                [rcpt].into_iter(),
+                message,
+                None,
+                0,
+            )
+            .await;
+
+        // Mark messages as notified
+        let mut batch = BatchBuilder::new();
+        for (item_id, mut item) in items {
+            item.notified = true;
+            batch.set(
+                ValueClass::Registry(RegistryClass::Item { object_id, item_id }),
+                item.to_pickled_vec(),
+            );
+            if batch.is_large_batch() {
+                server.store().write(batch.build_all()).await?;
+                batch = BatchBuilder::new();
+            }
+        }
+        if !batch.is_empty() {
+            server.store().write(batch.build_all()).await?;
+        }
+
+        trc::event!(
+            MessageIngest(MessageIngestEvent::QuarantineDigestSent),
+            AccountId = account_id,
+            To = rcpt.to_string(),
+        );
+    }
+
+    Ok(TaskResult::Success(vec![]))
+}
+
+fn html_escape(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
@@ -18,7 +18,9 @@ use common::{
 use registry::{
     schema::{
         enums::{TaskSpamFilterMaintenanceType, TaskStoreMaintenanceType, TaskType},
-        structs::{Task, TaskSpamFilterMaintenance, TaskStatus, TaskStoreMaintenance},
+        structs::{
+            Task, TaskQuarantineDigest, TaskSpamFilterMaintenance, TaskStatus, TaskStoreMaintenance,
+        },
     },
     types::EnumImpl,
 };
@@ -39,6 +41,7 @@ enum Event {
     OtelMetrics,
     CalculateMetrics,
     TrainSpamClassifier,
+    QuarantineDigest,
     RenewNodeIdLease,
     // SPDX-SnippetBegin
     // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
@@ -120,6 +123,14 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
                 );
             }
 
+            // Quarantine digests
+            if server.core.email.quarantine.send_digest {
+                queue.schedule(
+                    Instant::now() + server.core.email.quarantine.digest_frequency.time_to_next(),
+                    Event::QuarantineDigest,
+                );
+            }
+
             // OTEL Push Metrics
             if let Some(otel) = &server.core.metrics.otel {
                 OtelMetrics::enable_errors();
@@ -395,6 +406,26 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
                             }
                         }
                     }
+                    Event::QuarantineDigest => {
+                        if server.core.email.quarantine.send_digest {
+                            queue.schedule(
+                                Instant::now()
+                                    + server.core.email.quarantine.digest_frequency.time_to_next(),
+                                Event::QuarantineDigest,
+                            );
+
+                            if let Some(batch) = batch.as_mut() {
+                                trc::event!(
+                                    TaskManager(TaskManagerEvent::TaskQueued),
+                                    Type = TaskType::QuarantineDigest.as_str()
+                                );
+
+                                batch.schedule_task(Task::QuarantineDigest(TaskQuarantineDigest {
+                                    status: TaskStatus::now(),
+                                }));
+                            }
+                        }
+                    }
 
                     // SPDX-SnippetBegin
                     // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
@@ -570,6 +601,7 @@ impl Event {
             Event::OtelMetrics => "otelMetrics",
             Event::CalculateMetrics => "calculateMetrics",
             Event::TrainSpamClassifier => "trainSpamClassifier",
+            Event::QuarantineDigest => "quarantineDigest",
             Event::RenewNodeIdLease => "renewNodeIdLease",
             // SPDX-SnippetBegin
             // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <info@stalwartlabs.com>
@@ -123,6 +123,9 @@ pub struct SessionParameters {
     pub rcpt_dsn: bool,
     pub can_expn: bool,
     pub can_vrfy: bool,
+    pub can_etrn: bool,
+    pub can_atrn: bool,
+    pub can_burl: bool,
     pub max_message_size: usize,
 
     // Mail authentication parameters
@@ -236,6 +239,9 @@ impl Session<common::network::stream::NullIo> {
                 spf_mail_from: VerifyStrategy::Disable,
                 can_expn: false,
                 can_vrfy: false,
+                can_etrn: false,
+                can_atrn: false,
+                can_burl: false,
             },
         }
     }
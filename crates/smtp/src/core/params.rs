@@ -86,7 +86,7 @@ impl<T: SessionStream> Session<T> {
             .await
             .unwrap_or_else(|| Duration::from_secs(30));
 
-        // VRFY/EXPN parameters
+        // VRFY/EXPN/ETRN/ATRN/BURL parameters
         let ec = &self.server.core.smtp.session.extensions;
         self.params.can_expn = self
             .server
@@ -98,10 +98,25 @@ impl<T: SessionStream> Session<T> {
             .eval_if(&ec.vrfy, self, self.data.session_id)
             .await
             .unwrap_or(false);
+        self.params.can_etrn = self
+            .server
+            .eval_if(&ec.etrn, self, self.data.session_id)
+            .await
+            .unwrap_or(false);
+        self.params.can_atrn = self
+            .server
+            .eval_if(&ec.atrn, self, self.data.session_id)
+            .await
+            .unwrap_or(false);
+        self.params.can_burl = self
+            .server
+            .eval_if(&ec.burl, self, self.data.session_id)
+            .await
+            .unwrap_or(false);
     }
 
     pub async fn eval_post_auth_params(&mut self) {
-        // Refresh VRFY/EXPN parameters
+        // Refresh VRFY/EXPN/ETRN/ATRN/BURL parameters
         let ec = &self.server.core.smtp.session.extensions;
         self.params.can_expn = self
             .server
@@ -113,6 +128,21 @@ impl<T: SessionStream> Session<T> {
             .eval_if(&ec.vrfy, self, self.data.session_id)
             .await
             .unwrap_or(false);
+        self.params.can_etrn = self
+            .server
+            .eval_if(&ec.etrn, self, self.data.session_id)
+            .await
+            .unwrap_or(false);
+        self.params.can_atrn = self
+            .server
+            .eval_if(&ec.atrn, self, self.data.session_id)
+            .await
+            .unwrap_or(false);
+        self.params.can_burl = self
+            .server
+            .eval_if(&ec.burl, self, self.data.session_id)
+            .await
+            .unwrap_or(false);
     }
 
     pub async fn eval_rcpt_params(&mut self) {
@@ -0,0 +1,205 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{core::Session, inbound::FilterResponse, queue::QueueId};
+use common::{
+    config::smtp::session::{Antivirus, ClamdAddress},
+    network::SessionStream,
+};
+use mail_parser::{Message, MimeHeaders};
+use registry::schema::enums::{AntivirusAction, AntivirusScanMode};
+use std::{borrow::Cow, io, time::Instant};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+};
+use trc::SpamEvent;
+
+const INSTREAM_CHUNK_SIZE: usize = 64 * 1024;
+const MAX_RESPONSE_SIZE: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanResult {
+    Clean,
+    Infected(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AntivirusVerdict {
+    Clean,
+    Tag(String),
+    Quarantine(String),
+}
+
+impl<T: SessionStream> Session<T> {
+    /// Scans the message with clamd, returning the virus name when the configured
+    /// action is to tag or quarantine the message.
+    pub async fn run_antivirus(
+        &self,
+        raw_message: &[u8],
+        message: &Message<'_>,
+        queue_id: QueueId,
+    ) -> Result<AntivirusVerdict, FilterResponse> {
+        let Some(config) = &self.server.core.smtp.session.antivirus else {
+            return Ok(AntivirusVerdict::Clean);
+        };
+
+        let time = Instant::now();
+        let contents = match config.scan_mode {
+            AntivirusScanMode::Message => vec![(None, raw_message)],
+            AntivirusScanMode::Attachments => message
+                .attachments()
+                .map(|part| (part.attachment_name(), part.contents()))
+                .collect(),
+        };
+
+        let mut scanned = 0;
+        for (name, contents) in contents {
+            if contents.is_empty() || contents.len() > config.max_size {
+                continue;
+            }
+            scanned += 1;
+
+            match clamd_scan(config, contents).await {
+                Ok(ScanResult::Clean) => {}
+                Ok(ScanResult::Infected(virus)) => {
+                    trc::event!(
+                        Spam(SpamEvent::VirusFound),
+                        SpanId = self.data.session_id,
+                        QueueId = queue_id,
+                        Details = virus.clone(),
+                        Id = name.map(|name| name.to_string()),
+                        Result = match config.action {
+                            AntivirusAction::Reject => "reject",
+                            AntivirusAction::Discard => "discard",
+                            AntivirusAction::Tag => "tag",
+                            AntivirusAction::Quarantine => "quarantine",
+                        },
+                        Elapsed = time.elapsed(),
+                    );
+
+                    return match config.action {
+                        AntivirusAction::Reject => Err(FilterResponse {
+                            message: Cow::Owned(format!(
+                                "554 5.7.1 Message rejected, virus found: {}\r\n",
+                                virus.replace(['\r', '\n'], " ")
+                            )),
+                            disconnect: false,
+                        }),
+                        AntivirusAction::Discard => Err(FilterResponse::accept()),
+                        AntivirusAction::Tag => Ok(AntivirusVerdict::Tag(virus)),
+                        AntivirusAction::Quarantine => Ok(AntivirusVerdict::Quarantine(virus)),
+                    };
+                }
+                Err(err) => {
+                    trc::event!(
+                        Spam(SpamEvent::AntivirusError),
+                        SpanId = self.data.session_id,
+                        QueueId = queue_id,
+                        Reason = err.to_string(),
+                        Elapsed = time.elapsed(),
+                    );
+
+                    return if config.tempfail_on_error {
+                        Err(FilterResponse::server_failure())
+                    } else {
+                        Ok(AntivirusVerdict::Clean)
+                    };
+                }
+            }
+        }
+
+        trc::event!(
+            Spam(SpamEvent::Antivirus),
+            SpanId = self.data.session_id,
+            QueueId = queue_id,
+            Result = "clean",
+            Total = scanned,
+            Elapsed = time.elapsed(),
+        );
+
+        Ok(AntivirusVerdict::Clean)
+    }
+}
+
+pub async fn clamd_scan(config: &Antivirus, contents: &[u8]) -> io::Result<ScanResult> {
+    tokio::time::timeout(config.timeout, async {
+        match &config.address {
+            ClamdAddress::Tcp(addrs) => {
+                clamd_instream(TcpStream::connect(addrs.as_slice()).await?, contents).await
+            }
+            #[cfg(unix)]
+            ClamdAddress::Unix(path) => {
+                clamd_instream(tokio::net::UnixStream::connect(path).await?, contents).await
+            }
+            #[cfg(not(unix))]
+            ClamdAddress::Unix(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Unix sockets are not supported on this platform",
+            )),
+        }
+    })
+    .await
+    .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "clamd scan timed out"))?
+}
+
+async fn clamd_instream<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
+    contents: &[u8],
+) -> io::Result<ScanResult> {
+    // Chunks are prefixed with their length in network byte order,
+    // a zero-length chunk terminates the stream.
+    stream.write_all(b"zINSTREAM\0").await?;
+    for chunk in contents.chunks(INSTREAM_CHUNK_SIZE) {
+        stream
+            .write_all(&(chunk.len() as u32).to_be_bytes())
+            .await?;
+        stream.write_all(chunk).await?;
+    }
+    stream.write_all(&[0u8; 4]).await?;
+    stream.flush().await?;
+
+    let mut response = Vec::with_capacity(64);
+    let mut buf = [0u8; 256];
+    loop {
+        let len = stream.read(&mut buf).await?;
+        if len == 0 {
+            break;
+        }
+        response.extend_from_slice(&buf[..len]);
+        if response.contains(&0) || response.len() > MAX_RESPONSE_SIZE {
+            break;
+        }
+    }
+
+    parse_clamd_response(&response)
+}
+
+fn parse_clamd_response(response: &[u8]) -> io::Result<ScanResult> {
+    let response = std::str::from_utf8(response)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        .trim_end_matches(['\0', '\r', '\n']);
+    let result = response
+        .strip_prefix("stream:")
+        .map(|result| result.trim())
+        .unwrap_or(response);
+
+    if result == "OK" {
+        Ok(ScanResult::Clean)
+    } else if let Some(virus) = result.strip_suffix(" FOUND") {
+        Ok(ScanResult::Infected(virus.trim().to_string()))
+    } else if response.is_empty() {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "clamd closed the connection without a response",
+        ))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unexpected clamd response: {response}"),
+        ))
+    }
+}
@@ -5,16 +5,25 @@
  */
 
 use crate::core::Session;
-use common::{auth::AuthRequest, network::SessionStream};
-use directory::Credentials;
+use common::{
+    auth::{AccessToken, AuthRequest, scram::ScramState},
+    config::smtp::session::Mechanism,
+    network::SessionStream,
+};
+use directory::{Credentials, core::scram::ScramClientFirst};
 use mail_parser::decoders::base64::base64_decode;
 use registry::schema::enums::Permission;
-use smtp_proto::{AUTH_LOGIN, AUTH_OAUTHBEARER, AUTH_PLAIN, AUTH_XOAUTH2, IntoString};
+use smtp_proto::{
+    AUTH_EXTERNAL, AUTH_LOGIN, AUTH_OAUTHBEARER, AUTH_PLAIN, AUTH_SCRAM_SHA_256,
+    AUTH_SCRAM_SHA_256_PLUS, AUTH_XOAUTH2, IntoString,
+};
 use trc::AuthEvent;
 
 pub struct SaslToken {
     mechanism: u64,
     credentials: Credentials,
+    scram: Option<ScramState>,
+    challenged: bool,
 }
 
 impl SaslToken {
@@ -27,6 +36,8 @@ impl SaslToken {
                     secret: String::new(),
                     mfa_token: None,
                 },
+                scram: None,
+                challenged: false,
             }
             .into(),
             AUTH_OAUTHBEARER | AUTH_XOAUTH2 => SaslToken {
@@ -35,6 +46,28 @@ impl SaslToken {
                     username: None,
                     token: String::new(),
                 },
+                scram: None,
+                challenged: false,
+            }
+            .into(),
+            AUTH_SCRAM_SHA_256 | AUTH_SCRAM_SHA_256_PLUS => SaslToken {
+                mechanism,
+                credentials: Credentials::Bearer {
+                    username: None,
+                    token: String::new(),
+                },
+                scram: None,
+                challenged: false,
+            }
+            .into(),
+            AUTH_EXTERNAL => SaslToken {
+                mechanism,
+                credentials: Credentials::Bearer {
+                    username: None,
+                    token: String::new(),
+                },
+                scram: None,
+                challenged: false,
             }
             .into(),
             _ => None,
@@ -43,11 +76,51 @@ impl SaslToken {
 }
 
 impl<T: SessionStream> Session<T> {
+    pub async fn auth_mechanisms(&self) -> u64 {
+        let mechanisms: u64 = self
+            .server
+            .eval_if::<Mechanism, _>(
+                &self.server.core.smtp.session.auth.mechanisms,
+                self,
+                self.data.session_id,
+            )
+            .await
+            .unwrap_or_default()
+            .into();
+
+        // Channel binding requires a TLS session
+        let mechanisms = if mechanisms & AUTH_SCRAM_SHA_256_PLUS != 0
+            && !self.stream.channel_bindings(&self.server).is_available()
+        {
+            mechanisms & !AUTH_SCRAM_SHA_256_PLUS
+        } else {
+            mechanisms
+        };
+
+        // EXTERNAL is offered whenever the listener maps client certificates
+        if self.instance.acceptor.client_cert_mapping().is_some()
+            && self.stream.peer_certificate().is_some()
+        {
+            mechanisms | AUTH_EXTERNAL
+        } else {
+            mechanisms & !AUTH_EXTERNAL
+        }
+    }
+
     pub async fn handle_sasl_response(
         &mut self,
         token: &mut SaslToken,
         response: &[u8],
     ) -> Result<bool, ()> {
+        if matches!(
+            token.mechanism,
+            AUTH_SCRAM_SHA_256 | AUTH_SCRAM_SHA_256_PLUS
+        ) {
+            return self.handle_scram_response(token, response).await;
+        } else if token.mechanism == AUTH_EXTERNAL {
+            return self.handle_external_response(token, response).await;
+        }
+
         if response.is_empty() {
             match (token.mechanism, &token.credentials) {
                 (AUTH_PLAIN | AUTH_XOAUTH2 | AUTH_OAUTHBEARER, _) => {
@@ -107,6 +180,112 @@ impl<T: SessionStream> Session<T> {
         self.auth_error(b"500 5.5.6 Invalid challenge.\r\n").await
     }
 
+    async fn handle_scram_response(
+        &mut self,
+        token: &mut SaslToken,
+        response: &[u8],
+    ) -> Result<bool, ()> {
+        let response = if !response.is_empty() && response != b"=" {
+            match base64_decode(response) {
+                Some(response) => Some(response),
+                None => return self.auth_error(b"500 5.5.6 Invalid challenge.\r\n").await,
+            }
+        } else {
+            None
+        };
+
+        match (token.scram.take(), response) {
+            (None, None) => {
+                self.write(b"334 \r\n").await?;
+                Ok(true)
+            }
+            (None, Some(client_first)) => {
+                let Some(client_first) = ScramClientFirst::parse(
+                    &client_first,
+                    token.mechanism == AUTH_SCRAM_SHA_256_PLUS,
+                    &self.stream.channel_bindings(&self.server),
+                ) else {
+                    return self.auth_error(b"500 5.5.6 Invalid challenge.\r\n").await;
+                };
+
+                match self.server.scram_begin(client_first).await {
+                    Ok(auth) => {
+                        self.write(format!("334 {}\r\n", auth.challenge()).as_bytes())
+                            .await?;
+                        token.scram = Some(ScramState::ClientFinal(Box::new(auth)));
+                        Ok(true)
+                    }
+                    Err(err) => self.authenticated(Err(err)).await,
+                }
+            }
+            (Some(ScramState::ClientFinal(auth)), Some(client_final)) => {
+                match self
+                    .server
+                    .scram_authenticate(
+                        &auth,
+                        &client_final,
+                        self.data.session_id,
+                        self.data.remote_ip,
+                    )
+                    .await
+                {
+                    Ok((access_token, server_final)) => {
+                        self.write(format!("334 {server_final}\r\n").as_bytes())
+                            .await?;
+                        token.scram = Some(ScramState::Success(access_token));
+                        Ok(true)
+                    }
+                    Err(err) => self.authenticated(Err(err)).await,
+                }
+            }
+            (Some(ScramState::Success(access_token)), None) => {
+                self.authenticated(Ok(access_token)).await
+            }
+            _ => self.auth_error(b"500 5.5.6 Invalid challenge.\r\n").await,
+        }
+    }
+
+    async fn handle_external_response(
+        &mut self,
+        token: &mut SaslToken,
+        response: &[u8],
+    ) -> Result<bool, ()> {
+        if response.is_empty() && !token.challenged {
+            token.challenged = true;
+            self.write(b"334 \r\n").await?;
+            return Ok(true);
+        }
+
+        let authzid = if !response.is_empty() && response != b"=" {
+            match base64_decode(response).and_then(|authzid| String::from_utf8(authzid).ok()) {
+                Some(authzid) => Some(authzid),
+                None => return self.auth_error(b"500 5.5.6 Invalid challenge.\r\n").await,
+            }
+        } else {
+            None
+        };
+
+        let instance = self.instance.clone();
+        let (Some(mapping), Some(certificate)) = (
+            instance.acceptor.client_cert_mapping(),
+            self.stream.peer_certificate(),
+        ) else {
+            return self.auth_error(b"500 5.5.6 Invalid challenge.\r\n").await;
+        };
+        let result = self
+            .server
+            .authenticate_certificate(
+                mapping,
+                certificate,
+                authzid.as_deref(),
+                self.data.session_id,
+                self.data.remote_ip,
+            )
+            .await;
+
+        self.authenticated(result).await
+    }
+
     pub async fn authenticate(&mut self, credentials: Credentials) -> Result<bool, ()> {
         // Authenticate
         let result = self
@@ -116,10 +295,15 @@ impl<T: SessionStream> Session<T> {
                 self.data.session_id,
                 self.data.remote_ip,
             ))
-            .await
-            .and_then(|access_token| access_token.assert_has_permission(Permission::EmailSend));
+            .await;
+
+        self.authenticated(result).await
+    }
 
-        let result = match result {
+    async fn authenticated(&mut self, result: trc::Result<AccessToken>) -> Result<bool, ()> {
+        let result = match result
+            .and_then(|access_token| access_token.assert_has_permission(Permission::EmailSend))
+        {
             Ok(access_token) => self.server.account_info(access_token.account_id()).await,
             Err(err) => Err(err),
         };
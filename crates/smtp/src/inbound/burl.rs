@@ -0,0 +1,143 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::core::Session;
+use common::network::SessionStream;
+use email::message::urlauth::{ImapUrl, UrlAuthFetch, UrlRequester};
+use std::borrow::Cow;
+use trc::SmtpEvent;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn handle_burl(&mut self, uri: Cow<'_, str>, is_last: bool) -> Result<bool, ()> {
+        if !self.params.can_burl {
+            trc::event!(
+                Smtp(SmtpEvent::BurlDisabled),
+                SpanId = self.data.session_id,
+                Url = uri.as_ref().to_string(),
+            );
+
+            self.data.message = Vec::with_capacity(0);
+            self.write(b"502 5.5.1 BURL is disabled.\r\n").await?;
+            return Ok(false);
+        } else if !self.can_send_data().await? {
+            self.data.message = Vec::with_capacity(0);
+            return Ok(false);
+        }
+
+        let Some(account) = &self.data.authenticated_as else {
+            trc::event!(
+                Smtp(SmtpEvent::BurlFailed),
+                SpanId = self.data.session_id,
+                Url = uri.as_ref().to_string(),
+                Reason = "Not authenticated",
+            );
+
+            self.data.message = Vec::with_capacity(0);
+            self.write(b"530 5.7.0 Authentication required.\r\n")
+                .await?;
+            return Ok(false);
+        };
+        let Some(url) = ImapUrl::parse(uri.as_ref()) else {
+            trc::event!(
+                Smtp(SmtpEvent::BurlFailed),
+                SpanId = self.data.session_id,
+                Url = uri.as_ref().to_string(),
+                Reason = "Invalid IMAP URL",
+            );
+
+            self.data.message = Vec::with_capacity(0);
+            self.write(b"554 5.7.0 Invalid IMAP URL.\r\n").await?;
+            return Ok(false);
+        };
+
+        // Only URLs referencing this server can be resolved locally
+        if !url.host.eq_ignore_ascii_case(&self.hostname)
+            && !url
+                .host
+                .eq_ignore_ascii_case(&self.server.core.network.server_name)
+        {
+            trc::event!(
+                Smtp(SmtpEvent::BurlFailed),
+                SpanId = self.data.session_id,
+                Url = uri.as_ref().to_string(),
+                Reason = "IMAP URL references a foreign server",
+            );
+
+            self.data.message = Vec::with_capacity(0);
+            self.write(b"554 5.7.0 IMAP URL does not reference this server.\r\n")
+                .await?;
+            return Ok(false);
+        }
+
+        // Fetch the referenced message from the store
+        let requester = UrlRequester {
+            account_id: account.account_id,
+            name: account.name(),
+            is_submit: true,
+        };
+        match self.server.fetch_imap_url(&url, requester).await {
+            Ok(Ok(contents)) => {
+                if contents.len().saturating_add(self.data.message.len())
+                    < self.params.max_message_size
+                {
+                    trc::event!(
+                        Smtp(SmtpEvent::Burl),
+                        SpanId = self.data.session_id,
+                        Url = uri.as_ref().to_string(),
+                        Size = contents.len(),
+                    );
+
+                    if self.data.message.is_empty() {
+                        self.data.message = contents;
+                    } else {
+                        self.data.message.extend_from_slice(&contents);
+                    }
+
+                    if !is_last {
+                        self.write(b"250 2.5.0 URL content accepted.\r\n").await?;
+                    }
+
+                    Ok(true)
+                } else {
+                    trc::event!(
+                        Smtp(SmtpEvent::MessageTooLarge),
+                        SpanId = self.data.session_id,
+                    );
+
+                    self.data.message = Vec::with_capacity(0);
+                    self.write(b"552 5.3.4 Message too big for system.\r\n")
+                        .await?;
+                    Ok(false)
+                }
+            }
+            Ok(Err(err)) => {
+                trc::event!(
+                    Smtp(SmtpEvent::BurlFailed),
+                    SpanId = self.data.session_id,
+                    Url = uri.as_ref().to_string(),
+                    Reason = format!("{err:?}"),
+                );
+
+                self.data.message = Vec::with_capacity(0);
+                self.write(b"554 5.6.6 IMAP URL resolution failed.\r\n")
+                    .await?;
+                Ok(false)
+            }
+            Err(err) => {
+                trc::error!(
+                    err.span_id(self.data.session_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to resolve IMAP URL.")
+                );
+
+                self.data.message = Vec::with_capacity(0);
+                self.write(b"451 4.4.1 Unable to resolve IMAP URL at this time.\r\n")
+                    .await?;
+                Ok(false)
+            }
+        }
+    }
+}
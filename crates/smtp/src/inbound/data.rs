@@ -7,10 +7,10 @@
 use super::AuthResult;
 use crate::{
     core::{Session, SessionAddress, State},
-    inbound::{dkim::DkimSign, milter::Modification},
+    inbound::{antivirus::AntivirusVerdict, dkim::DkimSign, milter::Modification},
     queue::{
-        self, Message, MessageSource, MessageWrapper, QueueEnvelope, RCPT_SPAM_PAYLOAD,
-        quota::HasQueueQuota, spool::QueueParams,
+        self, Message, MessageSource, MessageWrapper, QueueEnvelope, RCPT_QUARANTINE,
+        RCPT_SPAM_PAYLOAD, quota::HasQueueQuota, spool::QueueParams,
     },
     reporting::analysis::AnalyzeReport,
     scripts::ScriptResult,
@@ -27,6 +27,7 @@ use common::{
     network::SessionStream,
     scripts::ScriptModification,
 };
+use email::message::quarantine::QUARANTINE_HEADER;
 use mail_auth::{
     AuthenticatedMessage, AuthenticationResults, Dkim2Result, DkimResult, DmarcResult, ReceivedSpf,
     SpfOutput, SpfResult,
@@ -196,7 +197,12 @@ impl<T: SessionStream> Session<T> {
             .eval_if(&ac.arc.verify, self, self.data.session_id)
             .await
             .unwrap_or(VerifyStrategy::Relaxed);
-        let arc_output = if arc.verify() {
+        let arc_sealer = self
+            .server
+            .eval_signers(&ac.arc.seal, self, self.data.session_id)
+            .await
+            .filter(|signers| signers.arc.is_some());
+        let arc_output = if arc.verify() || arc_sealer.is_some() {
             let time = Instant::now();
             let arc_output = self
                 .server
@@ -504,6 +510,26 @@ impl<T: SessionStream> Session<T> {
             auth_results.write_header(&mut headers);
         }
 
+        // Add ARC set
+        if let (Some(signers), Some(arc_output)) = (&arc_sealer, &arc_output)
+            && let Some(sealer) = &signers.arc
+            && arc_output.can_be_sealed()
+        {
+            match sealer.seal(&auth_message, &auth_results, arc_output) {
+                Ok(set) => {
+                    set.write_header(&mut headers);
+                }
+                Err(err) => {
+                    trc::error!(
+                        trc::Error::from(err)
+                            .span_id(self.data.session_id)
+                            .details("Failed to ARC seal message")
+                            .caused_by(trc::location!())
+                    );
+                }
+            }
+        }
+
         // Add Received-SPF header
         if let Some(spf_output) = &self.data.spf_mail_from
             && self
@@ -522,6 +548,30 @@ impl<T: SessionStream> Session<T> {
             .write_header(&mut headers);
         }
 
+        // Run antivirus scan
+        let mut quarantine = None;
+        let virus = match self
+            .run_antivirus(&raw_message, &parsed_message, message_id)
+            .await
+        {
+            Ok(AntivirusVerdict::Clean) => None,
+            Ok(AntivirusVerdict::Tag(virus)) => Some(virus),
+            Ok(AntivirusVerdict::Quarantine(virus)) => {
+                quarantine = Some(format!("Virus found: {virus}"));
+                Some(virus)
+            }
+            Err(response) => {
+                return response.into_bytes();
+            }
+        };
+
+        // Flag infected messages, the spam filter may be disabled for this session
+        if let Some(virus) = &virus {
+            headers.extend_from_slice(b"X-Virus-Found: ");
+            headers.extend_from_slice(virus.replace(['\r', '\n'], " ").as_bytes());
+            headers.extend_from_slice(b"\r\n");
+        }
+
         // Run SPAM filter
         let mut train_spam = None;
         let mut spam_status = None;
@@ -540,6 +590,7 @@ impl<T: SessionStream> Session<T> {
                     (&arc_output).into(),
                     dmarc_result.as_ref(),
                     dmarc_policy.as_ref(),
+                    virus.as_deref(),
                 )
                 .await
             {
@@ -567,6 +618,18 @@ impl<T: SessionStream> Session<T> {
                         }
                     }
                 }
+                SpamFilterAction::Discard if self.server.core.email.quarantine.quarantine_spam => {
+                    trc::event!(
+                        Spam(SpamEvent::Classify),
+                        SpanId = self.data.session_id,
+                        QueueId = message_id,
+                        Result = "quarantine",
+                        Reason = "Message quarantined due to excessive spam score.",
+                    );
+
+                    spam_status = Some(SpamStatus::Spam);
+                    quarantine.get_or_insert_with(|| "Excessive spam score".to_string());
+                }
                 SpamFilterAction::Discard => {
                     trc::event!(
                         Spam(SpamEvent::Classify),
@@ -628,6 +691,22 @@ impl<T: SessionStream> Session<T> {
             }
         };
 
+        // Milters and MTA hooks add their own quarantine header
+        let is_quarantined = if modifications
+            .iter()
+            .any(|m| matches!(m, Modification::Quarantine { .. }))
+        {
+            true
+        } else if let Some(reason) = &quarantine {
+            headers.extend_from_slice(QUARANTINE_HEADER.as_bytes());
+            headers.extend_from_slice(b": ");
+            headers.extend_from_slice(reason.replace(['\r', '\n'], " ").as_bytes());
+            headers.extend_from_slice(b"\r\n");
+            true
+        } else {
+            false
+        };
+
         // Apply modifications
         let mut edited_message = if !modifications.is_empty() {
             self.data
@@ -735,6 +814,13 @@ impl<T: SessionStream> Session<T> {
             }
         }
 
+        // Hold the message in the quarantine of local recipients
+        if is_quarantined {
+            for rcpt in self.data.rcpt_to.iter_mut() {
+                rcpt.flags |= RCPT_QUARANTINE;
+            }
+        }
+
         // Build message
         let mail_from = self.data.mail_from.clone().unwrap();
         let rcpt_to = std::mem::take(&mut self.data.rcpt_to);
@@ -5,10 +5,7 @@
  */
 
 use crate::{core::Session, scripts::ScriptResult};
-use common::{
-    config::smtp::session::{Mechanism, Stage},
-    network::SessionStream,
-};
+use common::{config::smtp::session::Stage, network::SessionStream};
 use mail_auth::{
     SpfResult,
     spf::verify::{HasValidLabels, SpfParameters},
@@ -149,7 +146,6 @@ impl<T: SessionStream> Session<T> {
             response.capabilities |= EXT_START_TLS;
         }
         let ec = &self.server.core.smtp.session.extensions;
-        let ac = &self.server.core.smtp.session.auth;
         let dc = &self.server.core.smtp.session.data;
 
         // Pipelining
@@ -192,6 +188,36 @@ impl<T: SessionStream> Session<T> {
             response.capabilities |= EXT_VRFY;
         }
 
+        // Remote Queue Processing
+        if self
+            .server
+            .eval_if(&ec.etrn, self, self.data.session_id)
+            .await
+            .unwrap_or(false)
+        {
+            response.capabilities |= EXT_ETRN;
+        }
+
+        // On-Demand Mail Relay
+        if self
+            .server
+            .eval_if(&ec.atrn, self, self.data.session_id)
+            .await
+            .unwrap_or(false)
+        {
+            response.capabilities |= EXT_ATRN;
+        }
+
+        // Message Submission by Reference
+        if self
+            .server
+            .eval_if(&ec.burl, self, self.data.session_id)
+            .await
+            .unwrap_or(false)
+        {
+            response.capabilities |= EXT_BURL;
+        }
+
         // Require TLS
         if self
             .server
@@ -214,12 +240,7 @@ impl<T: SessionStream> Session<T> {
 
         // Authentication
         if !self.is_authenticated() {
-            response.auth_mechanisms = self
-                .server
-                .eval_if::<Mechanism, _>(&ac.mechanisms, self, self.data.session_id)
-                .await
-                .unwrap_or_default()
-                .into();
+            response.auth_mechanisms = self.auth_mechanisms().await;
             if response.auth_mechanisms != 0 {
                 response.capabilities |= EXT_AUTH;
             }
@@ -0,0 +1,375 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    core::Session,
+    outbound::{DeliveryResult, client::SmtpClient, error::AssertReply, session::SessionParams},
+    queue::{
+        Metadata, Status,
+        dsn::SendDsn,
+        manager::{QueueFlush, matches_domain},
+        spool::SmtpSpool,
+    },
+};
+use ahash::AHashSet;
+use common::{config::smtp::queue::QueueName, network::SessionStream};
+use std::{borrow::Cow, time::Instant};
+use trc::{DeliveryEvent, SmtpEvent};
+use utils::DomainPart;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn handle_etrn(&mut self, node: Cow<'_, str>) -> Result<(), ()> {
+        if !self.params.can_etrn {
+            trc::event!(
+                Smtp(SmtpEvent::EtrnDisabled),
+                SpanId = self.data.session_id,
+                Domain = node.as_ref().to_string(),
+            );
+
+            return self.write(b"502 5.5.1 ETRN is disabled.\r\n").await;
+        } else if self.data.helo_domain.is_empty() {
+            trc::event!(
+                Smtp(SmtpEvent::DidNotSayEhlo),
+                SpanId = self.data.session_id,
+            );
+
+            return self
+                .write(b"503 5.5.1 Polite people say EHLO first.\r\n")
+                .await;
+        }
+
+        // Queue names are not supported, only domains and '@' prefixed subdomain wildcards
+        let node = node.trim().to_lowercase();
+        let domain = node.strip_prefix('@').unwrap_or(node.as_str());
+        if node.starts_with('#') || domain.is_empty() || !domain.contains('.') {
+            trc::event!(
+                Smtp(SmtpEvent::EtrnNotAllowed),
+                SpanId = self.data.session_id,
+                Domain = node.clone(),
+            );
+
+            return self
+                .write(format!("459 4.7.1 Node {node} not allowed.\r\n").as_bytes())
+                .await;
+        }
+
+        match self
+            .server
+            .flush_queue(std::slice::from_ref(&node), self.data.session_id)
+            .await
+        {
+            Ok(total) => {
+                trc::event!(
+                    Smtp(SmtpEvent::Etrn),
+                    SpanId = self.data.session_id,
+                    Domain = node.clone(),
+                    Total = total,
+                );
+
+                if total > 0 {
+                    self.write(
+                        format!("253 2.0.0 {total} pending messages for node {node} started.\r\n")
+                            .as_bytes(),
+                    )
+                    .await
+                } else {
+                    self.write(
+                        format!("251 2.0.0 No messages waiting for node {node}.\r\n").as_bytes(),
+                    )
+                    .await
+                }
+            }
+            Err(err) => {
+                trc::error!(
+                    err.span_id(self.data.session_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to flush queue.")
+                );
+
+                self.write(
+                    format!("458 4.3.0 Unable to queue messages for node {node}.\r\n").as_bytes(),
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn handle_atrn(&mut self, domains: Vec<Cow<'_, str>>) -> Result<(), ()> {
+        let mut domains = domains
+            .into_iter()
+            .map(|domain| domain.trim().to_lowercase())
+            .collect::<Vec<_>>();
+
+        if !self.params.can_atrn {
+            trc::event!(
+                Smtp(SmtpEvent::AtrnDisabled),
+                SpanId = self.data.session_id,
+                Domain = domains,
+            );
+
+            return self.write(b"502 5.5.1 ATRN is disabled.\r\n").await;
+        } else if !self.is_authenticated() {
+            trc::event!(
+                Smtp(SmtpEvent::AtrnNotAllowed),
+                SpanId = self.data.session_id,
+                Domain = domains,
+            );
+
+            return self.write(b"530 5.7.0 Authentication required.\r\n").await;
+        } else if self.data.mail_from.is_some() {
+            return self
+                .write(b"503 5.5.1 ATRN not allowed during a mail transaction.\r\n")
+                .await;
+        }
+
+        // Only domains the authenticated account has addresses in can be requested
+        let allowed_domains = self
+            .authenticated_emails()
+            .iter()
+            .map(|email| email.domain_part().to_lowercase())
+            .collect::<AHashSet<_>>();
+        if let Some(domain) = domains
+            .iter()
+            .find(|domain| !allowed_domains.contains(domain.as_str()))
+        {
+            trc::event!(
+                Smtp(SmtpEvent::AtrnNotAllowed),
+                SpanId = self.data.session_id,
+                AccountName = self.authenticated_as().unwrap_or_default().to_string(),
+                Domain = domain.clone(),
+            );
+
+            return self.write(b"450 4.7.1 ATRN request refused.\r\n").await;
+        } else if domains.is_empty() {
+            // Without arguments, turn around all the domains the client is authorized for
+            domains = allowed_domains.into_iter().collect();
+            domains.sort_unstable();
+        }
+
+        let queue_ids = match self.server.queued_messages_for(&domains).await {
+            Ok(queue_ids) if !queue_ids.is_empty() => queue_ids,
+            Ok(_) => {
+                trc::event!(
+                    Smtp(SmtpEvent::Atrn),
+                    SpanId = self.data.session_id,
+                    Domain = domains,
+                    Total = 0,
+                );
+
+                return self.write(b"453 4.7.0 You have no mail.\r\n").await;
+            }
+            Err(err) => {
+                trc::error!(
+                    err.span_id(self.data.session_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to fetch queued messages.")
+                );
+
+                return self
+                    .write(b"451 4.3.0 Unable to process ATRN request now.\r\n")
+                    .await;
+            }
+        };
+
+        trc::event!(
+            Smtp(SmtpEvent::Atrn),
+            SpanId = self.data.session_id,
+            Domain = domains.clone(),
+            Total = queue_ids.len(),
+        );
+
+        self.write(b"250 2.0.0 OK now reversing the connection.\r\n")
+            .await?;
+
+        // Turn the connection around and act as the client
+        let server = self.server.clone();
+        let session_id = self.data.session_id;
+        let conn_strategy = server.get_connection_or_default("default", session_id);
+        let hostname = self.data.helo_domain.clone();
+        let params = SessionParams {
+            server: &server,
+            hostname: &hostname,
+            credentials: None,
+            capabilities: None,
+            is_smtp: true,
+            local_hostname: &self.hostname,
+            conn_strategy,
+            session_id,
+        };
+        let mut smtp_client = SmtpClient {
+            stream: &mut self.stream,
+            timeout: conn_strategy.timeout_greeting,
+            session_id,
+        };
+
+        if let Err(status) = smtp_client.read_greeting(&hostname).await {
+            trc::event!(
+                Delivery(DeliveryEvent::GreetingFailed),
+                SpanId = session_id,
+                Hostname = hostname,
+                Details = status.to_string(),
+            );
+            return Err(());
+        }
+        let time = Instant::now();
+        let capabilities = match smtp_client.say_helo(&params).await {
+            Ok(capabilities) => {
+                trc::event!(
+                    Delivery(DeliveryEvent::Ehlo),
+                    SpanId = session_id,
+                    Hostname = hostname.clone(),
+                    Details = capabilities.capabilities(),
+                    Elapsed = time.elapsed(),
+                );
+
+                capabilities
+            }
+            Err(status) => {
+                trc::event!(
+                    Delivery(DeliveryEvent::EhloRejected),
+                    SpanId = session_id,
+                    Hostname = hostname,
+                    Details = status.to_string(),
+                    Elapsed = time.elapsed(),
+                );
+                smtp_client.quit().await;
+                return Err(());
+            }
+        };
+
+        let mut has_transaction = false;
+        'outer: for (queue_id, queue_names) in queue_ids {
+            // Lock all the virtual queues this message has pending deliveries in
+            // before reading it, so the queue manager cannot deliver it concurrently
+            let mut locked = Vec::with_capacity(queue_names.len());
+            for queue_name in queue_names {
+                if server.try_lock_event(queue_id, queue_name).await {
+                    locked.push(queue_name);
+                } else {
+                    for queue_name in locked {
+                        server.unlock_event(queue_id, queue_name).await;
+                    }
+                    continue 'outer;
+                }
+            }
+
+            // Skip messages that were delivered or rescheduled to other queues meanwhile
+            let Some(mut message) = server
+                .read_message(queue_id, QueueName::default())
+                .await
+                .filter(|message| {
+                    message.message.recipients.iter().all(|rcpt| {
+                        !matches!(rcpt.status, Status::Scheduled | Status::TemporaryFailure(_))
+                            || locked.contains(&rcpt.queue)
+                    })
+                })
+            else {
+                for queue_name in locked {
+                    server.unlock_event(queue_id, queue_name).await;
+                }
+                continue;
+            };
+            message.span_id = session_id;
+            let prev_events = message.message.next_events();
+
+            // Group recipients by their custom headers
+            let mut rcpt_groups: Vec<(Option<&[u8]>, Vec<usize>)> = Vec::new();
+            for (rcpt_idx, rcpt) in message.message.recipients.iter().enumerate() {
+                if matches!(rcpt.status, Status::Scheduled | Status::TemporaryFailure(_))
+                    && matches_domain(&domains, rcpt.domain_part())
+                {
+                    let rcpt_headers = message
+                        .message
+                        .metadata
+                        .iter()
+                        .filter_map(|metadata| match metadata {
+                            Metadata::Headers { value, id }
+                                if *id == rcpt_idx as u64 || *id == u64::MAX =>
+                            {
+                                Some((*id == rcpt_idx as u64, value.as_ref()))
+                            }
+                            _ => None,
+                        })
+                        .max_by_key(|(is_rcpt, _)| *is_rcpt)
+                        .map(|(_, value)| value);
+                    if let Some((_, rcpt_idxs)) = rcpt_groups
+                        .iter_mut()
+                        .find(|(headers, _)| *headers == rcpt_headers)
+                    {
+                        rcpt_idxs.push(rcpt_idx);
+                    } else {
+                        rcpt_groups.push((rcpt_headers, vec![rcpt_idx]));
+                    }
+                }
+            }
+
+            // Deliver message
+            let mut delivery_results = Vec::new();
+            let mut is_aborted = false;
+            for (rcpt_headers, rcpt_idxs) in rcpt_groups {
+                if has_transaction
+                    && let Err(err) = smtp_client
+                        .cmd(b"RSET\r\n")
+                        .await
+                        .and_then(|r| r.assert_positive_completion())
+                {
+                    delivery_results.push(DeliveryResult::domain(
+                        Status::from_smtp_error(&hostname, "RSET", err),
+                        rcpt_idxs,
+                    ));
+                    is_aborted = true;
+                    break;
+                }
+                has_transaction = true;
+
+                if !message
+                    .send_transaction(
+                        &mut smtp_client,
+                        &capabilities,
+                        rcpt_idxs,
+                        rcpt_headers,
+                        &mut delivery_results,
+                        &params,
+                    )
+                    .await
+                {
+                    is_aborted = true;
+                    break;
+                }
+            }
+
+            // Update message
+            message
+                .apply_delivery_results(delivery_results, &server)
+                .await;
+            server.send_dsn(&mut message).await;
+            let modified_rcpts = (0..message.message.recipients.len()).collect();
+            if message.message.next_event(None).is_some() {
+                message
+                    .save_registry_changes(&server, prev_events, modified_rcpts)
+                    .await;
+            } else {
+                trc::event!(
+                    Delivery(DeliveryEvent::Completed),
+                    SpanId = session_id,
+                    QueueId = queue_id,
+                );
+
+                message.remove_registry(&server, prev_events).await;
+            }
+            for queue_name in locked {
+                server.unlock_event(queue_id, queue_name).await;
+            }
+
+            if is_aborted {
+                return Err(());
+            }
+        }
+
+        smtp_client.quit().await;
+        Err(())
+    }
+}
@@ -128,11 +128,14 @@ impl<T: SessionStream> Session<T> {
                         Action::Discard => FilterResponse::accept(),
                         Action::Reject => FilterResponse::reject(),
                         Action::Quarantine => {
-                            modifications.push(Modification::AddHeader {
-                                name: "X-Quarantine".into(),
-                                value: "true".into(),
+                            // Accept the message and hold it in the recipients' quarantine
+                            modifications.push(Modification::Quarantine {
+                                reason: response
+                                    .response
+                                    .and_then(|response| response.message)
+                                    .unwrap_or_else(|| mta_hook.id.to_string()),
                             });
-                            FilterResponse::accept()
+                            continue;
                         }
                     };
 
@@ -7,10 +7,13 @@
 use mail_auth::{DkimResult, DmarcResult, IprevResult, SpfResult, dmarc::Policy};
 use std::borrow::Cow;
 
+pub mod antivirus;
 pub mod auth;
+pub mod burl;
 pub mod data;
 pub mod dkim;
 pub mod ehlo;
+pub mod etrn;
 pub mod hooks;
 pub mod mail;
 pub mod milter;
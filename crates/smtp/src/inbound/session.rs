@@ -5,7 +5,7 @@
  */
 
 use common::{
-    config::{server::ServerProtocol, smtp::session::Mechanism},
+    config::server::ServerProtocol,
     expr::{self, functions::ResolveVariable, *},
     network::SessionStream,
 };
@@ -86,16 +86,7 @@ impl<T: SessionStream> Session<T> {
                                 mechanism,
                                 initial_response,
                             } => {
-                                let auth: u64 = self
-                                    .server
-                                    .eval_if::<Mechanism, _>(
-                                        &self.server.core.smtp.session.auth.mechanisms,
-                                        self,
-                                        self.data.session_id,
-                                    )
-                                    .await
-                                    .unwrap_or_default()
-                                    .into();
+                                let auth = self.auth_mechanisms().await;
                                 if auth == 0 {
                                     trc::event!(
                                         Smtp(SmtpEvent::AuthNotAllowed),
@@ -223,17 +214,31 @@ impl<T: SessionStream> Session<T> {
                                         .await?;
                                 }
                             }
-                            cmd @ (Request::Etrn { .. }
-                            | Request::Atrn { .. }
-                            | Request::Burl { .. }) => {
-                                trc::event!(
-                                    Smtp(SmtpEvent::CommandNotImplemented),
-                                    SpanId = self.data.session_id,
-                                    Details = format!("{cmd:?}"),
-                                );
-
-                                self.write(b"502 5.5.1 Command not implemented.\r\n")
-                                    .await?;
+                            Request::Etrn { name } => {
+                                self.handle_etrn(name).await?;
+                            }
+                            Request::Atrn { domains } => {
+                                self.handle_atrn(domains).await?;
+                            }
+                            Request::Burl { uri, is_last } => {
+                                if self.handle_burl(uri, is_last).await? && is_last {
+                                    let message = self.queue_message().await;
+                                    if !message.is_empty() {
+                                        let num_responses =
+                                            if self.instance.protocol == ServerProtocol::Smtp {
+                                                1
+                                            } else {
+                                                self.data.rcpt_oks
+                                            };
+                                        for _ in 0..num_responses {
+                                            self.write(message.as_ref()).await?;
+                                        }
+                                        self.reset();
+                                    } else {
+                                        // Disconnect requested
+                                        return Err(());
+                                    }
+                                }
                             }
                         },
                         Err(err) => match err {
@@ -25,16 +25,19 @@ impl<T: SessionStream> Session<T> {
         arc_result: Option<&'x ArcOutput<'x>>,
         dmarc_result: Option<&'x DmarcResult>,
         dmarc_policy: Option<&'x Policy>,
+        virus: Option<&'x str>,
     ) -> SpamFilterAction<SpamFilterScore> {
         let server = &self.server;
-        let mut ctx = server.spam_filter_init(self.build_spam_input(
+        let mut input = self.build_spam_input(
             message,
             dkim_result,
             dkim2_result,
             arc_result,
             dmarc_result,
             dmarc_policy,
-        ));
+        );
+        input.virus = virus;
+        let mut ctx = server.spam_filter_init(input);
 
         if !self.is_authenticated() {
             // Spam classification
@@ -70,6 +73,7 @@ impl<T: SessionStream> Session<T> {
             authenticated_as: self.data.authenticated_as.as_ref().map(|a| a.name()),
             asn: self.data.asn_geo_data.asn.as_ref().map(|a| a.id),
             country: self.data.asn_geo_data.country.as_ref().map(|c| c.as_str()),
+            virus: None,
             is_tls: self.stream.is_tls(),
             env_from: self
                 .data
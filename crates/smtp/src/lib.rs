@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 pub mod core;
 pub mod inbound;
+pub mod lists;
 pub mod outbound;
 pub mod queue;
 pub mod reporting;
@@ -0,0 +1,108 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{LIST_BOUNCE_WINDOW, ListMessage, ManagedList};
+use common::{KV_LIST_BOUNCE, Server};
+use email::message::delivery::LocalDeliveryStatus;
+use mail_parser::{MessageParser, MimeHeaders};
+use store::dispatch::lookup::KeyValue;
+use trc::AddContext;
+
+impl ManagedList {
+    pub async fn process_bounce(
+        &mut self,
+        server: &Server,
+        token: &str,
+        message: &ListMessage<'_>,
+    ) -> trc::Result<LocalDeliveryStatus> {
+        let threshold = self.list.bounce_threshold as i64;
+        if threshold == 0 {
+            return Ok(LocalDeliveryStatus::Success);
+        }
+
+        // The subscriber is identified by the return path the copy was sent
+        // with, never by the contents of the notification
+        let Some(address) = self
+            .list
+            .recipients
+            .iter()
+            .find(|rcpt| self.bounce_token(server, rcpt) == token)
+            .map(|rcpt| rcpt.to_lowercase())
+        else {
+            return Ok(LocalDeliveryStatus::Success);
+        };
+        if !is_permanent_failure(message.raw_message) {
+            return Ok(LocalDeliveryStatus::Success);
+        }
+
+        let mut key = KeyValue::<()>::build_key(KV_LIST_BOUNCE, self.id.to_be_bytes());
+        key.extend_from_slice(address.as_bytes());
+        let bounces = server
+            .in_memory_store()
+            .counter_incr(
+                KeyValue::new(key.clone(), 1).expires(LIST_BOUNCE_WINDOW),
+                true,
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        if bounces >= threshold {
+            let suspended = self
+                .update(server, |list| {
+                    let len = list.recipients.len();
+                    list.recipients
+                        .inner_mut()
+                        .retain(|rcpt| !rcpt.eq_ignore_ascii_case(&address));
+                    if len != list.recipients.len() {
+                        list.suspended_recipients.push(address.clone());
+                        true
+                    } else {
+                        false
+                    }
+                })
+                .await?;
+            server
+                .in_memory_store()
+                .counter_delete(key)
+                .await
+                .caused_by(trc::location!())?;
+
+            if suspended {
+                trc::event!(
+                    MessageIngest(trc::MessageIngestEvent::ListSuspended),
+                    SpanId = message.session_id,
+                    Id = self.address(),
+                    To = address,
+                    Total = bounces,
+                );
+            }
+        }
+
+        Ok(LocalDeliveryStatus::Success)
+    }
+}
+
+/// Whether a delivery status notification (RFC 3464) reports a permanent
+/// delivery failure.
+fn is_permanent_failure(raw_message: &[u8]) -> bool {
+    let Some(message) = MessageParser::new().parse(raw_message) else {
+        return false;
+    };
+
+    message.parts.iter().any(|part| {
+        part.is_content_type("message", "delivery-status")
+            && raw_message
+                .get(part.offset_body as usize..part.offset_end as usize)
+                .is_some_and(|status| {
+                    String::from_utf8_lossy(status).lines().any(|line| {
+                        line.split_once(':').is_some_and(|(name, value)| {
+                            name.eq_ignore_ascii_case("Action")
+                                && value.trim().eq_ignore_ascii_case("failed")
+                        })
+                    })
+                })
+    })
+}
@@ -0,0 +1,393 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    HeldMessage, LIST_CONFIRM_EXPIRY, ListMessage, ManagedList, PendingSubscription,
+    post::{new_token, sender_address},
+};
+use common::{KV_LIST_CONFIRM, KV_LIST_MODERATION, Server, network::list::ListCommand};
+use email::message::delivery::LocalDeliveryStatus;
+use mail_builder::{
+    MessageBuilder,
+    headers::{HeaderType, content_type::ContentType},
+    mime::{BodyPart, MimePart, make_boundary},
+};
+use mail_parser::MessageParser;
+use registry::schema::enums::MailingListSubscriptionPolicy;
+use store::{
+    Serialize,
+    dispatch::lookup::KeyValue,
+    write::{AlignedBytes, Archive, Archiver},
+};
+use trc::AddContext;
+
+impl ManagedList {
+    pub async fn process_command(
+        &mut self,
+        server: &Server,
+        command: ListCommand,
+        message: &ListMessage<'_>,
+    ) -> trc::Result<LocalDeliveryStatus> {
+        let Some(parsed) = MessageParser::new().parse(message.raw_message) else {
+            return Ok(LocalDeliveryStatus::PermanentFailure {
+                code: [5, 6, 0],
+                reason: "Failed to parse message.".into(),
+            });
+        };
+
+        // Never reply to bounces or automatic responses
+        if message.return_path.is_empty()
+            || parsed.root_part().headers().iter().any(|header| {
+                header.name.as_str().eq_ignore_ascii_case("Auto-Submitted")
+                    && header
+                        .value()
+                        .as_text()
+                        .is_some_and(|value| !value.trim().eq_ignore_ascii_case("no"))
+            })
+        {
+            return Ok(LocalDeliveryStatus::Success);
+        }
+
+        let sender = sender_address(&parsed, message.return_path);
+        let session_id = message.session_id;
+        match command {
+            ListCommand::Subscribe => {
+                if self.list.subscription_policy == MailingListSubscriptionPolicy::Closed {
+                    self.notify(
+                        server,
+                        &sender,
+                        "Subscription request denied",
+                        format!(
+                            "The {} mailing list does not accept subscription requests.\r\n",
+                            self.address()
+                        ),
+                        None,
+                        session_id,
+                    )
+                    .await;
+                } else if self.is_subscriber(&sender) {
+                    self.notify(
+                        server,
+                        &sender,
+                        "Already subscribed",
+                        format!(
+                            "The address {sender} is already subscribed to the {} mailing list.\r\n",
+                            self.address()
+                        ),
+                        None,
+                        session_id,
+                    )
+                    .await;
+                } else if self.list.subscription_policy == MailingListSubscriptionPolicy::Open {
+                    self.subscribe(server, &sender, session_id).await?;
+                    self.notify_welcome(server, &sender, session_id).await;
+                } else {
+                    self.request_confirmation(server, &sender, true, session_id)
+                        .await?;
+                }
+            }
+            ListCommand::Unsubscribe => {
+                if self.is_subscriber(&sender) {
+                    self.request_confirmation(server, &sender, false, session_id)
+                        .await?;
+                } else {
+                    self.notify(
+                        server,
+                        &sender,
+                        "Not subscribed",
+                        format!(
+                            "The address {sender} is not subscribed to the {} mailing list.\r\n",
+                            self.address()
+                        ),
+                        None,
+                        session_id,
+                    )
+                    .await;
+                }
+            }
+            ListCommand::Confirm(token) => {
+                let key = KeyValue::<()>::build_key(KV_LIST_CONFIRM, token.as_bytes());
+                let pending = server
+                    .in_memory_store()
+                    .key_get::<Archive<AlignedBytes>>(key.clone())
+                    .await
+                    .caused_by(trc::location!())?
+                    .map(|archive| archive.deserialize::<PendingSubscription>())
+                    .transpose()
+                    .caused_by(trc::location!())?
+                    .filter(|pending| pending.list_id == self.id);
+
+                if let Some(pending) = pending {
+                    server
+                        .in_memory_store()
+                        .key_delete(key)
+                        .await
+                        .caused_by(trc::location!())?;
+
+                    if pending.subscribe {
+                        if self.subscribe(server, &pending.address, session_id).await? {
+                            self.notify_welcome(server, &pending.address, session_id)
+                                .await;
+                        }
+                    } else if self
+                        .unsubscribe(server, &pending.address, session_id)
+                        .await?
+                    {
+                        self.notify(
+                            server,
+                            &pending.address,
+                            "Unsubscribed",
+                            format!(
+                                "The address {} has been removed from the {} mailing list.\r\n",
+                                pending.address,
+                                self.address()
+                            ),
+                            None,
+                            session_id,
+                        )
+                        .await;
+                    }
+                } else {
+                    self.notify(
+                        server,
+                        &sender,
+                        "Invalid confirmation",
+                        "The confirmation code is invalid or has expired.\r\n".to_string(),
+                        None,
+                        session_id,
+                    )
+                    .await;
+                }
+            }
+            ListCommand::Approve(ref held_id) | ListCommand::Reject(ref held_id) => {
+                // Moderation rights are checked against the verified envelope sender
+                if !self.is_moderator(&message.return_path.to_lowercase()) {
+                    return Ok(LocalDeliveryStatus::PermanentFailure {
+                        code: [5, 7, 1],
+                        reason: "You are not a moderator of this mailing list.".into(),
+                    });
+                }
+
+                let key = KeyValue::<()>::build_key(KV_LIST_MODERATION, held_id.as_bytes());
+                let held = server
+                    .in_memory_store()
+                    .key_get::<Archive<AlignedBytes>>(key.clone())
+                    .await
+                    .caused_by(trc::location!())?
+                    .map(|archive| archive.deserialize::<HeldMessage>())
+                    .transpose()
+                    .caused_by(trc::location!())?
+                    .filter(|held| held.list_id == self.id);
+                let Some(held) = held else {
+                    self.notify(
+                        server,
+                        &sender,
+                        "Message not found",
+                        "The held message was already processed or has expired.\r\n".to_string(),
+                        None,
+                        session_id,
+                    )
+                    .await;
+                    return Ok(LocalDeliveryStatus::Success);
+                };
+                server
+                    .in_memory_store()
+                    .key_delete(key)
+                    .await
+                    .caused_by(trc::location!())?;
+
+                if matches!(command, ListCommand::Approve(_)) {
+                    if let Some(held_message) = MessageParser::new().parse(&held.message) {
+                        return self
+                            .distribute(server, &held_message, &held.message, session_id)
+                            .await;
+                    }
+                } else {
+                    trc::event!(
+                        MessageIngest(trc::MessageIngestEvent::ListRejected),
+                        SpanId = session_id,
+                        Id = self.address(),
+                        From = held.sender.clone(),
+                        Reason = "Rejected by moderator",
+                        Details = sender,
+                    );
+
+                    self.notify(
+                        server,
+                        &held.sender,
+                        "Message rejected",
+                        format!(
+                            "Your message to the {} mailing list was rejected by a moderator.\r\n",
+                            self.address()
+                        ),
+                        None,
+                        session_id,
+                    )
+                    .await;
+                }
+            }
+            ListCommand::Help => {
+                self.notify(server, &sender, "Help", self.help_text(), None, session_id)
+                    .await;
+            }
+            ListCommand::Bounces(_) => {}
+        }
+
+        Ok(LocalDeliveryStatus::Success)
+    }
+
+    async fn request_confirmation(
+        &self,
+        server: &Server,
+        address: &str,
+        subscribe: bool,
+        session_id: u64,
+    ) -> trc::Result<()> {
+        let token = new_token();
+        server
+            .in_memory_store()
+            .key_set(
+                KeyValue::with_prefix(
+                    KV_LIST_CONFIRM,
+                    token.as_bytes(),
+                    Archiver::new(PendingSubscription {
+                        list_id: self.id,
+                        address: address.to_string(),
+                        subscribe,
+                    })
+                    .untrusted()
+                    .serialize()
+                    .caused_by(trc::location!())?,
+                )
+                .expires(LIST_CONFIRM_EXPIRY),
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        let confirm_address = self.command_address(&format!("confirm-{token}"));
+        let (subject, action) = if subscribe {
+            ("Confirm subscription", "subscribe to")
+        } else {
+            ("Confirm unsubscription", "unsubscribe from")
+        };
+        self.notify(
+            server,
+            address,
+            subject,
+            format!(
+                concat!(
+                    "A request was received to {} the {} mailing list using the address {}.\r\n\r\n",
+                    "To confirm, reply to this message or send a message to:\r\n\r\n    {}\r\n\r\n",
+                    "If you did not make this request, you can ignore this message.\r\n"
+                ),
+                action,
+                self.address(),
+                address,
+                confirm_address,
+            ),
+            Some(&confirm_address),
+            session_id,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    async fn notify_welcome(&self, server: &Server, address: &str, session_id: u64) {
+        self.notify(
+            server,
+            address,
+            "Welcome",
+            format!(
+                "Welcome to the {} mailing list.\r\n\r\n{}",
+                self.address(),
+                self.help_text()
+            ),
+            None,
+            session_id,
+        )
+        .await;
+    }
+
+    async fn notify(
+        &self,
+        server: &Server,
+        rcpt: &str,
+        subject: &str,
+        text: String,
+        reply_to: Option<&str>,
+        session_id: u64,
+    ) {
+        let message = self.build_notice(rcpt, subject, text, reply_to, None);
+        self.send(
+            server,
+            self.command_address("bounces"),
+            rcpt,
+            None,
+            &message,
+            session_id,
+        )
+        .await;
+    }
+
+    pub(crate) fn build_notice(
+        &self,
+        rcpt: &str,
+        subject: &str,
+        text: String,
+        reply_to: Option<&str>,
+        attachment: Option<&[u8]>,
+    ) -> Vec<u8> {
+        let help_address = self.command_address("help");
+        let mut builder = MessageBuilder::new()
+            .from((self.display_name(), help_address.as_str()))
+            .header("To", HeaderType::Text(rcpt.into()))
+            .header("Auto-Submitted", HeaderType::Text("auto-replied".into()))
+            .header("Precedence", HeaderType::Text("list".into()))
+            .message_id(format!("<{}@{}>", make_boundary("."), self.domain))
+            .subject(format!("[{}] {subject}", self.display_name()));
+        if let Some(reply_to) = reply_to {
+            builder = builder.reply_to(reply_to);
+        }
+
+        let text = MimePart::new(ContentType::new("text/plain"), BodyPart::Text(text.into()));
+        let body = if let Some(attachment) = attachment {
+            MimePart::new(
+                ContentType::new("multipart/mixed"),
+                BodyPart::Multipart(vec![
+                    text,
+                    MimePart::new(
+                        ContentType::new("message/rfc822"),
+                        BodyPart::Text(String::from_utf8_lossy(attachment).into_owned().into()),
+                    ),
+                ]),
+            )
+        } else {
+            text
+        };
+
+        builder.body(body).write_to_vec().unwrap_or_default()
+    }
+
+    fn help_text(&self) -> String {
+        let mut text = format!(
+            "The following addresses are available for the {} mailing list:\r\n\r\n",
+            self.address()
+        );
+        if self.list.subscription_policy != MailingListSubscriptionPolicy::Closed {
+            text.push_str(&format!(
+                "    {}  (subscribe)\r\n",
+                self.command_address("subscribe")
+            ));
+        }
+        text.push_str(&format!(
+            "    {}  (unsubscribe)\r\n    {}  (this message)\r\n",
+            self.command_address("unsubscribe"),
+            self.command_address("help")
+        ));
+        text
+    }
+}
@@ -0,0 +1,369 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::queue::{
+    MessageSource,
+    spool::{QueueParams, SmtpSpool},
+};
+use common::{
+    Server,
+    auth::oauth::GrantType,
+    cache::invalidate::CacheInvalidationBuilder,
+    network::list::{ListCommand, ListRecipient},
+};
+use email::message::delivery::LocalDeliveryStatus;
+use registry::{
+    schema::{
+        prelude::{Object, ObjectType},
+        structs::MailingList,
+    },
+    types::id::ObjectId,
+};
+use std::future::Future;
+use store::registry::write::{RegistryWrite, RegistryWriteResult};
+use trc::AddContext;
+use types::id::Id;
+
+pub mod bounce;
+pub mod command;
+pub mod post;
+
+pub const LIST_CONFIRM_EXPIRY: u64 = 3 * 86400;
+pub const LIST_MODERATION_EXPIRY: u64 = 7 * 86400;
+pub const LIST_BOUNCE_WINDOW: u64 = 30 * 86400;
+pub const LIST_UNSUBSCRIBE_EXPIRY: u64 = 365 * 86400;
+
+pub struct ManagedList {
+    pub id: u32,
+    pub list: MailingList,
+    pub domain: String,
+}
+
+pub struct ListMessage<'x> {
+    pub return_path: &'x str,
+    pub raw_message: &'x [u8],
+    pub session_id: u64,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug)]
+pub struct PendingSubscription {
+    pub list_id: u32,
+    pub address: String,
+    pub subscribe: bool,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug)]
+pub struct HeldMessage {
+    pub list_id: u32,
+    pub return_path: String,
+    pub sender: String,
+    pub message: Vec<u8>,
+}
+
+pub trait MailingListManager: Sync + Send {
+    fn list_deliver(
+        &self,
+        recipient: ListRecipient,
+        message: ListMessage<'_>,
+    ) -> impl Future<Output = LocalDeliveryStatus> + Send;
+
+    fn list_unsubscribe_token(
+        &self,
+        token: &str,
+        session_id: u64,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+}
+
+impl MailingListManager for Server {
+    async fn list_deliver(
+        &self,
+        recipient: ListRecipient,
+        message: ListMessage<'_>,
+    ) -> LocalDeliveryStatus {
+        let mut list = match ManagedList::load(self, recipient.list_id).await {
+            Ok(Some(list)) => list,
+            Ok(None) => {
+                return LocalDeliveryStatus::PermanentFailure {
+                    code: [5, 1, 1],
+                    reason: "Mailing list not found.".into(),
+                };
+            }
+            Err(err) => {
+                trc::error!(
+                    err.span_id(message.session_id)
+                        .details("Failed to load mailing list.")
+                        .caused_by(trc::location!())
+                );
+                return LocalDeliveryStatus::TemporaryFailure {
+                    reason: "Mailing list lookup failed.".into(),
+                };
+            }
+        };
+
+        let result = match recipient.command {
+            None => list.post(self, &message).await,
+            Some(ListCommand::Bounces(Some(token))) => {
+                list.process_bounce(self, &token, &message).await
+            }
+            Some(command) => list.process_command(self, command, &message).await,
+        };
+
+        result.unwrap_or_else(|err| {
+            trc::error!(
+                err.span_id(message.session_id)
+                    .details("Failed to process mailing list message.")
+                    .caused_by(trc::location!())
+            );
+            LocalDeliveryStatus::TemporaryFailure {
+                reason: "Mailing list processing failed.".into(),
+            }
+        })
+    }
+
+    async fn list_unsubscribe_token(&self, token: &str, session_id: u64) -> trc::Result<bool> {
+        let token = self
+            .validate_access_token(GrantType::ListUnsubscribe.into(), token)
+            .await?;
+        let (Some(address), Some(mut list)) = (
+            token.claims,
+            ManagedList::load(self, token.account_id).await?,
+        ) else {
+            return Ok(false);
+        };
+
+        list.unsubscribe(self, &address, session_id).await
+    }
+}
+
+impl ManagedList {
+    pub async fn load(server: &Server, list_id: u32) -> trc::Result<Option<Self>> {
+        let Some(list) = server
+            .registry()
+            .object::<MailingList>(list_id.into())
+            .await
+            .caused_by(trc::location!())?
+            .filter(|list| list.managed)
+        else {
+            return Ok(None);
+        };
+        let Some(domain) = server
+            .domain_by_id(list.domain_id.document_id())
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(ManagedList {
+            id: list_id,
+            domain: domain.name().to_string(),
+            list,
+        }))
+    }
+
+    pub fn address(&self) -> String {
+        format!("{}@{}", self.list.name, self.domain)
+    }
+
+    pub fn command_address(&self, command: &str) -> String {
+        format!("{}+{command}@{}", self.list.name, self.domain)
+    }
+
+    /// Per-subscriber return path (VERP), bounces are only attributed to the
+    /// subscriber whose keyed token appears in the address they were sent to.
+    pub fn bounce_address(&self, server: &Server, rcpt: &str) -> String {
+        self.command_address(&format!("bounces-{}", self.bounce_token(server, rcpt)))
+    }
+
+    pub fn bounce_token(&self, server: &Server, rcpt: &str) -> String {
+        let mut hasher = blake3::Hasher::new_derive_key("list bounce token");
+        hasher.update(server.core.oauth.oauth_key.as_bytes());
+        hasher.update(&self.id.to_be_bytes());
+        hasher.update(rcpt.to_lowercase().as_bytes());
+        hasher.finalize().to_hex()[..20].to_string()
+    }
+
+    /// List identifier as defined in RFC 2919
+    pub fn list_id(&self) -> String {
+        format!("{}.{}", self.list.name, self.domain)
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.list.description.as_deref().unwrap_or(&self.list.name)
+    }
+
+    pub fn is_subscriber(&self, address: &str) -> bool {
+        self.list
+            .recipients
+            .iter()
+            .chain(self.list.suspended_recipients.iter())
+            .any(|rcpt| rcpt.eq_ignore_ascii_case(address))
+    }
+
+    pub fn is_moderator(&self, address: &str) -> bool {
+        self.list
+            .moderators
+            .iter()
+            .any(|rcpt| rcpt.eq_ignore_ascii_case(address))
+    }
+
+    pub async fn subscribe(
+        &mut self,
+        server: &Server,
+        address: &str,
+        session_id: u64,
+    ) -> trc::Result<bool> {
+        let address = address.to_lowercase();
+        let result = self
+            .update(server, |list| {
+                if list
+                    .recipients
+                    .iter()
+                    .any(|rcpt| rcpt.eq_ignore_ascii_case(&address))
+                {
+                    false
+                } else {
+                    list.suspended_recipients
+                        .inner_mut()
+                        .retain(|rcpt| !rcpt.eq_ignore_ascii_case(&address));
+                    list.recipients.push(address.clone());
+                    true
+                }
+            })
+            .await?;
+
+        if result {
+            trc::event!(
+                MessageIngest(trc::MessageIngestEvent::ListSubscribed),
+                SpanId = session_id,
+                Id = self.address(),
+                To = address,
+            );
+        }
+
+        Ok(result)
+    }
+
+    pub async fn unsubscribe(
+        &mut self,
+        server: &Server,
+        address: &str,
+        session_id: u64,
+    ) -> trc::Result<bool> {
+        let result = self
+            .update(server, |list| {
+                let len = list.recipients.len() + list.suspended_recipients.len();
+                list.recipients
+                    .inner_mut()
+                    .retain(|rcpt| !rcpt.eq_ignore_ascii_case(address));
+                list.suspended_recipients
+                    .inner_mut()
+                    .retain(|rcpt| !rcpt.eq_ignore_ascii_case(address));
+                len != list.recipients.len() + list.suspended_recipients.len()
+            })
+            .await?;
+
+        if result {
+            trc::event!(
+                MessageIngest(trc::MessageIngestEvent::ListUnsubscribed),
+                SpanId = session_id,
+                Id = self.address(),
+                To = address.to_string(),
+            );
+        }
+
+        Ok(result)
+    }
+
+    pub async fn update(
+        &mut self,
+        server: &Server,
+        change: impl FnOnce(&mut MailingList) -> bool,
+    ) -> trc::Result<bool> {
+        let Some(current_list) = server
+            .registry()
+            .get(ObjectId::new(ObjectType::MailingList, self.id.into()))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(false);
+        };
+
+        let mut list = MailingList::from(current_list.clone());
+        if !change(&mut list) {
+            return Ok(false);
+        }
+
+        let updated_list = Object::from(list.clone());
+        match server
+            .registry()
+            .write(RegistryWrite::update(
+                Id::from(self.id),
+                &updated_list,
+                &current_list,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            RegistryWriteResult::Success(id) => {
+                let mut invalidator = CacheInvalidationBuilder::default();
+                invalidator.process_update(id, &current_list, &updated_list);
+                server
+                    .invalidate_caches(invalidator)
+                    .await
+                    .caused_by(trc::location!())?;
+                self.list = list;
+
+                Ok(true)
+            }
+            failure => Err(trc::StoreEvent::UnexpectedError
+                .into_err()
+                .caused_by(trc::location!())
+                .details("Failed to update mailing list")
+                .id(self.id)
+                .reason(failure)),
+        }
+    }
+
+    /// Queues a message sent on behalf of the list, with bounces returned
+    /// to the list's bounce address and signed with the list domain keys.
+    pub async fn send(
+        &self,
+        server: &Server,
+        return_path: String,
+        rcpt: &str,
+        raw_headers: Option<&[u8]>,
+        raw_message: &[u8],
+        session_id: u64,
+    ) -> bool {
+        let mut message = server.new_message(return_path, session_id);
+        message.expand_and_add_recipient(rcpt, server).await;
+
+        let dkim_signers = match server.dkim_signers(&self.domain).await {
+            Ok(signers) => signers,
+            Err(err) => {
+                trc::error!(
+                    err.span_id(session_id)
+                        .details("Failed to retrieve DKIM signers")
+                        .caused_by(trc::location!())
+                );
+                None
+            }
+        };
+        message
+            .queue(
+                QueueParams::new(
+                    raw_message,
+                    session_id,
+                    server,
+                    MessageSource::Autogenerated,
+                )
+                .with_raw_headers_opt(raw_headers)
+                .with_dkim_signers(dkim_signers),
+            )
+            .await
+    }
+}
@@ -0,0 +1,405 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    HeldMessage, LIST_MODERATION_EXPIRY, LIST_UNSUBSCRIBE_EXPIRY, ListMessage, ManagedList,
+};
+use common::{KV_LIST_MODERATION, Server, auth::oauth::GrantType, psl};
+use email::message::delivery::LocalDeliveryStatus;
+use mail_auth::dmarc::{Dmarc, Policy};
+use mail_builder::headers::{Header, address::Address};
+use mail_parser::{HeaderName, Message, MessageParser};
+use registry::schema::enums::{
+    MailingListFromRewrite, MailingListPostingPolicy, MailingListSubscriptionPolicy,
+};
+use store::{Serialize, dispatch::lookup::KeyValue, write::Archiver};
+use trc::AddContext;
+use utils::DomainPart;
+
+const LIST_HEADERS: &[&str] = &[
+    "List-Id",
+    "List-Help",
+    "List-Post",
+    "List-Subscribe",
+    "List-Unsubscribe",
+    "List-Unsubscribe-Post",
+    "List-Archive",
+    "List-Owner",
+    "Precedence",
+    "Return-Path",
+    "Delivered-To",
+];
+
+enum PostAction {
+    Distribute,
+    Hold,
+    Reject,
+}
+
+impl ManagedList {
+    pub async fn post(
+        &mut self,
+        server: &Server,
+        message: &ListMessage<'_>,
+    ) -> trc::Result<LocalDeliveryStatus> {
+        let Some(parsed) = MessageParser::new().parse(message.raw_message) else {
+            return Ok(LocalDeliveryStatus::PermanentFailure {
+                code: [5, 6, 0],
+                reason: "Failed to parse message.".into(),
+            });
+        };
+
+        // Discard bounces and messages that already went through this list
+        let list_id = format!("<{}>", self.list_id());
+        if message.return_path.is_empty()
+            || parsed.root_part().headers().iter().any(|header| {
+                header.name == HeaderName::ListId
+                    && message
+                        .raw_message
+                        .get(header.offset_start() as usize..header.offset_end() as usize)
+                        .is_some_and(|value| {
+                            String::from_utf8_lossy(value)
+                                .to_lowercase()
+                                .contains(&list_id)
+                        })
+            })
+        {
+            trc::event!(
+                MessageIngest(trc::MessageIngestEvent::ListRejected),
+                SpanId = message.session_id,
+                Id = self.address(),
+                From = message.return_path.to_string(),
+                Reason = "Null sender or mail loop",
+            );
+
+            return Ok(LocalDeliveryStatus::Success);
+        }
+
+        // Apply posting policy to the envelope sender, which was either authenticated
+        // or SPF/DMARC verified on reception, the From header can be forged
+        let sender = message.return_path.to_lowercase();
+        let action = if self.is_moderator(&sender) {
+            PostAction::Distribute
+        } else {
+            match self.list.posting_policy {
+                MailingListPostingPolicy::Anyone => PostAction::Distribute,
+                MailingListPostingPolicy::Subscribers | MailingListPostingPolicy::Moderated
+                    if self.is_subscriber(&sender) =>
+                {
+                    PostAction::Distribute
+                }
+                MailingListPostingPolicy::Moderated if !self.list.moderators.is_empty() => {
+                    PostAction::Hold
+                }
+                _ => PostAction::Reject,
+            }
+        };
+
+        match action {
+            PostAction::Distribute => {
+                self.distribute(server, &parsed, message.raw_message, message.session_id)
+                    .await
+            }
+            PostAction::Hold => self.hold(server, &parsed, &sender, message).await,
+            PostAction::Reject => {
+                trc::event!(
+                    MessageIngest(trc::MessageIngestEvent::ListRejected),
+                    SpanId = message.session_id,
+                    Id = self.address(),
+                    From = sender,
+                    Reason = "Posting policy",
+                );
+
+                Ok(LocalDeliveryStatus::PermanentFailure {
+                    code: [5, 7, 1],
+                    reason: "You are not allowed to post to this mailing list.".into(),
+                })
+            }
+        }
+    }
+
+    pub async fn distribute(
+        &self,
+        server: &Server,
+        parsed: &Message<'_>,
+        raw_message: &[u8],
+        session_id: u64,
+    ) -> trc::Result<LocalDeliveryStatus> {
+        let from = parsed.from().and_then(|from| from.first());
+        let sender = from
+            .and_then(|from| from.address())
+            .unwrap_or_default()
+            .to_lowercase();
+        let rewrite_from = !sender.is_empty()
+            && match self.list.from_rewrite {
+                MailingListFromRewrite::Always => true,
+                MailingListFromRewrite::Never => false,
+                MailingListFromRewrite::Dmarc => {
+                    has_strict_dmarc(server, sender.domain_part()).await
+                }
+            };
+
+        // Build list headers
+        let mut list_message = Vec::with_capacity(raw_message.len() + 512);
+        self.write_list_headers(&mut list_message);
+
+        // Copy original headers, rewriting From if needed
+        let root = parsed.root_part();
+        let has_reply_to = root
+            .headers()
+            .iter()
+            .any(|header| header.name == HeaderName::ReplyTo);
+        for header in root.headers() {
+            let name = header.name.as_str();
+            if LIST_HEADERS
+                .iter()
+                .any(|list_header| list_header.eq_ignore_ascii_case(name))
+            {
+                continue;
+            }
+            let raw_header =
+                &raw_message[header.offset_field() as usize..header.offset_end() as usize];
+            if rewrite_from && header.name == HeaderName::From {
+                let display_name = match from.and_then(|from| from.name()) {
+                    Some(name) => format!("{name} via {}", self.display_name()),
+                    None => format!("{sender} via {}", self.display_name()),
+                };
+                list_message.extend_from_slice(b"From: ");
+                let _ = Address::new_address(display_name.into(), self.address())
+                    .write_header(&mut list_message, 6);
+                if !has_reply_to {
+                    list_message.extend_from_slice(b"Reply-To:");
+                    list_message.extend_from_slice(
+                        &raw_message[header.offset_start() as usize..header.offset_end() as usize],
+                    );
+                }
+            } else {
+                list_message.extend_from_slice(raw_header);
+            }
+        }
+        list_message.extend_from_slice(b"\r\n");
+        list_message.extend_from_slice(
+            raw_message
+                .get(root.raw_body_offset() as usize..)
+                .unwrap_or_default(),
+        );
+
+        // Queue a copy for each subscriber, each with its own unsubscribe link
+        let list_address = self.address();
+        let base_url = server.core.network.http.url_https.trim_end_matches('/');
+        let mut total = 0;
+        for rcpt in self
+            .list
+            .recipients
+            .iter()
+            .chain(self.list.archive_address.iter())
+            .filter(|rcpt| !rcpt.eq_ignore_ascii_case(&list_address))
+        {
+            let mut unsubscribe = Vec::with_capacity(256);
+            unsubscribe.extend_from_slice(b"List-Unsubscribe: ");
+            if !base_url.is_empty()
+                && self.list.recipients.contains(rcpt)
+                && let Ok(token) = server
+                    .encode_access_token(
+                        GrantType::ListUnsubscribe,
+                        self.id,
+                        "",
+                        LIST_UNSUBSCRIBE_EXPIRY,
+                        Some(rcpt),
+                        None,
+                    )
+                    .await
+            {
+                unsubscribe.extend_from_slice(
+                    format!(
+                        "<{base_url}/list/unsubscribe?t={token}>,\r\n\t<mailto:{}>\r\n",
+                        self.command_address("unsubscribe")
+                    )
+                    .as_bytes(),
+                );
+                unsubscribe
+                    .extend_from_slice(b"List-Unsubscribe-Post: List-Unsubscribe=One-Click\r\n");
+            } else {
+                unsubscribe.extend_from_slice(
+                    format!("<mailto:{}>\r\n", self.command_address("unsubscribe")).as_bytes(),
+                );
+            }
+
+            if self
+                .send(
+                    server,
+                    self.bounce_address(server, rcpt),
+                    rcpt,
+                    Some(&unsubscribe),
+                    &list_message,
+                    session_id,
+                )
+                .await
+            {
+                total += 1;
+            }
+        }
+
+        trc::event!(
+            MessageIngest(trc::MessageIngestEvent::ListDistributed),
+            SpanId = session_id,
+            Id = list_address,
+            From = sender,
+            Total = total,
+        );
+
+        Ok(LocalDeliveryStatus::Success)
+    }
+
+    async fn hold(
+        &self,
+        server: &Server,
+        parsed: &Message<'_>,
+        sender: &str,
+        message: &ListMessage<'_>,
+    ) -> trc::Result<LocalDeliveryStatus> {
+        let held_id = new_token();
+        server
+            .in_memory_store()
+            .key_set(
+                KeyValue::with_prefix(
+                    KV_LIST_MODERATION,
+                    held_id.as_bytes(),
+                    Archiver::new(HeldMessage {
+                        list_id: self.id,
+                        return_path: message.return_path.to_string(),
+                        sender: sender.to_string(),
+                        message: message.raw_message.to_vec(),
+                    })
+                    .untrusted()
+                    .serialize()
+                    .caused_by(trc::location!())?,
+                )
+                .expires(LIST_MODERATION_EXPIRY),
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        // Notify moderators
+        let subject = parsed.subject().unwrap_or_default();
+        let text = format!(
+            concat!(
+                "A message from {} to the {} mailing list is awaiting approval.\r\n\r\n",
+                "Subject: {}\r\n\r\n",
+                "To approve it, send a message to:\r\n\r\n    {}\r\n\r\n",
+                "To reject it, send a message to:\r\n\r\n    {}\r\n\r\n",
+                "The message will be discarded if no action is taken within {} days.\r\n"
+            ),
+            sender,
+            self.address(),
+            subject,
+            self.command_address(&format!("approve-{held_id}")),
+            self.command_address(&format!("reject-{held_id}")),
+            LIST_MODERATION_EXPIRY / 86400,
+        );
+        for moderator in self.list.moderators.iter() {
+            let notice = self.build_notice(
+                moderator,
+                &format!("Approval required: {subject}"),
+                text.clone(),
+                None,
+                Some(message.raw_message),
+            );
+            self.send(
+                server,
+                self.command_address("bounces"),
+                moderator,
+                None,
+                &notice,
+                message.session_id,
+            )
+            .await;
+        }
+
+        trc::event!(
+            MessageIngest(trc::MessageIngestEvent::ListHeld),
+            SpanId = message.session_id,
+            Id = self.address(),
+            From = sender.to_string(),
+            Details = held_id,
+        );
+
+        Ok(LocalDeliveryStatus::Success)
+    }
+
+    fn write_list_headers(&self, headers: &mut Vec<u8>) {
+        let display_name = self
+            .display_name()
+            .chars()
+            .filter(|ch| !matches!(ch, '"' | '\\' | '\r' | '\n'))
+            .collect::<String>();
+        headers.extend_from_slice(
+            format!("List-Id: \"{display_name}\" <{}>\r\n", self.list_id()).as_bytes(),
+        );
+        headers.extend_from_slice(
+            format!("List-Help: <mailto:{}>\r\n", self.command_address("help")).as_bytes(),
+        );
+        if self.list.posting_policy != MailingListPostingPolicy::Moderators {
+            headers.extend_from_slice(
+                format!("List-Post: <mailto:{}>\r\n", self.address()).as_bytes(),
+            );
+        } else {
+            headers.extend_from_slice(b"List-Post: NO\r\n");
+        }
+        if self.list.subscription_policy != MailingListSubscriptionPolicy::Closed {
+            headers.extend_from_slice(
+                format!(
+                    "List-Subscribe: <mailto:{}>\r\n",
+                    self.command_address("subscribe")
+                )
+                .as_bytes(),
+            );
+        }
+        headers.extend_from_slice(b"Precedence: list\r\n");
+    }
+}
+
+/// Returns the author of a message, falling back to the envelope sender.
+pub(crate) fn sender_address(message: &Message<'_>, return_path: &str) -> String {
+    message
+        .from()
+        .and_then(|from| from.first())
+        .and_then(|from| from.address())
+        .unwrap_or(return_path)
+        .to_lowercase()
+}
+
+pub(crate) fn new_token() -> String {
+    format!("{:020x}", rand::random::<u128>() >> 48)
+}
+
+/// Whether the domain publishes a DMARC policy that would cause list copies
+/// sent with the original From header to be quarantined or rejected.
+async fn has_strict_dmarc(server: &Server, domain: &str) -> bool {
+    let resolver = &server.core.smtp.resolvers.dns;
+    match resolver
+        .txt_lookup::<Dmarc>(
+            format!("_dmarc.{domain}."),
+            Some(&server.inner.cache.dns_txt),
+        )
+        .await
+    {
+        Ok(record) => matches!(record.p, Policy::Quarantine | Policy::Reject),
+        Err(_) => match psl::domain_str(domain).filter(|org_domain| *org_domain != domain) {
+            Some(org_domain) => resolver
+                .txt_lookup::<Dmarc>(
+                    format!("_dmarc.{org_domain}."),
+                    Some(&server.inner.cache.dns_txt),
+                )
+                .await
+                .is_ok_and(|record| {
+                    matches!(record.sp, Policy::Quarantine | Policy::Reject)
+                        || matches!(record.p, Policy::Quarantine | Policy::Reject)
+                }),
+            None => false,
+        },
+    }
+}
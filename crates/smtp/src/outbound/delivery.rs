@@ -1433,28 +1433,9 @@ impl QueuedMessage {
         }
 
         // Apply status changes
-        for delivery_result in delivery_results {
-            match delivery_result {
-                DeliveryResult::Domain { status, rcpt_idxs } => {
-                    for rcpt_idx in rcpt_idxs {
-                        message
-                            .set_rcpt_status(status.clone(), rcpt_idx, &server)
-                            .await;
-                    }
-                }
-                DeliveryResult::Account { status, rcpt_idx } => {
-                    message.set_rcpt_status(status, rcpt_idx, &server).await;
-                }
-                DeliveryResult::RateLimited {
-                    rcpt_idxs,
-                    retry_at,
-                } => {
-                    for rcpt_idx in rcpt_idxs {
-                        message.set_rcpt_rate_limit(rcpt_idx, retry_at);
-                    }
-                }
-            }
-        }
+        message
+            .apply_delivery_results(delivery_results, &server)
+            .await;
 
         // Send Delivery Status Notifications
         server.send_dsn(&mut message).await;
@@ -1590,6 +1571,33 @@ impl MessageWrapper {
         }
     }
 
+    pub(crate) async fn apply_delivery_results(
+        &mut self,
+        delivery_results: Vec<DeliveryResult>,
+        server: &Server,
+    ) {
+        for delivery_result in delivery_results {
+            match delivery_result {
+                DeliveryResult::Domain { status, rcpt_idxs } => {
+                    for rcpt_idx in rcpt_idxs {
+                        self.set_rcpt_status(status.clone(), rcpt_idx, server).await;
+                    }
+                }
+                DeliveryResult::Account { status, rcpt_idx } => {
+                    self.set_rcpt_status(status, rcpt_idx, server).await;
+                }
+                DeliveryResult::RateLimited {
+                    rcpt_idxs,
+                    retry_at,
+                } => {
+                    for rcpt_idx in rcpt_idxs {
+                        self.set_rcpt_rate_limit(rcpt_idx, retry_at);
+                    }
+                }
+            }
+        }
+    }
+
     pub fn set_rcpt_rate_limit(&mut self, rcpt_idx: usize, retry_at: u64) {
         let rcpt = &mut self.message.recipients[rcpt_idx];
         rcpt.retry.due = retry_at;
@@ -6,10 +6,12 @@
 
 use crate::{
     inbound::dkim::DkimSign,
+    lists::{ListMessage, MailingListManager},
     outbound::DeliveryResult,
     queue::{
         Error, ErrorDetails, FROM_AUTHENTICATED, FROM_UNAUTHENTICATED_DMARC, HostResponse,
-        MessageSource, MessageWrapper, RCPT_SPAM_PAYLOAD, Status, UnexpectedResponse,
+        MessageSource, MessageWrapper, RCPT_QUARANTINE, RCPT_SPAM_PAYLOAD, Status,
+        UnexpectedResponse,
         quota::HasQueueQuota,
         spool::{QueueParams, SmtpSpool},
     },
@@ -28,18 +30,103 @@ impl MessageWrapper {
     ) {
         // Prepare recipients list
         let mut pending_recipients = Vec::new();
+        let mut list_recipients = Vec::new();
         let mut recipients = Vec::new();
         for &rcpt_idx in rcpt_idxs {
             let rcpt = &self.message.recipients[rcpt_idx];
             let rcpt_addr = rcpt.address();
+            let address = rcpt_addr.to_lowercase();
+
+            // Managed mailing lists are processed separately
+            match server.list_recipient(&address).await {
+                Ok(Some(list_rcpt)) => {
+                    list_recipients.push((rcpt_idx, rcpt_addr, list_rcpt));
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    trc::error!(
+                        err.span_id(self.span_id)
+                            .caused_by(trc::location!())
+                            .details("Failed to resolve mailing list recipient.")
+                    );
+                    statuses.push(DeliveryResult::account(
+                        local_delivery_status(
+                            LocalDeliveryStatus::TemporaryFailure {
+                                reason: "Mailing list lookup failed.".into(),
+                            },
+                            rcpt_addr,
+                        ),
+                        rcpt_idx,
+                    ));
+                    continue;
+                }
+            }
+
             recipients.push(IngestRecipient {
-                address: rcpt_addr.to_lowercase(),
+                address,
                 orcpt: rcpt.orcpt.as_ref().map(|orcpt| orcpt.to_string()),
                 is_spam: rcpt.flags & RCPT_SPAM_PAYLOAD != 0,
+                is_quarantined: rcpt.flags & RCPT_QUARANTINE != 0,
             });
             pending_recipients.push((rcpt_idx, rcpt_addr));
         }
 
+        // Deliver to mailing lists
+        if !list_recipients.is_empty() {
+            let raw_message = match server
+                .blob_store()
+                .get_blob(self.message.blob_hash.as_slice(), 0..usize::MAX)
+                .await
+            {
+                Ok(Some(raw_message)) => Some(raw_message),
+                Ok(None) => {
+                    trc::event!(
+                        Queue(trc::QueueEvent::BlobNotFound),
+                        SpanId = self.span_id,
+                        BlobId = self.message.blob_hash.to_hex(),
+                        CausedBy = trc::location!()
+                    );
+                    None
+                }
+                Err(err) => {
+                    trc::error!(
+                        err.span_id(self.span_id)
+                            .caused_by(trc::location!())
+                            .details("Failed to fetch blobId from store.")
+                    );
+                    None
+                }
+            };
+
+            for (rcpt_idx, rcpt_addr, list_rcpt) in list_recipients {
+                let result = if let Some(raw_message) = &raw_message {
+                    server
+                        .list_deliver(
+                            list_rcpt,
+                            ListMessage {
+                                return_path: &self.message.return_path,
+                                raw_message,
+                                session_id: self.span_id,
+                            },
+                        )
+                        .await
+                } else {
+                    LocalDeliveryStatus::TemporaryFailure {
+                        reason: "Failed to fetch message.".into(),
+                    }
+                };
+                statuses.push(DeliveryResult::account(
+                    local_delivery_status(result, rcpt_addr),
+                    rcpt_idx,
+                ));
+            }
+
+            if recipients.is_empty() {
+                return;
+            }
+        }
+
         // Deliver message
         let delivery_result = server
             .deliver_message(IngestMessage {
@@ -58,43 +145,10 @@ impl MessageWrapper {
         for ((rcpt_idx, rcpt_addr), result) in
             pending_recipients.into_iter().zip(delivery_result.status)
         {
-            let status = match result {
-                LocalDeliveryStatus::Success => Status::Completed(HostResponse {
-                    hostname: "localhost".into(),
-                    response: Response {
-                        code: 250,
-                        esc: [2, 1, 5],
-                        message: "OK".into(),
-                    },
-                }),
-                LocalDeliveryStatus::TemporaryFailure { reason } => {
-                    Status::TemporaryFailure(ErrorDetails {
-                        entity: "localhost".into(),
-                        details: Error::UnexpectedResponse(UnexpectedResponse {
-                            command: format!("RCPT TO:<{rcpt_addr}>").into_boxed_str(),
-                            response: Response {
-                                code: 451,
-                                esc: [4, 3, 0],
-                                message: reason.into(),
-                            },
-                        }),
-                    })
-                }
-                LocalDeliveryStatus::PermanentFailure { code, reason } => {
-                    Status::PermanentFailure(ErrorDetails {
-                        entity: "localhost".into(),
-                        details: Error::UnexpectedResponse(UnexpectedResponse {
-                            command: format!("RCPT TO:<{rcpt_addr}>").into_boxed_str(),
-                            response: Response {
-                                code: 550,
-                                esc: code,
-                                message: reason.into(),
-                            },
-                        }),
-                    })
-                }
-            };
-            statuses.push(DeliveryResult::account(status, rcpt_idx));
+            statuses.push(DeliveryResult::account(
+                local_delivery_status(result, rcpt_addr),
+                rcpt_idx,
+            ));
         }
 
         // Process autogenerated messages
@@ -139,3 +193,45 @@ impl MessageWrapper {
         }
     }
 }
+
+fn local_delivery_status(
+    result: LocalDeliveryStatus,
+    rcpt_addr: &str,
+) -> Status<HostResponse<Box<str>>, ErrorDetails> {
+    match result {
+        LocalDeliveryStatus::Success => Status::Completed(HostResponse {
+            hostname: "localhost".into(),
+            response: Response {
+                code: 250,
+                esc: [2, 1, 5],
+                message: "OK".into(),
+            },
+        }),
+        LocalDeliveryStatus::TemporaryFailure { reason } => {
+            Status::TemporaryFailure(ErrorDetails {
+                entity: "localhost".into(),
+                details: Error::UnexpectedResponse(UnexpectedResponse {
+                    command: format!("RCPT TO:<{rcpt_addr}>").into_boxed_str(),
+                    response: Response {
+                        code: 451,
+                        esc: [4, 3, 0],
+                        message: reason.into(),
+                    },
+                }),
+            })
+        }
+        LocalDeliveryStatus::PermanentFailure { code, reason } => {
+            Status::PermanentFailure(ErrorDetails {
+                entity: "localhost".into(),
+                details: Error::UnexpectedResponse(UnexpectedResponse {
+                    command: format!("RCPT TO:<{rcpt_addr}>").into_boxed_str(),
+                    response: Response {
+                        code: 550,
+                        esc: code,
+                        message: reason.into(),
+                    },
+                }),
+            })
+        }
+    }
+}
@@ -26,7 +26,7 @@ pub mod lookup;
 pub mod mta_sts;
 pub mod session;
 
-pub(super) enum DeliveryResult {
+pub(crate) enum DeliveryResult {
     Domain {
         status: Status<HostResponse<Box<str>>, ErrorDetails>,
         rcpt_idxs: Vec<usize>,
@@ -119,10 +119,34 @@ impl MessageWrapper {
             };*/
         }
 
+        self.send_transaction(
+            &mut smtp_client,
+            &capabilities,
+            rcpt_idxs,
+            rcpt_headers,
+            statuses,
+            &params,
+        )
+        .await;
+
+        smtp_client.quit().await;
+    }
+
+    /// Runs a single MAIL/RCPT/DATA transaction on an already established session.
+    /// Returns `false` when the session cannot be reused for further transactions.
+    pub(crate) async fn send_transaction<T: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        smtp_client: &mut SmtpClient<T>,
+        capabilities: &EhloResponse<String>,
+        rcpt_idxs: Vec<usize>,
+        rcpt_headers: Option<&[u8]>,
+        statuses: &mut Vec<DeliveryResult>,
+        params: &SessionParams<'_>,
+    ) -> bool {
         // MAIL FROM
         let time = Instant::now();
         smtp_client.timeout = params.conn_strategy.timeout_mail;
-        let cmd = self.build_mail_from(&capabilities);
+        let cmd = self.build_mail_from(capabilities);
         match smtp_client.cmd(cmd.as_bytes()).await.and_then(|r| {
             if r.is_positive_completion() {
                 Ok(r)
@@ -150,12 +174,11 @@ impl MessageWrapper {
                     Elapsed = time.elapsed(),
                 );
 
-                smtp_client.quit().await;
                 statuses.push(DeliveryResult::domain(
                     Status::from_smtp_error(params.hostname, &cmd, err),
                     rcpt_idxs,
                 ));
-                return;
+                return false;
             }
         }
 
@@ -172,7 +195,7 @@ impl MessageWrapper {
                 continue;
             }
 
-            let cmd = self.build_rcpt_to(rcpt, &capabilities);
+            let cmd = self.build_rcpt_to(rcpt, capabilities);
             match smtp_client.cmd(cmd.as_bytes()).await {
                 Ok(response) => match response.severity() {
                     Severity::PositiveCompletion => {
@@ -234,12 +257,11 @@ impl MessageWrapper {
                     );
 
                     // Something went wrong, abort.
-                    smtp_client.quit().await;
                     statuses.push(DeliveryResult::domain(
                         Status::from_smtp_error(params.hostname, "", err),
                         rcpt_idxs,
                     ));
-                    return;
+                    return false;
                 }
             }
         }
@@ -250,7 +272,7 @@ impl MessageWrapper {
             let mut bdat_cmd = capabilities.has_capability(EXT_CHUNKING).then(String::new);
 
             if let Err(status) = smtp_client
-                .send_message(self, rcpt_headers, &mut bdat_cmd, &params)
+                .send_message(self, rcpt_headers, &mut bdat_cmd, params)
                 .await
             {
                 trc::event!(
@@ -261,9 +283,8 @@ impl MessageWrapper {
                     Elapsed = time.elapsed(),
                 );
 
-                smtp_client.quit().await;
                 statuses.push(DeliveryResult::domain(status, rcpt_idxs));
-                return;
+                return false;
             }
 
             if params.is_smtp {
@@ -298,7 +319,6 @@ impl MessageWrapper {
                                 Elapsed = time.elapsed(),
                             );
 
-                            smtp_client.quit().await;
                             statuses.push(DeliveryResult::domain(
                                 Status::from_smtp_error(
                                     params.hostname,
@@ -307,7 +327,7 @@ impl MessageWrapper {
                                 ),
                                 rcpt_idxs,
                             ));
-                            return;
+                            return false;
                         }
                     }
                     Err(status) => {
@@ -319,9 +339,8 @@ impl MessageWrapper {
                             Elapsed = time.elapsed(),
                         );
 
-                        smtp_client.quit().await;
                         statuses.push(DeliveryResult::domain(status, rcpt_idxs));
-                        return;
+                        return false;
                     }
                 }
             } else {
@@ -395,15 +414,14 @@ impl MessageWrapper {
                             Elapsed = time.elapsed(),
                         );
 
-                        smtp_client.quit().await;
                         statuses.push(DeliveryResult::domain(status, rcpt_idxs));
-                        return;
+                        return false;
                     }
                 }
             }
         }
 
-        smtp_client.quit().await;
+        true
     }
 
     fn build_mail_from(&self, capabilities: &EhloResponse<String>) -> String {
@@ -4,22 +4,27 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::{Message, QueueId, Status, spool::SmtpSpool};
+use super::{ArchivedStatus, Message, MessageWrapper, QueueId, Status, spool::SmtpSpool};
 use crate::queue::{Recipient, spool::LOCK_EXPIRY};
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use common::{
-    BuildServer, Inner,
+    BuildServer, Inner, Server,
     config::smtp::queue::{QueueExpiry, QueueName},
-    ipc::{QueueEvent, QueueEventStatus},
+    ipc::{BroadcastEvent, QueueEvent, QueueEventStatus},
 };
 use rand::{Rng, seq::SliceRandom};
 use std::{
     collections::hash_map::Entry,
+    future::Future,
     sync::{Arc, atomic::Ordering},
     time::{Duration, Instant},
 };
-use store::write::now;
+use store::{
+    Deserialize, IterateParams, ValueKey,
+    write::{AlignedBytes, Archive, QueueClass, ValueClass, key::DeserializeBigEndian, now},
+};
 use tokio::sync::mpsc;
+use trc::{AddContext, ServerEvent};
 
 pub struct Queue {
     pub core: Arc<Inner>,
@@ -359,6 +364,144 @@ pub trait SpawnQueue {
     fn spawn(self, core: Arc<Inner>);
 }
 
+pub trait QueueFlush: Sync + Send {
+    fn queued_messages_for(
+        &self,
+        domains: &[String],
+    ) -> impl Future<Output = trc::Result<Vec<(QueueId, AHashSet<QueueName>)>>> + Send;
+
+    fn flush_queue(
+        &self,
+        domains: &[String],
+        session_id: u64,
+    ) -> impl Future<Output = trc::Result<usize>> + Send;
+}
+
+impl QueueFlush for Server {
+    async fn queued_messages_for(
+        &self,
+        domains: &[String],
+    ) -> trc::Result<Vec<(QueueId, AHashSet<QueueName>)>> {
+        let from_key = ValueKey::from(ValueClass::Queue(QueueClass::Message(0)));
+        let to_key = ValueKey::from(ValueClass::Queue(QueueClass::Message(u64::MAX)));
+        let mut queue_ids = Vec::new();
+
+        self.store()
+            .iterate(
+                IterateParams::new(from_key, to_key).ascending(),
+                |key, value| {
+                    let message_ = <Archive<AlignedBytes> as Deserialize>::deserialize(value)
+                        .add_context(|ctx| ctx.ctx(trc::Key::Key, key))?;
+                    let message = message_
+                        .unarchive::<Message>()
+                        .add_context(|ctx| ctx.ctx(trc::Key::Key, key))?;
+
+                    let mut pending = message.recipients.iter().filter(|rcpt| {
+                        matches!(
+                            rcpt.status,
+                            ArchivedStatus::Scheduled | ArchivedStatus::TemporaryFailure(_)
+                        )
+                    });
+                    if pending
+                        .clone()
+                        .any(|rcpt| matches_domain(domains, rcpt.domain_part()))
+                    {
+                        queue_ids.push((
+                            key.deserialize_be_u64(0)?,
+                            pending
+                                .filter_map(|rcpt| QueueName::from_bytes(rcpt.queue.as_slice()))
+                                .collect(),
+                        ));
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(queue_ids)
+    }
+
+    async fn flush_queue(&self, domains: &[String], session_id: u64) -> trc::Result<usize> {
+        let mut total_flushed = 0;
+
+        for (queue_id, _) in self.queued_messages_for(domains).await? {
+            let Some(archive) = self.read_message_archive(queue_id).await? else {
+                continue;
+            };
+            let mut message = archive
+                .deserialize::<Message>()
+                .caused_by(trc::location!())?;
+            let prev_events = message.next_events();
+            let now = now();
+            let mut modified_rcpts = AHashSet::new();
+
+            let mut is_pending = false;
+
+            for (idx, rcpt) in message.recipients.iter_mut().enumerate() {
+                if matches!(rcpt.status, Status::Scheduled | Status::TemporaryFailure(_))
+                    && matches_domain(domains, rcpt.domain_part())
+                {
+                    // Recipients already due are pending as well, only reschedule the rest
+                    is_pending = true;
+                    if rcpt.retry.due > now {
+                        rcpt.retry.due = now;
+                        modified_rcpts.insert(idx);
+                    }
+                }
+            }
+
+            if is_pending
+                && (modified_rcpts.is_empty()
+                    || MessageWrapper::new(message, queue_id, QueueName::default())
+                        .save_registry_changes(self, prev_events, modified_rcpts)
+                        .await)
+            {
+                total_flushed += 1;
+            }
+        }
+
+        if total_flushed > 0 {
+            if self
+                .inner
+                .ipc
+                .queue_tx
+                .send(QueueEvent::Refresh)
+                .await
+                .is_err()
+            {
+                trc::event!(
+                    Server(ServerEvent::ThreadError),
+                    Reason = "Channel closed.",
+                    CausedBy = trc::location!(),
+                    SpanId = session_id,
+                );
+            }
+
+            self.cluster_broadcast(BroadcastEvent::QueueRefresh).await;
+        }
+
+        Ok(total_flushed)
+    }
+}
+
+// Domains prefixed with '@' also match their subdomains (RFC 1985)
+pub fn matches_domain(domains: &[String], domain: &str) -> bool {
+    let domain = domain.to_lowercase();
+    domains.iter().any(|node| {
+        let node = node.to_lowercase();
+        if let Some(node) = node.strip_prefix('@') {
+            domain == node
+                || domain
+                    .strip_suffix(node)
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        } else {
+            domain == node
+        }
+    })
+}
+
 impl QueueStats {
     fn new(max_in_flight: usize) -> Self {
         QueueStats {
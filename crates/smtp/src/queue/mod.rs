@@ -130,6 +130,7 @@ pub const FROM_AUTOGENERATED: u64 = 1 << 37;
 pub const RCPT_DSN_SENT: u64 = 1 << 32;
 //pub const RCPT_UNDISCLOSED: u64 = 1 << 33;
 pub const RCPT_SPAM_PAYLOAD: u64 = 1 << 34;
+pub const RCPT_QUARANTINE: u64 = 1 << 35;
 
 #[derive(
     Debug,
@@ -255,6 +255,11 @@ impl SpamFilterAnalyzeScore for Server {
         // Pyzor checks
         self.spam_filter_analyze_pyzor(ctx).await;
 
+        // Antivirus verdict
+        if ctx.input.virus.is_some() {
+            ctx.result.add_tag("CLAMAV_VIRUS");
+        }
+
         // Model classification
         self.spam_filter_analyze_classify(ctx).await;
 
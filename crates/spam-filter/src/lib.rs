@@ -44,6 +44,9 @@ pub struct SpamFilterInput<'x> {
     pub asn: Option<u32>,
     pub country: Option<&'x str>,
 
+    // Antivirus verdict
+    pub virus: Option<&'x str>,
+
     // TLS
     pub is_tls: bool,
 
@@ -159,6 +162,7 @@ impl<'x> SpamFilterInput<'x> {
             authenticated_as: None,
             asn: None,
             country: None,
+            virus: None,
             is_tls: true,
             env_from: "",
             env_from_flags: 0,
@@ -19,8 +19,8 @@ use registry::{
     schema::{
         prelude::{Object, ObjectInner, ObjectType, Property},
         structs::{
-            ArchivedItem, DmarcInternalReport, Metric, SpamTrainingSample, Task, TlsInternalReport,
-            Trace,
+            ArchivedItem, DmarcInternalReport, Metric, QuarantinedMessage, SpamTrainingSample,
+            Task, TlsInternalReport, Trace,
         },
     },
     types::{EnumImpl, ObjectImpl, id::ObjectId},
@@ -142,6 +142,19 @@ impl Deserialize for ArchivedItem {
     }
 }
 
+impl Deserialize for QuarantinedMessage {
+    fn deserialize(bytes: &[u8]) -> trc::Result<Self> {
+        PickledStream::new(bytes)
+            .and_then(|mut stream| Self::unpickle(&mut stream))
+            .ok_or_else(|| {
+                trc::EventType::Registry(trc::RegistryEvent::DeserializationError)
+                    .into_err()
+                    .caused_by(trc::location!())
+                    .ctx(trc::Key::Value, bytes)
+            })
+    }
+}
+
 impl Deserialize for TlsInternalReport {
     fn deserialize(bytes: &[u8]) -> trc::Result<Self> {
         PickledStream::new(bytes)
@@ -309,6 +309,15 @@ impl BatchBuilder {
         self
     }
 
+    /// Bumps the revision of the current account, used by writes that are not
+    /// recorded in the changelog so incremental backups still pick them up.
+    pub fn log_unversioned_change(&mut self) -> &mut Self {
+        if self.current_account_id.is_some() {
+            self.add(ValueClass::Revision, 1);
+        }
+        self
+    }
+
     pub fn log_vanished_item(
         &mut self,
         collection: VanishedCollection,
@@ -352,6 +352,7 @@ impl ValueClass {
             },
             ValueClass::DocumentId => serializer.write(account_id).write(collection),
             ValueClass::ChangeId => serializer.write(account_id),
+            ValueClass::Revision => serializer.write(account_id).write(u8::MAX - 2),
             ValueClass::Quota => serializer.write(account_id).write(u8::MAX),
             ValueClass::TenantQuota(tenant_id) => serializer.write(*tenant_id).write(u8::MAX - 1),
             ValueClass::NodeId(node_id) => serializer.write(u32::MAX).write(*node_id),
@@ -468,6 +469,7 @@ const MAILBOX_COLLECTION: u8 = Collection::Mailbox as u8;
 const MAILBOX_COUNTER_FIELD: u8 = MailboxField::UidCounter as u8;
 const REG_ARCHIVED_ITEM: u16 = ObjectType::ArchivedItem as u16;
 const REG_SPAM_SAMPLE: u16 = ObjectType::SpamTrainingSample as u16;
+const REG_QUARANTINED_MESSAGE: u16 = ObjectType::QuarantinedMessage as u16;
 const REG_ACCOUNT: u16 = ObjectType::Account as u16;
 const REG_DOMAIN: u16 = ObjectType::Domain as u16;
 const REG_TENANT: u16 = ObjectType::Tenant as u16;
@@ -522,7 +524,10 @@ impl ValueClass {
             ValueClass::Telemetry(telemetry) => match telemetry {
                 TelemetryClass::Span(_) | TelemetryClass::Metric(_) => U64_LEN + 1,
             },
-            ValueClass::DocumentId | ValueClass::Quota | ValueClass::TenantQuota(_) => U32_LEN + 1,
+            ValueClass::DocumentId
+            | ValueClass::Revision
+            | ValueClass::Quota
+            | ValueClass::TenantQuota(_) => U32_LEN + 1,
             ValueClass::ChangeId => U32_LEN,
             ValueClass::ShareNotification { .. } => U32_LEN + U64_LEN + 1,
             ValueClass::NodeId(_) => (U16_LEN * 3) + 1,
@@ -557,7 +562,7 @@ impl ValueClass {
                 RegistryClass::Item { object_id, .. } => match *object_id {
                     REG_ACCOUNT | REG_DOMAIN | REG_TENANT | REG_ROLE | REG_OAUTH_CLIENT
                     | REG_MAILING_LIST | REG_MASKED_EMAIL | REG_PUBLIC_KEY => SUBSPACE_DIRECTORY,
-                    REG_ARCHIVED_ITEM => SUBSPACE_DELETED_ITEMS,
+                    REG_ARCHIVED_ITEM | REG_QUARANTINED_MESSAGE => SUBSPACE_DELETED_ITEMS,
                     REG_SPAM_SAMPLE => SUBSPACE_SPAM_SAMPLES,
                     REG_TRACE => SUBSPACE_TELEMETRY_SPAN,
                     REG_METRIC => SUBSPACE_TELEMETRY_METRIC,
@@ -591,6 +596,7 @@ impl ValueClass {
             },
             ValueClass::DocumentId
             | ValueClass::ChangeId
+            | ValueClass::Revision
             | ValueClass::Quota
             | ValueClass::TenantQuota(_) => SUBSPACE_COUNTER,
             ValueClass::ShareNotification { .. } => SUBSPACE_LOGS,
@@ -181,6 +181,7 @@ pub enum ValueClass {
     },
     DocumentId,
     ChangeId,
+    Revision,
     Quota,
     TenantQuota(u32),
     NodeId(u16),
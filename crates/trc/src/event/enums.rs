@@ -6,7 +6,7 @@
 
 // This file is auto-generated. Do not edit directly.
 
-pub const TOTAL_EVENT_COUNT: usize = 633;
+pub const TOTAL_EVENT_COUNT: usize = 666;
 pub const TOTAL_METRIC_COUNT: usize = 367;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -111,6 +111,7 @@ pub enum AuthEvent {
     Error = 34,
     Warning = 595,
     CredentialExpired = 276,
+    PasskeyRegistered = 665,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -336,6 +337,15 @@ pub enum ImapEvent {
     Unsubscribe = 194,
     Thread = 193,
     GetQuota = 57,
+    SetQuota = 650,
+    GenUrlAuth = 642,
+    ResetKey = 643,
+    UrlFetch = 644,
+    Notify = 645,
+    GetMetadata = 646,
+    SetMetadata = 647,
+    Compress = 648,
+    Replace = 649,
     Error = 168,
     RawInput = 183,
     RawOutput = 184,
@@ -462,6 +472,17 @@ pub enum MessageIngestEvent {
     Duplicate = 281,
     Error = 282,
     SearchIndex = 142,
+    ListDistributed = 651,
+    ListHeld = 652,
+    ListRejected = 653,
+    ListSubscribed = 654,
+    ListUnsubscribed = 655,
+    ListSuspended = 656,
+    Quarantined = 660,
+    QuarantineReleased = 661,
+    QuarantineDigestSent = 662,
+    TextExtracted = 663,
+    TextExtractFailed = 664,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -725,6 +746,15 @@ pub enum SmtpEvent {
     Expn = 429,
     ExpnNotFound = 431,
     ExpnDisabled = 430,
+    Etrn = 633,
+    EtrnNotAllowed = 634,
+    EtrnDisabled = 635,
+    Atrn = 636,
+    AtrnNotAllowed = 637,
+    AtrnDisabled = 638,
+    Burl = 639,
+    BurlFailed = 640,
+    BurlDisabled = 641,
     RequireTlsDisabled = 471,
     DeliverByDisabled = 418,
     DeliverByInvalid = 419,
@@ -759,6 +789,9 @@ pub enum SmtpEvent {
 pub enum SpamEvent {
     Pyzor = 564,
     PyzorError = 494,
+    Antivirus = 657,
+    VirusFound = 658,
+    AntivirusError = 659,
     Dnsbl = 562,
     DnsblError = 563,
     TrainStarted = 588,
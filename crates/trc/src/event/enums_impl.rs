@@ -51,6 +51,7 @@ impl EventType {
             b"auth.error" => EventType::Auth(AuthEvent::Error),
             b"auth.warning" => EventType::Auth(AuthEvent::Warning),
             b"auth.credential-expired" => EventType::Auth(AuthEvent::CredentialExpired),
+            b"auth.passkey-registered" => EventType::Auth(AuthEvent::PasskeyRegistered),
             b"calendar.rule-expansion-error" => EventType::Calendar(CalendarEvent::RuleExpansionError),
             b"calendar.alarm-sent" => EventType::Calendar(CalendarEvent::AlarmSent),
             b"calendar.alarm-skipped" => EventType::Calendar(CalendarEvent::AlarmSkipped),
@@ -226,6 +227,15 @@ impl EventType {
             b"imap.unsubscribe" => EventType::Imap(ImapEvent::Unsubscribe),
             b"imap.thread" => EventType::Imap(ImapEvent::Thread),
             b"imap.get-quota" => EventType::Imap(ImapEvent::GetQuota),
+            b"imap.set-quota" => EventType::Imap(ImapEvent::SetQuota),
+            b"imap.gen-url-auth" => EventType::Imap(ImapEvent::GenUrlAuth),
+            b"imap.reset-key" => EventType::Imap(ImapEvent::ResetKey),
+            b"imap.url-fetch" => EventType::Imap(ImapEvent::UrlFetch),
+            b"imap.notify" => EventType::Imap(ImapEvent::Notify),
+            b"imap.get-metadata" => EventType::Imap(ImapEvent::GetMetadata),
+            b"imap.set-metadata" => EventType::Imap(ImapEvent::SetMetadata),
+            b"imap.compress" => EventType::Imap(ImapEvent::Compress),
+            b"imap.replace" => EventType::Imap(ImapEvent::Replace),
             b"imap.error" => EventType::Imap(ImapEvent::Error),
             b"imap.raw-input" => EventType::Imap(ImapEvent::RawInput),
             b"imap.raw-output" => EventType::Imap(ImapEvent::RawOutput),
@@ -317,6 +327,17 @@ impl EventType {
             b"message-ingest.duplicate" => EventType::MessageIngest(MessageIngestEvent::Duplicate),
             b"message-ingest.error" => EventType::MessageIngest(MessageIngestEvent::Error),
             b"message-ingest.search-index" => EventType::MessageIngest(MessageIngestEvent::SearchIndex),
+            b"message-ingest.list-distributed" => EventType::MessageIngest(MessageIngestEvent::ListDistributed),
+            b"message-ingest.list-held" => EventType::MessageIngest(MessageIngestEvent::ListHeld),
+            b"message-ingest.list-rejected" => EventType::MessageIngest(MessageIngestEvent::ListRejected),
+            b"message-ingest.list-subscribed" => EventType::MessageIngest(MessageIngestEvent::ListSubscribed),
+            b"message-ingest.list-unsubscribed" => EventType::MessageIngest(MessageIngestEvent::ListUnsubscribed),
+            b"message-ingest.list-suspended" => EventType::MessageIngest(MessageIngestEvent::ListSuspended),
+            b"message-ingest.quarantined" => EventType::MessageIngest(MessageIngestEvent::Quarantined),
+            b"message-ingest.quarantine-released" => EventType::MessageIngest(MessageIngestEvent::QuarantineReleased),
+            b"message-ingest.quarantine-digest-sent" => EventType::MessageIngest(MessageIngestEvent::QuarantineDigestSent),
+            b"message-ingest.text-extracted" => EventType::MessageIngest(MessageIngestEvent::TextExtracted),
+            b"message-ingest.text-extract-failed" => EventType::MessageIngest(MessageIngestEvent::TextExtractFailed),
             b"milter.read" => EventType::Milter(MilterEvent::Read),
             b"milter.write" => EventType::Milter(MilterEvent::Write),
             b"milter.action-accept" => EventType::Milter(MilterEvent::ActionAccept),
@@ -510,6 +531,15 @@ impl EventType {
             b"smtp.expn" => EventType::Smtp(SmtpEvent::Expn),
             b"smtp.expn-not-found" => EventType::Smtp(SmtpEvent::ExpnNotFound),
             b"smtp.expn-disabled" => EventType::Smtp(SmtpEvent::ExpnDisabled),
+            b"smtp.etrn" => EventType::Smtp(SmtpEvent::Etrn),
+            b"smtp.etrn-not-allowed" => EventType::Smtp(SmtpEvent::EtrnNotAllowed),
+            b"smtp.etrn-disabled" => EventType::Smtp(SmtpEvent::EtrnDisabled),
+            b"smtp.atrn" => EventType::Smtp(SmtpEvent::Atrn),
+            b"smtp.atrn-not-allowed" => EventType::Smtp(SmtpEvent::AtrnNotAllowed),
+            b"smtp.atrn-disabled" => EventType::Smtp(SmtpEvent::AtrnDisabled),
+            b"smtp.burl" => EventType::Smtp(SmtpEvent::Burl),
+            b"smtp.burl-failed" => EventType::Smtp(SmtpEvent::BurlFailed),
+            b"smtp.burl-disabled" => EventType::Smtp(SmtpEvent::BurlDisabled),
             b"smtp.require-tls-disabled" => EventType::Smtp(SmtpEvent::RequireTlsDisabled),
             b"smtp.deliver-by-disabled" => EventType::Smtp(SmtpEvent::DeliverByDisabled),
             b"smtp.deliver-by-invalid" => EventType::Smtp(SmtpEvent::DeliverByInvalid),
@@ -539,6 +569,9 @@ impl EventType {
             b"smtp.request-too-large" => EventType::Smtp(SmtpEvent::RequestTooLarge),
             b"spam.pyzor" => EventType::Spam(SpamEvent::Pyzor),
             b"spam.pyzor-error" => EventType::Spam(SpamEvent::PyzorError),
+            b"spam.antivirus" => EventType::Spam(SpamEvent::Antivirus),
+            b"spam.virus-found" => EventType::Spam(SpamEvent::VirusFound),
+            b"spam.antivirus-error" => EventType::Spam(SpamEvent::AntivirusError),
             b"spam.dnsbl" => EventType::Spam(SpamEvent::Dnsbl),
             b"spam.dnsbl-error" => EventType::Spam(SpamEvent::DnsblError),
             b"spam.train-started" => EventType::Spam(SpamEvent::TrainStarted),
@@ -690,6 +723,7 @@ impl EventType {
             EventType::Auth(AuthEvent::Error) => "auth.error",
             EventType::Auth(AuthEvent::Warning) => "auth.warning",
             EventType::Auth(AuthEvent::CredentialExpired) => "auth.credential-expired",
+            EventType::Auth(AuthEvent::PasskeyRegistered) => "auth.passkey-registered",
             EventType::Calendar(CalendarEvent::RuleExpansionError) => {
                 "calendar.rule-expansion-error"
             }
@@ -887,6 +921,15 @@ impl EventType {
             EventType::Imap(ImapEvent::Unsubscribe) => "imap.unsubscribe",
             EventType::Imap(ImapEvent::Thread) => "imap.thread",
             EventType::Imap(ImapEvent::GetQuota) => "imap.get-quota",
+            EventType::Imap(ImapEvent::SetQuota) => "imap.set-quota",
+            EventType::Imap(ImapEvent::GenUrlAuth) => "imap.gen-url-auth",
+            EventType::Imap(ImapEvent::ResetKey) => "imap.reset-key",
+            EventType::Imap(ImapEvent::UrlFetch) => "imap.url-fetch",
+            EventType::Imap(ImapEvent::Notify) => "imap.notify",
+            EventType::Imap(ImapEvent::GetMetadata) => "imap.get-metadata",
+            EventType::Imap(ImapEvent::SetMetadata) => "imap.set-metadata",
+            EventType::Imap(ImapEvent::Compress) => "imap.compress",
+            EventType::Imap(ImapEvent::Replace) => "imap.replace",
             EventType::Imap(ImapEvent::Error) => "imap.error",
             EventType::Imap(ImapEvent::RawInput) => "imap.raw-input",
             EventType::Imap(ImapEvent::RawOutput) => "imap.raw-output",
@@ -1026,6 +1069,17 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => {
                 "message-ingest.search-index"
             }
+            EventType::MessageIngest(MessageIngestEvent::ListDistributed) => "message-ingest.list-distributed",
+            EventType::MessageIngest(MessageIngestEvent::ListHeld) => "message-ingest.list-held",
+            EventType::MessageIngest(MessageIngestEvent::ListRejected) => "message-ingest.list-rejected",
+            EventType::MessageIngest(MessageIngestEvent::ListSubscribed) => "message-ingest.list-subscribed",
+            EventType::MessageIngest(MessageIngestEvent::ListUnsubscribed) => "message-ingest.list-unsubscribed",
+            EventType::MessageIngest(MessageIngestEvent::ListSuspended) => "message-ingest.list-suspended",
+            EventType::MessageIngest(MessageIngestEvent::Quarantined) => "message-ingest.quarantined",
+            EventType::MessageIngest(MessageIngestEvent::QuarantineReleased) => "message-ingest.quarantine-released",
+            EventType::MessageIngest(MessageIngestEvent::QuarantineDigestSent) => "message-ingest.quarantine-digest-sent",
+            EventType::MessageIngest(MessageIngestEvent::TextExtracted) => "message-ingest.text-extracted",
+            EventType::MessageIngest(MessageIngestEvent::TextExtractFailed) => "message-ingest.text-extract-failed",
             EventType::Milter(MilterEvent::Read) => "milter.read",
             EventType::Milter(MilterEvent::Write) => "milter.write",
             EventType::Milter(MilterEvent::ActionAccept) => "milter.action-accept",
@@ -1265,6 +1319,15 @@ impl EventType {
             EventType::Smtp(SmtpEvent::Expn) => "smtp.expn",
             EventType::Smtp(SmtpEvent::ExpnNotFound) => "smtp.expn-not-found",
             EventType::Smtp(SmtpEvent::ExpnDisabled) => "smtp.expn-disabled",
+            EventType::Smtp(SmtpEvent::Etrn) => "smtp.etrn",
+            EventType::Smtp(SmtpEvent::EtrnNotAllowed) => "smtp.etrn-not-allowed",
+            EventType::Smtp(SmtpEvent::EtrnDisabled) => "smtp.etrn-disabled",
+            EventType::Smtp(SmtpEvent::Atrn) => "smtp.atrn",
+            EventType::Smtp(SmtpEvent::AtrnNotAllowed) => "smtp.atrn-not-allowed",
+            EventType::Smtp(SmtpEvent::AtrnDisabled) => "smtp.atrn-disabled",
+            EventType::Smtp(SmtpEvent::Burl) => "smtp.burl",
+            EventType::Smtp(SmtpEvent::BurlFailed) => "smtp.burl-failed",
+            EventType::Smtp(SmtpEvent::BurlDisabled) => "smtp.burl-disabled",
             EventType::Smtp(SmtpEvent::RequireTlsDisabled) => "smtp.require-tls-disabled",
             EventType::Smtp(SmtpEvent::DeliverByDisabled) => "smtp.deliver-by-disabled",
             EventType::Smtp(SmtpEvent::DeliverByInvalid) => "smtp.deliver-by-invalid",
@@ -1296,6 +1359,9 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge) => "smtp.request-too-large",
             EventType::Spam(SpamEvent::Pyzor) => "spam.pyzor",
             EventType::Spam(SpamEvent::PyzorError) => "spam.pyzor-error",
+            EventType::Spam(SpamEvent::Antivirus) => "spam.antivirus",
+            EventType::Spam(SpamEvent::VirusFound) => "spam.virus-found",
+            EventType::Spam(SpamEvent::AntivirusError) => "spam.antivirus-error",
             EventType::Spam(SpamEvent::Dnsbl) => "spam.dnsbl",
             EventType::Spam(SpamEvent::DnsblError) => "spam.dnsbl-error",
             EventType::Spam(SpamEvent::TrainStarted) => "spam.train-started",
@@ -1464,6 +1530,7 @@ impl EventType {
             EventType::Auth(AuthEvent::Error) => 34,
             EventType::Auth(AuthEvent::Warning) => 595,
             EventType::Auth(AuthEvent::CredentialExpired) => 276,
+            EventType::Auth(AuthEvent::PasskeyRegistered) => 665,
             EventType::Calendar(CalendarEvent::RuleExpansionError) => 576,
             EventType::Calendar(CalendarEvent::AlarmSent) => 579,
             EventType::Calendar(CalendarEvent::AlarmSkipped) => 580,
@@ -1639,6 +1706,15 @@ impl EventType {
             EventType::Imap(ImapEvent::Unsubscribe) => 194,
             EventType::Imap(ImapEvent::Thread) => 193,
             EventType::Imap(ImapEvent::GetQuota) => 57,
+            EventType::Imap(ImapEvent::SetQuota) => 650,
+            EventType::Imap(ImapEvent::GenUrlAuth) => 642,
+            EventType::Imap(ImapEvent::ResetKey) => 643,
+            EventType::Imap(ImapEvent::UrlFetch) => 644,
+            EventType::Imap(ImapEvent::Notify) => 645,
+            EventType::Imap(ImapEvent::GetMetadata) => 646,
+            EventType::Imap(ImapEvent::SetMetadata) => 647,
+            EventType::Imap(ImapEvent::Compress) => 648,
+            EventType::Imap(ImapEvent::Replace) => 649,
             EventType::Imap(ImapEvent::Error) => 168,
             EventType::Imap(ImapEvent::RawInput) => 183,
             EventType::Imap(ImapEvent::RawOutput) => 184,
@@ -1730,6 +1806,17 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::Duplicate) => 281,
             EventType::MessageIngest(MessageIngestEvent::Error) => 282,
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => 142,
+            EventType::MessageIngest(MessageIngestEvent::ListDistributed) => 651,
+            EventType::MessageIngest(MessageIngestEvent::ListHeld) => 652,
+            EventType::MessageIngest(MessageIngestEvent::ListRejected) => 653,
+            EventType::MessageIngest(MessageIngestEvent::ListSubscribed) => 654,
+            EventType::MessageIngest(MessageIngestEvent::ListUnsubscribed) => 655,
+            EventType::MessageIngest(MessageIngestEvent::ListSuspended) => 656,
+            EventType::MessageIngest(MessageIngestEvent::Quarantined) => 660,
+            EventType::MessageIngest(MessageIngestEvent::QuarantineReleased) => 661,
+            EventType::MessageIngest(MessageIngestEvent::QuarantineDigestSent) => 662,
+            EventType::MessageIngest(MessageIngestEvent::TextExtracted) => 663,
+            EventType::MessageIngest(MessageIngestEvent::TextExtractFailed) => 664,
             EventType::Milter(MilterEvent::Read) => 299,
             EventType::Milter(MilterEvent::Write) => 303,
             EventType::Milter(MilterEvent::ActionAccept) => 287,
@@ -1923,6 +2010,15 @@ impl EventType {
             EventType::Smtp(SmtpEvent::Expn) => 429,
             EventType::Smtp(SmtpEvent::ExpnNotFound) => 431,
             EventType::Smtp(SmtpEvent::ExpnDisabled) => 430,
+            EventType::Smtp(SmtpEvent::Etrn) => 633,
+            EventType::Smtp(SmtpEvent::EtrnNotAllowed) => 634,
+            EventType::Smtp(SmtpEvent::EtrnDisabled) => 635,
+            EventType::Smtp(SmtpEvent::Atrn) => 636,
+            EventType::Smtp(SmtpEvent::AtrnNotAllowed) => 637,
+            EventType::Smtp(SmtpEvent::AtrnDisabled) => 638,
+            EventType::Smtp(SmtpEvent::Burl) => 639,
+            EventType::Smtp(SmtpEvent::BurlFailed) => 640,
+            EventType::Smtp(SmtpEvent::BurlDisabled) => 641,
             EventType::Smtp(SmtpEvent::RequireTlsDisabled) => 471,
             EventType::Smtp(SmtpEvent::DeliverByDisabled) => 418,
             EventType::Smtp(SmtpEvent::DeliverByInvalid) => 419,
@@ -1952,6 +2048,9 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge) => 470,
             EventType::Spam(SpamEvent::Pyzor) => 564,
             EventType::Spam(SpamEvent::PyzorError) => 494,
+            EventType::Spam(SpamEvent::Antivirus) => 657,
+            EventType::Spam(SpamEvent::VirusFound) => 658,
+            EventType::Spam(SpamEvent::AntivirusError) => 659,
             EventType::Spam(SpamEvent::Dnsbl) => 562,
             EventType::Spam(SpamEvent::DnsblError) => 563,
             EventType::Spam(SpamEvent::TrainStarted) => 588,
@@ -2102,6 +2201,7 @@ impl EventType {
             34 => Some(EventType::Auth(AuthEvent::Error)),
             595 => Some(EventType::Auth(AuthEvent::Warning)),
             276 => Some(EventType::Auth(AuthEvent::CredentialExpired)),
+            665 => Some(EventType::Auth(AuthEvent::PasskeyRegistered)),
             576 => Some(EventType::Calendar(CalendarEvent::RuleExpansionError)),
             579 => Some(EventType::Calendar(CalendarEvent::AlarmSent)),
             580 => Some(EventType::Calendar(CalendarEvent::AlarmSkipped)),
@@ -2277,6 +2377,15 @@ impl EventType {
             194 => Some(EventType::Imap(ImapEvent::Unsubscribe)),
             193 => Some(EventType::Imap(ImapEvent::Thread)),
             57 => Some(EventType::Imap(ImapEvent::GetQuota)),
+            650 => Some(EventType::Imap(ImapEvent::SetQuota)),
+            642 => Some(EventType::Imap(ImapEvent::GenUrlAuth)),
+            643 => Some(EventType::Imap(ImapEvent::ResetKey)),
+            644 => Some(EventType::Imap(ImapEvent::UrlFetch)),
+            645 => Some(EventType::Imap(ImapEvent::Notify)),
+            646 => Some(EventType::Imap(ImapEvent::GetMetadata)),
+            647 => Some(EventType::Imap(ImapEvent::SetMetadata)),
+            648 => Some(EventType::Imap(ImapEvent::Compress)),
+            649 => Some(EventType::Imap(ImapEvent::Replace)),
             168 => Some(EventType::Imap(ImapEvent::Error)),
             183 => Some(EventType::Imap(ImapEvent::RawInput)),
             184 => Some(EventType::Imap(ImapEvent::RawOutput)),
@@ -2386,6 +2495,17 @@ impl EventType {
             281 => Some(EventType::MessageIngest(MessageIngestEvent::Duplicate)),
             282 => Some(EventType::MessageIngest(MessageIngestEvent::Error)),
             142 => Some(EventType::MessageIngest(MessageIngestEvent::SearchIndex)),
+            651 => Some(EventType::MessageIngest(MessageIngestEvent::ListDistributed)),
+            652 => Some(EventType::MessageIngest(MessageIngestEvent::ListHeld)),
+            653 => Some(EventType::MessageIngest(MessageIngestEvent::ListRejected)),
+            654 => Some(EventType::MessageIngest(MessageIngestEvent::ListSubscribed)),
+            655 => Some(EventType::MessageIngest(MessageIngestEvent::ListUnsubscribed)),
+            656 => Some(EventType::MessageIngest(MessageIngestEvent::ListSuspended)),
+            660 => Some(EventType::MessageIngest(MessageIngestEvent::Quarantined)),
+            661 => Some(EventType::MessageIngest(MessageIngestEvent::QuarantineReleased)),
+            662 => Some(EventType::MessageIngest(MessageIngestEvent::QuarantineDigestSent)),
+            663 => Some(EventType::MessageIngest(MessageIngestEvent::TextExtracted)),
+            664 => Some(EventType::MessageIngest(MessageIngestEvent::TextExtractFailed)),
             299 => Some(EventType::Milter(MilterEvent::Read)),
             303 => Some(EventType::Milter(MilterEvent::Write)),
             287 => Some(EventType::Milter(MilterEvent::ActionAccept)),
@@ -2599,6 +2719,15 @@ impl EventType {
             429 => Some(EventType::Smtp(SmtpEvent::Expn)),
             431 => Some(EventType::Smtp(SmtpEvent::ExpnNotFound)),
             430 => Some(EventType::Smtp(SmtpEvent::ExpnDisabled)),
+            633 => Some(EventType::Smtp(SmtpEvent::Etrn)),
+            634 => Some(EventType::Smtp(SmtpEvent::EtrnNotAllowed)),
+            635 => Some(EventType::Smtp(SmtpEvent::EtrnDisabled)),
+            636 => Some(EventType::Smtp(SmtpEvent::Atrn)),
+            637 => Some(EventType::Smtp(SmtpEvent::AtrnNotAllowed)),
+            638 => Some(EventType::Smtp(SmtpEvent::AtrnDisabled)),
+            639 => Some(EventType::Smtp(SmtpEvent::Burl)),
+            640 => Some(EventType::Smtp(SmtpEvent::BurlFailed)),
+            641 => Some(EventType::Smtp(SmtpEvent::BurlDisabled)),
             471 => Some(EventType::Smtp(SmtpEvent::RequireTlsDisabled)),
             418 => Some(EventType::Smtp(SmtpEvent::DeliverByDisabled)),
             419 => Some(EventType::Smtp(SmtpEvent::DeliverByInvalid)),
@@ -2628,6 +2757,9 @@ impl EventType {
             470 => Some(EventType::Smtp(SmtpEvent::RequestTooLarge)),
             564 => Some(EventType::Spam(SpamEvent::Pyzor)),
             494 => Some(EventType::Spam(SpamEvent::PyzorError)),
+            657 => Some(EventType::Spam(SpamEvent::Antivirus)),
+            658 => Some(EventType::Spam(SpamEvent::VirusFound)),
+            659 => Some(EventType::Spam(SpamEvent::AntivirusError)),
             562 => Some(EventType::Spam(SpamEvent::Dnsbl)),
             563 => Some(EventType::Spam(SpamEvent::DnsblError)),
             588 => Some(EventType::Spam(SpamEvent::TrainStarted)),
@@ -2806,6 +2938,7 @@ impl EventType {
             EventType::Acme(AcmeEvent::TlsAlpnReceived) => Level::Info,
             EventType::Auth(AuthEvent::Success) => Level::Info,
             EventType::Auth(AuthEvent::ClientRegistration) => Level::Info,
+            EventType::Auth(AuthEvent::PasskeyRegistered) => Level::Info,
             EventType::Calendar(CalendarEvent::AlarmSent) => Level::Info,
             EventType::Calendar(CalendarEvent::ItipMessageSent) => Level::Info,
             EventType::Calendar(CalendarEvent::ItipMessageReceived) => Level::Info,
@@ -2879,6 +3012,15 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::JmapAppend) => Level::Info,
             EventType::MessageIngest(MessageIngestEvent::Duplicate) => Level::Info,
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::ListDistributed) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::ListHeld) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::ListRejected) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::ListSubscribed) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::ListUnsubscribed) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::ListSuspended) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::Quarantined) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::QuarantineReleased) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::QuarantineDigestSent) => Level::Info,
             EventType::Milter(MilterEvent::ActionAccept) => Level::Info,
             EventType::Milter(MilterEvent::ActionDiscard) => Level::Info,
             EventType::Milter(MilterEvent::ActionReject) => Level::Info,
@@ -2981,6 +3123,15 @@ impl EventType {
             EventType::Smtp(SmtpEvent::Expn) => Level::Info,
             EventType::Smtp(SmtpEvent::ExpnNotFound) => Level::Info,
             EventType::Smtp(SmtpEvent::ExpnDisabled) => Level::Info,
+            EventType::Smtp(SmtpEvent::Etrn) => Level::Info,
+            EventType::Smtp(SmtpEvent::EtrnNotAllowed) => Level::Info,
+            EventType::Smtp(SmtpEvent::EtrnDisabled) => Level::Info,
+            EventType::Smtp(SmtpEvent::Atrn) => Level::Info,
+            EventType::Smtp(SmtpEvent::AtrnNotAllowed) => Level::Info,
+            EventType::Smtp(SmtpEvent::AtrnDisabled) => Level::Info,
+            EventType::Smtp(SmtpEvent::Burl) => Level::Info,
+            EventType::Smtp(SmtpEvent::BurlFailed) => Level::Info,
+            EventType::Smtp(SmtpEvent::BurlDisabled) => Level::Info,
             EventType::Smtp(SmtpEvent::AuthNotAllowed) => Level::Info,
             EventType::Smtp(SmtpEvent::AuthMechanismNotSupported) => Level::Info,
             EventType::Smtp(SmtpEvent::RequestTooLarge) => Level::Info,
@@ -3003,6 +3154,7 @@ impl EventType {
             EventType::TlsRpt(TlsRptEvent::RecordFetch) => Level::Info,
             EventType::TlsRpt(TlsRptEvent::RecordFetchError) => Level::Info,
             EventType::TlsRpt(TlsRptEvent::RecordNotFound) => Level::Info,
+            EventType::Imap(ImapEvent::SetQuota) => Level::Info,
             EventType::Ai(AiEvent::LlmResponse) => Level::Trace,
             EventType::Auth(AuthEvent::MfaRequired) => Level::Trace,
             EventType::Cluster(ClusterEvent::MessageReceived) => Level::Trace,
@@ -3078,6 +3230,9 @@ impl EventType {
             EventType::Smtp(SmtpEvent::IdNotFound) => Level::Warn,
             EventType::Smtp(SmtpEvent::MissingLocalHostname) => Level::Warn,
             EventType::Spam(SpamEvent::TrainSampleNotFound) => Level::Warn,
+            EventType::Spam(SpamEvent::Antivirus) => Level::Info,
+            EventType::Spam(SpamEvent::VirusFound) => Level::Warn,
+            EventType::Spam(SpamEvent::AntivirusError) => Level::Warn,
             EventType::Store(StoreEvent::HttpStoreError) => Level::Warn,
             EventType::Store(StoreEvent::BlobMissingMarker) => Level::Warn,
             EventType::TaskManager(TaskManagerEvent::TaskFailed) => Level::Warn,
@@ -3135,6 +3290,7 @@ impl EventType {
             EventType::Auth(AuthEvent::Error) => "Authentication error",
             EventType::Auth(AuthEvent::Warning) => "Authentication warning",
             EventType::Auth(AuthEvent::CredentialExpired) => "Credential expired",
+            EventType::Auth(AuthEvent::PasskeyRegistered) => "Passkey registered",
             EventType::Calendar(CalendarEvent::RuleExpansionError) => {
                 "Calendar rule expansion error"
             }
@@ -3342,6 +3498,15 @@ impl EventType {
             EventType::Imap(ImapEvent::Unsubscribe) => "IMAP UNSUBSCRIBE command",
             EventType::Imap(ImapEvent::Thread) => "IMAP THREAD command",
             EventType::Imap(ImapEvent::GetQuota) => "IMAP GETQUOTA command",
+            EventType::Imap(ImapEvent::SetQuota) => "IMAP SETQUOTA command",
+            EventType::Imap(ImapEvent::GenUrlAuth) => "IMAP GENURLAUTH command",
+            EventType::Imap(ImapEvent::ResetKey) => "IMAP RESETKEY command",
+            EventType::Imap(ImapEvent::UrlFetch) => "IMAP URLFETCH command",
+            EventType::Imap(ImapEvent::Notify) => "IMAP NOTIFY command",
+            EventType::Imap(ImapEvent::GetMetadata) => "IMAP GETMETADATA command",
+            EventType::Imap(ImapEvent::SetMetadata) => "IMAP SETMETADATA command",
+            EventType::Imap(ImapEvent::Compress) => "IMAP COMPRESS command",
+            EventType::Imap(ImapEvent::Replace) => "IMAP REPLACE command",
             EventType::Imap(ImapEvent::Error) => "IMAP error occurred",
             EventType::Imap(ImapEvent::RawInput) => "Raw IMAP input received",
             EventType::Imap(ImapEvent::RawOutput) => "Raw IMAP output sent",
@@ -3479,6 +3644,17 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::Duplicate) => "Skipping duplicate message",
             EventType::MessageIngest(MessageIngestEvent::Error) => "Message ingestion error",
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => "Search index updated",
+            EventType::MessageIngest(MessageIngestEvent::ListDistributed) => "Mailing list message distributed",
+            EventType::MessageIngest(MessageIngestEvent::ListHeld) => "Mailing list message held",
+            EventType::MessageIngest(MessageIngestEvent::ListRejected) => "Mailing list message rejected",
+            EventType::MessageIngest(MessageIngestEvent::ListSubscribed) => "Mailing list subscription added",
+            EventType::MessageIngest(MessageIngestEvent::ListUnsubscribed) => "Mailing list subscription removed",
+            EventType::MessageIngest(MessageIngestEvent::ListSuspended) => "Mailing list subscriber suspended",
+            EventType::MessageIngest(MessageIngestEvent::Quarantined) => "Message quarantined",
+            EventType::MessageIngest(MessageIngestEvent::QuarantineReleased) => "Quarantined message released",
+            EventType::MessageIngest(MessageIngestEvent::QuarantineDigestSent) => "Quarantine digest sent",
+            EventType::MessageIngest(MessageIngestEvent::TextExtracted) => "Text extracted",
+            EventType::MessageIngest(MessageIngestEvent::TextExtractFailed) => "Text extraction failed",
             EventType::Milter(MilterEvent::Read) => "Reading from Milter",
             EventType::Milter(MilterEvent::Write) => "Writing to Milter",
             EventType::Milter(MilterEvent::ActionAccept) => "Milter action: Accept",
@@ -3720,6 +3896,15 @@ impl EventType {
             EventType::Smtp(SmtpEvent::Expn) => "SMTP EXPN command",
             EventType::Smtp(SmtpEvent::ExpnNotFound) => "EXPN address not found",
             EventType::Smtp(SmtpEvent::ExpnDisabled) => "EXPN command disabled",
+            EventType::Smtp(SmtpEvent::Etrn) => "SMTP ETRN command",
+            EventType::Smtp(SmtpEvent::EtrnNotAllowed) => "ETRN node not allowed",
+            EventType::Smtp(SmtpEvent::EtrnDisabled) => "ETRN command disabled",
+            EventType::Smtp(SmtpEvent::Atrn) => "SMTP ATRN command",
+            EventType::Smtp(SmtpEvent::AtrnNotAllowed) => "ATRN domain not allowed",
+            EventType::Smtp(SmtpEvent::AtrnDisabled) => "ATRN command disabled",
+            EventType::Smtp(SmtpEvent::Burl) => "SMTP BURL command",
+            EventType::Smtp(SmtpEvent::BurlFailed) => "BURL URL resolution failed",
+            EventType::Smtp(SmtpEvent::BurlDisabled) => "BURL command disabled",
             EventType::Smtp(SmtpEvent::RequireTlsDisabled) => "REQUIRETLS extension disabled",
             EventType::Smtp(SmtpEvent::DeliverByDisabled) => "DELIVERBY extension disabled",
             EventType::Smtp(SmtpEvent::DeliverByInvalid) => "Invalid DELIVERBY parameter",
@@ -3751,6 +3936,9 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge) => "Request too large",
             EventType::Spam(SpamEvent::Pyzor) => "Pyzor success",
             EventType::Spam(SpamEvent::PyzorError) => "Pyzor error",
+            EventType::Spam(SpamEvent::Antivirus) => "Antivirus scan",
+            EventType::Spam(SpamEvent::VirusFound) => "Virus found",
+            EventType::Spam(SpamEvent::AntivirusError) => "Antivirus error",
             EventType::Spam(SpamEvent::Dnsbl) => "DNSBL query",
             EventType::Spam(SpamEvent::DnsblError) => "Error querying DNSBL",
             EventType::Spam(SpamEvent::TrainStarted) => "Spam classifier training started",
@@ -3892,6 +4080,7 @@ impl EventType {
             EventType::Auth(AuthEvent::ClientRegistration) => "Authentication error",
             EventType::Auth(AuthEvent::Error) => "Authentication error",
             EventType::Auth(AuthEvent::CredentialExpired) => "Credential expired",
+            EventType::Auth(AuthEvent::PasskeyRegistered) => "Authentication error",
             EventType::Imap(ImapEvent::ConnectionStart) => "IMAP error",
             EventType::Imap(ImapEvent::ConnectionEnd) => "IMAP error",
             EventType::Imap(ImapEvent::GetAcl) => "IMAP error",
@@ -3926,6 +4115,15 @@ impl EventType {
             EventType::Imap(ImapEvent::Unsubscribe) => "IMAP error",
             EventType::Imap(ImapEvent::Thread) => "IMAP error",
             EventType::Imap(ImapEvent::GetQuota) => "IMAP error",
+            EventType::Imap(ImapEvent::SetQuota) => "IMAP error",
+            EventType::Imap(ImapEvent::GenUrlAuth) => "IMAP error",
+            EventType::Imap(ImapEvent::ResetKey) => "IMAP error",
+            EventType::Imap(ImapEvent::UrlFetch) => "IMAP error",
+            EventType::Imap(ImapEvent::Notify) => "IMAP error",
+            EventType::Imap(ImapEvent::GetMetadata) => "IMAP error",
+            EventType::Imap(ImapEvent::SetMetadata) => "IMAP error",
+            EventType::Imap(ImapEvent::Compress) => "IMAP error",
+            EventType::Imap(ImapEvent::Replace) => "IMAP error",
             EventType::Imap(ImapEvent::Error) => "IMAP error",
             EventType::Imap(ImapEvent::RawInput) => "IMAP error",
             EventType::Imap(ImapEvent::RawOutput) => "IMAP error",
@@ -4082,6 +4280,15 @@ impl EventType {
             EventType::Smtp(SmtpEvent::Expn) => "SMTP error",
             EventType::Smtp(SmtpEvent::ExpnNotFound) => "SMTP error",
             EventType::Smtp(SmtpEvent::ExpnDisabled) => "SMTP error",
+            EventType::Smtp(SmtpEvent::Etrn) => "SMTP error",
+            EventType::Smtp(SmtpEvent::EtrnNotAllowed) => "SMTP error",
+            EventType::Smtp(SmtpEvent::EtrnDisabled) => "SMTP error",
+            EventType::Smtp(SmtpEvent::Atrn) => "SMTP error",
+            EventType::Smtp(SmtpEvent::AtrnNotAllowed) => "SMTP error",
+            EventType::Smtp(SmtpEvent::AtrnDisabled) => "SMTP error",
+            EventType::Smtp(SmtpEvent::Burl) => "SMTP error",
+            EventType::Smtp(SmtpEvent::BurlFailed) => "SMTP error",
+            EventType::Smtp(SmtpEvent::BurlDisabled) => "SMTP error",
             EventType::Smtp(SmtpEvent::RequireTlsDisabled) => "SMTP error",
             EventType::Smtp(SmtpEvent::DeliverByDisabled) => "SMTP error",
             EventType::Smtp(SmtpEvent::DeliverByInvalid) => "SMTP error",
@@ -4189,6 +4396,7 @@ impl EventType {
             EventType::Auth(AuthEvent::Error),
             EventType::Auth(AuthEvent::Warning),
             EventType::Auth(AuthEvent::CredentialExpired),
+            EventType::Auth(AuthEvent::PasskeyRegistered),
             EventType::Calendar(CalendarEvent::RuleExpansionError),
             EventType::Calendar(CalendarEvent::AlarmSent),
             EventType::Calendar(CalendarEvent::AlarmSkipped),
@@ -4364,6 +4572,15 @@ impl EventType {
             EventType::Imap(ImapEvent::Unsubscribe),
             EventType::Imap(ImapEvent::Thread),
             EventType::Imap(ImapEvent::GetQuota),
+            EventType::Imap(ImapEvent::SetQuota),
+            EventType::Imap(ImapEvent::GenUrlAuth),
+            EventType::Imap(ImapEvent::ResetKey),
+            EventType::Imap(ImapEvent::UrlFetch),
+            EventType::Imap(ImapEvent::Notify),
+            EventType::Imap(ImapEvent::GetMetadata),
+            EventType::Imap(ImapEvent::SetMetadata),
+            EventType::Imap(ImapEvent::Compress),
+            EventType::Imap(ImapEvent::Replace),
             EventType::Imap(ImapEvent::Error),
             EventType::Imap(ImapEvent::RawInput),
             EventType::Imap(ImapEvent::RawOutput),
@@ -4455,6 +4672,17 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::Duplicate),
             EventType::MessageIngest(MessageIngestEvent::Error),
             EventType::MessageIngest(MessageIngestEvent::SearchIndex),
+            EventType::MessageIngest(MessageIngestEvent::ListDistributed),
+            EventType::MessageIngest(MessageIngestEvent::ListHeld),
+            EventType::MessageIngest(MessageIngestEvent::ListRejected),
+            EventType::MessageIngest(MessageIngestEvent::ListSubscribed),
+            EventType::MessageIngest(MessageIngestEvent::ListUnsubscribed),
+            EventType::MessageIngest(MessageIngestEvent::ListSuspended),
+            EventType::MessageIngest(MessageIngestEvent::Quarantined),
+            EventType::MessageIngest(MessageIngestEvent::QuarantineReleased),
+            EventType::MessageIngest(MessageIngestEvent::QuarantineDigestSent),
+            EventType::MessageIngest(MessageIngestEvent::TextExtracted),
+            EventType::MessageIngest(MessageIngestEvent::TextExtractFailed),
             EventType::Milter(MilterEvent::Read),
             EventType::Milter(MilterEvent::Write),
             EventType::Milter(MilterEvent::ActionAccept),
@@ -4648,6 +4876,15 @@ impl EventType {
             EventType::Smtp(SmtpEvent::Expn),
             EventType::Smtp(SmtpEvent::ExpnNotFound),
             EventType::Smtp(SmtpEvent::ExpnDisabled),
+            EventType::Smtp(SmtpEvent::Etrn),
+            EventType::Smtp(SmtpEvent::EtrnNotAllowed),
+            EventType::Smtp(SmtpEvent::EtrnDisabled),
+            EventType::Smtp(SmtpEvent::Atrn),
+            EventType::Smtp(SmtpEvent::AtrnNotAllowed),
+            EventType::Smtp(SmtpEvent::AtrnDisabled),
+            EventType::Smtp(SmtpEvent::Burl),
+            EventType::Smtp(SmtpEvent::BurlFailed),
+            EventType::Smtp(SmtpEvent::BurlDisabled),
             EventType::Smtp(SmtpEvent::RequireTlsDisabled),
             EventType::Smtp(SmtpEvent::DeliverByDisabled),
             EventType::Smtp(SmtpEvent::DeliverByInvalid),
@@ -4677,6 +4914,9 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge),
             EventType::Spam(SpamEvent::Pyzor),
             EventType::Spam(SpamEvent::PyzorError),
+            EventType::Spam(SpamEvent::Antivirus),
+            EventType::Spam(SpamEvent::VirusFound),
+            EventType::Spam(SpamEvent::AntivirusError),
             EventType::Spam(SpamEvent::Dnsbl),
             EventType::Spam(SpamEvent::DnsblError),
             EventType::Spam(SpamEvent::TrainStarted),
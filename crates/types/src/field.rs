@@ -42,12 +42,16 @@ pub enum EmailField {
     Metadata,
     Threading,
     DeletedAt,
+    SmimeVerification,
+    SavedAt,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum MailboxField {
     UidCounter = 84,
+    AccessKey = 85,
+    ImapMetadata = 86,
     Archive = ARCHIVE_FIELD,
 }
 
@@ -82,6 +86,7 @@ pub enum PrincipalField {
     DefaultAddressBookId = 48,
     ActiveScriptId = 49,
     PushSubscriptions = 44,
+    ImapMetadata = 52,
 }
 
 impl From<ContactField> for u8 {
@@ -119,6 +124,8 @@ impl From<EmailField> for u8 {
             EmailField::Metadata => 71,
             EmailField::Threading => 90,
             EmailField::DeletedAt => 91,
+            EmailField::SmimeVerification => 92,
+            EmailField::SavedAt => 93,
             EmailField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -128,6 +135,8 @@ impl From<MailboxField> for u8 {
     fn from(value: MailboxField) -> Self {
         match value {
             MailboxField::UidCounter => 84,
+            MailboxField::AccessKey => 85,
+            MailboxField::ImapMetadata => 86,
             MailboxField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -160,6 +169,7 @@ impl From<PrincipalField> for u8 {
             PrincipalField::DefaultAddressBookId => 48,
             PrincipalField::ActiveScriptId => 49,
             PrincipalField::PushSubscriptions => 44,
+            PrincipalField::ImapMetadata => 52,
             PrincipalField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -0,0 +1,133 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AssertResult, Type};
+use crate::utils::server::TestServer;
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress};
+use imap_proto::ResponseType;
+use std::time::Duration;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+struct DeflateConnection {
+    stream: TcpStream,
+    compress: Compress,
+    decompress: Decompress,
+    buf: Vec<u8>,
+}
+
+pub async fn test(test: &TestServer) {
+    println!("Running COMPRESS tests...");
+
+    let account = test.account("jdoe@example.com");
+    let mut imap = account.imap_client().await;
+
+    imap.send("CAPABILITY").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("COMPRESS=DEFLATE");
+    imap.send("COMPRESS LZMA").await;
+    imap.assert_read(Type::Tagged, ResponseType::Bad).await;
+    imap.send("COMPRESS DEFLATE").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("DEFLATE active");
+
+    let mut imap = DeflateConnection {
+        stream: imap.into_inner(),
+        compress: Compress::new(Compression::default(), false),
+        decompress: Decompress::new(false),
+        buf: Vec::new(),
+    };
+
+    // Commands and responses are compressed
+    imap.send("C1 CREATE Compressed\r\n").await;
+    imap.read_until("C1 OK").await;
+    imap.send("C2 COMPRESS DEFLATE\r\n").await;
+    assert!(
+        imap.read_until("C2 ")
+            .await
+            .contains("C2 NO [COMPRESSIONACTIVE]")
+    );
+    imap.send("C3 STARTTLS\r\n").await;
+    imap.read_until("C3 NO").await;
+
+    // Synchronizing literals
+    let message = "From: test@example.com\r\nSubject: compressed\r\n\r\nhello\r\n";
+    imap.send(&format!("C4 APPEND Compressed {{{}}}\r\n", message.len()))
+        .await;
+    imap.read_until("+ ").await;
+    imap.send(&format!("{message}\r\n")).await;
+    imap.read_until("C4 OK").await;
+
+    // Idle
+    imap.send("C5 SELECT Compressed\r\n").await;
+    imap.read_until("C5 OK").await;
+    imap.send("C5 IDLE\r\n").await;
+    imap.read_until("+ ").await;
+    imap.send("DONE\r\n").await;
+    imap.read_until("C5 OK").await;
+
+    imap.send("C6 FETCH * (BODY.PEEK[HEADER.FIELDS (SUBJECT)])\r\n")
+        .await;
+    assert!(
+        imap.read_until("C6 OK")
+            .await
+            .contains("Subject: compressed")
+    );
+    imap.send("C7 UNSELECT\r\n").await;
+    imap.read_until("C7 OK").await;
+    imap.send("C7 DELETE Compressed\r\n").await;
+    imap.read_until("C7 OK").await;
+    imap.send("C8 LOGOUT\r\n").await;
+    imap.read_until("C8 OK").await;
+}
+
+impl DeflateConnection {
+    async fn send(&mut self, text: &str) {
+        let mut buf = Vec::with_capacity(text.len() + 64);
+        self.compress
+            .compress_vec(text.as_bytes(), &mut buf, FlushCompress::Sync)
+            .unwrap();
+        self.stream.write_all(&buf).await.unwrap();
+    }
+
+    async fn read_until(&mut self, pattern: &str) -> String {
+        let mut response = String::new();
+        let mut buf = vec![0u8; 4096];
+
+        loop {
+            let bytes_read =
+                tokio::time::timeout(Duration::from_secs(5), self.stream.read(&mut buf))
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert!(bytes_read > 0, "Connection closed, got {response:?}");
+            self.buf.extend_from_slice(&buf[..bytes_read]);
+
+            let mut output = Vec::with_capacity(8192);
+            loop {
+                let total_in = self.decompress.total_in();
+                self.decompress
+                    .decompress_vec(&self.buf, &mut output, FlushDecompress::None)
+                    .unwrap();
+                self.buf
+                    .drain(..(self.decompress.total_in() - total_in) as usize);
+                if self.buf.is_empty() || output.len() < output.capacity() {
+                    break;
+                }
+                output.reserve(8192);
+            }
+            response.push_str(&String::from_utf8_lossy(&output));
+
+            if response.lines().any(|line| line.starts_with(pattern)) {
+                return response;
+            }
+        }
+    }
+}
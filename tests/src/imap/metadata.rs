@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AssertResult, ImapConnection, Type};
+use imap_proto::ResponseType;
+
+pub async fn test(imap: &mut ImapConnection, imap_check: &mut ImapConnection) {
+    println!("Running METADATA tests...");
+
+    imap.send("CAPABILITY").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("METADATA")
+        .assert_contains("METADATA-SERVER");
+
+    // Mailbox entries
+    imap.send_ok("CREATE Annotated").await;
+    imap.send_ok(
+        "SETMETADATA Annotated (/private/comment \"My comment\" /shared/comment {14+}\r\nLine 1\r\nLine 2)",
+    )
+    .await;
+    imap.send_ok(
+        "SETMETADATA Annotated (/private/vendor/acme/a \"1\" /private/vendor/acme/b/c \"2\")",
+    )
+    .await;
+    imap_check
+        .send("GETMETADATA Annotated (/private/comment /shared/comment /shared/missing)")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(
+            "* METADATA \"Annotated\" (/private/comment \"My comment\" /shared/comment {14}",
+        );
+
+    // Depth and maximum size
+    imap_check
+        .send("GETMETADATA (DEPTH 1) Annotated /private/vendor/acme")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("/private/vendor/acme/a \"1\"")
+        .assert_not_contains("/private/vendor/acme/b/c");
+    imap_check
+        .send("GETMETADATA (DEPTH infinity MAXSIZE 10) Annotated /private/vendor")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("/private/vendor/acme/b/c \"2\"");
+    imap_check
+        .send("GETMETADATA (MAXSIZE 5) Annotated (/private/comment /shared/comment)")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_not_contains("* METADATA")
+        .assert_response_code("METADATA LONGENTRIES 14");
+
+    // Removing entries
+    imap.send_ok("SETMETADATA Annotated (/private/comment NIL)")
+        .await;
+    imap_check
+        .send("GETMETADATA Annotated (/private/comment /shared/comment)")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_not_contains("/private/comment")
+        .assert_contains("/shared/comment");
+
+    // Limits and errors
+    imap.send(&format!(
+        "SETMETADATA Annotated (/private/large {{70000+}}\r\n{})",
+        "a".repeat(70000)
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_response_code("METADATA MAXSIZE 65536");
+    imap.send("SETMETADATA Annotated (/private/comment* \"invalid\")")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::Bad).await;
+    imap.send("GETMETADATA \"Does not exist\" /private/comment")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_response_code("NONEXISTENT");
+
+    // Server entries
+    imap.send_ok("SETMETADATA \"\" (/private/vendor/acme/theme \"dark\")")
+        .await;
+    imap.send("SETMETADATA \"\" (/shared/admin \"mailto:admin@example.com\")")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_response_code("NOPERM");
+    imap_check
+        .send("GETMETADATA (DEPTH infinity) \"\" (/private/vendor /shared/admin)")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("* METADATA \"\" (/private/vendor/acme/theme \"dark\")");
+    imap.send_ok("SETMETADATA \"\" (/private/vendor/acme/theme NIL)")
+        .await;
+    imap_check
+        .send("GETMETADATA (DEPTH infinity) \"\" /private/vendor")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_not_contains("* METADATA");
+
+    imap.send_ok("DELETE Annotated").await;
+}
@@ -9,17 +9,23 @@ pub mod antispam;
 pub mod append;
 pub mod basic;
 pub mod body_structure;
+pub mod compress;
 pub mod condstore;
 pub mod copy_move;
 pub mod fetch;
 pub mod idle;
 pub mod mailbox;
 pub mod managesieve;
+pub mod metadata;
+pub mod notify;
 pub mod objectid;
 pub mod pop;
+pub mod quota;
+pub mod replace;
 pub mod search;
 pub mod store;
 pub mod thread;
+pub mod urlauth;
 
 use crate::utils::{
     imap::{AssertResult, ImapConnection, Type},
@@ -251,12 +257,18 @@ pub async fn imap_tests() {
     search::test(&mut imap, &mut imap_check, &test).await;
     fetch::test(&mut imap, &mut imap_check).await;
     objectid::test(&test).await;
+    compress::test(&test).await;
     store::test(&mut imap, &mut imap_check, &test).await;
     copy_move::test(&mut imap, &mut imap_check).await;
     thread::test(&mut imap, &mut imap_check, &test).await;
     idle::test(&mut imap, &mut imap_check, false).await;
     condstore::test(&mut imap, &mut imap_check).await;
     acl::test(&mut imap, &mut imap_check, &test).await;
+    urlauth::test(&mut imap, &mut imap_check).await;
+    notify::test(&mut imap, &mut imap_check).await;
+    metadata::test(&mut imap, &mut imap_check).await;
+    replace::test(&mut imap, &test).await;
+    quota::test(&mut imap, &test).await;
 
     // Logout
     for imap in [&mut imap, &mut imap_check] {
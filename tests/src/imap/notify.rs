@@ -0,0 +1,156 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AssertResult, ImapConnection, Type};
+use imap_proto::ResponseType;
+use std::time::Duration;
+
+const MESSAGE: &str = "From: test@domain.com\r\nSubject: NOTIFY test\r\n\r\nTest message\r\n";
+
+pub async fn test(imap: &mut ImapConnection, imap_check: &mut ImapConnection) {
+    println!("Running NOTIFY tests...");
+
+    imap_check.send("CAPABILITY").await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("NOTIFY");
+
+    // Unsupported events and invalid event combinations
+    imap_check
+        .send("NOTIFY SET (personal (MessageNew MessageExpunge AnnotationChange))")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_contains("[BADEVENT (MessageNew MessageExpunge");
+    imap_check.send("NOTIFY SET (personal (FlagChange))").await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Bad)
+        .await;
+
+    // Create a mailbox with one message
+    imap.send_ok("CREATE Notify").await;
+    imap.send_ok("CREATE NotifyOutside").await;
+    append(imap, "Notify").await;
+
+    // Subscribe to the Notify subtree and expect an initial status
+    imap_check
+        .send("NOTIFY SET STATUS (subtree Notify (MessageNew MessageExpunge FlagChange MailboxName SubscriptionChange))")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("STATUS \"Notify\"")
+        .assert_contains("MESSAGES 1")
+        .assert_contains("UNSEEN 1");
+
+    // Mailbox creation
+    imap.send_ok("CREATE Notify/Child").await;
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("LIST () \"/\" \"Notify/Child\"");
+
+    // New messages
+    append(imap, "Notify/Child").await;
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("STATUS \"Notify/Child\"")
+        .assert_contains("MESSAGES 1")
+        .assert_contains("UIDNEXT 2");
+
+    // Subscription changes
+    imap.send_ok("SUBSCRIBE Notify/Child").await;
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("LIST (\\Subscribed) \"/\" \"Notify/Child\"");
+
+    // Changes outside the subtree are not reported
+    append(imap, "NotifyOutside").await;
+    imap.send_ok("CREATE Notify/Other").await;
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("LIST () \"/\" \"Notify/Other\"");
+
+    // Selected mailbox events are reported as in IDLE, plus the requested attributes
+    imap_check.send_ok("SELECT Notify").await;
+    imap_check
+        .send("NOTIFY SET (selected (MessageNew (UID FLAGS) MessageExpunge FlagChange)) (subtree Notify (MessageNew MessageExpunge))")
+        .await;
+    imap_check.assert_read(Type::Tagged, ResponseType::Ok).await;
+    append(imap, "Notify").await;
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("* 2 EXISTS");
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("* 2 FETCH (FLAGS () UID 2)");
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("* 2 FETCH (UID 2 FLAGS ())");
+    append(imap, "Notify/Child").await;
+    imap_check
+        .assert_read(Type::Status, ResponseType::Ok)
+        .await
+        .assert_contains("STATUS \"Notify/Child\"")
+        .assert_contains("MESSAGES 2");
+
+    // Expunges are not reported with SELECTED-DELAYED until a command allows them
+    imap_check
+        .send("NOTIFY SET (selected-delayed (MessageNew MessageExpunge FlagChange))")
+        .await;
+    imap_check.assert_read(Type::Tagged, ResponseType::Ok).await;
+    imap.send_ok("SELECT Notify").await;
+    imap.send_ok("MOVE 1 Notify/Child").await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    imap_check.send("FETCH 1:* (UID)").await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_not_contains("EXPUNGE");
+    imap_check.send("NOOP").await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("* 1 EXPUNGE");
+    imap.send_ok("UNSELECT").await;
+
+    // Disable notifications
+    imap_check.send_ok("NOTIFY NONE").await;
+    imap.send_ok("CREATE Notify/Silent").await;
+    imap_check.send("NOOP").await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_not_contains("Notify/Silent");
+    imap_check.send_ok("UNSELECT").await;
+
+    for mailbox in [
+        "Notify/Child",
+        "Notify/Other",
+        "Notify/Silent",
+        "Notify",
+        "NotifyOutside",
+    ] {
+        imap.send_ok(&format!("DELETE {mailbox}")).await;
+    }
+}
+
+async fn append(imap: &mut ImapConnection, mailbox: &str) {
+    imap.send(&format!(
+        "APPEND {mailbox} {{{}+}}\r\n{MESSAGE}",
+        MESSAGE.len()
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+}
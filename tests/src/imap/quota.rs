@@ -0,0 +1,62 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AssertResult, ImapConnection, Type};
+use crate::utils::server::TestServer;
+use imap_proto::ResponseType;
+
+pub async fn test(imap: &mut ImapConnection, test: &TestServer) {
+    println!("Running QUOTA tests...");
+
+    let root = format!(
+        "\"#{}\"",
+        test.account("jdoe@example.com").id().document_id()
+    );
+    imap.send("CAPABILITY").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("QUOTA=SET");
+    imap.send(&format!("GETQUOTA {root}")).await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(&format!("* QUOTA {root} ()"));
+
+    // Regular users cannot change quotas
+    imap.send(&format!("SETQUOTA {root} (STORAGE 1024)")).await;
+    imap.assert_read(Type::Tagged, ResponseType::No).await;
+
+    // Set and remove a storage limit as an administrator
+    let mut admin = test.account("admin@example.com").imap_client().await;
+    admin.send(&format!("SETQUOTA {root} (STORAGE 1024)")).await;
+    admin
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(&format!("* QUOTA {root} (STORAGE "))
+        .assert_contains(" 1024)");
+    imap.send("GETQUOTAROOT INBOX").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(&format!("* QUOTA {root} (STORAGE "))
+        .assert_contains(" 1024)");
+
+    admin.send(&format!("SETQUOTA {root} (MESSAGE 100)")).await;
+    admin.assert_read(Type::Tagged, ResponseType::No).await;
+    admin.send("SETQUOTA \"#999999\" (STORAGE 1024)").await;
+    admin.assert_read(Type::Tagged, ResponseType::No).await;
+    admin.send(&format!("SETQUOTA {root} (STORAGE)")).await;
+    admin.assert_read(Type::Tagged, ResponseType::Bad).await;
+
+    admin.send(&format!("SETQUOTA {root} ()")).await;
+    admin
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(&format!("* QUOTA {root} ()"));
+    imap.send(&format!("GETQUOTA {root}")).await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(&format!("* QUOTA {root} ()"));
+    admin.send("LOGOUT").await;
+}
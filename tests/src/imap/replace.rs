@@ -0,0 +1,122 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AssertResult, ImapConnection, Type};
+use crate::utils::server::TestServer;
+use imap_proto::ResponseType;
+
+pub async fn test(imap: &mut ImapConnection, test: &TestServer) {
+    println!("Running REPLACE tests...");
+
+    imap.send("CAPABILITY").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("REPLACE")
+        .assert_contains("UIDONLY");
+
+    imap.send_ok("CREATE ReplaceDrafts").await;
+    imap.send_ok("CREATE ReplaceSent").await;
+    imap.append("ReplaceDrafts", "Subject: draft 1\r\n\r\nfirst draft\r\n")
+        .await;
+    imap.send_ok("SELECT ReplaceDrafts").await;
+
+    // Replace within the same mailbox
+    imap.send("REPLACE 1 ReplaceDrafts (\\Draft) {28+}\r\nSubject: draft 2\r\n\r\nsecond\r\n")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("* OK [APPENDUID ")
+        .assert_contains("* 1 EXPUNGE")
+        .assert_contains("REPLACE completed");
+    imap.send("UID FETCH 1:* (BODY.PEEK[HEADER.FIELDS (SUBJECT)] FLAGS)")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("Subject: draft 2")
+        .assert_contains("\\Draft")
+        .assert_not_contains("Subject: draft 1");
+
+    // Replace into a different mailbox
+    imap.send("UID REPLACE * ReplaceSent {29+}\r\nSubject: sent\r\n\r\nfinal text\r\n")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("* OK [APPENDUID ")
+        .assert_contains("* 1 EXPUNGE");
+    imap.send("STATUS ReplaceSent (MESSAGES)").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("MESSAGES 1");
+    imap.send("STATUS ReplaceDrafts (MESSAGES)").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("MESSAGES 0");
+
+    // Errors
+    imap.send("UID REPLACE 9999 ReplaceSent {5+}\r\nhello\r\n")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::No).await;
+    imap.send("REPLACE 1:2 ReplaceSent {5+}\r\nhello\r\n").await;
+    imap.assert_read(Type::Tagged, ResponseType::Bad).await;
+    imap.send_ok("EXAMINE ReplaceSent").await;
+    imap.send("UID REPLACE * ReplaceDrafts {5+}\r\nhello\r\n")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::No).await;
+    imap.send_ok("UNSELECT").await;
+
+    println!("Running UIDONLY tests...");
+    let mut imap_uid = test.account("jdoe@example.com").imap_client().await;
+    imap_uid.send("ENABLE UIDONLY").await;
+    imap_uid
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("* ENABLED UIDONLY");
+    imap_uid.send_ok("SELECT ReplaceSent").await;
+
+    // Sequence numbers are rejected
+    for command in [
+        "FETCH 1 FLAGS",
+        "STORE 1 +FLAGS (\\Seen)",
+        "SEARCH ALL",
+        "COPY 1 ReplaceDrafts",
+        "UID SEARCH 1:*",
+    ] {
+        imap_uid.send(command).await;
+        imap_uid
+            .assert_read(Type::Tagged, ResponseType::Bad)
+            .await
+            .assert_response_code("UIDREQUIRED");
+    }
+
+    // Responses use UIDs
+    imap_uid.send("UID FETCH 1:* (FLAGS)").await;
+    imap_uid
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(" UIDFETCH (FLAGS (")
+        .assert_not_contains(" FETCH (");
+    imap_uid.send("UID STORE 1:* +FLAGS (\\Deleted)").await;
+    imap_uid
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(" UIDFETCH (FLAGS (");
+    imap_uid.send("UID SEARCH DELETED").await;
+    imap_uid
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("* ESEARCH (TAG ");
+    imap_uid.send("EXPUNGE").await;
+    imap_uid
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("* VANISHED ");
+
+    imap_uid.send_ok("UNSELECT").await;
+    imap_uid.send("LOGOUT").await;
+
+    imap.send_ok("DELETE ReplaceDrafts").await;
+    imap.send_ok("DELETE ReplaceSent").await;
+}
@@ -136,4 +136,80 @@ pub async fn test(imap: &mut ImapConnection, imap_check: &mut ImapConnection, te
         } else {
             "COUNT 10 ALL 9,3,7:8,2,6,4:5,1,10"
         }); //6,4:5,1,10,9,3,7:8,2");
+
+    // Partial results
+    imap_check
+        .send("UID SEARCH RETURN (COUNT PARTIAL -1:-3) ALL")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("COUNT 10 PARTIAL (-1:-3 8:10)");
+    imap_check
+        .send("UID SEARCH RETURN (MIN PARTIAL 2:1) ALL")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("MIN 1 PARTIAL (1:2 1:2)");
+    imap_check
+        .send("UID SEARCH RETURN (PARTIAL 11:20) ALL")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("PARTIAL (11:20 NIL)");
+    imap_check
+        .send("UID SEARCH RETURN (ALL PARTIAL 1:5) ALL")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Bad)
+        .await;
+    imap.send("UID SORT RETURN (PARTIAL 1:3) (DATE SUBJECT) UTF-8 ALL")
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(if !test.server.search_store().is_mysql() {
+            "PARTIAL (1:3 6,4:5)"
+        } else {
+            "PARTIAL (1:3 9,3,7)"
+        });
+    imap_check
+        .send("UID FETCH 1:* (FLAGS) (PARTIAL -1:-2)")
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("(UID 9 FLAGS")
+        .assert_contains("(UID 10 FLAGS")
+        .assert_not_contains("(UID 8 FLAGS");
+
+    // Save date
+    imap_check.send("UID FETCH 1 (SAVEDATE)").await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("SAVEDATE \"");
+    imap_check.send("UID SEARCH SAVEDATESUPPORTED").await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_equals("* SEARCH 1 2 3 4 5 6 7 8 9 10");
+
+    // All messages were saved today, regardless of their received date
+    let today = chrono::Utc::now().format("%d-%b-%Y");
+    imap_check
+        .send(&format!("UID SEARCH SAVEDSINCE {today}"))
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_equals("* SEARCH 1 2 3 4 5 6 7 8 9 10");
+    imap_check
+        .send(&format!("UID SEARCH SAVEDBEFORE {today}"))
+        .await;
+    imap_check
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_equals("* SEARCH");
 }
@@ -0,0 +1,169 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AssertResult, ImapConnection, Type};
+use imap_proto::ResponseType;
+
+const MESSAGE: &str = concat!(
+    "From: jdoe@example.com\r\n",
+    "To: jane.smith@example.com\r\n",
+    "Subject: URLAUTH test\r\n",
+    "\r\n",
+    "Forwarded by reference.\r\n"
+);
+
+pub async fn test(imap: &mut ImapConnection, _imap_check: &mut ImapConnection) {
+    println!("Running URLAUTH and CATENATE tests...");
+
+    // Both extensions should be advertised
+    imap.send("CAPABILITY").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("URLAUTH")
+        .assert_contains("CATENATE");
+
+    // Append a test message
+    imap.send_ok("CREATE UrlAuth").await;
+    imap.send(&format!(
+        "APPEND UrlAuth {{{}+}}\r\n{MESSAGE}",
+        MESSAGE.len()
+    ))
+    .await;
+    let code = imap
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .into_response_code();
+    let mut code = code.split(' ').skip(1);
+    let uid_validity = code.next().unwrap().to_string();
+    let uid = code.next().unwrap().to_string();
+    let rump = format!(
+        "imap://jdoe%40example.com@localhost/UrlAuth;UIDVALIDITY={uid_validity}/;UID={uid}"
+    );
+
+    // URL rumps without URLAUTH or for other users should be rejected
+    imap.send(&format!("GENURLAUTH \"{rump}\" INTERNAL")).await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_contains("[BADURL");
+    imap.send(&format!(
+        "GENURLAUTH \"{}\" INTERNAL",
+        rump.replace("jdoe%40", "jane.smith%40") + ";URLAUTH=anonymous"
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_contains("[BADURL");
+    imap.send(&format!(
+        "GENURLAUTH \"{rump};URLAUTH=anonymous\" SOMETHING"
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::No).await;
+
+    // Generate authorized URLs
+    imap.send(&format!(
+        "GENURLAUTH \"{rump};URLAUTH=user+jdoe%40example.com\" INTERNAL \"{rump}/;SECTION=TEXT;URLAUTH=anonymous\" INTERNAL"
+    ))
+    .await;
+    let response = imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    let urls = response
+        .iter()
+        .find_map(|line| line.strip_prefix("* GENURLAUTH "))
+        .unwrap()
+        .split(' ')
+        .map(|url| url.trim_matches('"').to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(urls.len(), 2);
+    assert!(urls[0].contains(";URLAUTH=user+jdoe%40example.com:internal:"));
+    assert!(urls[1].contains(";URLAUTH=anonymous:internal:"));
+
+    // Fetch the message using the authorized URLs
+    let mut url_tampered = urls[0].clone();
+    let last_ch = url_tampered.pop().unwrap();
+    url_tampered.push(if last_ch == '0' { '1' } else { '0' });
+    imap.send(&format!(
+        "URLFETCH \"{}\" \"{}\" \"{url_tampered}\"",
+        urls[0], urls[1]
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("Subject: URLAUTH test")
+        .assert_contains("Forwarded by reference.")
+        .assert_contains(&format!("\"{url_tampered}\" NIL"));
+
+    // Build a new message with CATENATE
+    imap.send(&format!(
+        "APPEND UrlAuth CATENATE (URL \"/UrlAuth;UIDVALIDITY={uid_validity}/;UID={uid}/;SECTION=HEADER\" TEXT {{18+}}\r\nAppended by URL.\r\n URL \"{}\")",
+        urls[1]
+    ))
+    .await;
+    let new_uid = imap
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .into_append_uid();
+    imap.send(&format!(
+        "URLFETCH \"imap://jdoe%40example.com@localhost/UrlAuth;UIDVALIDITY={uid_validity}/;UID={new_uid}\""
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("Subject: URLAUTH test")
+        .assert_contains("Appended by URL.")
+        .assert_contains("Forwarded by reference.");
+
+    // URLs without a mailbox name are relative to the selected mailbox
+    imap.send(&format!(
+        "APPEND UrlAuth CATENATE (URL \";UID={uid}/;SECTION=HEADER\")"
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_contains("[BADURL");
+    imap.send_ok("SELECT UrlAuth").await;
+    imap.send(&format!(
+        "APPEND UrlAuth CATENATE (URL \";UID={uid}/;SECTION=HEADER\" TEXT {{23+}}\r\nAppended by relative.\r\n URL \"/;UID={uid}/;SECTION=TEXT\")"
+    ))
+    .await;
+    let new_uid = imap
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .into_append_uid();
+    imap.send(&format!(
+        "URLFETCH \"imap://jdoe%40example.com@localhost/UrlAuth;UIDVALIDITY={uid_validity}/;UID={new_uid}\""
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("Subject: URLAUTH test")
+        .assert_contains("Appended by relative.")
+        .assert_contains("Forwarded by reference.");
+    imap.send_ok("UNSELECT").await;
+
+    // CATENATE with an invalid URL should fail
+    imap.send(&format!(
+        "APPEND UrlAuth CATENATE (TEXT {{5+}}\r\nhello URL \"{url_tampered}\")"
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_contains(&format!("[BADURL {url_tampered}]"));
+
+    // Resetting the access key revokes all issued URLs
+    imap.send_ok("RESETKEY UrlAuth INTERNAL").await;
+    imap.send(&format!("URLFETCH \"{}\" \"{}\"", urls[0], urls[1]))
+        .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_count(" NIL", 1)
+        .assert_not_contains("Forwarded by reference.");
+    imap.send("RESETKEY \"Does not exist\"").await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_response_code("NONEXISTENT");
+    imap.send_ok("RESETKEY").await;
+
+    imap.send_ok("DELETE UrlAuth").await;
+}
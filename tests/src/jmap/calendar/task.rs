@@ -0,0 +1,574 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{
+    jmap::{ChangeType, JmapUtils},
+    server::TestServer,
+};
+use ahash::AHashSet;
+use groupware::cache::GroupwareCache;
+use hyper::StatusCode;
+use jmap_proto::request::method::MethodObject;
+use serde_json::json;
+use std::str::FromStr;
+use types::{collection::SyncCollection, id::Id};
+
+pub async fn test(test: &TestServer) {
+    println!("Running Task tests...");
+    let account = test.account("jdoe@example.com");
+    let account_id = account.id().document_id();
+    let dav_client = account.webdav_client();
+
+    // Obtain task list state
+    let list_change_id = account
+        .jmap_get(
+            MethodObject::TaskList,
+            Vec::<&str>::new(),
+            Vec::<&str>::new(),
+        )
+        .await
+        .state()
+        .to_string();
+
+    // Create a task list and a calendar that only accepts events
+    let list_id = account
+        .jmap_create(
+            MethodObject::TaskList,
+            [json!({
+                "name": "Chores",
+                "color": "#ff0000",
+            })],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string();
+    let resources = test
+        .server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+        .await
+        .unwrap();
+    dav_client
+        .mkcol(
+            "MKCALENDAR",
+            &resources.format_collection("events-only"),
+            [],
+            [
+                ("D:displayname", "Events only"),
+                (
+                    "A:supported-calendar-component-set",
+                    "<A:comp name=\"VEVENT\"/>",
+                ),
+            ],
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+    let events_only_id = account
+        .jmap_get(MethodObject::Calendar, ["id", "name"], Vec::<&str>::new())
+        .await
+        .list()
+        .iter()
+        .find(|calendar| calendar["name"] == "Events only")
+        .unwrap()
+        .id()
+        .to_string();
+
+    // Calendars without VTODO support are not task lists
+    let task_list_ids = account
+        .jmap_get(MethodObject::TaskList, ["id"], Vec::<&str>::new())
+        .await
+        .list()
+        .iter()
+        .map(|list| list.id().to_string())
+        .collect::<AHashSet<_>>();
+    assert!(task_list_ids.contains(&list_id));
+    assert!(!task_list_ids.contains(&events_only_id));
+    assert_eq!(
+        account
+            .jmap_get(MethodObject::TaskList, ["id"], [&events_only_id])
+            .await
+            .not_found()
+            .collect::<Vec<_>>(),
+        vec![events_only_id.as_str()]
+    );
+    let response = account
+        .jmap_changes(MethodObject::TaskList, &list_change_id)
+        .await;
+    let changes = response.changes().collect::<AHashSet<_>>();
+    assert!(changes.contains(&ChangeType::Created(&list_id)));
+    assert!(!changes.contains(&ChangeType::Created(&events_only_id)));
+
+    // Update the task list
+    account
+        .jmap_update(
+            MethodObject::TaskList,
+            [(&list_id, json!({"name": "Home chores", "color": "#00ff00"}))],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&list_id);
+    account
+        .jmap_get(MethodObject::TaskList, ["id", "name", "color"], [&list_id])
+        .await
+        .list()[0]
+        .assert_is_equal(json!({
+            "id": &list_id,
+            "name": "Home chores",
+            "color": "#00ff00"
+        }));
+    assert_eq!(
+        account
+            .jmap_update(
+                MethodObject::TaskList,
+                [(&events_only_id, json!({"name": "Tasks?"}))],
+                Vec::<(&str, &str)>::new(),
+            )
+            .await
+            .not_updated(&events_only_id)
+            .typ(),
+        "notFound"
+    );
+
+    // Obtain task state
+    let task_change_id = account
+        .jmap_get(MethodObject::Task, Vec::<&str>::new(), Vec::<&str>::new())
+        .await
+        .state()
+        .to_string();
+
+    // Create two tasks and an event in the same list
+    let response = account
+        .jmap_create(
+            MethodObject::Task,
+            [
+                json!({
+                    "taskListId": &list_id,
+                    "uid": "renew-passport@example.com",
+                    "title": "Renew passport",
+                    "description": "Bring two photos",
+                    "timeZone": "Europe/Madrid",
+                    "start": "2026-11-02T09:00:00",
+                    "due": "2026-11-09T18:00:00",
+                    "progress": "needs-action",
+                    "priority": 1
+                }),
+                json!({
+                    "taskListId": &list_id,
+                    "uid": "water-plants@example.com",
+                    "title": "Water the plants",
+                    "timeZone": "Europe/Madrid",
+                    "start": "2026-10-20T08:00:00",
+                    "progress": "in-process",
+                    "percentComplete": 50
+                }),
+            ],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    let task_1_id = response.created(0).id().to_string();
+    let task_2_id = response.created(1).id().to_string();
+    let event_id = account
+        .jmap_create(
+            MethodObject::CalendarEvent,
+            [json!({
+                "calendarIds": {&list_id: true},
+                "@type": "Event",
+                "uid": "family-dinner@example.com",
+                "title": "Family dinner",
+                "timeZone": "Europe/Madrid",
+                "start": "2026-10-25T20:00:00",
+                "duration": "PT2H"
+            })],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .created(0)
+        .id()
+        .to_string();
+
+    // Only tasks are reported as changes
+    assert_eq!(
+        account
+            .jmap_changes(MethodObject::Task, &task_change_id)
+            .await
+            .changes()
+            .collect::<AHashSet<_>>(),
+        [
+            ChangeType::Created(&task_1_id),
+            ChangeType::Created(&task_2_id)
+        ]
+        .into_iter()
+        .collect::<AHashSet<_>>()
+    );
+
+    // Verify task contents
+    let response = account
+        .jmap_get(
+            MethodObject::Task,
+            [
+                "id",
+                "@type",
+                "taskListId",
+                "uid",
+                "title",
+                "timeZone",
+                "start",
+                "due",
+                "progress",
+                "percentComplete",
+            ],
+            [&task_1_id, &task_2_id, &event_id],
+        )
+        .await;
+    let list = response.list();
+    assert_eq!(list.len(), 2, "{list:?}");
+    for (task, id, uid, title, progress) in [
+        (
+            &list[0],
+            &task_1_id,
+            "renew-passport@example.com",
+            "Renew passport",
+            "needs-action",
+        ),
+        (
+            &list[1],
+            &task_2_id,
+            "water-plants@example.com",
+            "Water the plants",
+            "in-process",
+        ),
+    ] {
+        assert_eq!(task["id"], id.as_str(), "{task}");
+        assert_eq!(task["@type"], "Task", "{task}");
+        assert_eq!(task["taskListId"], list_id.as_str(), "{task}");
+        assert!(task.get("calendarIds").is_none(), "{task}");
+        assert_eq!(task["uid"], uid, "{task}");
+        assert_eq!(task["title"], title, "{task}");
+        assert_eq!(task["timeZone"], "Europe/Madrid", "{task}");
+        assert_eq!(task["progress"], progress, "{task}");
+    }
+    assert_eq!(list[0]["due"], "2026-11-09T18:00:00", "{list:?}");
+    assert_eq!(list[1]["percentComplete"], 50, "{list:?}");
+
+    // Events are not visible as tasks
+    assert_eq!(
+        response.not_found().collect::<Vec<_>>(),
+        vec![event_id.as_str()]
+    );
+
+    // Only tasks can be stored in task lists accepting VTODO components
+    let response = account
+        .jmap_create(
+            MethodObject::Task,
+            [
+                json!({
+                    "taskListId": &list_id,
+                    "@type": "Event",
+                    "title": "Not a task",
+                }),
+                json!({
+                    "taskListId": &events_only_id,
+                    "title": "Wrong list",
+                }),
+                json!({
+                    "calendarIds": {&list_id: true},
+                    "title": "Wrong property",
+                }),
+            ],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    for (idx, property) in [(0, "@type"), (1, "taskListId"), (2, "calendarIds")] {
+        let err = response.not_created(idx);
+        assert_eq!(err.typ(), "invalidProperties", "{err}");
+        assert_eq!(err["properties"], json!([property]), "{err}");
+    }
+    for update in [
+        json!({"@type": "Event"}),
+        json!({"taskListId": &events_only_id}),
+    ] {
+        assert_eq!(
+            account
+                .jmap_update(
+                    MethodObject::Task,
+                    [(&task_2_id, update)],
+                    Vec::<(&str, &str)>::new(),
+                )
+                .await
+                .not_updated(&task_2_id)
+                .typ(),
+            "invalidProperties"
+        );
+    }
+    assert_eq!(
+        account
+            .jmap_update(
+                MethodObject::Task,
+                [(&event_id, json!({"title": "Not a task"}))],
+                Vec::<(&str, &str)>::new(),
+            )
+            .await
+            .not_updated(&event_id)
+            .typ(),
+        "notFound"
+    );
+    assert_eq!(
+        account
+            .jmap_destroy(MethodObject::Task, [&event_id], Vec::<(&str, &str)>::new(),)
+            .await
+            .not_destroyed(&event_id)
+            .typ(),
+        "notFound"
+    );
+
+    // Update a task
+    account
+        .jmap_update(
+            MethodObject::Task,
+            [(
+                &task_1_id,
+                json!({"title": "Renew passport and ID card", "progress": "completed"}),
+            )],
+            Vec::<(&str, &str)>::new(),
+        )
+        .await
+        .updated(&task_1_id);
+    account
+        .jmap_get(
+            MethodObject::Task,
+            ["id", "title", "progress"],
+            [&task_1_id],
+        )
+        .await
+        .list()[0]
+        .assert_is_equal(json!({
+            "id": &task_1_id,
+            "title": "Renew passport and ID card",
+            "progress": "completed"
+        }));
+
+    // Tasks created over JMAP are served as VTODO over CalDAV
+    let list_document_id = Id::from_str(&list_id).unwrap().document_id();
+    let task_1_document_id = Id::from_str(&task_1_id).unwrap().document_id();
+    let resources = test
+        .server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+        .await
+        .unwrap();
+    let list_path = resources.format_resource(
+        resources
+            .container_resource_path_by_id(list_document_id)
+            .unwrap(),
+    );
+    let task_1_path = resources.format_resource(
+        resources
+            .children(list_document_id)
+            .find(|item| item.document_id() == task_1_document_id)
+            .unwrap(),
+    );
+    let ical = dav_client
+        .request("GET", &task_1_path, "")
+        .await
+        .with_status(StatusCode::OK)
+        .expect_body()
+        .lines()
+        .map(String::from)
+        .collect::<AHashSet<_>>();
+    for line in [
+        "BEGIN:VTODO",
+        "UID:renew-passport@example.com",
+        "SUMMARY:Renew passport and ID card",
+        "DESCRIPTION:Bring two photos",
+        "DUE;TZID=Europe/Madrid:20261109T180000",
+        "STATUS:COMPLETED",
+        "END:VTODO",
+    ] {
+        assert!(ical.contains(line), "missing {line:?} in {ical:?}");
+    }
+    assert!(!ical.contains("BEGIN:VEVENT"), "{ical:?}");
+
+    // Tasks created over CalDAV are visible over JMAP
+    dav_client
+        .request("PUT", &format!("{list_path}dentist.ics"), TEST_VTODO)
+        .await
+        .with_status(StatusCode::CREATED);
+    dav_client
+        .request("PUT", &format!("{list_path}meeting.ics"), TEST_VEVENT)
+        .await
+        .with_status(StatusCode::CREATED);
+    test.wait_for_tasks().await;
+    let response = account
+        .jmap_query(
+            MethodObject::Task,
+            [("uid", "dentist-appointment@example.com")],
+            Vec::<&str>::new(),
+            Vec::<(&str, &str)>::new(),
+        )
+        .await;
+    let task_3_id = response.ids().next().unwrap().to_string();
+    account
+        .jmap_get(
+            MethodObject::Task,
+            [
+                "id",
+                "@type",
+                "uid",
+                "title",
+                "progress",
+                "percentComplete",
+                "priority",
+            ],
+            [&task_3_id],
+        )
+        .await
+        .list()[0]
+        .assert_is_equal(json!({
+            "id": &task_3_id,
+            "@type": "Task",
+            "uid": "dentist-appointment@example.com",
+            "title": "Book dentist appointment",
+            "progress": "in-process",
+            "percentComplete": 25,
+            "priority": 5
+        }));
+
+    // Query tasks
+    assert_eq!(
+        account
+            .jmap_query(
+                MethodObject::Task,
+                [("inTaskList", list_id.as_str())],
+                ["start"],
+                Vec::<(&str, &str)>::new(),
+            )
+            .await
+            .ids()
+            .collect::<Vec<_>>(),
+        vec![task_2_id.as_str(), task_1_id.as_str(), task_3_id.as_str()]
+    );
+    assert_eq!(
+        account
+            .jmap_query(
+                MethodObject::Task,
+                [("title", "passport")],
+                Vec::<&str>::new(),
+                Vec::<(&str, &str)>::new(),
+            )
+            .await
+            .ids()
+            .collect::<Vec<_>>(),
+        vec![task_1_id.as_str()]
+    );
+    assert_eq!(
+        account
+            .jmap_query(
+                MethodObject::Task,
+                [("text", "dinner")],
+                Vec::<&str>::new(),
+                Vec::<(&str, &str)>::new(),
+            )
+            .await
+            .ids()
+            .count(),
+        0
+    );
+
+    // Destroy a task
+    assert_eq!(
+        account
+            .jmap_destroy(MethodObject::Task, [&task_2_id], Vec::<(&str, &str)>::new(),)
+            .await
+            .destroyed()
+            .collect::<Vec<_>>(),
+        vec![task_2_id.as_str()]
+    );
+    dav_client
+        .request(
+            "GET",
+            &resources.format_resource(
+                resources
+                    .children(list_document_id)
+                    .find(|item| {
+                        item.document_id() == Id::from_str(&task_2_id).unwrap().document_id()
+                    })
+                    .unwrap(),
+            ),
+            "",
+        )
+        .await
+        .with_status(StatusCode::NOT_FOUND);
+
+    // Task lists with tasks can only be destroyed using force
+    assert_eq!(
+        account
+            .jmap_destroy(
+                MethodObject::TaskList,
+                [&list_id],
+                Vec::<(&str, &str)>::new(),
+            )
+            .await
+            .not_destroyed(&list_id)
+            .typ(),
+        "calendarHasEvent"
+    );
+    assert_eq!(
+        account
+            .jmap_destroy(
+                MethodObject::TaskList,
+                [&list_id],
+                [("onDestroyRemoveTasks", true)],
+            )
+            .await
+            .destroyed()
+            .collect::<Vec<_>>(),
+        vec![list_id.as_str()]
+    );
+    assert_eq!(
+        account
+            .jmap_get(MethodObject::Task, ["id"], [&task_1_id, &task_3_id])
+            .await
+            .not_found()
+            .collect::<AHashSet<_>>(),
+        [task_1_id.as_str(), task_3_id.as_str()]
+            .into_iter()
+            .collect::<AHashSet<_>>()
+    );
+
+    // Clean up
+    test.wait_for_tasks().await;
+    account.destroy_all_calendars().await;
+    test.assert_is_empty().await;
+}
+
+const TEST_VTODO: &str = r#"BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Example Corp//Tasks//EN
+BEGIN:VTODO
+UID:dentist-appointment@example.com
+DTSTAMP:20261017T080000Z
+DTSTART:20261103T080000Z
+DUE:20261103T090000Z
+SUMMARY:Book dentist appointment
+STATUS:IN-PROCESS
+PERCENT-COMPLETE:25
+PRIORITY:5
+END:VTODO
+END:VCALENDAR
+"#;
+
+const TEST_VEVENT: &str = r#"BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Example Corp//Calendar//EN
+BEGIN:VEVENT
+UID:team-meeting@example.com
+DTSTAMP:20261017T080000Z
+DTSTART:20261104T100000Z
+DURATION:PT1H
+SUMMARY:Team meeting
+END:VEVENT
+END:VCALENDAR
+"#;
@@ -0,0 +1,292 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{account::Account, server::TestServer};
+use jmap_client::mailbox::Role;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+pub async fn test(test: &TestServer) {
+    println!("Running MDN tests...");
+    let john = test.account("jdoe@example.com");
+    let jane = test.account("jane.smith@example.com");
+    let client = john.jmap_client().await;
+
+    let mailbox_id = client
+        .mailbox_create("JMAP MDN", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    let identity_id = client
+        .identity_create("John Doe", "jdoe@example.com")
+        .await
+        .unwrap()
+        .take_id();
+
+    // Import a message requesting a read receipt and one that does not
+    let email_id = client
+        .email_import(
+            concat!(
+                "From: Jane Smith <jane.smith@example.com>\r\n",
+                "To: John Doe <jdoe@example.com>\r\n",
+                "Subject: Budget review\r\n",
+                "Message-ID: <budget-review@example.com>\r\n",
+                "Disposition-Notification-To: Jane Smith <jane.smith@example.com>\r\n",
+                "\r\n",
+                "Please confirm that you have read this message.\r\n",
+            )
+            .as_bytes()
+            .to_vec(),
+            [&mailbox_id],
+            None::<Vec<&str>>,
+            None,
+        )
+        .await
+        .unwrap()
+        .take_id();
+    let no_mdn_email_id = client
+        .email_import(
+            concat!(
+                "From: Jane Smith <jane.smith@example.com>\r\n",
+                "To: John Doe <jdoe@example.com>\r\n",
+                "Subject: No receipt needed\r\n",
+                "\r\n",
+                "Nothing to confirm.\r\n",
+            )
+            .as_bytes()
+            .to_vec(),
+            [&mailbox_id],
+            None::<Vec<&str>>,
+            None,
+        )
+        .await
+        .unwrap()
+        .take_id();
+
+    // Line breaks in report fields should be rejected
+    for (property, value) in [
+        ("reportingUA", json!("webmail.example.com\r\nX-Injected: 1")),
+        (
+            "finalRecipient",
+            json!("rfc822; jdoe@example.com\nX-Injected: 1"),
+        ),
+        ("error", json!(["failed\r\nX-Injected: 1"])),
+        (
+            "extensionFields",
+            json!({"X-Receipt-Source": "web\r\nX-Injected: 1"}),
+        ),
+    ] {
+        let response = john
+            .jmap_method_call(
+                "MDN/send",
+                json!({
+                    "accountId": john.id_string(),
+                    "identityId": &identity_id,
+                    "send": {
+                        "k1": {
+                            "forEmailId": &email_id,
+                            "disposition": {
+                                "actionMode": "manual-action",
+                                "sendingMode": "mdn-sent-manually",
+                                "type": "displayed"
+                            },
+                            property: value
+                        }
+                    }
+                }),
+            )
+            .await;
+        assert_eq!(
+            response.pointer("/methodResponses/0/1/notSent/k1/type"),
+            Some(&Value::from("invalidProperties")),
+            "{response}"
+        );
+        assert_eq!(
+            response.pointer("/methodResponses/0/1/notSent/k1/properties/0"),
+            Some(&Value::from(property)),
+            "{response}"
+        );
+    }
+    assert_eq!(keywords(john, &email_id).await, Vec::<String>::new());
+
+    // Send a read receipt and mark the original as seen
+    let response = mdn_send(john, &identity_id, &email_id).await;
+    let sent = response
+        .pointer("/methodResponses/0/1/sent/k1")
+        .unwrap_or_else(|| panic!("MDN was not sent: {response}"));
+    assert_eq!(sent["finalRecipient"], "rfc822; jdoe@example.com");
+    assert_eq!(sent["originalMessageId"], "<budget-review@example.com>");
+    assert_eq!(sent["reportingUA"], "webmail.example.com; Test Client");
+    assert_eq!(
+        response.pointer("/methodResponses/1/0"),
+        Some(&Value::from("Email/set")),
+        "{response}"
+    );
+    assert_eq!(
+        keywords(john, &email_id).await,
+        vec!["$mdnsent".to_string(), "$seen".to_string()]
+    );
+
+    // A second receipt for the same message should be rejected
+    let response = mdn_send(john, &identity_id, &email_id).await;
+    assert_eq!(
+        response.pointer("/methodResponses/0/1/notSent/k1/type"),
+        Some(&Value::from("mdnAlreadySent")),
+        "{response}"
+    );
+
+    // Messages without Disposition-Notification-To cannot be acknowledged
+    let response = mdn_send(john, &identity_id, &no_mdn_email_id).await;
+    assert_eq!(
+        response.pointer("/methodResponses/0/1/notSent/k1/type"),
+        Some(&Value::from("invalidProperties")),
+        "{response}"
+    );
+    assert_eq!(keywords(john, &no_mdn_email_id).await, Vec::<String>::new());
+
+    // Unknown identities should fail
+    let response = mdn_send(john, "zzzzzz", &email_id).await;
+    assert_eq!(
+        response.pointer("/methodResponses/0/0"),
+        Some(&Value::from("error")),
+        "{response}"
+    );
+
+    // The receipt should be delivered to the requester
+    let mut mdn = None;
+    for _ in 0..50 {
+        mdn = jane
+            .jmap_get("Email", ["subject", "blobId"], Vec::<&str>::new())
+            .await
+            .list()
+            .iter()
+            .find(|email| email["subject"] == "Read: Budget review")
+            .cloned();
+        if mdn.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let mdn = mdn.expect("MDN was not delivered");
+    let mdn_blob_id = mdn["blobId"].as_str().unwrap();
+
+    // Parse the delivered receipt
+    let original_blob_id =
+        john.jmap_get("Email", ["blobId"], [&email_id]).await.list()[0]["blobId"]
+            .as_str()
+            .unwrap()
+            .to_string();
+    let response = jane
+        .jmap_method_call(
+            "MDN/parse",
+            json!({
+                "accountId": jane.id_string(),
+                "blobIds": [mdn_blob_id, &original_blob_id, "invalid"],
+            }),
+        )
+        .await;
+    let parsed = response
+        .pointer(&format!("/methodResponses/0/1/parsed/{mdn_blob_id}"))
+        .unwrap_or_else(|| panic!("MDN was not parsed: {response}"));
+    assert_eq!(parsed["subject"], "Read: Budget review");
+    assert_eq!(
+        parsed["textBody"].as_str().unwrap().trim(),
+        "Your message was displayed."
+    );
+    assert_eq!(parsed["reportingUA"], "webmail.example.com; Test Client");
+    assert_eq!(parsed["finalRecipient"], "rfc822; jdoe@example.com");
+    assert_eq!(parsed["originalMessageId"], "<budget-review@example.com>");
+    assert_eq!(
+        parsed["disposition"],
+        json!({
+            "actionMode": "manual-action",
+            "sendingMode": "mdn-sent-manually",
+            "type": "displayed"
+        })
+    );
+    assert_eq!(
+        parsed["extensionFields"],
+        json!({"X-Receipt-Source": "webmail"})
+    );
+
+    // Jane cannot access John's blobs and invalid ids are not found
+    assert_eq!(
+        response.pointer("/methodResponses/0/1/notFound"),
+        Some(&json!([original_blob_id, "invalid"])),
+        "{response}"
+    );
+
+    // Messages without a disposition notification are not parsable
+    let response = john
+        .jmap_method_call(
+            "MDN/parse",
+            json!({
+                "accountId": john.id_string(),
+                "blobIds": [&original_blob_id],
+            }),
+        )
+        .await;
+    assert_eq!(
+        response.pointer("/methodResponses/0/1/notParsable"),
+        Some(&json!([original_blob_id])),
+        "{response}"
+    );
+
+    // Clean up
+    client.identity_destroy(&identity_id).await.unwrap();
+    test.destroy_all_mailboxes(john).await;
+    test.destroy_all_mailboxes(jane).await;
+    test.assert_is_empty().await;
+}
+
+async fn mdn_send(account: &Account, identity_id: &str, email_id: &str) -> Value {
+    account
+        .jmap_method_calls(json!([[
+            "MDN/send",
+            {
+                "accountId": account.id_string(),
+                "identityId": identity_id,
+                "send": {
+                    "k1": {
+                        "forEmailId": email_id,
+                        "subject": "Read: Budget review",
+                        "textBody": "Your message was displayed.",
+                        "reportingUA": "webmail.example.com; Test Client",
+                        "disposition": {
+                            "actionMode": "manual-action",
+                            "sendingMode": "mdn-sent-manually",
+                            "type": "displayed"
+                        },
+                        "extensionFields": {
+                            "X-Receipt-Source": "webmail"
+                        }
+                    }
+                },
+                "onSuccessUpdateEmail": {
+                    "#k1": {
+                        "keywords/$seen": true
+                    }
+                }
+            },
+            "0"
+        ]]))
+        .await
+        .into_inner()
+}
+
+async fn keywords(account: &Account, email_id: &str) -> Vec<String> {
+    let mut keywords = account
+        .jmap_get("Email", ["keywords"], [email_id])
+        .await
+        .list()[0]["keywords"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    keywords.sort_unstable();
+    keywords
+}
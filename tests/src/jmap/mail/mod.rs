@@ -9,12 +9,14 @@ pub mod changes;
 pub mod copy;
 pub mod get;
 pub mod mailbox;
+pub mod mdn;
 pub mod parse;
 pub mod query;
 pub mod query_changes;
 pub mod search_snippet;
 pub mod set;
 pub mod sieve_script;
+pub mod smime;
 pub mod submission;
 pub mod thread_get;
 pub mod thread_merge;
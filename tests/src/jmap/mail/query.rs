@@ -57,6 +57,7 @@ pub async fn test(test: &TestServer) {
                     uid_validity: 0,
                     subscribers: vec![],
                     acls: vec![],
+                    metadata: vec![],
                 }))
                 .unwrap();
         }
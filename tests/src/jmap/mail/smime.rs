@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{account::Account, server::TestServer};
+use jmap_client::{client::Client, mailbox::Role};
+use registry::schema::{prelude::Property, structs::Jmap};
+use serde_json::Value;
+use std::{fs, path::PathBuf};
+
+pub async fn test(test: &TestServer) {
+    println!("Running S/MIME verification tests...");
+
+    let mut test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    test_dir.push("resources");
+    test_dir.push("jmap");
+    test_dir.push("smime");
+    let read = |name: &str| fs::read_to_string(test_dir.join(name)).unwrap();
+
+    // Trust the test CA
+    let admin = test.account("admin@example.com");
+    admin
+        .registry_update_setting(
+            Jmap {
+                smime_trust_anchors: Some(read("ca.pem")),
+                ..Default::default()
+            },
+            &[Property::SmimeTrustAnchors],
+        )
+        .await;
+    admin.reload_settings().await;
+
+    let account = test.account("jdoe@example.com");
+    let client = account.jmap_client().await;
+    let mailbox_id = client
+        .mailbox_create("JMAP S/MIME", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    let signed = read("signed.eml");
+
+    for (name, message, expected_status, expected_error) in [
+        ("detached", signed.clone(), "signed/verified", None),
+        ("opaque", read("opaque.eml"), "signed/verified", None),
+        (
+            "tampered",
+            signed.replace("Revenue is up 12%", "Revenue is up 21%"),
+            "signed/failed",
+            Some("Message digest does not match signed content"),
+        ),
+        (
+            "untrusted",
+            read("untrusted.eml"),
+            "signed/failed",
+            Some("Signer certificate is not issued by a trusted authority"),
+        ),
+        (
+            "spoofed sender",
+            signed.replace(
+                "From: Jane Smith <jane.smith@example.com>",
+                "From: Bill <bill@example.com>",
+            ),
+            "signed/failed",
+            Some("Signer certificate does not match sender address bill@example.com"),
+        ),
+        (
+            "missing signature",
+            signed.replace("application/pkcs7-signature; name", "text/plain; name"),
+            "signed/failed",
+            Some("Signature part not found"),
+        ),
+    ] {
+        let email_id = import(&client, &mailbox_id, message).await;
+        let email = smime_properties(account, &email_id).await;
+        assert_eq!(email["smimeStatus"], expected_status, "{name}: {email}");
+        match expected_error {
+            Some(expected_error) => assert_eq!(
+                email["smimeErrors"],
+                Value::Array(vec![expected_error.into()]),
+                "{name}: {email}"
+            ),
+            None => assert_eq!(email["smimeErrors"], Value::Null, "{name}: {email}"),
+        }
+        assert!(email["smimeVerifiedAt"].is_string(), "{name}: {email}");
+
+        // Verification results are cached
+        assert_eq!(
+            smime_properties(account, &email_id).await,
+            email,
+            "{name}: {email}"
+        );
+    }
+
+    // Unsigned messages have no S/MIME status
+    let email_id = import(
+        &client,
+        &mailbox_id,
+        concat!(
+            "From: Jane Smith <jane.smith@example.com>\r\n",
+            "To: John Doe <jdoe@example.com>\r\n",
+            "Subject: Unsigned\r\n",
+            "\r\n",
+            "This message is not signed.\r\n",
+        )
+        .to_string(),
+    )
+    .await;
+    let email = smime_properties(account, &email_id).await;
+    for property in ["smimeStatus", "smimeErrors", "smimeVerifiedAt"] {
+        assert_eq!(email[property], Value::Null, "{property}: {email}");
+    }
+
+    // Without trust anchors, valid signatures are reported as signed only
+    admin
+        .registry_update_setting(
+            Jmap {
+                smime_trust_anchors: None,
+                ..Default::default()
+            },
+            &[Property::SmimeTrustAnchors],
+        )
+        .await;
+    admin.reload_settings().await;
+    let email_id = import(&client, &mailbox_id, signed).await;
+    let email = smime_properties(account, &email_id).await;
+    assert_eq!(email["smimeStatus"], "signed", "{email}");
+    assert_eq!(email["smimeErrors"], Value::Null, "{email}");
+
+    // Clean up
+    test.destroy_all_mailboxes(account).await;
+    test.assert_is_empty().await;
+}
+
+async fn import(client: &Client, mailbox_id: &str, message: String) -> String {
+    client
+        .email_import(message.into_bytes(), [mailbox_id], None::<Vec<&str>>, None)
+        .await
+        .unwrap()
+        .take_id()
+}
+
+async fn smime_properties(account: &Account, email_id: &str) -> Value {
+    account
+        .jmap_get(
+            "Email",
+            ["smimeStatus", "smimeErrors", "smimeVerifiedAt"],
+            [email_id],
+        )
+        .await
+        .list()[0]
+        .clone()
+}
@@ -196,6 +196,8 @@ pub async fn jmap_tests() {
     mail::sieve_script::test(&test).await;
     mail::vacation_response::test(&test).await;
     mail::submission::test(&test).await;
+    mail::mdn::test(&test).await;
+    mail::smime::test(&test).await;
 
     core::event_source::test(&test).await;
     core::websocket::test(&test).await;
@@ -213,6 +215,7 @@ pub async fn jmap_tests() {
     calendar::event::test(&test).await;
     calendar::notification::test(&test).await;
     calendar::alarm::test(&test).await;
+    calendar::task::test(&test).await;
 
     calendar::identity::test(&test).await;
     calendar::acl::test(&test).await;
@@ -45,12 +45,15 @@ pub async fn test(test: &TestServer) {
         "urn:ietf:params:jmap:mail": {},
         "urn:ietf:params:jmap:calendars": {},
         "urn:ietf:params:jmap:calendars:parse": {},
+        "urn:ietf:params:jmap:tasks": {},
         "urn:ietf:params:jmap:contacts": {},
         "urn:ietf:params:jmap:contacts:parse": {},
         "urn:ietf:params:jmap:filenode": {},
         "urn:ietf:params:jmap:principals": {},
         "urn:ietf:params:jmap:principals:availability": {},
         "urn:ietf:params:jmap:submission": {},
+        "urn:ietf:params:jmap:mdn": {},
+        "urn:ietf:params:jmap:smimeverify": {},
         "urn:ietf:params:jmap:vacationresponse": {},
         "urn:ietf:params:jmap:sieve": {
           "implementation": "Stalwart v1.0.0"
@@ -102,6 +105,8 @@ pub async fn test(test: &TestServer) {
                 "REQUIRETLS": []
               }
             },
+            "urn:ietf:params:jmap:mdn": {},
+            "urn:ietf:params:jmap:smimeverify": {},
             "urn:ietf:params:jmap:vacationresponse": {},
             "urn:ietf:params:jmap:contacts": {
               "maxAddressBooksPerCard": null,
@@ -117,6 +122,7 @@ pub async fn test(test: &TestServer) {
               "mayCreateCalendar": true
             },
             "urn:ietf:params:jmap:calendars:parse": {},
+            "urn:ietf:params:jmap:tasks": {},
             "urn:ietf:params:jmap:websocket": {},
             "urn:ietf:params:jmap:sieve": {
               "maxSizeScriptName": 512,
@@ -249,11 +255,14 @@ pub async fn test(test: &TestServer) {
       "primaryAccounts": {
         "urn:ietf:params:jmap:mail": john_id,
         "urn:ietf:params:jmap:submission": john_id,
+        "urn:ietf:params:jmap:mdn": john_id,
+        "urn:ietf:params:jmap:smimeverify": john_id,
         "urn:ietf:params:jmap:vacationresponse": john_id,
         "urn:ietf:params:jmap:contacts": john_id,
         "urn:ietf:params:jmap:contacts:parse": john_id,
         "urn:ietf:params:jmap:calendars": john_id,
         "urn:ietf:params:jmap:calendars:parse": john_id,
+        "urn:ietf:params:jmap:tasks": john_id,
         "urn:ietf:params:jmap:websocket": john_id,
         "urn:ietf:params:jmap:sieve": john_id,
         "urn:ietf:params:jmap:blob": john_id,
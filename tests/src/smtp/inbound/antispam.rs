@@ -524,6 +524,7 @@ async fn antispam() {
                         arc_result.as_ref(),
                         dmarc_result.as_ref(),
                         dmarc_policy.as_ref(),
+                        None,
                     )
                     .await
                 {
@@ -0,0 +1,235 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    smtp::{
+        inbound::TestMessage,
+        session::{TestSession, VerifyResponse},
+    },
+    utils::server::TestServerBuilder,
+};
+use registry::schema::{
+    prelude::{ObjectType, Property},
+    structs::MtaAntivirus,
+};
+use serde_json::json;
+use smtp::queue::RCPT_QUARANTINE;
+use std::time::Duration;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use types::id::Id;
+
+const VIRUS_MARKER: &[u8] = b"STALWART-ANTIVIRUS-TEST-SIGNATURE";
+
+#[tokio::test]
+async fn antivirus_session() {
+    let mut test = TestServerBuilder::new("smtp_antivirus_test")
+        .await
+        .capture_queue()
+        .disable_services()
+        .build()
+        .await;
+
+    // Add test settings
+    let admin = test.account("admin");
+    admin.mta_no_auth().await;
+    admin.mta_allow_relaying().await;
+    admin
+        .registry_create_object(MtaAntivirus {
+            enable: true,
+            host: "127.0.0.1".into(),
+            port: 9335,
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+
+    tokio::spawn(spawn_mock_clamd_server());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Build session
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session.ehlo("mx.doe.org").await;
+
+    // Clean messages should be accepted
+    session
+        .send_message(
+            "john@doe.org",
+            &["bill@foobar.org"],
+            "From: john@doe.org\r\nSubject: Clean\r\n\r\nHello world\r\n",
+            "250 2.0.0",
+        )
+        .await;
+    test.expect_message()
+        .await
+        .read_lines(&test)
+        .await
+        .assert_not_contains("X-Virus-Found");
+
+    // Infected messages should be rejected
+    let infected = format!(
+        "From: john@doe.org\r\nSubject: Infected\r\n\r\n{}\r\n",
+        std::str::from_utf8(VIRUS_MARKER).unwrap()
+    );
+    session
+        .send_message("john@doe.org", &["bill@foobar.org"], &infected, "554 5.7.1")
+        .await;
+    test.assert_no_events();
+
+    // Infected messages should be silently discarded
+    admin
+        .registry_update_object(
+            ObjectType::MtaAntivirus,
+            Id::singleton(),
+            json!({
+                Property::Action: "discard"
+            }),
+        )
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+    session
+        .send_message("john@doe.org", &["bill@foobar.org"], &infected, "250 2.0.0")
+        .await;
+    test.assert_no_events();
+
+    // Infected messages should be delivered when tagging
+    admin
+        .registry_update_object(
+            ObjectType::MtaAntivirus,
+            Id::singleton(),
+            json!({
+                Property::Action: "tag"
+            }),
+        )
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+    session
+        .send_message("john@doe.org", &["bill@foobar.org"], &infected, "250 2.0.0")
+        .await;
+    test.expect_message()
+        .await
+        .read_lines(&test)
+        .await
+        .assert_contains("X-Virus-Found: Stalwart-Test-Signature");
+
+    // Infected messages should be delivered to the quarantine
+    admin
+        .registry_update_object(
+            ObjectType::MtaAntivirus,
+            Id::singleton(),
+            json!({
+                Property::Action: "quarantine"
+            }),
+        )
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+    session
+        .send_message("john@doe.org", &["bill@foobar.org"], &infected, "250 2.0.0")
+        .await;
+    let message = test.expect_message().await;
+    assert!(
+        message
+            .message
+            .recipients
+            .iter()
+            .all(|rcpt| rcpt.flags & RCPT_QUARANTINE != 0)
+    );
+    message
+        .read_lines(&test)
+        .await
+        .assert_contains("X-Quarantine: Virus found: Stalwart-Test-Signature")
+        .assert_contains("X-Virus-Found: Stalwart-Test-Signature");
+
+    // Scanner errors should not block delivery by default
+    admin
+        .registry_update_object(
+            ObjectType::MtaAntivirus,
+            Id::singleton(),
+            json!({
+                Property::Port: 9336
+            }),
+        )
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+    session
+        .send_message("john@doe.org", &["bill@foobar.org"], &infected, "250 2.0.0")
+        .await;
+    test.expect_message().await;
+
+    // Unless configured to return a temporary failure
+    admin
+        .registry_update_object(
+            ObjectType::MtaAntivirus,
+            Id::singleton(),
+            json!({
+                Property::TempFailOnError: true
+            }),
+        )
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+    session
+        .send_message("john@doe.org", &["bill@foobar.org"], &infected, "451 4.3.5")
+        .await;
+    test.assert_no_events();
+}
+
+async fn spawn_mock_clamd_server() {
+    let listener = TcpListener::bind("127.0.0.1:9335")
+        .await
+        .unwrap_or_else(|e| {
+            panic!("Failed to bind mock clamd server to 127.0.0.1:9335: {e}");
+        });
+
+    loop {
+        let (stream, _) = listener.accept().await.unwrap();
+        tokio::spawn(handle_clamd_session(stream));
+    }
+}
+
+async fn handle_clamd_session(mut stream: TcpStream) {
+    let mut command = [0u8; 10];
+    stream.read_exact(&mut command).await.unwrap();
+    assert_eq!(&command, b"zINSTREAM\0");
+
+    let mut contents = Vec::new();
+    loop {
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).await.unwrap();
+        let len = u32::from_be_bytes(len) as usize;
+        if len == 0 {
+            break;
+        }
+        let offset = contents.len();
+        contents.resize(offset + len, 0);
+        stream.read_exact(&mut contents[offset..]).await.unwrap();
+    }
+
+    let response: &[u8] = if contents
+        .windows(VIRUS_MARKER.len())
+        .any(|window| window == VIRUS_MARKER)
+    {
+        b"stream: Stalwart-Test-Signature FOUND\0"
+    } else {
+        b"stream: OK\0"
+    };
+    stream.write_all(response).await.unwrap();
+}
@@ -0,0 +1,183 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    smtp::{
+        inbound::TestMessage,
+        session::{TestSession, VerifyResponse},
+    },
+    utils::server::TestServerBuilder,
+};
+use ::email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    mailbox::DRAFTS_ID,
+    message::{
+        ingest::{EmailIngest, IngestEmail, IngestSource},
+        urlauth::{ImapUrl, UrlAuthFetch},
+    },
+};
+use common::auth::{AccessToken, AccountCache, AccountInfo};
+use mail_parser::MessageParser;
+use std::sync::Arc;
+
+const DRAFT: &[u8] =
+    b"From: john@foobar.org\r\nTo: bill@example.net\r\nSubject: Draft\r\n\r\nThis is a draft.\r\n";
+
+#[tokio::test]
+async fn burl() {
+    let mut test = TestServerBuilder::new("smtp_burl_test")
+        .await
+        .with_http_listener(19052)
+        .await
+        .disable_services()
+        .capture_queue()
+        .build()
+        .await;
+
+    // Create test users
+    let admin = test.account("admin");
+    let john = admin
+        .create_user_account(
+            "john@foobar.org",
+            "12345 + extra safety",
+            "John Doe",
+            &[],
+            vec![],
+        )
+        .await;
+    admin
+        .create_user_account(
+            "jane@foobar.org",
+            "abcde + extra safety",
+            "Jane Smith",
+            &[],
+            vec![],
+        )
+        .await;
+    admin.mta_allow_relaying().await;
+    admin.mta_no_auth().await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+
+    // Store a draft in John's mailbox
+    let account_id = john.id().document_id();
+    let uid_validity = test
+        .server
+        .get_cached_messages(account_id)
+        .await
+        .unwrap()
+        .mailbox_by_id(&DRAFTS_ID)
+        .unwrap()
+        .uid_validity;
+    let uid = test
+        .server
+        .email_ingest(IngestEmail {
+            raw_message: DRAFT,
+            message: MessageParser::new().parse(DRAFT),
+            blob_hash: None,
+            access_token: &AccessToken::from_id_maybe_invalid(account_id),
+            mailbox_ids: vec![DRAFTS_ID],
+            keywords: vec![],
+            received_at: None,
+            source: IngestSource::Imap {
+                train_classifier: false,
+            },
+            session_id: 0,
+        })
+        .await
+        .unwrap()
+        .imap_uids[0];
+
+    // Generate authorized URLs
+    let access_key = test
+        .server
+        .mailbox_access_key(account_id, DRAFTS_ID, true)
+        .await
+        .unwrap()
+        .unwrap();
+    let sign = |access: &str| {
+        let rump = format!(
+            "imap://john%40foobar.org@mail.example.org/Drafts;UIDVALIDITY={uid_validity}/;UID={uid};URLAUTH={access}"
+        );
+        let token = ImapUrl::parse(&rump)
+            .unwrap()
+            .auth
+            .unwrap()
+            .generate_token(access_key.as_bytes());
+        format!("{rump}:INTERNAL:{token}")
+    };
+    let url = sign("submit+john%40foobar.org");
+    let url_jane = sign("submit+jane%40foobar.org");
+    let url_foreign = url.replace("@mail.example.org/", "@mail.attacker.net/");
+    let mut url_tampered = url.clone();
+    let last_ch = url_tampered.pop().unwrap();
+    url_tampered.push(if last_ch == '0' { '1' } else { '0' });
+
+    // BURL should not be advertised to unauthenticated clients
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session
+        .ehlo("mx.foobar.org")
+        .await
+        .assert_not_contains("BURL");
+    session.cmd(&format!("BURL {url} LAST"), "502 5.5.1").await;
+
+    // Authenticate as John
+    session.data.authenticated_as = Some(AccountInfo {
+        account_id,
+        addresses: vec!["john@foobar.org".into()],
+        account: Arc::new(AccountCache {
+            name: "john@foobar.org".into(),
+            ..Default::default()
+        }),
+    });
+    session.eval_post_auth_params().await;
+    session.ehlo("mx.foobar.org").await.assert_contains("BURL");
+
+    // BURL requires an open mail transaction
+    session.cmd(&format!("BURL {url} LAST"), "503 5.5.1").await;
+    session.mail_from("john@foobar.org", "250").await;
+    session.rcpt_to("bill@example.net", "250").await;
+
+    // Invalid, tampered or unauthorized URLs should be rejected
+    session
+        .cmd("BURL imap://mail.foobar.org/Drafts LAST", "554 5.7.0")
+        .await;
+    session
+        .cmd(&format!("BURL {url_foreign} LAST"), "554 5.7.0")
+        .await;
+    session
+        .cmd(&format!("BURL {url_tampered} LAST"), "554 5.6.6")
+        .await;
+    session
+        .cmd(&format!("BURL {url_jane} LAST"), "554 5.6.6")
+        .await;
+
+    // A failed reference discards the partially assembled message
+    session.cmd(&format!("BURL {url}"), "250 2.5.0").await;
+    session
+        .cmd(&format!("BURL {url_tampered} LAST"), "554 5.6.6")
+        .await;
+    assert!(session.data.message.is_empty());
+
+    // Submit the draft by reference
+    session.cmd(&format!("BURL {url} LAST"), "250 2.0.0").await;
+    let message = test.expect_message().await;
+    assert_eq!(
+        message.message.recipients.last().unwrap().address(),
+        "bill@example.net"
+    );
+    let contents = message.read_message(&test).await;
+    assert!(contents.contains("Subject: Draft"), "{contents}");
+    assert_eq!(
+        contents.matches("This is a draft.").count(),
+        1,
+        "{contents}"
+    );
+    test.assert_no_events();
+}
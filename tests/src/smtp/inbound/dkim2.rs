@@ -400,6 +400,7 @@ impl Account {
             dkim_sign_domain: expr(dkim_sign_domain),
             dkim_verify: expr("relaxed"),
             dkim_strict: false,
+            arc_seal_domain: expr("false"),
         })
         .await;
     }
@@ -95,6 +95,10 @@ async fn dmarc() {
                 else_: "strict".into(),
             },
             dkim_strict: false,
+            arc_seal_domain: Expression {
+                else_: "false".into(),
+                ..Default::default()
+            },
         })
         .await;
     admin
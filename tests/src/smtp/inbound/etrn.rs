@@ -0,0 +1,215 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    smtp::{
+        inbound::TestQueueEvent,
+        session::{TestSession, VerifyResponse},
+    },
+    utils::server::TestServerBuilder,
+};
+use common::config::smtp::queue::QueueName;
+use registry::{
+    schema::structs::{Expression, ExpressionMatch, MtaExtensions, MtaStageAuth},
+    types::list::List,
+};
+use smtp::queue::{Status, manager::matches_domain, spool::SmtpSpool};
+use store::write::now;
+
+#[tokio::test]
+async fn etrn_atrn() {
+    let mut test = TestServerBuilder::new("smtp_etrn_test")
+        .await
+        .with_http_listener(19051)
+        .await
+        .disable_services()
+        .capture_queue()
+        .build()
+        .await;
+
+    // Add test settings
+    let admin = test.account("admin");
+    admin.mta_allow_relaying().await;
+    admin
+        .registry_create_object(MtaStageAuth {
+            require: Expression {
+                else_: "false".into(),
+                ..Default::default()
+            },
+            sasl_mechanisms: Expression {
+                else_: "[plain, login]".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await;
+    admin
+        .registry_create_object(MtaExtensions {
+            etrn: Expression {
+                match_: List::from_iter([ExpressionMatch {
+                    if_: "remote_ip = '10.0.0.1'".into(),
+                    then: "true".into(),
+                }]),
+                else_: "false".into(),
+            },
+            atrn: Expression {
+                match_: List::from_iter([ExpressionMatch {
+                    if_: "remote_ip = '10.0.0.3'".into(),
+                    then: "true".into(),
+                }]),
+                else_: "false".into(),
+            },
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+
+    // EHLO should not advertise ETRN/ATRN to 10.0.0.2
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.2".into();
+    session.eval_session_params().await;
+    session
+        .ehlo("mx.foobar.org")
+        .await
+        .assert_not_contains("ETRN")
+        .assert_not_contains("ATRN");
+    session.cmd("ETRN example.net", "502 5.5.1").await;
+    session.cmd("ATRN example.net", "502 5.5.1").await;
+
+    // EHLO is required before ETRN
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session.cmd("ETRN example.net", "503 5.5.1").await;
+    session
+        .ehlo("mx.foobar.org")
+        .await
+        .assert_contains("ETRN")
+        .assert_not_contains("ATRN");
+
+    // Queue names are not supported
+    session.cmd("ETRN #default", "459 4.7.1").await;
+
+    // No messages waiting
+    session.cmd("ETRN example.net", "251 2.0.0").await;
+
+    // Queue a message and postpone its delivery
+    session
+        .send_message(
+            "john@foobar.org",
+            &["bill@example.net", "jane@example.org"],
+            "test:no_dkim",
+            "250",
+        )
+        .await;
+    let mut message = test.expect_message().await;
+    let queue_id = message.queue_id;
+    let prev_events = message.message.next_events();
+    for rcpt in message.message.recipients.iter_mut() {
+        rcpt.retry.due = now() + 3600;
+    }
+    let num_rcpts = message.message.recipients.len();
+    assert!(
+        message
+            .save_registry_changes(&test.server, prev_events, (0..num_rcpts).collect())
+            .await
+    );
+
+    // ETRN should only reschedule the recipients of the requested node
+    session
+        .cmd("ETRN @example.net", "253 2.0.0 1 pending messages")
+        .await;
+    test.read_event().await.assert_refresh();
+    let message = test
+        .server
+        .read_message(queue_id, QueueName::default())
+        .await
+        .unwrap();
+    for rcpt in &message.message.recipients {
+        if rcpt.address() == "bill@example.net" {
+            assert!(rcpt.retry.due <= now());
+        } else {
+            assert!(rcpt.retry.due > now());
+        }
+    }
+    session.cmd("ETRN example.com", "251 2.0.0").await;
+    test.assert_no_events();
+
+    // Recipients that are already due are still reported as pending
+    session
+        .cmd("ETRN @example.net", "253 2.0.0 1 pending messages")
+        .await;
+    test.read_event().await.assert_refresh();
+
+    // Node matching is case insensitive
+    assert!(matches_domain(&["@example.net".into()], "MX.Example.NET"));
+    assert!(matches_domain(&["EXAMPLE.NET".into()], "example.net"));
+    assert!(!matches_domain(&["@example.net".into()], "badexample.net"));
+
+    // ATRN requires authentication
+    test.account("admin")
+        .create_user_account(
+            "relay@example.net",
+            "12345 + extra safety",
+            "Relay",
+            &[],
+            vec![],
+        )
+        .await;
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.3".into();
+    session.eval_session_params().await;
+    session.stream.tls = true;
+    session.ehlo("mx.example.net").await.assert_contains("ATRN");
+    session.cmd("ATRN example.net", "530 5.7.0").await;
+    session
+        .auth_plain("relay@example.net", "12345 + extra safety", "235 2.7.0")
+        .await;
+
+    // Only domains the account has addresses in can be requested
+    session.cmd("ATRN example.org", "450 4.7.1").await;
+    session
+        .cmd("ATRN example.net,example.org", "450 4.7.1")
+        .await;
+
+    // ATRN without arguments turns the connection around for all authorized domains
+    session.queue_rx(&[
+        "220 mx.example.net ESMTP\r\n",
+        "250 mx.example.net\r\n",
+        "250 2.1.0 OK\r\n",
+        "250 2.1.5 OK\r\n",
+        "354 Start mail input\r\n",
+        "250 2.0.0 Message accepted\r\n",
+        "221 2.0.0 Bye\r\n",
+    ]);
+    assert!(session.ingest(b"ATRN\r\n").await.is_err());
+    let transcript = session.response();
+    assert_eq!(transcript[0], "250 2.0.0 OK now reversing the connection.");
+    transcript
+        .assert_contains("EHLO localhost")
+        .assert_contains("MAIL FROM:<john@foobar.org>")
+        .assert_contains("RCPT TO:<bill@example.net>")
+        .assert_not_contains("RCPT TO:<jane@example.org>")
+        .assert_contains("DATA")
+        .assert_contains("QUIT");
+    assert!(session.stream.rx_queue.is_empty());
+
+    // Only the recipients of the requested domains were delivered
+    let message = test
+        .server
+        .read_message(queue_id, QueueName::default())
+        .await
+        .unwrap();
+    for rcpt in &message.message.recipients {
+        if rcpt.address() == "bill@example.net" {
+            assert!(matches!(rcpt.status, Status::Completed(_)), "{rcpt:?}");
+        } else {
+            assert!(matches!(rcpt.status, Status::Scheduled), "{rcpt:?}");
+        }
+    }
+}
@@ -0,0 +1,678 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    smtp::inbound::TestMessage,
+    utils::{
+        http::HttpRequest,
+        server::{TestServer, TestServerBuilder},
+    },
+};
+use email::message::delivery::LocalDeliveryStatus;
+use hyper::Method;
+use registry::{
+    schema::{
+        enums::{MailingListFromRewrite, MailingListPostingPolicy, MailingListSubscriptionPolicy},
+        prelude::{ObjectType, Property},
+        structs::MailingList,
+    },
+    types::{EnumImpl, map::Map},
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use smtp::lists::{ListMessage, MailingListManager};
+use types::id::Id;
+
+#[tokio::test]
+async fn mailing_lists() {
+    let mut test = TestServerBuilder::new("smtp_list_test")
+        .await
+        .with_http_listener(19053)
+        .await
+        .disable_services()
+        .capture_queue()
+        .build()
+        .await;
+
+    let admin = test.account("admin");
+    let domain_id = admin.find_or_create_domain("example.org").await;
+    let list_id = admin
+        .registry_create_object(MailingList {
+            name: "team".into(),
+            domain_id,
+            managed: true,
+            recipients: Map::new(vec!["bob@remote.org".into(), "carol@remote.org".into()]),
+            moderators: Map::new(vec!["mod@remote.org".into()]),
+            subscription_policy: MailingListSubscriptionPolicy::Open,
+            posting_policy: MailingListPostingPolicy::Moderated,
+            from_rewrite: MailingListFromRewrite::Never,
+            bounce_threshold: 2,
+            ..Default::default()
+        })
+        .await;
+
+    // Open subscription policy subscribes immediately
+    assert_eq!(
+        deliver(&test, "team+subscribe@example.org", "dave@remote.org", "").await,
+        LocalDeliveryStatus::Success
+    );
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].rcpt, "dave@remote.org");
+    assert!(messages[0].contents.contains("[team] Welcome"));
+    assert!(
+        list(&test, list_id)
+            .await
+            .recipients
+            .contains("dave@remote.org")
+    );
+
+    // Subscribing twice is reported back to the sender
+    deliver(&test, "team+subscribe@example.org", "dave@remote.org", "").await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contents.contains("[team] Already subscribed"));
+
+    // Confirm policy requires a reply to the confirmation address
+    update_list(
+        &test,
+        list_id,
+        json!({Property::SubscriptionPolicy: MailingListSubscriptionPolicy::Confirm.as_str()}),
+    )
+    .await;
+    deliver(&test, "team+subscribe@example.org", "erin@remote.org", "").await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].rcpt, "erin@remote.org");
+    assert!(messages[0].contents.contains("[team] Confirm subscription"));
+    assert!(
+        !list(&test, list_id)
+            .await
+            .recipients
+            .contains("erin@remote.org")
+    );
+    let token = token_after(&messages[0].contents, "team+confirm-");
+
+    // Invalid confirmation codes are rejected
+    deliver(
+        &test,
+        "team+confirm-0123456789abcdef0123@example.org",
+        "erin@remote.org",
+        "",
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contents.contains("[team] Invalid confirmation"));
+    assert!(
+        !list(&test, list_id)
+            .await
+            .recipients
+            .contains("erin@remote.org")
+    );
+
+    // Valid confirmation subscribes the address, the code can only be used once
+    deliver(
+        &test,
+        &format!("team+confirm-{token}@example.org"),
+        "erin@remote.org",
+        "",
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contents.contains("[team] Welcome"));
+    assert!(
+        list(&test, list_id)
+            .await
+            .recipients
+            .contains("erin@remote.org")
+    );
+    deliver(
+        &test,
+        &format!("team+confirm-{token}@example.org"),
+        "erin@remote.org",
+        "",
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert!(messages[0].contents.contains("[team] Invalid confirmation"));
+
+    // Closed lists deny subscription requests
+    update_list(
+        &test,
+        list_id,
+        json!({Property::SubscriptionPolicy: MailingListSubscriptionPolicy::Closed.as_str()}),
+    )
+    .await;
+    deliver(&test, "team+subscribe@example.org", "frank@remote.org", "").await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert!(
+        messages[0]
+            .contents
+            .contains("[team] Subscription request denied")
+    );
+    assert!(
+        !list(&test, list_id)
+            .await
+            .recipients
+            .contains("frank@remote.org")
+    );
+
+    // Posts from subscribers are distributed to every subscriber
+    assert_eq!(
+        deliver(
+            &test,
+            "team@example.org",
+            "bob@remote.org",
+            &post("Bob <bob@remote.org>", "Hello team"),
+        )
+        .await,
+        LocalDeliveryStatus::Success
+    );
+    let messages = take_messages(&mut test).await;
+    assert_eq!(
+        messages.iter().map(|m| m.rcpt.as_str()).collect::<Vec<_>>(),
+        vec![
+            "bob@remote.org",
+            "carol@remote.org",
+            "dave@remote.org",
+            "erin@remote.org"
+        ]
+    );
+    for message in &messages {
+        assert!(
+            message
+                .contents
+                .contains("List-Id: \"team\" <team.example.org>"),
+            "{}",
+            message.contents
+        );
+        assert!(message.contents.contains("From: Bob <bob@remote.org>"));
+        assert!(!message.contents.contains("Reply-To:"));
+        assert!(
+            message
+                .contents
+                .contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click")
+        );
+    }
+
+    // Each subscriber gets its own return path
+    let mut return_paths = messages
+        .iter()
+        .map(|m| m.return_path.as_str())
+        .collect::<Vec<_>>();
+    assert!(
+        return_paths
+            .iter()
+            .all(|rp| rp.starts_with("team+bounces-") && rp.ends_with("@example.org"))
+    );
+    return_paths.sort_unstable();
+    return_paths.dedup();
+    assert_eq!(return_paths.len(), messages.len());
+
+    // From rewriting keeps the original author in Reply-To
+    update_list(
+        &test,
+        list_id,
+        json!({Property::FromRewrite: MailingListFromRewrite::Always.as_str()}),
+    )
+    .await;
+    deliver(
+        &test,
+        "team@example.org",
+        "bob@remote.org",
+        &post("Bob <bob@remote.org>", "Rewritten"),
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 4);
+    for message in &messages {
+        assert!(
+            message.contents.contains("Bob via team")
+                && message.contents.contains("<team@example.org>"),
+            "{}",
+            message.contents
+        );
+        assert!(message.contents.contains("Reply-To: Bob <bob@remote.org>"));
+    }
+    update_list(
+        &test,
+        list_id,
+        json!({Property::FromRewrite: MailingListFromRewrite::Never.as_str()}),
+    )
+    .await;
+
+    // Posts from non-subscribers are held for moderation
+    assert_eq!(
+        deliver(
+            &test,
+            "team@example.org",
+            "stranger@remote.org",
+            &post("stranger@remote.org", "Buy now"),
+        )
+        .await,
+        LocalDeliveryStatus::Success
+    );
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].rcpt, "mod@remote.org");
+    assert!(
+        messages[0]
+            .contents
+            .contains("[team] Approval required: Buy now")
+    );
+    let reject_id = token_after(&messages[0].contents, "team+reject-");
+
+    // Only moderators can approve or reject
+    assert!(matches!(
+        deliver(
+            &test,
+            &format!("team+reject-{reject_id}@example.org"),
+            "bob@remote.org",
+            "",
+        )
+        .await,
+        LocalDeliveryStatus::PermanentFailure {
+            code: [5, 7, 1],
+            ..
+        }
+    ));
+    assert!(take_messages(&mut test).await.is_empty());
+
+    assert!(matches!(
+        deliver(
+            &test,
+            &format!("team+reject-{reject_id}@example.org"),
+            "bob@remote.org",
+            "From: mod@remote.org\r\nSubject: command\r\n\r\ncommand\r\n",
+        )
+        .await,
+        LocalDeliveryStatus::PermanentFailure {
+            code: [5, 7, 1],
+            ..
+        }
+    ));
+    assert!(take_messages(&mut test).await.is_empty());
+
+    // Rejecting notifies the sender and discards the message
+    deliver(
+        &test,
+        &format!("team+reject-{reject_id}@example.org"),
+        "mod@remote.org",
+        "",
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].rcpt, "stranger@remote.org");
+    assert!(messages[0].contents.contains("[team] Message rejected"));
+
+    // Held messages can only be processed once
+    deliver(
+        &test,
+        &format!("team+approve-{reject_id}@example.org"),
+        "mod@remote.org",
+        "",
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contents.contains("[team] Message not found"));
+
+    // Approving distributes the held message
+    deliver(
+        &test,
+        "team@example.org",
+        "stranger@remote.org",
+        &post("stranger@remote.org", "Please approve"),
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    let approve_id = token_after(&messages[0].contents, "team+approve-");
+    deliver(
+        &test,
+        &format!("team+approve-{approve_id}@example.org"),
+        "mod@remote.org",
+        "",
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(messages.len(), 4);
+    assert!(
+        messages
+            .iter()
+            .all(|m| m.contents.contains("Subject: Please approve"))
+    );
+
+    // Subscribers-only lists reject posts from non-subscribers
+    update_list(
+        &test,
+        list_id,
+        json!({Property::PostingPolicy: MailingListPostingPolicy::Subscribers.as_str()}),
+    )
+    .await;
+    assert!(matches!(
+        deliver(
+            &test,
+            "team@example.org",
+            "stranger@remote.org",
+            &post("stranger@remote.org", "Let me in"),
+        )
+        .await,
+        LocalDeliveryStatus::PermanentFailure {
+            code: [5, 7, 1],
+            ..
+        }
+    ));
+    assert!(take_messages(&mut test).await.is_empty());
+
+    // Forging a subscriber's From header does not bypass the posting policy
+    assert!(matches!(
+        deliver(
+            &test,
+            "team@example.org",
+            "stranger@remote.org",
+            &post("Bob <bob@remote.org>", "Let me in"),
+        )
+        .await,
+        LocalDeliveryStatus::PermanentFailure {
+            code: [5, 7, 1],
+            ..
+        }
+    ));
+    assert!(take_messages(&mut test).await.is_empty());
+
+    // Obtain each subscriber's return path and unsubscribe link
+    deliver(
+        &test,
+        "team@example.org",
+        "carol@remote.org",
+        &post("carol@remote.org", "Weekly update"),
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    let bob = messages
+        .iter()
+        .find(|m| m.rcpt == "bob@remote.org")
+        .unwrap();
+    let carol = messages
+        .iter()
+        .find(|m| m.rcpt == "carol@remote.org")
+        .unwrap();
+    let dave = messages
+        .iter()
+        .find(|m| m.rcpt == "dave@remote.org")
+        .unwrap();
+
+    // RFC 8058 one-click unsubscribe, GET only renders a confirmation form
+    let unsubscribe_url = link_after(&dave.contents, "https://127.0.0.1");
+    assert!(unsubscribe_url.starts_with("/list/unsubscribe?t="));
+    let http = HttpRequest {
+        port: 19053,
+        username: None,
+        password: None,
+    };
+    let response = http
+        .send_full(Method::GET, &unsubscribe_url, None, None)
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.contains("method=\"post\""));
+    assert!(
+        list(&test, list_id)
+            .await
+            .recipients
+            .contains("dave@remote.org")
+    );
+
+    let response = http
+        .send_full(
+            Method::POST,
+            &unsubscribe_url,
+            Some(b"List-Unsubscribe=One-Click".to_vec()),
+            Some("application/x-www-form-urlencoded"),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.contains("You have been unsubscribed"));
+    assert!(
+        !list(&test, list_id)
+            .await
+            .recipients
+            .contains("dave@remote.org")
+    );
+
+    // Replaying the link does not report a second unsubscription
+    let response = http
+        .send_full(
+            Method::POST,
+            &unsubscribe_url,
+            Some(b"List-Unsubscribe=One-Click".to_vec()),
+            Some("application/x-www-form-urlencoded"),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    // Tampered tokens are rejected
+    let response = http
+        .send_full(
+            Method::POST,
+            "/list/unsubscribe?t=invalid",
+            Some(b"List-Unsubscribe=One-Click".to_vec()),
+            Some("application/x-www-form-urlencoded"),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+
+    // Forged bounces to the shared bounce address are ignored
+    for _ in 0..3 {
+        deliver(
+            &test,
+            "team+bounces@example.org",
+            "",
+            &dsn("bob@remote.org", "failed"),
+        )
+        .await;
+    }
+    assert!(
+        list(&test, list_id)
+            .await
+            .recipients
+            .contains("bob@remote.org")
+    );
+
+    // Bounces are attributed to the subscriber the return path belongs to,
+    // regardless of the recipient named in the report
+    for _ in 0..3 {
+        deliver(
+            &test,
+            &carol.return_path,
+            "",
+            &dsn("bob@remote.org", "delayed"),
+        )
+        .await;
+    }
+    let current = list(&test, list_id).await;
+    assert!(current.recipients.contains("bob@remote.org"));
+    assert!(current.recipients.contains("carol@remote.org"));
+
+    // Tampered return paths are ignored
+    deliver(
+        &test,
+        "team+bounces-0123456789abcdef0123@example.org",
+        "",
+        &dsn("bob@remote.org", "failed"),
+    )
+    .await;
+    deliver(
+        &test,
+        &bob.return_path,
+        "",
+        &dsn("bob@remote.org", "failed"),
+    )
+    .await;
+    assert!(
+        list(&test, list_id)
+            .await
+            .recipients
+            .contains("bob@remote.org")
+    );
+
+    // Reaching the threshold suspends the subscriber
+    deliver(
+        &test,
+        &bob.return_path,
+        "",
+        &dsn("bob@remote.org", "failed"),
+    )
+    .await;
+    let current = list(&test, list_id).await;
+    assert!(!current.recipients.contains("bob@remote.org"));
+    assert!(current.suspended_recipients.contains("bob@remote.org"));
+    assert!(current.recipients.contains("carol@remote.org"));
+    assert!(take_messages(&mut test).await.is_empty());
+
+    // Suspended subscribers no longer receive posts but can still post
+    deliver(
+        &test,
+        "team@example.org",
+        "bob@remote.org",
+        &post("bob@remote.org", "Am I still here?"),
+    )
+    .await;
+    let messages = take_messages(&mut test).await;
+    assert_eq!(
+        messages.iter().map(|m| m.rcpt.as_str()).collect::<Vec<_>>(),
+        vec!["carol@remote.org", "erin@remote.org"]
+    );
+}
+
+struct ListCopy {
+    rcpt: String,
+    return_path: String,
+    contents: String,
+}
+
+async fn deliver(
+    test: &TestServer,
+    rcpt: &str,
+    return_path: &str,
+    message: &str,
+) -> LocalDeliveryStatus {
+    let recipient = test
+        .server
+        .list_recipient(rcpt)
+        .await
+        .unwrap()
+        .unwrap_or_else(|| panic!("{rcpt} is not a list address"));
+    let message = if message.is_empty() {
+        format!("From: {return_path}\r\nSubject: command\r\n\r\ncommand\r\n")
+    } else {
+        message.to_string()
+    };
+
+    test.server
+        .list_deliver(
+            recipient,
+            ListMessage {
+                return_path,
+                raw_message: message.as_bytes(),
+                session_id: 0,
+            },
+        )
+        .await
+}
+
+async fn take_messages(test: &mut TestServer) -> Vec<ListCopy> {
+    let mut messages = Vec::new();
+    for message in test.read_queued_messages().await {
+        messages.push(ListCopy {
+            rcpt: message.message.recipients[0].address().to_string(),
+            return_path: message.message.return_path.to_string(),
+            contents: message.read_message(test).await,
+        });
+    }
+    while test.try_read_event().await.is_some() {}
+    test.clear_queue().await;
+    messages.sort_by(|a, b| a.rcpt.cmp(&b.rcpt));
+    messages
+}
+
+async fn list(test: &TestServer, list_id: Id) -> MailingList {
+    test.account("admin")
+        .registry_get::<MailingList>(list_id)
+        .await
+}
+
+async fn update_list(test: &TestServer, list_id: Id, changes: Value) {
+    test.account("admin")
+        .registry_update_object(ObjectType::MailingList, list_id, changes)
+        .await;
+}
+
+fn post(from: &str, subject: &str) -> String {
+    format!(
+        concat!(
+            "From: {}\r\n",
+            "To: team@example.org\r\n",
+            "Subject: {}\r\n",
+            "Message-ID: <{}@remote.org>\r\n",
+            "\r\n",
+            "Test message.\r\n"
+        ),
+        from,
+        subject,
+        subject.replace(' ', ".")
+    )
+}
+
+fn dsn(rcpt: &str, action: &str) -> String {
+    format!(
+        concat!(
+            "From: MAILER-DAEMON@remote.org\r\n",
+            "To: team@example.org\r\n",
+            "Subject: Delivery Status Notification\r\n",
+            "MIME-Version: 1.0\r\n",
+            "Content-Type: multipart/report; report-type=delivery-status; boundary=\"b\"\r\n",
+            "\r\n",
+            "--b\r\n",
+            "Content-Type: text/plain\r\n",
+            "\r\n",
+            "Delivery to {} {}.\r\n",
+            "--b\r\n",
+            "Content-Type: message/delivery-status\r\n",
+            "\r\n",
+            "Reporting-MTA: dns; mx.remote.org\r\n",
+            "\r\n",
+            "Final-Recipient: rfc822; {}\r\n",
+            "Action: {}\r\n",
+            "Status: 5.1.1\r\n",
+            "\r\n",
+            "--b--\r\n"
+        ),
+        rcpt, action, rcpt, action
+    )
+}
+
+fn token_after(text: &str, prefix: &str) -> String {
+    let start = text
+        .find(prefix)
+        .unwrap_or_else(|| panic!("{prefix} not found in {text}"))
+        + prefix.len();
+    text[start..]
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphanumeric())
+        .collect()
+}
+
+fn link_after(text: &str, prefix: &str) -> String {
+    let start = text
+        .find(prefix)
+        .unwrap_or_else(|| panic!("{prefix} not found in {text}"))
+        + prefix.len();
+    text[start..].chars().take_while(|ch| *ch != '>').collect()
+}
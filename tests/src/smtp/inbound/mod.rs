@@ -20,14 +20,18 @@ use tokio::sync::mpsc::error::TryRecvError;
 use types::id::Id;
 
 pub mod antispam;
+pub mod antivirus;
 pub mod asn;
 pub mod auth;
 pub mod basic;
+pub mod burl;
 pub mod data;
 pub mod dkim2;
 pub mod dmarc;
 pub mod ehlo;
+pub mod etrn;
 pub mod limits;
+pub mod lists;
 pub mod mail;
 pub mod milter;
 pub mod rcpt;
@@ -82,6 +82,10 @@ async fn sign_and_seal() {
                 ..Default::default()
             },
             dkim_strict: false,
+            arc_seal_domain: Expression {
+                else_: "'example.com'".into(),
+                ..Default::default()
+            },
         })
         .await;
     admin.reload_settings().await;
@@ -148,20 +152,17 @@ async fn sign_and_seal() {
             "DKIM-Signature: v=1; a=rsa-sha256; s=rsa; d=example.com; c=simple/relaxed;",
         );
 
-    // Test ARC verify
+    // Test ARC verify and seal
     session
         .send_message("bill@foobar.org", &["jdoe@example.com"], "test:arc", "250")
         .await;
-    test.expect_message().await;
-
-    /*
-    // DMARC WG is ending the ARC experiment
-
-    .read_lines(&test)
+    test.expect_message()
+        .await
+        .read_lines(&test)
         .await
-        .assert_contains("ARC-Seal: i=3; a=ed25519-sha256; s=ed; d=example.com; cv=pass;")
+        .assert_contains("ARC-Seal: i=3; a=rsa-sha256; s=rsa; d=example.com; cv=pass;")
         .assert_contains(
-            "ARC-Message-Signature: i=3; a=ed25519-sha256; s=ed; d=example.com; c=relaxed/simple;",
+            "ARC-Message-Signature: i=3; a=rsa-sha256; s=rsa; d=example.com; c=simple/relaxed;",
         );
 
     // Test ARC sealing of a DKIM signed message
@@ -172,10 +173,10 @@ async fn sign_and_seal() {
         .await
         .read_lines(&test)
         .await
-        .assert_contains("ARC-Seal: i=1; a=ed25519-sha256; s=ed; d=example.com; cv=none;")
+        .assert_contains("ARC-Seal: i=1; a=rsa-sha256; s=rsa; d=example.com; cv=none;")
         .assert_contains(
-            "ARC-Message-Signature: i=1; a=ed25519-sha256; s=ed; d=example.com; c=relaxed/simple;",
-        );*/
+            "ARC-Message-Signature: i=1; a=rsa-sha256; s=rsa; d=example.com; c=simple/relaxed;",
+        );
 }
 
 impl Account {
@@ -10,9 +10,10 @@ use common::{
     config::server::ServerProtocol,
     network::{ServerInstance, SessionStream, TcpAcceptor, limiter::ConcurrencyLimiter},
 };
+use directory::core::scram::ChannelBindings;
 use rustls::{ServerConfig, server::ResolvesServerCert};
 use smtp::core::{Session, SessionAddress, SessionData, SessionParameters, State};
-use std::{borrow::Cow, path::PathBuf, sync::Arc};
+use std::{borrow::Cow, collections::VecDeque, path::PathBuf, sync::Arc};
 use tokio::{
     io::{AsyncRead, AsyncWrite},
     sync::watch,
@@ -23,6 +24,7 @@ use utils::snowflake::SnowflakeIdGenerator;
 pub struct DummyIo {
     pub tx_buf: Vec<u8>,
     pub rx_buf: Vec<u8>,
+    pub rx_queue: VecDeque<Vec<u8>>,
     pub tls: bool,
 }
 
@@ -36,6 +38,9 @@ impl AsyncRead for DummyIo {
             buf.put_slice(&self.rx_buf);
             self.rx_buf.clear();
             std::task::Poll::Ready(Ok(()))
+        } else if let Some(chunk) = self.rx_queue.pop_front() {
+            buf.put_slice(&chunk);
+            std::task::Poll::Ready(Ok(()))
         } else {
             std::task::Poll::Pending
         }
@@ -75,6 +80,14 @@ impl SessionStream for DummyIo {
     fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>) {
         ("".into(), "".into())
     }
+
+    fn channel_bindings(&self, _: &Server) -> ChannelBindings {
+        ChannelBindings::default()
+    }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
 }
 
 impl Unpin for DummyIo {}
@@ -85,6 +98,7 @@ pub trait TestSession {
     fn test_with_shutdown(server: Server, shutdown_rx: watch::Receiver<bool>) -> Self;
     fn response(&mut self) -> Vec<String>;
     fn write_rx(&mut self, data: &str);
+    fn queue_rx(&mut self, chunks: &[&str]);
     async fn rset(&mut self);
     async fn cmd(&mut self, cmd: &str, expected_code: &str) -> Vec<String>;
     async fn auth_plain(&mut self, username: &str, secret: &str, expected_code: &str);
@@ -105,6 +119,7 @@ impl TestSession for Session<DummyIo> {
             server,
             stream: DummyIo {
                 rx_buf: vec![],
+                rx_queue: VecDeque::new(),
                 tx_buf: vec![],
                 tls: false,
             },
@@ -149,6 +164,13 @@ impl TestSession for Session<DummyIo> {
         self.stream.rx_buf.extend_from_slice(data.as_bytes());
     }
 
+    // Each chunk is returned by a separate read, used to script a remote peer
+    fn queue_rx(&mut self, chunks: &[&str]) {
+        self.stream
+            .rx_queue
+            .extend(chunks.iter().map(|chunk| chunk.as_bytes().to_vec()));
+    }
+
     async fn rset(&mut self) {
         self.ingest(b"RSET\r\n").await.unwrap();
         self.response().assert_code("250");
@@ -366,6 +388,7 @@ impl TestServerInstance for ServerInstance {
                 config: tls_config.clone(),
                 acceptor: TlsAcceptor::from(tls_config),
                 implicit: false,
+                client_cert_mapping: None,
             },
             limiter: ConcurrencyLimiter::new(100),
             shutdown_rx,
@@ -11,7 +11,14 @@ use crate::utils::{
 };
 use ::registry::schema::enums::CompressionAlgo;
 use ahash::AHashSet;
-use common::{DATABASE_SCHEMA_VERSION, manager::backup::BackupParams};
+use common::{
+    DATABASE_SCHEMA_VERSION,
+    manager::{backup::BackupParams, restore::RestoreParams},
+};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use store::{
     rand,
     write::{
@@ -201,17 +208,164 @@ pub async fn test(test: &TestServer) {
 
     // Import store
     println!("Importing store...");
-    test.server.core.restore(temp_dir.path.clone()).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(temp_dir.path.clone()))
+        .await;
 
     // Verify hash
     print!("Verifying store hash...");
     snapshot.assert_is_eq(&Snapshot::new(&db).await);
     println!(" GREAT SUCCESS!");
+    temp_dir.delete();
+
+    // Full backup
+    println!("Running full backup...");
+    let backup_dir = TempDir::new("art_vandelay_backups", true);
+    test.server
+        .core
+        .backup(BackupParams::incremental(backup_dir.path.clone(), true))
+        .await;
+
+    // Modify a single account and the queue
+    println!("Modifying account data...");
+    let data = random_bytes(512);
+    let new_hash = BlobHash::generate(data.as_slice());
+    test.server
+        .blob_store()
+        .put_blob(new_hash.as_ref(), &data, CompressionAlgo::Lz4)
+        .await
+        .unwrap();
+    let mut batch = BatchBuilder::new();
+    batch
+        .set(
+            ValueClass::Blob(BlobOp::Commit {
+                hash: new_hash.clone(),
+            }),
+            vec![],
+        )
+        .set(
+            ValueClass::Queue(QueueClass::Message(rand::random())),
+            random_bytes(10),
+        )
+        .with_account_id(3)
+        .with_collection(Collection::Email)
+        .with_document(10)
+        .clear(ValueClass::Property(0))
+        .set(ValueClass::Property(1), random_bytes(64))
+        .acl_revoke(5)
+        .clear(ValueClass::Blob(BlobOp::Link {
+            hash: blob_hashes[0].clone(),
+            to: BlobLink::Document,
+        }))
+        .set(
+            ValueClass::Blob(BlobOp::Link {
+                hash: new_hash,
+                to: BlobLink::Document,
+            }),
+            vec![],
+        )
+        .log_item_update(SyncCollection::Email, None);
+    db.write(batch.build_all()).await.unwrap();
+    let modified_snapshot = Snapshot::new(&db).await;
+
+    // Incremental backup, backups are identified by their creation time in seconds
+    println!("Running incremental backup...");
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    test.server
+        .core
+        .backup(BackupParams::incremental(backup_dir.path.clone(), false))
+        .await;
+    let manifests = read_manifests(&backup_dir.path);
+    assert_eq!(manifests.len(), 2);
+    let (full_path, full) = &manifests[0];
+    let (incremental_path, incremental) = &manifests[1];
+    let full_created_at = full["createdAt"].as_u64().unwrap();
+    assert_eq!(full["kind"], "full");
+    assert_eq!(incremental["kind"], "incremental");
+    assert_eq!(incremental["parent"], full["id"]);
+    assert_eq!(incremental["accounts"], serde_json::json!([3]));
+
+    // Only the changed account and the new blob links are exported again
+    for subspace in [
+        SUBSPACE_PROPERTY,
+        SUBSPACE_INDEXES,
+        SUBSPACE_ACL,
+        SUBSPACE_BLOB_LINK,
+        SUBSPACE_BLOBS,
+    ] {
+        let name = format!("subspace_{}", char::from(subspace));
+        let full_size = std::fs::metadata(full_path.join(&name)).unwrap().len();
+        let incremental_size = std::fs::metadata(incremental_path.join(&name))
+            .unwrap()
+            .len();
+        assert!(
+            incremental_size < full_size / 4,
+            "{name}: {incremental_size} >= {full_size} / 4"
+        );
+    }
+
+    // Restore the full backup only
+    println!("Restoring full backup...");
+    store_destroy(&db).await;
+    store_assert_is_empty(&db, db.clone().into(), true).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(backup_dir.path.clone()).with_until(full_created_at))
+        .await;
+    snapshot.assert_is_eq(&Snapshot::new(&db).await);
+
+    // Restore the full and incremental backups
+    println!("Restoring incremental backup...");
+    store_destroy(&db).await;
+    store_assert_is_empty(&db, db.clone().into(), true).await;
+    test.server
+        .core
+        .restore(RestoreParams::new(backup_dir.path.clone()))
+        .await;
+    modified_snapshot.assert_is_eq(&Snapshot::new(&db).await);
+
+    // Corrupted backups are rejected
+    println!("Verifying backup checksums...");
+    assert_eq!(RestoreParams::new(backup_dir.path.clone()).verify(), Ok(()));
+    let corrupted = incremental_path.join(format!("subspace_{}", char::from(SUBSPACE_ACL)));
+    let mut contents = std::fs::read(&corrupted).unwrap();
+    *contents.last_mut().unwrap() ^= 0xFF;
+    std::fs::write(&corrupted, contents).unwrap();
+    assert!(
+        RestoreParams::new(backup_dir.path.clone())
+            .verify()
+            .unwrap_err()
+            .contains("Checksum mismatch")
+    );
+    assert_eq!(
+        RestoreParams::new(backup_dir.path.clone())
+            .with_until(full_created_at)
+            .verify(),
+        Ok(())
+    );
+    backup_dir.delete();
 
     // Destroy store
     store_destroy(&db).await;
     store_assert_is_empty(&db, db.clone().into(), true).await;
-    temp_dir.delete();
+}
+
+fn read_manifests(path: &Path) -> Vec<(PathBuf, serde_json::Value)> {
+    let mut manifests = std::fs::read_dir(path)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join("manifest.json").is_file())
+        .map(|path| {
+            let manifest = serde_json::from_slice::<serde_json::Value>(
+                &std::fs::read(path.join("manifest.json")).unwrap(),
+            )
+            .unwrap();
+            (path, manifest)
+        })
+        .collect::<Vec<_>>();
+    manifests.sort_by_key(|(_, manifest)| manifest["createdAt"].as_u64().unwrap());
+    manifests
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -67,6 +67,7 @@ pub async fn test(test: &TestServer) {
                 expires_at: None,
                 otp_auth: "otpauth://totp/test?secret=SECRET".to_string().into(),
                 secret: "secret".into(),
+                scram_secret: None,
             }),
             Credential::AppPassword(SecondaryCredential {
                 allowed_ips: Map::new(vec![IpAddrOrMask::from_str("192.168.1.0/24").unwrap()]),
@@ -554,6 +555,7 @@ fn test_patch_regressions() {
                     expires_at: None,
                     otp_auth: None,
                     secret: "secret".into(),
+                    scram_secret: None,
                 }),
                 Credential::Password(PasswordCredential {
                     allowed_ips: Map::new(vec![IpAddrOrMask::from_str("10.0.0.1").unwrap()]),
@@ -561,6 +563,7 @@ fn test_patch_regressions() {
                     expires_at: None,
                     otp_auth: None,
                     secret: "another".into(),
+                    scram_secret: None,
                 }),
             ]),
             domain_id: 1u64.into(),
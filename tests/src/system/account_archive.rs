@@ -0,0 +1,301 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{
+    account::Account,
+    imap::{AssertResult, Type},
+    server::TestServer,
+};
+use email::cache::MessageCacheFetch;
+use imap_proto::ResponseType;
+use registry::schema::{
+    enums::AccountArchiveFormat,
+    structs::{Task, TaskAccountExport, TaskAccountImport, TaskStatus},
+};
+use std::collections::{BTreeMap, BTreeSet};
+use types::id::Id;
+
+pub async fn test(test: &mut TestServer) {
+    println!("Running account export/import tests...");
+
+    // Populate the source account
+    let source = test
+        .create_user_account(
+            "admin@example.org",
+            "export@example.org",
+            "this is a very strong password",
+            &[],
+            "Export Source",
+        )
+        .await;
+    let mut imap = source.imap_client().await;
+    for mailbox in ["Work", "Work/Projects"] {
+        imap.send(&format!("CREATE \"{mailbox}\"")).await;
+        imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    }
+    for (mailbox, flags, date, message) in [
+        (
+            "INBOX",
+            "\\Seen $Important",
+            "15-Feb-2024 10:00:00 +0000",
+            MESSAGE_KICKOFF,
+        ),
+        (
+            "Work/Projects",
+            "\\Answered \\Flagged",
+            "16-Feb-2024 11:30:00 +0000",
+            MESSAGE_REPLY,
+        ),
+        ("Work", "", "17-Feb-2024 09:15:00 +0000", MESSAGE_REPORT),
+        (
+            "INBOX",
+            "\\Draft $Later",
+            "18-Feb-2024 18:45:00 +0000",
+            MESSAGE_FROM_LINE,
+        ),
+    ] {
+        imap.send(&format!(
+            "APPEND \"{mailbox}\" ({flags}) \"{date}\" {{{}}}",
+            message.len()
+        ))
+        .await;
+        imap.assert_read(Type::Continuation, ResponseType::Ok).await;
+        imap.send_untagged(message).await;
+        imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    }
+
+    // More custom keywords than Maildir can map to the letters a-z
+    imap.send("SELECT INBOX").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    imap.send(&format!(
+        "STORE 1 +FLAGS.SILENT ({})",
+        (1..=30)
+            .map(|idx| format!("$Label{idx}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    imap.send("LOGOUT").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+
+    let expected = MailSnapshot::new(test, &source).await;
+    assert_eq!(expected.messages.len(), 4);
+    assert_eq!(expected.threads.len(), 3);
+    assert!(expected.used_quota > 0);
+
+    // Export and import the account in every format
+    for (format, name, target_name) in [
+        (
+            AccountArchiveFormat::Maildir,
+            "export.maildir",
+            "import-maildir@example.org",
+        ),
+        (
+            AccountArchiveFormat::Mbox,
+            "export.mbox",
+            "import-mbox@example.org",
+        ),
+        (
+            AccountArchiveFormat::EmlZip,
+            "export.zip",
+            "import-zip@example.org",
+        ),
+    ] {
+        println!("Testing {format:?} account export/import...");
+        let path = test.temp_dir.path.join(name).to_string_lossy().into_owned();
+        run_task(
+            test,
+            Task::AccountExport(TaskAccountExport {
+                account_id: source.id(),
+                format,
+                path: path.clone(),
+                status: TaskStatus::now(),
+            }),
+        )
+        .await;
+
+        let target = test
+            .create_user_account(
+                "admin@example.org",
+                target_name,
+                "this is a very strong password",
+                &[],
+                "Import Target",
+            )
+            .await;
+        run_task(
+            test,
+            Task::AccountImport(TaskAccountImport {
+                account_id: target.id(),
+                format,
+                path,
+                status: TaskStatus::now(),
+            }),
+        )
+        .await;
+
+        assert_eq!(
+            MailSnapshot::new(test, &target).await,
+            expected,
+            "{format:?}"
+        );
+    }
+}
+
+async fn run_task(test: &TestServer, task: Task) {
+    let admin = test.account("admin@example.org");
+    admin.registry_create_object(task).await;
+    test.wait_for_tasks_skip_failures().await;
+
+    // Failed tasks are kept in the registry
+    let failed = admin
+        .registry_get_all::<Task>()
+        .await
+        .into_iter()
+        .filter(|(_, task)| matches!(task, Task::AccountExport(_) | Task::AccountImport(_)))
+        .collect::<Vec<_>>();
+    assert!(failed.is_empty(), "Task failed: {failed:?}");
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct MailSnapshot {
+    mailboxes: BTreeSet<String>,
+    // Subject -> (mailbox paths, keywords, size, received at)
+    messages: BTreeMap<String, (BTreeSet<String>, BTreeSet<String>, u64, String)>,
+    // Thread ids differ across accounts, compare which messages share a thread
+    threads: BTreeSet<BTreeSet<String>>,
+    used_quota: i64,
+}
+
+impl MailSnapshot {
+    async fn new(test: &TestServer, account: &Account) -> Self {
+        let account_id = account.id().document_id();
+        let cache = test.server.get_cached_messages(account_id).await.unwrap();
+        let mailbox_paths = cache
+            .mailboxes
+            .items
+            .iter()
+            .map(|mailbox| {
+                (
+                    Id::from(mailbox.document_id).to_string(),
+                    mailbox.path.to_string(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let response = account
+            .jmap_get(
+                "Email",
+                [
+                    "subject",
+                    "mailboxIds",
+                    "keywords",
+                    "threadId",
+                    "size",
+                    "receivedAt",
+                ],
+                Vec::<&str>::new(),
+            )
+            .await;
+        let mut messages = BTreeMap::new();
+        let mut threads: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for email in response.list() {
+            let subject = email["subject"].as_str().unwrap().to_string();
+            let keys = |property: &str| {
+                email[property]
+                    .as_object()
+                    .unwrap()
+                    .keys()
+                    .cloned()
+                    .collect::<BTreeSet<_>>()
+            };
+            let mailboxes = keys("mailboxIds")
+                .into_iter()
+                .map(|id| mailbox_paths.get(&id).cloned().unwrap_or(id))
+                .collect();
+            threads
+                .entry(email["threadId"].as_str().unwrap().to_string())
+                .or_default()
+                .insert(subject.clone());
+            messages.insert(
+                subject,
+                (
+                    mailboxes,
+                    keys("keywords"),
+                    email["size"].as_u64().unwrap(),
+                    email["receivedAt"].as_str().unwrap().to_string(),
+                ),
+            );
+        }
+
+        MailSnapshot {
+            mailboxes: mailbox_paths.into_values().collect(),
+            messages,
+            threads: threads.into_values().collect(),
+            used_quota: test
+                .server
+                .get_used_quota_account(account_id)
+                .await
+                .unwrap(),
+        }
+    }
+}
+
+const MESSAGE_KICKOFF: &str = concat!(
+    "From: Jane Smith <jane@example.org>\r\n",
+    "To: export@example.org\r\n",
+    "Subject: Project kickoff\r\n",
+    "Message-ID: <kickoff@example.org>\r\n",
+    "Date: Thu, 15 Feb 2024 10:00:00 +0000\r\n",
+    "\r\n",
+    "The project starts next Monday.\r\n",
+);
+
+const MESSAGE_REPLY: &str = concat!(
+    "From: export@example.org\r\n",
+    "To: Jane Smith <jane@example.org>\r\n",
+    "Subject: Re: Project kickoff\r\n",
+    "Message-ID: <kickoff-reply@example.org>\r\n",
+    "In-Reply-To: <kickoff@example.org>\r\n",
+    "References: <kickoff@example.org>\r\n",
+    "Date: Fri, 16 Feb 2024 11:30:00 +0000\r\n",
+    "\r\n",
+    "Sounds good, see you on Monday.\r\n",
+);
+
+const MESSAGE_REPORT: &str = concat!(
+    "From: Finance <finance@example.org>\r\n",
+    "To: export@example.org\r\n",
+    "Subject: Quarterly report\r\n",
+    "Message-ID: <report@example.org>\r\n",
+    "Date: Sat, 17 Feb 2024 09:15:00 +0000\r\n",
+    "Content-Type: multipart/mixed; boundary=\"boundary\"\r\n",
+    "\r\n",
+    "--boundary\r\n",
+    "Content-Type: text/plain\r\n",
+    "\r\n",
+    "The quarterly report is attached.\r\n",
+    "--boundary\r\n",
+    "Content-Type: text/csv; name=\"report.csv\"\r\n",
+    "Content-Disposition: attachment; filename=\"report.csv\"\r\n",
+    "\r\n",
+    "quarter,revenue\r\n",
+    "Q1,1000\r\n",
+    "--boundary--\r\n",
+);
+
+// Lines starting with "From " must survive mboxrd quoting
+const MESSAGE_FROM_LINE: &str = concat!(
+    "From: export@example.org\r\n",
+    "To: export@example.org\r\n",
+    "Subject: Draft notes\r\n",
+    "Message-ID: <draft@example.org>\r\n",
+    "Date: Sun, 18 Feb 2024 18:45:00 +0000\r\n",
+    "\r\n",
+    "From now on, meetings start at 9.\r\n",
+    ">From the archive, quoted once.\r\n",
+);
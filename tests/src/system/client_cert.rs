@@ -0,0 +1,530 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServerBuilder;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer, KeyPair,
+    KeyUsagePurpose, SanType,
+};
+use registry::{
+    schema::{
+        enums::{NetworkListenerProtocol, TlsClientAuth, TlsClientCertMapping},
+        prelude::SocketAddr,
+        structs::{Expression, MtaStageAuth, NetworkListener},
+    },
+    types::map::Map,
+};
+use rustls::{
+    ClientConfig, DigitallySignedStruct, SignatureScheme,
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::aws_lc_rs::default_provider,
+};
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{str::FromStr, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines, ReadHalf, WriteHalf},
+    net::TcpStream,
+};
+use tokio_rustls::{TlsConnector, client::TlsStream};
+use utils::map::vec_map::VecMap;
+
+const IMAP_PORT: u16 = 19993;
+const SMTP_PORT: u16 = 19465;
+const POP3_PORT: u16 = 19995;
+const SIEVE_PORT: u16 = 19190;
+const HTTP_SAN_PORT: u16 = 19441;
+const HTTP_SUBJECT_PORT: u16 = 19442;
+const HTTP_FINGERPRINT_PORT: u16 = 19443;
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn client_certificate_tests() {
+    // Build the client certificate hierarchy
+    let ca = TestCa::new("Device CA");
+    let rogue_ca = TestCa::new("Rogue CA");
+    let john = ca.issue("jdoe@example.org", "jdoe@example.org");
+    let jane = ca.issue("Jane Device", "jane@example.org");
+    let unknown = ca.issue("Unknown Device", "nobody@example.org");
+    let rogue = rogue_ca.issue("jdoe@example.org", "jdoe@example.org");
+
+    // Fingerprints are accepted in any case, with or without separators
+    let jane_fingerprint = Sha256::digest(jane.der.as_ref())
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":");
+
+    let mut builder = TestServerBuilder::new("client_certificate_tests")
+        .await
+        .with_http_listener(19800)
+        .await
+        .with_object(MtaStageAuth {
+            require: Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await;
+    for (protocol, name, port, client_auth, mapping) in [
+        (
+            NetworkListenerProtocol::Imap,
+            "imap-cert",
+            IMAP_PORT,
+            TlsClientAuth::Optional,
+            TlsClientCertMapping::SanEmail,
+        ),
+        (
+            NetworkListenerProtocol::Smtp,
+            "submissions-cert",
+            SMTP_PORT,
+            TlsClientAuth::Optional,
+            TlsClientCertMapping::SanEmail,
+        ),
+        (
+            NetworkListenerProtocol::Pop3,
+            "pop3-cert",
+            POP3_PORT,
+            TlsClientAuth::Optional,
+            TlsClientCertMapping::SubjectCommonName,
+        ),
+        (
+            NetworkListenerProtocol::ManageSieve,
+            "sieve-cert",
+            SIEVE_PORT,
+            TlsClientAuth::Optional,
+            TlsClientCertMapping::Fingerprint,
+        ),
+        (
+            NetworkListenerProtocol::Http,
+            "https-cert-san",
+            HTTP_SAN_PORT,
+            TlsClientAuth::Optional,
+            TlsClientCertMapping::SanEmail,
+        ),
+        (
+            NetworkListenerProtocol::Http,
+            "https-cert-subject",
+            HTTP_SUBJECT_PORT,
+            TlsClientAuth::Required,
+            TlsClientCertMapping::SubjectCommonName,
+        ),
+        (
+            NetworkListenerProtocol::Http,
+            "https-cert-fingerprint",
+            HTTP_FINGERPRINT_PORT,
+            TlsClientAuth::Optional,
+            TlsClientCertMapping::Fingerprint,
+        ),
+    ] {
+        builder = builder
+            .with_object(NetworkListener {
+                bind: Map::new(vec![
+                    SocketAddr::from_str(&format!("0.0.0.0:{port}")).unwrap(),
+                ]),
+                name: name.to_string(),
+                protocol,
+                use_tls: true,
+                tls_implicit: true,
+                tls_client_auth: client_auth,
+                tls_client_ca: Some(ca.pem.clone()),
+                tls_client_cert_mapping: mapping,
+                tls_client_cert_fingerprints: VecMap::from_iter([(
+                    jane_fingerprint.clone(),
+                    "jane@example.org".to_string(),
+                )]),
+                ..Default::default()
+            })
+            .await;
+    }
+    let test = builder.build().await;
+    for (name, description) in [
+        ("jdoe@example.org", "John Doe"),
+        ("jane@example.org", "Jane Smith"),
+    ] {
+        test.create_user_account(
+            "admin",
+            name,
+            "this is a very strong password",
+            &[],
+            description,
+        )
+        .await;
+    }
+
+    println!("Running IMAP SASL EXTERNAL tests...");
+
+    // EXTERNAL is not offered without a client certificate
+    let mut imap = LineClient::connect(IMAP_PORT, None).await;
+    let greeting = imap.read_line().await;
+    assert!(greeting.starts_with("* OK"), "{greeting}");
+    assert!(!greeting.contains("AUTH=EXTERNAL"), "{greeting}");
+    imap.send("a1 AUTHENTICATE EXTERNAL =").await;
+    imap.assert_tagged("a1", "a1 NO").await;
+
+    // Authenticate using the SAN email, with and without an initial response
+    let mut imap = LineClient::connect(IMAP_PORT, Some(&john)).await;
+    let greeting = imap.read_line().await;
+    assert!(greeting.contains("AUTH=EXTERNAL"), "{greeting}");
+    imap.send("a1 AUTHENTICATE EXTERNAL").await;
+    let challenge = imap.read_line().await;
+    assert!(challenge.starts_with('+'), "{challenge}");
+    imap.send("").await;
+    imap.assert_tagged("a1", "a1 OK").await;
+    imap.send("a2 SELECT INBOX").await;
+    imap.assert_tagged("a2", "a2 OK").await;
+
+    // The authorization identity must match the certificate
+    let mut imap = LineClient::connect(IMAP_PORT, Some(&john)).await;
+    imap.read_line().await;
+    imap.send(&format!(
+        "a1 AUTHENTICATE EXTERNAL {}",
+        STANDARD.encode("jane@example.org")
+    ))
+    .await;
+    imap.assert_tagged("a1", "a1 NO").await;
+    imap.send(&format!(
+        "a2 AUTHENTICATE EXTERNAL {}",
+        STANDARD.encode("jdoe@example.org")
+    ))
+    .await;
+    imap.assert_tagged("a2", "a2 OK").await;
+
+    // Certificates that do not map to an account are rejected
+    let mut imap = LineClient::connect(IMAP_PORT, Some(&unknown)).await;
+    imap.read_line().await;
+    imap.send("a1 AUTHENTICATE EXTERNAL =").await;
+    imap.assert_tagged("a1", "a1 NO").await;
+
+    println!("Running SMTP SASL EXTERNAL tests...");
+
+    // EXTERNAL is not offered without a client certificate
+    let mut smtp = LineClient::connect(SMTP_PORT, None).await;
+    smtp.read_line().await;
+    smtp.send("EHLO client.example.org").await;
+    let ehlo = smtp.read_until(|line| line.starts_with("250 ")).await;
+    assert!(
+        !ehlo.iter().any(|line| line.contains("EXTERNAL")),
+        "{ehlo:?}"
+    );
+    smtp.send("AUTH EXTERNAL =").await;
+    let response = smtp.read_line().await;
+    assert!(response.starts_with('5'), "{response}");
+
+    // Authenticate using the SAN email
+    let mut smtp = LineClient::connect(SMTP_PORT, Some(&john)).await;
+    smtp.read_line().await;
+    smtp.send("EHLO client.example.org").await;
+    let ehlo = smtp.read_until(|line| line.starts_with("250 ")).await;
+    assert!(
+        ehlo.iter()
+            .any(|line| line.contains("AUTH") && line.contains("EXTERNAL")),
+        "{ehlo:?}"
+    );
+    smtp.send("AUTH EXTERNAL =").await;
+    let response = smtp.read_line().await;
+    assert!(response.starts_with("235"), "{response}");
+    smtp.send("MAIL FROM:<jdoe@example.org>").await;
+    let response = smtp.read_line().await;
+    assert!(response.starts_with("250"), "{response}");
+
+    println!("Running POP3 SASL EXTERNAL tests...");
+
+    // Authenticate using the subject common name
+    let mut pop3 = LineClient::connect(POP3_PORT, Some(&john)).await;
+    pop3.read_line().await;
+    pop3.send("CAPA").await;
+    let capabilities = pop3.read_until(|line| line == ".").await;
+    assert!(
+        capabilities
+            .iter()
+            .any(|line| line.starts_with("SASL") && line.contains("EXTERNAL")),
+        "{capabilities:?}"
+    );
+    pop3.send("AUTH EXTERNAL =").await;
+    let response = pop3.read_line().await;
+    assert!(response.starts_with("+OK"), "{response}");
+    pop3.send("STAT").await;
+    let response = pop3.read_line().await;
+    assert!(response.starts_with("+OK"), "{response}");
+
+    // Jane's certificate has no account in its common name
+    let mut pop3 = LineClient::connect(POP3_PORT, Some(&jane)).await;
+    pop3.read_line().await;
+    pop3.send("AUTH EXTERNAL =").await;
+    let response = pop3.read_line().await;
+    assert!(response.starts_with("-ERR"), "{response}");
+
+    println!("Running ManageSieve SASL EXTERNAL tests...");
+
+    // Authenticate using the certificate fingerprint
+    let mut sieve = LineClient::connect(SIEVE_PORT, Some(&jane)).await;
+    let capabilities = sieve.read_until(|line| line.starts_with("OK")).await;
+    assert!(
+        capabilities
+            .iter()
+            .any(|line| line.starts_with("\"SASL\"") && line.contains("EXTERNAL")),
+        "{capabilities:?}"
+    );
+    sieve.send("AUTHENTICATE \"EXTERNAL\" \"=\"").await;
+    let response = sieve.read_until(|line| !line.starts_with('"')).await;
+    assert!(response.last().unwrap().starts_with("OK"), "{response:?}");
+    sieve.send("LISTSCRIPTS").await;
+    let response = sieve.read_until(|line| !line.starts_with('"')).await;
+    assert!(response.last().unwrap().starts_with("OK"), "{response:?}");
+
+    // John's fingerprint is not mapped to an account
+    let mut sieve = LineClient::connect(SIEVE_PORT, Some(&john)).await;
+    sieve.read_until(|line| line.starts_with("OK")).await;
+    sieve.send("AUTHENTICATE \"EXTERNAL\" \"=\"").await;
+    let response = sieve.read_until(|line| !line.starts_with('"')).await;
+    assert!(response.last().unwrap().starts_with("NO"), "{response:?}");
+
+    println!("Running HTTP client certificate tests...");
+
+    // Mapping by SAN email
+    for (identity, expected) in [
+        (Some(&john), Some("jdoe@example.org")),
+        (Some(&jane), Some("jane@example.org")),
+        (Some(&unknown), None),
+        (None, None),
+    ] {
+        assert_eq!(
+            jmap_session_username(HTTP_SAN_PORT, identity)
+                .await
+                .unwrap()
+                .as_deref(),
+            expected
+        );
+    }
+
+    // Mapping by subject common name, certificates are required
+    assert_eq!(
+        jmap_session_username(HTTP_SUBJECT_PORT, Some(&john))
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("jdoe@example.org")
+    );
+    assert_eq!(
+        jmap_session_username(HTTP_SUBJECT_PORT, Some(&jane))
+            .await
+            .unwrap(),
+        None
+    );
+    assert!(
+        jmap_session_username(HTTP_SUBJECT_PORT, None)
+            .await
+            .is_err()
+    );
+    assert!(
+        jmap_session_username(HTTP_SUBJECT_PORT, Some(&rogue))
+            .await
+            .is_err()
+    );
+
+    // Mapping by fingerprint
+    assert_eq!(
+        jmap_session_username(HTTP_FINGERPRINT_PORT, Some(&jane))
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("jane@example.org")
+    );
+    assert_eq!(
+        jmap_session_username(HTTP_FINGERPRINT_PORT, Some(&john))
+            .await
+            .unwrap(),
+        None
+    );
+
+    if test.is_reset() {
+        test.temp_dir.delete();
+    }
+}
+
+/// Returns the authenticated username, or `None` when the request was not authenticated.
+async fn jmap_session_username(
+    port: u16,
+    identity: Option<&ClientIdentity>,
+) -> reqwest::Result<Option<String>> {
+    let mut client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(1500))
+        .danger_accept_invalid_certs(true);
+    if let Some(identity) = identity {
+        client = client.identity(reqwest::Identity::from_pem(identity.pem.as_bytes())?);
+    }
+    let response = client
+        .build()?
+        .get(format!("https://127.0.0.1:{port}/jmap/session"))
+        .send()
+        .await?;
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Ok(None);
+    }
+    let session = response.error_for_status()?.json::<Value>().await?;
+    Ok(session["username"].as_str().map(String::from))
+}
+
+struct TestCa {
+    issuer: Issuer<'static, KeyPair>,
+    pem: String,
+}
+
+struct ClientIdentity {
+    der: CertificateDer<'static>,
+    key: Vec<u8>,
+    pem: String,
+}
+
+impl TestCa {
+    fn new(common_name: &str) -> Self {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params
+            .distinguished_name
+            .push(DnType::CommonName, common_name);
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let pem = params.self_signed(&key).unwrap().pem();
+        TestCa {
+            issuer: Issuer::new(params, key),
+            pem,
+        }
+    }
+
+    fn issue(&self, common_name: &str, email: &str) -> ClientIdentity {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, common_name);
+        params.subject_alt_names = vec![SanType::Rfc822Name(email.try_into().unwrap())];
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let cert = params.signed_by(&key, &self.issuer).unwrap();
+        ClientIdentity {
+            pem: format!("{}{}", cert.pem(), key.serialize_pem()),
+            der: cert.der().clone(),
+            key: key.serialize_der(),
+        }
+    }
+}
+
+struct LineClient {
+    reader: Lines<BufReader<ReadHalf<TlsStream<TcpStream>>>>,
+    writer: WriteHalf<TlsStream<TcpStream>>,
+}
+
+impl LineClient {
+    async fn connect(port: u16, identity: Option<&ClientIdentity>) -> Self {
+        let config = ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyServerCert));
+        let config = match identity {
+            Some(identity) => config
+                .with_client_auth_cert(
+                    vec![identity.der.clone()],
+                    PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(identity.key.clone())),
+                )
+                .unwrap(),
+            None => config.with_no_client_auth(),
+        };
+        let (reader, writer) = tokio::io::split(
+            TlsConnector::from(Arc::new(config))
+                .connect(
+                    ServerName::try_from("mail.example.org").unwrap().to_owned(),
+                    TcpStream::connect(format!("127.0.0.1:{port}"))
+                        .await
+                        .unwrap(),
+                )
+                .await
+                .unwrap(),
+        );
+
+        LineClient {
+            reader: BufReader::new(reader).lines(),
+            writer,
+        }
+    }
+
+    async fn send(&mut self, text: &str) {
+        self.writer.write_all(text.as_bytes()).await.unwrap();
+        self.writer.write_all(b"\r\n").await.unwrap();
+    }
+
+    async fn read_line(&mut self) -> String {
+        match tokio::time::timeout(Duration::from_millis(1500), self.reader.next_line()).await {
+            Ok(Ok(Some(line))) => line,
+            Ok(Ok(None)) => panic!("Connection closed by server"),
+            Ok(Err(err)) => panic!("Connection broken: {err}"),
+            Err(_) => panic!("Timeout while waiting for server response"),
+        }
+    }
+
+    async fn read_until(&mut self, is_done: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut lines = Vec::new();
+        loop {
+            let line = self.read_line().await;
+            let done = is_done(&line);
+            lines.push(line);
+            if done {
+                return lines;
+            }
+        }
+    }
+
+    async fn assert_tagged(&mut self, tag: &str, expected: &str) {
+        let lines = self
+            .read_until(|line| line.starts_with(&format!("{tag} ")))
+            .await;
+        assert!(
+            lines.last().unwrap().starts_with(expected),
+            "Expected {expected:?} but got {lines:?}"
+        );
+    }
+}
+
+#[derive(Debug)]
+struct AcceptAnyServerCert;
+
+impl ServerCertVerifier for AcceptAnyServerCert {
+    fn verify_server_cert(
+        &self,
+        _: &CertificateDer<'_>,
+        _: &[CertificateDer<'_>],
+        _: &ServerName<'_>,
+        _: &[u8],
+        _: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _: &[u8],
+        _: &CertificateDer<'_>,
+        _: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _: &[u8],
+        _: &CertificateDer<'_>,
+        _: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        default_provider()
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
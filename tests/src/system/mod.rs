@@ -4,16 +4,20 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+pub mod account_archive;
 pub mod antispam;
 pub mod archiving;
 pub mod authentication;
 pub mod authorization;
+pub mod client_cert;
 pub mod crypto;
 pub mod delivery;
 pub mod directory;
 pub mod oidc;
 pub mod purge;
+pub mod quarantine;
 pub mod quota;
+pub mod scim;
 pub mod security;
 pub mod task;
 pub mod tenant;
@@ -63,6 +67,7 @@ pub async fn system_tests() {
     oidc::test(&mut test).await;
     authorization::test(&mut test).await;
     tenant::test(&mut test).await;
+    scim::test(&mut test).await;
     security::test(&mut test).await;
     quota::test(&mut test).await;
     purge::test(&mut test).await;
@@ -70,6 +75,8 @@ pub async fn system_tests() {
     crypto::test(&mut test).await;
     antispam::test(&mut test).await;
     archiving::test(&mut test).await;
+    account_archive::test(&mut test).await;
+    quarantine::test(&mut test).await;
     task::test(&mut test).await;
 
     if test.is_reset() {
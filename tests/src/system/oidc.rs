@@ -11,15 +11,26 @@ use crate::utils::{
     server::TestServer,
     smtp::SmtpConnection,
 };
-use base64::{Engine, engine::general_purpose};
+use aws_lc_rs::{
+    digest::{SHA256, digest},
+    rand::SystemRandom,
+    signature::{ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, KeyPair},
+};
+use base64::{
+    Engine,
+    engine::general_purpose::{self, URL_SAFE_NO_PAD},
+};
 use biscuit::{JWT, SingleOrMultiple, jwk::JWKSet};
 use bytes::Bytes;
-use common::auth::oauth::{
-    introspect::OAuthIntrospect,
-    oidc::StandardClaims,
-    registration::{
-        ClientRegistrationRequest, ClientRegistrationResponse, TokenEndpointAuthMethod,
+use common::auth::{
+    oauth::{
+        introspect::OAuthIntrospect,
+        oidc::StandardClaims,
+        registration::{
+            ClientRegistrationRequest, ClientRegistrationResponse, TokenEndpointAuthMethod,
+        },
     },
+    webauthn::{PasskeyAssertion, PasskeyRegistration},
 };
 use http::auth::oauth::{
     DeviceAuthResponse, ErrorType, TokenResponse,
@@ -309,6 +320,7 @@ pub async fn test(test: &mut TestServer) {
         account_name: "user@example.org".to_string(),
         account_secret: "this is a very strong password".to_string(),
         mfa_token: None,
+        passkey: None,
         client_id: client_id.to_string(),
         redirect_uri: "com.example.app:/evil".to_string().into(),
         nonce: None,
@@ -329,6 +341,7 @@ pub async fn test(test: &mut TestServer) {
         account_name: "user@example.org".to_string(),
         account_secret: "this is a very strong password".to_string(),
         mfa_token: None,
+        passkey: None,
         client_id: client_id.to_string(),
         redirect_uri: "com.example.app:/cb".to_string().into(),
         nonce: None,
@@ -352,6 +365,7 @@ pub async fn test(test: &mut TestServer) {
                 account_name: "user@example.org".to_string(),
                 account_secret: "this is a very strong password".to_string(),
                 mfa_token: None,
+                passkey: None,
                 client_id: client_id.to_string(),
                 redirect_uri: "com.example.app:/cb".to_string().into(),
                 nonce: "abc1234".to_string().into(),
@@ -730,6 +744,7 @@ pub async fn test(test: &mut TestServer) {
                 account_name: "user@example.org".to_string(),
                 account_secret: "this is a very strong password".to_string(),
                 mfa_token: None,
+                passkey: None,
                 code: device_response.user_code.clone(),
             },
         )
@@ -758,6 +773,7 @@ pub async fn test(test: &mut TestServer) {
                 account_name: "user@example.org".to_string(),
                 account_secret: "this is a very strong password".to_string(),
                 mfa_token: None,
+                passkey: None,
                 code: device_response.user_code.clone(),
             },
         )
@@ -855,6 +871,121 @@ pub async fn test(test: &mut TestServer) {
         }
     );
 
+    // ------------------------
+    // Passkeys
+    // ------------------------
+    let passkey_user = test
+        .create_user_account(
+            "admin@example.org",
+            "passkey@example.org",
+            "another very strong password",
+            &[],
+            "passkey@example.org",
+        )
+        .await;
+    let passkey = TestPasskey::new(&metadata.issuer);
+
+    // Register a passkey
+    let options: serde_json::Value = post_json_basic(
+        "https://127.0.0.1:8899/api/passkey/registration/options",
+        "passkey@example.org",
+        "another very strong password",
+        &serde_json::json!({}),
+    )
+    .await;
+    let user_handle = options["user"]["id"].as_str().unwrap().to_string();
+    let passkey_registration = passkey.registration(options["challenge"].as_str().unwrap());
+    let response: serde_json::Value = post_json_basic(
+        "https://127.0.0.1:8899/api/passkey/registration",
+        "passkey@example.org",
+        "another very strong password",
+        &passkey_registration,
+    )
+    .await;
+    assert!(response["id"].is_string(), "{response}");
+
+    // Registration challenges are single use
+    let response: serde_json::Value = post_json_basic(
+        "https://127.0.0.1:8899/api/passkey/registration",
+        "passkey@example.org",
+        "another very strong password",
+        &passkey_registration,
+    )
+    .await;
+    assert!(response["id"].is_null(), "{response}");
+
+    // Password logins now require the passkey as a second factor
+    assert_eq!(
+        http.post::<LoginResponse>(
+            "/api/auth",
+            &passkey_login(
+                &client_id,
+                "passkey@example.org",
+                "another very strong password",
+                None
+            ),
+        )
+        .await
+        .unwrap(),
+        LoginResponse::MfaRequired
+    );
+
+    // Passwordless login
+    let assertion = passkey.assertion(&assertion_challenge(&http).await, 1, Some(&user_handle));
+    assert!(matches!(
+        http.post::<LoginResponse>(
+            "/api/auth",
+            &passkey_login(&client_id, "", "", Some(assertion.clone())),
+        )
+        .await
+        .unwrap(),
+        LoginResponse::Authenticated { .. }
+    ));
+
+    // Assertions cannot be replayed
+    assert_eq!(
+        http.post::<LoginResponse>(
+            "/api/auth",
+            &passkey_login(&client_id, "", "", Some(assertion)),
+        )
+        .await
+        .unwrap(),
+        LoginResponse::Failure
+    );
+
+    // Passkey as a second factor, the signature counter must increase
+    let assertion = passkey.assertion(&assertion_challenge(&http).await, 2, None);
+    assert!(matches!(
+        http.post::<LoginResponse>(
+            "/api/auth",
+            &passkey_login(
+                &client_id,
+                "passkey@example.org",
+                "another very strong password",
+                Some(assertion)
+            ),
+        )
+        .await
+        .unwrap(),
+        LoginResponse::Authenticated { .. }
+    ));
+    let assertion = passkey.assertion(&assertion_challenge(&http).await, 2, None);
+    assert_eq!(
+        http.post::<LoginResponse>(
+            "/api/auth",
+            &passkey_login(
+                &client_id,
+                "passkey@example.org",
+                "another very strong password",
+                Some(assertion)
+            ),
+        )
+        .await
+        .unwrap(),
+        LoginResponse::Failure
+    );
+    admin.destroy_account(passkey_user).await;
+
     // Clean up
     admin.registry_destroy_all(ObjectType::OAuthClient).await;
     admin.destroy_account(user).await;
@@ -969,6 +1100,7 @@ async fn obtain_auth_code(http: &HttpRequest, client_id: &str, redirect_uri: &st
             account_name: "user@example.org".to_string(),
             account_secret: "this is a very strong password".to_string(),
             mfa_token: None,
+            passkey: None,
             client_id: client_id.to_string(),
             redirect_uri: redirect_uri.to_string().into(),
             nonce: None,
@@ -984,6 +1116,135 @@ async fn obtain_auth_code(http: &HttpRequest, client_id: &str, redirect_uri: &st
     .unwrap_code()
 }
 
+fn passkey_login(
+    client_id: &str,
+    account_name: &str,
+    account_secret: &str,
+    passkey: Option<PasskeyAssertion>,
+) -> LoginRequest {
+    LoginRequest::AuthCode {
+        account_name: account_name.to_string(),
+        account_secret: account_secret.to_string(),
+        mfa_token: None,
+        passkey,
+        client_id: client_id.to_string(),
+        redirect_uri: "com.example.app:/cb".to_string().into(),
+        nonce: None,
+        scope: Some(PROFILE_SCOPE.to_string()),
+        code_challenge: Some(PKCE_CHALLENGE.to_string()),
+        code_challenge_method: Some("S256".to_string()),
+        state: None,
+        resource: vec![],
+    }
+}
+
+async fn assertion_challenge(http: &HttpRequest) -> String {
+    http.post::<serde_json::Value>(
+        "/api/passkey/assertion/options",
+        &serde_json::json!({ "passwordless": true }),
+    )
+    .await
+    .unwrap()["challenge"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+struct TestPasskey {
+    key: EcdsaKeyPair,
+    key_id: Vec<u8>,
+    origin: String,
+    rp_id_hash: Vec<u8>,
+}
+
+impl TestPasskey {
+    fn new(origin: &str) -> Self {
+        let pkcs8 =
+            EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &SystemRandom::new())
+                .unwrap();
+        let origin = origin.trim_end_matches('/');
+        let rp_id = origin
+            .split_once("://")
+            .map_or(origin, |(_, host)| host)
+            .split(':')
+            .next()
+            .unwrap();
+
+        TestPasskey {
+            key: EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref()).unwrap(),
+            key_id: store::rand::random::<[u8; 16]>().to_vec(),
+            origin: origin.to_string(),
+            rp_id_hash: digest(&SHA256, rp_id.as_bytes()).as_ref().to_vec(),
+        }
+    }
+
+    fn registration(&self, challenge: &str) -> PasskeyRegistration {
+        // P-256 SubjectPublicKeyInfo header followed by the uncompressed point
+        let mut public_key = vec![
+            0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06,
+            0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+        ];
+        public_key.extend_from_slice(self.key.public_key().as_ref());
+
+        let mut authenticator_data = self.rp_id_hash.clone();
+        authenticator_data.push(0x45);
+        authenticator_data.extend_from_slice(&0u32.to_be_bytes());
+        authenticator_data.extend_from_slice(&[0u8; 16]);
+        authenticator_data.extend_from_slice(&(self.key_id.len() as u16).to_be_bytes());
+        authenticator_data.extend_from_slice(&self.key_id);
+
+        PasskeyRegistration {
+            id: URL_SAFE_NO_PAD.encode(&self.key_id),
+            client_data_json: self.client_data("webauthn.create", challenge),
+            authenticator_data: URL_SAFE_NO_PAD.encode(&authenticator_data),
+            public_key: URL_SAFE_NO_PAD.encode(&public_key),
+            public_key_algorithm: -7,
+            description: Some("Test passkey".to_string()),
+        }
+    }
+
+    fn assertion(
+        &self,
+        challenge: &str,
+        sign_count: u32,
+        user_handle: Option<&str>,
+    ) -> PasskeyAssertion {
+        let mut authenticator_data = self.rp_id_hash.clone();
+        authenticator_data.push(0x05);
+        authenticator_data.extend_from_slice(&sign_count.to_be_bytes());
+
+        let client_data_json = self.client_data("webauthn.get", challenge);
+        let mut message = authenticator_data.clone();
+        message.extend_from_slice(
+            digest(&SHA256, &URL_SAFE_NO_PAD.decode(&client_data_json).unwrap()).as_ref(),
+        );
+
+        PasskeyAssertion {
+            id: URL_SAFE_NO_PAD.encode(&self.key_id),
+            client_data_json,
+            authenticator_data: URL_SAFE_NO_PAD.encode(&authenticator_data),
+            signature: URL_SAFE_NO_PAD.encode(
+                self.key
+                    .sign(&SystemRandom::new(), &message)
+                    .unwrap()
+                    .as_ref(),
+            ),
+            user_handle: user_handle.map(|handle| handle.to_string()),
+        }
+    }
+
+    fn client_data(&self, typ: &str, challenge: &str) -> String {
+        URL_SAFE_NO_PAD.encode(
+            serde_json::json!({
+                "type": typ,
+                "challenge": challenge,
+                "origin": self.origin,
+            })
+            .to_string(),
+        )
+    }
+}
+
 async fn post_json_raw(url: &str, body: &impl Serialize) -> (u16, serde_json::Value) {
     let response = reqwest::Client::builder()
         .timeout(Duration::from_millis(500))
@@ -0,0 +1,568 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{http::HttpRequest, jmap::JmapUtils, server::TestServer};
+use email::{
+    cache::MessageCacheFetch,
+    message::delivery::{IngestMessage, IngestRecipient, LocalDeliveryStatus, MailDelivery},
+};
+use hyper::Method;
+use jmap_proto::error::set::SetErrorType;
+use mail_parser::MessageParser;
+use registry::{
+    schema::{
+        enums::{QuarantineStatus, TaskStoreMaintenanceType},
+        prelude::{ObjectType, Property},
+        structs::{
+            Account, CertificateManagement, Credential, DkimManagement, DnsManagement, Domain,
+            PasswordCredential, QuarantineSettings, QuarantinedMessage, Task, TaskQuarantineDigest,
+            TaskStatus, TaskStoreMaintenance, Tenant, UserAccount, UserRoles,
+        },
+    },
+    types::list::List,
+};
+use serde_json::json;
+use std::time::Duration;
+use store::write::now;
+use types::id::Id;
+
+pub async fn test(test: &mut TestServer) {
+    println!("Running Quarantine tests...");
+    let admin = test.account("admin@example.org");
+
+    // Create test accounts
+    let john = test
+        .create_user_account(
+            "admin@example.org",
+            "jdoe@example.org",
+            "this is a very strong password",
+            &[],
+            "jdoe@example.org",
+        )
+        .await;
+    let jane = test
+        .create_user_account(
+            "admin@example.org",
+            "jane@example.org",
+            "this is a very strong password",
+            &[],
+            "jane@example.org",
+        )
+        .await;
+
+    // Create a tenant with an administrator and a user
+    let tenant_id = admin
+        .registry_create_object(Tenant {
+            name: "Tenant Q".to_string(),
+            ..Default::default()
+        })
+        .await;
+    let domain_id = admin
+        .registry_create_object(Domain {
+            name: "tenantq.org".to_string(),
+            member_tenant_id: tenant_id.into(),
+            certificate_management: CertificateManagement::Manual,
+            dns_management: DnsManagement::Manual,
+            dkim_management: DkimManagement::Manual,
+            ..Default::default()
+        })
+        .await;
+    let mut tenant_account_ids = Vec::new();
+    for (name, roles, secret) in [
+        ("admin", UserRoles::Admin, "tenant q admin secret"),
+        ("user", UserRoles::User, "tenant q user secret"),
+    ] {
+        tenant_account_ids.push(
+            admin
+                .registry_create_object(Account::User(UserAccount {
+                    name: name.to_string(),
+                    domain_id,
+                    member_tenant_id: tenant_id.into(),
+                    roles,
+                    credentials: List::from_iter([Credential::Password(PasswordCredential {
+                        secret: secret.to_string(),
+                        ..Default::default()
+                    })]),
+                    ..Default::default()
+                }))
+                .await,
+        );
+    }
+    let tenant_admin = crate::utils::account::Account::new(
+        "admin@tenantq.org",
+        "tenant q admin secret",
+        &[],
+        "Tenant Q Admin",
+        tenant_account_ids[0],
+    );
+    let tenant_user = crate::utils::account::Account::new(
+        "user@tenantq.org",
+        "tenant q user secret",
+        &[],
+        "Tenant Q User",
+        tenant_account_ids[1],
+    );
+
+    // Quarantine messages for each account
+    for (account, subject) in [
+        (&john, "Invoice overdue"),
+        (&john, "Account verification"),
+        (&jane, "Lottery winner"),
+        (&jane, "Cheap watches"),
+        (&tenant_user, "Tenant offer"),
+    ] {
+        quarantine_message(test, account.name(), account.id(), subject).await;
+    }
+
+    // Quarantined messages should not be delivered
+    for account in [&john, &jane, &tenant_user] {
+        assert_eq!(
+            inbox_subjects(test, account.id()).await,
+            Vec::<String>::new()
+        );
+    }
+
+    // Each user should only see their own quarantined messages
+    let mut quarantine_ids = Vec::new();
+    for (account, expected_subjects) in [
+        (&john, vec!["Account verification", "Invoice overdue"]),
+        (&jane, vec!["Cheap watches", "Lottery winner"]),
+        (&tenant_user, vec!["Tenant offer"]),
+    ] {
+        let ids = account
+            .registry_query_ids(
+                ObjectType::QuarantinedMessage,
+                Vec::<(&str, &str)>::new(),
+                Vec::<&str>::new(),
+            )
+            .await;
+        assert_eq!(ids.len(), expected_subjects.len(), "{}", account.name());
+
+        let mut subjects = Vec::new();
+        for id in &ids {
+            let item = account.registry_get::<QuarantinedMessage>(*id).await;
+            assert_eq!(item.account_id, account.id());
+            assert_eq!(item.from, "spammer@example.com");
+            assert_eq!(item.reason, "Suspicious attachment");
+            assert!(item.size > 0);
+            assert!(!item.notified);
+            assert_eq!(item.blob_id.class.account_id(), account.id().document_id());
+            assert_eq!(
+                item.expires_at.timestamp() - item.received_at.timestamp(),
+                30 * 86400
+            );
+            subjects.push(item.subject);
+        }
+        subjects.sort_unstable();
+        assert_eq!(subjects, expected_subjects);
+        quarantine_ids.push(ids);
+    }
+    let john_ids = quarantine_ids[0].clone();
+    let jane_ids = quarantine_ids[1].clone();
+    let tenant_user_ids = quarantine_ids[2].clone();
+
+    // John should not be able to get, release or destroy Jane's quarantined messages
+    assert_eq!(
+        john.registry_get_many(ObjectType::QuarantinedMessage, [jane_ids[0]])
+            .await
+            .not_found()
+            .count(),
+        1
+    );
+    john.registry_update_object_expect_err(
+        ObjectType::QuarantinedMessage,
+        jane_ids[0],
+        json!({
+            Property::Status: QuarantineStatus::RequestRelease,
+        }),
+    )
+    .await
+    .assert_type(SetErrorType::NotFound);
+    john.registry_destroy_object_expect_err(ObjectType::QuarantinedMessage, jane_ids[0])
+        .await
+        .assert_type(SetErrorType::NotFound);
+
+    // Quarantined messages cannot be created
+    john.registry_create_object_expect_err(QuarantinedMessage {
+        subject: "Forged".to_string(),
+        account_id: john.id(),
+        ..Default::default()
+    })
+    .await
+    .assert_type(SetErrorType::Forbidden);
+
+    // Tenant administrators should not see messages quarantined for their users
+    assert_eq!(
+        tenant_admin
+            .registry_query_ids(
+                ObjectType::QuarantinedMessage,
+                Vec::<(&str, &str)>::new(),
+                Vec::<&str>::new(),
+            )
+            .await,
+        Vec::<Id>::new()
+    );
+    assert_eq!(
+        tenant_admin
+            .registry_get_many(
+                ObjectType::QuarantinedMessage,
+                [tenant_user_ids[0], john_ids[0]]
+            )
+            .await
+            .not_found()
+            .count(),
+        2
+    );
+
+    // The system administrator should see all quarantined messages
+    let mut all_ids = john_ids
+        .iter()
+        .chain(jane_ids.iter())
+        .chain(tenant_user_ids.iter())
+        .copied()
+        .collect::<Vec<_>>();
+    all_ids.sort_unstable();
+    let mut ids = admin
+        .registry_query_ids(
+            ObjectType::QuarantinedMessage,
+            Vec::<(&str, &str)>::new(),
+            Vec::<&str>::new(),
+        )
+        .await;
+    ids.sort_unstable();
+    assert_eq!(ids, all_ids);
+    let mut ids = admin
+        .registry_query_ids(
+            ObjectType::QuarantinedMessage,
+            [(Property::AccountId, jane.id().to_string())],
+            Vec::<&str>::new(),
+        )
+        .await;
+    ids.sort_unstable();
+    let mut expected_ids = jane_ids.clone();
+    expected_ids.sort_unstable();
+    assert_eq!(ids, expected_ids);
+
+    // John releases one message and deletes the other one
+    let (release_id, destroy_id) = if john
+        .registry_get::<QuarantinedMessage>(john_ids[0])
+        .await
+        .subject
+        == "Invoice overdue"
+    {
+        (john_ids[0], john_ids[1])
+    } else {
+        (john_ids[1], john_ids[0])
+    };
+    john.registry_update_object(
+        ObjectType::QuarantinedMessage,
+        release_id,
+        json!({
+            Property::Status: QuarantineStatus::RequestRelease,
+        }),
+    )
+    .await;
+    john.registry_destroy(ObjectType::QuarantinedMessage, [destroy_id])
+        .await
+        .assert_destroyed(&[destroy_id]);
+    test.wait_for_tasks().await;
+    assert_eq!(
+        inbox_subjects(test, john.id()).await,
+        vec!["Invoice overdue".to_string()]
+    );
+    assert_eq!(
+        john.registry_query_ids(
+            ObjectType::QuarantinedMessage,
+            Vec::<(&str, &str)>::new(),
+            Vec::<&str>::new(),
+        )
+        .await,
+        Vec::<Id>::new()
+    );
+
+    // Send quarantine digests
+    admin
+        .registry_create_object(Task::QuarantineDigest(TaskQuarantineDigest {
+            status: TaskStatus::now(),
+        }))
+        .await;
+    test.wait_for_tasks().await;
+    let digest = wait_for_message(test, jane.id(), "Quarantined messages").await;
+    assert!(
+        digest.contains("2 message(s) addressed to jane@example.org"),
+        "{digest}"
+    );
+    let mut release_links = Vec::new();
+    let mut subject = "";
+    for line in digest.lines() {
+        if let Some(value) = line.strip_prefix("Subject: ") {
+            subject = value.trim();
+        } else if let Some(value) = line.strip_prefix("Release: ") {
+            let path = &value[value.find("/quarantine/release?t=").unwrap()..];
+            release_links.push((subject.to_string(), path.trim().to_string()));
+        }
+    }
+    release_links.sort_unstable();
+    assert_eq!(
+        release_links
+            .iter()
+            .map(|(subject, _)| subject.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Cheap watches", "Lottery winner"],
+        "{digest}"
+    );
+    wait_for_message(test, tenant_user.id(), "Quarantined messages").await;
+    for id in &jane_ids {
+        assert!(jane.registry_get::<QuarantinedMessage>(*id).await.notified);
+    }
+
+    // Notified messages should not be included in subsequent digests
+    admin
+        .registry_create_object(Task::QuarantineDigest(TaskQuarantineDigest {
+            status: TaskStatus::now(),
+        }))
+        .await;
+    test.wait_for_tasks().await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(
+        inbox_subjects(test, jane.id()).await,
+        vec!["Quarantined messages".to_string()]
+    );
+
+    // Opening the release link should only display a confirmation form
+    let http = HttpRequest::new();
+    let (_, release_link) = &release_links[1];
+    let response = http.send_full(Method::GET, release_link, None, None).await;
+    assert_eq!(response.status.as_u16(), 200);
+    assert!(
+        response.body.contains("<form method=\"post\""),
+        "{}",
+        response.body
+    );
+    assert_eq!(
+        jane.registry_query_ids(
+            ObjectType::QuarantinedMessage,
+            Vec::<(&str, &str)>::new(),
+            Vec::<&str>::new(),
+        )
+        .await
+        .len(),
+        2
+    );
+
+    // Confirming the release should deliver the message to the inbox
+    let response = http.send_full(Method::POST, release_link, None, None).await;
+    assert_eq!(response.status.as_u16(), 200);
+    assert!(
+        response.body.contains("released to your inbox"),
+        "{}",
+        response.body
+    );
+    test.wait_for_tasks().await;
+    assert_eq!(
+        inbox_subjects(test, jane.id()).await,
+        vec![
+            "Lottery winner".to_string(),
+            "Quarantined messages".to_string()
+        ]
+    );
+    assert_eq!(
+        jane.registry_query_ids(
+            ObjectType::QuarantinedMessage,
+            Vec::<(&str, &str)>::new(),
+            Vec::<&str>::new(),
+        )
+        .await
+        .len(),
+        1
+    );
+
+    // Release links cannot be reused
+    let response = http.send_full(Method::POST, release_link, None, None).await;
+    assert_eq!(response.status.as_u16(), 404);
+
+    // Invalid release links should be rejected
+    let response = http
+        .send_full(
+            Method::POST,
+            "/quarantine/release?t=invalid_token",
+            None,
+            None,
+        )
+        .await;
+    assert_eq!(response.status.as_u16(), 400);
+
+    // Expired messages should be purged
+    admin
+        .registry_update_setting(
+            QuarantineSettings {
+                hold_for: 1000u64.into(),
+                ..Default::default()
+            },
+            &[Property::HoldFor],
+        )
+        .await;
+    admin.reload_settings().await;
+    quarantine_message(test, john.name(), john.id(), "Expiring soon").await;
+    let expired_ids = john
+        .registry_query_ids(
+            ObjectType::QuarantinedMessage,
+            Vec::<(&str, &str)>::new(),
+            Vec::<&str>::new(),
+        )
+        .await;
+    assert_eq!(expired_ids.len(), 1);
+    let item = john
+        .registry_get::<QuarantinedMessage>(expired_ids[0])
+        .await;
+    assert!(item.expires_at.timestamp() as u64 <= now() + 1);
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    admin
+        .registry_create_object(Task::StoreMaintenance(TaskStoreMaintenance {
+            maintenance_type: TaskStoreMaintenanceType::PurgeBlob,
+            shard_index: None,
+            status: TaskStatus::now(),
+        }))
+        .await;
+    test.wait_for_tasks().await;
+    assert_eq!(
+        john.registry_get_many(ObjectType::QuarantinedMessage, [expired_ids[0]])
+            .await
+            .not_found()
+            .count(),
+        1
+    );
+    assert_eq!(
+        jane.registry_query_ids(
+            ObjectType::QuarantinedMessage,
+            Vec::<(&str, &str)>::new(),
+            Vec::<&str>::new(),
+        )
+        .await
+        .len(),
+        1
+    );
+
+    // Restore settings
+    admin
+        .registry_update_setting(QuarantineSettings::default(), &[Property::HoldFor])
+        .await;
+    admin.reload_settings().await;
+
+    // Delete accounts, quarantined messages are removed with them
+    admin.destroy_account(john).await;
+    admin.destroy_account(jane).await;
+    for (object_type, id) in [
+        (ObjectType::Account, tenant_account_ids[1]),
+        (ObjectType::Account, tenant_account_ids[0]),
+        (ObjectType::Domain, domain_id),
+        (ObjectType::Tenant, tenant_id),
+    ] {
+        admin
+            .registry_destroy(object_type, [id])
+            .await
+            .assert_destroyed(&[id]);
+    }
+    test.wait_for_tasks().await;
+    assert_eq!(
+        admin
+            .registry_query_ids(
+                ObjectType::QuarantinedMessage,
+                Vec::<(&str, &str)>::new(),
+                Vec::<&str>::new(),
+            )
+            .await,
+        Vec::<Id>::new()
+    );
+
+    test.cleanup().await;
+}
+
+async fn quarantine_message(test: &TestServer, address: &str, account_id: Id, subject: &str) {
+    let message = QUARANTINED_MESSAGE
+        .replace("ADDRESS", address)
+        .replace("SUBJECT", subject);
+    let (message_blob, _) = test
+        .server
+        .put_temporary_blob(account_id.document_id(), message.as_bytes(), 60)
+        .await
+        .unwrap();
+    assert_eq!(
+        test.server
+            .deliver_message(IngestMessage {
+                sender_address: "spammer@example.com".to_string(),
+                sender_authenticated: false,
+                recipients: vec![IngestRecipient {
+                    address: address.to_string(),
+                    orcpt: None,
+                    is_spam: false,
+                    is_quarantined: true
+                }],
+                message_blob,
+                message_size: message.len() as u64,
+                session_id: 0,
+            })
+            .await
+            .status,
+        vec![LocalDeliveryStatus::Success]
+    );
+}
+
+async fn inbox_subjects(test: &TestServer, account_id: Id) -> Vec<String> {
+    let account_id = account_id.document_id();
+    let mut subjects = Vec::new();
+    for item in &test
+        .server
+        .get_cached_messages(account_id)
+        .await
+        .unwrap()
+        .emails
+        .items
+    {
+        let raw = test.fetch_email(account_id, item.document_id).await;
+        subjects.push(
+            MessageParser::new()
+                .parse(&raw)
+                .unwrap()
+                .subject()
+                .unwrap_or_default()
+                .to_string(),
+        );
+    }
+    subjects.sort_unstable();
+    subjects
+}
+
+async fn wait_for_message(test: &TestServer, account_id: Id, subject: &str) -> String {
+    let account_id_ = account_id.document_id();
+    for _ in 0..50 {
+        for item in &test
+            .server
+            .get_cached_messages(account_id_)
+            .await
+            .unwrap()
+            .emails
+            .items
+        {
+            let raw = test.fetch_email(account_id_, item.document_id).await;
+            let message = MessageParser::new().parse(&raw).unwrap();
+            if message.subject() == Some(subject) {
+                return message.body_text(0).unwrap().into_owned();
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Message {subject:?} was not delivered to account {account_id}");
+}
+
+const QUARANTINED_MESSAGE: &str = concat!(
+    "From: spammer@example.com\r\n",
+    "To: ADDRESS\r\n",
+    "Subject: SUBJECT\r\n",
+    "X-Quarantine: Suspicious attachment\r\n",
+    "\r\n",
+    "Please open the attached file.\r\n",
+);
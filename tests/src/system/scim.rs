@@ -0,0 +1,576 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    system::authentication::validate_password_with_ip,
+    utils::{jmap::JmapUtils, server::TestServer},
+};
+use http::scim::{SCHEMA_GROUP, SCHEMA_LIST_RESPONSE, SCHEMA_PATCH_OP, SCHEMA_USER};
+use hyper::{Method, StatusCode};
+use registry::{
+    schema::{
+        prelude::ObjectType,
+        structs::{
+            Account, ApiKey, CertificateManagement, Credential, DkimManagement, DnsManagement,
+            Domain, PasswordCredential, Tenant, UserAccount, UserRoles,
+        },
+    },
+    types::list::List,
+};
+use serde_json::{Value, json};
+
+pub async fn test(test: &mut TestServer) {
+    println!("Running SCIM provisioning tests...");
+    let admin_system = test.account("admin@example.org");
+
+    // Create a tenant with its own domain and administrator
+    let tenant_id = admin_system
+        .registry_create_object(Tenant {
+            name: "SCIM Tenant".to_string(),
+            ..Default::default()
+        })
+        .await;
+    let domain_id = admin_system
+        .registry_create_object(Domain {
+            name: "scim.org".to_string(),
+            member_tenant_id: tenant_id.into(),
+            certificate_management: CertificateManagement::Manual,
+            dns_management: DnsManagement::Manual,
+            dkim_management: DkimManagement::Manual,
+            ..Default::default()
+        })
+        .await;
+    let tenant_admin_id = admin_system
+        .registry_create_object(Account::User(UserAccount {
+            name: "admin".to_string(),
+            domain_id,
+            member_tenant_id: tenant_id.into(),
+            roles: UserRoles::Admin,
+            description: "SCIM Admin".to_string().into(),
+            credentials: List::from_iter([Credential::Password(PasswordCredential {
+                secret: "scim tenant admin secret".to_string(),
+                ..Default::default()
+            })]),
+            ..Default::default()
+        }))
+        .await;
+    let tenant_admin = crate::utils::account::Account::new(
+        "admin@scim.org",
+        "scim tenant admin secret",
+        &[],
+        "SCIM Admin",
+        tenant_admin_id,
+    );
+    let api_key = tenant_admin
+        .registry_create([ApiKey {
+            description: "Identity provider".to_string(),
+            ..Default::default()
+        }])
+        .await
+        .created(0)
+        .text_field("secret")
+        .to_string();
+    let scim = ScimClient::Bearer(api_key);
+
+    // Only tenant API keys are accepted
+    let system_key = admin_system
+        .registry_create([ApiKey {
+            description: "System key".to_string(),
+            ..Default::default()
+        }])
+        .await;
+    let system_key_id = system_key.created(0).object_id();
+    for client in [
+        ScimClient::None,
+        ScimClient::Basic(
+            "admin@scim.org".to_string(),
+            "scim tenant admin secret".to_string(),
+        ),
+        ScimClient::Bearer(system_key.created(0).text_field("secret").to_string()),
+    ] {
+        let (status, _) = client.send(Method::GET, "/Users", None).await;
+        assert!(
+            matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN),
+            "{status}"
+        );
+    }
+    admin_system
+        .registry_destroy(ObjectType::ApiKey, [system_key_id])
+        .await;
+
+    // Discovery endpoints
+    let (status, config) = scim.get("/ServiceProviderConfig").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(config["patch"]["supported"], true);
+    assert_eq!(config["bulk"]["supported"], false);
+    let (_, resource_types) = scim.get("/ResourceTypes").await;
+    assert_eq!(resource_types["totalResults"], 2);
+    let (status, schema) = scim.get(&format!("/Schemas/{SCHEMA_USER}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(schema["name"], "User");
+
+    // Provision users
+    let (status, jdoe) = scim
+        .post(
+            "/Users",
+            json!({
+                "schemas": [SCHEMA_USER],
+                "userName": "JDoe@scim.org",
+                "name": {
+                    "givenName": "John",
+                    "familyName": "Doe"
+                },
+                "emails": [
+                    {"value": "jdoe@scim.org", "type": "work", "primary": true},
+                    {"value": "john.doe@scim.org", "type": "other"}
+                ],
+                "password": "provisioned strong password 1",
+                "active": true
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{jdoe}");
+    assert_eq!(jdoe["userName"], "jdoe@scim.org");
+    assert_eq!(jdoe["displayName"], "John Doe");
+    assert_eq!(jdoe["active"], true);
+    assert_eq!(jdoe["meta"]["resourceType"], "User");
+    assert_eq!(emails(&jdoe), ["jdoe@scim.org", "john.doe@scim.org"]);
+    assert!(jdoe.get("password").is_none());
+    let jdoe_id = jdoe["id"].as_str().unwrap().to_string();
+    validate_password_with_ip(
+        "jdoe@scim.org",
+        "provisioned strong password 1",
+        "127.0.0.1",
+        true,
+    )
+    .await;
+
+    let mut user_ids = vec![jdoe_id.clone()];
+    for (user_name, display_name) in [("jane@scim.org", "Jane Smith"), ("bob", "Bob Jones")] {
+        let (status, user) = scim
+            .post(
+                "/Users",
+                json!({
+                    "schemas": [SCHEMA_USER],
+                    "userName": user_name,
+                    "displayName": display_name
+                }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{user}");
+        user_ids.push(user["id"].as_str().unwrap().to_string());
+    }
+
+    // Bare user names are placed in the provisioning account's domain
+    let (_, bob) = scim.get(&format!("/Users/{}", user_ids[2])).await;
+    assert_eq!(bob["userName"], "bob@scim.org");
+
+    // Duplicates and foreign domains are rejected
+    for (request, expected_status, expected_type) in [
+        (
+            json!({"userName": "jdoe@scim.org"}),
+            StatusCode::CONFLICT,
+            "uniqueness",
+        ),
+        (
+            json!({"userName": "jdoe@example.org"}),
+            StatusCode::BAD_REQUEST,
+            "invalidValue",
+        ),
+        (
+            json!({"userName": "other@scim.org", "emails": [{"value": "other@example.org"}]}),
+            StatusCode::BAD_REQUEST,
+            "invalidValue",
+        ),
+        (
+            json!({"displayName": "No user name"}),
+            StatusCode::BAD_REQUEST,
+            "invalidValue",
+        ),
+    ] {
+        let (status, response) = scim.post("/Users", request).await;
+        assert_eq!(status, expected_status, "{response}");
+        assert_eq!(response["scimType"], expected_type, "{response}");
+    }
+
+    // List, filter and paginate users
+    let (_, list) = scim.get("/Users").await;
+    assert_eq!(list["schemas"][0], SCHEMA_LIST_RESPONSE);
+    assert_eq!(list["totalResults"], 4, "{list}");
+    let (_, list) = scim.get("/Users?startIndex=2&count=2").await;
+    assert_eq!(list["totalResults"], 4);
+    assert_eq!(list["startIndex"], 2);
+    assert_eq!(list["itemsPerPage"], 2);
+    for (filter, expected) in [
+        (r#"userName eq "JANE@scim.org""#, vec!["jane@scim.org"]),
+        (
+            r#"emails[value eq "john.doe@scim.org"]"#,
+            vec!["jdoe@scim.org"],
+        ),
+        (
+            r#"displayName co "j" and not (userName sw "admin")"#,
+            vec!["jdoe@scim.org", "jane@scim.org", "bob@scim.org"],
+        ),
+        (r#"userName eq "nobody@scim.org""#, vec![]),
+    ] {
+        let (status, list) = scim.get(&format!("/Users?filter={}", encode(filter))).await;
+        assert_eq!(status, StatusCode::OK, "{list}");
+        let mut user_names = list["Resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["userName"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        let mut expected = expected;
+        user_names.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(user_names, expected, "{filter}");
+        assert_eq!(list["totalResults"], expected.len());
+    }
+    let (status, response) = scim
+        .get(&format!("/Users?filter={}", encode("userName xx \"a\"")))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response["scimType"], "invalidFilter");
+    let (_, jdoe) = scim
+        .get(&format!("/Users/{jdoe_id}?attributes=userName"))
+        .await;
+    assert_eq!(jdoe["userName"], "jdoe@scim.org");
+    assert!(jdoe.get("emails").is_none());
+
+    // Patch a user the way Entra ID does
+    let (status, jdoe) = scim
+        .patch(
+            &format!("/Users/{jdoe_id}"),
+            json!({
+                "schemas": [SCHEMA_PATCH_OP],
+                "Operations": [
+                    {
+                        "op": "Replace",
+                        "value": {
+                            "displayName": "Johnny Doe",
+                            "password": "provisioned strong password 2"
+                        }
+                    },
+                    {
+                        "op": "Add",
+                        "path": "emails",
+                        "value": [{"value": "jd@scim.org", "type": "other"}]
+                    },
+                    {
+                        "op": "Remove",
+                        "path": "emails[value eq \"john.doe@scim.org\"]"
+                    }
+                ]
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{jdoe}");
+    assert_eq!(jdoe["displayName"], "Johnny Doe");
+    assert_eq!(emails(&jdoe), ["jdoe@scim.org", "jd@scim.org"]);
+    validate_password_with_ip(
+        "jdoe@scim.org",
+        "provisioned strong password 2",
+        "127.0.0.1",
+        true,
+    )
+    .await;
+    let (status, response) = scim
+        .patch(
+            &format!("/Users/{jdoe_id}"),
+            json!({
+                "schemas": [SCHEMA_PATCH_OP],
+                "Operations": [{"op": "remove", "path": "emails[value eq \"x@scim.org\"]"}]
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response["scimType"], "noTarget");
+
+    // Replace a user the way Okta does
+    let (status, jane) = scim
+        .put(
+            &format!("/Users/{}", user_ids[1]),
+            json!({
+                "schemas": [SCHEMA_USER],
+                "id": user_ids[1],
+                "userName": "jane.smith@scim.org",
+                "name": {"formatted": "Jane A. Smith"},
+                "emails": [{"value": "jane.smith@scim.org", "primary": true}],
+                "active": true
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{jane}");
+    assert_eq!(jane["userName"], "jane.smith@scim.org");
+    assert_eq!(jane["displayName"], "Jane A. Smith");
+    assert_eq!(emails(&jane), ["jane.smith@scim.org"]);
+
+    // Provision groups
+    let (status, group) = scim
+        .post(
+            "/Groups",
+            json!({
+                "schemas": [SCHEMA_GROUP],
+                "displayName": "Sales Team",
+                "members": [{"value": jdoe_id}]
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{group}");
+    assert_eq!(group["displayName"], "Sales Team");
+    assert_eq!(members(&group), [jdoe_id.as_str()]);
+    let group_id = group["id"].as_str().unwrap().to_string();
+    let (_, jdoe) = scim.get(&format!("/Users/{jdoe_id}")).await;
+    assert_eq!(jdoe["groups"][0]["value"], group_id);
+
+    let (status, response) = scim
+        .post(
+            "/Groups",
+            json!({
+                "displayName": "Invalid Members",
+                "members": [{"value": group_id}]
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response["scimType"], "invalidValue");
+
+    // Update group membership
+    let (status, group) = scim
+        .patch(
+            &format!("/Groups/{group_id}"),
+            json!({
+                "schemas": [SCHEMA_PATCH_OP],
+                "Operations": [
+                    {"op": "add", "path": "members", "value": [{"value": user_ids[1]}, {"value": user_ids[2]}]},
+                    {"op": "remove", "path": "members", "value": [{"value": jdoe_id}]},
+                    {"op": "replace", "path": "displayName", "value": "Sales"}
+                ]
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{group}");
+    assert_eq!(group["displayName"], "Sales");
+    let mut expected = vec![user_ids[1].as_str(), user_ids[2].as_str()];
+    let mut group_members = members(&group);
+    expected.sort_unstable();
+    group_members.sort_unstable();
+    assert_eq!(group_members, expected);
+    let (_, jdoe) = scim.get(&format!("/Users/{jdoe_id}")).await;
+    assert_eq!(jdoe["groups"], json!([]));
+
+    let (_, group) = scim
+        .patch(
+            &format!("/Groups/{group_id}"),
+            json!({
+                "schemas": [SCHEMA_PATCH_OP],
+                "Operations": [
+                    {"op": "remove", "path": format!("members[value eq \"{}\"]", user_ids[1])}
+                ]
+            }),
+        )
+        .await;
+    assert_eq!(members(&group), [user_ids[2].as_str()]);
+    let (_, list) = scim
+        .get(&format!(
+            "/Groups?filter={}",
+            encode("displayName eq \"sales\"")
+        ))
+        .await;
+    assert_eq!(list["totalResults"], 1);
+    assert_eq!(list["Resources"][0]["id"], group_id);
+
+    // Deactivating a user suspends logins but keeps the account and its data
+    for (active, expected) in [("False", false), ("True", true)] {
+        let (status, jdoe) = scim
+            .patch(
+                &format!("/Users/{jdoe_id}"),
+                json!({
+                    "schemas": [SCHEMA_PATCH_OP],
+                    "Operations": [{"op": "Replace", "path": "active", "value": active}]
+                }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "{jdoe}");
+        assert_eq!(jdoe["active"], expected);
+        let (status, jdoe) = scim.get(&format!("/Users/{jdoe_id}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(jdoe["active"], expected);
+        assert_eq!(emails(&jdoe), ["jdoe@scim.org", "jd@scim.org"]);
+        validate_password_with_ip(
+            "jdoe@scim.org",
+            "provisioned strong password 2",
+            "127.0.0.1",
+            expected,
+        )
+        .await;
+    }
+    let (status, bob) = scim
+        .put(
+            &format!("/Users/{}", user_ids[2]),
+            json!({
+                "schemas": [SCHEMA_USER],
+                "userName": "bob@scim.org",
+                "displayName": "Bob Jones",
+                "active": false
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{bob}");
+    assert_eq!(bob["active"], false);
+    let (_, group) = scim.get(&format!("/Groups/{group_id}")).await;
+    assert_eq!(members(&group), [user_ids[2].as_str()]);
+
+    // Users can be provisioned in an inactive state
+    let (status, carol) = scim
+        .post(
+            "/Users",
+            json!({
+                "schemas": [SCHEMA_USER],
+                "userName": "carol@scim.org",
+                "password": "provisioned strong password 3",
+                "active": false
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{carol}");
+    assert_eq!(carol["active"], false);
+    validate_password_with_ip(
+        "carol@scim.org",
+        "provisioned strong password 3",
+        "127.0.0.1",
+        false,
+    )
+    .await;
+    let carol_id = carol["id"].as_str().unwrap().to_string();
+
+    // Delete resources
+    let (status, response) = scim.delete(&format!("/Users/{}", tenant_admin_id)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response["scimType"], "mutability");
+    for path in [
+        format!("/Groups/{group_id}"),
+        format!("/Users/{}", user_ids[1]),
+        format!("/Users/{}", user_ids[2]),
+        format!("/Users/{carol_id}"),
+        format!("/Users/{jdoe_id}"),
+    ] {
+        let (status, _) = scim.delete(&path).await;
+        assert_eq!(status, StatusCode::NO_CONTENT, "{path}");
+        let (status, response) = scim.get(&path).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{path}");
+        assert_eq!(response["status"], "404");
+    }
+    validate_password_with_ip(
+        "jdoe@scim.org",
+        "provisioned strong password 2",
+        "127.0.0.1",
+        false,
+    )
+    .await;
+
+    // Resources from other tenants are not visible
+    let (status, _) = scim
+        .get(&format!("/Users/{}", admin_system.id_string()))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Remove the tenant
+    for (object_type, id) in [
+        (ObjectType::Account, tenant_admin_id),
+        (ObjectType::Domain, domain_id),
+        (ObjectType::Tenant, tenant_id),
+    ] {
+        assert_eq!(
+            admin_system
+                .registry_destroy(object_type, [id])
+                .await
+                .destroyed_ids()
+                .count(),
+            1
+        );
+    }
+
+    test.cleanup().await;
+}
+
+enum ScimClient {
+    None,
+    Basic(String, String),
+    Bearer(String),
+}
+
+impl ScimClient {
+    async fn get(&self, path: &str) -> (StatusCode, Value) {
+        self.send(Method::GET, path, None).await
+    }
+
+    async fn post(&self, path: &str, body: Value) -> (StatusCode, Value) {
+        self.send(Method::POST, path, Some(body)).await
+    }
+
+    async fn put(&self, path: &str, body: Value) -> (StatusCode, Value) {
+        self.send(Method::PUT, path, Some(body)).await
+    }
+
+    async fn patch(&self, path: &str, body: Value) -> (StatusCode, Value) {
+        self.send(Method::PATCH, path, Some(body)).await
+    }
+
+    async fn delete(&self, path: &str) -> (StatusCode, Value) {
+        self.send(Method::DELETE, path, None).await
+    }
+
+    async fn send(&self, method: Method, path: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let mut request = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap()
+            .request(method, format!("https://127.0.0.1:8899/scim/v2{path}"));
+        match self {
+            ScimClient::None => {}
+            ScimClient::Basic(username, password) => {
+                request = request.basic_auth(username, Some(password));
+            }
+            ScimClient::Bearer(token) => {
+                request = request.bearer_auth(token);
+            }
+        }
+        if let Some(body) = body {
+            request = request
+                .header(hyper::header::CONTENT_TYPE, "application/scim+json")
+                .body(body.to_string());
+        }
+
+        let response = request.send().await.unwrap();
+        let status = response.status();
+        let body = response.text().await.unwrap();
+        (status, serde_json::from_str(&body).unwrap_or(Value::Null))
+    }
+}
+
+fn emails(user: &Value) -> Vec<&str> {
+    user["emails"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|email| email["value"].as_str().unwrap())
+        .collect()
+}
+
+fn members(group: &Value) -> Vec<&str> {
+    group["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["value"].as_str().unwrap())
+        .collect()
+}
+
+fn encode(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
@@ -530,7 +530,8 @@ pub async fn test(test: &mut TestServer) {
                 recipients: vec![IngestRecipient {
                     address: "user@tenantx.org".to_string(),
                     orcpt: None,
-                    is_spam: false
+                    is_spam: false,
+                    is_quarantined: false
                 }],
                 message_blob: message_blob.clone(),
                 message_size: TEST_MESSAGE.len() as u64,
@@ -567,7 +568,8 @@ pub async fn test(test: &mut TestServer) {
                 recipients: vec![IngestRecipient {
                     address: "user@tenantx.org".to_string(),
                     orcpt: None,
-                    is_spam: false
+                    is_spam: false,
+                    is_quarantined: false
                 }],
                 message_blob,
                 message_size: TEST_MESSAGE.len() as u64,
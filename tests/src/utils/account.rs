@@ -14,7 +14,7 @@ use registry::{
         structs::{
             self, CertificateManagement, Credential, CustomRoles, DkimManagement, DnsManagement,
             Domain, EmailAlias, GroupAccount, PasswordCredential, Permissions, PermissionsList,
-            Roles, UserAccount,
+            ResourceAccount, Roles, UserAccount,
         },
     },
     types::{list::List, map::Map},
@@ -229,6 +229,34 @@ impl Account {
         Account::new(name, "", aliases, description, account_id)
     }
 
+    pub async fn create_resource_account(
+        &self,
+        name: &'static str,
+        description: &'static str,
+        is_location: bool,
+        max_duration: Option<Duration>,
+    ) -> Account {
+        let (account_name, domain) = name.rsplit_once('@').expect("Invalid email address");
+        let domain_id = self.find_or_create_domain(domain).await;
+        let resource = ResourceAccount {
+            name: account_name.to_string(),
+            domain_id,
+            description: description.to_string().into(),
+            max_duration: max_duration.map(registry::types::duration::Duration),
+            ..Default::default()
+        };
+
+        let account_id = self
+            .registry_create_object(if is_location {
+                structs::Account::Location(resource)
+            } else {
+                structs::Account::Resource(resource)
+            })
+            .await;
+
+        Account::new(name, "", &[], description, account_id)
+    }
+
     pub async fn create_domain(&self, name: &'static str) -> Id {
         self.registry_create_object(Domain {
             is_enabled: true,
@@ -394,11 +394,14 @@ impl Account {
             "urn:ietf:params:jmap:core",
             "urn:ietf:params:jmap:mail",
             "urn:ietf:params:jmap:submission",
+            "urn:ietf:params:jmap:mdn",
+            "urn:ietf:params:jmap:smimeverify",
             "urn:ietf:params:jmap:vacationresponse",
             "urn:ietf:params:jmap:contacts",
             "urn:ietf:params:jmap:contacts:parse",
             "urn:ietf:params:jmap:calendars",
             "urn:ietf:params:jmap:calendars:parse",
+            "urn:ietf:params:jmap:tasks",
             "urn:ietf:params:jmap:websocket",
             "urn:ietf:params:jmap:sieve",
             "urn:ietf:params:jmap:blob",
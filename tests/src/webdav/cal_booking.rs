@@ -0,0 +1,161 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{server::TestServer, webdav::DummyWebDavClient};
+use dav_proto::schema::property::{DavProperty, WebDavProperty};
+use groupware::DavResourceName;
+use hyper::StatusCode;
+use mail_parser::DateTime;
+use registry::schema::prelude::ObjectType;
+use store::write::now;
+
+pub async fn test(test: &TestServer) {
+    println!("Running calendar resource booking tests...");
+    let admin = test.account("admin");
+    let bill = test.account("bill@example.com");
+    let jane = test.account("jane@example.com");
+    let john = test.account("john@example.com");
+    let bill_client = bill.webdav_client();
+    let jane_client = jane.webdav_client();
+    let john_client = john.webdav_client();
+
+    // Create a meeting room that can be booked for up to two hours
+    let room = admin
+        .create_resource_account(
+            "boardroom@example.com",
+            "Board Room",
+            true,
+            Some(std::time::Duration::from_secs(2 * 60 * 60)),
+        )
+        .await;
+
+    // Rooms should be discoverable by calendar user type
+    let response = bill_client
+        .request(
+            "REPORT",
+            DavResourceName::Principal.collection_path(),
+            ROOM_PROPERTY_SEARCH_QUERY,
+        )
+        .await
+        .with_status(StatusCode::MULTI_STATUS)
+        .into_propfind_response(None);
+    let room_href = format!(
+        "{}/boardroom%40example.com/",
+        DavResourceName::Principal.base_path()
+    );
+    response.with_hrefs([room_href.as_str()]);
+    response
+        .properties(&room_href)
+        .get(DavProperty::WebDav(WebDavProperty::DisplayName))
+        .with_values(["Board Room"]);
+
+    // Booking a free room should be accepted
+    book_room(&bill_client, "booking-1", 60 * 60, 2 * 60 * 60).await;
+    expect_reply(test, &bill_client, "ACCEPTED").await;
+
+    // Overlapping bookings should be declined
+    book_room(&jane_client, "booking-2", 90 * 60, 3 * 60 * 60).await;
+    expect_reply(test, &jane_client, "DECLINED").await;
+
+    // Bookings longer than the maximum duration should be declined
+    book_room(&john_client, "booking-3", 4 * 60 * 60, 7 * 60 * 60).await;
+    expect_reply(test, &john_client, "DECLINED").await;
+
+    // Back-to-back bookings do not conflict
+    book_room(&john_client, "booking-4", 2 * 60 * 60, 3 * 60 * 60).await;
+    expect_reply(test, &john_client, "ACCEPTED").await;
+
+    // Clean up
+    admin
+        .registry_destroy(ObjectType::Account, [room.id()])
+        .await
+        .assert_destroyed(&[room.id()]);
+    for client in [bill_client, jane_client, john_client] {
+        client.delete_default_containers().await;
+    }
+    for account in [bill, jane, john] {
+        test.destroy_all_mailboxes(account).await;
+    }
+
+    test.assert_is_empty().await;
+}
+
+async fn book_room(client: &DummyWebDavClient, uid: &str, start: i64, end: i64) {
+    let ical = TEST_BOOKING
+        .replace("$UID", uid)
+        .replace("$ORGANIZER", client.name)
+        .replace(
+            "$START",
+            &DateTime::from_timestamp(now() as i64 + start)
+                .to_rfc3339()
+                .replace(['-', ':'], ""),
+        )
+        .replace(
+            "$END",
+            &DateTime::from_timestamp(now() as i64 + end)
+                .to_rfc3339()
+                .replace(['-', ':'], ""),
+        );
+    client
+        .request_with_headers(
+            "PUT",
+            &format!(
+                "{}/{}/default/{uid}.ics",
+                DavResourceName::Cal.base_path(),
+                client.name.replace('@', "%40")
+            ),
+            [("content-type", "text/calendar; charset=utf-8")],
+            &ical,
+        )
+        .await
+        .with_status(StatusCode::CREATED);
+}
+
+async fn expect_reply(test: &TestServer, client: &DummyWebDavClient, partstat: &str) {
+    // The invitation and the automatic reply are both delivered over iMIP
+    for _ in 0..2 {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        test.wait_for_tasks().await;
+    }
+    let itips = client.fetch_and_remove_itips().await;
+    assert_eq!(itips.len(), 1, "unexpected itips: {itips:?}");
+    assert!(
+        itips[0].contains("METHOD:REPLY")
+            && itips[0].contains(&format!("PARTSTAT={partstat}:mailto:boardroom@example.com")),
+        "failed for itip: {}",
+        itips[0]
+    );
+}
+
+const ROOM_PROPERTY_SEARCH_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+   <D:principal-property-search xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+     <D:property-search>
+       <D:prop>
+         <C:calendar-user-type/>
+       </D:prop>
+       <D:match>ROOM</D:match>
+     </D:property-search>
+     <D:prop>
+       <D:displayname/>
+     </D:prop>
+</D:principal-property-search>"#;
+
+const TEST_BOOKING: &str = r#"BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Example Corp.//CalDAV Client//EN
+BEGIN:VEVENT
+UID:$UID
+SEQUENCE:0
+DTSTART:$START
+DTEND:$END
+DTSTAMP:20090602T170000Z
+TRANSP:OPAQUE
+SUMMARY:Board meeting
+ORGANIZER:mailto:$ORGANIZER
+ATTENDEE;CUTYPE=ROOM:mailto:boardroom@example.com
+END:VEVENT
+END:VCALENDAR
+"#;
@@ -537,7 +537,7 @@ pub async fn test(test: &TestServer) {
 }
 
 impl DummyWebDavClient {
-    async fn fetch_and_remove_itips(&self) -> Vec<String> {
+    pub(crate) async fn fetch_and_remove_itips(&self) -> Vec<String> {
         let inbox_href = format!("/dav/itip/{}/inbox/", self.name.replace('@', "%40"));
         let response = self
             .propfind_with_headers(&inbox_href, ALL_DAV_PROPERTIES, [("depth", "1")])